@@ -0,0 +1,49 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Ascii {
+    foreground: vec4<f32>,
+    background: vec4<f32>,
+    cell_size: f32,
+};
+@group(1) @binding(0)
+var atlas: texture_2d<f32>;
+@group(1) @binding(1)
+var atlas_sampler: sampler;
+@group(1) @binding(2)
+var<uniform> ascii: Ascii;
+
+// The atlas is a single row of glyphs, ordered by how much of the cell they cover.
+const GLYPH_COUNT: f32 = 10.0;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(t));
+    let cell_size = max(ascii.cell_size, 1.0);
+    let pixel = in.uv * size;
+
+    // Sample the image once per cell, at its center.
+    let cell = floor(pixel / cell_size);
+    let center = (cell + 0.5) * cell_size / size;
+    let color = textureSampleLevel(t, ts, center, 0.0).rgb;
+
+    let luminance = clamp(dot(color, vec3<f32>(0.2126, 0.7152, 0.0722)), 0.0, 1.0);
+    let glyph = min(floor(luminance * GLYPH_COUNT), GLYPH_COUNT - 1.0);
+
+    // Where in the glyph this pixel is.
+    let within = fract(pixel / cell_size);
+    let atlas_uv = vec2<f32>((glyph + within.x) / GLYPH_COUNT, within.y);
+    let ink = textureSampleLevel(atlas, atlas_sampler, atlas_uv, 0.0).r;
+
+    let foreground = mix(color, ascii.foreground.rgb, ascii.foreground.a);
+    let output = mix(ascii.background.rgb, foreground, ink);
+
+    return vec4<f32>(output, 1.0);
+}
@@ -0,0 +1,32 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct AutoExposure {
+    min_ev: f32,
+    max_ev: f32,
+    low_percentile: f32,
+    high_percentile: f32,
+    // In EV per second.
+    adapt_to_bright: f32,
+    adapt_to_dark: f32,
+    // In EV.
+    compensation: f32,
+};
+@group(1) @binding(0)
+var<uniform> auto_exposure: AutoExposure;
+// The exposure the view has adapted to, in EV, see `auto_exposure_compute.wgsl`.
+// Zero where compute shaders are not supported.
+@group(1) @binding(1)
+var exposure: texture_2d<f32>;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+    let ev = textureLoad(exposure, vec2<i32>(0, 0), 0).r + auto_exposure.compensation;
+
+    return vec4<f32>(sample.rgb * exp2(ev), sample.a);
+}
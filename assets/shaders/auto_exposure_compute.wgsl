@@ -0,0 +1,142 @@
+// Meters the image into a histogram of its luminance, and adapts the exposure of the view to it.
+#import bevy_render::globals Globals
+
+struct AutoExposure {
+    min_ev: f32,
+    max_ev: f32,
+    low_percentile: f32,
+    high_percentile: f32,
+    // In EV per second.
+    adapt_to_bright: f32,
+    adapt_to_dark: f32,
+    // In EV. Applied when drawing, not metered.
+    compensation: f32,
+};
+
+struct ExposureState {
+    // The exposure the view has adapted to, in EV.
+    exposure: f32,
+    // Zero until the first image is metered, which the exposure is set to right away.
+    metered: u32,
+};
+
+// As in `auto_exposure.rs`.
+const BINS: u32 = 64u;
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> auto_exposure: AutoExposure;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var<storage, read_write> histogram: array<atomic<u32>, 64>;
+@group(0) @binding(4)
+var<storage, read_write> state: ExposureState;
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// Bin 0 is for black pixels, which have no EV.
+// The other bins split the range from `min_ev` to `max_ev` evenly, and pixels outside of it
+// go into the first or last of them.
+fn bin_of(color: vec3<f32>) -> u32 {
+    let l = luminance(color);
+    if l < 0.00001 {
+        return 0u;
+    }
+
+    let range = auto_exposure.max_ev - auto_exposure.min_ev;
+    let t = saturate((log2(l) - auto_exposure.min_ev) / range);
+    return 1u + u32(t * (f32(BINS - 1u) - 0.001));
+}
+
+// The EV at the middle of a bin, other than bin 0.
+fn ev_of(bin: u32) -> f32 {
+    let t = (f32(bin) - 0.5) / f32(BINS - 1u);
+    return mix(auto_exposure.min_ev, auto_exposure.max_ev, t);
+}
+
+var<workgroup> local_histogram: array<atomic<u32>, 64>;
+
+// Each workgroup counts its tile of the image into shared memory first,
+// so only a few of its invocations add to the histogram in the buffer.
+@compute @workgroup_size(16, 16, 1)
+fn histogram_pass(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+) {
+    if index < BINS {
+        atomicStore(&local_histogram[index], 0u);
+    }
+    workgroupBarrier();
+
+    let size = textureDimensions(source);
+    if all(id.xy < size) {
+        let color = textureLoad(source, vec2<i32>(id.xy), 0).rgb;
+        atomicAdd(&local_histogram[bin_of(color)], 1u);
+    }
+    workgroupBarrier();
+
+    if index < BINS {
+        let count = atomicLoad(&local_histogram[index]);
+        if count > 0u {
+            atomicAdd(&histogram[index], count);
+        }
+    }
+}
+
+var<workgroup> counts: array<u32, 64>;
+
+@compute @workgroup_size(64, 1, 1)
+fn adapt_pass(@builtin(local_invocation_index) index: u32) {
+    // Taken out of the histogram, which starts over for the next image.
+    counts[index] = atomicExchange(&histogram[index], 0u);
+    workgroupBarrier();
+
+    if index != 0u {
+        return;
+    }
+
+    var total = 0u;
+    for (var i = 1u; i < BINS; i++) {
+        total += counts[i];
+    }
+
+    // Only the pixels between the percentiles are averaged,
+    // so a few very dark or bright ones such as the sun don't throw the exposure off.
+    let low = f32(total) * auto_exposure.low_percentile;
+    let high = f32(total) * auto_exposure.high_percentile;
+
+    var below = 0.0;
+    var sum = 0.0;
+    var weight = 0.0;
+    for (var i = 1u; i < BINS; i++) {
+        let count = f32(counts[i]);
+        let averaged = max(min(below + count, high) - max(below, low), 0.0);
+        sum += averaged * ev_of(i);
+        weight += averaged;
+        below += count;
+    }
+
+    // A black image has nothing to meter, so the exposure stays as it is.
+    if weight <= 0.0 {
+        return;
+    }
+
+    // Exposes the average to middle grey.
+    let goal = log2(0.18) - sum / weight;
+
+    if state.metered == 0u {
+        state.exposure = goal;
+        state.metered = 1u;
+        return;
+    }
+
+    // Brighter images lower the exposure.
+    let delta = goal - state.exposure;
+    let speed = select(auto_exposure.adapt_to_dark, auto_exposure.adapt_to_bright, delta < 0.0);
+    let step = speed * globals.delta_time;
+    state.exposure += clamp(delta, -step, step);
+}
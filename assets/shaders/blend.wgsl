@@ -0,0 +1,77 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// The output of the effect.
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+#ifdef BACKGROUND
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+#endif
+
+struct Blend {
+    factor: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> blend: Blend;
+// The image from before the effect.
+@group(1) @binding(1)
+var previous: texture_2d<f32>;
+@group(1) @binding(2)
+var previous_sampler: sampler;
+// The effect mask of the camera, white if it has none.
+@group(1) @binding(3)
+var effect_mask: texture_2d<f32>;
+
+#ifdef BACKGROUND
+// The depth at the given UV, see the fog shader.
+fn load_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth));
+    return textureLoad(depth, vec2<i32>(min(uv * size, size - 1.0)), 0);
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let effect = textureSample(source, source_sampler, in.uv);
+    let image = textureSample(previous, previous_sampler, in.uv);
+
+#ifdef BACKGROUND
+    // Bevy uses reverse z with an infinite far plane,
+    // so anything in front of the background has a depth above zero.
+    if load_depth(in.uv) > 0.0 {
+        return image;
+    }
+#endif
+
+#ifdef NORMAL
+    let blended = effect.rgb;
+#endif
+#ifdef ADDITIVE
+    let blended = image.rgb + effect.rgb;
+#endif
+#ifdef MULTIPLY
+    let blended = image.rgb * effect.rgb;
+#endif
+#ifdef SCREEN
+    let blended = 1.0 - (1.0 - image.rgb) * (1.0 - effect.rgb);
+#endif
+#ifdef OVERLAY
+    let multiplied = 2.0 * image.rgb * effect.rgb;
+    let screened = 1.0 - 2.0 * (1.0 - image.rgb) * (1.0 - effect.rgb);
+    let blended = select(screened, multiplied, image.rgb < vec3<f32>(0.5));
+#endif
+
+    var factor = saturate(blend.factor);
+#ifdef EFFECT_MASK
+    var coverage = textureSampleLevel(effect_mask, previous_sampler, in.uv, 0.0)[#{EFFECT_MASK_CHANNEL}];
+#ifdef OUTSIDE_EFFECT_MASK
+    coverage = 1.0 - coverage;
+#endif
+    factor *= coverage;
+#endif
+
+    return mix(image, vec4<f32>(blended, effect.a), factor);
+}
@@ -0,0 +1,57 @@
+#import bevy_vfx_bag::compute source, source_sampler, output, load_tile, tile_sample, pixel_uv, APRON
+
+struct Blur {
+    amount: f32,
+    kernel_radius: f32
+};
+@group(1) @binding(0)
+var<uniform> blur: Blur;
+
+// The same kernel as `blur.wgsl`, gathered from the tile.
+@compute @workgroup_size(16, 16, 1)
+fn gather(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(workgroup_id) workgroup: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    let size = vec2<f32>(textureDimensions(source));
+    let radius = blur.kernel_radius * size;
+
+    // Kernels reaching past the tile would read the image for all but the center sample,
+    // so reading the tile first would only cost time. The same for the whole workgroup.
+    let tiled = max(radius.x, radius.y) + 1.0 < f32(APRON);
+    if tiled {
+        load_tile(workgroup, local_index);
+    }
+
+    if any(id.xy >= vec2<u32>(size)) {
+        return;
+    }
+
+    let uv = pixel_uv(id.xy);
+    let position = uv * size;
+
+    var sum = vec3<f32>(0.0);
+    var original = vec3<f32>(0.0);
+    for (var y = -1; y <= 1; y++) {
+        for (var x = -1; x <= 1; x++) {
+            let offset = vec2<f32>(f32(x), f32(y)) * radius;
+
+            var sample: vec3<f32>;
+            if tiled {
+                sample = tile_sample(position + offset).rgb;
+            } else {
+                sample = textureSampleLevel(source, source_sampler, uv + offset / size, 0.0).rgb;
+            }
+
+            sum += sample;
+            if x == 0 && y == 0 {
+                original = sample;
+            }
+        }
+    }
+
+    let output_color = mix(original, sum / 9.0, blur.amount);
+
+    textureStore(output, vec2<i32>(id.xy), vec4<f32>(output_color, 1.0));
+}
@@ -0,0 +1,34 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct Bulge {
+    center: vec2<f32>,
+    // As a fraction of the height of the image.
+    radius: f32,
+    strength: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> bulge: Bulge;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+
+    // Measured in heights of the image, so the area stays round.
+    let aspect = vec2<f32>(resolution.x / resolution.y, 1.0);
+    let distance = length((in.uv - bulge.center) * aspect) / max(bulge.radius, 0.0001);
+
+    // Strongest in the middle, fading out smoothly towards the edge of the area.
+    let weight = pow(max(1.0 - distance, 0.0), 2.0);
+
+    // Bulging samples closer to the middle, which enlarges it. Pinching samples further out.
+    let scale = 1.0 - clamp(bulge.strength, -1.0, 1.0) * weight;
+    let uv = bulge.center + (in.uv - bulge.center) * scale;
+
+    return textureSample(source, source_sampler, uv);
+}
@@ -1,12 +1,16 @@
+#ifdef EFFECT_MATERIAL
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+#else
 #import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
 #import bevy_render::globals Globals
 
 @group(0) @binding(0)
-var t: texture_2d<f32>;
+var source: texture_2d<f32>;
 @group(0) @binding(1)
-var ts: sampler;
+var source_sampler: sampler;
 @group(0) @binding(2)
 var<uniform> globals: Globals;
+#endif
 
 struct ChromaticAberration {
     dir_r: vec2<f32>,
@@ -19,17 +23,40 @@ struct ChromaticAberration {
     magnitude_b: f32,
 };
 
+#ifdef EFFECT_MATERIAL
+@group(1) @binding(2)
+#else
 @group(1) @binding(0)
+#endif
 var<uniform> ca: ChromaticAberration;
 
 
-@fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+// The effect at `uv`, shared by post processing and materials.
+fn effect(uv: vec2<f32>) -> vec4<f32> {
     let out = vec3<f32>(
-        textureSample(t, ts, in.uv + (ca.dir_r * ca.magnitude_r)).r,
-        textureSample(t, ts, in.uv + (ca.dir_g * ca.magnitude_g)).g,
-        textureSample(t, ts, in.uv + (ca.dir_b * ca.magnitude_b)).b,
+        textureSample(source, source_sampler, uv + (ca.dir_r * ca.magnitude_r)).r,
+        textureSample(source, source_sampler, uv + (ca.dir_g * ca.magnitude_g)).g,
+        textureSample(source, source_sampler, uv + (ca.dir_b * ca.magnitude_b)).b,
     );
 
-    return vec4<f32>(out, 1.0);
-}
\ No newline at end of file
+#ifdef EFFECT_MATERIAL
+    // Sprites keep their shape.
+    let alpha = textureSample(source, source_sampler, uv).a;
+#else
+    let alpha = 1.0;
+#endif
+
+    return vec4<f32>(out, alpha);
+}
+
+#ifdef EFFECT_MATERIAL
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return finish(in.uv, effect(in.uv));
+}
+#else
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return effect(in.uv);
+}
+#endif
@@ -0,0 +1,44 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct ColorAdjust {
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    hue_shift: f32,
+    gamma: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> color_adjust: ColorAdjust;
+
+// Rotates the color around the gray axis, which turns the hue and keeps the brightness.
+fn shift_hue(color: vec3<f32>, degrees: f32) -> vec3<f32> {
+    let angle = radians(degrees);
+    let axis = vec3<f32>(0.57735);
+    let cos_angle = cos(angle);
+
+    return color * cos_angle + cross(axis, color) * sin(angle) + axis * dot(axis, color) * (1.0 - cos_angle);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let original = textureSample(source, source_sampler, in.uv);
+    var color = original.rgb;
+
+    color += color_adjust.brightness;
+    color = (color - 0.5) * color_adjust.contrast + 0.5;
+
+    let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color = mix(vec3<f32>(luminance), color, color_adjust.saturation);
+
+    color = shift_hue(color, color_adjust.hue_shift);
+
+    color = pow(max(color, vec3<f32>(0.0)), vec3<f32>(1.0 / max(color_adjust.gamma, 0.0001)));
+
+    return vec4<f32>(color, original.a);
+}
@@ -0,0 +1,66 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct ColorBlindness {
+    severity: f32,
+};
+@group(1) @binding(0)
+var<uniform> color_blindness: ColorBlindness;
+
+// How colors look with the cone missing, in linear RGB (Machado, Oliveira and Fernandes, 2009).
+// The rows are the columns here, so `color * deficiency` multiplies by the rows.
+#ifdef PROTANOPIA
+const deficiency = mat3x3<f32>(
+    vec3<f32>(0.152286, 1.052583, -0.204868),
+    vec3<f32>(0.114503, 0.786281, 0.099216),
+    vec3<f32>(-0.003882, -0.048116, 1.051998),
+);
+#endif
+#ifdef DEUTERANOPIA
+const deficiency = mat3x3<f32>(
+    vec3<f32>(0.367322, 0.860646, -0.227968),
+    vec3<f32>(0.280085, 0.672501, 0.047413),
+    vec3<f32>(-0.011820, 0.042940, 0.968881),
+);
+#endif
+#ifdef TRITANOPIA
+const deficiency = mat3x3<f32>(
+    vec3<f32>(1.255528, -0.076749, -0.178779),
+    vec3<f32>(-0.078411, 0.930809, 0.147602),
+    vec3<f32>(0.004733, 0.691367, 0.303900),
+);
+#endif
+
+fn simulate(color: vec3<f32>) -> vec3<f32> {
+    return mix(color, color * deficiency, color_blindness.severity);
+}
+
+#ifdef DALTONIZE
+// Moves the difference which can't be seen into the channels which can (Fidaner, Lin and Ozguven).
+fn daltonize(color: vec3<f32>) -> vec3<f32> {
+    let error = color - simulate(color);
+
+#ifdef TRITANOPIA
+    let shift = vec3<f32>(error.r + 0.7 * error.b, error.g + 0.7 * error.b, 0.0);
+#else
+    let shift = vec3<f32>(0.0, 0.7 * error.r + error.g, 0.7 * error.r + error.b);
+#endif
+
+    return max(color + shift, vec3<f32>(0.0));
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(source, source_sampler, in.uv);
+
+#ifdef DALTONIZE
+    return vec4<f32>(daltonize(color.rgb), color.a);
+#else
+    return vec4<f32>(max(simulate(color.rgb), vec3<f32>(0.0)), color.a);
+#endif
+}
@@ -0,0 +1,175 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+// The eye the camera renders, see `StereoEye`.
+struct Eye {
+    index: u32,
+    // -1.0 for the left eye, 1.0 for the right eye, 0.0 without an eye.
+    side: f32,
+};
+@group(0) @binding(5)
+var<uniform> eye: Eye;
+
+// The uniforms of each effect, see the shaders of the effects.
+struct Combined {
+    flip: vec2<f32>,
+    block_size: f32,
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    hue_shift: f32,
+    gamma: f32,
+    white_balance: vec3<f32>,
+    mask_strength: f32,
+    mask_fade: f32,
+    mask_nasal_offset: f32,
+    mask_color: vec4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> combined: Combined;
+
+// See the white balance shader.
+const RGB_TO_LMS: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(0.390405, 0.549941, 0.008926),
+    vec3<f32>(0.070842, 0.963172, 0.001358),
+    vec3<f32>(0.023108, 0.128021, 0.936245),
+);
+const LMS_TO_RGB: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(2.858470, -1.628790, -0.024891),
+    vec3<f32>(-0.210182, 1.158200, 0.000324),
+    vec3<f32>(-0.041812, -0.118169, 1.068670),
+);
+
+fn pixelate(uv: vec2<f32>) -> vec2<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let width_height_over_block_size = resolution / max(1.0, combined.block_size);
+
+    var pixelated = uv + 0.5;
+    pixelated *= width_height_over_block_size;
+    pixelated = floor(pixelated);
+    pixelated /= width_height_over_block_size;
+    return pixelated - 0.5;
+}
+
+// Where the stage reads the image it is given. The stages match `Stage`.
+fn sampling_stage(stage: u32, uv: vec2<f32>) -> vec2<f32> {
+    switch stage {
+        case 0u: {
+            return abs(combined.flip - uv);
+        }
+        case 1u: {
+            return pixelate(uv);
+        }
+        default: {
+            return uv;
+        }
+    }
+}
+
+fn shift_hue(color: vec3<f32>, degrees: f32) -> vec3<f32> {
+    let angle = radians(degrees);
+    let axis = vec3<f32>(0.57735);
+    let cos_angle = cos(angle);
+
+    return color * cos_angle + cross(axis, color) * sin(angle) + axis * dot(axis, color) * (1.0 - cos_angle);
+}
+
+fn color_adjust(original: vec4<f32>) -> vec4<f32> {
+    var color = original.rgb;
+
+    color += combined.brightness;
+    color = (color - 0.5) * combined.contrast + 0.5;
+
+    let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color = mix(vec3<f32>(luminance), color, combined.saturation);
+
+    color = shift_hue(color, combined.hue_shift);
+
+    color = pow(max(color, vec3<f32>(0.0)), vec3<f32>(1.0 / max(combined.gamma, 0.0001)));
+
+    return vec4<f32>(color, original.a);
+}
+
+fn white_balance(original: vec4<f32>) -> vec4<f32> {
+    let lms = (original.rgb * RGB_TO_LMS) * combined.white_balance;
+    return vec4<f32>(max(lms * LMS_TO_RGB, vec3<f32>(0.0)), original.a);
+}
+
+fn mask(original: vec4<f32>, uv: vec2<f32>) -> vec4<f32> {
+    var result = 1.0;
+
+#ifdef SQUARE
+    let uv_big = saturate(uv * combined.mask_strength);
+    let uv_big_inv = saturate((1. - uv) * combined.mask_strength);
+    let square = uv_big * uv_big_inv;
+    result = square.r * square.g;
+#endif
+#ifdef CRT
+    let square = uv * (1. - uv);
+    var norm = square.r * square.g;
+    norm *= norm;
+    norm *= combined.mask_strength;
+    result = saturate(norm);
+#endif
+#ifdef VIGNETTE
+    var uv_centered = uv * 2. - 1.;
+    uv_centered.x += eye.side * combined.mask_nasal_offset * 2.;
+    uv_centered *= combined.mask_strength;
+
+    var dist = saturate(length(uv_centered));
+    dist = pow(dist, 1.5);
+    dist = 1. - dist;
+    dist += 0.05;
+    result = saturate(dist);
+#endif
+
+    return vec4<f32>(mix(combined.mask_color.rgb, original.rgb, saturate(result + combined.mask_fade)), 1.0);
+}
+
+// The color the stage turns the given color into, where `uv` is the position on the screen.
+// The stages match `Stage`.
+fn color_stage(stage: u32, color: vec4<f32>, uv: vec2<f32>) -> vec4<f32> {
+    switch stage {
+        case 2u: {
+            return color_adjust(color);
+        }
+        case 3u: {
+            return white_balance(color);
+        }
+        case 4u: {
+            return mask(color, uv);
+        }
+        default: {
+            return color;
+        }
+    }
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // The stages are picked by shader defs, so the switches are resolved when compiling.
+    var uv = in.uv;
+#ifdef SAMPLING_STAGE_0
+    uv = sampling_stage(#{SAMPLING_STAGE_0}u, uv);
+#endif
+#ifdef SAMPLING_STAGE_1
+    uv = sampling_stage(#{SAMPLING_STAGE_1}u, uv);
+#endif
+
+    var color = textureSample(source, source_sampler, uv);
+#ifdef COLOR_STAGE_0
+    color = color_stage(#{COLOR_STAGE_0}u, color, in.uv);
+#endif
+#ifdef COLOR_STAGE_1
+    color = color_stage(#{COLOR_STAGE_1}u, color, in.uv);
+#endif
+#ifdef COLOR_STAGE_2
+    color = color_stage(#{COLOR_STAGE_2}u, color, in.uv);
+#endif
+
+    return color;
+}
@@ -0,0 +1,47 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// The layers composited so far.
+@group(0) @binding(0)
+var below: texture_2d<f32>;
+@group(0) @binding(1)
+var layer_sampler: sampler;
+// The layer drawn over them.
+@group(0) @binding(2)
+var layer: texture_2d<f32>;
+
+struct Composite {
+    opacity: f32,
+};
+
+@group(0) @binding(3)
+var<uniform> composite: Composite;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let image = textureSample(below, layer_sampler, in.uv);
+    let top = textureSample(layer, layer_sampler, in.uv);
+
+    // The blend modes of `blend.wgsl`.
+#ifdef NORMAL
+    let blended = top.rgb;
+#endif
+#ifdef ADDITIVE
+    let blended = image.rgb + top.rgb;
+#endif
+#ifdef MULTIPLY
+    let blended = image.rgb * top.rgb;
+#endif
+#ifdef SCREEN
+    let blended = 1.0 - (1.0 - image.rgb) * (1.0 - top.rgb);
+#endif
+#ifdef OVERLAY
+    let multiplied = 2.0 * image.rgb * top.rgb;
+    let screened = 1.0 - 2.0 * (1.0 - image.rgb) * (1.0 - top.rgb);
+    let blended = select(screened, multiplied, image.rgb < vec3<f32>(0.5));
+#endif
+
+    // Where the layer rendered nothing, its transparent clear color leaves the layers below.
+    let factor = saturate(composite.opacity) * top.a;
+
+    return mix(image, vec4<f32>(blended, 1.0), factor);
+}
@@ -0,0 +1,83 @@
+// The shader interface of the compute variants of effects, see `ComputePath`.
+//
+// Each workgroup draws 16x16 pixels. It first reads its tile of the image, reaching `APRON`
+// pixels past its own on each side, into shared memory. Its pixels then gather from the tile,
+// and only read the image for samples further out than that.
+#define_import_path bevy_vfx_bag::compute
+
+// The image so far, and a linear sampler for it.
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+// The depth prepass, if the camera has a `DepthPrepass` and MSAA is off.
+@group(0) @binding(2)
+var depth_prepass: texture_depth_2d;
+
+// Where the output goes, before it is drawn into the camera.
+@group(0) @binding(3)
+var output: texture_storage_2d<rgba16float, write>;
+
+// As in `@workgroup_size`, which needs a literal.
+const WORKGROUP_SIZE: i32 = 16;
+const APRON: i32 = 14;
+const TILE_SIZE: i32 = 44;
+const TILE_TEXELS: i32 = 1936;
+
+// Packed as half floats, so a larger apron fits the 16 KiB of shared memory WebGPU guarantees.
+var<workgroup> tile: array<vec2<u32>, TILE_TEXELS>;
+
+// The pixel of the image at the top left corner of the tile.
+var<private> tile_origin: vec2<i32>;
+
+// The largest half float, as larger HDR values would become infinite.
+const HALF_MAX: f32 = 65504.0;
+
+// Reads the tile of the workgroup. All invocations must call this, before any returns.
+fn load_tile(workgroup: vec3<u32>, local_index: u32) {
+    let size = vec2<i32>(textureDimensions(source));
+    tile_origin = vec2<i32>(workgroup.xy) * WORKGROUP_SIZE - APRON;
+
+    for (var i = i32(local_index); i < TILE_TEXELS; i += WORKGROUP_SIZE * WORKGROUP_SIZE) {
+        // Clamped to the edge, as the sampler does.
+        let pixel = clamp(tile_origin + vec2<i32>(i % TILE_SIZE, i / TILE_SIZE), vec2<i32>(0), size - 1);
+        let color = clamp(textureLoad(source, pixel, 0), vec4<f32>(-HALF_MAX), vec4<f32>(HALF_MAX));
+        tile[i] = vec2<u32>(pack2x16float(color.rg), pack2x16float(color.ba));
+    }
+
+    workgroupBarrier();
+}
+
+fn tile_texel(local: vec2<i32>) -> vec4<f32> {
+    let packed = tile[local.y * TILE_SIZE + local.x];
+    return vec4<f32>(unpack2x16float(packed.x), unpack2x16float(packed.y));
+}
+
+// Samples the image at a position in pixels, as `textureSampleLevel` with the linear sampler would.
+// Positions too far out for the tile read the image instead.
+fn tile_sample(position: vec2<f32>) -> vec4<f32> {
+    let texel = position - 0.5;
+    let local = vec2<i32>(floor(texel)) - tile_origin;
+
+    if any(local < vec2<i32>(0)) || any(local + 1 >= vec2<i32>(TILE_SIZE)) {
+        let size = vec2<f32>(textureDimensions(source));
+        return textureSampleLevel(source, source_sampler, position / size, 0.0);
+    }
+
+    let weight = fract(texel);
+
+    // Samples on pixel centers, e.g. of kernels a whole number of pixels wide, need a single read.
+    if all(weight == vec2<f32>(0.0)) {
+        return tile_texel(local);
+    }
+
+    let top = mix(tile_texel(local), tile_texel(local + vec2<i32>(1, 0)), weight.x);
+    let bottom = mix(tile_texel(local + vec2<i32>(0, 1)), tile_texel(local + vec2<i32>(1, 1)), weight.x);
+    return mix(top, bottom, weight.y);
+}
+
+// The UV of the center of a pixel, as fragment shaders get it.
+fn pixel_uv(pixel: vec2<u32>) -> vec2<f32> {
+    return (vec2<f32>(pixel) + 0.5) / vec2<f32>(textureDimensions(source));
+}
@@ -0,0 +1,87 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+@group(0) @binding(4)
+var normals: texture_2d<f32>;
+
+struct DayForNight {
+    // Linear, alpha scales the rim.
+    moonlight: vec4<f32>,
+    // Towards the moon, in world space.
+    moon_direction: vec3<f32>,
+    // In stops.
+    exposure: f32,
+    // In world space.
+    view_forward: vec3<f32>,
+    highlight_crush: f32,
+    contrast: f32,
+    desaturation: f32,
+    blue_shift: f32,
+    // Zero without a depth and normal prepass.
+    rim: f32,
+    rim_power: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> day_for_night: DayForNight;
+
+const LUMA: vec3<f32> = vec3<f32>(0.2126, 0.7152, 0.0722);
+
+// Middle gray, before the image is darkened.
+const MIDDLE_GRAY: f32 = 0.18;
+
+// Holds down the highlights, along a curve which leaves the shadows nearly as they are.
+fn crush(luminance: f32) -> f32 {
+    return luminance / (1.0 + day_for_night.highlight_crush * luminance);
+}
+
+// A rim of moonlight where surfaces facing the moon are seen edge on.
+fn rim(uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(depth));
+    let pixel = clamp(vec2<i32>(uv * vec2<f32>(size)), vec2<i32>(0), size - 1);
+
+    // Bevy uses reverse z, so nothing was drawn where the depth is zero, e.g. the sky.
+    if textureLoad(depth, pixel, 0) <= 0.0 {
+        return 0.0;
+    }
+
+    let normal = normalize(textureLoad(normals, pixel, 0).xyz * 2.0 - 1.0);
+    let edge = pow(1.0 - saturate(dot(normal, -day_for_night.view_forward)), day_for_night.rim_power);
+    let lit = saturate(dot(normal, day_for_night.moon_direction));
+
+    return edge * lit * day_for_night.rim;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    // Scaling keeps the ratios between light and dark, where subtracting would flatten the shadows.
+    let scale = exp2(day_for_night.exposure);
+    var color = max(sample.rgb, vec3<f32>(0.0)) * scale;
+
+    // The contrast is taken around the middle of the darkened image, and applied to the luminance
+    // alone so it doesn't change the colors.
+    let luminance = max(dot(color, LUMA), 0.0001);
+    let middle = crush(MIDDLE_GRAY * scale);
+    let graded = middle * pow(crush(luminance) / middle, day_for_night.contrast);
+    color *= graded / luminance;
+
+    let gray = vec3<f32>(dot(color, LUMA));
+    color = mix(color, gray, day_for_night.desaturation);
+
+    // Towards the color of the moonlight, at the same brightness.
+    let moonlight = day_for_night.moonlight.rgb / max(dot(day_for_night.moonlight.rgb, LUMA), 0.0001);
+    color = mix(color, dot(color, LUMA) * moonlight, day_for_night.blue_shift);
+
+    if day_for_night.rim > 0.0 {
+        color += day_for_night.moonlight.rgb * day_for_night.moonlight.a * rim(in.uv);
+    }
+
+    return vec4<f32>(color, sample.a);
+}
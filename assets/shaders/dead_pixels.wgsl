@@ -0,0 +1,62 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+const MAX_DEAD_PIXELS: u32 = 64u;
+const MAX_SCRATCHES: u32 = 8u;
+
+struct DeadPixels {
+    // In UV coordinates, in xy.
+    positions: array<vec4<f32>, MAX_DEAD_PIXELS>,
+    colors: array<vec4<f32>, MAX_DEAD_PIXELS>,
+    // From xy to zw, in UV coordinates.
+    scratches: array<vec4<f32>, MAX_SCRATCHES>,
+    pixel_count: u32,
+    scratch_count: u32,
+    // In pixels.
+    size: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> dead_pixels: DeadPixels;
+
+// The distance from p to the segment from a to b.
+fn segment_distance(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+    let ab = b - a;
+    let t = saturate(dot(p - a, ab) / max(dot(ab, ab), 0.0001));
+    return length(p - (a + ab * t));
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSample(source, source_sampler, in.uv);
+    let resolution = vec2<f32>(textureDimensions(source));
+
+    // Everything is measured in whole pixels of the display, so nothing swims or blurs.
+    let pixel = floor(in.uv * resolution);
+    let size = max(floor(dead_pixels.size), 1.0);
+
+    for (var i = 0u; i < min(dead_pixels.scratch_count, MAX_SCRATCHES); i++) {
+        let scratch = dead_pixels.scratches[i];
+        let a = floor(scratch.xy * resolution);
+        let b = floor(scratch.zw * resolution);
+
+        // A faint, thin line, a little lighter than the image.
+        let line = 1.0 - saturate(segment_distance(pixel, a, b) - 0.5);
+        color = vec4<f32>(mix(color.rgb, vec3<f32>(0.8), line * 0.35), color.a);
+    }
+
+    for (var i = 0u; i < min(dead_pixels.pixel_count, MAX_DEAD_PIXELS); i++) {
+        let start = floor(dead_pixels.positions[i].xy * resolution);
+        let offset = pixel - start;
+
+        if all(offset >= vec2<f32>(0.0)) && all(offset < vec2<f32>(size)) {
+            color = vec4<f32>(dead_pixels.colors[i].rgb, color.a);
+        }
+    }
+
+    return color;
+}
@@ -0,0 +1,60 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Deband {
+    // In steps of 8 bit color.
+    strength: f32,
+};
+@group(1) @binding(0)
+var<uniform> deband: Deband;
+
+// Random in [0, 1), different for each pixel and frame.
+fn random(position: vec2<f32>, seed: f32) -> f32 {
+    let p = position + fract(globals.time * vec2<f32>(12.9898, 78.233)) * 1000.0 + seed;
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Noise in [-1, 1] with a triangular distribution, the sum of two uniform distributions.
+// Unlike uniform noise, its error doesn't depend on the color, so gradients don't pulse.
+fn triangular(position: vec2<f32>, seed: f32) -> f32 {
+    return random(position, seed) + random(position, seed + 0.5) - 1.0;
+}
+
+fn to_srgb(color: vec3<f32>) -> vec3<f32> {
+    return select(
+        1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055,
+        color * 12.92,
+        color <= vec3<f32>(0.0031308),
+    );
+}
+
+fn to_linear(color: vec3<f32>) -> vec3<f32> {
+    return select(
+        pow((color + 0.055) / 1.055, vec3<f32>(2.4)),
+        color / 12.92,
+        color <= vec3<f32>(0.04045),
+    );
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(source, source_sampler, in.uv);
+
+    // The image is quantized to 8 bits after sRGB encoding, so the noise goes in there,
+    // each channel with noise of its own.
+    let noise = vec3<f32>(
+        triangular(in.position.xy, 0.0),
+        triangular(in.position.xy, 1.0),
+        triangular(in.position.xy, 2.0),
+    );
+    let encoded = to_srgb(max(color.rgb, vec3<f32>(0.0))) + noise * deband.strength / 255.0;
+
+    return vec4<f32>(to_linear(max(encoded, vec3<f32>(0.0))), color.a);
+}
@@ -0,0 +1,103 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct DepthOfField {
+    focal_distance: f32,
+    aperture: f32,
+    max_blur_radius: f32,
+    sample_count: u32,
+    inverse_projection: mat4x4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> dof: DepthOfField;
+
+// Read by UV rather than by fragment position, so the passes also work at a lower resolution.
+fn raw_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(depth));
+    let texel = vec2<i32>(floor(uv * vec2<f32>(size)));
+    return textureLoad(depth, clamp(texel, vec2<i32>(0), size - 1), 0);
+}
+
+#ifdef CIRCLE_OF_CONFUSION
+// The distance from the camera to what is seen at the given fragment.
+fn view_distance(uv: vec2<f32>) -> f32 {
+    let d = raw_depth(uv);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return 3.40282e+38;
+    }
+
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let view = dof.inverse_projection * ndc;
+
+    return length(view.xyz / view.w);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(t, ts, in.uv);
+    let distance = view_distance(in.uv);
+
+    // How far away from the focal plane we are, relative to the distance.
+    // Goes towards 1.0 far away, and grows quickly in front of the focal plane.
+    let defocus = abs(distance - dof.focal_distance) / max(distance, 0.0001);
+
+    // Stored normalized, so the gather pass can scale it by the max radius.
+    let coc = saturate(defocus * dof.aperture);
+
+    return vec4<f32>(sample.rgb, coc);
+}
+#endif
+
+#ifdef GATHER
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(t));
+    let count = max(dof.sample_count, 1u);
+
+    let center = textureSample(t, ts, in.uv);
+    let center_radius = center.a * dof.max_blur_radius;
+    let center_depth = raw_depth(in.uv);
+
+    var color = center.rgb;
+    var total = 1.0;
+
+    for (var i = 0u; i < count; i += 1u) {
+        // Sample positions spiral outwards, evenly covering the disc.
+        let radius = dof.max_blur_radius * sqrt((f32(i) + 0.5) / f32(count));
+        let angle = f32(i) * GOLDEN_ANGLE;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius;
+
+        let sample = textureSampleLevel(t, ts, in.uv + offset * texel, 0.0);
+        var sample_radius = sample.a * dof.max_blur_radius;
+
+        // Things behind the center pixel should not bleed over it
+        // more than the center pixel is blurred itself.
+        // Reverse z: a smaller depth is further away.
+        if raw_depth(in.uv + offset * texel) < center_depth {
+            sample_radius = min(sample_radius, center_radius * 2.0);
+        }
+
+        // A sample contributes if its blur reaches this pixel.
+        let weight = smoothstep(radius - 0.5, radius + 0.5, sample_radius);
+
+        color += sample.rgb * weight;
+        total += weight;
+    }
+
+    return vec4<f32>(color / total, 1.0);
+}
+#endif
@@ -0,0 +1,72 @@
+#import bevy_vfx_bag::compute source, depth_prepass, output, load_tile, tile_sample, pixel_uv
+
+struct DepthOfField {
+    focal_distance: f32,
+    aperture: f32,
+    max_blur_radius: f32,
+    sample_count: u32,
+    inverse_projection: mat4x4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> dof: DepthOfField;
+
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+// Read by UV, as in `depth-of-field.wgsl`.
+fn raw_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(depth_prepass));
+    let texel = vec2<i32>(floor(uv * vec2<f32>(size)));
+    return textureLoad(depth_prepass, clamp(texel, vec2<i32>(0), size - 1), 0);
+}
+
+// The gather pass of `depth-of-field.wgsl`, with the colors and circles of confusion
+// gathered from the tile. The circle of confusion pass is cheap, so it stays a fragment shader.
+@compute @workgroup_size(16, 16, 1)
+fn gather(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(workgroup_id) workgroup: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    load_tile(workgroup, local_index);
+
+    let size = textureDimensions(source);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let count = max(dof.sample_count, 1u);
+    let uv = pixel_uv(id.xy);
+    let position = uv * vec2<f32>(size);
+
+    let center = tile_sample(position);
+    let center_radius = center.a * dof.max_blur_radius;
+    let center_depth = raw_depth(uv);
+
+    var color = center.rgb;
+    var total = 1.0;
+
+    for (var i = 0u; i < count; i += 1u) {
+        // Sample positions spiral outwards, evenly covering the disc.
+        let radius = dof.max_blur_radius * sqrt((f32(i) + 0.5) / f32(count));
+        let angle = f32(i) * GOLDEN_ANGLE;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius;
+
+        let sample = tile_sample(position + offset);
+        var sample_radius = sample.a * dof.max_blur_radius;
+
+        // Things behind the center pixel should not bleed over it
+        // more than the center pixel is blurred itself.
+        // Reverse z: a smaller depth is further away.
+        if raw_depth((position + offset) / vec2<f32>(size)) < center_depth {
+            sample_radius = min(sample_radius, center_radius * 2.0);
+        }
+
+        // A sample contributes if its blur reaches this pixel.
+        let weight = smoothstep(radius - 0.5, radius + 0.5, sample_radius);
+
+        color += sample.rgb * weight;
+        total += weight;
+    }
+
+    textureStore(output, vec2<i32>(id.xy), vec4<f32>(color / total, 1.0));
+}
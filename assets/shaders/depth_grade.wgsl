@@ -0,0 +1,85 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct Zone {
+    tint: vec3<f32>,
+    saturation: f32,
+    contrast: f32,
+};
+
+struct DepthGrade {
+    near: Zone,
+    mid: Zone,
+    far: Zone,
+    near_distance: f32,
+    far_distance: f32,
+    transition: f32,
+    inverse_projection: mat4x4<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> depth_grade: DepthGrade;
+
+// The depth at the given UV, see the fog shader.
+fn load_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth));
+    return textureLoad(depth, vec2<i32>(min(uv * size, size - 1.0)), 0);
+}
+
+// The distance from the camera to what is seen at the given fragment, see the fog shader.
+fn view_distance(uv: vec2<f32>) -> f32 {
+    let d = load_depth(uv);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return 3.40282e+38;
+    }
+
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let view = depth_grade.inverse_projection * ndc;
+
+    return length(view.xyz / view.w);
+}
+
+// The same grading as the color adjust shader, plus a tint.
+fn grade(color: vec3<f32>, zone: Zone) -> vec3<f32> {
+    var graded = color * zone.tint;
+    graded = (graded - 0.5) * zone.contrast + 0.5;
+
+    let luminance = dot(graded, vec3<f32>(0.2126, 0.7152, 0.0722));
+    return max(mix(vec3<f32>(luminance), graded, zone.saturation), vec3<f32>(0.0));
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+    let distance = view_distance(in.uv);
+
+    let half_transition = depth_grade.transition * 0.5;
+    let to_mid = smoothstep(
+        depth_grade.near_distance - half_transition,
+        depth_grade.near_distance + half_transition,
+        distance
+    );
+    let to_far = smoothstep(
+        depth_grade.far_distance - half_transition,
+        depth_grade.far_distance + half_transition,
+        distance
+    );
+
+    // Each zone is graded from the original colors, and the results are blended,
+    // so the transitions don't grade twice.
+    let near = grade(sample.rgb, depth_grade.near);
+    let mid = grade(sample.rgb, depth_grade.mid);
+    let far = grade(sample.rgb, depth_grade.far);
+    let color = mix(mix(near, mid, to_mid), far, to_far);
+
+    return vec4<f32>(color, sample.a);
+}
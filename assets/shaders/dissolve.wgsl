@@ -0,0 +1,55 @@
+// Only drawn as a material, see `EffectMaterial2d`.
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, finish
+
+struct Dissolve {
+    // The color of the burning edge.
+    edge_color: vec4<f32>,
+    // How much is dissolved, between 0.0 and 1.0.
+    threshold: f32,
+    // In units of the noise, which is between 0.0 and 1.0.
+    edge_width: f32,
+    // Noise cells across the image.
+    noise_scale: f32,
+};
+@group(1) @binding(2)
+var<uniform> dissolve: Dissolve;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Smooth 2D value noise between 0.0 and 1.0.
+fn value_noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+
+    let bottom = mix(hash(i), hash(i + vec2<f32>(1.0, 0.0)), u.x);
+    let top = mix(hash(i + vec2<f32>(0.0, 1.0)), hash(i + vec2<f32>(1.0, 1.0)), u.x);
+    return mix(bottom, top, u.y);
+}
+
+// Two octaves, so the burning edge is ragged rather than blobby.
+fn noise(uv: vec2<f32>) -> f32 {
+    let p = uv * dissolve.noise_scale;
+    return value_noise(p) * 0.65 + value_noise(p * 2.7 + 17.0) * 0.35;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let image = textureSample(source, source_sampler, in.uv);
+    let edge_width = max(dissolve.edge_width, 0.0001);
+
+    // At 0.0 even the edge is below all of the noise, at 1.0 all of the noise is gone.
+    let cut = mix(-edge_width, 1.0, saturate(dissolve.threshold));
+    let n = noise(in.uv);
+
+    // Strongest right at the cut, fading out over the width of the edge.
+    let edge = 1.0 - saturate((n - cut) / edge_width);
+    let color = mix(image.rgb, dissolve.edge_color.rgb, edge * dissolve.edge_color.a);
+
+    // Without branching, since `finish` samples the image, which needs uniform control flow.
+    let alpha = select(image.a, 0.0, n < cut);
+
+    return finish(in.uv, vec4<f32>(color, alpha));
+}
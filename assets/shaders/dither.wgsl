@@ -0,0 +1,55 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Dither {
+    levels: u32,
+};
+@group(1) @binding(0)
+var<uniform> dither: Dither;
+@group(1) @binding(1)
+var blue_noise: texture_2d<f32>;
+
+#ifdef BAYER
+// The threshold of a pixel in a Bayer matrix of size 2^BAYER_BITS.
+// Each bit of the position picks a quadrant in the 2x2 matrix [[0, 2], [3, 1]],
+// with the lowest bits of the position mattering most.
+fn threshold(pixel: vec2<u32>) -> f32 {
+    var value = 0u;
+    for (var i = 0u; i < #{BAYER_BITS}u; i++) {
+        let x = (pixel.x >> i) & 1u;
+        let y = (pixel.y >> i) & 1u;
+        value = (value << 2u) | ((x ^ y) << 1u) | y;
+    }
+
+    let size = f32(1u << (2u * #{BAYER_BITS}u));
+    return (f32(value) + 0.5) / size;
+}
+#endif
+
+#ifdef BLUE_NOISE
+fn threshold(pixel: vec2<u32>) -> f32 {
+    let size = vec2<u32>(textureDimensions(blue_noise));
+    return textureLoad(blue_noise, vec2<i32>(pixel % size), 0).r;
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(t, ts, in.uv).rgb;
+    let pixel = vec2<u32>(in.position.xy);
+
+    // Quantize in a perceptual space, so the levels look evenly spaced.
+    let perceptual = pow(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)), vec3<f32>(1.0 / 2.2));
+
+    let steps = f32(max(dither.levels, 2u) - 1u);
+    let quantized = floor(perceptual * steps + threshold(pixel)) / steps;
+
+    return vec4<f32>(pow(quantized, vec3<f32>(2.2)), 1.0);
+}
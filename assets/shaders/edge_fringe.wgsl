@@ -0,0 +1,37 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct EdgeFringe {
+    // In UV space, in the corners.
+    strength: f32,
+    // From 0.0 in the middle to 1.0 in the corners.
+    start: f32,
+    falloff: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> fringe: EdgeFringe;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let centered = in.uv - 0.5;
+
+    // 0.0 in the middle, 1.0 in the corners.
+    let distance = length(centered) * sqrt(2.0);
+    let edge = saturate((distance - fringe.start) / max(1.0 - fringe.start, 0.0001));
+    let amount = pow(edge, max(fringe.falloff, 0.0001)) * fringe.strength;
+
+    // Like a lens, red is magnified slightly more than blue, so the fringes point outwards.
+    let direction = centered / max(length(centered), 0.0001);
+    let offset = direction * amount;
+
+    let r = textureSample(source, source_sampler, in.uv - offset).r;
+    let g = textureSample(source, source_sampler, in.uv);
+    let b = textureSample(source, source_sampler, in.uv + offset).b;
+
+    return vec4<f32>(r, g.g, b, g.a);
+}
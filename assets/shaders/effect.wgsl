@@ -0,0 +1,36 @@
+// The shader interface of post processing effects, see `PostProcessingEffect`.
+//
+// Custom effects import the bindings they need from here instead of declaring them,
+// e.g. `#import bevy_vfx_bag::effect source, source_sampler, globals`.
+//
+// The bindings only change along with `SHADER_INTERFACE_VERSION`.
+#define_import_path bevy_vfx_bag::effect
+
+#import bevy_render::globals Globals
+
+const VERSION: u32 = 1u;
+
+// The image so far, and a linear sampler for it.
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+// The time of the `VfxTime` clock.
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+// The prepass textures, if the camera has a `DepthPrepass` or `NormalPrepass` and MSAA is off.
+@group(0) @binding(3)
+var depth_prepass: texture_depth_2d;
+@group(0) @binding(4)
+var normal_prepass: texture_2d<f32>;
+
+// The eye the camera renders, see `StereoEye`.
+struct Eye {
+    index: u32,
+    // -1.0 for the left eye, 1.0 for the right eye, 0.0 without an eye.
+    side: f32,
+};
+@group(0) @binding(5)
+var<uniform> eye: Eye;
@@ -0,0 +1,27 @@
+// The shader of the custom-effect example, see `PostProcessingEffect`.
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_vfx_bag::effect source, source_sampler, globals
+
+struct Sepia {
+    tone: vec4<f32>,
+    strength: f32,
+    grain: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> sepia: Sepia;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(source, source_sampler, in.uv);
+
+    let luma = dot(color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let noise = (hash(in.position.xy + fract(globals.time) * 100.0) - 0.5) * sepia.grain;
+    let toned = (luma + noise) * sepia.tone.rgb;
+
+    return vec4<f32>(mix(color.rgb, toned, sepia.strength), color.a);
+}
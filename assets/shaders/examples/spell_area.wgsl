@@ -0,0 +1,21 @@
+// The shader of the effect-mask example, which draws a circle into the effect mask.
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+struct SpellArea {
+    // In physical pixels.
+    center: vec2<f32>,
+    radius: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> spell_area: SpellArea;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.position.xy - spell_area.center) / spell_area.radius;
+
+    // A soft edge, so the effect fades out towards it.
+    let coverage = 1.0 - smoothstep(0.8, 1.0, distance);
+
+    return vec4<f32>(coverage, 0.0, 0.0, 0.0);
+}
@@ -0,0 +1,127 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct FalseColor {
+    // In stops.
+    exposure: f32,
+    // Non-zero to map the image to bands.
+    show_bands: u32,
+    // Non-zero to draw the chart.
+    show_chart: u32,
+    // As a fraction of the height of the image.
+    chart_size: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> false_color: FalseColor;
+
+// The sRGB colors of the classic color checker, row by row.
+const CHECKER: array<vec3<f32>, 24> = array<vec3<f32>, 24>(
+    vec3<f32>(115.0, 82.0, 68.0),
+    vec3<f32>(194.0, 150.0, 130.0),
+    vec3<f32>(98.0, 122.0, 157.0),
+    vec3<f32>(87.0, 108.0, 67.0),
+    vec3<f32>(133.0, 128.0, 177.0),
+    vec3<f32>(103.0, 189.0, 170.0),
+    vec3<f32>(214.0, 126.0, 44.0),
+    vec3<f32>(80.0, 91.0, 166.0),
+    vec3<f32>(193.0, 90.0, 99.0),
+    vec3<f32>(94.0, 60.0, 108.0),
+    vec3<f32>(157.0, 188.0, 64.0),
+    vec3<f32>(224.0, 163.0, 46.0),
+    vec3<f32>(56.0, 61.0, 150.0),
+    vec3<f32>(70.0, 148.0, 73.0),
+    vec3<f32>(175.0, 54.0, 60.0),
+    vec3<f32>(231.0, 199.0, 31.0),
+    vec3<f32>(187.0, 86.0, 149.0),
+    vec3<f32>(8.0, 133.0, 161.0),
+    vec3<f32>(243.0, 243.0, 242.0),
+    vec3<f32>(200.0, 200.0, 200.0),
+    vec3<f32>(160.0, 160.0, 160.0),
+    vec3<f32>(122.0, 122.0, 121.0),
+    vec3<f32>(85.0, 85.0, 85.0),
+    vec3<f32>(52.0, 52.0, 52.0),
+);
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        return value * 12.92;
+    }
+    return 1.055 * pow(value, 1.0 / 2.4) - 0.055;
+}
+
+// The color of the chart at the given pixel, with the alpha set where the chart is.
+fn chart(pixel: vec2<f32>, resolution: vec2<f32>) -> vec4<f32> {
+    let height = false_color.chart_size * resolution.y;
+    let patch = height / 4.0;
+    let margin = 0.02 * resolution.y;
+
+    // Relative to the top left corner of the chart, which sits in the bottom left of the image.
+    let position = pixel - vec2<f32>(margin, resolution.y - margin - height);
+    if any(position < vec2<f32>(0.0)) || position.x >= patch * 6.0 || position.y >= height {
+        return vec4<f32>(0.0);
+    }
+
+    let cell = floor(position / patch);
+    let within = position / patch - cell;
+
+    // Patches are framed in black, like on the real chart.
+    if any(within < vec2<f32>(0.06)) || any(within > vec2<f32>(0.94)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    // Constant arrays can't be indexed dynamically, so this copies it.
+    var checker = CHECKER;
+    let index = u32(cell.y) * 6u + u32(cell.x);
+    return vec4<f32>(srgb_to_linear(checker[index] / 255.0), 1.0);
+}
+
+fn band(color: vec3<f32>) -> vec3<f32> {
+    let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722)) * exp2(false_color.exposure);
+    let value = linear_to_srgb(max(luminance, 0.0));
+
+    if value < 0.025 {
+        return vec3<f32>(0.35, 0.0, 0.6);
+    } else if value < 0.05 {
+        return vec3<f32>(0.0, 0.15, 1.0);
+    } else if value >= 0.43 && value < 0.49 {
+        return vec3<f32>(0.0, 0.8, 0.1);
+    } else if value >= 0.58 && value < 0.64 {
+        return vec3<f32>(1.0, 0.35, 0.6);
+    } else if value >= 0.99 {
+        return vec3<f32>(1.0, 0.0, 0.0);
+    } else if value >= 0.95 {
+        return vec3<f32>(1.0, 0.9, 0.0);
+    }
+
+    // Grayscale in between, as it would look on the display.
+    return vec3<f32>(luminance);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let sample = textureSample(source, source_sampler, in.uv);
+    var color = sample.rgb;
+
+    if false_color.show_chart != 0u {
+        let chart_color = chart(in.uv * resolution, resolution);
+        color = mix(color, chart_color.rgb, chart_color.a);
+    }
+
+    if false_color.show_bands != 0u {
+        color = band(color);
+    }
+
+    return vec4<f32>(color, sample.a);
+}
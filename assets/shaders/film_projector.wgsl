@@ -0,0 +1,75 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct FilmProjector {
+    // Projected frames per second.
+    frame_rate: f32,
+    // Shutter blades, each of which interrupts the light once per frame.
+    blades: f32,
+    flicker: f32,
+    // In UV space.
+    weave: f32,
+    // In seconds, zero for no splices.
+    splice_interval: f32,
+    // In seconds.
+    splice_duration: f32,
+    // In heights of the image.
+    splice_jump: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> projector: FilmProjector;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Smooth noise over frames, between -1 and 1.
+fn wander(frames: f32, seed: f32) -> f32 {
+    let i = floor(frames);
+    let f = smoothstep(0.0, 1.0, fract(frames));
+    return mix(hash(vec2<f32>(i, seed)), hash(vec2<f32>(i + 1.0, seed)), f) * 2.0 - 1.0;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let frames = globals.time * max(projector.frame_rate, 0.0);
+    // Wrapped, so the hash stays precise.
+    let frame = floor(frames) % 1024.0;
+    let within_frame = fract(frames);
+
+    // The gate holds each frame still, but the film wanders a little from frame to frame,
+    // mostly sideways.
+    let weave = vec2<f32>(wander(frame * 0.25, 1.0), wander(frame * 0.25, 2.0) * 0.5);
+    var uv = in.uv + weave * projector.weave;
+
+    // At a splice the film slips in the gate, and settles back over the duration.
+    var splice = 0.0;
+    if projector.splice_interval > 0.0 && projector.splice_duration > 0.0 {
+        let since_splice = globals.time % projector.splice_interval;
+        splice = 1.0 - clamp(since_splice / projector.splice_duration, 0.0, 1.0);
+    }
+    uv.y += splice * projector.splice_jump;
+
+    // The gap between two frames shows up as a dark bar while the film slips.
+    let bar = step(fract(uv.y), 0.06) * step(0.0, splice - 0.001);
+    uv.y = fract(uv.y);
+
+    var color = textureSample(source, source_sampler, uv);
+
+    // The shutter blades cut the light several times per frame. Against the refresh rate
+    // of the display this beats into a visible flicker, as do density changes between frames.
+    let shutter = 0.5 - 0.5 * cos(6.28318 * projector.blades * within_frame);
+    let density = hash(vec2<f32>(frame, 3.0)) - 0.5;
+    let brightness = 1.0 - projector.flicker * (shutter * 0.5 + density);
+
+    color = vec4<f32>(color.rgb * max(brightness, 0.0) * (1.0 - bar), color.a);
+    return color;
+}
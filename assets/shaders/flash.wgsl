@@ -0,0 +1,14 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// The color of the flash, whose alpha is how much it covers the image.
+struct Flash {
+    color: vec4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> flash: Flash;
+
+// Drawn over the image by its alpha, see `Composition::AlphaOver`.
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return flash.color;
+}
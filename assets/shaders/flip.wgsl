@@ -1,22 +1,42 @@
+#ifdef EFFECT_MATERIAL
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+#else
 #import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
 #import bevy_render::globals Globals
 
 @group(0) @binding(0)
-var t: texture_2d<f32>;
+var source: texture_2d<f32>;
 @group(0) @binding(1)
-var ts: sampler;
+var source_sampler: sampler;
 @group(0) @binding(2)
 var<uniform> globals: Globals;
+#endif
 
 struct Flip {
     x: f32,
     y: f32,
 };
+#ifdef EFFECT_MATERIAL
+@group(1) @binding(2)
+#else
 @group(1) @binding(0)
+#endif
 var<uniform> flip: Flip;
 
+// The effect at `uv`, shared by post processing and materials.
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let flipped = abs(vec2<f32>(flip.x, flip.y) - uv);
+    return textureSample(source, source_sampler, flipped);
+}
+
+#ifdef EFFECT_MATERIAL
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return finish(in.uv, effect(in.uv));
+}
+#else
 @fragment
 fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let uv = abs(vec2<f32>(flip.x, flip.y) - in.uv);
-    return textureSample(t, ts, uv);
-}
\ No newline at end of file
+    return effect(in.uv);
+}
+#endif
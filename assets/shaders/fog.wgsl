@@ -0,0 +1,62 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct Fog {
+    color: vec4<f32>,
+    start: f32,
+    end: f32,
+    density: f32,
+    inverse_projection: mat4x4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> fog: Fog;
+
+// The depth at the given UV. Read by UV rather than by fragment position,
+// so the effect also works when drawn at a lower resolution.
+fn load_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth));
+    return textureLoad(depth, vec2<i32>(min(uv * size, size - 1.0)), 0);
+}
+
+// The distance from the camera to what is seen at the given fragment.
+fn view_distance(uv: vec2<f32>) -> f32 {
+    let d = load_depth(uv);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return 3.40282e+38;
+    }
+
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let view = fog.inverse_projection * ndc;
+
+    return length(view.xyz / view.w);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(t, ts, in.uv);
+    let distance = max(view_distance(in.uv) - fog.start, 0.0);
+
+    #ifdef LINEAR
+    let amount = saturate(distance / max(fog.end - fog.start, 0.0001));
+    #endif
+    #ifdef EXPONENTIAL
+    let amount = 1.0 - exp(-fog.density * distance);
+    #endif
+    #ifdef EXPONENTIAL_SQUARED
+    let amount = 1.0 - exp(-pow(fog.density * distance, 2.0));
+    #endif
+
+    return vec4<f32>(mix(sample.rgb, fog.color.rgb, amount * fog.color.a), 1.0);
+}
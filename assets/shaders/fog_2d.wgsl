@@ -0,0 +1,85 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct Fog2d {
+    // Linear, alpha is the opacity of the fog at its thickest.
+    color: vec4<f32>,
+    // Start, end and amount of each band, in UV space.
+    bands: array<vec4<f32>, 4>,
+    // The depth of each layer.
+    depths: vec4<f32>,
+    band_count: u32,
+    layer_count: u32,
+    density: f32,
+    background_depth: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> fog: Fog2d;
+@group(1) @binding(1)
+var layer_0: texture_2d<f32>;
+@group(1) @binding(2)
+var layer_0_sampler: sampler;
+@group(1) @binding(3)
+var layer_1: texture_2d<f32>;
+@group(1) @binding(4)
+var layer_1_sampler: sampler;
+@group(1) @binding(5)
+var layer_2: texture_2d<f32>;
+@group(1) @binding(6)
+var layer_2_sampler: sampler;
+@group(1) @binding(7)
+var layer_3: texture_2d<f32>;
+@group(1) @binding(8)
+var layer_3_sampler: sampler;
+
+// How thick the fog of a band is at the given height.
+fn band(band: vec4<f32>, y: f32) -> f32 {
+    let range = band.y - band.x;
+    // A hard edge, with the fog below it.
+    if abs(range) < 0.0001 {
+        return select(0.0, band.z, y >= band.x);
+    }
+    return saturate((y - band.x) / range) * band.z;
+}
+
+// The depth of what is drawn at the given UV, where the layers cover each other from back to front.
+fn depth(uv: vec2<f32>) -> f32 {
+    // Sampled up front, as texture samples must be in uniform control flow.
+    let coverage = vec4<f32>(
+        textureSample(layer_0, layer_0_sampler, uv).a,
+        textureSample(layer_1, layer_1_sampler, uv).a,
+        textureSample(layer_2, layer_2_sampler, uv).a,
+        textureSample(layer_3, layer_3_sampler, uv).a,
+    );
+
+    var depth = fog.background_depth;
+    for (var i = 0u; i < min(fog.layer_count, 4u); i += 1u) {
+        depth = mix(depth, fog.depths[i], coverage[i]);
+    }
+    return depth;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+    let depth = depth(in.uv);
+
+    // The bands and the depth fog each let some of the image through, the fog is what none of them do.
+    var clear = 1.0;
+    for (var i = 0u; i < min(fog.band_count, 4u); i += 1u) {
+        clear *= 1.0 - band(fog.bands[i], in.uv.y);
+    }
+    if fog.layer_count > 0u {
+        clear *= exp(-fog.density * max(depth, 0.0));
+    }
+
+    let amount = saturate(1.0 - clear) * fog.color.a;
+    let color = mix(sample.rgb, fog.color.rgb, amount);
+
+    return vec4<f32>(color, sample.a);
+}
@@ -0,0 +1,66 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct FrostedGlass {
+    // Linear, alpha is the amount mixed in.
+    tint: vec4<f32>,
+    // In repeats of the normal map per second.
+    drift: vec2<f32>,
+    // In pixels.
+    distortion: f32,
+    // In pixels per repeat of the normal map.
+    distortion_scale: f32,
+    // In pixels.
+    blur_radius: f32,
+    // How much the glass bends the image, from 0.0 (on the scene) to 1.0 (on the camera).
+    parallax: f32,
+    // How far the glass has scrolled against the scene, in heights of the view.
+    scroll: vec2<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> frosted_glass: FrostedGlass;
+@group(1) @binding(1)
+var normals: texture_2d<f32>;
+@group(1) @binding(2)
+var normals_sampler: sampler;
+
+// Taps of the blur, spread over a disk along the golden angle.
+const TAPS: i32 = 12;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let pixel = in.uv * resolution;
+
+    // Wrapped, so the offset stays precise as time goes on.
+    let drift = fract(frosted_glass.drift * globals.time);
+    let scrolled = pixel + frosted_glass.scroll * resolution.y;
+    let normal_uv = scrolled / max(frosted_glass.distortion_scale, 1.0) + drift;
+    let normal = textureSample(normals, normals_sampler, normal_uv).xy * 2.0 - 1.0;
+
+    let uv = in.uv + normal * frosted_glass.distortion * frosted_glass.parallax / resolution;
+
+    var color = vec4<f32>(0.0);
+    for (var i = 0; i < TAPS; i += 1) {
+        // The square root spreads the taps evenly over the area of the disk.
+        let radius = sqrt((f32(i) + 0.5) / f32(TAPS)) * frosted_glass.blur_radius;
+        let angle = f32(i) * GOLDEN_ANGLE;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius / resolution;
+
+        color += textureSample(source, source_sampler, uv + offset);
+    }
+    color /= f32(TAPS);
+
+    let tinted = mix(color.rgb, frosted_glass.tint.rgb, frosted_glass.tint.a);
+
+    return vec4<f32>(tinted, color.a);
+}
@@ -0,0 +1,37 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Ghosting {
+    decay: f32,
+    drift: vec2<f32>,
+    zoom: f32,
+};
+@group(1) @binding(0)
+var<uniform> ghosting: Ghosting;
+@group(1) @binding(1)
+var history: texture_2d<f32>;
+@group(1) @binding(2)
+var history_sampler: sampler;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let current = textureSample(t, ts, in.uv);
+
+    // Drifting and zooming the history each frame makes the trails flow, instead of just lingering.
+    let history_uv = (in.uv - 0.5) / ghosting.zoom + 0.5 - ghosting.drift;
+    var previous = textureSample(history, history_sampler, history_uv);
+
+    // Nothing to trail from outside the previous frame.
+    if any(history_uv < vec2<f32>(0.0)) || any(history_uv > vec2<f32>(1.0)) {
+        previous = current;
+    }
+
+    return mix(current, previous, ghosting.decay);
+}
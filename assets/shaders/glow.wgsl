@@ -0,0 +1,106 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Glow {
+    tint: vec4<f32>,
+    intensity: f32,
+    threshold: f32,
+    knee: f32,
+    radius: f32,
+    // Whether the glow only comes from a channel of the effect mask, and which.
+    masked: u32,
+    mask_channel: u32,
+};
+@group(1) @binding(0)
+var<uniform> glow: Glow;
+
+// The image before the glow, captured before the first pass.
+@group(1) @binding(1)
+var original: texture_2d<f32>;
+@group(1) @binding(2)
+var original_sampler: sampler;
+
+// The effect mask of the camera, or white without one.
+@group(1) @binding(3)
+var effect_mask: texture_2d<f32>;
+
+fn texel_size() -> vec2<f32> {
+    return 1.0 / vec2<f32>(textureDimensions(t));
+}
+
+#ifdef THRESHOLD
+// Keeps the parts brighter than the threshold, with a soft knee below it so the glow doesn't pop in.
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(t, ts, in.uv).rgb;
+    let brightness = max(color.r, max(color.g, color.b));
+
+    let knee = glow.threshold * glow.knee;
+    var soft = clamp(brightness - glow.threshold + knee, 0.0, 2.0 * knee);
+    soft = soft * soft / (4.0 * knee + 0.00001);
+
+    var contribution = max(soft, brightness - glow.threshold) / max(brightness, 0.00001);
+    if glow.masked != 0u {
+        contribution *= textureSample(effect_mask, original_sampler, in.uv)[glow.mask_channel];
+    }
+
+    return vec4<f32>(color * contribution, 1.0);
+}
+#endif
+
+#ifdef DOWNSAMPLE
+// Averages a 4x4 block of texels (each bilinear sample covers 2x2), so the blur works on a smoother,
+// lower frequency image and doesn't flicker on small bright details.
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let offset = texel_size();
+
+    var color = textureSample(t, ts, in.uv + vec2<f32>(-offset.x, -offset.y)).rgb;
+    color += textureSample(t, ts, in.uv + vec2<f32>(offset.x, -offset.y)).rgb;
+    color += textureSample(t, ts, in.uv + vec2<f32>(-offset.x, offset.y)).rgb;
+    color += textureSample(t, ts, in.uv + vec2<f32>(offset.x, offset.y)).rgb;
+
+    return vec4<f32>(color * 0.25, 1.0);
+}
+#endif
+
+#ifdef BLUR
+// A separable gaussian blur, with the taps spread out to reach the radius.
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+#ifdef BLUR_VERTICAL
+    let direction = vec2<f32>(0.0, 1.0);
+#else
+    let direction = vec2<f32>(1.0, 0.0);
+#endif
+    let step = direction * texel_size() * glow.radius / 4.0;
+
+    var weights = array<f32, 5>(0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+    var color = textureSample(t, ts, in.uv).rgb * weights[0];
+    for (var i = 1; i < 5; i += 1) {
+        let offset = step * f32(i);
+        color += textureSample(t, ts, in.uv + offset).rgb * weights[i];
+        color += textureSample(t, ts, in.uv - offset).rgb * weights[i];
+    }
+
+    return vec4<f32>(color, 1.0);
+}
+#endif
+
+#ifdef COMPOSITE
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let base = textureSample(original, original_sampler, in.uv);
+    let bloom = textureSample(t, ts, in.uv).rgb;
+
+    return vec4<f32>(base.rgb + bloom * glow.tint.rgb * glow.intensity, base.a);
+}
+#endif
@@ -0,0 +1,63 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct GodRays2d {
+    // Linear, alpha scales the brightness.
+    color: vec4<f32>,
+    // In UV space.
+    light_position: vec2<f32>,
+    // As a fraction of the height of the image.
+    light_radius: f32,
+    density: f32,
+    decay: f32,
+    exposure: f32,
+    samples: u32,
+};
+
+@group(1) @binding(0)
+var<uniform> god_rays: GodRays2d;
+@group(1) @binding(1)
+var occluders: texture_2d<f32>;
+@group(1) @binding(2)
+var occluders_sampler: sampler;
+
+// How much light the light disk gives off at the given UV, where it isn't blocked.
+fn light(uv: vec2<f32>, aspect: f32) -> f32 {
+    let distance = length((uv - god_rays.light_position) * vec2<f32>(aspect, 1.0));
+    let radius = max(god_rays.light_radius, 0.0001);
+    let disk = 1.0 - smoothstep(radius * 0.5, radius, distance);
+
+    // Sampled without derivatives, as this is called in a loop.
+    let blocked = textureSampleLevel(occluders, occluders_sampler, uv, 0.0).a;
+
+    return disk * (1.0 - blocked);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let aspect = resolution.x / resolution.y;
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    let samples = max(god_rays.samples, 1u);
+    let step = (in.uv - god_rays.light_position) * god_rays.density / f32(samples);
+
+    // March towards the light, gathering the light which isn't blocked along the way.
+    var uv = in.uv;
+    var weight = 1.0;
+    var rays = 0.0;
+    for (var i = 0u; i < samples; i += 1u) {
+        uv -= step;
+        rays += light(uv, aspect) * weight;
+        weight *= god_rays.decay;
+    }
+    rays *= god_rays.exposure / f32(samples);
+
+    let color = sample.rgb + god_rays.color.rgb * god_rays.color.a * rays;
+
+    return vec4<f32>(color, sample.a);
+}
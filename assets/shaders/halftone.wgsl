@@ -0,0 +1,78 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Halftone {
+    dot_size: f32,
+    threshold: f32,
+    cyan_angle: f32,
+    magenta_angle: f32,
+    yellow_angle: f32,
+    black_angle: f32,
+};
+@group(1) @binding(0)
+var<uniform> halftone: Halftone;
+
+fn rotation(angle: f32) -> mat2x2<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return mat2x2<f32>(c, s, -s, c);
+}
+
+fn to_cmyk(rgb: vec3<f32>) -> vec4<f32> {
+    let k = 1.0 - max(max(rgb.r, rgb.g), rgb.b);
+    // Avoid dividing by zero for pure black.
+    let cmy = (1.0 - rgb - k) / max(1.0 - k, 0.0001);
+    return vec4<f32>(cmy, k);
+}
+
+// How much of the given pixel is covered by the dot of one ink channel.
+fn coverage(pixel: vec2<f32>, size: vec2<f32>, angle: f32, channel: i32) -> f32 {
+    let to_grid = rotation(angle);
+    let from_grid = transpose(to_grid);
+
+    // Find the center of the grid cell this pixel is in, in the rotated grid.
+    let grid = to_grid * pixel / halftone.dot_size;
+    let cell = floor(grid) + 0.5;
+
+    // The amount of ink is decided by the color at the center of the cell.
+    let center = from_grid * cell * halftone.dot_size;
+    let rgb = textureSampleLevel(t, ts, center / size, 0.0).rgb;
+    var ink = to_cmyk(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)))[channel];
+    ink *= step(halftone.threshold, ink);
+
+    // The area of the dot follows the amount of ink.
+    // At full ink the dot reaches the corners of the cell, so the whole cell is covered.
+    let radius = sqrt(ink) * 0.7072;
+    let distance = length(grid - cell);
+
+    // Antialias the edge of the dot over about one pixel.
+    let edge = 1.0 / halftone.dot_size;
+    return 1.0 - smoothstep(radius - edge, radius + edge, distance);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    if halftone.dot_size <= 0.0 {
+        return textureSample(t, ts, in.uv);
+    }
+
+    let size = vec2<f32>(textureDimensions(t));
+    let pixel = in.uv * size;
+
+    let c = coverage(pixel, size, halftone.cyan_angle, 0);
+    let m = coverage(pixel, size, halftone.magenta_angle, 1);
+    let y = coverage(pixel, size, halftone.yellow_angle, 2);
+    let k = coverage(pixel, size, halftone.black_angle, 3);
+
+    // The inks are subtractive: each one removes its complement from the white paper.
+    let output = (vec3<f32>(1.0) - vec3<f32>(c, m, y)) * (1.0 - k);
+
+    return vec4<f32>(output, 1.0);
+}
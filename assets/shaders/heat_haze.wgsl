@@ -0,0 +1,72 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct HeatHaze {
+    // The min and max corners of the rect, in UV space.
+    rect: vec4<f32>,
+    strength: f32,
+    scale: f32,
+    speed: f32,
+    feather: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> heat_haze: HeatHaze;
+@group(1) @binding(1)
+var mask: texture_2d<f32>;
+@group(1) @binding(2)
+var mask_sampler: sampler;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Value noise in [0, 1].
+fn noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+
+    let a = hash(cell);
+    let b = hash(cell + vec2<f32>(1.0, 0.0));
+    let c = hash(cell + vec2<f32>(0.0, 1.0));
+    let d = hash(cell + vec2<f32>(1.0, 1.0));
+
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+// How much haze there is at the given UV, from the rect and the mask.
+fn weight(uv: vec2<f32>) -> f32 {
+    let feather = max(heat_haze.feather, 0.0001);
+    let from_min = smoothstep(vec2<f32>(0.0), vec2<f32>(feather), uv - heat_haze.rect.xy);
+    let from_max = smoothstep(vec2<f32>(0.0), vec2<f32>(feather), heat_haze.rect.zw - uv);
+    let in_rect = from_min.x * from_min.y * from_max.x * from_max.y;
+
+    return in_rect * textureSample(mask, mask_sampler, uv).r;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let dimensions = vec2<f32>(textureDimensions(source));
+
+    // Keep the noise cells square, and let them rise over time.
+    let aspect = vec2<f32>(dimensions.x / dimensions.y, 1.0);
+    let p = in.uv * aspect * heat_haze.scale + vec2<f32>(0.0, globals.time * heat_haze.speed * heat_haze.scale);
+
+    // Two octaves, offset so the x and y displacement don't move in lockstep.
+    let n = vec2<f32>(
+        noise(p) + 0.5 * noise(p * 2.0 + 17.0),
+        noise(p + 31.0) + 0.5 * noise(p * 2.0 + 47.0),
+    ) / 1.5;
+
+    let offset = (n * 2.0 - 1.0) * heat_haze.strength * weight(in.uv);
+
+    return textureSample(source, source_sampler, in.uv + offset);
+}
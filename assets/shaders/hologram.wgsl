@@ -0,0 +1,86 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Hologram {
+    // Scanlines across the height of the image.
+    line_density: f32,
+    // In pixels.
+    jitter_amplitude: f32,
+    // Dropouts per second, on average.
+    dropout_frequency: f32,
+    // Linear, alpha is the amount added.
+    tint: vec4<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> hologram: Hologram;
+
+// How long a dropout lasts, in seconds.
+const DROPOUT_DURATION: f32 = 0.12;
+
+// How many bands of the image jitter independently.
+const BANDS: f32 = 48.0;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// How much the signal has dropped out right now, from 0.0 to 1.0.
+fn dropout(time: f32) -> f32 {
+    if hologram.dropout_frequency <= 0.0 {
+        return 0.0;
+    }
+
+    // Time is cut into slots as long as a dropout, and a random few of them drop out.
+    let slot = floor(time / DROPOUT_DURATION);
+    let chance = min(hologram.dropout_frequency * DROPOUT_DURATION, 1.0);
+    if hash(vec2<f32>(slot % 1024.0, 7.0)) >= chance {
+        return 0.0;
+    }
+
+    // Fades in and out within the slot, so it flickers rather than cuts.
+    let phase = fract(time / DROPOUT_DURATION);
+    return sin(phase * 3.14159265);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let signal_loss = dropout(globals.time);
+
+    // The offsets change often but not every frame. Wrapped, so the hash stays precise.
+    let step = floor(globals.time * 20.0) % 1024.0;
+    let band = floor(in.uv.y * BANDS);
+
+    // Most bands hold still, a few tear. During a dropout all of them do.
+    let random = hash(vec2<f32>(band, step)) * 2.0 - 1.0;
+    let tear = select(0.0, 1.0, abs(random) > 0.8);
+    let amplitude = hologram.jitter_amplitude * (1.0 + 4.0 * signal_loss);
+    let offset = random * max(tear, signal_loss) * amplitude;
+
+    var uv = in.uv;
+    uv.x += offset / resolution.x;
+
+    let sample = textureSample(source, source_sampler, uv);
+    var color = sample.rgb + hologram.tint.rgb * hologram.tint.a;
+
+    // Scanlines, slowly drifting down the image.
+    let line = in.uv.y * hologram.line_density - globals.time * 2.0;
+    let scanline = 0.5 + 0.5 * cos(line * 6.28318531);
+    color *= 1.0 - 0.4 * scanline;
+
+    // Static replaces the image while the signal is lost.
+    let pixel = floor(in.uv * resolution);
+    let noise = hash(pixel + vec2<f32>(step * 13.0, step * 7.0));
+    let static_color = vec3<f32>(noise) * hologram.tint.rgb;
+    color = mix(color, static_color, signal_loss);
+
+    return vec4<f32>(color, sample.a);
+}
@@ -0,0 +1,70 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Kuwahara {
+    radius: f32,
+    samples_per_side: u32,
+};
+@group(1) @binding(0)
+var<uniform> kuwahara: Kuwahara;
+
+const MAX_SAMPLES_PER_SIDE: u32 = 16u;
+
+struct Region {
+    mean: vec3<f32>,
+    variance: f32,
+};
+
+// Mean color and total variance of the square region spanning from the origin
+// to `direction * radius`, where `direction` is one of the four diagonals.
+fn region(uv: vec2<f32>, step: vec2<f32>, direction: vec2<f32>, samples: u32) -> Region {
+    var sum = vec3<f32>(0.0);
+    var sum_squared = vec3<f32>(0.0);
+
+    for (var y = 0u; y <= samples; y++) {
+        for (var x = 0u; x <= samples; x++) {
+            let offset = vec2<f32>(f32(x), f32(y)) * step * direction;
+            let color = textureSampleLevel(t, ts, uv + offset, 0.0).rgb;
+
+            sum += color;
+            sum_squared += color * color;
+        }
+    }
+
+    let count = f32((samples + 1u) * (samples + 1u));
+    let mean = sum / count;
+    let variance = abs(sum_squared / count - mean * mean);
+
+    return Region(mean, variance.r + variance.g + variance.b);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let samples = clamp(kuwahara.samples_per_side, 1u, MAX_SAMPLES_PER_SIDE);
+
+    // The radius is given in pixels, so convert it to a step in UV space.
+    let step = kuwahara.radius / f32(samples) / vec2<f32>(textureDimensions(t));
+
+    var best = region(in.uv, step, vec2<f32>(-1.0, -1.0), samples);
+
+    var directions = array<vec2<f32>, 3>(
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    for (var i = 0; i < 3; i++) {
+        let candidate = region(in.uv, step, directions[i], samples);
+        if candidate.variance < best.variance {
+            best = candidate;
+        }
+    }
+
+    return vec4<f32>(best.mean, 1.0);
+}
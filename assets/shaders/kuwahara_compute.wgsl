@@ -0,0 +1,71 @@
+#import bevy_vfx_bag::compute source, output, load_tile, tile_sample, pixel_uv
+
+struct Kuwahara {
+    radius: f32,
+    samples_per_side: u32,
+};
+@group(1) @binding(0)
+var<uniform> kuwahara: Kuwahara;
+
+const MAX_SAMPLES_PER_SIDE: u32 = 16u;
+
+struct Region {
+    mean: vec3<f32>,
+    variance: f32,
+};
+
+// As in `kuwahara.wgsl`, but in pixels and gathered from the tile.
+fn region(position: vec2<f32>, step: f32, direction: vec2<f32>, samples: u32) -> Region {
+    var sum = vec3<f32>(0.0);
+    var sum_squared = vec3<f32>(0.0);
+
+    for (var y = 0u; y <= samples; y++) {
+        for (var x = 0u; x <= samples; x++) {
+            let offset = vec2<f32>(f32(x), f32(y)) * step * direction;
+            let color = tile_sample(position + offset).rgb;
+
+            sum += color;
+            sum_squared += color * color;
+        }
+    }
+
+    let count = f32((samples + 1u) * (samples + 1u));
+    let mean = sum / count;
+    let variance = abs(sum_squared / count - mean * mean);
+
+    return Region(mean, variance.r + variance.g + variance.b);
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn gather(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(workgroup_id) workgroup: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    load_tile(workgroup, local_index);
+
+    let size = textureDimensions(source);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let samples = clamp(kuwahara.samples_per_side, 1u, MAX_SAMPLES_PER_SIDE);
+    let step = kuwahara.radius / f32(samples);
+    let position = pixel_uv(id.xy) * vec2<f32>(size);
+
+    var best = region(position, step, vec2<f32>(-1.0, -1.0), samples);
+
+    var directions = array<vec2<f32>, 3>(
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    for (var i = 0; i < 3; i++) {
+        let candidate = region(position, step, directions[i], samples);
+        if candidate.variance < best.variance {
+            best = candidate;
+        }
+    }
+
+    textureStore(output, vec2<i32>(id.xy), vec4<f32>(best.mean, 1.0));
+}
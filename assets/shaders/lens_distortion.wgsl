@@ -0,0 +1,46 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct LensDistortion {
+    // Positive values push the edges outwards (pincushion), negative values pull them in (barrel).
+    coefficient: f32,
+    fringing: f32,
+    scale: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> lens_distortion: LensDistortion;
+
+// Where to sample for the given position, distorted by the given coefficient.
+fn distort(centered: vec2<f32>, radius_squared: f32, coefficient: f32) -> vec2<f32> {
+    let factor = (1.0 + coefficient * radius_squared) * lens_distortion.scale;
+    return 0.5 + 0.5 * centered * factor;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let aspect = resolution.x / resolution.y;
+
+    // From -1.0 to 1.0 across the view.
+    let centered = in.uv * 2.0 - 1.0;
+
+    // The radius is measured with the aspect ratio, so the distortion is round.
+    // It is normalized so the corners are at 1.0.
+    let aspect_centered = centered * vec2<f32>(aspect, 1.0);
+    let radius_squared = dot(aspect_centered, aspect_centered) / (aspect * aspect + 1.0);
+
+    let k = lens_distortion.coefficient;
+    let fringing = lens_distortion.fringing;
+
+    // Each channel is bent a little differently, like cheap glass.
+    let r = textureSample(source, source_sampler, distort(centered, radius_squared, k * (1.0 + fringing))).r;
+    let g = textureSample(source, source_sampler, distort(centered, radius_squared, k));
+    let b = textureSample(source, source_sampler, distort(centered, radius_squared, k * (1.0 - fringing))).b;
+
+    return vec4<f32>(r, g.g, b, g.a);
+}
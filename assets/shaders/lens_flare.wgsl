@@ -0,0 +1,121 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct LensFlare {
+    color: vec4<f32>,
+    light_position: vec2<f32>,
+    light_depth: f32,
+    intensity: f32,
+    ghosts: u32,
+    ghost_spacing: f32,
+    ghost_size: f32,
+    halo_radius: f32,
+    halo_width: f32,
+    halo_intensity: f32,
+    streak_length: f32,
+    streak_intensity: f32,
+    occlusion_radius: f32,
+};
+@group(1) @binding(0)
+var<uniform> lens_flare: LensFlare;
+
+// How much of the light is visible, by comparing the depth around it against the light's own depth.
+fn visibility() -> f32 {
+    let size = vec2<i32>(textureDimensions(depth));
+    let center = vec2<i32>(lens_flare.light_position * vec2<f32>(size));
+    let radius = lens_flare.occlusion_radius;
+
+    var visible = 0.0;
+    var total = 0.0;
+    for (var y = -2; y <= 2; y += 1) {
+        for (var x = -2; x <= 2; x += 1) {
+            let offset = vec2<i32>(vec2<f32>(f32(x), f32(y)) * radius * 0.5);
+            let texel = center + offset;
+
+            // Samples off screen can't be occluded by anything we know about.
+            total += 1.0;
+            if any(texel < vec2<i32>(0)) || any(texel >= size) {
+                visible += 1.0;
+                continue;
+            }
+
+            // Bevy uses reverse z, so anything closer than the light has a greater depth.
+            let d = textureLoad(depth, texel, 0);
+            if d <= lens_flare.light_depth {
+                visible += 1.0;
+            }
+        }
+    }
+
+    return visible / total;
+}
+
+// A soft disc of the given radius, in aspect corrected UV space.
+fn disc(uv: vec2<f32>, center: vec2<f32>, radius: f32, aspect: f32) -> f32 {
+    let d = length((uv - center) * vec2<f32>(aspect, 1.0));
+    return 1.0 - smoothstep(radius * 0.5, radius, d);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(t, ts, in.uv);
+
+    // Fade out as the light leaves the screen, over a fifth of a screen beyond the edges.
+    let outside = max(abs(lens_flare.light_position - 0.5) - 0.5, vec2<f32>(0.0));
+    let on_screen = 1.0 - saturate(length(outside) * 5.0);
+
+    let strength = lens_flare.intensity * on_screen;
+    if strength <= 0.0 {
+        return sample;
+    }
+
+    let visible = visibility();
+    if visible <= 0.0 {
+        return sample;
+    }
+
+    let dimensions = vec2<f32>(textureDimensions(t));
+    let aspect = dimensions.x / dimensions.y;
+    let light = lens_flare.light_position;
+
+    // Ghosts are reflections inside the lens, spread along the line from the light through the center.
+    let ghost_step = (vec2<f32>(0.5) - light) * lens_flare.ghost_spacing;
+    var ghosts = vec3<f32>(0.0);
+    for (var i = 1u; i <= lens_flare.ghosts; i += 1u) {
+        let center = light + ghost_step * f32(i);
+
+        // Vary the size and hue of each ghost, so they don't look like copies of each other.
+        let size = lens_flare.ghost_size * (0.6 + 0.8 * fract(f32(i) * 0.618));
+        let hue = vec3<f32>(
+            0.6 + 0.4 * sin(f32(i) * 1.7),
+            0.6 + 0.4 * sin(f32(i) * 1.7 + 2.1),
+            0.6 + 0.4 * sin(f32(i) * 1.7 + 4.2),
+        );
+        ghosts += disc(in.uv, center, size, aspect) * hue * 0.3;
+    }
+
+    // The halo is a ring around the light.
+    let distance_to_light = length((in.uv - light) * vec2<f32>(aspect, 1.0));
+    let ring = abs(distance_to_light - lens_flare.halo_radius) / max(lens_flare.halo_width, 0.0001);
+    let halo = exp(-ring * ring) * lens_flare.halo_intensity;
+
+    // A glow on the light itself, stretched horizontally into an anamorphic streak.
+    let offset = (in.uv - light) * vec2<f32>(aspect, 1.0);
+    let glow = exp(-distance_to_light * 40.0);
+    let streak_falloff = 1.0 - saturate(abs(offset.x) / max(lens_flare.streak_length, 0.0001));
+    let streak = exp(-abs(offset.y) * 400.0) * streak_falloff * streak_falloff * lens_flare.streak_intensity;
+
+    let flare = (ghosts + vec3<f32>(halo + glow + streak)) * lens_flare.color.rgb;
+    let result = sample.rgb + flare * strength * visible;
+
+    return vec4<f32>(result, sample.a);
+}
@@ -0,0 +1,41 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct Letterbox {
+    // Linear.
+    color: vec4<f32>,
+    // Width over height.
+    aspect_ratio: f32,
+    progress: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> letterbox: Letterbox;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    let size = vec2<f32>(textureDimensions(source));
+    let screen_aspect_ratio = size.x / size.y;
+
+    // How much of each side the bars take, in UV space. Only one of the two is ever above zero:
+    // top and bottom bars on screens narrower than the aspect ratio, side bars on wider ones.
+    let visible = vec2<f32>(
+        min(letterbox.aspect_ratio / screen_aspect_ratio, 1.0),
+        min(screen_aspect_ratio / letterbox.aspect_ratio, 1.0),
+    );
+    // Whole pixels, so the edges of the bars stay sharp.
+    let bars = round((1.0 - visible) * 0.5 * letterbox.progress * size) / size;
+
+    let inside = all(in.uv >= bars) && all(in.uv <= 1.0 - bars);
+    if inside {
+        return sample;
+    }
+
+    return vec4<f32>(mix(sample.rgb, letterbox.color.rgb, letterbox.color.a), sample.a);
+}
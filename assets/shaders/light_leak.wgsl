@@ -0,0 +1,83 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct LightLeak {
+    color: vec4<f32>,
+    intensity: f32,
+    // As a fraction of the height of the image.
+    scale: f32,
+    speed: f32,
+    // In seconds.
+    interval: f32,
+    seed: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> leak: LightLeak;
+
+const BLOBS: u32 = 3u;
+const PI: f32 = 3.14159265359;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// A set of blobs, placed according to the generation.
+fn blobs(uv: vec2<f32>, aspect: vec2<f32>, generation: f32) -> vec3<f32> {
+    // Wrapped, so the hashes stay precise.
+    let g = generation % 512.0 + leak.seed * 13.0;
+    let time = globals.time * leak.speed;
+
+    var light = vec3<f32>(0.0);
+    for (var i = 0u; i < BLOBS; i++) {
+        let fi = f32(i);
+        let a = hash(vec2<f32>(g, fi));
+        let b = hash(vec2<f32>(fi + 3.7, g));
+        let c = hash(vec2<f32>(g + 1.9, fi + 5.3));
+
+        // Leaks mostly come in from the sides of the frame.
+        let side = select(-0.1, 1.1, a > 0.5);
+        let start = vec2<f32>(mix(side, b, 0.3), b * 1.2 - 0.1);
+        let drift = vec2<f32>(sin(time * 0.21 + fi * 2.1), cos(time * 0.17 + fi * 1.3)) * 0.12;
+        let center = start + drift;
+
+        let size = max(leak.scale, 0.0001) * (0.6 + 0.8 * c);
+        let d = length((uv - center) * aspect) / size;
+        let falloff = exp(-d * d * 2.0);
+
+        // Vary the hue slightly towards yellow or red.
+        let tint = mix(leak.color.rgb, leak.color.rgb * vec3<f32>(1.0, 1.3, 0.6), c);
+        light += tint * falloff;
+    }
+
+    return light;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let aspect = vec2<f32>(resolution.x / resolution.y, 1.0);
+
+    // Two overlapping generations, half an interval apart, which fade in and out.
+    // When one is gone, it comes back as the next generation at new places.
+    let phase = globals.time / max(leak.interval, 0.0001);
+    let phase_a = phase;
+    let phase_b = phase + 0.5;
+    let weight_a = sin(PI * fract(phase_a));
+    let weight_b = sin(PI * fract(phase_b));
+
+    let leaks = blobs(in.uv, aspect, floor(phase_a) * 2.0) * weight_a
+        + blobs(in.uv, aspect, floor(phase_b) * 2.0 + 1.0) * weight_b;
+    let light = saturate(leaks * saturate(leak.intensity));
+
+    // Screened onto the image by the pipeline, which only brightens it.
+    // The alpha of the image is kept.
+    return vec4<f32>(light, 0.0);
+}
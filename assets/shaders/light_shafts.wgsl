@@ -0,0 +1,54 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct LightShafts {
+    light_position: vec2<f32>,
+    density: f32,
+    decay: f32,
+    weight: f32,
+    exposure: f32,
+    threshold: f32,
+    samples: u32,
+};
+@group(1) @binding(0)
+var<uniform> light_shafts: LightShafts;
+
+// Only bright pixels cast shafts.
+fn bright(uv: vec2<f32>) -> vec3<f32> {
+    let color = textureSample(t, ts, uv).rgb;
+    let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+
+    return color * smoothstep(light_shafts.threshold, light_shafts.threshold + 0.1, luminance);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(t, ts, in.uv);
+
+    // Radially blur the bright pixels towards the light, see GPU Gems 3, chapter 13.
+    let samples = max(light_shafts.samples, 1u);
+    let step = (in.uv - light_shafts.light_position) * light_shafts.density / f32(samples);
+
+    var uv = in.uv;
+    var illumination_decay = 1.0;
+    var shafts = vec3<f32>(0.0);
+    for (var i = 0u; i < samples; i += 1u) {
+        uv -= step;
+        shafts += bright(uv) * illumination_decay * light_shafts.weight;
+        illumination_decay *= light_shafts.decay;
+    }
+
+    // Fade out as the light leaves the screen, over half a screen beyond the edges.
+    let outside = max(abs(light_shafts.light_position - 0.5) - 0.5, vec2<f32>(0.0));
+    let on_screen = 1.0 - saturate(length(outside) * 2.0);
+
+    let result = sample.rgb + shafts * light_shafts.exposure * on_screen;
+    return vec4<f32>(result, sample.a);
+}
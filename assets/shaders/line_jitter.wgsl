@@ -0,0 +1,66 @@
+#ifdef EFFECT_MATERIAL
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+#else
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+#endif
+
+struct LineJitter {
+    // In pixels.
+    amplitude: f32,
+    // Changes per second.
+    frequency: f32,
+    // In pixels.
+    line_height: f32,
+    // In heights of the image per second.
+    roll: f32,
+};
+
+#ifdef EFFECT_MATERIAL
+@group(1) @binding(2)
+#else
+@group(1) @binding(0)
+#endif
+var<uniform> jitter: LineJitter;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// The effect at `uv`, shared by post processing and materials.
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+
+    var jittered = uv;
+    jittered.y = fract(jittered.y + globals.time * jitter.roll);
+
+    // The offsets only change a few times per second. Wrapped, so the hash stays precise.
+    let step = floor(globals.time * max(jitter.frequency, 0.0)) % 1024.0;
+    let line = floor(jittered.y * resolution.y / max(jitter.line_height, 1.0));
+
+    // Mostly small offsets, with the occasional line torn further.
+    let random = hash(vec2<f32>(line, step)) * 2.0 - 1.0;
+    let offset = random * random * random * jitter.amplitude;
+    jittered.x += offset / resolution.x;
+
+    return textureSample(source, source_sampler, jittered);
+}
+
+#ifdef EFFECT_MATERIAL
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return finish(in.uv, effect(in.uv));
+}
+#else
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return effect(in.uv);
+}
+#endif
@@ -0,0 +1,44 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct Magnifier {
+    border_color: vec4<f32>,
+    center: vec2<f32>,
+    // As a fraction of the height of the image.
+    radius: f32,
+    zoom: f32,
+    // In pixels.
+    border_width: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> magnifier: Magnifier;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+
+    // Measured in heights of the image, so the circle stays round.
+    let aspect = vec2<f32>(resolution.x / resolution.y, 1.0);
+    let distance = length((in.uv - magnifier.center) * aspect);
+    let pixel = 1.0 / resolution.y;
+
+    let clean = textureSample(source, source_sampler, in.uv);
+    let magnified_uv = magnifier.center + (in.uv - magnifier.center) / max(magnifier.zoom, 0.0001);
+    let magnified = textureSample(source, source_sampler, magnified_uv);
+
+    // Edges are smoothed over a pixel.
+    let lens = 1.0 - smoothstep(magnifier.radius - pixel, magnifier.radius, distance);
+    var color = mix(clean, magnified, lens);
+
+    let outer = magnifier.radius + magnifier.border_width * pixel;
+    let border = (1.0 - lens) * (1.0 - smoothstep(outer - pixel, outer, distance));
+    let border_amount = select(0.0, border, magnifier.border_width > 0.0) * magnifier.border_color.a;
+    color = vec4<f32>(mix(color.rgb, magnifier.border_color.rgb, border_amount), clean.a);
+
+    return color;
+}
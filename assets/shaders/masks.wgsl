@@ -1,18 +1,49 @@
+#ifdef EFFECT_MATERIAL
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+#else
 #import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
 #import bevy_render::globals Globals
 
 @group(0) @binding(0)
-var t: texture_2d<f32>;
+var source: texture_2d<f32>;
 @group(0) @binding(1)
-var ts: sampler;
+var source_sampler: sampler;
 @group(0) @binding(2)
 var<uniform> globals: Globals;
+#endif
+
+#ifdef EFFECT_MATERIAL
+// Materials aren't drawn per eye.
+fn eye_side() -> f32 {
+    return 0.0;
+}
+#else
+// The eye the camera renders, see `StereoEye`.
+struct Eye {
+    index: u32,
+    // -1.0 for the left eye, 1.0 for the right eye, 0.0 without an eye.
+    side: f32,
+};
+@group(0) @binding(5)
+var<uniform> eye: Eye;
+
+fn eye_side() -> f32 {
+    return eye.side;
+}
+#endif
 
 struct Mask {
     strength: f32,
     fade: f32,
+    nasal_offset: f32,
+    // The color the masked parts fade to.
+    color: vec4<f32>,
 };
+#ifdef EFFECT_MATERIAL
+@group(1) @binding(2)
+#else
 @group(1) @binding(0)
+#endif
 var<uniform> mask: Mask;
 
 #ifdef SQUARE
@@ -57,6 +88,9 @@ fn vignette(uv: vec2<f32>) -> f32 {
     // Strategy is to use the UV distance from the screen's center.
     var uv_centered = uv * 2. - 1.;
 
+    // The nose is to the right of the left eye, and to the left of the right eye.
+    uv_centered.x += eye_side() * mask.nasal_offset * 2.;
+
     // By scaling this we can adjust how bright/dark the vignette is.
     uv_centered *= mask.strength;
 
@@ -71,19 +105,38 @@ fn vignette(uv: vec2<f32>) -> f32 {
 }
 #endif
 
-@fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let sample = textureSample(t, ts, in.uv);
+// The effect at `uv`, shared by post processing and materials.
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let sample = textureSample(source, source_sampler, uv);
 
     #ifdef SQUARE
-    let result = square(in.uv);
+    let result = square(uv);
     #endif
     #ifdef CRT
-    let result = crt(in.uv);
+    let result = crt(uv);
     #endif
     #ifdef VIGNETTE
-    let result = vignette(in.uv);
+    let result = vignette(uv);
     #endif
 
-    return vec4<f32>(sample.rgb * saturate(result + mask.fade), 1.0);
+#ifdef EFFECT_MATERIAL
+    // Sprites keep their shape.
+    let alpha = sample.a;
+#else
+    let alpha = 1.0;
+#endif
+
+    return vec4<f32>(mix(mask.color.rgb, sample.rgb, saturate(result + mask.fade)), alpha);
+}
+
+#ifdef EFFECT_MATERIAL
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return finish(in.uv, effect(in.uv));
 }
+#else
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return effect(in.uv);
+}
+#endif
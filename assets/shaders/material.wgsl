@@ -0,0 +1,37 @@
+// The shader interface of effects drawn as a `Material2d`, see `EffectMaterial2d`.
+//
+// Effect shaders support both post processing and materials: they import the bindings from here
+// with the `EFFECT_MATERIAL` shader def, and declare the post processing bindings without it.
+#define_import_path bevy_vfx_bag::material
+
+#import bevy_render::globals Globals
+
+// The globals of the 2d mesh view bindings.
+@group(0) @binding(1)
+var<uniform> globals: Globals;
+
+// The image of the material, and its sampler.
+@group(1) @binding(0)
+var source: texture_2d<f32>;
+@group(1) @binding(1)
+var source_sampler: sampler;
+
+// The uniform of the effect is at @group(1) @binding(2).
+
+struct EffectMaterial {
+    fade: f32,
+};
+@group(1) @binding(3)
+var<uniform> material: EffectMaterial;
+
+// The parts of the output of Bevy's 2d mesh vertex shader effects read.
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+// Fades the color of the effect at `uv` back to the image.
+fn finish(uv: vec2<f32>, color: vec4<f32>) -> vec4<f32> {
+    let image = textureSample(source, source_sampler, uv);
+    return mix(color, image, saturate(material.fade));
+}
@@ -0,0 +1,83 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct MotionBlur {
+    inverse_view_projection: mat4x4<f32>,
+    previous_view_projection: mat4x4<f32>,
+    shutter: f32,
+    max_blur: f32,
+    max_samples: u32,
+    motion_vectors: u32,
+};
+@group(1) @binding(0)
+var<uniform> motion_blur: MotionBlur;
+@group(1) @binding(1)
+var motion_vectors: texture_2d<f32>;
+
+// How far the point seen at the given fragment moved on screen since the previous frame, in UV space,
+// assuming only the camera moved.
+fn camera_velocity(uv: vec2<f32>, d: f32) -> vec2<f32> {
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+
+    // Don't divide by w, so points infinitely far away (a depth of zero) still work.
+    let world = motion_blur.inverse_view_projection * ndc;
+    let previous_clip = motion_blur.previous_view_projection * world;
+    if previous_clip.w <= 0.0 {
+        return vec2<f32>(0.0);
+    }
+
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+    let previous_uv = vec2<f32>(previous_ndc.x * 0.5 + 0.5, 0.5 - previous_ndc.y * 0.5);
+
+    return uv - previous_uv;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Found from the UV, which still matches the prepass when the blur is drawn at a lower resolution.
+    let size = vec2<i32>(textureDimensions(depth));
+    let texel = min(vec2<i32>(in.uv * vec2<f32>(size)), size - 1);
+    let d = textureLoad(depth, texel, 0);
+
+    var velocity: vec2<f32>;
+    if motion_blur.motion_vectors != 0u && d > 0.0 {
+        // The prepass stores the motion of each mesh as the current minus the previous UV,
+        // which includes moving objects and not just the camera.
+        velocity = textureLoad(motion_vectors, texel, 0).xy;
+    } else {
+        velocity = camera_velocity(in.uv, d);
+    }
+
+    // The shutter is open for part of the frame, so only that part of the motion is blurred.
+    velocity *= motion_blur.shutter;
+
+    // Limit the blur, so a sudden jump (e.g. a camera cut) doesn't smear the whole view.
+    let speed = length(velocity);
+    if speed > motion_blur.max_blur {
+        velocity *= motion_blur.max_blur / speed;
+    }
+
+    let pixels = length(velocity * vec2<f32>(textureDimensions(t)));
+    let samples = clamp(u32(ceil(pixels)), 1u, max(motion_blur.max_samples, 1u));
+    if samples == 1u {
+        return textureSampleLevel(t, ts, in.uv, 0.0);
+    }
+
+    // Average the image along the motion, centered on the fragment.
+    var color = vec4<f32>(0.0);
+    for (var i = 0u; i < samples; i += 1u) {
+        let offset = f32(i) / f32(samples - 1u) - 0.5;
+        color += textureSampleLevel(t, ts, in.uv + velocity * offset, 0.0);
+    }
+
+    return color / f32(samples);
+}
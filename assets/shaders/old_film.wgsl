@@ -0,0 +1,112 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct OldFilm {
+    sepia: f32,
+    scratches: f32,
+    dust: f32,
+    flicker: f32,
+    // In pixels.
+    jitter: f32,
+    // Frames per second.
+    frame_rate: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> old_film: OldFilm;
+
+// How many scratches there can be at once.
+const SCRATCHES: i32 = 4;
+
+// The size of the cells which hold at most one speck of dust each, in pixels.
+const DUST_CELL: f32 = 48.0;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// How much of a scratch covers the given pixel, from 0.0 to 1.0.
+fn scratch(pixel: vec2<f32>, resolution: vec2<f32>, frame: f32, index: f32) -> f32 {
+    // Scratches stay in place for a while, wandering slowly, then move elsewhere.
+    let era = floor(globals.time * 0.5 + index * 0.37) % 1024.0;
+    if hash(vec2<f32>(era, index)) >= old_film.scratches {
+        return 0.0;
+    }
+
+    let wobble = sin(globals.time * (1.0 + index) + index * 5.0) * 0.004;
+    let x = (hash(vec2<f32>(era, index + 10.0)) + wobble) * resolution.x;
+    let line = 1.0 - smoothstep(0.0, 1.2, abs(pixel.x - x));
+
+    // They skip now and then along their length, and some frames they are fainter.
+    let along = hash(vec2<f32>(floor(pixel.y / 40.0), era + index * 3.0));
+    let faint = 0.5 + 0.5 * hash(vec2<f32>(frame, index + 20.0));
+    return line * step(0.15, along) * faint;
+}
+
+// How much dust covers the given pixel, from 0.0 to 1.0.
+fn dust(pixel: vec2<f32>, frame: f32) -> f32 {
+    let cell = floor(pixel / DUST_CELL);
+    let seed = cell + vec2<f32>(frame * 7.0, frame * 13.0);
+    if hash(seed) >= old_film.dust * 0.15 {
+        return 0.0;
+    }
+
+    // Kept away from the edges of the cell, so the speck isn't cut off.
+    let center = (cell + 0.2 + 0.6 * vec2<f32>(hash(seed + 1.0), hash(seed + 2.0))) * DUST_CELL;
+    let radius = 0.8 + 2.5 * hash(seed + 3.0);
+
+    // Some of the dust is hair: stretched out along a random direction.
+    let angle = hash(seed + 4.0) * 6.28318;
+    let direction = vec2<f32>(cos(angle), sin(angle));
+    let stretch = select(1.0, 6.0, hash(seed + 5.0) < 0.2);
+    let offset = pixel - center;
+    let along = dot(offset, direction) / stretch;
+    let across = dot(offset, vec2<f32>(-direction.y, direction.x)) * select(1.0, 2.0, stretch > 1.0);
+
+    return 1.0 - smoothstep(radius - 1.0, radius, length(vec2<f32>(along, across)));
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+
+    // Wrapped, so the hash stays precise.
+    let frame = floor(globals.time * max(old_film.frame_rate, 0.0)) % 1024.0;
+
+    // The film sits a little differently in each frame, mostly up and down.
+    let jitter = vec2<f32>(hash(vec2<f32>(frame, 1.0)) - 0.5, hash(vec2<f32>(frame, 2.0)) - 0.5);
+    let uv = in.uv + jitter * vec2<f32>(0.5, 1.0) * old_film.jitter * 2.0 / resolution;
+    let pixel = in.uv * resolution;
+
+    let sample = textureSample(source, source_sampler, uv);
+    var color = sample.rgb;
+
+    let sepia = luminance(color) * vec3<f32>(1.07, 0.74, 0.43);
+    color = mix(color, sepia, saturate(old_film.sepia));
+
+    let flicker = (hash(vec2<f32>(frame, 3.0)) * 2.0 - 1.0) * old_film.flicker;
+    color *= 1.0 + flicker;
+
+    // Scratches through the emulsion let light through, so they are bright.
+    var scratches = 0.0;
+    for (var i = 0; i < SCRATCHES; i += 1) {
+        scratches = max(scratches, scratch(pixel, resolution, frame, f32(i)));
+    }
+    color = mix(color, vec3<f32>(0.9, 0.88, 0.8), scratches * 0.6);
+
+    // Dust on the print blocks the light, so it is dark.
+    color *= 1.0 - dust(pixel, frame) * 0.85;
+
+    return vec4<f32>(color, sample.a);
+}
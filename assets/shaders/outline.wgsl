@@ -0,0 +1,92 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+@group(0) @binding(4)
+var normals: texture_2d<f32>;
+
+struct Outline {
+    color: vec4<f32>,
+    width: f32,
+    depth_threshold: f32,
+    normal_threshold: f32,
+    inverse_projection: mat4x4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> outline: Outline;
+
+fn clamp_position(position: vec2<f32>) -> vec2<i32> {
+    let size = vec2<i32>(textureDimensions(depth)) - 1;
+    return clamp(vec2<i32>(position), vec2<i32>(0), size);
+}
+
+// The view space depth (distance along the view direction) at the given pixel.
+fn view_depth(position: vec2<f32>) -> f32 {
+    let d = textureLoad(depth, clamp_position(position), 0);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return 1e6;
+    }
+
+    let view = outline.inverse_projection * vec4<f32>(0.0, 0.0, d, 1.0);
+    return -view.z / view.w;
+}
+
+fn normal(position: vec2<f32>) -> vec3<f32> {
+    return textureLoad(normals, clamp_position(position), 0).xyz * 2.0 - 1.0;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(t, ts, in.uv);
+    let w = outline.width;
+
+    // The pixel in the prepass, which stays at full resolution if the outline is drawn at a lower one.
+    let pixel = in.uv * vec2<f32>(textureDimensions(depth));
+
+    // Sobel kernels, sampled in a 3x3 grid spread out by the width.
+    var depth_x = 0.0;
+    var depth_y = 0.0;
+    var normal_x = vec3<f32>(0.0);
+    var normal_y = vec3<f32>(0.0);
+
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let offset = vec2<f32>(f32(x), f32(y));
+
+            // The center row/column weighs double.
+            let kx = f32(x) * (2.0 - abs(f32(y)));
+            let ky = f32(y) * (2.0 - abs(f32(x)));
+
+            let p = pixel + offset * w;
+            let d = view_depth(p);
+            let n = normal(p);
+
+            depth_x += d * kx;
+            depth_y += d * ky;
+            normal_x += n * kx;
+            normal_y += n * ky;
+        }
+    }
+
+    // Depth differences grow with distance, so compare relative to the center depth.
+    let center_depth = view_depth(pixel);
+    let depth_edge = length(vec2<f32>(depth_x, depth_y)) / max(center_depth, 0.0001);
+    let normal_edge = sqrt(dot(normal_x, normal_x) + dot(normal_y, normal_y));
+
+    let edge = max(
+        step(outline.depth_threshold, depth_edge),
+        step(outline.normal_threshold, normal_edge),
+    );
+
+    return vec4<f32>(mix(sample.rgb, outline.color.rgb, edge * outline.color.a), 1.0);
+}
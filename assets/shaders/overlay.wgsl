@@ -0,0 +1,25 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// The image of the overlay camera, after its effects.
+@group(0) @binding(0)
+var overlay: texture_2d<f32>;
+@group(0) @binding(1)
+var overlay_sampler: sampler;
+// The image of the overlay camera as its main pass left it, before its effects.
+@group(0) @binding(2)
+var coverage: texture_2d<f32>;
+
+// Blended over the cameras below as premultiplied alpha,
+// since the main pass draws onto transparent black.
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(overlay, overlay_sampler, in.uv);
+
+    // Many effects make the image opaque, which would hide the cameras below everywhere.
+    // Where it is opaque, what the overlay drew before the effects covers them instead.
+    // Effects keeping the alpha, e.g. distortions, move the coverage along with the image.
+    let before = textureSample(coverage, overlay_sampler, in.uv).a;
+    let alpha = select(color.a, before, color.a >= 1.0);
+
+    return vec4<f32>(color.rgb, alpha);
+}
@@ -0,0 +1,73 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct PaletteSwap {
+    // From 0.0 to 1.0.
+    dither: f32,
+    // In pixels.
+    dither_scale: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> palette_swap: PaletteSwap;
+@group(1) @binding(1)
+var palette: texture_2d<f32>;
+@group(1) @binding(2)
+var palette_sampler: sampler;
+
+const MAX_COLORS: u32 = 256u;
+
+// The threshold of a pixel in a 4x4 Bayer matrix, see the dither shader.
+fn threshold(pixel: vec2<u32>) -> f32 {
+    var value = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let x = (pixel.x >> i) & 1u;
+        let y = (pixel.y >> i) & 1u;
+        value = (value << 2u) | ((x ^ y) << 1u) | y;
+    }
+
+    return (f32(value) + 0.5) / 16.0;
+}
+
+// Roughly perceptual, so nearest colors are found the way they look rather than by their values.
+fn perceptual(color: vec3<f32>) -> vec3<f32> {
+    return sqrt(max(color, vec3<f32>(0.0))) * vec3<f32>(0.5, 0.8, 0.3);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+    var wanted = perceptual(sample.rgb);
+
+    if palette_swap.dither > 0.0 {
+        let pixel = vec2<u32>(in.position.xy / max(palette_swap.dither_scale, 1.0));
+        wanted += (threshold(pixel) - 0.5) * 0.25 * palette_swap.dither;
+    }
+
+    let size = textureDimensions(palette);
+    let count = min(size.x * size.y, MAX_COLORS);
+
+    var nearest = sample.rgb;
+    var nearest_distance = 1e9;
+    for (var i = 0u; i < count; i++) {
+        let color = textureLoad(palette, vec2<u32>(i % size.x, i / size.x), 0);
+
+        // Transparent texels pad the palette, or stand in while it loads.
+        if color.a <= 0.0 {
+            continue;
+        }
+
+        let delta = perceptual(color.rgb) - wanted;
+        let distance = dot(delta, delta);
+        if distance < nearest_distance {
+            nearest = color.rgb;
+            nearest_distance = distance;
+        }
+    }
+
+    return vec4<f32>(nearest, sample.a);
+}
@@ -1,30 +1,88 @@
+#ifdef EFFECT_MATERIAL
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+#else
 #import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
 #import bevy_render::globals Globals
 
 @group(0) @binding(0)
-var t: texture_2d<f32>;
+var source: texture_2d<f32>;
 @group(0) @binding(1)
-var ts: sampler;
+var source_sampler: sampler;
 @group(0) @binding(2)
 var<uniform> globals: Globals;
+#endif
 
 struct Pixelate {
     block_size: f32,
 };
+#ifdef EFFECT_MATERIAL
+@group(1) @binding(2)
+#else
 @group(1) @binding(0)
+#endif
 var<uniform> pixelate: Pixelate;
 
-@fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let resolution = vec2<f32>(textureDimensions(t));
+#ifdef DOMINANT
+// Taps per axis within each block.
+const TAPS: u32 = 4u;
+
+// The color most like the others in the block, out of a grid of taps.
+// Every pixel in the block takes the same taps, so they all agree on the color.
+fn dominant(block_origin: vec2<f32>, block_uv_size: vec2<f32>) -> vec4<f32> {
+    var taps: array<vec4<f32>, 16>;
+    for (var i = 0u; i < TAPS * TAPS; i++) {
+        let offset = (vec2<f32>(f32(i % TAPS), f32(i / TAPS)) + 0.5) / f32(TAPS);
+        taps[i] = textureSampleLevel(source, source_sampler, block_origin + offset * block_uv_size, 0.0);
+    }
+
+    // The tap with the least distance to all the others (the medoid).
+    // Thin features only cover a few taps, so they don't win and don't flicker.
+    var best = taps[0];
+    var best_distance = 1e20;
+    for (var i = 0u; i < TAPS * TAPS; i++) {
+        var distance = 0.0;
+        for (var j = 0u; j < TAPS * TAPS; j++) {
+            let difference = taps[i].rgb - taps[j].rgb;
+            distance += dot(difference, difference);
+        }
+
+        if distance < best_distance {
+            best_distance = distance;
+            best = taps[i];
+        }
+    }
+
+    return best;
+}
+#endif
+
+// The effect at `uv`, shared by post processing and materials.
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
 
     let width_height_over_block_size = resolution / max(1.0, pixelate.block_size);
 
-    var uv = in.uv + 0.5;
-    uv *= width_height_over_block_size;
-    uv = floor(uv);
-    uv /= width_height_over_block_size;
-    uv -= 0.5;
+    var block_uv = uv + 0.5;
+    block_uv *= width_height_over_block_size;
+    block_uv = floor(block_uv);
+    block_uv /= width_height_over_block_size;
+    block_uv -= 0.5;
+
+#ifdef DOMINANT
+    return dominant(block_uv, 1.0 / width_height_over_block_size);
+#else
+    return textureSample(source, source_sampler, block_uv);
+#endif
+}
 
-    return textureSample(t, ts, uv); 
-}
\ No newline at end of file
+#ifdef EFFECT_MATERIAL
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return finish(in.uv, effect(in.uv));
+}
+#else
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return effect(in.uv);
+}
+#endif
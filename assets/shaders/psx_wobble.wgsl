@@ -0,0 +1,64 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct PsxWobble {
+    // All in pixels.
+    snap: f32,
+    wobble: f32,
+    cell_size: f32,
+    // Jumps per second.
+    rate: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> psx: PsxWobble;
+
+fn hash2(p: vec2<f32>) -> vec2<f32> {
+    let q = vec2<f32>(dot(p, vec2<f32>(127.1, 311.7)), dot(p, vec2<f32>(269.5, 183.3)));
+    return fract(sin(q) * 43758.5453);
+}
+
+// How far the corner of the grid of triangles is moved at the given jump, in pixels.
+fn corner_offset(corner: vec2<f32>, jump: f32) -> vec2<f32> {
+    return (hash2(corner + jump * vec2<f32>(17.0, 59.0)) * 2.0 - 1.0) * psx.wobble;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(source));
+    let pixel = in.uv * size;
+
+    // The corners hold still between jumps, like vertices snapped to whole pixels.
+    let jump = floor(globals.time * psx.rate);
+
+    // Each cell is split along its diagonal into two triangles, and the offsets of the corners
+    // are interpolated linearly across the triangle, like a texture without perspective correction.
+    let grid = pixel / psx.cell_size;
+    let cell = floor(grid);
+    let f = fract(grid);
+
+    let o00 = corner_offset(cell, jump);
+    let o11 = corner_offset(cell + vec2<f32>(1.0, 1.0), jump);
+
+    var offset: vec2<f32>;
+    if f.x > f.y {
+        let o10 = corner_offset(cell + vec2<f32>(1.0, 0.0), jump);
+        offset = o00 * (1.0 - f.x) + o10 * (f.x - f.y) + o11 * f.y;
+    } else {
+        let o01 = corner_offset(cell + vec2<f32>(0.0, 1.0), jump);
+        offset = o00 * (1.0 - f.y) + o01 * (f.y - f.x) + o11 * f.x;
+    }
+
+    // Read from the middle of the snapped block, so the image moves in coarse steps.
+    let snapped = (floor((pixel + offset) / psx.snap) + 0.5) * psx.snap;
+    let uv = clamp(snapped / size, vec2<f32>(0.0), vec2<f32>(1.0));
+
+    return textureSample(source, source_sampler, uv);
+}
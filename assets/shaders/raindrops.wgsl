@@ -11,7 +11,14 @@ var<uniform> globals: Globals;
 struct Raindrops {
     time_scaling: f32,
     intensity: f32,
-    zoom: f32
+    zoom: f32,
+    wetness: f32,
+    // How far the glass has scrolled against the scene, in heights of the view.
+    scroll: vec2<f32>,
+    // Non-zero for 2d scenes, where the refraction keeps its shape regardless of the aspect ratio.
+    flat: u32,
+    // How much the glass bends the image, from 0.0 (on the scene) to 1.0 (on the camera).
+    parallax: f32,
 };
 
 @group(1) @binding(0)
@@ -39,7 +46,7 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     let resolution = vec2<f32>(textureDimensions(t));
     let uv_aspect_fixed = vec2<f32>(in.uv.x * resolution.x / resolution.y, in.uv.y);
 
-    let t_raindrops = textureSample(t_rain, ts_rain, uv_aspect_fixed * raindrops.zoom).rgba;
+    let t_raindrops = textureSample(t_rain, ts_rain, (uv_aspect_fixed + raindrops.scroll) * raindrops.zoom).rgba;
     let t_raindrops_rga = remap_raindrops_rga(t_raindrops.rga);
 
     // Really the alpha channel of the original texture.
@@ -55,9 +62,16 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     // Using (-1, 1) range offsets in the droplet positions
     // means the droplets would span the entire scene.
     // Thus scale it far down (by default).
-    let offset = t_raindrops_rga.rg * raindrops.intensity;
+    var offset = t_raindrops_rga.rg * raindrops.intensity * raindrops.parallax;
+    if raindrops.flat != 0u {
+        offset.x *= resolution.y / resolution.x;
+    }
 
-    let mask = (animation(t_raindrops.b) * mask_anim) + mask_static;
+    // Drops appear in the order of their animation offset as the camera gets wetter,
+    // and disappear in reverse as it dries off.
+    let coverage = 1. - smoothstep(raindrops.wetness * 1.1 - 0.1, raindrops.wetness * 1.1, t_raindrops.b);
+
+    let mask = ((animation(t_raindrops.b) * mask_anim) + mask_static) * coverage;
     let masked_norms = mask * offset;
 
     return vec4<f32>(textureSample(t, ts, in.uv + masked_norms).rgb, 1.0);
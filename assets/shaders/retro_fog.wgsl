@@ -0,0 +1,82 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct RetroFog {
+    color: vec4<f32>,
+    start: f32,
+    end: f32,
+    // Zero for a smooth gradient.
+    bands: u32,
+    // Non-zero to dither between bands.
+    dither: u32,
+    inverse_projection: mat4x4<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> retro_fog: RetroFog;
+
+// The depth at the given UV, see the fog shader.
+fn load_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth));
+    return textureLoad(depth, vec2<i32>(min(uv * size, size - 1.0)), 0);
+}
+
+// The distance from the camera to what is seen at the given fragment.
+fn view_distance(uv: vec2<f32>) -> f32 {
+    let d = load_depth(uv);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return 3.40282e+38;
+    }
+
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let view = retro_fog.inverse_projection * ndc;
+
+    return length(view.xyz / view.w);
+}
+
+// The threshold of a pixel in a 4x4 Bayer matrix, see the dither shader.
+fn threshold(pixel: vec2<u32>) -> f32 {
+    var value = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let x = (pixel.x >> i) & 1u;
+        let y = (pixel.y >> i) & 1u;
+        value = (value << 2u) | ((x ^ y) << 1u) | y;
+    }
+
+    return (f32(value) + 0.5) / 16.0;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    let distance = view_distance(in.uv) - retro_fog.start;
+    var amount = saturate(distance / max(retro_fog.end - retro_fog.start, 0.0001));
+
+    if retro_fog.bands > 0u {
+        let bands = f32(retro_fog.bands);
+        let level = amount * bands;
+
+        var band = floor(level + 0.5);
+        if retro_fog.dither != 0u {
+            // Picks the band above for a share of the pixels which grows towards it.
+            band = floor(level);
+            if level - band > threshold(vec2<u32>(in.position.xy)) {
+                band += 1.0;
+            }
+        }
+
+        amount = band / bands;
+    }
+
+    return vec4<f32>(mix(sample.rgb, retro_fog.color.rgb, amount * retro_fog.color.a), sample.a);
+}
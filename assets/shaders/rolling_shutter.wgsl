@@ -0,0 +1,78 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct RollingShutter {
+    inverse_view_projection: mat4x4<f32>,
+    previous_view_projection: mat4x4<f32>,
+    readout: f32,
+    skew: f32,
+    max_skew: f32,
+    bands: u32,
+};
+@group(1) @binding(0)
+var<uniform> rolling_shutter: RollingShutter;
+@group(1) @binding(1)
+var history: texture_2d<f32>;
+@group(1) @binding(2)
+var history_sampler: sampler;
+
+// How far the view at the given fragment turned on screen since the previous frame, in UV space.
+// Points infinitely far away (a depth of zero) only move when the camera rotates.
+fn rotation_velocity(uv: vec2<f32>) -> vec2<f32> {
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+
+    // Don't divide by w, since the point is infinitely far away.
+    let direction = rolling_shutter.inverse_view_projection * ndc;
+    let previous_clip = rolling_shutter.previous_view_projection * direction;
+    if previous_clip.w <= 0.0 {
+        return vec2<f32>(0.0);
+    }
+
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+    let previous_uv = vec2<f32>(previous_ndc.x * 0.5 + 0.5, 0.5 - previous_ndc.y * 0.5);
+
+    return uv - previous_uv;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    var row = in.uv.y;
+    if rolling_shutter.bands > 0u {
+        let bands = f32(rolling_shutter.bands);
+        row = (floor(row * bands) + 0.5) / bands;
+    }
+
+    // Rows further down are read out later, and the bottom row is seen as of the current frame.
+    // The age is how many frames ago the row was read out.
+    let age = rolling_shutter.readout * (1.0 - clamp(row, 0.0, 1.0));
+
+    // Limit the skew, so a sudden jump (e.g. a camera cut) doesn't tear the whole view.
+    var velocity = rotation_velocity(in.uv) * rolling_shutter.skew;
+    let speed = length(velocity);
+    if speed > rolling_shutter.max_skew {
+        velocity *= rolling_shutter.max_skew / speed;
+    }
+
+    // What the row showed back then is where the view has turned to since,
+    // both in the current frame and the previous one.
+    let current_uv = in.uv + velocity * age;
+    let previous_uv = in.uv - velocity * (1.0 - age);
+
+    let current = textureSample(t, ts, current_uv);
+    var previous = textureSample(history, history_sampler, previous_uv);
+
+    // Nothing to read from outside the previous frame.
+    if any(previous_uv < vec2<f32>(0.0)) || any(previous_uv > vec2<f32>(1.0)) {
+        previous = current;
+    }
+
+    // Rows read out closer to the previous frame take more of it.
+    return mix(current, previous, age);
+}
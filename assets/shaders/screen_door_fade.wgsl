@@ -0,0 +1,41 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct ScreenDoorFade {
+    // Linear.
+    color: vec4<f32>,
+    progress: f32,
+    // Pixels per cell of the pattern, at least 1.
+    scale: u32,
+};
+
+@group(1) @binding(0)
+var<uniform> screen_door_fade: ScreenDoorFade;
+
+// The threshold of a cell in a 4x4 Bayer matrix, see the dither shader.
+fn threshold(cell: vec2<u32>) -> f32 {
+    var value = 0u;
+    for (var i = 0u; i < 2u; i++) {
+        let x = (cell.x >> i) & 1u;
+        let y = (cell.y >> i) & 1u;
+        value = (value << 2u) | ((x ^ y) << 1u) | y;
+    }
+
+    return (f32(value) + 0.5) / 16.0;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    // Each pixel is either covered or not, never blended, so the picture keeps its hard edges.
+    let cell = vec2<u32>(in.position.xy) / screen_door_fade.scale;
+    let covered = threshold(cell) < screen_door_fade.progress;
+
+    let color = mix(sample.rgb, screen_door_fade.color.rgb, screen_door_fade.color.a);
+    return vec4<f32>(select(sample.rgb, color, covered), sample.a);
+}
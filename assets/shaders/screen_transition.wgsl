@@ -0,0 +1,61 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct ScreenTransition {
+    // Linear.
+    color: vec4<f32>,
+    // Of the iris, in UV space.
+    center: vec2<f32>,
+    // Of the wipe, normalized.
+    direction: vec2<f32>,
+    progress: f32,
+    softness: f32,
+    kind: u32,
+};
+
+@group(1) @binding(0)
+var<uniform> transition: ScreenTransition;
+
+const FADE: u32 = 0u;
+const IRIS: u32 = 1u;
+const WIPE: u32 = 2u;
+
+// When the pixel gets covered, from 0.0 (first) to 1.0 (last).
+fn cover_order(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(source));
+    // Keeps the iris round and the wipe straight on screens which aren't square.
+    let aspect = vec2<f32>(size.x / size.y, 1.0);
+
+    if transition.kind == IRIS {
+        // The farthest corner is covered first.
+        let farthest = max(transition.center, 1.0 - transition.center) * aspect;
+        return 1.0 - length((uv - transition.center) * aspect) / max(length(farthest), 0.0001);
+    }
+
+    // A wipe covers the corner it moves away from first.
+    let direction = normalize(transition.direction * aspect);
+    let extent = dot(abs(direction), aspect * 0.5);
+    return (dot((uv - 0.5) * aspect, direction) + extent) / (2.0 * extent);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    var coverage = transition.progress;
+    if transition.kind != FADE {
+        // The edge starts a softness short of the first pixel, so nothing is covered at 0.0,
+        // and everything is at 1.0.
+        let softness = max(transition.softness, 0.0001);
+        let edge = transition.progress * (1.0 + softness) - softness;
+        coverage = 1.0 - smoothstep(edge, edge + softness, cover_order(in.uv));
+    }
+
+    let color = mix(sample.rgb, transition.color.rgb, coverage * transition.color.a);
+
+    return vec4<f32>(color, sample.a);
+}
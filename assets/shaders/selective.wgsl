@@ -0,0 +1,6 @@
+// Marks where an entity with a `SelectiveEffect` is in the effect mask.
+// The pipeline only writes the channel of the entity, so all channels are written here.
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
@@ -0,0 +1,49 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct Sharpen {
+    strength: f32,
+    radius: f32,
+};
+@group(1) @binding(0)
+var<uniform> sharpen: Sharpen;
+
+fn s(uv: vec2<f32>) -> vec3<f32> {
+    return textureSample(t, ts, uv).rgb;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // The radius is given in pixels, so convert it to a step in UV space.
+    let texel = sharpen.radius / vec2<f32>(textureDimensions(t));
+
+    let original = s(in.uv);
+
+    // A small gaussian-ish kernel: the direct neighbours weigh twice
+    // as much as the diagonal ones.
+    let edges =
+        s(in.uv + vec2<f32>(texel.x, 0.0)) +
+        s(in.uv - vec2<f32>(texel.x, 0.0)) +
+        s(in.uv + vec2<f32>(0.0, texel.y)) +
+        s(in.uv - vec2<f32>(0.0, texel.y));
+    let corners =
+        s(in.uv + texel) +
+        s(in.uv - texel) +
+        s(in.uv + vec2<f32>(texel.x, -texel.y)) +
+        s(in.uv + vec2<f32>(-texel.x, texel.y));
+
+    let blurred = (original * 4.0 + edges * 2.0 + corners) / 16.0;
+
+    // Unsharp mask: add back the detail the blur removed.
+    let detail = original - blurred;
+    let output = max(original + detail * sharpen.strength, vec3<f32>(0.0));
+
+    return vec4<f32>(output, 1.0);
+}
@@ -0,0 +1,41 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct Shockwave {
+    center: vec2<f32>,
+    radius: f32,
+    thickness: f32,
+    speed: f32,
+    strength: f32,
+    max_radius: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> shockwave: Shockwave;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Distances are relative to the view height, so the ring stays round.
+    let resolution = vec2<f32>(textureDimensions(source));
+    let aspect = vec2<f32>(resolution.x / resolution.y, 1.0);
+
+    let offset = (in.uv - shockwave.center) * aspect;
+    let distance = length(offset);
+
+    // Position across the ring, from -1.0 on the inner edge to 1.0 on the outer edge.
+    let ring = (distance - shockwave.radius) / max(shockwave.thickness, 0.0001);
+
+    // Bulges the image outwards in the middle of the ring, and is zero at its edges and outside.
+    let inside = abs(ring) < 1.0 && distance > 0.0;
+    let profile = select(0.0, ring * (1.0 - ring * ring), inside);
+    let fade = 1.0 - saturate(shockwave.radius / max(shockwave.max_radius, 0.0001));
+
+    let direction = offset / max(distance, 0.0001);
+    let displacement = direction * profile * shockwave.thickness * shockwave.strength * fade;
+
+    return textureSample(source, source_sampler, in.uv + displacement / aspect);
+}
@@ -0,0 +1,119 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+@group(0) @binding(4)
+var normals: texture_2d<f32>;
+
+struct Sketch {
+    ink: vec4<f32>,
+    paper: vec4<f32>,
+    edge_strength: f32,
+    // In pixels when screen aligned, world units when world aligned.
+    hatch_scale: f32,
+    // Non-zero to fix the strokes to the surfaces of the scene.
+    world_aligned: u32,
+    // How much the paper takes on the colors of the image.
+    color: f32,
+    inverse_view_projection: mat4x4<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> sketch: Sketch;
+@group(1) @binding(1)
+var hatching: texture_2d<f32>;
+@group(1) @binding(2)
+var hatching_sampler: sampler;
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// The stroke layers of the hatching at the given pixel, fixed to the screen.
+fn screen_hatching(pixel: vec2<f32>) -> vec4<f32> {
+    // Sampled without derivatives, as this is also used where world aligned strokes are missing.
+    return textureSampleLevel(hatching, hatching_sampler, pixel / max(sketch.hatch_scale, 1.0), 0.0);
+}
+
+// The stroke layers of the hatching at the given UV, fixed to the surfaces of the scene
+// by projecting the strokes from all three axes, weighted by how much the surface faces them.
+fn world_hatching(uv: vec2<f32>, pixel: vec2<f32>) -> vec4<f32> {
+    let size = vec2<f32>(textureDimensions(depth));
+    let texel = vec2<i32>(min(uv * size, size - 1.0));
+    let d = textureLoad(depth, texel, 0);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return screen_hatching(pixel);
+    }
+
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let world = sketch.inverse_view_projection * ndc;
+    let position = world.xyz / world.w / max(sketch.hatch_scale, 0.0001);
+
+    let normal = textureLoad(normals, texel, 0).xyz * 2.0 - 1.0;
+    var weights = pow(abs(normal), vec3<f32>(4.0));
+    weights /= max(weights.x + weights.y + weights.z, 0.0001);
+
+    return textureSampleLevel(hatching, hatching_sampler, position.zy, 0.0) * weights.x
+        + textureSampleLevel(hatching, hatching_sampler, position.xz, 0.0) * weights.y
+        + textureSampleLevel(hatching, hatching_sampler, position.xy, 0.0) * weights.z;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = vec2<f32>(textureDimensions(source));
+    let texel = 1.0 / resolution;
+    let pixel = in.uv * resolution;
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    // Edges, via a Sobel filter over the luminance.
+    var edge_x = 0.0;
+    var edge_y = 0.0;
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let offset = vec2<f32>(f32(x), f32(y));
+
+            // The center row/column weighs double.
+            let kx = f32(x) * (2.0 - abs(f32(y)));
+            let ky = f32(y) * (2.0 - abs(f32(x)));
+
+            let l = luminance(textureSample(source, source_sampler, in.uv + offset * texel).rgb);
+            edge_x += l * kx;
+            edge_y += l * ky;
+        }
+    }
+    let edge = saturate(length(vec2<f32>(edge_x, edge_y)) * sketch.edge_strength);
+
+    var strokes: vec4<f32>;
+    if sketch.world_aligned != 0u {
+        strokes = world_hatching(in.uv, pixel);
+    } else {
+        strokes = screen_hatching(pixel);
+    }
+
+    // Each layer of strokes fades in as the tone gets darker, lightest layer first.
+    let tone = saturate(luminance(sample.rgb));
+    let layers = 1.0 - smoothstep(
+        vec4<f32>(0.75, 0.55, 0.35, 0.15),
+        vec4<f32>(0.85, 0.65, 0.45, 0.25),
+        vec4<f32>(tone)
+    );
+    let layered = strokes * layers;
+    let coverage = max(max(layered.r, layered.g), max(layered.b, layered.a));
+
+    // The hue of the image, without its tone, which the strokes already show.
+    let hue = sample.rgb / max(tone, 0.0001);
+    let paper = mix(sketch.paper.rgb, sketch.paper.rgb * hue, sketch.color);
+    let ink = saturate(max(coverage, edge)) * sketch.ink.a;
+
+    return vec4<f32>(mix(paper, sketch.ink.rgb, ink), sample.a);
+}
@@ -0,0 +1,63 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct SpeedLines {
+    // Linear.
+    color: vec4<f32>,
+    center: vec2<f32>,
+    density: f32,
+    length: f32,
+    thickness: f32,
+    intensity: f32,
+    flicker: f32,
+};
+@group(1) @binding(0)
+var<uniform> speed_lines: SpeedLines;
+
+const TAU: f32 = 6.28318530718;
+
+// Random in [0, 1) for a line, the same for the whole line.
+fn random(line: f32, frame: f32, seed: f32) -> f32 {
+    return fract(sin(dot(vec3<f32>(line, frame, seed), vec3<f32>(12.9898, 78.233, 37.719))) * 43758.5453);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(source, source_sampler, in.uv);
+
+    // Around the center, with square pixels.
+    let size = vec2<f32>(textureDimensions(source));
+    let aspect = vec2<f32>(size.x / size.y, 1.0);
+    let offset = (in.uv - speed_lines.center) * aspect;
+    let distance = length(offset);
+
+    // Each line has a slice of the circle around the center.
+    let position = (atan2(offset.y, offset.x) / TAU + 0.5) * speed_lines.density;
+    let line = floor(position);
+
+    // All lines are drawn again with new lengths and thicknesses this many times a second.
+    let frame = floor(globals.time * speed_lines.flicker);
+
+    // The lines reach in from beyond the farthest corner, some further than others.
+    let farthest = length(max(speed_lines.center, 1.0 - speed_lines.center) * aspect);
+    let reach = speed_lines.length * mix(0.4, 1.0, random(line, frame, 0.0));
+    let inner = farthest * (1.0 - reach);
+
+    // Thick at the edge and pointed at the tip.
+    let taper = saturate((distance - inner) / max(farthest - inner, 0.0001));
+    let width = speed_lines.thickness * mix(0.3, 1.0, random(line, frame, 1.0)) * taper;
+
+    let from_middle = abs(fract(position) - 0.5) * 2.0;
+    let smoothing = fwidth(position) * 2.0;
+    let coverage = 1.0 - smoothstep(width - smoothing, width, from_middle);
+
+    let amount = saturate(coverage * speed_lines.color.a * speed_lines.intensity);
+    return vec4<f32>(mix(sample.rgb, speed_lines.color.rgb, amount), sample.a);
+}
@@ -0,0 +1,43 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct StableRegions {
+    // Min and max corners of each region in UV space, as (min_x, min_y, max_x, max_y).
+    rects: array<vec4<f32>, 8>,
+    count: u32,
+    feather: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> stable_regions: StableRegions;
+@group(1) @binding(1)
+var input: texture_2d<f32>;
+@group(1) @binding(2)
+var input_sampler: sampler;
+
+// 1.0 inside the rect, fading to 0.0 over the feather outside of it.
+fn coverage(uv: vec2<f32>, rect: vec4<f32>, feather: vec2<f32>) -> f32 {
+    let outside = max(rect.xy - uv, uv - rect.zw);
+    let fade = saturate(1.0 - max(outside / feather, vec2<f32>(0.0)));
+    return fade.x * fade.y;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let distorted = textureSample(source, source_sampler, in.uv);
+    let clean = textureSample(input, input_sampler, in.uv);
+
+    // The feather is given in pixels.
+    let feather = max(stable_regions.feather, 0.0001) / vec2<f32>(textureDimensions(source));
+
+    var amount = 0.0;
+    for (var i = 0u; i < min(stable_regions.count, 8u); i++) {
+        amount = max(amount, coverage(in.uv, stable_regions.rects[i], feather));
+    }
+
+    return mix(distorted, clean, amount);
+}
@@ -0,0 +1,88 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+@group(0) @binding(3)
+var depth: texture_depth_2d;
+
+struct Underwater {
+    tint: vec4<f32>,
+    murk_color: vec4<f32>,
+    distortion_amplitude: f32,
+    distortion_frequency: f32,
+    distortion_speed: f32,
+    murk_density: f32,
+    caustics_intensity: f32,
+    caustics_scale: f32,
+    caustics_speed: f32,
+    inverse_projection: mat4x4<f32>,
+};
+@group(1) @binding(0)
+var<uniform> underwater: Underwater;
+
+// The distance from the camera to what is seen at the given pixel.
+fn view_distance(pixel: vec2<i32>, uv: vec2<f32>) -> f32 {
+    let d = textureLoad(depth, pixel, 0);
+
+    // Bevy uses reverse z with an infinite far plane,
+    // so a depth of zero means nothing was rendered here (e.g. the sky).
+    if d <= 0.0 {
+        return 3.40282e+38;
+    }
+
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, d, 1.0);
+    let view = underwater.inverse_projection * ndc;
+
+    return length(view.xyz / view.w);
+}
+
+// Bright, thin ridges where two warped wave patterns line up, like light refracted by waves.
+fn caustics(p: vec2<f32>, time: f32) -> f32 {
+    let warped = p + vec2<f32>(sin(p.y * 1.3 + time), cos(p.x * 1.1 - time * 0.7)) * 0.6;
+    let v = sin(warped.x * 1.7 + time) * sin(warped.y * 1.9 - time * 0.9);
+
+    return pow(1.0 - abs(v), 8.0);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let dimensions = vec2<f32>(textureDimensions(t));
+    let aspect = vec2<f32>(dimensions.x / dimensions.y, 1.0);
+
+    // Distortion.
+    let wave_time = globals.time * underwater.distortion_speed;
+    let wave_uv = in.uv * aspect * underwater.distortion_frequency;
+    let offset = vec2<f32>(
+        sin(wave_uv.y * 6.2832 + wave_time),
+        cos(wave_uv.x * 6.2832 + wave_time * 1.3),
+    ) * underwater.distortion_amplitude;
+    let uv = clamp(in.uv + offset, vec2<f32>(0.0), vec2<f32>(1.0));
+
+    var color = textureSample(t, ts, uv).rgb;
+
+    // Color cast.
+    color *= mix(vec3<f32>(1.0), underwater.tint.rgb, underwater.tint.a);
+
+    // Murk. Caustics fade out along with what they light up.
+    let pixel = vec2<i32>(uv * dimensions);
+    var murk = 0.0;
+    // The sky is infinitely far away, so check the density to avoid zero times infinity.
+    if underwater.murk_density > 0.0 {
+        let distance = view_distance(min(pixel, vec2<i32>(dimensions) - 1), uv);
+        murk = (1.0 - exp(-underwater.murk_density * distance)) * underwater.murk_color.a;
+    }
+
+    // Caustics.
+    let caustics_p = in.uv * aspect * underwater.caustics_scale;
+    let light = caustics(caustics_p, globals.time * underwater.caustics_speed);
+    color += light * underwater.caustics_intensity * (1.0 - murk);
+
+    color = mix(color, underwater.murk_color.rgb, murk);
+
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,63 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+struct Upsample {
+    // How many times smaller the effect was drawn, e.g. 2.0 at half resolution.
+    divisor: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> upsample: Upsample;
+// The output of the effect, at the lower resolution.
+@group(1) @binding(1)
+var scaled: texture_2d<f32>;
+@group(1) @binding(2)
+var scaled_sampler: sampler;
+
+#ifdef BICUBIC
+// Catmull-Rom filtering, in 9 bilinear taps instead of 16 point ones.
+fn sample_bicubic(uv: vec2<f32>, size: vec2<f32>) -> vec4<f32> {
+    let position = uv * size;
+    let center = floor(position - 0.5) + 0.5;
+    let f = position - center;
+
+    let w0 = f * (-0.5 + f * (1.0 - 0.5 * f));
+    let w1 = 1.0 + f * f * (-2.5 + 1.5 * f);
+    let w2 = f * (0.5 + f * (2.0 - 1.5 * f));
+    let w3 = f * f * (-0.5 + 0.5 * f);
+
+    // The middle two taps are merged into one, which the sampler blends.
+    let w12 = w1 + w2;
+    let uv0 = (center - 1.0) / size;
+    let uv12 = (center + w2 / w12) / size;
+    let uv3 = (center + 2.0) / size;
+
+    var color = vec4<f32>(0.0);
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv0.x, uv0.y), 0.0) * w0.x * w0.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv12.x, uv0.y), 0.0) * w12.x * w0.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv3.x, uv0.y), 0.0) * w3.x * w0.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv0.x, uv12.y), 0.0) * w0.x * w12.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv12.x, uv12.y), 0.0) * w12.x * w12.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv3.x, uv12.y), 0.0) * w3.x * w12.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv0.x, uv3.y), 0.0) * w0.x * w3.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv12.x, uv3.y), 0.0) * w12.x * w3.y;
+    color += textureSampleLevel(scaled, scaled_sampler, vec2<f32>(uv3.x, uv3.y), 0.0) * w3.x * w3.y;
+
+    // The curve overshoots at hard edges, which must not go negative.
+    return max(color, vec4<f32>(0.0));
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(scaled));
+
+    // Found from the position on screen, which also holds within a camera viewport.
+    let uv = in.position.xy / upsample.divisor / size;
+
+#ifdef BILINEAR
+    return textureSampleLevel(scaled, scaled_sampler, uv, 0.0);
+#endif
+#ifdef BICUBIC
+    return sample_bicubic(uv, size);
+#endif
+}
@@ -0,0 +1,68 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var t: texture_2d<f32>;
+@group(0) @binding(1)
+var ts: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct VisionMode {
+    tint: vec4<f32>,
+    gain: f32,
+    noise: f32,
+    vignette: f32,
+};
+@group(1) @binding(0)
+var<uniform> vision_mode: VisionMode;
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// Random in [0, 1], changing every frame.
+fn grain(uv: vec2<f32>) -> f32 {
+    let seed = uv + fract(globals.time * vec2<f32>(12.9898, 78.233));
+    return fract(sin(dot(seed, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+#ifdef THERMAL
+// Maps "temperature" in [0, 1] to the classic iron false-color gradient.
+fn thermal(heat: f32) -> vec3<f32> {
+    var stops = array<vec3<f32>, 6>(
+        vec3<f32>(0.0, 0.0, 0.0),
+        vec3<f32>(0.1, 0.0, 0.5),
+        vec3<f32>(0.6, 0.0, 0.6),
+        vec3<f32>(0.95, 0.2, 0.0),
+        vec3<f32>(1.0, 0.85, 0.0),
+        vec3<f32>(1.0, 1.0, 1.0),
+    );
+
+    let scaled = saturate(heat) * 5.0;
+    let index = min(u32(scaled), 4u);
+    return mix(stops[index], stops[index + 1u], scaled - f32(index));
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let sample = textureSample(t, ts, in.uv);
+    var lum = luminance(sample.rgb) * vision_mode.gain;
+
+    // Grain is centered on zero, so it doesn't brighten the image on average.
+    lum += (grain(in.uv) - 0.5) * vision_mode.noise;
+
+    #ifdef NIGHT_VISION
+    var color = saturate(lum) * vision_mode.tint.rgb;
+    #endif
+    #ifdef THERMAL
+    var color = thermal(lum);
+    #endif
+
+    let from_center = in.uv * 2. - 1.;
+    let vignette = 1. - saturate(dot(from_center, from_center) * 0.5 * vision_mode.vignette);
+    color *= vignette;
+
+    return vec4<f32>(color, 1.0);
+}
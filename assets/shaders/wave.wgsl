@@ -1,6 +1,10 @@
+#import bevy_pbr::utils PI
+
+#ifdef EFFECT_MATERIAL
+#import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+#else
 #import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
 #import bevy_render::globals Globals
-#import bevy_pbr::utils PI
 
 @group(0) @binding(0)
 var source: texture_2d<f32>;
@@ -8,6 +12,7 @@ var source: texture_2d<f32>;
 var source_sampler: sampler;
 @group(0) @binding(2)
 var<uniform> globals: Globals;
+#endif
 
 struct Wave {
     waves_x: f32,
@@ -20,18 +25,66 @@ struct Wave {
     amplitude_y: f32
 };
 
+#ifdef EFFECT_MATERIAL
+@group(1) @binding(2)
+#else
 @group(1) @binding(0)
+#endif
 var<uniform> wave: Wave;
 
-@fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let pi_uv = PI * in.uv;
+#ifdef NOISE
+fn hash(n: f32) -> f32 {
+    return fract(sin(n * 127.1) * 43758.5453);
+}
+
+// Smooth 1D value noise between 0.0 and 1.0.
+fn value_noise(x: f32) -> f32 {
+    let i = floor(x);
+    let f = fract(x);
+    return mix(hash(i), hash(i + 1.0), f * f * (3.0 - 2.0 * f));
+}
+#endif
+
+// The waveform of the selected profile, between -1.0 and 1.0, starting at 0.0 and rising like a sine.
+fn waveform(phase: f32) -> f32 {
+#ifdef TRIANGLE
+    let p = phase / (2.0 * PI);
+    return 4.0 * abs(fract(p - 0.25) - 0.5) - 1.0;
+#endif
+#ifdef SAWTOOTH
+    let p = phase / (2.0 * PI);
+    return 2.0 * fract(p + 0.5) - 1.0;
+#endif
+#ifdef NOISE
+    // The amplitude swells and fades irregularly along the wave.
+    return sin(phase) * mix(0.25, 1.5, value_noise(phase * 0.37));
+#endif
+#ifdef SINE
+    return sin(phase);
+#endif
+}
+
+// The effect at `uv`, shared by post processing and materials.
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let pi_uv = PI * uv;
     let pi_time = PI * globals.time;
 
-    let offset_x = sin((pi_uv.y * wave.waves_x) + (pi_time * wave.speed_x)) * wave.amplitude_x;
-    let offset_y = sin((pi_uv.x * wave.waves_y) + (pi_time * wave.speed_y)) * wave.amplitude_y;
+    let offset_x = waveform((pi_uv.y * wave.waves_x) + (pi_time * wave.speed_x)) * wave.amplitude_x;
+    let offset_y = waveform((pi_uv.x * wave.waves_y) + (pi_time * wave.speed_y)) * wave.amplitude_y;
 
-    let uv_displaced = vec2<f32>(in.uv.x + offset_x, in.uv.y + offset_y);
+    let uv_displaced = vec2<f32>(uv.x + offset_x, uv.y + offset_y);
 
     return textureSample(source, source_sampler, uv_displaced);
 }
+
+#ifdef EFFECT_MATERIAL
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return finish(in.uv, effect(in.uv));
+}
+#else
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return effect(in.uv);
+}
+#endif
@@ -0,0 +1,99 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct WeatherOverlay {
+    color: vec4<f32>,
+    density: f32,
+    speed: f32,
+    // In radians.
+    angle: f32,
+    opacity: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> weather: WeatherOverlay;
+
+// Layers of drops or flakes, from far (small, slow) to near (big, fast).
+const LAYERS: u32 = 3u;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+#ifdef RAIN
+// A streak per grid cell, falling down the cells.
+fn layer(p: vec2<f32>, depth: f32) -> f32 {
+    // Tall cells, so the streaks are long.
+    let scale = vec2<f32>(24.0, 4.0) * (1.5 - depth);
+    var q = p * scale;
+    q.y -= globals.time * weather.speed * (6.0 + depth * 4.0);
+
+    let cell = floor(q);
+    let f = fract(q);
+
+    let present = step(hash(cell), weather.density);
+    let x = 0.2 + 0.6 * hash(cell + 1.3);
+    let head = 0.3 + 0.5 * hash(cell + 2.7);
+
+    let width = 0.02 + 0.03 * depth;
+    let line = 1.0 - smoothstep(0.0, width, abs(f.x - x));
+
+    // Brightest at the head of the streak, fading up the tail.
+    let along = (f.y - (head - 0.3)) / 0.3;
+    let streak = select(0.0, along, along >= 0.0 && along <= 1.0);
+
+    return present * line * streak * (0.4 + 0.6 * depth);
+}
+#endif
+
+#ifdef SNOW
+// A flake per grid cell, drifting down and from side to side.
+fn layer(p: vec2<f32>, depth: f32) -> f32 {
+    let scale = 12.0 * (1.5 - depth);
+    var q = p * scale;
+    q.y -= globals.time * weather.speed * (0.6 + depth * 0.6);
+    q.x += sin(q.y * 0.7 + globals.time * 0.8 + depth * 10.0) * 0.25;
+
+    let cell = floor(q);
+    let f = fract(q);
+
+    let present = step(hash(cell), weather.density);
+    let center = vec2<f32>(0.2 + 0.6 * hash(cell + 1.3), 0.2 + 0.6 * hash(cell + 2.7));
+    let radius = (0.04 + 0.06 * hash(cell + 4.1)) * (0.5 + depth);
+
+    let flake = 1.0 - smoothstep(radius * 0.4, radius, length(f - center));
+
+    return present * flake * (0.5 + 0.5 * depth);
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let original = textureSample(source, source_sampler, in.uv);
+    let resolution = vec2<f32>(textureDimensions(source));
+
+    // Measured in heights of the image, so cells are square regardless of the aspect ratio.
+    let aspect = vec2<f32>(resolution.x / resolution.y, 1.0);
+
+    // Turned so the weather falls at an angle.
+    let c = cos(weather.angle);
+    let s = sin(weather.angle);
+    let p = mat2x2<f32>(vec2<f32>(c, s), vec2<f32>(-s, c)) * (in.uv * aspect);
+
+    var amount = 0.0;
+    for (var i = 0u; i < LAYERS; i++) {
+        let depth = f32(i) / f32(LAYERS - 1u);
+        // Offset so the layers don't line up.
+        amount += layer(p + f32(i) * vec2<f32>(0.37, 0.61), depth);
+    }
+
+    let coverage = saturate(amount) * weather.opacity * weather.color.a;
+    return vec4<f32>(mix(original.rgb, weather.color.rgb, coverage), original.a);
+}
@@ -0,0 +1,76 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+#import bevy_render::globals Globals
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> globals: Globals;
+
+struct WetScreenSheen {
+    wetness: f32,
+    threshold: f32,
+    highlight_boost: f32,
+    reflection_strength: f32,
+    // As a fraction of the height of the image.
+    reflection_length: f32,
+    horizon: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> sheen: WetScreenSheen;
+
+const REFLECTION_SAMPLES: i32 = 8;
+
+fn hash(x: f32) -> f32 {
+    return fract(sin(x * 127.1) * 43758.5453);
+}
+
+// The part of the color brighter than the threshold.
+fn highlight(color: vec3<f32>) -> vec3<f32> {
+    let luma = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    return color * smoothstep(sheen.threshold, 1.0, luma);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(source, source_sampler, in.uv);
+
+    if sheen.wetness <= 0.0 {
+        return color;
+    }
+
+    // Glossy highlights.
+    var sheened = color.rgb + highlight(color.rgb) * sheen.highlight_boost * sheen.wetness;
+
+    // The reflections fade in below the horizon.
+    let below = smoothstep(sheen.horizon, sheen.horizon + 0.1, in.uv.y);
+
+    if below > 0.0 && sheen.reflection_length > 0.0 {
+        let resolution = vec2<f32>(textureDimensions(source));
+
+        // Narrow columns of slightly different strength, which slowly shimmer, break the
+        // reflections up into streaks.
+        let column = floor(in.uv.x * resolution.x / 3.0);
+        let shimmer = 0.5 + 0.5 * sin(globals.time * 1.5 + hash(column) * 6.2831);
+        let streak = mix(0.4, 1.0, hash(column + 17.0)) * mix(0.7, 1.0, shimmer);
+
+        // Highlights above smear down onto this pixel, fading with distance.
+        var reflection = vec3<f32>(0.0);
+        var total_weight = 0.0;
+        for (var i = 1; i <= REFLECTION_SAMPLES; i++) {
+            let t = f32(i) / f32(REFLECTION_SAMPLES);
+            let uv = vec2<f32>(in.uv.x, in.uv.y - t * sheen.reflection_length);
+            let weight = 1.0 - t;
+
+            reflection += highlight(textureSampleLevel(source, source_sampler, uv, 0.0).rgb) * weight;
+            total_weight += weight;
+        }
+        reflection /= total_weight;
+
+        sheened += reflection * streak * below * sheen.reflection_strength * sheen.wetness;
+    }
+
+    return vec4<f32>(sheened, color.a);
+}
@@ -0,0 +1,37 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct WhiteBalance {
+    // What the cone responses are multiplied with.
+    balance: vec3<f32>,
+};
+
+@group(1) @binding(0)
+var<uniform> white_balance: WhiteBalance;
+
+// Linear sRGB to LMS cone responses (CAT02), and back.
+// Written as rows, so colors are multiplied from the left.
+const RGB_TO_LMS: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(0.390405, 0.549941, 0.008926),
+    vec3<f32>(0.070842, 0.963172, 0.001358),
+    vec3<f32>(0.023108, 0.128021, 0.936245),
+);
+const LMS_TO_RGB: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(2.858470, -1.628790, -0.024891),
+    vec3<f32>(-0.210182, 1.158200, 0.000324),
+    vec3<f32>(-0.041812, -0.118169, 1.068670),
+);
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let original = textureSample(source, source_sampler, in.uv);
+
+    let lms = (original.rgb * RGB_TO_LMS) * white_balance.balance;
+    let color = max(lms * LMS_TO_RGB, vec3<f32>(0.0));
+
+    return vec4<f32>(color, original.a);
+}
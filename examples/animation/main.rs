@@ -0,0 +1,67 @@
+//! Shows tweening effect parameters with `Animate`.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    animation::{Animate, Easing, Looping},
+    post_processing::{masks::Mask, pixelate::Pixelate},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to pixelate the image for a moment");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        // A breathing vignette.
+        Animate::new(
+            Mask::vignette(),
+            Mask {
+                strength: 1.2,
+                ..Mask::vignette()
+            },
+            1.5,
+        )
+        .with_looping(Looping::PingPong),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    camera: Query<Entity, With<Camera>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        let pixelated = Pixelate {
+            block_size: 24.0,
+            ..default()
+        };
+        let sharp = Pixelate {
+            block_size: 1.0,
+            ..default()
+        };
+
+        // Snaps to blocks, then eases back to sharp.
+        commands
+            .entity(camera.single())
+            .insert(Animate::new(pixelated, sharp, 0.6).with_easing(Easing::EaseOut));
+    }
+}
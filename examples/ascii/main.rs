@@ -0,0 +1,48 @@
+//! Shows the ascii effect, which draws the scene as text art.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::ascii::Ascii, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Ascii>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change cell size, [Space] to toggle colored glyphs");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Ascii::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Ascii>) {
+    let mut ascii = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        ascii.cell_size += 8.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        ascii.cell_size = (ascii.cell_size - 8.0).max(8.0);
+    }
+
+    // A transparent foreground draws the glyphs in the colors of the scene.
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        let alpha = ascii.foreground.a();
+        ascii.foreground.set_a(1.0 - alpha);
+    }
+}
@@ -0,0 +1,84 @@
+//! Shows the exposure of an HDR camera adapting when the light in the scene changes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::auto_exposure::AutoExposure, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<AutoExposure>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [L] to make the lights much brighter or dimmer, [Up|Down] to change the compensation, [E] to toggle the effect");
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        AutoExposure::default(),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut lights: Query<&mut PointLight>,
+    mut cameras: Query<(Entity, Option<&mut AutoExposure>), With<Camera>>,
+    mut bright: Local<bool>,
+) {
+    if keyboard_input.just_pressed(KeyCode::L) {
+        *bright = !*bright;
+
+        // Four EV brighter or dimmer, which the exposure catches up with over a few seconds.
+        for mut light in &mut lights {
+            if *bright {
+                light.intensity *= 16.0;
+            } else {
+                light.intensity /= 16.0;
+            }
+        }
+        info!("Lights are {}", if *bright { "bright" } else { "dim" });
+    }
+
+    let Ok((entity, auto_exposure)) = cameras.get_single_mut() else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::E) {
+        if auto_exposure.is_some() {
+            commands.entity(entity).remove::<AutoExposure>();
+        } else {
+            commands.entity(entity).insert(AutoExposure::default());
+        }
+        return;
+    }
+
+    let Some(mut auto_exposure) = auto_exposure else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        auto_exposure.compensation += 0.5;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        auto_exposure.compensation -= 0.5;
+    }
+}
@@ -0,0 +1,66 @@
+//! Shows an effect limited to the background, here cycling the hue of the sky.
+//! Note that the camera needs a depth prepass, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::{
+        blend::{Blend, EffectScope},
+        color_adjust::ColorAdjust,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .insert_resource(ClearColor(Color::rgb(0.35, 0.55, 0.9)))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<Blend<ColorAdjust>>,
+            update,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [S] to toggle between the background and the whole image");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        ColorAdjust::default(),
+        Blend::<ColorAdjust>::default().with_scope(EffectScope::Background),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut ColorAdjust, &mut Blend<ColorAdjust>)>,
+) {
+    let (mut color_adjust, mut blend) = query.single_mut();
+
+    color_adjust.hue_shift = (time.elapsed_seconds() * 45.0) % 360.0;
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        blend.scope = match blend.scope {
+            EffectScope::Everywhere => EffectScope::Background,
+            EffectScope::Background => EffectScope::Everywhere,
+        };
+    }
+}
@@ -0,0 +1,56 @@
+//! Bakes effects into image files without opening a window.
+//!
+//! Run with e.g. `cargo run --example batch --features batch -- textures/ascii.png ascii-baked.png`.
+//! Arguments are pairs of an asset path to read and a file to write.
+use std::time::Duration;
+
+use bevy::{app::ScheduleRunnerPlugin, prelude::*, window::ExitCondition, winit::WinitPlugin};
+use bevy_vfx_bag::{
+    batch::{BatchConfig, BatchPlugin},
+    post_processing::{dither::Dither, pixelate::Pixelate},
+    state::EffectState,
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let effects = EffectState::new([
+        Pixelate {
+            block_size: 4.0,
+            ..default()
+        }
+        .into(),
+        Dither::default().into(),
+    ]);
+
+    let mut config = BatchConfig::new(effects).exit_when_done();
+    if args.is_empty() {
+        config = config.with_job("textures/ascii.png", "ascii-baked.png");
+    }
+    for pair in args.chunks(2) {
+        let [input, output] = pair else {
+            panic!("Arguments should be pairs of an input asset path and an output file");
+        };
+        config = config.with_job(input.as_str(), output.as_str());
+    }
+
+    let mut app = App::new();
+
+    app.insert_resource(config)
+        .add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(ImagePlugin::default_nearest())
+                .disable::<WinitPlugin>(),
+            // Without a window, something else has to keep the app updating.
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0)),
+            BevyVfxBagPlugin::default(),
+            BatchPlugin,
+        ))
+        .run();
+}
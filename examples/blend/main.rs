@@ -0,0 +1,62 @@
+//! Shows blending the output of an effect with the image it started from.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        blend::{Blend, BlendMode},
+        halftone::Halftone,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Blend<Halftone>>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [M] to change the blend mode, [Up|Down] to change the factor");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Halftone::default(),
+        Blend::<Halftone>::new(BlendMode::Multiply, 1.0),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Blend<Halftone>>) {
+    let mut blend = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::M) {
+        blend.mode = match blend.mode {
+            BlendMode::Normal => BlendMode::Additive,
+            BlendMode::Additive => BlendMode::Multiply,
+            BlendMode::Multiply => BlendMode::Screen,
+            BlendMode::Screen => BlendMode::Overlay,
+            BlendMode::Overlay => BlendMode::Normal,
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        blend.factor = (blend.factor + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        blend.factor = (blend.factor - 0.1).max(0.0);
+    }
+}
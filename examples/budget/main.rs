@@ -0,0 +1,60 @@
+//! Shows effects being shed when the frame time goes over budget, and brought back after.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        budget::EffectBudget, chromatic_aberration::ChromaticAberration,
+        depth_of_field::DepthOfField, dither::Dither, glow::Glow, vision_mode::VisionMode,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(EffectBudget::new(1000.0 / 60.0))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the target frame time");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        VisionMode::night_vision(),
+        DepthOfField::default(),
+        Glow::default(),
+        ChromaticAberration::default(),
+        Dither::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut budget: ResMut<EffectBudget>) {
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        budget.target_frame_time_ms += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        budget.target_frame_time_ms = (budget.target_frame_time_ms - 1.0).max(1.0);
+    } else {
+        return;
+    }
+
+    info!(
+        "Target: {:.1}ms, frame time: {:.2}ms, shed: {:?}",
+        budget.target_frame_time_ms,
+        budget.frame_time_ms(),
+        budget.shed_effects().collect::<Vec<_>>()
+    );
+}
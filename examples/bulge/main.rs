@@ -0,0 +1,71 @@
+//! Shows bulging and pinching the image, and a heartbeat pulse.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::bulge::Bulge, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Bulge>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the strength, [Left|Right] to change the radius, [H] to toggle a heartbeat");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Bulge::default(),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    mut heartbeat: Local<bool>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<&mut Bulge>,
+) {
+    let mut bulge = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::H) {
+        *heartbeat = !*heartbeat;
+        if *heartbeat {
+            bulge.radius = 1.0;
+        } else {
+            *bulge = Bulge::default();
+        }
+    }
+
+    if *heartbeat {
+        // Two quick beats, then a rest.
+        let beat = (time.elapsed_seconds() * 1.2).fract();
+        let pulse =
+            (-((beat - 0.1) * 30.0).powi(2)).exp() + 0.6 * (-((beat - 0.3) * 30.0).powi(2)).exp();
+        bulge.strength = 0.15 * pulse;
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        bulge.strength = (bulge.strength + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        bulge.strength = (bulge.strength - 0.1).max(-1.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        bulge.radius += 0.05;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        bulge.radius = (bulge.radius - 0.05).max(0.05);
+    }
+}
@@ -0,0 +1,49 @@
+//! Shows bypassing a stack of effects, for before and after comparisons.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        bypass::{BypassKeys, EffectCategory},
+        chromatic_aberration::ChromaticAberration,
+        masks::Mask,
+        pixelate::Pixelate,
+        vision_mode::VisionMode,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(
+        BypassKeys::default()
+            .with_category(KeyCode::Key1, EffectCategory::Color)
+            .with_category(KeyCode::Key2, EffectCategory::Lens)
+            .with_category(KeyCode::Key3, EffectCategory::Stylize),
+    )
+    .add_systems(Startup, startup)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [F9] to bypass all effects, [1] color, [2] lens, [3] stylize effects");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        VisionMode::night_vision(),
+        ChromaticAberration::default(),
+        Mask::default(),
+        Pixelate::default(),
+    ));
+}
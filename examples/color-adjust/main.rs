@@ -0,0 +1,64 @@
+//! Shows simple color grading, tweaked at runtime.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::color_adjust::ColorAdjust, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<ColorAdjust>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Hold [B] brightness, [C] contrast, [S] saturation, [H] hue, [G] gamma, and press [Up|Down] to change it. [R] resets");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        ColorAdjust::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut ColorAdjust>) {
+    let mut color_adjust = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        *color_adjust = ColorAdjust::default();
+        return;
+    }
+
+    let change = if keyboard_input.just_pressed(KeyCode::Up) {
+        1.0
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        -1.0
+    } else {
+        return;
+    };
+
+    if keyboard_input.pressed(KeyCode::B) {
+        color_adjust.brightness += change * 0.05;
+    } else if keyboard_input.pressed(KeyCode::C) {
+        color_adjust.contrast += change * 0.1;
+    } else if keyboard_input.pressed(KeyCode::S) {
+        color_adjust.saturation += change * 0.1;
+    } else if keyboard_input.pressed(KeyCode::H) {
+        color_adjust.hue_shift += change * 15.0;
+    } else if keyboard_input.pressed(KeyCode::G) {
+        color_adjust.gamma += change * 0.1;
+    }
+}
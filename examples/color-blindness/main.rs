@@ -0,0 +1,63 @@
+//! Shows the scene as seen with color blindness, or corrected for it.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::color_blindness::{ColorBlindness, ColorBlindnessMode, ColorBlindnessVariant},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<ColorBlindness>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [v] to change the variant, [m] to simulate or daltonize, [up/down] to change the severity");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        ColorBlindness::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut ColorBlindness>) {
+    let mut color_blindness = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::V) {
+        color_blindness.variant = match color_blindness.variant {
+            ColorBlindnessVariant::Protanopia => ColorBlindnessVariant::Deuteranopia,
+            ColorBlindnessVariant::Deuteranopia => ColorBlindnessVariant::Tritanopia,
+            ColorBlindnessVariant::Tritanopia => ColorBlindnessVariant::Protanopia,
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::M) {
+        color_blindness.mode = match color_blindness.mode {
+            ColorBlindnessMode::Simulate => ColorBlindnessMode::Daltonize,
+            ColorBlindnessMode::Daltonize => ColorBlindnessMode::Simulate,
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        color_blindness.severity = (color_blindness.severity + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        color_blindness.severity = (color_blindness.severity - 0.1).max(0.0);
+    }
+}
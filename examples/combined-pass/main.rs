@@ -0,0 +1,76 @@
+//! Shows a stack of cheap effects drawn in a single pass.
+//! The image looks the same either way, only the number of passes changes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        color_adjust::ColorAdjust, combined::CombinedPass, masks::Mask, pixelate::Pixelate,
+        white_balance::WhiteBalance, PostProcessingOrder,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [C] to toggle the combined pass, [Up|Down] to change the temperature");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Pixelate::default().order(0.0),
+        ColorAdjust {
+            contrast: 1.2,
+            saturation: 0.8,
+            ..default()
+        }
+        .order(1.0),
+        WhiteBalance {
+            temperature: 8000.0,
+            ..default()
+        }
+        .order(2.0),
+        Mask::vignette().order(3.0),
+        CombinedPass,
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, &mut WhiteBalance, Option<&CombinedPass>)>,
+) {
+    let (entity, mut white_balance, combined) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::C) {
+        if combined.is_some() {
+            info!("Drawing each effect in its own pass");
+            commands.entity(entity).remove::<CombinedPass>();
+        } else {
+            info!("Drawing the effects in a single pass");
+            commands.entity(entity).insert(CombinedPass);
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        white_balance.temperature += 500.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        white_balance.temperature -= 500.0;
+    }
+}
@@ -0,0 +1,118 @@
+//! Shows the images of two cameras composited in an explicit order.
+//! The world camera draws the shapes, and an overlay camera draws a pixelated cube of its own,
+//! which the compositor blends over the world. The pixelation only applies to the overlay,
+//! while the vignette on the compositor applies to the composited image.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_vfx_bag::{
+    post_processing::{
+        blend::BlendMode,
+        compositor::{Compositor, CompositorLayer},
+        masks::Mask,
+        pixelate::Pixelate,
+    },
+    BevyVfxBagPlugin,
+};
+
+/// The layer only the overlay camera sees.
+const OVERLAY_LAYER: u8 = 1;
+
+#[derive(Component)]
+struct Spinning;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (spin, update))
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press [B] to change the blend mode of the overlay, [Up]/[Down] to change its opacity");
+
+    let world_camera = commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        })
+        .id();
+
+    let overlay_camera = commands
+        .spawn((
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+                ..default()
+            },
+            RenderLayers::layer(OVERLAY_LAYER),
+            Pixelate {
+                block_size: 8.0,
+                ..default()
+            },
+        ))
+        .id();
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Cube::new(1.5).into()),
+            material: materials.add(Color::rgb(0.9, 0.3, 0.6).into()),
+            ..default()
+        },
+        RenderLayers::layer(OVERLAY_LAYER),
+        Spinning,
+    ));
+
+    commands.spawn((
+        Compositor::new(vec![
+            CompositorLayer::new(world_camera),
+            CompositorLayer::new(overlay_camera),
+        ]),
+        Mask::vignette(),
+    ));
+}
+
+fn spin(time: Res<Time>, mut query: Query<&mut Transform, With<Spinning>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_seconds());
+        transform.rotate_x(time.delta_seconds() * 0.5);
+    }
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut compositors: Query<&mut Compositor>) {
+    if !keyboard_input.any_just_pressed([KeyCode::B, KeyCode::Up, KeyCode::Down]) {
+        return;
+    }
+
+    let mut compositor = compositors.single_mut();
+    let overlay = &mut compositor.layers[1];
+
+    if keyboard_input.just_pressed(KeyCode::B) {
+        overlay.blend = match overlay.blend {
+            BlendMode::Normal => BlendMode::Additive,
+            BlendMode::Additive => BlendMode::Multiply,
+            BlendMode::Multiply => BlendMode::Screen,
+            BlendMode::Screen => BlendMode::Overlay,
+            BlendMode::Overlay => BlendMode::Normal,
+        };
+    }
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        overlay.opacity = (overlay.opacity + 0.1).min(1.0);
+    }
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        overlay.opacity = (overlay.opacity - 0.1).max(0.0);
+    }
+
+    info!("{}", *compositor);
+}
@@ -0,0 +1,58 @@
+//! Shows a red vignette fading out after taking damage, driven by a curve from a `.curve.ron` file.
+//!
+//! Edit `assets/curves/damage_recovery.curve.ron` while this runs to change the fade.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{curve::DriveWithCurve, post_processing::masks::Mask, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Mask>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to take damage");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Mask {
+            strength: 1.2,
+            fade: 1.0,
+            color: Color::RED,
+            ..Mask::vignette()
+        },
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<Input<KeyCode>>,
+    query: Query<Entity, With<Mask>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    for entity in &query {
+        commands.entity(entity).insert(DriveWithCurve::<Mask>::new(
+            asset_server.load("curves/damage_recovery.curve.ron"),
+            "fade",
+            3.0,
+        ));
+    }
+}
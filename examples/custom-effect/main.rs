@@ -0,0 +1,74 @@
+//! Shows a post processing effect defined outside of the crate, with its own WGSL shader.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+use bevy_vfx_bag::{
+    post_processing::custom::{CustomPostProcessingPlugin, PostProcessingEffect},
+    BevyVfxBagPlugin,
+};
+
+/// Tones the image like an old photograph, with some film grain.
+#[derive(Debug, Clone, Component, ShaderType)]
+struct Sepia {
+    /// The color of the brightest parts of the image.
+    tone: Vec4,
+    strength: f32,
+    grain: f32,
+}
+
+impl PostProcessingEffect for Sepia {
+    const SHADER_INTERFACE: u32 = 1;
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/examples/sepia.wgsl".into()
+    }
+}
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+        CustomPostProcessingPlugin::<Sepia>::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [up] or [down] to change the strength, [G] to toggle the grain");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Sepia {
+            tone: Vec4::new(1.1, 0.9, 0.65, 1.0),
+            strength: 1.0,
+            grain: 0.08,
+        },
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, time: Res<Time>, mut query: Query<&mut Sepia>) {
+    let mut sepia = query.single_mut();
+
+    if keyboard_input.pressed(KeyCode::Up) {
+        sepia.strength = (sepia.strength + time.delta_seconds()).min(1.0);
+    } else if keyboard_input.pressed(KeyCode::Down) {
+        sepia.strength = (sepia.strength - time.delta_seconds()).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::G) {
+        sepia.grain = if sepia.grain > 0.0 { 0.0 } else { 0.08 };
+    }
+}
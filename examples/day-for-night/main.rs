@@ -0,0 +1,87 @@
+//! Shows a scene lit for the day turned into night.
+//! Note that the moonlight rim needs a depth and normal prepass, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::prepass::{DepthPrepass, NormalPrepass},
+    prelude::*,
+};
+use bevy_vfx_bag::{post_processing::day_for_night::DayForNight, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<DayForNight>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the exposure, [Left|Right] to move the moon, [R] to toggle the rim, [Space] to toggle the effect");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        NormalPrepass,
+        DayForNight::default(),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, Option<&mut DayForNight>), With<Camera>>,
+    mut stashed: Local<Option<DayForNight>>,
+) {
+    let (entity, day_for_night) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        match day_for_night {
+            Some(day_for_night) => {
+                *stashed = Some(*day_for_night);
+                commands.entity(entity).remove::<DayForNight>();
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(stashed.take().unwrap_or_default());
+            }
+        }
+        return;
+    }
+
+    let Some(mut day_for_night) = day_for_night else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        day_for_night.exposure += 0.25;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        day_for_night.exposure -= 0.25;
+    }
+    day_for_night.exposure = day_for_night.exposure.min(0.);
+
+    if keyboard_input.just_pressed(KeyCode::Left) {
+        day_for_night.moon_direction = Quat::from_rotation_y(0.3) * day_for_night.moon_direction;
+    } else if keyboard_input.just_pressed(KeyCode::Right) {
+        day_for_night.moon_direction = Quat::from_rotation_y(-0.3) * day_for_night.moon_direction;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        day_for_night.rim = if day_for_night.rim > 0. { 0. } else { 0.5 };
+    }
+}
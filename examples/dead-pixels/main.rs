@@ -0,0 +1,66 @@
+//! Shows dead and stuck pixels and scratches, as on a damaged display.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::dead_pixels::{DeadPixel, DeadPixels},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<DeadPixels>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the count, [Left|Right] to change the size, [S] to reseed, [C] to add a scratch");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DeadPixels {
+            // A stuck pixel right in the middle, where it's most annoying.
+            pixels: vec![DeadPixel::stuck(Vec2::splat(0.5), Color::GREEN)],
+            ..default()
+        },
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut DeadPixels>) {
+    let mut dead_pixels = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        dead_pixels.count += 4;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        dead_pixels.count = dead_pixels.count.saturating_sub(4);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        dead_pixels.size += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        dead_pixels.size = (dead_pixels.size - 1.0).max(1.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        dead_pixels.seed += 1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::C) {
+        dead_pixels.scratches = (dead_pixels.scratches + 1) % 9;
+    }
+}
@@ -0,0 +1,71 @@
+//! Shows debanding the smooth gradient of a dark vignette.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        deband::{Deband, QualityBundle},
+        masks::Mask,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Deband>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [t] to toggle debanding, [up/down] to change its strength");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Mask {
+            strength: 2.0,
+            ..Mask::vignette()
+        },
+        QualityBundle::default(),
+    ));
+}
+
+fn update(
+    mut saved_settings: Local<Deband>,
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, Option<&mut Deband>), With<Camera>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::T) {
+        match query.single() {
+            (entity, None) => {
+                info!("Toggling ON");
+                commands.entity(entity).insert(*saved_settings);
+            }
+            (entity, Some(settings)) => {
+                info!("Toggling OFF");
+                commands.entity(entity).remove::<Deband>();
+                *saved_settings = *settings;
+            }
+        };
+    }
+
+    if let (_, Some(mut settings)) = query.single_mut() {
+        if keyboard_input.just_pressed(KeyCode::Up) {
+            settings.strength += 0.5;
+        } else if keyboard_input.just_pressed(KeyCode::Down) {
+            settings.strength = (settings.strength - 0.5).max(0.0);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Shows the image after each effect in a stack, tiled in a contact sheet.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        chromatic_aberration::ChromaticAberration, debug_view::EffectDebugView, masks::Mask,
+        pixelate::Pixelate, wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(EffectDebugView::enabled())
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to toggle the contact sheet, [T] to list the tiles");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        Wave {
+            waves_x: 4.0,
+            speed_x: 2.0,
+            amplitude_x: 0.01,
+            ..default()
+        },
+        ChromaticAberration::default(),
+        Pixelate::default(),
+        Mask::default(),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut debug_view: ResMut<EffectDebugView>,
+    cameras: Query<Entity, With<Camera3d>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        debug_view.enabled = !debug_view.enabled;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::T) {
+        for camera in &cameras {
+            info!("Tiles: {:?}", debug_view.tiles(camera));
+        }
+    }
+}
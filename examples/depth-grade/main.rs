@@ -0,0 +1,80 @@
+//! Shows separate grading of the near, middle and far parts of the scene:
+//! a warm foreground against a cool, washed out distance.
+//! Note that the camera needs a depth prepass, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::depth_grade::{DepthGrade, DepthGradeZone},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<DepthGrade>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to move the near zone, [Left|Right] to move the far zone, [T] to change the transition");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        DepthGrade {
+            near: DepthGradeZone {
+                tint: Color::rgb(1.0, 0.9, 0.75),
+                saturation: 1.2,
+                contrast: 1.1,
+            },
+            far: DepthGradeZone {
+                tint: Color::rgb(0.75, 0.85, 1.0),
+                saturation: 0.4,
+                contrast: 0.8,
+            },
+            near_distance: 11.0,
+            far_distance: 16.0,
+            ..default()
+        },
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut DepthGrade>) {
+    let mut grade = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        grade.near_distance = (grade.near_distance + 1.0).min(grade.far_distance);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        grade.near_distance = (grade.near_distance - 1.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        grade.far_distance += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        grade.far_distance = (grade.far_distance - 1.0).max(grade.near_distance);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::T) {
+        grade.transition = if grade.transition < 8.0 {
+            grade.transition * 2.0
+        } else {
+            1.0
+        };
+    }
+}
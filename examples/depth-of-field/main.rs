@@ -0,0 +1,63 @@
+//! Shows the depth of field effect.
+//! Note that the camera needs a depth prepass, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_vfx_bag::{post_processing::depth_of_field::DepthOfField, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<DepthOfField>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change focal distance, [Left|Right] to change aperture, [PgUp|PgDown] to change sample count");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        DepthOfField::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut DepthOfField>) {
+    let mut dof = query.single_mut();
+
+    if keyboard_input.pressed(KeyCode::Up) {
+        dof.focal_distance += 0.1;
+    } else if keyboard_input.pressed(KeyCode::Down) {
+        dof.focal_distance -= 0.1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        dof.aperture += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        dof.aperture -= 0.1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::PageUp) {
+        dof.sample_count += 8;
+    } else if keyboard_input.just_pressed(KeyCode::PageDown) {
+        dof.sample_count = dof.sample_count.saturating_sub(8);
+    }
+
+    dof.focal_distance = dof.focal_distance.max(0.1);
+    dof.aperture = dof.aperture.max(0.);
+}
@@ -0,0 +1,73 @@
+//! Shows quads burning away with the dissolve material, and being despawned afterwards.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_vfx_bag::{
+    post_processing::{
+        dissolve::{Dissolve, DissolveAway},
+        material::EffectMaterial2d,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, spawn_on_key)
+    .run();
+}
+
+#[derive(Resource)]
+struct Quad {
+    mesh: Handle<Mesh>,
+    image: Handle<Image>,
+}
+
+fn startup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    info!("Press [Space] to spawn a quad which dissolves away");
+
+    commands.spawn(Camera2dBundle::default());
+    commands.insert_resource(Quad {
+        mesh: meshes.add(shape::Quad::new(Vec2::splat(256.0)).into()),
+        image: asset_server.load("textures/ascii.png"),
+    });
+}
+
+fn spawn_on_key(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    quad: Res<Quad>,
+    mut materials: ResMut<Assets<EffectMaterial2d<Dissolve>>>,
+    mut count: Local<u32>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    *count += 1;
+    let x = ((*count % 3) as f32 - 1.0) * 300.0;
+
+    // Each quad gets a material of its own, since dissolving changes it.
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: quad.mesh.clone().into(),
+            material: materials.add(EffectMaterial2d::new(
+                Dissolve::default(),
+                quad.image.clone(),
+            )),
+            transform: Transform::from_xyz(x, 0.0, 0.0),
+            ..default()
+        },
+        DissolveAway::new(2.0),
+    ));
+}
@@ -0,0 +1,55 @@
+//! Shows the dither effect, which reduces the number of colors in the scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::dither::{Dither, DitherMode},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Dither>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1|2|3|4] to change mode, [Up|Down] to change levels");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Dither::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Dither>) {
+    let mut dither = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        dither.mode = DitherMode::Bayer2;
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        dither.mode = DitherMode::Bayer4;
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        dither.mode = DitherMode::Bayer8;
+    } else if keyboard_input.just_pressed(KeyCode::Key4) {
+        dither.mode = DitherMode::BlueNoise;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        dither.levels += 1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        dither.levels = dither.levels.saturating_sub(1).max(2);
+    }
+}
@@ -0,0 +1,59 @@
+//! Shows color fringes near the edges of the image, like a cheap lens.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::edge_fringe::EdgeFringe, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<EdgeFringe>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the strength, [Left|Right] to change where the fringes start, [F] to change the falloff");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        EdgeFringe::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut EdgeFringe>) {
+    let mut fringe = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        fringe.strength += 0.002;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        fringe.strength = (fringe.strength - 0.002).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        fringe.start = (fringe.start + 0.1).min(0.9);
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        fringe.start = (fringe.start - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F) {
+        fringe.falloff = if fringe.falloff >= 4.0 {
+            1.0
+        } else {
+            fringe.falloff + 1.0
+        };
+    }
+}
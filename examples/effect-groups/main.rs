@@ -0,0 +1,81 @@
+//! Shows groups of effects which are enabled, disabled and faded together.
+//! The damage group adds chromatic aberration and a vignette, the underwater group distorts,
+//! tints and blurs the image.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        blur::Blur,
+        chromatic_aberration::ChromaticAberration,
+        group::{EffectGroup, EffectGroups},
+        masks::Mask,
+        underwater::Underwater,
+    },
+    BevyVfxBagPlugin,
+};
+
+const DAMAGE: &str = "damage";
+const UNDERWATER: &str = "underwater";
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(
+        EffectGroups::default()
+            .with_disabled(DAMAGE)
+            .with_disabled(UNDERWATER),
+    )
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [D] to toggle the damage group, [U] to toggle the underwater group");
+    info!("Press [Left|Right] to change the intensity of the underwater group");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        ChromaticAberration::default(),
+        EffectGroup::<ChromaticAberration>::new(DAMAGE),
+        Mask::vignette(),
+        EffectGroup::<Mask>::new(DAMAGE),
+        Underwater::default(),
+        EffectGroup::<Underwater>::new(UNDERWATER),
+        Blur::default(),
+        EffectGroup::<Blur>::new(UNDERWATER),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut groups: ResMut<EffectGroups>) {
+    if !keyboard_input.any_just_pressed([KeyCode::D, KeyCode::U, KeyCode::Left, KeyCode::Right]) {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::D) {
+        groups.toggle(DAMAGE);
+    }
+    if keyboard_input.just_pressed(KeyCode::U) {
+        groups.toggle(UNDERWATER);
+    }
+
+    let underwater = groups.settings_mut(UNDERWATER);
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        underwater.intensity = (underwater.intensity + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        underwater.intensity = (underwater.intensity - 0.1).max(0.0);
+    }
+
+    info!("{}", *groups);
+}
@@ -0,0 +1,291 @@
+//! Shows an effect limited to a mask which a render pass of the app draws,
+//! here a spell area following the cursor.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::{core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, FragmentState, LoadOp,
+            MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
+            RenderPassDescriptor, RenderPipelineDescriptor, ShaderStages, ShaderType,
+            UniformBuffer,
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+    window::PrimaryWindow,
+};
+use bevy_vfx_bag::{
+    post_processing::{
+        blend::{Blend, EffectScope},
+        effect_mask::{EffectMask, EffectMaskChannel, ViewEffectMask},
+        graph::node,
+        wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+        SpellAreaPlugin,
+    ))
+    .init_resource::<SpellArea>()
+    .add_systems(Startup, startup)
+    .add_systems(Update, (update, follow_cursor))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Move the cursor to move the spell area, press [Up|Down] to change its radius, and [I] to invert the mask");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        EffectMask,
+        Wave {
+            waves_x: 1.,
+            waves_y: 20.,
+            speed_x: 1.3,
+            speed_y: 20.,
+            amplitude_x: 0.25,
+            amplitude_y: 0.005,
+            ..default()
+        },
+        Blend::<Wave>::default().with_scope(EffectScope::Mask(EffectMaskChannel::Red)),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut spell_area: ResMut<SpellArea>,
+    mut query: Query<&mut Blend<Wave>>,
+) {
+    if keyboard_input.pressed(KeyCode::Up) {
+        spell_area.radius += 200.0 * time.delta_seconds();
+    } else if keyboard_input.pressed(KeyCode::Down) {
+        spell_area.radius = (spell_area.radius - 200.0 * time.delta_seconds()).max(10.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::I) {
+        let mut blend = query.single_mut();
+        blend.scope = match blend.scope {
+            EffectScope::Mask(channel) => EffectScope::OutsideMask(channel),
+            EffectScope::OutsideMask(channel) => EffectScope::Mask(channel),
+            scope => scope,
+        };
+        info!("Scope: {:?}", blend.scope);
+    }
+}
+
+fn follow_cursor(windows: Query<&Window, With<PrimaryWindow>>, mut spell_area: ResMut<SpellArea>) {
+    let window = windows.single();
+
+    // The mask is drawn in physical pixels, while the cursor is in logical ones.
+    if let Some(cursor) = window.cursor_position() {
+        spell_area.center = cursor * window.scale_factor() as f32;
+    }
+}
+
+/// Where the spell is cast, in physical pixels.
+#[derive(Debug, Clone, Resource, ExtractResource)]
+struct SpellArea {
+    center: Vec2,
+    radius: f32,
+}
+
+impl Default for SpellArea {
+    fn default() -> Self {
+        Self {
+            center: Vec2::new(640.0, 360.0),
+            radius: 200.0,
+        }
+    }
+}
+
+#[derive(Default, ShaderType)]
+struct SpellAreaUniform {
+    center: Vec2,
+    radius: f32,
+}
+
+/// Draws the spell area into the red channel of the effect mask.
+struct SpellAreaPlugin;
+
+impl Plugin for SpellAreaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractResourcePlugin::<SpellArea>::default());
+    }
+
+    fn finish(&self, app: &mut App) {
+        let render_app = app.sub_app_mut(RenderApp);
+
+        render_app
+            .init_resource::<SpellAreaPipeline>()
+            .init_resource::<SpellAreaBuffer>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_render_graph_node::<ViewNodeRunner<SpellAreaNode>>(
+                core_3d::graph::NAME,
+                "spell_area",
+            )
+            .add_render_graph_edges(
+                core_3d::graph::NAME,
+                &[node::START_EFFECT_MASK, "spell_area", node::END_EFFECT_MASK],
+            );
+    }
+}
+
+#[derive(Resource)]
+struct SpellAreaPipeline {
+    layout: BindGroupLayout,
+    id: CachedRenderPipelineId,
+}
+
+impl FromWorld for SpellAreaPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("Spell area layout"),
+                    entries: &[BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: Some(SpellAreaUniform::min_size()),
+                        },
+                        count: None,
+                    }],
+                });
+
+        let shader = world
+            .resource::<AssetServer>()
+            .load("shaders/examples/spell_area.wgsl");
+
+        let id =
+            world
+                .resource::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("Spell area pipeline".into()),
+                    layout: vec![layout.clone()],
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader,
+                        shader_defs: vec![],
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: ViewEffectMask::FORMAT,
+                            blend: None,
+                            // Leaves the other channels to other masks.
+                            write_mask: ColorWrites::RED,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    push_constant_ranges: vec![],
+                });
+
+        Self { layout, id }
+    }
+}
+
+#[derive(Default, Resource)]
+struct SpellAreaBuffer {
+    buffer: UniformBuffer<SpellAreaUniform>,
+    bind_group: Option<BindGroup>,
+}
+
+fn prepare(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    pipeline: Res<SpellAreaPipeline>,
+    spell_area: Res<SpellArea>,
+    mut buffer: ResMut<SpellAreaBuffer>,
+) {
+    buffer.buffer.set(SpellAreaUniform {
+        center: spell_area.center,
+        radius: spell_area.radius,
+    });
+    buffer.buffer.write_buffer(&render_device, &render_queue);
+
+    let Some(binding) = buffer.buffer.binding() else {
+        return;
+    };
+
+    let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Spell area bind group"),
+        layout: &pipeline.layout,
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: binding,
+        }],
+    });
+    buffer.bind_group = Some(bind_group);
+}
+
+#[derive(Default)]
+struct SpellAreaNode;
+
+impl ViewNode for SpellAreaNode {
+    type ViewQuery = &'static ViewEffectMask;
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        mask: QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline = world.resource::<SpellAreaPipeline>();
+        let Some(render_pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pipeline.id)
+        else {
+            return Ok(());
+        };
+        let Some(bind_group) = &world.resource::<SpellAreaBuffer>().bind_group else {
+            return Ok(());
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Spell area pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: mask.view(),
+                resolve_target: None,
+                // Keeps what other nodes drew into the mask.
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        render_pass.set_render_pipeline(render_pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
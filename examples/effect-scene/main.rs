@@ -0,0 +1,62 @@
+//! Shows spawning an effect stack saved in a Bevy scene onto a camera.
+//!
+//! The effects are in `assets/scenes/wavy.scn.ron`.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{chromatic_aberration::ChromaticAberration, wave::Wave},
+    scene::PostProcessingSceneSpawner,
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, respawn_on_key)
+    .run();
+}
+
+#[derive(Component)]
+struct SceneCamera;
+
+fn startup(mut commands: Commands, mut spawner: PostProcessingSceneSpawner) {
+    info!("Press [Space] to remove the effects and spawn them from the scene again");
+
+    let camera = commands
+        .spawn((
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 6., 12.0)
+                    .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+                ..default()
+            },
+            SceneCamera,
+        ))
+        .id();
+
+    spawner.load("scenes/wavy.scn.ron", camera);
+}
+
+fn respawn_on_key(
+    mut commands: Commands,
+    mut spawner: PostProcessingSceneSpawner,
+    keyboard_input: Res<Input<KeyCode>>,
+    camera: Query<Entity, With<SceneCamera>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    let camera = camera.single();
+    commands
+        .entity(camera)
+        .remove::<(Wave, ChromaticAberration)>();
+    spawner.load("scenes/wavy.scn.ron", camera);
+}
@@ -0,0 +1,58 @@
+//! Shows the exposure of the image in false colors, along with a color checker chart.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::false_color::FalseColor, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<FalseColor>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!(
+        "Press [Up|Down] to change the exposure, [B] to toggle the bands, [C] to toggle the chart"
+    );
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        FalseColor {
+            show_chart: true,
+            ..default()
+        },
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut FalseColor>) {
+    let mut false_color = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        false_color.exposure += 0.5;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        false_color.exposure -= 0.5;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::B) {
+        false_color.show_bands = !false_color.show_bands;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::C) {
+        false_color.show_chart = !false_color.show_chart;
+    }
+}
@@ -0,0 +1,80 @@
+//! Shows the flicker, weave and splices of an old film projector, and logs its clicks.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::film_projector::{FilmProjector, FilmProjectorEvent, FilmProjectorEventKind},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<FilmProjector>,
+            update,
+            sounds,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the flicker, [Left|Right] to change the weave, [B] to cycle the shutter blades, [S] to toggle splices");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        FilmProjector::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut FilmProjector>) {
+    let mut projector = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        projector.flicker = (projector.flicker + 0.05).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        projector.flicker = (projector.flicker - 0.05).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        projector.weave += 0.001;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        projector.weave = (projector.weave - 0.001).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::B) {
+        projector.blades = projector.blades % 3 + 1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        projector.splice_interval = if projector.splice_interval > 0.0 {
+            0.0
+        } else {
+            FilmProjector::default().splice_interval
+        };
+    }
+}
+
+/// A game would play sounds here. Frame clicks are too frequent to log, so only count them.
+fn sounds(mut events: EventReader<FilmProjectorEvent>, mut clicks: Local<u32>) {
+    for event in events.iter() {
+        match event.kind {
+            FilmProjectorEventKind::Frame => *clicks += 1,
+            FilmProjectorEventKind::Splice => info!("Splice! ({} clicks so far)", *clicks),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Shows fog over the parallax layers of a 2d side-scroller, thickening with their depth.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_vfx_bag::{
+    post_processing::fog_2d::{Fog2d, Fog2dBand, Fog2dLayer},
+    BevyVfxBagPlugin,
+};
+
+/// The parallax layers, from the furthest to the nearest: render layer, depth, color,
+/// how fast the layer scrolls, and the heights of its hills.
+const LAYERS: [(u8, f32, Color, f32, f32); 3] = [
+    (1, 3.0, Color::rgb(0.3, 0.35, 0.45), 0.2, 260.0),
+    (2, 1.5, Color::rgb(0.2, 0.3, 0.25), 0.5, 160.0),
+    (3, 0.2, Color::rgb(0.1, 0.15, 0.1), 1.0, 80.0),
+];
+
+#[derive(Component)]
+struct Parallax(f32);
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(ClearColor(Color::rgb(0.55, 0.65, 0.8)))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Fog2d>, update, scroll),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("[Left|Right] to scroll, [Up|Down] to change the density, [B] to toggle the ground mist");
+
+    let mut fog = Fog2d::new(Color::rgb(0.75, 0.8, 0.85))
+        .with_band(Fog2dBand::new(0.7, 1.0, 0.5))
+        .with_band(Fog2dBand::new(0.3, 0.0, 0.2));
+    for (layer, depth, ..) in LAYERS {
+        fog = fog.with_layer(Fog2dLayer::new(layer, depth));
+    }
+
+    commands.spawn((Camera2dBundle::default(), fog));
+
+    // Hills along each layer, seen by the camera and given their depth, so they are on both layers.
+    for (index, (layer, _, color, speed, height)) in LAYERS.into_iter().enumerate() {
+        let z = index as f32;
+        let parent = commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(0.0, 0.0, z)),
+                Parallax(speed),
+            ))
+            .id();
+
+        for hill in -12..=12 {
+            let x = hill as f32 * 160.0;
+            // Varied, but the same each run.
+            let hill_height = height * (0.6 + 0.4 * ((hill * 7 + index as i32 * 3) as f32).sin());
+
+            let child = commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::new(180.0, hill_height)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(
+                            x,
+                            -360.0 + hill_height / 2.0 + index as f32 * -40.0,
+                            0.0,
+                        ),
+                        ..default()
+                    },
+                    RenderLayers::from_layers(&[0, layer]),
+                ))
+                .id();
+
+            commands.entity(parent).add_child(child);
+        }
+    }
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut fog: Query<&mut Fog2d>) {
+    let mut fog = fog.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        fog.density += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        fog.density = (fog.density - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::B) {
+        let amount = &mut fog.bands[0].amount;
+        *amount = if *amount > 0.0 { 0.0 } else { 0.5 };
+    }
+}
+
+/// Scrolls the layers at their own speeds, so the nearer layers move faster.
+fn scroll(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut layers: Query<(&mut Transform, &Parallax)>,
+) {
+    let mut direction = 0.0;
+    if keyboard_input.pressed(KeyCode::Left) {
+        direction += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::Right) {
+        direction -= 1.0;
+    }
+
+    for (mut transform, parallax) in &mut layers {
+        transform.translation.x += direction * parallax.0 * 400.0 * time.delta_seconds();
+    }
+}
@@ -0,0 +1,74 @@
+//! Shows the depth-aware fog effect.
+//! Note that the camera needs a depth prepass, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::fog::{Fog, FogFalloff},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Fog>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1|2|3] to change falloff, [Up|Down] to change start, [Left|Right] to change end/density");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        Fog::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Fog>) {
+    let mut fog = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        fog.falloff = FogFalloff::Linear;
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        fog.falloff = FogFalloff::Exponential;
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        fog.falloff = FogFalloff::ExponentialSquared;
+    }
+
+    if keyboard_input.pressed(KeyCode::Up) {
+        fog.start += 0.1;
+    } else if keyboard_input.pressed(KeyCode::Down) {
+        fog.start -= 0.1;
+    }
+    fog.start = fog.start.max(0.);
+
+    let linear = fog.falloff == FogFalloff::Linear;
+    if keyboard_input.pressed(KeyCode::Right) {
+        if linear {
+            fog.end += 0.1;
+        } else {
+            fog.density += 0.002;
+        }
+    } else if keyboard_input.pressed(KeyCode::Left) {
+        if linear {
+            fog.end -= 0.1;
+        } else {
+            fog.density -= 0.002;
+        }
+    }
+    fog.end = fog.end.max(fog.start);
+    fog.density = fog.density.max(0.);
+}
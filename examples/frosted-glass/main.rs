@@ -0,0 +1,59 @@
+//! Shows the scene through frosted glass, like behind a pause menu or when frozen.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::frosted_glass::FrostedGlass, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<FrostedGlass>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the blur radius, [Left|Right] to change the distortion, [I] to toggle ice");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        FrostedGlass::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut FrostedGlass>) {
+    let mut frosted_glass = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        frosted_glass.blur_radius += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        frosted_glass.blur_radius = (frosted_glass.blur_radius - 1.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        frosted_glass.distortion += 2.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        frosted_glass.distortion = (frosted_glass.distortion - 2.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::I) {
+        *frosted_glass = if *frosted_glass == FrostedGlass::ice() {
+            FrostedGlass::default()
+        } else {
+            FrostedGlass::ice()
+        };
+    }
+}
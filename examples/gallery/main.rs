@@ -0,0 +1,226 @@
+//! A gallery of all effects, for trying how they look together.
+//!
+//! Effects can be toggled, tweaked and moved around in the stack while this runs.
+//! The stack is printed as a preset each time it changes, which can be saved as a `.vfx.ron` file.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    prelude::*,
+    reflect::{GetPath, ReflectRef},
+};
+use bevy_vfx_bag::{
+    preset::EffectPreset,
+    state::{EffectKind, EffectState},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .init_resource::<Gallery>()
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+/// The effect and parameter which are selected, and the effects in the stack, first to last.
+#[derive(Debug, Default, Resource)]
+struct Gallery {
+    selected: usize,
+    parameter: usize,
+    stack: Vec<EffectKind>,
+}
+
+impl Gallery {
+    fn kind(&self) -> EffectKind {
+        EffectKind::ALL[self.selected]
+    }
+}
+
+#[derive(Component)]
+struct GalleryCamera;
+
+fn startup(mut commands: Commands) {
+    info!("Press [Left|Right] to select an effect, [Space] to toggle it");
+    info!("Press [PageUp|PageDown] to move the selected effect in the stack");
+    info!("Press [Tab] to select a parameter, [Up|Down] to change it");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        GalleryCamera,
+    ));
+}
+
+fn update(world: &mut World) {
+    let pressed: Vec<KeyCode> = world
+        .resource::<Input<KeyCode>>()
+        .get_just_pressed()
+        .copied()
+        .collect();
+    let camera = world
+        .query_filtered::<Entity, With<GalleryCamera>>()
+        .single(world);
+
+    world.resource_scope(|world, mut gallery: Mut<Gallery>| {
+        let mut restack = false;
+
+        for key in pressed {
+            match key {
+                KeyCode::Left | KeyCode::Right => {
+                    let step = if key == KeyCode::Right {
+                        1
+                    } else {
+                        EffectKind::ALL.len() - 1
+                    };
+                    gallery.selected = (gallery.selected + step) % EffectKind::ALL.len();
+                    gallery.parameter = 0;
+                    info!("Selected {}", gallery.kind().name());
+                }
+                KeyCode::Space => {
+                    let kind = gallery.kind();
+                    match gallery.stack.iter().position(|k| *k == kind) {
+                        Some(index) => {
+                            gallery.stack.remove(index);
+                        }
+                        None => gallery.stack.push(kind),
+                    }
+                    restack = true;
+                }
+                KeyCode::PageUp | KeyCode::PageDown => {
+                    let kind = gallery.kind();
+                    let Some(index) = gallery.stack.iter().position(|k| *k == kind) else {
+                        continue;
+                    };
+                    let other = if key == KeyCode::PageUp {
+                        index.checked_sub(1)
+                    } else {
+                        Some(index + 1).filter(|other| *other < gallery.stack.len())
+                    };
+                    if let Some(other) = other {
+                        gallery.stack.swap(index, other);
+                        restack = true;
+                    }
+                }
+                KeyCode::Tab => {
+                    let parameters = parameters(gallery.kind());
+                    if parameters.is_empty() {
+                        info!("{} has no parameters", gallery.kind().name());
+                        continue;
+                    }
+                    gallery.parameter = (gallery.parameter + 1) % parameters.len();
+                    info!(
+                        "Selected {}.{}",
+                        gallery.kind().name(),
+                        parameters[gallery.parameter]
+                    );
+                }
+                KeyCode::Up | KeyCode::Down => {
+                    let direction = if key == KeyCode::Up { 1.0 } else { -1.0 };
+                    tweak(world, camera, &gallery, direction);
+                }
+                _ => {}
+            }
+        }
+
+        if restack {
+            apply_stack(world, camera, &gallery.stack);
+        }
+    });
+}
+
+/// The names of the parameters of a kind of effect.
+fn parameters(kind: EffectKind) -> Vec<String> {
+    let effect = kind.default_effect();
+    let ReflectRef::Struct(effect) = effect.as_reflect().reflect_ref() else {
+        return vec![];
+    };
+
+    (0..effect.field_len())
+        .filter_map(|index| effect.name_at(index))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Nudges the selected parameter of the selected effect on the camera, if it is a number or a flag.
+fn tweak(world: &mut World, camera: Entity, gallery: &Gallery, direction: f32) {
+    let kind = gallery.kind();
+    let Some(parameter) = parameters(kind).get(gallery.parameter).cloned() else {
+        return;
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let reflect_component = type_registry
+        .get_with_short_name(kind.name())
+        .and_then(|registration| registration.data::<ReflectComponent>())
+        .expect("Effects should be registered");
+
+    let mut entity = world.entity_mut(camera);
+    let Some(mut effect) = reflect_component.reflect_mut(&mut entity) else {
+        info!(
+            "{} is not in the stack, press [Space] to add it",
+            kind.name()
+        );
+        return;
+    };
+    let Ok(value) = effect.reflect_path_mut(parameter.as_str()) else {
+        return;
+    };
+
+    // Steps are relative to the value, so large and small parameters both change at a useful pace.
+    if let Some(value) = value.downcast_mut::<f32>() {
+        *value += direction * 0.05 * value.abs().max(1.0);
+        info!("{}.{parameter}: {value:.3}", kind.name());
+    } else if let Some(value) = value.downcast_mut::<u32>() {
+        *value = value.saturating_add_signed(direction as i32);
+        info!("{}.{parameter}: {value}", kind.name());
+    } else if let Some(value) = value.downcast_mut::<bool>() {
+        *value = !*value;
+        info!("{}.{parameter}: {value}", kind.name());
+    } else {
+        info!("{}.{parameter} can't be changed here", kind.name());
+    }
+}
+
+/// Makes the effects on the camera match the stack, keeping the parameters of effects which stay.
+fn apply_stack(world: &mut World, camera: Entity, stack: &[EffectKind]) {
+    let current = EffectState::capture(&world.entity(camera));
+
+    let state = stack
+        .iter()
+        .enumerate()
+        .fold(EffectState::default(), |state, (index, kind)| {
+            let effect = current
+                .get(*kind)
+                .map(|entry| entry.effect)
+                .unwrap_or_else(|| kind.default_effect());
+            state.with(effect, Some(index as f32))
+        });
+    state.apply(&mut world.entity_mut(camera));
+
+    let mut effects = state.effects().to_vec();
+    effects.sort_by(|a, b| {
+        a.order
+            .partial_cmp(&b.order)
+            .expect("Orders should be numbers")
+    });
+    let preset = EffectPreset {
+        effects,
+        ..default()
+    };
+
+    match ron::ser::to_string_pretty(&preset, ron::ser::PrettyConfig::default()) {
+        Ok(ron) => info!("The stack is now:\n{ron}"),
+        Err(err) => error!("Could not print the stack: {err}"),
+    }
+}
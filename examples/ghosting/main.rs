@@ -0,0 +1,59 @@
+//! Shows trails behind the moving shapes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::ghosting::Ghosting, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Ghosting>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change decay, [Left|Right] to change zoom, [D] to toggle drift");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        Ghosting::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Ghosting>) {
+    let mut ghosting = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        ghosting.decay = (ghosting.decay + 0.05).min(0.99);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        ghosting.decay = (ghosting.decay - 0.05).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        ghosting.zoom += 0.005;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        ghosting.zoom -= 0.005;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::D) {
+        ghosting.drift = if ghosting.drift == Vec2::ZERO {
+            Vec2::new(0.0, -0.002)
+        } else {
+            Vec2::ZERO
+        };
+    }
+}
@@ -0,0 +1,98 @@
+//! Shows a glow which only comes from the intended emitters, and not from other bright parts
+//! of the scene such as a white wall or the white text of the UI.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        effect_mask::{EffectMask, EffectMaskChannel},
+        glow::Glow,
+        selective::SelectiveEffect,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Glow>, update))
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!(
+        "Press [Space] to switch between a glow from the emitters only, and from everything bright"
+    );
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        EffectMask,
+        Glow {
+            emitter_mask: Some(EffectMaskChannel::Red),
+            ..default()
+        },
+    ));
+
+    // The emitters, drawn into the mask.
+    for x in [-4.0, 4.0] {
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(
+                    shape::UVSphere {
+                        radius: 0.5,
+                        ..default()
+                    }
+                    .into(),
+                ),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgb(1.0, 0.8, 0.4),
+                    emissive: Color::rgb(1.0, 0.8, 0.4),
+                    ..default()
+                }),
+                transform: Transform::from_xyz(x, 4.5, 2.0),
+                ..default()
+            },
+            SelectiveEffect {
+                channel: EffectMaskChannel::Red,
+            },
+        ));
+    }
+
+    // A white wall, as bright as the emitters but not one of them.
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(shape::Box::new(3.0, 2.0, 0.2).into()),
+        material: materials.add(StandardMaterial {
+            base_color: Color::WHITE,
+            unlit: true,
+            ..default()
+        }),
+        transform: Transform::from_xyz(0.0, 4.5, 1.0),
+        ..default()
+    });
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Glow>) {
+    let mut glow = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        glow.emitter_mask = match glow.emitter_mask {
+            Some(_) => None,
+            None => Some(EffectMaskChannel::Red),
+        };
+    }
+}
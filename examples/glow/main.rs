@@ -0,0 +1,58 @@
+//! Shows a glow around the bright parts of the scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::glow::Glow, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (examples_common::print_on_change::<Glow>, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!(
+        "Press [Up|Down] to change intensity, [Left|Right] to change threshold, [T] to toggle tint"
+    );
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        Glow::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Glow>) {
+    let mut glow = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        glow.intensity += 0.25;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        glow.intensity = (glow.intensity - 0.25).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        glow.threshold += 0.05;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        glow.threshold = (glow.threshold - 0.05).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::T) {
+        glow.tint = if glow.tint == Color::WHITE {
+            Color::rgb(1.0, 0.6, 0.3)
+        } else {
+            Color::WHITE
+        };
+    }
+}
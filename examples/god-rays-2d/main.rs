@@ -0,0 +1,117 @@
+//! Shows light shafts from a sun behind the platforms of a 2d side-scroller.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_vfx_bag::{post_processing::god_rays_2d::GodRays2d, BevyVfxBagPlugin};
+
+/// The layer of the sprites which block the light.
+const OCCLUDERS: u8 = 1;
+
+#[derive(Component)]
+struct Sun;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(ClearColor(Color::rgb(0.35, 0.45, 0.6)))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<GodRays2d>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Move the mouse to move the sun, [Up|Down] to change the exposure, [Left|Right] to change the decay");
+
+    commands.spawn((
+        Camera2dBundle::default(),
+        GodRays2d {
+            occluders: RenderLayers::layer(OCCLUDERS),
+            ..default()
+        },
+    ));
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(1.0, 0.95, 0.8),
+                custom_size: Some(Vec2::splat(80.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 200.0, 0.0),
+            ..default()
+        },
+        Sun,
+    ));
+
+    // Platforms with gaps between them, which the light shines through.
+    // They are seen by the camera and block the light, so they are on both layers.
+    let platforms = [
+        (Vec2::new(-420.0, 60.0), Vec2::new(260.0, 30.0)),
+        (Vec2::new(-60.0, 40.0), Vec2::new(180.0, 30.0)),
+        (Vec2::new(260.0, 80.0), Vec2::new(220.0, 30.0)),
+        (Vec2::new(-250.0, -80.0), Vec2::new(200.0, 30.0)),
+        (Vec2::new(120.0, -60.0), Vec2::new(160.0, 30.0)),
+        (Vec2::new(0.0, -300.0), Vec2::new(1400.0, 120.0)),
+    ];
+    for (position, size) in platforms {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.15, 0.12, 0.1),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(1.0)),
+                ..default()
+            },
+            RenderLayers::from_layers(&[0, OCCLUDERS]),
+        ));
+    }
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    windows: Query<&Window>,
+    mut cameras: Query<(&Camera, &GlobalTransform, &mut GodRays2d)>,
+    mut sun: Query<&mut Transform, With<Sun>>,
+) {
+    let (camera, camera_transform, mut god_rays) = cameras.single_mut();
+    let mut sun = sun.single_mut();
+
+    if let Some(cursor) = windows.single().cursor_position() {
+        if let Some(position) = camera.viewport_to_world_2d(camera_transform, cursor) {
+            sun.translation = position.extend(0.0);
+        }
+    }
+
+    // Keep the light on the sun.
+    if let (Some(position), Some(size)) = (
+        camera.world_to_viewport(camera_transform, sun.translation),
+        camera.logical_viewport_size(),
+    ) {
+        let light_position = position / size;
+        if god_rays.light_position != light_position {
+            god_rays.light_position = light_position;
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        god_rays.exposure += 0.25;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        god_rays.exposure = (god_rays.exposure - 0.25).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        god_rays.decay = (god_rays.decay + 0.005).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        god_rays.decay -= 0.005;
+    }
+}
@@ -0,0 +1,61 @@
+//! Shows the GPU time of each effect, logged as Bevy diagnostics.
+//!
+//! Needs a GPU and backend with timestamp queries, e.g. Vulkan or DX12.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{diagnostic::LogDiagnosticsPlugin, ecs::query::Has, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::{
+        blur::Blur, chromatic_aberration::ChromaticAberration, glow::Glow, masks::Mask,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+        LogDiagnosticsPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [G] to toggle the glow, and see its time drop out of the total");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Blur::default(),
+        Glow::default(),
+        ChromaticAberration::default(),
+        Mask::vignette(),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    cameras: Query<(Entity, Has<Glow>), With<Camera>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::G) {
+        return;
+    }
+
+    for (camera, has_glow) in &cameras {
+        if has_glow {
+            commands.entity(camera).remove::<Glow>();
+        } else {
+            commands.entity(camera).insert(Glow::default());
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Shows the halftone effect, which prints the scene as CMYK dots like a comic book.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::halftone::Halftone, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Halftone>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change dot size, [Left|Right] to change threshold, [R] to rotate the grids");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Halftone::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Halftone>) {
+    let mut halftone = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Left) {
+        halftone.threshold -= 0.05;
+    } else if keyboard_input.just_pressed(KeyCode::Right) {
+        halftone.threshold += 0.05;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        halftone.dot_size += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        halftone.dot_size -= 1.0;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        let step = 5f32.to_radians();
+        halftone.cyan_angle += step;
+        halftone.magenta_angle += step;
+        halftone.yellow_angle += step;
+        halftone.black_angle += step;
+    }
+
+    halftone.threshold = halftone.threshold.clamp(0., 1.);
+    halftone.dot_size = halftone.dot_size.max(1.);
+}
@@ -0,0 +1,83 @@
+//! Shows effects on an HDR camera, before and after tonemapping.
+//! Before tonemapping, the glow only picks up the emissive sphere, which is far brighter than white.
+//! After tonemapping, everything is at most white, so the glow can't tell it apart from the lit shapes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{glow::Glow, masks::Mask, EffectStage, Order, PostProcessingOrder},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press [S] to move the glow before or after tonemapping, [H] to toggle HDR");
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Glow {
+            threshold: 1.5,
+            ..default()
+        },
+        Order::<Glow>::new(0.0).with_stage(EffectStage::BeforeTonemapping),
+        Mask::vignette().order_in(EffectStage::AfterTonemapping, 0.0),
+    ));
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(
+            shape::UVSphere {
+                radius: 0.75,
+                ..default()
+            }
+            .into(),
+        ),
+        material: materials.add(StandardMaterial {
+            emissive: Color::rgb_linear(8.0, 3.0, 1.0),
+            ..default()
+        }),
+        transform: Transform::from_xyz(0.0, 4.0, 0.0),
+        ..default()
+    });
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<(&mut Camera, &mut Order<Glow>)>) {
+    let (mut camera, mut order) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        order.stage = match order.stage {
+            EffectStage::BeforeTonemapping => EffectStage::AfterTonemapping,
+            EffectStage::AfterTonemapping => EffectStage::BeforeTonemapping,
+        };
+        info!("Glow runs {}", order.stage);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::H) {
+        camera.hdr = !camera.hdr;
+        info!("HDR: {}", camera.hdr);
+    }
+}
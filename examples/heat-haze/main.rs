@@ -0,0 +1,95 @@
+//! Shows the heat haze effect, confined to a part of the view or shaped by a mask.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vfx_bag::{post_processing::heat_haze::HeatHaze, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<HeatHaze>, update),
+    )
+    .run();
+}
+
+#[derive(Resource)]
+struct RadialMask(Handle<Image>);
+
+fn startup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    info!("Press [1|2|3] to haze the whole view, the bottom half, or a radial mask. [Up|Down] to change strength");
+
+    // A mask which is strongest in the middle of the view, and fades out towards the edges.
+    const SIZE: u32 = 64;
+    let data = (0..SIZE * SIZE)
+        .flat_map(|i| {
+            let uv = Vec2::new((i % SIZE) as f32, (i / SIZE) as f32) / (SIZE - 1) as f32;
+            let haze = (1.0 - uv.distance(Vec2::splat(0.5)) * 2.0).clamp(0.0, 1.0);
+            let value = (haze * 255.0) as u8;
+            [value, value, value, 255]
+        })
+        .collect();
+
+    let mask = images.add(Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+    ));
+    commands.insert_resource(RadialMask(mask));
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        HeatHaze::default(),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mask: Res<RadialMask>,
+    mut query: Query<&mut HeatHaze>,
+) {
+    let mut heat_haze = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        *heat_haze = HeatHaze {
+            strength: heat_haze.strength,
+            ..default()
+        };
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        *heat_haze = HeatHaze {
+            strength: heat_haze.strength,
+            ..HeatHaze::in_rect(Rect::new(0.0, 0.5, 1.0, 1.0))
+        };
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        *heat_haze = HeatHaze {
+            strength: heat_haze.strength,
+            ..HeatHaze::with_mask(mask.0.clone())
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        heat_haze.strength += 0.002;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        heat_haze.strength = (heat_haze.strength - 0.002).max(0.0);
+    }
+}
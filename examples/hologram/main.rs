@@ -0,0 +1,110 @@
+//! Shows a camera feed as a hologram on an in-world projection.
+//!
+//! A camera renders the scene into an image with the [`Hologram`] effect,
+//! and the image is shown on a quad in the scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    prelude::*,
+    render::{camera::RenderTarget, view::RenderLayers},
+};
+use bevy_vfx_bag::{
+    post_processing::{hologram::Hologram, process_image::output_image},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Hologram>, update),
+    )
+    .run();
+}
+
+/// The layer of the projection, which the feed camera doesn't see, so it doesn't show itself.
+const PROJECTION_LAYER: u8 = 1;
+
+fn startup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!(
+        "Press [Up|Down] to change the line density, [Left|Right] to change the jitter amplitude"
+    );
+    info!("Press [D] to toggle the dropouts");
+
+    let feed = images.add(output_image(UVec2::new(640, 360)));
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                target: RenderTarget::Image(feed.clone()),
+                order: -1,
+                ..default()
+            },
+            transform: Transform::from_xyz(6.0, 3.0, 6.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Hologram::default(),
+    ));
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Quad::new(Vec2::new(6.4, 3.6)).into()),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(feed),
+                unlit: true,
+                alpha_mode: AlphaMode::Add,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, 4.0, -3.0),
+            ..default()
+        },
+        RenderLayers::layer(PROJECTION_LAYER),
+    ));
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        RenderLayers::default().with(PROJECTION_LAYER),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Hologram>) {
+    let mut hologram = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        hologram.line_density += 20.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        hologram.line_density = (hologram.line_density - 20.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        hologram.jitter_amplitude += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        hologram.jitter_amplitude = (hologram.jitter_amplitude - 1.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::D) {
+        hologram.dropout_frequency = if hologram.dropout_frequency == 0.0 {
+            Hologram::default().dropout_frequency
+        } else {
+            0.0
+        };
+    }
+}
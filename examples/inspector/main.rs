@@ -0,0 +1,40 @@
+//! Shows the inspector window, for changing the effects on cameras at runtime.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    inspector::PostProcessingInspectorPlugin,
+    post_processing::{
+        chromatic_aberration::ChromaticAberration, masks::Mask, PostProcessingOrder,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+        PostProcessingInspectorPlugin,
+    ))
+    .add_systems(Startup, startup)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Drag the effects by their handle to reorder them");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Name::new("Main camera"),
+        ChromaticAberration::default().order(0.0),
+        Mask::vignette().order(1.0),
+    ));
+}
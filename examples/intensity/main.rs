@@ -0,0 +1,82 @@
+//! Shows softening and disabling effects without removing them.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        chromatic_aberration::ChromaticAberration,
+        intensity::{Intensity, PostProcessingSettings},
+        wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<Intensity<Wave>>,
+            examples_common::print_on_change::<PostProcessingSettings>,
+            update,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [W] to toggle the wave, [Left|Right] to change its intensity");
+    info!("Press [Space] to toggle all effects, [Up|Down] to change the master intensity");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Wave {
+            waves_x: 4.,
+            speed_x: 2.,
+            amplitude_x: 0.02,
+            ..default()
+        },
+        Intensity::<Wave>::default(),
+        ChromaticAberration::default(),
+        PostProcessingSettings::default(),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut Intensity<Wave>, &mut PostProcessingSettings)>,
+) {
+    let (mut intensity, mut settings) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::W) {
+        intensity.enabled = !intensity.enabled;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        intensity.intensity = (intensity.intensity + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        intensity.intensity = (intensity.intensity - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        settings.enabled = !settings.enabled;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        settings.intensity = (settings.intensity + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        settings.intensity = (settings.intensity - 0.1).max(0.0);
+    }
+}
@@ -0,0 +1,64 @@
+//! Shows the Kuwahara filter, which makes the scene look like an oil painting.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{compute::ComputePath, kuwahara::Kuwahara},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Kuwahara>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change radius, [Left|Right] to change samples per side");
+    info!("Press [C] to switch between the compute and fragment shaders");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Kuwahara::default(),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut compute_path: ResMut<ComputePath>,
+    mut query: Query<&mut Kuwahara>,
+) {
+    let mut kuwahara = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::C) {
+        compute_path.enabled = !compute_path.enabled;
+        info!("Compute shaders enabled: {}", compute_path.enabled);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Left) {
+        kuwahara.samples_per_side = kuwahara.samples_per_side.saturating_sub(1).max(1);
+    } else if keyboard_input.just_pressed(KeyCode::Right) {
+        kuwahara.samples_per_side = (kuwahara.samples_per_side + 1).min(16);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        kuwahara.radius += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        kuwahara.radius = (kuwahara.radius - 1.0).max(0.);
+    }
+}
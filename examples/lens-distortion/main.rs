@@ -0,0 +1,55 @@
+//! Shows barrel and pincushion lens distortion, with chromatic fringing.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::lens_distortion::LensDistortion, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<LensDistortion>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change distortion, [Left|Right] to change fringing, [S] to toggle auto scale");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        LensDistortion::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut LensDistortion>) {
+    let mut lens_distortion = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        lens_distortion.distortion += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        lens_distortion.distortion -= 0.1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        lens_distortion.fringing += 0.02;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        lens_distortion.fringing = (lens_distortion.fringing - 0.02).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        lens_distortion.auto_scale = !lens_distortion.auto_scale;
+    }
+}
@@ -0,0 +1,78 @@
+//! Shows a lens flare from a moving light, which disappears as the light passes behind the shapes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::lens_flare::{LensFlare, LensFlareFollow},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    // The depth prepass can't be read by post processing effects when multisampled.
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+#[derive(Component)]
+struct Light;
+
+fn startup(mut commands: Commands) {
+    info!("The light moves on its own. Press [Up|Down] to change intensity, [Left|Right] to change the number of ghosts");
+
+    // The light has no mesh, since a mesh around it would be closer to the camera than the light itself,
+    // and hide the flare.
+    let light = commands
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 1.0, -6.0)),
+            Light,
+        ))
+        .id();
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        LensFlare::default(),
+        LensFlareFollow::point(light),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut lights: Query<&mut Transform, With<Light>>,
+    mut query: Query<&mut LensFlare>,
+) {
+    for mut transform in &mut lights {
+        transform.translation.x = (time.elapsed_seconds() * 0.3).sin() * 8.0;
+    }
+
+    let mut lens_flare = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        lens_flare.intensity += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        lens_flare.intensity = (lens_flare.intensity - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        lens_flare.ghosts += 1;
+        info!("{}", *lens_flare);
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        lens_flare.ghosts = lens_flare.ghosts.saturating_sub(1);
+        info!("{}", *lens_flare);
+    }
+}
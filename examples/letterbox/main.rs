@@ -0,0 +1,70 @@
+//! Shows cinematic bars with `Letterbox`, moving in and out as for a cutscene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::letterbox::Letterbox, BevyVfxBagPlugin};
+
+/// Widescreen, the usual film ratio, and 4:3, which is a pillarbox on most screens.
+const ASPECT_RATIOS: [f32; 3] = [2.39, 1.85, 4.0 / 3.0];
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Letterbox>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to move the bars in or out, [1|2|3] to change the aspect ratio");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Letterbox::default().animate_in(1.0),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, &mut Letterbox)>,
+) {
+    let Ok((entity, mut letterbox)) = query.get_single_mut() else {
+        // Not added by the animation yet.
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        let bars = Letterbox {
+            progress: 1.0,
+            ..*letterbox
+        };
+        if letterbox.progress > 0.5 {
+            commands.entity(entity).insert(bars.animate_out(1.0));
+        } else {
+            commands.entity(entity).insert(bars.animate_in(1.0));
+        }
+    }
+
+    for (key, aspect_ratio) in [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3]
+        .into_iter()
+        .zip(ASPECT_RATIOS)
+    {
+        if keyboard_input.just_pressed(key) {
+            letterbox.aspect_ratio = aspect_ratio;
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Shows warm light leaks drifting over the image.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::light_leak::LightLeak, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<LightLeak>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the intensity, [Left|Right] to change the interval, [S] to reseed");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        LightLeak::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut LightLeak>) {
+    let mut leak = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        leak.intensity = (leak.intensity + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        leak.intensity = (leak.intensity - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        leak.interval += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        leak.interval = (leak.interval - 1.0).max(1.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        leak.seed += 1;
+    }
+}
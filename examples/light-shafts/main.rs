@@ -0,0 +1,94 @@
+//! Shows light shafts streaming from a glowing orb behind the shapes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::light_shafts::{LightShafts, LightShaftsFollow},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<LightShafts>, update),
+    )
+    .run();
+}
+
+#[derive(Component)]
+struct Orb;
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("The orb moves on its own. Press [Up|Down] to change decay, [Left|Right] to change exposure");
+
+    let orb = commands
+        .spawn((
+            PbrBundle {
+                mesh: meshes.add(
+                    shape::UVSphere {
+                        radius: 1.5,
+                        ..default()
+                    }
+                    .into(),
+                ),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::WHITE,
+                    emissive: Color::rgb(1.0, 0.9, 0.7),
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(0.0, 4.0, -10.0),
+                ..default()
+            },
+            Orb,
+        ))
+        .id();
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        LightShafts::default(),
+        LightShaftsFollow::point(orb),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut orbs: Query<&mut Transform, With<Orb>>,
+    mut query: Query<&mut LightShafts>,
+) {
+    for mut transform in &mut orbs {
+        transform.translation.x = (time.elapsed_seconds() * 0.3).sin() * 8.0;
+    }
+
+    let mut light_shafts = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        light_shafts.decay = (light_shafts.decay + 0.01).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        light_shafts.decay -= 0.01;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        light_shafts.exposure += 0.02;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        light_shafts.exposure = (light_shafts.exposure - 0.02).max(0.0);
+    }
+}
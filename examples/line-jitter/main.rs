@@ -0,0 +1,59 @@
+//! Shows lines of the image jittering sideways, like an unstable analog display.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::line_jitter::LineJitter, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<LineJitter>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the amplitude, [Left|Right] to change the frequency, [R] to toggle the roll");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        LineJitter::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut LineJitter>) {
+    let mut jitter = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        jitter.amplitude += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        jitter.amplitude = (jitter.amplitude - 1.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        jitter.frequency += 2.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        jitter.frequency = (jitter.frequency - 2.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        jitter.roll = if jitter.roll == 0.0 {
+            LineJitter::rolling().roll
+        } else {
+            0.0
+        };
+    }
+}
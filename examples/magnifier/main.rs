@@ -0,0 +1,64 @@
+//! Shows a magnifier following the cursor.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{input::mouse::MouseWheel, prelude::*, window::PrimaryWindow};
+use bevy_vfx_bag::{post_processing::magnifier::Magnifier, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Magnifier>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Move the cursor to move the magnifier, scroll to zoom, and press [Up|Down] to change the radius");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Magnifier::default(),
+    ));
+}
+
+fn update(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut scroll: EventReader<MouseWheel>,
+    mut query: Query<&mut Magnifier>,
+) {
+    let mut magnifier = query.single_mut();
+
+    let window = windows.single();
+    if let Some(cursor) = window.cursor_position() {
+        let center = cursor / Vec2::new(window.width(), window.height());
+
+        // Only changed when moved, so it's only printed when moved.
+        if magnifier.center != center {
+            magnifier.center = center;
+        }
+    }
+
+    for event in scroll.iter() {
+        magnifier.zoom = (magnifier.zoom + event.y.signum() * 0.25).max(1.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        magnifier.radius += 0.05;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        magnifier.radius = (magnifier.radius - 0.05).max(0.05);
+    }
+}
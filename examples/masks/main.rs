@@ -3,7 +3,10 @@ mod examples_common;
 
 use bevy::prelude::*;
 use bevy_vfx_bag::{
-    post_processing::masks::{Mask, MaskVariant},
+    post_processing::{
+        masks::{Mask, MaskVariant},
+        precompile::PrecompileVariants,
+    },
     BevyVfxBagPlugin,
 };
 
@@ -28,7 +31,8 @@ fn startup(mut commands: Commands) {
                 .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
             ..default()
         })
-        .insert(Mask::default());
+        // Switching masks doesn't hitch, since all of them are compiled along with the first.
+        .insert((Mask::default(), PrecompileVariants));
 }
 
 fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Mask, With<Camera>>) {
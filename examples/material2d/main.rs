@@ -0,0 +1,151 @@
+//! Shows effects drawn on quads of their own as materials, instead of on everything a camera sees.
+//! Each quad shows the same image with a different effect.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    prelude::*,
+    sprite::{Material2d, MaterialMesh2dBundle},
+};
+use bevy_vfx_bag::{
+    post_processing::{
+        chromatic_aberration::ChromaticAberration, line_jitter::LineJitter, masks::Mask,
+        material::EffectMaterial2d, pixelate::Pixelate, wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut masks: ResMut<Assets<EffectMaterial2d<Mask>>>,
+    mut pixelates: ResMut<Assets<EffectMaterial2d<Pixelate>>>,
+    mut waves: ResMut<Assets<EffectMaterial2d<Wave>>>,
+    mut aberrations: ResMut<Assets<EffectMaterial2d<ChromaticAberration>>>,
+    mut jitters: ResMut<Assets<EffectMaterial2d<LineJitter>>>,
+) {
+    info!("Press [Up|Down] to fade the effects out and in");
+
+    let image: Handle<Image> = asset_server.load("textures/ascii.png");
+    let quad = meshes.add(shape::Quad::new(Vec2::splat(220.0)).into());
+
+    commands.spawn(Camera2dBundle::default());
+
+    spawn(
+        &mut commands,
+        &quad,
+        -240.0,
+        120.0,
+        masks.add(EffectMaterial2d::new(Mask::vignette(), image.clone())),
+    );
+    spawn(
+        &mut commands,
+        &quad,
+        0.0,
+        120.0,
+        pixelates.add(EffectMaterial2d::new(
+            Pixelate {
+                block_size: 12.0,
+                ..default()
+            },
+            image.clone(),
+        )),
+    );
+    spawn(
+        &mut commands,
+        &quad,
+        240.0,
+        120.0,
+        waves.add(EffectMaterial2d::new(
+            Wave {
+                waves_x: 4.0,
+                speed_x: 0.5,
+                amplitude_x: 0.02,
+                ..default()
+            },
+            image.clone(),
+        )),
+    );
+    spawn(
+        &mut commands,
+        &quad,
+        -120.0,
+        -120.0,
+        aberrations.add(EffectMaterial2d::new(
+            ChromaticAberration::default(),
+            image.clone(),
+        )),
+    );
+    spawn(
+        &mut commands,
+        &quad,
+        120.0,
+        -120.0,
+        jitters.add(EffectMaterial2d::new(LineJitter::rolling(), image)),
+    );
+}
+
+fn spawn<M: Material2d>(
+    commands: &mut Commands,
+    quad: &Handle<Mesh>,
+    x: f32,
+    y: f32,
+    material: Handle<M>,
+) {
+    commands.spawn(MaterialMesh2dBundle {
+        mesh: quad.clone().into(),
+        material,
+        transform: Transform::from_xyz(x, y, 0.0),
+        ..default()
+    });
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut fade: Local<f32>,
+    mut masks: ResMut<Assets<EffectMaterial2d<Mask>>>,
+    mut pixelates: ResMut<Assets<EffectMaterial2d<Pixelate>>>,
+    mut waves: ResMut<Assets<EffectMaterial2d<Wave>>>,
+    mut aberrations: ResMut<Assets<EffectMaterial2d<ChromaticAberration>>>,
+    mut jitters: ResMut<Assets<EffectMaterial2d<LineJitter>>>,
+) {
+    let change = if keyboard_input.just_pressed(KeyCode::Up) {
+        0.25
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        -0.25
+    } else {
+        return;
+    };
+
+    *fade = (*fade + change).clamp(0.0, 1.0);
+    info!("Fade: {:.2}", *fade);
+
+    for (_, material) in masks.iter_mut() {
+        material.fade = *fade;
+    }
+    for (_, material) in pixelates.iter_mut() {
+        material.fade = *fade;
+    }
+    for (_, material) in waves.iter_mut() {
+        material.fade = *fade;
+    }
+    for (_, material) in aberrations.iter_mut() {
+        material.fade = *fade;
+    }
+    for (_, material) in jitters.iter_mut() {
+        material.fade = *fade;
+    }
+}
@@ -0,0 +1,60 @@
+//! Shows effect parameters driven by signals, here chromatic aberration kicking with a beat.
+//!
+//! A real app would feed the [`AudioLevel`] from its audio library,
+//! this example makes up a beat at 120 BPM instead.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    modulation::{AudioLevel, Envelope, Modulate, Random, SineWave},
+    post_processing::{chromatic_aberration::ChromaticAberration, masks::Mask},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (beat, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1|2|3] to drive the vignette by the beat, a sine wave, or randomly");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        ChromaticAberration::default(),
+        Modulate::<ChromaticAberration>::new(Envelope::new(0.01, 0.25), "magnitude_r", 0.0, 0.03),
+        Mask::vignette(),
+        Modulate::<Mask>::new(Envelope::default(), "strength", 0.5, 2.5),
+    ));
+}
+
+/// A kick drum on every beat, which dies down quickly.
+fn beat(time: Res<Time>, mut audio_level: ResMut<AudioLevel>) {
+    let beats = time.elapsed_seconds() * 2.0;
+    *audio_level = AudioLevel((1.0 - beats.fract() * 4.0).max(0.0));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Modulate<Mask>>) {
+    let mut modulate = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        modulate.signal = Envelope::default().into();
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        modulate.signal = SineWave::default().into();
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        modulate.signal = Random::default().into();
+    }
+}
@@ -0,0 +1,65 @@
+//! Shows motion blur while the camera orbits around the shapes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::prepass::{DepthPrepass, MotionVectorPrepass},
+    prelude::*,
+};
+use bevy_vfx_bag::{post_processing::motion_blur::MotionBlur, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    // The prepass textures can't be read by post processing effects when multisampled.
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<MotionBlur>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the shutter angle, [Left|Right] to change the max samples");
+
+    commands.spawn((
+        Camera3dBundle::default(),
+        DepthPrepass,
+        MotionVectorPrepass,
+        MotionBlur::default(),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut cameras: Query<&mut Transform, With<Camera3d>>,
+    mut query: Query<&mut MotionBlur>,
+) {
+    for mut transform in &mut cameras {
+        let angle = time.elapsed_seconds() * 1.5;
+        *transform = Transform::from_xyz(angle.sin() * 12.0, 4.0, angle.cos() * 12.0)
+            .looking_at(Vec3::new(0., 1., 0.), Vec3::Y);
+    }
+
+    let mut motion_blur = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        motion_blur.shutter_angle = (motion_blur.shutter_angle + 45.0).min(360.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        motion_blur.shutter_angle = (motion_blur.shutter_angle - 45.0).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        motion_blur.max_samples *= 2;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        motion_blur.max_samples = (motion_blur.max_samples / 2).max(1);
+    }
+}
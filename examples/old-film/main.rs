@@ -0,0 +1,58 @@
+//! Shows the scene as an old, worn film print.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::old_film::OldFilm, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<OldFilm>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1] to toggle sepia, [2] scratches, [3] dust, [4] flicker, [5] jitter");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        OldFilm::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut OldFilm>) {
+    let mut old_film = query.single_mut();
+    let defaults = OldFilm::default();
+
+    let toggle = |value: &mut f32, on: f32| *value = if *value > 0.0 { 0.0 } else { on };
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        toggle(&mut old_film.sepia, defaults.sepia);
+    }
+    if keyboard_input.just_pressed(KeyCode::Key2) {
+        toggle(&mut old_film.scratches, defaults.scratches);
+    }
+    if keyboard_input.just_pressed(KeyCode::Key3) {
+        toggle(&mut old_film.dust, defaults.dust);
+    }
+    if keyboard_input.just_pressed(KeyCode::Key4) {
+        toggle(&mut old_film.flicker, defaults.flicker);
+    }
+    if keyboard_input.just_pressed(KeyCode::Key5) {
+        toggle(&mut old_film.jitter, defaults.jitter);
+    }
+}
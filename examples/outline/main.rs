@@ -0,0 +1,68 @@
+//! Shows the outline effect.
+//! Note that the camera needs a depth prepass (and optionally a normal prepass), and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::prepass::{DepthPrepass, NormalPrepass},
+    prelude::*,
+};
+use bevy_vfx_bag::{post_processing::outline::Outline, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Outline>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change width, [Left|Right] to change the normal threshold, [B] for a blueprint look");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        NormalPrepass,
+        Outline::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Outline>) {
+    let mut outline = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        outline.width += 0.5;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        outline.width -= 0.5;
+    }
+    outline.width = outline.width.max(0.5);
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        outline.normal_threshold += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        outline.normal_threshold -= 0.1;
+    }
+    outline.normal_threshold = outline.normal_threshold.max(0.);
+
+    if keyboard_input.just_pressed(KeyCode::B) {
+        outline.color = if outline.color == Color::BLACK {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        };
+    }
+}
@@ -0,0 +1,138 @@
+//! Shows a world camera, a weapon camera and a HUD camera drawing into the same window,
+//! each with effects of its own which only apply to what that camera draws.
+//! The vignette of the HUD camera can run on the composited image instead.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig, ecs::query::Has, prelude::*,
+    render::view::RenderLayers,
+};
+use bevy_vfx_bag::{
+    post_processing::{
+        chromatic_aberration::ChromaticAberration, masks::Mask, overlay::ApplyAfterCompositing,
+        pixelate::Pixelate,
+    },
+    BevyVfxBagPlugin,
+};
+
+/// The layer only the weapon camera sees.
+const WEAPON_LAYER: u8 = 1;
+
+/// The layer only the HUD camera sees.
+const HUD_LAYER: u8 = 2;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!(
+        "Press [A] to apply the vignette of the HUD camera after compositing, or only to the HUD"
+    );
+
+    // The world, pixelated.
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Pixelate::default(),
+    ));
+
+    // The weapon, with chromatic aberration, over the world.
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
+            camera_3d: Camera3d {
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(WEAPON_LAYER),
+        ChromaticAberration {
+            magnitude_r: 0.02,
+            magnitude_g: 0.0,
+            magnitude_b: 0.02,
+            ..default()
+        },
+    ));
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Box::new(0.15, 0.15, 0.8).into()),
+            material: materials.add(Color::rgb(0.8, 0.6, 0.5).into()),
+            transform: Transform::from_xyz(0.35, -0.3, -0.6),
+            ..default()
+        },
+        RenderLayers::layer(WEAPON_LAYER),
+    ));
+
+    // The HUD, with a vignette, over the world and the weapon.
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 2,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            ..default()
+        },
+        RenderLayers::layer(HUD_LAYER),
+        Mask::vignette(),
+    ));
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.2, 0.9, 0.4, 0.8),
+                custom_size: Some(Vec2::new(240.0, 24.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, -280.0, 0.0),
+            ..default()
+        },
+        RenderLayers::layer(HUD_LAYER),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    hud: Query<(Entity, Has<ApplyAfterCompositing<Mask>>), With<Camera2d>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::A) {
+        return;
+    }
+
+    let (entity, after_compositing) = hud.single();
+    if after_compositing {
+        commands
+            .entity(entity)
+            .remove::<ApplyAfterCompositing<Mask>>();
+        info!("The vignette only applies to the HUD");
+    } else {
+        commands
+            .entity(entity)
+            .insert(ApplyAfterCompositing::<Mask>::new());
+        info!("The vignette applies to the composited image");
+    }
+}
@@ -0,0 +1,73 @@
+//! Limits the image to the colors of old consoles.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{palette_swap::PaletteSwap, pixelate::Pixelate, PostProcessingOrder},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<PaletteSwap>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!(
+        "Press [1|2|3] to pick the Game Boy, NES or CGA palette, [Up|Down] to change the dithering"
+    );
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Pixelate {
+            block_size: 4.0,
+            ..default()
+        }
+        .order(0.0),
+        PaletteSwap {
+            dither_scale: 4.0,
+            ..PaletteSwap::game_boy().with_dither(0.5)
+        }
+        .order(1.0),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut PaletteSwap>) {
+    let mut palette_swap = query.single_mut();
+
+    let palette = if keyboard_input.just_pressed(KeyCode::Key1) {
+        Some(PaletteSwap::game_boy())
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        Some(PaletteSwap::nes())
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        Some(PaletteSwap::cga())
+    } else {
+        None
+    };
+
+    if let Some(palette) = palette {
+        palette_swap.palette = palette.palette;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        palette_swap.dither = (palette_swap.dither + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        palette_swap.dither = (palette_swap.dither - 0.1).max(0.0);
+    }
+}
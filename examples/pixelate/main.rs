@@ -5,7 +5,10 @@
 mod examples_common;
 
 use bevy::prelude::*;
-use bevy_vfx_bag::{post_processing::pixelate::Pixelate, BevyVfxBagPlugin};
+use bevy_vfx_bag::{
+    post_processing::pixelate::{Pixelate, PixelateSampling},
+    BevyVfxBagPlugin,
+};
 
 fn main() {
     let mut app = App::new();
@@ -21,7 +24,7 @@ fn main() {
 }
 
 fn startup(mut commands: Commands) {
-    info!("Press [t] to toggle, [up/down] to change");
+    info!("Press [t] to toggle, [up/down] to change, [s] to change sampling");
 
     commands.spawn((
         Camera3dBundle {
@@ -59,5 +62,12 @@ fn update(
         } else if keyboard_input.just_pressed(KeyCode::Down) {
             settings.block_size -= 1.0;
         };
+
+        if keyboard_input.just_pressed(KeyCode::S) {
+            settings.sampling = match settings.sampling {
+                PixelateSampling::Point => PixelateSampling::Dominant,
+                PixelateSampling::Dominant => PixelateSampling::Point,
+            };
+        }
     }
 }
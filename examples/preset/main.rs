@@ -0,0 +1,33 @@
+//! Shows loading an effect stack from a `.vfx.ron` preset.
+//!
+//! Edit `assets/presets/underwater.vfx.ron` while this runs to see the camera update.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{preset::EffectPreset, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .run();
+}
+
+fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    info!("Edit assets/presets/underwater.vfx.ron to change the effects");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        asset_server.load::<EffectPreset, _>("presets/underwater.vfx.ron"),
+    ));
+}
@@ -0,0 +1,87 @@
+//! Shows effects applied to an image instead of a camera, e.g. for UI thumbnails.
+//! The image on the left is the input, the one on the right is the output.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        pixelate::Pixelate,
+        process_image::{output_image, ImageProcessed, ProcessImage},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (update, report))
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    info!("Press [Up|Down] to change the block size, the image is processed again each time");
+
+    let input = asset_server.load("textures/ascii.png");
+    let output = images.add(output_image(UVec2::new(512, 512)));
+
+    commands.spawn((
+        ProcessImage::once(input.clone(), output.clone()),
+        Pixelate {
+            block_size: 16.0,
+            ..default()
+        },
+    ));
+
+    commands.spawn(Camera2dBundle::default());
+
+    for (image, x) in [(input, -300.0), (output, 300.0)] {
+        commands.spawn(SpriteBundle {
+            texture: image,
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(512.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(x, 0.0, 0.0),
+            ..default()
+        });
+    }
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut ProcessImage, &mut Pixelate)>,
+) {
+    let (mut process, mut pixelate) = query.single_mut();
+
+    let change = if keyboard_input.just_pressed(KeyCode::Up) {
+        4.0
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        -4.0
+    } else {
+        return;
+    };
+
+    pixelate.block_size = (pixelate.block_size + change).max(4.0);
+    process.again();
+}
+
+fn report(mut events: EventReader<ImageProcessed>, query: Query<&Pixelate>) {
+    for event in events.iter() {
+        if let Ok(pixelate) = query.get(event.entity) {
+            info!(
+                "Processed the image with a block size of {}",
+                pixelate.block_size
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Shows the warped, jittery textures of the original PlayStation, approximated in post.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{pixelate::Pixelate, psx_wobble::PsxWobble, PostProcessingOrder},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<PsxWobble>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the wobble, [Left|Right] to change the snapping, [P] to toggle pixelation");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        PsxWobble::default(),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, &mut PsxWobble, Option<&Pixelate>)>,
+) {
+    let (entity, mut wobble, pixelate) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        wobble.wobble += 0.5;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        wobble.wobble = (wobble.wobble - 0.5).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        wobble.snap += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        wobble.snap = (wobble.snap - 1.0).max(1.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::P) {
+        if pixelate.is_some() {
+            commands.entity(entity).remove::<Pixelate>();
+        } else {
+            // A low resolution completes the look.
+            commands.entity(entity).insert(
+                Pixelate {
+                    block_size: 3.0,
+                    ..default()
+                }
+                .order(1.0),
+            );
+        }
+    }
+}
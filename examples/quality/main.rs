@@ -0,0 +1,60 @@
+//! Shows switching the quality of the effects at runtime with `SetQuality`.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        depth_of_field::DepthOfField,
+        glow::Glow,
+        kuwahara::Kuwahara,
+        quality::{QualitySettings, QualityTier, SetQuality},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1|2|3] to switch to low, medium or high quality");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthOfField::default(),
+        Glow::default(),
+        Kuwahara::default(),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    quality: Res<QualitySettings>,
+    mut set_quality: EventWriter<SetQuality>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        set_quality.send(QualityTier::Low.into());
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        set_quality.send(QualityTier::Medium.into());
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        set_quality.send(QualityTier::High.into());
+    }
+
+    if quality.is_changed() && !quality.is_added() {
+        info!("{:?}", *quality);
+    }
+}
@@ -0,0 +1,118 @@
+//! Shows raindrops and frosted glass in front of a scrolling 2d scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        frosted_glass::FrostedGlass,
+        raindrops::{Raindrops, RefractionMode},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(ClearColor(Color::rgb(0.2, 0.25, 0.35)))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<Raindrops>,
+            examples_common::print_on_change::<FrostedGlass>,
+            update,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Left|Right] to scroll, [Up|Down] to change the parallax, [P] to compare with the 3d refraction, [F] to toggle frosted glass");
+
+    commands.spawn((
+        Camera2dBundle::default(),
+        Raindrops {
+            refraction: RefractionMode::flat(),
+            ..default()
+        },
+    ));
+
+    // A row of buildings, so the scrolling is easy to see.
+    for i in -20..=20 {
+        let height = 150.0 + (i * 7919).rem_euclid(5) as f32 * 60.0;
+        let hue = ((i + 20) * 37).rem_euclid(360) as f32;
+
+        commands.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::hsl(hue, 0.5, 0.5),
+                custom_size: Some(Vec2::new(90.0, height)),
+                ..default()
+            },
+            transform: Transform::from_xyz(i as f32 * 110.0, height / 2.0 - 250.0, 0.0),
+            ..default()
+        });
+    }
+}
+
+fn update(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut cameras: Query<(
+        Entity,
+        &mut Transform,
+        &mut Raindrops,
+        Option<&FrostedGlass>,
+    )>,
+) {
+    let (entity, mut transform, mut raindrops, frosted_glass) = cameras.single_mut();
+
+    if keyboard_input.pressed(KeyCode::Right) {
+        transform.translation.x += 400.0 * time.delta_seconds();
+    } else if keyboard_input.pressed(KeyCode::Left) {
+        transform.translation.x -= 400.0 * time.delta_seconds();
+    }
+
+    let mut refraction = raindrops.refraction;
+    if let RefractionMode::Flat { parallax } = &mut refraction {
+        if keyboard_input.just_pressed(KeyCode::Up) {
+            *parallax = (*parallax + 0.1).min(1.0);
+        } else if keyboard_input.just_pressed(KeyCode::Down) {
+            *parallax = (*parallax - 0.1).max(0.0);
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::P) {
+        refraction = match refraction {
+            RefractionMode::Perspective => RefractionMode::flat(),
+            RefractionMode::Flat { .. } => RefractionMode::Perspective,
+        };
+    }
+
+    if raindrops.refraction != refraction {
+        raindrops.refraction = refraction;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F) {
+        if frosted_glass.is_some() {
+            commands.entity(entity).remove::<FrostedGlass>();
+        } else {
+            commands.entity(entity).insert(FrostedGlass {
+                refraction,
+                ..default()
+            });
+        }
+    } else if let Some(frosted_glass) = frosted_glass {
+        if frosted_glass.refraction != refraction {
+            commands.entity(entity).insert(FrostedGlass {
+                refraction,
+                ..*frosted_glass
+            });
+        }
+    }
+}
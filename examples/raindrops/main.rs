@@ -17,7 +17,7 @@ fn main() {
 }
 
 fn startup(mut commands: Commands) {
-    info!("Press [up|down|left|right|mouse scroll] to change settings");
+    info!("Press [up|down|left|right|mouse scroll] to change settings, [E] to toggle shelter");
 
     commands
         .spawn(Camera3dBundle {
@@ -47,6 +47,10 @@ fn update(
         raindrops.warping -= 0.01;
     }
 
+    if keyboard_input.just_pressed(KeyCode::E) {
+        raindrops.exposure = if raindrops.exposure > 0.0 { 0.0 } else { 1.0 };
+    }
+
     for scroll in mouse_wheel_events.iter() {
         if scroll.y > 0.0 {
             raindrops.zoom += 0.1;
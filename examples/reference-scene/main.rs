@@ -0,0 +1,74 @@
+//! The reference scene of the `test_fixtures` module, for evaluating one effect at a time.
+//!
+//! Press [Left|Right] to apply each effect in turn with its default parameters,
+//! and [Space] to pause the clock so the motion target holds still.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    state::{EffectKind, EffectState},
+    test_fixtures::{ReferenceSceneCamera, ReferenceScenePlugin},
+    time::VfxTime,
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+        ReferenceScenePlugin,
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup() {
+    info!("Press [Left|Right] to select an effect, [Space] to pause the clock");
+}
+
+fn update(world: &mut World, mut selected: Local<Option<usize>>) {
+    let keyboard = world.resource::<Input<KeyCode>>();
+    let step: isize = if keyboard.just_pressed(KeyCode::Right) {
+        1
+    } else if keyboard.just_pressed(KeyCode::Left) {
+        -1
+    } else {
+        0
+    };
+
+    if keyboard.just_pressed(KeyCode::Space) {
+        let mut time = world.resource_mut::<VfxTime>();
+        time.paused = !time.paused;
+    }
+
+    if step == 0 {
+        return;
+    }
+
+    // Stepping past either end shows the scene without effects.
+    let count = EffectKind::ALL.len() as isize + 1;
+    let index = selected.map_or(0, |index| index as isize + 1);
+    let index = (index + step).rem_euclid(count);
+    *selected = (index > 0).then(|| index as usize - 1);
+
+    let state = match *selected {
+        Some(index) => {
+            let kind = EffectKind::ALL[index];
+            info!("Effect: {}", kind.name());
+            EffectState::new([kind.default_effect()])
+        }
+        None => {
+            info!("No effect");
+            EffectState::default()
+        }
+    };
+
+    let camera = world
+        .query_filtered::<Entity, With<ReferenceSceneCamera>>()
+        .single(world);
+    state.apply(&mut world.entity_mut(camera));
+}
@@ -0,0 +1,64 @@
+//! Shows a heavy effect drawn at a lower resolution, then scaled back up.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        kuwahara::Kuwahara,
+        resolution::{Resolution, ResolutionScale, Upsampling},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<Resolution<Kuwahara>>,
+            update,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [R] to cycle the resolution, [U] to toggle bicubic upsampling");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Kuwahara::default(),
+        Resolution::<Kuwahara>::new(ResolutionScale::Half, Upsampling::Bilinear),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Resolution<Kuwahara>>) {
+    let mut resolution = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        resolution.scale = match resolution.scale {
+            ResolutionScale::Full => ResolutionScale::Half,
+            ResolutionScale::Half => ResolutionScale::Quarter,
+            ResolutionScale::Quarter => ResolutionScale::Full,
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::U) {
+        resolution.upsampling = match resolution.upsampling {
+            Upsampling::Bilinear => Upsampling::Bicubic,
+            Upsampling::Bicubic => Upsampling::Bilinear,
+        };
+    }
+}
@@ -0,0 +1,80 @@
+//! Shows distance fog in flat, dithered bands, as part of a retro look.
+//! Note that the camera needs a depth prepass, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::{pixelate::Pixelate, retro_fog::RetroFog, PostProcessingOrder},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<RetroFog>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the number of bands, [D] to toggle dithering, [Left|Right] to change the end, [P] to toggle pixelation");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        RetroFog {
+            start: 4.0,
+            end: 20.0,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, &mut RetroFog, Option<&Pixelate>)>,
+) {
+    let (entity, mut fog, pixelate) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        fog.bands += 1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        fog.bands = fog.bands.saturating_sub(1);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::D) {
+        fog.dither = !fog.dither;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        fog.end += 1.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        fog.end = (fog.end - 1.0).max(fog.start);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::P) {
+        if pixelate.is_some() {
+            commands.entity(entity).remove::<Pixelate>();
+        } else {
+            // The fog is pixelated along with the scene.
+            commands
+                .entity(entity)
+                .insert(Pixelate::default().order(1.0));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Shows rolling shutter skew while the camera pans back and forth across the shapes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::rolling_shutter::RollingShutter, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<RollingShutter>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the readout, [Left|Right] to change the skew, [B] to toggle bands");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        RollingShutter::default(),
+    ));
+}
+
+fn update(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut cameras: Query<&mut Transform, With<Camera3d>>,
+    mut query: Query<&mut RollingShutter>,
+) {
+    // Whip pans to the sides and back, with a little shake on top.
+    for mut transform in &mut cameras {
+        let t = time.elapsed_seconds();
+        let yaw = (t * 2.0).sin() * 0.6;
+        let shake = (t * 23.0).sin() * 0.01;
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw + shake, -0.08, 0.0);
+    }
+
+    let mut rolling_shutter = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        rolling_shutter.readout = (rolling_shutter.readout + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        rolling_shutter.readout = (rolling_shutter.readout - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        rolling_shutter.skew += 0.5;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        rolling_shutter.skew = (rolling_shutter.skew - 0.5).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::B) {
+        rolling_shutter.bands = if rolling_shutter.bands == 0 { 8 } else { 0 };
+    }
+}
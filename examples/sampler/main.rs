@@ -0,0 +1,70 @@
+//! Shows how the sampler of an effect changes what it reads: nearest pixels or blended ones,
+//! and what is read past the edges of the image.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        sampler::{SamplerAddress, SamplerFilter, SourceSampler},
+        wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<SourceSampler<Wave>>,
+            update,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [F] to toggle nearest filtering, [A] to cycle what is read past the edges");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Wave {
+            waves_x: 3.0,
+            speed_x: 0.5,
+            amplitude_x: 0.1,
+            ..default()
+        },
+        SourceSampler::<Wave>::nearest(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut SourceSampler<Wave>>) {
+    let mut sampler = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::F) {
+        sampler.filter = match sampler.filter {
+            SamplerFilter::Linear => SamplerFilter::Nearest,
+            SamplerFilter::Nearest => SamplerFilter::Linear,
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::A) {
+        sampler.address = match sampler.address {
+            SamplerAddress::ClampToEdge => SamplerAddress::Repeat,
+            SamplerAddress::Repeat => SamplerAddress::MirrorRepeat,
+            SamplerAddress::MirrorRepeat => SamplerAddress::ClampToEdge,
+        };
+    }
+}
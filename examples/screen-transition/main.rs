@@ -0,0 +1,87 @@
+//! Shows fades, irises and wipes with `ScreenTransition`, as when leaving a level and entering the next.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::screen_transition::{
+        ScreenTransition, ScreenTransitionFinished, ScreenTransitionKind,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<ScreenTransition>,
+            update,
+            reveal_when_covered,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1|2|3] for a fade, an iris or a wipe, [Space] to run it, [Up|Down] to drive it by hand");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        ScreenTransition::fade(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut ScreenTransition>) {
+    let mut transition = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        transition.kind = ScreenTransitionKind::Fade;
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        // Closes in on the shapes, a little below the middle of the screen.
+        transition.kind = ScreenTransitionKind::Iris {
+            center: Vec2::new(0.5, 0.6),
+        };
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        transition.kind = ScreenTransitionKind::Wipe { direction: Vec2::X };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        *transition = transition.covering(0.75);
+    }
+
+    if keyboard_input.pressed(KeyCode::Up) {
+        transition.timer = None;
+        transition.progress = (transition.progress + 0.01).min(1.);
+    } else if keyboard_input.pressed(KeyCode::Down) {
+        transition.timer = None;
+        transition.progress = (transition.progress - 0.01).max(0.);
+    }
+}
+
+/// This is where a game would load the next level, while the screen is covered.
+fn reveal_when_covered(
+    mut events: EventReader<ScreenTransitionFinished>,
+    mut query: Query<&mut ScreenTransition>,
+) {
+    for event in events.iter() {
+        info!("Finished, covered: {}", event.covered);
+
+        if event.covered {
+            if let Ok(mut transition) = query.get_mut(event.camera) {
+                *transition = transition.revealing(0.75);
+            }
+        }
+    }
+}
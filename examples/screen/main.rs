@@ -0,0 +1,117 @@
+//! Shows effects on an in-world screen, here a security monitor with a wobbly, rained on feed.
+//!
+//! A camera renders the scene into an image, which [`ProcessImage`] applies the effects to
+//! every frame, and the result is shown on a quad in the scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    ecs::query::Has,
+    prelude::*,
+    render::{camera::RenderTarget, view::RenderLayers},
+};
+use bevy_vfx_bag::{
+    post_processing::{
+        process_image::{output_image, ProcessImage},
+        raindrops::Raindrops,
+        wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+/// The layer of the screen, which the feed camera doesn't see, so the screen doesn't show itself.
+const SCREEN_LAYER: u8 = 1;
+
+fn startup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press [Space] to toggle the effects on the screen");
+
+    let size = UVec2::new(640, 360);
+    let feed = images.add(output_image(size));
+    let screen = images.add(output_image(size));
+
+    // The feed renders before the effects are applied to it.
+    commands.spawn(Camera3dBundle {
+        camera: Camera {
+            target: RenderTarget::Image(feed.clone()),
+            order: -2,
+            ..default()
+        },
+        transform: Transform::from_xyz(6.0, 3.0, 6.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+        ..default()
+    });
+
+    commands.spawn((
+        ProcessImage::every_frame(feed, screen.clone()),
+        screen_effects(),
+    ));
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Quad::new(Vec2::new(6.4, 3.6)).into()),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(screen),
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, 4.0, -3.0),
+            ..default()
+        },
+        RenderLayers::layer(SCREEN_LAYER),
+    ));
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        RenderLayers::default().with(SCREEN_LAYER),
+    ));
+}
+
+fn screen_effects() -> (Wave, Raindrops) {
+    (
+        Wave {
+            waves_y: 20.,
+            speed_y: 10.,
+            amplitude_y: 0.003,
+            ..default()
+        },
+        Raindrops::default(),
+    )
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut commands: Commands,
+    screen: Query<(Entity, Has<Wave>), With<ProcessImage>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    let (entity, has_effects) = screen.single();
+    if has_effects {
+        commands.entity(entity).remove::<(Wave, Raindrops)>();
+    } else {
+        commands.entity(entity).insert(screen_effects());
+    }
+}
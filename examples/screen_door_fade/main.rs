@@ -0,0 +1,62 @@
+//! Shows the view fading out and back in through a dither pattern with `ScreenDoorFade`.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::screen_door_fade::ScreenDoorFade, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<ScreenDoorFade>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to fade out or in, [Up|Down] to change the scale of the pattern");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        ScreenDoorFade {
+            scale: 4,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, &mut ScreenDoorFade)>,
+) {
+    let Ok((entity, mut fade)) = query.get_single_mut() else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if fade.progress > 0.5 {
+            commands.entity(entity).insert(fade.fade_in(1.0));
+        } else {
+            commands.entity(entity).insert(fade.fade_out(1.0));
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        fade.scale += 1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        fade.scale = fade.scale.saturating_sub(1).max(1);
+    }
+}
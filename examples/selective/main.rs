@@ -0,0 +1,78 @@
+//! Shows an effect limited to some entities, here x-ray vision through a wall.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        blend::{Blend, EffectScope},
+        effect_mask::{EffectMask, EffectMaskChannel},
+        selective::SelectiveEffect,
+        vision_mode::VisionMode,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (mark_shapes, update))
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press [Space] to switch between thermal vision on the shapes only, even behind the wall, and everywhere");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        EffectMask,
+        VisionMode::thermal(),
+        Blend::<VisionMode>::default().with_scope(EffectScope::Mask(EffectMaskChannel::Red)),
+    ));
+
+    // A wall hiding half of the shapes, which the x-ray vision sees through.
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(shape::Box::new(7.0, 4.0, 0.2).into()),
+        material: materials.add(Color::GRAY.into()),
+        transform: Transform::from_xyz(-3.5, 2.0, 3.0),
+        ..default()
+    });
+}
+
+fn mark_shapes(
+    mut commands: Commands,
+    shapes: Query<Entity, (With<examples_common::Shape>, Without<SelectiveEffect>)>,
+) {
+    for entity in &shapes {
+        commands.entity(entity).insert(SelectiveEffect::default());
+    }
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut blend: Query<&mut Blend<VisionMode>, With<Camera>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    let mut blend = blend.single_mut();
+    blend.scope = match blend.scope {
+        EffectScope::Mask(_) => EffectScope::Everywhere,
+        _ => EffectScope::Mask(EffectMaskChannel::Red),
+    };
+}
@@ -0,0 +1,63 @@
+//! Shows the sharpen effect stacked on top of a blur,
+//! which demonstrates how sharpening can recover some of the lost detail.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{blur::Blur, sharpen::Sharpen, PostProcessingOrder},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Sharpen>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change strength, [Left|Right] to change radius");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Blur {
+            amount: 0.3,
+            kernel_radius: 0.002,
+        }
+        .order(0.),
+        Sharpen::default().order(1.),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Sharpen>) {
+    let mut sharpen = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Left) {
+        sharpen.radius -= 0.25;
+    } else if keyboard_input.just_pressed(KeyCode::Right) {
+        sharpen.radius += 0.25;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        sharpen.strength += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        sharpen.strength -= 0.1;
+    }
+
+    sharpen.radius = sharpen.radius.max(0.);
+    sharpen.strength = sharpen.strength.max(0.);
+}
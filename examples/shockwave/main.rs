@@ -0,0 +1,60 @@
+//! Shows shockwaves sent from a point in the world, which stay on it as the camera moves.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::shockwave::{Shockwave, ShockwaveOrigin},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (orbit, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to send a shockwave, [Up|Down] to change strength");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        Shockwave::default(),
+        ShockwaveOrigin::new(Vec3::new(0., 1., 0.)),
+    ));
+}
+
+fn orbit(time: Res<Time>, mut query: Query<&mut Transform, With<Camera>>) {
+    let mut transform = query.single_mut();
+
+    let angle = time.elapsed_seconds() * 0.2;
+    *transform = Transform::from_xyz(angle.sin() * 12., 3., angle.cos() * 12.)
+        .looking_at(Vec3::new(0., 2., 0.), Vec3::Y);
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Shockwave>) {
+    let mut shockwave = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        shockwave.restart();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        shockwave.strength += 0.1;
+        info!("{}", *shockwave);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        shockwave.strength -= 0.1;
+        info!("{}", *shockwave);
+    }
+}
@@ -0,0 +1,65 @@
+//! Draws the scene in pencil, with hatching fixed to the screen or to the surfaces of the scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::prepass::{DepthPrepass, NormalPrepass},
+    prelude::*,
+};
+use bevy_vfx_bag::{
+    post_processing::sketch::{Sketch, SketchAlignment},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    // Prepass textures can't be read by post processing effects when multisampled.
+    app.insert_resource(Msaa::Off)
+        .add_plugins((
+            examples_common::SaneDefaultsPlugin,
+            examples_common::ShapesExamplePlugin::without_3d_camera(),
+            BevyVfxBagPlugin::default(),
+        ))
+        .add_systems(Startup, startup)
+        .add_systems(Update, (examples_common::print_on_change::<Sketch>, update))
+        .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [A] to toggle the alignment of the strokes, [Up|Down] to change the edge strength, [C] to toggle the color");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        DepthPrepass,
+        NormalPrepass,
+        Sketch::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Sketch>) {
+    let mut sketch = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::A) {
+        let color = sketch.color;
+        *sketch = match sketch.alignment {
+            SketchAlignment::Screen => Sketch::world_aligned(),
+            SketchAlignment::World => Sketch::default(),
+        };
+        sketch.color = color;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        sketch.edge_strength = (sketch.edge_strength + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        sketch.edge_strength = (sketch.edge_strength - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::C) {
+        sketch.color = if sketch.color == 0.0 { 0.6 } else { 0.0 };
+    }
+}
@@ -0,0 +1,63 @@
+//! Saves a snapshot of the camera, with its effects, as a png file.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        pixelate::Pixelate,
+        snapshot::{SnapshotTaken, TakeSnapshot},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (take_snapshot, save_snapshot))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to save a snapshot to snapshot.png");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Pixelate::default(),
+    ));
+}
+
+fn take_snapshot(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    camera: Query<Entity, With<Camera>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        commands.entity(camera.single()).insert(TakeSnapshot::new());
+    }
+}
+
+fn save_snapshot(mut snapshots: EventReader<SnapshotTaken>) {
+    for snapshot in snapshots.iter() {
+        match snapshot
+            .image
+            .clone()
+            .try_into_dynamic()
+            .map_err(|err| err.to_string())
+            .and_then(|image| image.save("snapshot.png").map_err(|err| err.to_string()))
+        {
+            Ok(()) => info!("Saved snapshot.png"),
+            Err(err) => error!("Could not save the snapshot: {err}"),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Shows `SpeedLines` bursting in for dashes.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::speed_lines::SpeedLines, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<SpeedLines>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!(
+        "Press [Space] to dash, [Up|Down] to change the density, [Left|Right] to change the length"
+    );
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        SpeedLines {
+            intensity: 0.0,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, &mut SpeedLines)>,
+) {
+    let Ok((entity, mut lines)) = query.get_single_mut() else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        commands.entity(entity).insert(lines.burst(0.6));
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        lines.density += 8.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        lines.density = (lines.density - 8.0).max(8.0);
+    }
+
+    if keyboard_input.pressed(KeyCode::Right) {
+        lines.length = (lines.length + 0.01).min(1.0);
+    } else if keyboard_input.pressed(KeyCode::Left) {
+        lines.length = (lines.length - 0.01).max(0.0);
+    }
+}
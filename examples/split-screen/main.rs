@@ -0,0 +1,114 @@
+//! Shows two split-screen cameras with their own effects.
+//! Each camera only applies its effects to its own half of the window.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig, ecs::query::Has, prelude::*,
+    render::camera::Viewport, window::PrimaryWindow,
+};
+use bevy_vfx_bag::{
+    post_processing::{color_adjust::ColorAdjust, pixelate::Pixelate},
+    BevyVfxBagPlugin,
+};
+
+/// The player a camera shows the game to, from left to right.
+#[derive(Component)]
+struct Player(u32);
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (layout_viewports, update))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [1] to toggle the pixelation of the left player, [2] to toggle the grading of the right player");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(-6.0, 6., 10.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Player(0),
+        Pixelate::default(),
+    ));
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
+            camera_3d: Camera3d {
+                // The cameras share the window, so the second one mustn't clear what the first drew.
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            transform: Transform::from_xyz(6.0, 6., 10.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Player(1),
+        ColorAdjust {
+            saturation: 0.2,
+            ..default()
+        },
+    ));
+}
+
+/// Gives each player half of the window.
+fn layout_viewports(
+    windows: Query<Ref<Window>, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Camera, &Player)>,
+) {
+    let window = windows.single();
+    if !window.is_changed() {
+        return;
+    }
+
+    let size = UVec2::new(window.physical_width() / 2, window.physical_height()).max(UVec2::ONE);
+
+    for (mut camera, player) in &mut cameras {
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(player.0 * size.x, 0),
+            physical_size: size,
+            ..default()
+        });
+    }
+}
+
+fn update(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    cameras: Query<(Entity, &Player, Has<Pixelate>, Has<ColorAdjust>)>,
+) {
+    for (entity, player, pixelated, graded) in &cameras {
+        if player.0 == 0 && keyboard_input.just_pressed(KeyCode::Key1) {
+            if pixelated {
+                commands.entity(entity).remove::<Pixelate>();
+            } else {
+                commands.entity(entity).insert(Pixelate::default());
+            }
+        }
+
+        if player.0 == 1 && keyboard_input.just_pressed(KeyCode::Key2) {
+            if graded {
+                commands.entity(entity).remove::<ColorAdjust>();
+            } else {
+                commands.entity(entity).insert(ColorAdjust {
+                    saturation: 0.2,
+                    ..default()
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Shows keeping part of the image clean under heavy distortion,
+//! as for a HUD drawn as part of the scene.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        chromatic_aberration::ChromaticAberration, stable_regions::StableRegions, wave::Wave,
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<StableRegions>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [T] to toggle the stable regions, [Up|Down] to change the feather");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Wave {
+            waves_x: 2.,
+            waves_y: 20.,
+            speed_x: 1.,
+            speed_y: 10.,
+            amplitude_x: 0.03,
+            amplitude_y: 0.01,
+            ..default()
+        },
+        ChromaticAberration {
+            magnitude_r: 0.01,
+            magnitude_g: 0.0,
+            magnitude_b: -0.01,
+            ..default()
+        },
+        // The middle of the image, where the shapes are.
+        StableRegions::new([Rect::new(0.3, 0.35, 0.7, 0.75)]),
+    ));
+}
+
+fn update(
+    mut saved_settings: Local<Option<StableRegions>>,
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(Entity, Option<&mut StableRegions>), With<Camera>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::T) {
+        match query.single_mut() {
+            (entity, None) => {
+                info!("Toggling ON");
+                commands
+                    .entity(entity)
+                    .insert(saved_settings.take().unwrap_or_default());
+            }
+            (entity, Some(settings)) => {
+                info!("Toggling OFF");
+                *saved_settings = Some(settings.clone());
+                commands.entity(entity).remove::<StableRegions>();
+            }
+        };
+    }
+
+    if let (_, Some(mut settings)) = query.single_mut() {
+        if keyboard_input.just_pressed(KeyCode::Up) {
+            settings.feather += 2.0;
+        } else if keyboard_input.just_pressed(KeyCode::Down) {
+            settings.feather = (settings.feather - 2.0).max(0.0);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Shows a chain of effects ordered by their position, instead of by picking orders by hand.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{
+        masks::Mask,
+        pixelate::Pixelate,
+        stack::{InsertPostProcessingStack, PostProcessingStack},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+/// Pixelating the vignette gives blocky edges, while vignetting the pixelated image keeps them smooth.
+fn stack(mask_first: bool) -> PostProcessingStack {
+    let stack = PostProcessingStack::new();
+
+    if mask_first {
+        stack.then_with(Mask::vignette()).then::<Pixelate>()
+    } else {
+        stack.then::<Pixelate>().then_with(Mask::vignette())
+    }
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [S] to swap the order of the effects");
+
+    let camera = commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        })
+        .id();
+
+    commands.add(InsertPostProcessingStack {
+        entity: camera,
+        stack: stack(false),
+    });
+}
+
+fn update(
+    mut commands: Commands,
+    mut mask_first: Local<bool>,
+    keyboard_input: Res<Input<KeyCode>>,
+    cameras: Query<Entity, With<Camera>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::S) {
+        return;
+    }
+
+    *mask_first = !*mask_first;
+    let stack = stack(*mask_first);
+    info!("Effects: {:?}", stack);
+
+    commands.add(InsertPostProcessingStack {
+        entity: cameras.single(),
+        stack,
+    });
+}
@@ -0,0 +1,113 @@
+//! Shows effects rendering per eye, as side by side stereo.
+//! The vignette of each eye moves toward the nose, so it looks centered once the eyes are fused.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig, prelude::*, render::camera::Viewport,
+    window::PrimaryWindow,
+};
+use bevy_vfx_bag::{
+    post_processing::{masks::Mask, stereo::StereoEye},
+    BevyVfxBagPlugin,
+};
+
+/// The distance between the eyes, in meters.
+const EYE_DISTANCE: f32 = 0.064;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (
+            examples_common::print_on_change::<Mask>,
+            set_viewports,
+            update,
+        ),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the nasal offset of the vignette");
+
+    let head = Transform::from_xyz(0.0, 6., 12.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y);
+
+    for (eye, order) in [(StereoEye::Left, 0), (StereoEye::Right, 1)] {
+        let side = match eye {
+            StereoEye::Left => -1.0,
+            StereoEye::Right => 1.0,
+        };
+
+        let mut transform = head;
+        transform.translation += head.right() * side * EYE_DISTANCE * 0.5;
+
+        commands.spawn((
+            Camera3dBundle {
+                transform,
+                camera: Camera { order, ..default() },
+                camera_3d: Camera3d {
+                    // The second eye must not clear the image of the first.
+                    clear_color: if order == 0 {
+                        ClearColorConfig::Default
+                    } else {
+                        ClearColorConfig::None
+                    },
+                    ..default()
+                },
+                ..default()
+            },
+            eye,
+            Mask {
+                nasal_offset: 0.05,
+                ..Mask::vignette()
+            },
+        ));
+    }
+}
+
+/// Puts the left eye on the left half of the window, and the right eye on the right half.
+fn set_viewports(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Camera, &StereoEye)>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let size = UVec2::new(window.physical_width() / 2, window.physical_height());
+
+    for (mut camera, eye) in &mut cameras {
+        let x = match eye {
+            StereoEye::Left => 0,
+            StereoEye::Right => size.x,
+        };
+
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(x, 0),
+            physical_size: size,
+            ..default()
+        });
+    }
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Mask>) {
+    let change = if keyboard_input.just_pressed(KeyCode::Up) {
+        0.01
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        -0.01
+    } else {
+        return;
+    };
+
+    for mut mask in &mut query {
+        mask.nasal_offset += change;
+    }
+}
@@ -0,0 +1,37 @@
+//! Measures the frame times of a stack of effects across several cameras.
+//!
+//! Run with e.g. `cargo run --release --example stress --features stress -- 4 6`
+//! for four cameras with six effects each.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{diagnostic::LogDiagnosticsPlugin, prelude::*};
+use bevy_vfx_bag::{
+    stress::{StressConfig, StressPlugin},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut args = std::env::args().skip(1).map(|arg| {
+        arg.parse::<usize>()
+            .expect("Arguments should be the number of cameras and effects per camera")
+    });
+    let cameras = args.next().unwrap_or(2);
+    let effects_per_camera = args.next().unwrap_or(4);
+
+    let mut app = App::new();
+
+    app.insert_resource(
+        StressConfig::new(cameras, effects_per_camera)
+            .with_budget_ms(1000. / 60.)
+            .exit_when_done(),
+    )
+    .add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+        StressPlugin,
+        LogDiagnosticsPlugin::default(),
+    ))
+    .run();
+}
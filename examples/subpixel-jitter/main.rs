@@ -0,0 +1,71 @@
+//! Shows temporal supersampling: the projection is jittered by a subpixel amount each frame,
+//! and the frames are averaged by blending them with the previous output.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{jitter::SubpixelJitter, post_processing::ghosting::Ghosting, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    // Without MSAA, so the edges are only smoothed by the jitter.
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [J] to toggle the jitter, [Up|Down] to change how many frames are averaged");
+
+    let jitter = SubpixelJitter::default();
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        jitter,
+        averaging(jitter.length),
+    ));
+}
+
+/// Blends each frame with the previous output, so roughly the given number of frames are averaged.
+fn averaging(frames: u32) -> Ghosting {
+    Ghosting {
+        decay: 1.0 - 1.0 / frames as f32,
+        ..default()
+    }
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut SubpixelJitter, &mut Ghosting)>,
+) {
+    let (mut jitter, mut ghosting) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::J) {
+        jitter.scale = if jitter.scale > 0.0 { 0.0 } else { 1.0 };
+        info!("Jitter scale: {}", jitter.scale);
+    }
+
+    let length = if keyboard_input.just_pressed(KeyCode::Up) {
+        jitter.length * 2
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        (jitter.length / 2).max(1)
+    } else {
+        return;
+    };
+
+    jitter.length = length.min(64);
+    jitter.reset();
+    *ghosting = averaging(jitter.length);
+    info!("Averaging {} frames", jitter.length);
+}
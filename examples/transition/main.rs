@@ -0,0 +1,81 @@
+//! Shows cross-fading between sets of effects with `Transition`.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{masks::Mask, underwater::Underwater, wave::Wave},
+    state::EffectState,
+    transition::{Transition, TransitionFinished},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (update, log_finished))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Space] to dive and surface");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        Mask::vignette(),
+    ));
+}
+
+fn update(
+    mut commands: Commands,
+    mut underwater: Local<bool>,
+    keyboard_input: Res<Input<KeyCode>>,
+    camera: Query<Entity, With<Camera>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    *underwater = !*underwater;
+
+    let effects = if *underwater {
+        // The vignette stays, but gets stronger.
+        EffectState::new([
+            Underwater::default().into(),
+            Wave {
+                waves_x: 2.,
+                speed_x: 1.,
+                amplitude_x: 0.01,
+                ..default()
+            }
+            .into(),
+            Mask {
+                strength: 1.2,
+                ..Mask::vignette()
+            }
+            .into(),
+        ])
+    } else {
+        EffectState::new([Mask::vignette().into()])
+    };
+
+    commands
+        .entity(camera.single())
+        .insert(Transition::new(effects, 1.5));
+}
+
+fn log_finished(mut events: EventReader<TransitionFinished>) {
+    for event in events.iter() {
+        info!("Transition finished on {:?}", event.camera);
+    }
+}
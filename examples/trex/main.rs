@@ -42,6 +42,7 @@ fn update(
             speed_y: 20.,
             amplitude_x: 0.01,
             amplitude_y: 0.01,
+            ..default()
         });
         info!("<GROUND SHAKE>");
     }
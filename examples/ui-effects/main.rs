@@ -0,0 +1,114 @@
+//! Shows effects applied to the images of UI nodes.
+//! From left to right: blur, glitch, pixelate and vignette.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use bevy_vfx_bag::{
+    post_processing::{
+        blur::Blur,
+        ui::{UiBlur, UiEffect, UiGlitch, UiPixelate, UiVignette},
+    },
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+#[derive(Component)]
+struct Panel(usize);
+
+fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    info!("Press [t] to toggle the effects, [Up|Down] to change the blur");
+
+    let image: Handle<Image> = asset_server.load("textures/ascii.png");
+
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for index in 0..4 {
+                let mut panel = parent.spawn((
+                    ImageBundle {
+                        style: Style {
+                            width: Val::Px(256.0),
+                            height: Val::Px(256.0),
+                            ..default()
+                        },
+                        image: UiImage::new(image.clone()),
+                        ..default()
+                    },
+                    Panel(index),
+                ));
+                insert_effect(&mut panel, index);
+            }
+        });
+}
+
+fn insert_effect(panel: &mut EntityCommands, index: usize) {
+    match index {
+        0 => panel.insert(UiBlur::new(Blur {
+            amount: 0.5,
+            ..default()
+        })),
+        1 => panel.insert(UiGlitch::default()),
+        2 => panel.insert(UiPixelate::default()),
+        _ => panel.insert(UiVignette::default()),
+    };
+}
+
+fn update(
+    mut commands: Commands,
+    mut enabled: Local<Option<bool>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    panels: Query<(Entity, &Panel)>,
+    mut blurs: Query<&mut UiEffect<Blur>>,
+) {
+    let enabled = enabled.get_or_insert(true);
+
+    if keyboard_input.just_pressed(KeyCode::T) {
+        *enabled = !*enabled;
+        info!("Effects {}", if *enabled { "on" } else { "off" });
+
+        for (entity, panel) in &panels {
+            let mut panel_commands = commands.entity(entity);
+            if *enabled {
+                insert_effect(&mut panel_commands, panel.0);
+            } else {
+                panel_commands.remove::<(UiBlur, UiGlitch, UiPixelate, UiVignette)>();
+            }
+        }
+    }
+
+    let change = if keyboard_input.just_pressed(KeyCode::Up) {
+        0.1
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        -0.1
+    } else {
+        return;
+    };
+
+    for mut blur in &mut blurs {
+        blur.effect.amount = (blur.effect.amount + change).max(0.0);
+        info!("Blur amount: {:.1}", blur.effect.amount);
+    }
+}
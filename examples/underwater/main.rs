@@ -1,48 +1,70 @@
+//! Shows the underwater effect, with each of its parts toggled by keys.
+//! Note that the camera needs a depth prepass for the murk, and that MSAA must be off.
 #[path = "../examples_common.rs"]
 mod examples_common;
 
-use bevy::prelude::*;
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
 use bevy_vfx_bag::{
-    post_processing::{
-        chromatic_aberration::ChromaticAberration, lut::Lut, masks::Mask, raindrops::Raindrops,
-        wave::Wave,
-    },
+    post_processing::{raindrops::Raindrops, underwater::Underwater, PostProcessingOrder},
     BevyVfxBagPlugin,
 };
 
 fn main() {
     let mut app = App::new();
 
-    app.add_plugin(examples_common::SaneDefaultsPlugin)
-        .add_plugin(examples_common::ShapesExamplePlugin::without_3d_camera())
-        .add_plugin(BevyVfxBagPlugin::default())
-        .add_startup_system(startup)
-        .run();
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<Underwater>, update),
+    )
+    .run();
 }
 
 fn startup(mut commands: Commands) {
+    info!("Press [1|2|3|4] to toggle distortion, tint, murk, and caustics");
+
     commands.spawn((
         Camera3dBundle {
             transform: Transform::from_xyz(0.0, 6., 12.0)
                 .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
             ..default()
         },
-        Raindrops::default(),
-        ChromaticAberration {
-            magnitude_r: 0.003,
-            magnitude_g: 0.003,
-            magnitude_b: 0.003,
-            ..default()
-        },
-        Wave {
-            waves_x: 1.,
-            speed_x: 0.1,
-            amplitude_x: 0.07,
-            waves_y: 10.,
-            speed_y: 0.3,
-            amplitude_y: 0.01,
-        },
-        Lut::arctic(),
-        Mask::vignette(),
+        DepthPrepass,
+        Underwater::default().order(0.),
+        // Drops on the goggles.
+        Raindrops::default().order(1.),
     ));
 }
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Underwater>) {
+    let mut underwater = query.single_mut();
+    let defaults = Underwater::default();
+
+    fn toggle(value: &mut f32, default: f32) {
+        *value = if *value == 0.0 { default } else { 0.0 };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        toggle(
+            &mut underwater.distortion.amplitude,
+            defaults.distortion.amplitude,
+        );
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        let mut alpha = underwater.tint.color.a();
+        toggle(&mut alpha, defaults.tint.color.a());
+        underwater.tint.color.set_a(alpha);
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        toggle(&mut underwater.murk.density, defaults.murk.density);
+    } else if keyboard_input.just_pressed(KeyCode::Key4) {
+        toggle(
+            &mut underwater.caustics.intensity,
+            defaults.caustics.intensity,
+        );
+    }
+}
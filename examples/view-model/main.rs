@@ -0,0 +1,124 @@
+//! Shows a first person view model drawn by its own camera over the world.
+//! The depth of field of the world camera blurs the world but not the view model,
+//! while the grading of the view model camera applies to both, since it runs after compositing.
+//! Note that the world camera needs a depth prepass for the depth of field, and that MSAA must be off.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*, render::view::RenderLayers};
+use bevy_vfx_bag::{
+    post_processing::{
+        color_adjust::ColorAdjust, depth_of_field::DepthOfField, overlay::ApplyAfterCompositing,
+        view_model::ViewModel,
+    },
+    BevyVfxBagPlugin,
+};
+
+/// The layer only the view model camera sees.
+const VIEW_MODEL_LAYER: u8 = 1;
+
+#[derive(Component)]
+struct Arm;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, startup)
+    .add_systems(Update, update)
+    .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press [V] to draw the arm with the world camera instead, [G] to toggle the grading");
+
+    let world_camera = commands
+        .spawn((
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 6., 12.0)
+                    .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+                ..default()
+            },
+            DepthPrepass,
+            DepthOfField {
+                focal_distance: 12.0,
+                ..default()
+            },
+        ))
+        .id();
+
+    let view_model_camera = commands
+        .spawn((
+            Camera3dBundle {
+                projection: PerspectiveProjection {
+                    fov: 70.0_f32.to_radians(),
+                    ..default()
+                }
+                .into(),
+                ..default()
+            },
+            RenderLayers::layer(VIEW_MODEL_LAYER),
+            ViewModel::new(world_camera),
+            ColorAdjust {
+                saturation: 1.4,
+                contrast: 1.1,
+                ..default()
+            },
+            ApplyAfterCompositing::<ColorAdjust>::new(),
+        ))
+        .set_parent(world_camera)
+        .id();
+
+    commands
+        .spawn((
+            PbrBundle {
+                mesh: meshes.add(shape::Box::new(0.15, 0.15, 0.8).into()),
+                material: materials.add(Color::rgb(0.8, 0.6, 0.5).into()),
+                transform: Transform::from_xyz(0.35, -0.3, -0.6),
+                ..default()
+            },
+            RenderLayers::layer(VIEW_MODEL_LAYER),
+            Arm,
+        ))
+        .set_parent(view_model_camera);
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut arms: Query<&mut RenderLayers, With<Arm>>,
+    mut grading: Query<&mut ColorAdjust>,
+) {
+    if keyboard_input.just_pressed(KeyCode::V) {
+        let mut layers = arms.single_mut();
+        *layers = if layers.intersects(&RenderLayers::layer(VIEW_MODEL_LAYER)) {
+            info!("The world camera draws the arm, so the depth of field blurs it");
+            RenderLayers::default()
+        } else {
+            info!("The view model camera draws the arm");
+            RenderLayers::layer(VIEW_MODEL_LAYER)
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::G) {
+        let mut grading = grading.single_mut();
+        *grading = if grading.saturation == 1.0 {
+            ColorAdjust {
+                saturation: 1.4,
+                contrast: 1.1,
+                ..default()
+            }
+        } else {
+            ColorAdjust::default()
+        };
+        info!("Grading: {:?}", *grading);
+    }
+}
@@ -0,0 +1,53 @@
+//! Shows the vision mode effect, with night vision and thermal vision presets.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::vision_mode::VisionMode, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<VisionMode>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!(
+        "Press [1|2] to switch between night vision and thermal vision, [Up|Down] to change gain"
+    );
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        VisionMode::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut VisionMode>) {
+    let mut vision_mode = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        *vision_mode = VisionMode::night_vision();
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        *vision_mode = VisionMode::thermal();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        vision_mode.gain += 0.5;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        vision_mode.gain = (vision_mode.gain - 0.5).max(0.0);
+    }
+}
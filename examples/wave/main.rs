@@ -2,7 +2,10 @@
 mod examples_common;
 
 use bevy::prelude::*;
-use bevy_vfx_bag::{post_processing::wave::Wave, BevyVfxBagPlugin};
+use bevy_vfx_bag::{
+    post_processing::wave::{Wave, WaveProfile},
+    BevyVfxBagPlugin,
+};
 
 fn main() {
     let mut app = App::new();
@@ -16,7 +19,7 @@ fn main() {
 }
 
 fn startup(mut commands: Commands) {
-    info!("Press [1|2|3|4|5] to change which wave preset to use.");
+    info!("Press [1|2|3|4|5|6] to change which wave preset to use, [P] to change the profile.");
 
     commands.spawn((
         Camera3dBundle {
@@ -40,6 +43,7 @@ fn update(mut query: Query<&mut Wave, With<Camera>>, keyboard_input: Res<Input<K
             speed_y: 20.,
             amplitude_x: 0.25,
             amplitude_y: 0.005,
+            ..default()
         };
     } else if keyboard_input.just_pressed(KeyCode::Key2) {
         info!("We're being lazy in the x direction.");
@@ -66,6 +70,7 @@ fn update(mut query: Query<&mut Wave, With<Camera>>, keyboard_input: Res<Input<K
             speed_y: 1.,
             amplitude_x: 0.03,
             amplitude_y: 0.04,
+            ..default()
         };
     } else if keyboard_input.just_pressed(KeyCode::Key5) {
         info!("Oh no, earthquake!");
@@ -76,6 +81,26 @@ fn update(mut query: Query<&mut Wave, With<Camera>>, keyboard_input: Res<Input<K
             speed_y: 13.,
             amplitude_x: 0.02,
             amplitude_y: 0.03,
+            profile: WaveProfile::Noise,
+        };
+    } else if keyboard_input.just_pressed(KeyCode::Key6) {
+        info!("The signal is breaking up.");
+        *wave = Wave {
+            waves_y: 40.,
+            speed_y: 6.,
+            amplitude_y: 0.01,
+            profile: WaveProfile::Sawtooth,
+            ..default()
         };
     };
+
+    if keyboard_input.just_pressed(KeyCode::P) {
+        wave.profile = match wave.profile {
+            WaveProfile::Sine => WaveProfile::Triangle,
+            WaveProfile::Triangle => WaveProfile::Sawtooth,
+            WaveProfile::Sawtooth => WaveProfile::Noise,
+            WaveProfile::Noise => WaveProfile::Sine,
+        };
+        info!("Profile: {:?}", wave.profile);
+    }
 }
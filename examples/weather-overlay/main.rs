@@ -0,0 +1,61 @@
+//! Shows rain and snow drawn in screen space.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::weather_overlay::{WeatherOverlay, WeatherVariant},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<WeatherOverlay>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [V] to switch between rain and snow, [Up|Down] to change the density, [Left|Right] to change the angle");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        WeatherOverlay::rain(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut WeatherOverlay>) {
+    let mut weather = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::V) {
+        *weather = match weather.variant {
+            WeatherVariant::Rain => WeatherOverlay::snow(),
+            WeatherVariant::Snow => WeatherOverlay::rain(),
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        weather.density = (weather.density + 0.1).min(1.0);
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        weather.density = (weather.density - 0.1).max(0.0);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        weather.angle += 5.0;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        weather.angle -= 5.0;
+    }
+}
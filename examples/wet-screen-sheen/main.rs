@@ -0,0 +1,83 @@
+//! Shows the scene getting wet while it rains, and drying off after.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{
+    post_processing::{weather_overlay::WeatherOverlay, wet_screen_sheen::WetScreenSheen, Order},
+    BevyVfxBagPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(Update, (update, weather))
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [R] to toggle the rain, [Up|Down] to change the reflections");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 6., 12.0)
+                .looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+            ..default()
+        },
+        WetScreenSheen {
+            wetness: 0.0,
+            ..default()
+        },
+        // The rain falls in front of the wet scene.
+        WeatherOverlay::rain(),
+        Order::<WeatherOverlay>::new(1.0),
+    ));
+}
+
+fn update(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut WeatherOverlay, &mut WetScreenSheen)>,
+) {
+    let (mut weather, mut sheen) = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        weather.density = if weather.density > 0.0 {
+            0.0
+        } else {
+            WeatherOverlay::rain().density
+        };
+        info!("Rain density: {:.2}", weather.density);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        sheen.reflection_strength += 0.05;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        sheen.reflection_strength = (sheen.reflection_strength - 0.05).max(0.0);
+    }
+
+    if keyboard_input.any_just_pressed([KeyCode::Up, KeyCode::Down]) {
+        info!("{}", *sheen);
+    }
+}
+
+/// A simple weather system: the scene soaks quickly while it rains, and dries off slowly.
+fn weather(time: Res<Time>, mut query: Query<(&WeatherOverlay, &mut WetScreenSheen)>) {
+    let (weather, mut sheen) = query.single_mut();
+
+    let wetness = if weather.density > 0.0 {
+        sheen.wetness + time.delta_seconds() * 0.5
+    } else {
+        sheen.wetness - time.delta_seconds() * 0.1
+    };
+
+    let wetness = wetness.clamp(0.0, 1.0);
+    if wetness != sheen.wetness {
+        sheen.wetness = wetness;
+    }
+}
@@ -0,0 +1,65 @@
+//! Shows warming and cooling the image with white balance, e.g. for day and night moods.
+#[path = "../examples_common.rs"]
+mod examples_common;
+
+use bevy::prelude::*;
+use bevy_vfx_bag::{post_processing::white_balance::WhiteBalance, BevyVfxBagPlugin};
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        examples_common::SaneDefaultsPlugin,
+        examples_common::ShapesExamplePlugin::without_3d_camera(),
+        BevyVfxBagPlugin::default(),
+    ))
+    .add_systems(Startup, startup)
+    .add_systems(
+        Update,
+        (examples_common::print_on_change::<WhiteBalance>, update),
+    )
+    .run();
+}
+
+fn startup(mut commands: Commands) {
+    info!("Press [Up|Down] to change the temperature, [Left|Right] to change the tint. [1] sunset, [2] moonlight, [R] resets");
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 3., 12.0)
+                .looking_at(Vec3::new(0., 2., 0.), Vec3::Y),
+            ..default()
+        },
+        WhiteBalance::default(),
+    ));
+}
+
+fn update(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut WhiteBalance>) {
+    let mut white_balance = query.single_mut();
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        *white_balance = WhiteBalance::default();
+    } else if keyboard_input.just_pressed(KeyCode::Key1) {
+        *white_balance = WhiteBalance {
+            temperature: 9500.0,
+            tint: 0.2,
+        };
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        *white_balance = WhiteBalance {
+            temperature: 4000.0,
+            tint: -0.1,
+        };
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        white_balance.temperature += 500.0;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        white_balance.temperature -= 500.0;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        white_balance.tint += 0.1;
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        white_balance.tint -= 0.1;
+    }
+}
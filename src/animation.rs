@@ -0,0 +1,320 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    prelude::*,
+    reflect::{ReflectMut, ReflectRef},
+};
+
+use crate::{post_processing::for_each_effect, time::VfxTime, util};
+
+/// How an [`Animate`] moves from its start to its end.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    /// Move at a constant rate.
+    Linear,
+
+    /// Start slowly, then speed up.
+    EaseIn,
+
+    /// Start quickly, then slow down.
+    EaseOut,
+
+    /// Start and end slowly.
+    #[default]
+    EaseInOut,
+
+    /// Start quickly, overshoot the end slightly, then settle on it.
+    BackOut,
+
+    /// Start quickly, then spring around the end before settling on it.
+    ElasticOut,
+}
+
+impl Easing {
+    /// The eased progress, given the linear progress between `0.0` and `1.0`.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::BackOut => {
+                const OVERSHOOT: f32 = 1.70158;
+                let t = t - 1.0;
+                1.0 + (OVERSHOOT + 1.0) * t * t * t + OVERSHOOT * t * t
+            }
+            Easing::ElasticOut => {
+                if t <= 0.0 || t >= 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+        }
+    }
+}
+
+/// What an [`Animate`] does once it reaches its end.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Looping {
+    /// Stop at the end, and remove the [`Animate`].
+    #[default]
+    Once,
+
+    /// Jump back to the start and play again.
+    Repeat,
+
+    /// Play backwards to the start, then forwards again.
+    PingPong,
+}
+
+/// Animates the parameters of the effect `C` on the same entity from one value to another.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::{animation::{Animate, Easing}, post_processing::masks::Mask};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     Animate::new(Mask::vignette(), Mask { strength: 3.0, ..Mask::vignette() }, 0.5)
+///         .with_easing(Easing::EaseOut),
+/// ));
+/// ```
+///
+/// Numeric parameters (including vectors and colors) are interpolated.
+/// Parameters which can't be interpolated, such as the variant of an effect,
+/// switch from the start to the end halfway through.
+///
+/// If the entity doesn't have the effect yet, it is added.
+/// The animation is advanced by the [`VfxTime`] clock, so it is paused and slowed down
+/// along with the rest of the effects.
+///
+/// The built-in effects are animated out of the box. For other reflected components,
+/// add [`AnimatePlugin`].
+#[derive(Debug, Clone, Component)]
+pub struct Animate<C> {
+    /// The parameters at the start.
+    pub from: C,
+
+    /// The parameters at the end.
+    pub to: C,
+
+    /// How many seconds it takes to go from the start to the end.
+    pub duration: f32,
+
+    /// How to move from the start to the end.
+    pub easing: Easing,
+
+    /// What to do at the end.
+    pub looping: Looping,
+
+    /// Seconds since the animation started.
+    pub elapsed: f32,
+}
+
+impl<C> Animate<C> {
+    /// Animates from one value to another over the given seconds, easing in and out, once.
+    pub fn new(from: C, to: C, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing: Easing::default(),
+            looping: Looping::default(),
+            elapsed: 0.0,
+        }
+    }
+
+    /// Use the given easing.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Use the given looping.
+    pub fn with_looping(mut self, looping: Looping) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// True if the animation played once and doesn't loop.
+    pub fn finished(&self) -> bool {
+        self.looping == Looping::Once && self.elapsed >= self.duration
+    }
+
+    /// The eased progress from the start (`0.0`) to the end (`1.0`).
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        let t = match self.looping {
+            Looping::Once => self.elapsed / self.duration,
+            Looping::Repeat => (self.elapsed / self.duration).fract(),
+            Looping::PingPong => {
+                let t = (self.elapsed / self.duration).rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+
+        self.easing.ease(t)
+    }
+}
+
+/// Interpolates the numbers within the reflected value `out` between those in `from` and `to`.
+///
+/// Other values are left as they are, as are lists of different lengths and enums
+/// of different variants.
+pub(crate) fn interpolate(out: &mut dyn Reflect, from: &dyn Reflect, to: &dyn Reflect, t: f32) {
+    match (out.reflect_mut(), from.reflect_ref(), to.reflect_ref()) {
+        (ReflectMut::Struct(out), ReflectRef::Struct(from), ReflectRef::Struct(to)) => {
+            for i in 0..out.field_len() {
+                if let (Some(out), Some(from), Some(to)) =
+                    (out.field_at_mut(i), from.field_at(i), to.field_at(i))
+                {
+                    interpolate(out, from, to, t);
+                }
+            }
+        }
+        (
+            ReflectMut::TupleStruct(out),
+            ReflectRef::TupleStruct(from),
+            ReflectRef::TupleStruct(to),
+        ) => {
+            for i in 0..out.field_len() {
+                if let (Some(out), Some(from), Some(to)) =
+                    (out.field_mut(i), from.field(i), to.field(i))
+                {
+                    interpolate(out, from, to, t);
+                }
+            }
+        }
+        (ReflectMut::Enum(out), ReflectRef::Enum(from), ReflectRef::Enum(to)) => {
+            if from.variant_name() != to.variant_name() || out.variant_name() != from.variant_name()
+            {
+                return;
+            }
+
+            for i in 0..out.field_len() {
+                if let (Some(out), Some(from), Some(to)) =
+                    (out.field_at_mut(i), from.field_at(i), to.field_at(i))
+                {
+                    interpolate(out, from, to, t);
+                }
+            }
+        }
+        (ReflectMut::List(out), ReflectRef::List(from), ReflectRef::List(to)) => {
+            if from.len() != to.len() || out.len() != from.len() {
+                return;
+            }
+
+            for i in 0..out.len() {
+                if let (Some(out), Some(from), Some(to)) = (out.get_mut(i), from.get(i), to.get(i))
+                {
+                    interpolate(out, from, to, t);
+                }
+            }
+        }
+        (ReflectMut::Array(out), ReflectRef::Array(from), ReflectRef::Array(to)) => {
+            for i in 0..out.len() {
+                if let (Some(out), Some(from), Some(to)) = (out.get_mut(i), from.get(i), to.get(i))
+                {
+                    interpolate(out, from, to, t);
+                }
+            }
+        }
+        (ReflectMut::Value(out), _, _) => {
+            if let (Some(from), Some(to)) = (util::get_number(from), util::get_number(to)) {
+                util::set_number(out, from + (to - from) * t);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn animate<C: Component + Reflect + Clone>(
+    mut commands: Commands,
+    time: Res<VfxTime>,
+    mut animations: Query<(Entity, &mut Animate<C>, Option<&mut C>)>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, mut animation, effect) in &mut animations {
+        animation.elapsed += delta;
+
+        let t = animation.progress();
+
+        // Values which can't be interpolated switch from the start to the end halfway through.
+        // They are cloned rather than reflected, so e.g. asset handles stay valid.
+        let mut value = if t < 0.5 {
+            animation.from.clone()
+        } else {
+            animation.to.clone()
+        };
+        interpolate(&mut value, &animation.from, &animation.to, t);
+
+        match effect {
+            Some(mut effect) => *effect = value,
+            None => {
+                commands.entity(entity).insert(value);
+            }
+        }
+
+        if animation.finished() {
+            commands.entity(entity).remove::<Animate<C>>();
+        }
+    }
+}
+
+/// Animates the component `C` via [`Animate<C>`].
+///
+/// This is already added for the built-in effects, so it is only needed for other components.
+#[derive(Debug)]
+pub struct AnimatePlugin<C>(std::marker::PhantomData<C>);
+
+impl<C> Default for AnimatePlugin<C> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<C: Component + Reflect + Clone> bevy::prelude::Plugin for AnimatePlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, animate::<C>);
+    }
+}
+
+/// Adds an [`AnimatePlugin`] for each of the given effects.
+macro_rules! animated {
+    ($app:expr, $($name:ident($effect:path) $meta:tt),* $(,)?) => {
+        $(
+            $app.add_plugins(AnimatePlugin::<$effect>::default());
+        )*
+    };
+}
+
+/// Animates the built-in effects.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Easing>().register_type::<Looping>();
+
+        for_each_effect!(animated, app);
+    }
+}
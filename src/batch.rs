@@ -0,0 +1,414 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use bevy::{
+    app::AppExit,
+    asset::LoadState,
+    prelude::*,
+    render::{
+        render_asset::RenderAssets,
+        render_resource::{
+            Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d,
+            ImageCopyBuffer, ImageDataLayout, MapMode, TextureDimension, TextureFormat,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::BevyDefault,
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use crate::{
+    post_processing::process_image::{output_image, ImageProcessed, ProcessImage},
+    state::EffectState,
+};
+
+/// An image processed by a batch, see [`BatchConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchJob {
+    /// The asset path of the image to process, e.g. `"textures/crate.png"`.
+    pub input: String,
+
+    /// The file the result is saved to.
+    /// The file format follows the extension, e.g. `.png`, and must be enabled in Bevy.
+    pub output: PathBuf,
+}
+
+/// Which images a batch processes, and the effects it applies to them.
+///
+/// Insert this as a resource before adding the [`BatchPlugin`].
+/// The images are processed with [`ProcessImage`], so the results match what the same effects
+/// look like in the game.
+#[derive(Debug, Clone, Default, PartialEq, Resource)]
+pub struct BatchConfig {
+    /// The images to process.
+    pub jobs: Vec<BatchJob>,
+
+    /// The effects applied to every image.
+    pub effects: EffectState,
+
+    /// If true, the app exits once all images are saved.
+    pub exit_when_done: bool,
+}
+
+impl BatchConfig {
+    /// A batch applying the given effects, e.g. from an [`EffectState`] saved by a tool.
+    pub fn new(effects: EffectState) -> Self {
+        Self {
+            effects,
+            ..default()
+        }
+    }
+
+    /// Process the image at the given asset path, and save the result to the given file.
+    pub fn with_job(mut self, input: impl Into<String>, output: impl Into<PathBuf>) -> Self {
+        self.jobs.push(BatchJob {
+            input: input.into(),
+            output: output.into(),
+        });
+        self
+    }
+
+    /// Exit the app once all images are saved, e.g. for baking textures from scripts.
+    pub fn exit_when_done(mut self) -> Self {
+        self.exit_when_done = true;
+        self
+    }
+}
+
+/// The outcome of a batch.
+///
+/// Inserted as a resource (and logged) once all images are saved or failed.
+#[derive(Debug, Clone, Default, PartialEq, Resource)]
+pub struct BatchReport {
+    /// The files which were saved.
+    pub saved: Vec<PathBuf>,
+
+    /// The files which could not be made, with the reason.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// How far along a [`BatchJob`] is.
+#[derive(Debug)]
+enum JobProgress {
+    Loading(Handle<Image>),
+    Processing(Entity),
+    ReadingBack(Handle<Image>),
+    Done,
+}
+
+/// The progress of each [`BatchJob`], in the order of the [`BatchConfig`].
+#[derive(Debug, Default, Resource)]
+struct BatchProgress {
+    jobs: Vec<JobProgress>,
+    report: BatchReport,
+}
+
+/// Images to read back from the GPU, and their contents once read, shared between the render world
+/// and the main world.
+#[derive(Debug, Default, Clone, Resource)]
+struct SharedReadback(Arc<Mutex<Readback>>);
+
+#[derive(Debug, Default)]
+struct Readback {
+    requested: Vec<Handle<Image>>,
+    read: Vec<(Handle<Image>, Image)>,
+}
+
+fn load_inputs(
+    config: Res<BatchConfig>,
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<BatchProgress>,
+) {
+    progress.jobs = config
+        .jobs
+        .iter()
+        .map(|job| JobProgress::Loading(asset_server.load(job.input.as_str())))
+        .collect();
+}
+
+/// Spawns a [`ProcessImage`] with the effects for each loaded input.
+fn process_loaded(world: &mut World) {
+    world.resource_scope(|world, mut progress: Mut<BatchProgress>| {
+        let config = world.resource::<BatchConfig>().clone();
+        let BatchProgress { jobs, report } = &mut *progress;
+
+        for (job, config_job) in jobs.iter_mut().zip(&config.jobs) {
+            let JobProgress::Loading(input) = job else {
+                continue;
+            };
+
+            if world.resource::<AssetServer>().get_load_state(&*input) == LoadState::Failed {
+                error!("Could not load {}", config_job.input);
+                report.failed.push((
+                    config_job.output.clone(),
+                    format!("Could not load {}", config_job.input),
+                ));
+                *job = JobProgress::Done;
+                continue;
+            }
+
+            let Some(size) = world
+                .resource::<Assets<Image>>()
+                .get(&*input)
+                .map(|image| image.size().as_uvec2())
+            else {
+                continue;
+            };
+
+            let output = world
+                .resource_mut::<Assets<Image>>()
+                .add(output_image(size));
+
+            let mut entity = world.spawn(ProcessImage::once(input.clone(), output));
+            config.effects.apply(&mut entity);
+
+            *job = JobProgress::Processing(entity.id());
+        }
+    });
+}
+
+/// Requests the outputs of finished [`ProcessImage`]s from the GPU.
+fn read_back_processed(
+    mut commands: Commands,
+    shared: Res<SharedReadback>,
+    mut processed: EventReader<ImageProcessed>,
+    mut progress: ResMut<BatchProgress>,
+    processes: Query<&ProcessImage>,
+) {
+    for event in processed.iter() {
+        let Some(job) = progress
+            .jobs
+            .iter_mut()
+            .find(|job| matches!(job, JobProgress::Processing(entity) if *entity == event.entity))
+        else {
+            continue;
+        };
+        let Ok(process) = processes.get(event.entity) else {
+            continue;
+        };
+
+        // The output image outlives the entity, since the job holds on to it.
+        let output = process.output.clone();
+        commands.entity(event.entity).despawn_recursive();
+
+        shared
+            .0
+            .lock()
+            .expect("Readback should not be poisoned")
+            .requested
+            .push(output.clone());
+        *job = JobProgress::ReadingBack(output);
+    }
+}
+
+/// Saves the outputs which were read back, and reports once all are saved.
+fn save_read_back(
+    mut commands: Commands,
+    config: Res<BatchConfig>,
+    shared: Res<SharedReadback>,
+    mut progress: ResMut<BatchProgress>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let read = std::mem::take(
+        &mut shared
+            .0
+            .lock()
+            .expect("Readback should not be poisoned")
+            .read,
+    );
+
+    for (handle, image) in read {
+        let Some(index) = progress
+            .jobs
+            .iter()
+            .position(|job| matches!(job, JobProgress::ReadingBack(output) if *output == handle))
+        else {
+            continue;
+        };
+        let path = &config.jobs[index].output;
+
+        match image
+            .try_into_dynamic()
+            .map_err(|err| err.to_string())
+            .and_then(|image| image.save(path).map_err(|err| err.to_string()))
+        {
+            Ok(()) => {
+                info!("Saved {}", path.display());
+                progress.report.saved.push(path.clone());
+            }
+            Err(err) => {
+                error!("Could not save {}: {err}", path.display());
+                progress.report.failed.push((path.clone(), err));
+            }
+        }
+
+        progress.jobs[index] = JobProgress::Done;
+    }
+
+    let all_done = progress
+        .jobs
+        .iter()
+        .all(|job| matches!(job, JobProgress::Done));
+    if all_done && !progress.jobs.is_empty() {
+        let report = std::mem::take(&mut progress.report);
+        info!(
+            "Batch done: {} saved, {} failed",
+            report.saved.len(),
+            report.failed.len()
+        );
+
+        commands.insert_resource(report);
+        progress.jobs.clear();
+
+        if config.exit_when_done {
+            exit.send(AppExit);
+        }
+    }
+}
+
+/// A buffer an image is copied into, which is read once it is mapped.
+struct PendingReadback {
+    image: Handle<Image>,
+    size: UVec2,
+    padded_bytes_per_row: u32,
+    buffer: Buffer,
+    mapped: Arc<AtomicBool>,
+}
+
+/// Copies the requested images into buffers, and reads the buffers which are mapped.
+fn copy_and_read(
+    shared: Res<SharedReadback>,
+    images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut pending: Local<Vec<PendingReadback>>,
+) {
+    let _span = info_span!("vfx::batch::copy_and_read").entered();
+
+    let mut shared = shared.0.lock().expect("Readback should not be poisoned");
+
+    // Buffers are mapped while the device is polled, which happens when the queue is submitted,
+    // so they are read in a later frame than they are copied in.
+    let (mapped, still_pending): (Vec<_>, Vec<_>) = pending
+        .drain(..)
+        .partition(|readback| readback.mapped.load(Ordering::Acquire));
+    *pending = still_pending;
+
+    for readback in mapped {
+        let bytes_per_row = readback.size.x as usize * 4;
+        let data = readback
+            .buffer
+            .slice(..)
+            .get_mapped_range()
+            .chunks(readback.padded_bytes_per_row as usize)
+            .flat_map(|row| &row[..bytes_per_row])
+            .copied()
+            .collect();
+        readback.buffer.unmap();
+
+        let image = Image::new(
+            Extent3d {
+                width: readback.size.x,
+                height: readback.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::bevy_default(),
+        );
+        shared.read.push((readback.image, image));
+    }
+
+    for handle in std::mem::take(&mut shared.requested) {
+        // The output may not be prepared yet.
+        let Some(image) = images.get(&handle) else {
+            shared.requested.push(handle);
+            continue;
+        };
+
+        let size = image.size.as_uvec2();
+        let padded_bytes_per_row = RenderDevice::align_copy_bytes_per_row(size.x as usize * 4);
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("Batch readback buffer"),
+            size: (padded_bytes_per_row * size.y as usize) as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("Batch readback"),
+        });
+        encoder.copy_texture_to_buffer(
+            image.texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row as u32),
+                    rows_per_image: None,
+                },
+            },
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+        render_queue.submit([encoder.finish()]);
+
+        let mapped = Arc::new(AtomicBool::new(false));
+        let on_mapped = mapped.clone();
+        render_device.map_buffer(&buffer.slice(..), MapMode::Read, move |result| {
+            if let Err(err) = result {
+                error!("Could not read back a batch image: {err}");
+            }
+            on_mapped.store(true, Ordering::Release);
+        });
+
+        pending.push(PendingReadback {
+            image: handle,
+            size,
+            padded_bytes_per_row: padded_bytes_per_row as u32,
+            buffer,
+            mapped,
+        });
+    }
+}
+
+/// Processes the images of the [`BatchConfig`] resource and saves the results,
+/// e.g. for pre-baking graded or stylized textures with the shaders the game uses.
+///
+/// The app can run without a window, see the `batch` example.
+/// Needs the [`crate::BevyVfxBagPlugin`] and the `batch` feature.
+#[derive(Debug, Default)]
+pub struct BatchPlugin;
+
+impl Plugin for BatchPlugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedReadback::default();
+
+        app.init_resource::<BatchConfig>()
+            .init_resource::<BatchProgress>()
+            .insert_resource(shared.clone())
+            .add_systems(Startup, load_inputs)
+            .add_systems(
+                Update,
+                (process_loaded, read_back_processed, save_read_back).chain(),
+            );
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            warn!("The batch needs a renderer, no images will be processed");
+            return;
+        };
+
+        // After rendering, so the outputs of the frame are written.
+        render_app
+            .insert_resource(shared)
+            .add_systems(Render, copy_and_read.in_set(RenderSet::Cleanup));
+    }
+}
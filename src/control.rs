@@ -0,0 +1,261 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*, utils::HashSet};
+
+use crate::util;
+
+/// A message from a control surface, such as a MIDI controller or an OSC app.
+///
+/// This crate does not talk to devices itself. Receive messages with the library of your choice
+/// (e.g. `midir` or `rosc`), and send them as events:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::control::ControlMessage;
+/// fn forward_midi(mut messages: EventWriter<ControlMessage>) {
+///     // Raw bytes as received from a MIDI input port.
+///     let bytes = [0xB0, 7, 64];
+///
+///     if let Some(message) = ControlMessage::from_midi(&bytes) {
+///         messages.send(message);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Event)]
+pub enum ControlMessage {
+    /// A MIDI control change.
+    MidiCc {
+        /// The MIDI channel, `0` to `15`.
+        channel: u8,
+
+        /// The controller number, `0` to `127`.
+        controller: u8,
+
+        /// The value, `0` to `127`.
+        value: u8,
+    },
+
+    /// An OSC message with a single number.
+    Osc {
+        /// The OSC address, e.g. `"/vfx/vignette"`.
+        address: String,
+
+        /// The value, expected in the range `[0.0, 1.0]` as most OSC apps send.
+        value: f32,
+    },
+}
+
+impl ControlMessage {
+    /// Parse a raw MIDI message.
+    ///
+    /// Returns `None` if it isn't a control change.
+    pub fn from_midi(bytes: &[u8]) -> Option<Self> {
+        match *bytes {
+            [status, controller, value] if status & 0xF0 == 0xB0 => Some(Self::MidiCc {
+                channel: status & 0x0F,
+                controller: controller & 0x7F,
+                value: value & 0x7F,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parse a raw OSC packet.
+    ///
+    /// Returns `None` unless it is a single message whose first argument is a float or an int.
+    /// Bundles are not supported, unpack them with an OSC library instead.
+    pub fn from_osc(bytes: &[u8]) -> Option<Self> {
+        /// Reads a null terminated string, padded to a multiple of four bytes.
+        fn osc_string(bytes: &[u8]) -> Option<(&str, &[u8])> {
+            let end = bytes.iter().position(|byte| *byte == 0)?;
+            let string = std::str::from_utf8(&bytes[..end]).ok()?;
+            let padded = (end + 4) & !3;
+            Some((string, bytes.get(padded..)?))
+        }
+
+        let (address, rest) = osc_string(bytes)?;
+        if !address.starts_with('/') {
+            return None;
+        }
+
+        let (type_tags, rest) = osc_string(rest)?;
+        let argument: [u8; 4] = rest.get(..4)?.try_into().ok()?;
+
+        let value = match type_tags.strip_prefix(',')?.chars().next()? {
+            'f' => f32::from_be_bytes(argument),
+            'i' => i32::from_be_bytes(argument) as f32,
+            _ => return None,
+        };
+
+        Some(Self::Osc {
+            address: address.to_string(),
+            value,
+        })
+    }
+
+    /// The value of the message, normalized to `[0.0, 1.0]`.
+    fn normalized(&self) -> f32 {
+        match self {
+            ControlMessage::MidiCc { value, .. } => *value as f32 / 127.0,
+            ControlMessage::Osc { value, .. } => value.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Which knob, fader or address a [`ControlBinding`] listens to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlSource {
+    /// A MIDI control change.
+    MidiCc {
+        /// The MIDI channel, `0` to `15`.
+        channel: u8,
+
+        /// The controller number, `0` to `127`.
+        controller: u8,
+    },
+
+    /// An OSC address.
+    Osc(String),
+}
+
+impl ControlSource {
+    fn matches(&self, message: &ControlMessage) -> bool {
+        match (self, message) {
+            (
+                ControlSource::MidiCc {
+                    channel,
+                    controller,
+                },
+                ControlMessage::MidiCc {
+                    channel: message_channel,
+                    controller: message_controller,
+                    ..
+                },
+            ) => channel == message_channel && controller == message_controller,
+            (ControlSource::Osc(address), ControlMessage::Osc { address: other, .. }) => {
+                address == other
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Maps a control to a numeric effect parameter.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlBinding {
+    /// The control to listen to.
+    pub source: ControlSource,
+
+    /// The name of the effect component, e.g. `"Mask"`.
+    /// The full type path also works.
+    pub component: String,
+
+    /// The path of the parameter within the component, e.g. `"strength"` or `"color.a"`.
+    pub field: String,
+
+    /// The value of the parameter when the control is at its lowest.
+    pub min: f32,
+
+    /// The value of the parameter when the control is at its highest.
+    pub max: f32,
+}
+
+/// The table of which controls drive which effect parameters.
+///
+/// A control moves the parameter on every entity with the effect.
+/// Since this is reflected (and serializable with the `serialize` feature),
+/// the table can be kept in a file and edited without recompiling.
+#[derive(Debug, Default, Clone, PartialEq, Resource, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct ControlMapping {
+    /// The bindings. Several bindings may listen to the same control.
+    pub bindings: Vec<ControlBinding>,
+}
+
+impl ControlMapping {
+    /// Add a binding.
+    pub fn bind(
+        mut self,
+        source: ControlSource,
+        component: impl Into<String>,
+        field: impl Into<String>,
+        min: f32,
+        max: f32,
+    ) -> Self {
+        self.bindings.push(ControlBinding {
+            source,
+            component: component.into(),
+            field: field.into(),
+            min,
+            max,
+        });
+        self
+    }
+}
+
+fn apply_control_messages(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<ControlMessage>>,
+    mut warned: Local<HashSet<(String, String)>>,
+) {
+    let messages: Vec<ControlMessage> = reader
+        .iter(world.resource::<Events<ControlMessage>>())
+        .cloned()
+        .collect();
+
+    if messages.is_empty() {
+        return;
+    }
+
+    let mapping = world.resource::<ControlMapping>().clone();
+    let entities: Vec<Entity> = world.iter_entities().map(|entity| entity.id()).collect();
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    for message in &messages {
+        for binding in mapping
+            .bindings
+            .iter()
+            .filter(|binding| binding.source.matches(message))
+        {
+            let value = binding.min + (binding.max - binding.min) * message.normalized();
+
+            for entity in &entities {
+                if let Err(e) = util::set_component_number(
+                    &type_registry,
+                    &mut world.entity_mut(*entity),
+                    &binding.component,
+                    &binding.field,
+                    value,
+                ) {
+                    // Only warn once per parameter, instead of every message.
+                    if warned.insert((binding.component.clone(), binding.field.clone())) {
+                        warn!(
+                            "Can't control `{}` of `{}`: {e}",
+                            binding.field, binding.component
+                        );
+                    }
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Applies [`ControlMessage`]s via the [`ControlMapping`].
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ControlMessage>()
+            .init_resource::<ControlMapping>()
+            .register_type::<ControlSource>()
+            .register_type::<ControlBinding>()
+            .register_type::<Vec<ControlBinding>>()
+            .register_type::<ControlMapping>()
+            .add_systems(PostUpdate, apply_control_messages);
+    }
+}
@@ -0,0 +1,296 @@
+use std::{marker::PhantomData, time::Duration};
+
+#[cfg(feature = "ron")]
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    utils::BoxedFuture,
+};
+use bevy::{
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+};
+
+use crate::{animation::Easing, post_processing::for_each_effect, time::VfxTime, util};
+
+/// A point of a [`CurveAsset`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveKey {
+    /// Where on the curve this key is.
+    pub time: f32,
+
+    /// The value of the curve at this key.
+    pub value: f32,
+
+    /// How the curve goes from this key to the next one.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub easing: Easing,
+}
+
+impl CurveKey {
+    /// A key easing in and out towards the next one.
+    pub fn new(time: f32, value: f32) -> Self {
+        Self {
+            time,
+            value,
+            easing: Easing::default(),
+        }
+    }
+
+    /// Use the given easing towards the next key.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A curve of keys with an easing per segment, authored in a `.curve.ron` file.
+///
+/// Curves drive effect parameters via [`DriveWithCurve`]. A curve file lists its keys:
+///
+/// ```ron
+/// (
+///     keys: [
+///         (time: 0.0, value: 0.0, easing: Linear),
+///         (time: 0.1, value: 0.0, easing: EaseIn),
+///         (time: 1.0, value: 1.0),
+///     ],
+/// )
+/// ```
+///
+/// The keys are sorted by time when loaded, so they may be given in any order.
+/// Keys without an easing ease in and out.
+#[derive(Debug, Clone, Default, PartialEq, TypeUuid, TypePath)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[uuid = "0f4a5075-4fa0-4852-89cf-a5b35c040794"]
+pub struct CurveAsset {
+    /// The keys, sorted by time.
+    pub keys: Vec<CurveKey>,
+}
+
+impl CurveAsset {
+    /// A curve through the given keys, which are sorted by time.
+    pub fn new(mut keys: Vec<CurveKey>) -> Self {
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self { keys }
+    }
+
+    /// Parse a curve from the contents of a `.curve.ron` file.
+    #[cfg(feature = "ron")]
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        let curve: Self = ron::from_str(ron)?;
+        Ok(Self::new(curve.keys))
+    }
+
+    /// The value of the curve at the given time.
+    ///
+    /// Before the first and after the last key, the value of that key is held.
+    /// Returns `None` if there are no keys.
+    pub fn sample(&self, time: f32) -> Option<f32> {
+        let first = self.keys.first()?;
+
+        // The index of the first key after `time`.
+        let next = self.keys.partition_point(|key| key.time <= time);
+        if next == 0 {
+            return Some(first.value);
+        }
+
+        let from = self.keys[next - 1];
+        let Some(to) = self.keys.get(next) else {
+            return Some(from.value);
+        };
+
+        let t = from.easing.ease((time - from.time) / (to.time - from.time));
+        Some(from.value + (to.value - from.value) * t)
+    }
+
+    /// The time of the first key, or `0.0` without keys.
+    pub fn start(&self) -> f32 {
+        self.keys.first().map_or(0.0, |key| key.time)
+    }
+
+    /// The time of the last key, or `0.0` without keys.
+    pub fn end(&self) -> f32 {
+        self.keys.last().map_or(0.0, |key| key.time)
+    }
+}
+
+/// Drives a numeric parameter of the effect `C` on the same entity with a [`CurveAsset`],
+/// played over a timer.
+///
+/// For example, this fades a red vignette out over three seconds after taking damage:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::{curve::DriveWithCurve, post_processing::masks::Mask};
+/// fn on_damage(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+///     camera: Query<Entity, With<Camera>>,
+/// ) {
+///     commands.entity(camera.single()).insert((
+///         Mask {
+///             color: Color::RED,
+///             ..Mask::vignette()
+///         },
+///         DriveWithCurve::<Mask>::new(
+///             asset_server.load("curves/damage_recovery.curve.ron"),
+///             "fade",
+///             3.0,
+///         ),
+///     ));
+/// }
+/// ```
+///
+/// The curve is stretched from its first to its last key over the duration of the timer,
+/// so the same curve can be played over different durations. The values of the curve are
+/// written to the parameter as they are. The timer is advanced by the [`VfxTime`] clock once
+/// the curve is loaded, after `Update`. A timer which doesn't repeat removes this once it
+/// finishes, leaving the parameter at the value of the last key.
+///
+/// The built-in effects can be driven out of the box. For other reflected components,
+/// add [`DriveWithCurvePlugin`].
+#[derive(Debug, Clone, Component)]
+pub struct DriveWithCurve<C> {
+    /// The curve driving the parameter.
+    pub curve: Handle<CurveAsset>,
+
+    /// The path of the parameter within the effect, e.g. `"strength"` or `"color.a"`.
+    /// See [`crate::expression::ParamDriver::field`] for which parameters can be driven.
+    pub field: String,
+
+    /// How far through the curve the parameter is.
+    pub timer: Timer,
+
+    warned: bool,
+    _effect: PhantomData<fn() -> C>,
+}
+
+impl<C> DriveWithCurve<C> {
+    /// Plays the curve on the parameter `field` once, over the given seconds.
+    pub fn new(curve: Handle<CurveAsset>, field: impl Into<String>, seconds: f32) -> Self {
+        Self {
+            curve,
+            field: field.into(),
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            warned: false,
+            _effect: PhantomData,
+        }
+    }
+
+    /// Plays the curve over and over instead of once.
+    pub fn repeating(mut self) -> Self {
+        self.timer.set_mode(TimerMode::Repeating);
+        self
+    }
+}
+
+fn drive_with_curves<C: Component + Reflect>(
+    mut commands: Commands,
+    time: Res<VfxTime>,
+    curves: Res<Assets<CurveAsset>>,
+    mut drivers: Query<(Entity, &mut DriveWithCurve<C>, &mut C)>,
+) {
+    let delta = Duration::from_secs_f32(time.delta_seconds().max(0.0));
+
+    for (entity, mut driver, mut effect) in &mut drivers {
+        let driver = &mut *driver;
+        let Some(curve) = curves.get(&driver.curve) else {
+            continue;
+        };
+
+        driver.timer.tick(delta);
+
+        let time = curve.start() + (curve.end() - curve.start()) * driver.timer.percent();
+        let Some(value) = curve.sample(time) else {
+            continue;
+        };
+
+        let effect: &mut dyn Reflect = &mut *effect;
+        if let Err(reason) = util::set_field_number(effect, &driver.field, value) {
+            if !driver.warned {
+                driver.warned = true;
+                warn!(
+                    "Can't drive `{}` of `{}` with a curve: {reason}",
+                    driver.field,
+                    std::any::type_name::<C>()
+                );
+            }
+        }
+
+        if driver.timer.finished() && driver.timer.mode() == TimerMode::Once {
+            commands.entity(entity).remove::<DriveWithCurve<C>>();
+        }
+    }
+}
+
+/// Drives the component `C` via [`DriveWithCurve<C>`].
+///
+/// This is already added for the built-in effects, so it is only needed for other components.
+#[derive(Debug)]
+pub struct DriveWithCurvePlugin<C>(PhantomData<fn() -> C>);
+
+impl<C> Default for DriveWithCurvePlugin<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: Component + Reflect> bevy::prelude::Plugin for DriveWithCurvePlugin<C> {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Assets<CurveAsset>>() {
+            app.add_asset::<CurveAsset>();
+        }
+
+        app.add_systems(PostUpdate, drive_with_curves::<C>);
+    }
+}
+
+#[cfg(feature = "ron")]
+#[derive(Debug, Default)]
+struct CurveAssetLoader;
+
+#[cfg(feature = "ron")]
+impl AssetLoader for CurveAssetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let curve: CurveAsset = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(CurveAsset::new(curve.keys)));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["curve.ron"]
+    }
+}
+
+/// Adds a [`DriveWithCurvePlugin`] for each of the given effects.
+macro_rules! curved {
+    ($app:expr, $($name:ident($effect:path) $meta:tt),* $(,)?) => {
+        $(
+            $app.add_plugins(DriveWithCurvePlugin::<$effect>::default());
+        )*
+    };
+}
+
+/// Loads [`CurveAsset`]s and drives the built-in effects with them.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<CurveAsset>()
+            .register_type::<CurveKey>()
+            .register_type::<Vec<CurveKey>>();
+
+        #[cfg(feature = "ron")]
+        app.init_asset_loader::<CurveAssetLoader>();
+
+        for_each_effect!(curved, app);
+    }
+}
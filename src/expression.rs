@@ -0,0 +1,501 @@
+use std::fmt::Display;
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{time::VfxTime, util};
+
+/// Drives a single numeric effect parameter from an expression.
+///
+/// For example, this pulses the strength of a [`crate::post_processing::masks::Mask`]:
+///
+/// ```
+/// # use bevy_vfx_bag::expression::ParamDriver;
+/// let driver = ParamDriver::new("Mask", "strength", "sin(time * 2) * 0.5 + 0.5");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamDriver {
+    /// The name of the effect component, e.g. `"Blur"`.
+    /// The full type path (e.g. `"bevy_vfx_bag::post_processing::blur::Blur"`) also works.
+    ///
+    /// The component must be registered in the type registry, which all effects in this crate are.
+    pub component: String,
+
+    /// The path of the parameter within the component, e.g. `"amount"` or `"color.a"`.
+    ///
+    /// The parameter must be an `f32`, `f64`, `u32` or `i32`.
+    /// Integer parameters get the result of the expression rounded.
+    pub field: String,
+
+    /// The expression computing the value of the parameter.
+    ///
+    /// Supports numbers, `+ - * / ^`, parentheses, and these variables:
+    ///
+    /// - `time`: Seconds elapsed on the [`VfxTime`] clock
+    /// - `delta`: Seconds the [`VfxTime`] clock advanced last frame
+    /// - `frame`: The frame count of the [`VfxTime`] clock
+    /// - `pi`
+    ///
+    /// As well as these functions:
+    /// `sin`, `cos`, `tan`, `abs`, `sqrt`, `exp`, `floor`, `fract`,
+    /// `min(a, b)`, `max(a, b)`, `pow(a, b)`, `clamp(x, lo, hi)` and `mix(a, b, t)`.
+    pub expression: String,
+}
+
+impl ParamDriver {
+    /// Creates a driver for the parameter `field` of the effect `component`.
+    pub fn new(
+        component: impl Into<String>,
+        field: impl Into<String>,
+        expression: impl Into<String>,
+    ) -> Self {
+        Self {
+            component: component.into(),
+            field: field.into(),
+            expression: expression.into(),
+        }
+    }
+}
+
+/// Expressions driving effect parameters on the same entity.
+///
+/// The expressions are evaluated on the CPU each frame, before the effects are extracted.
+/// Since this component is reflected, it can be authored in scene files.
+#[derive(Debug, Default, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct ParamDrivers(pub Vec<ParamDriver>);
+
+/// Why an expression could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    /// A character which is not part of the expression syntax.
+    UnexpectedCharacter(char),
+
+    /// The expression ended where more was expected.
+    UnexpectedEnd,
+
+    /// A token which does not fit where it is.
+    UnexpectedToken(String),
+
+    /// A variable which does not exist.
+    UnknownVariable(String),
+
+    /// A function which does not exist, or was called with the wrong number of arguments.
+    UnknownFunction {
+        /// The name of the function.
+        name: String,
+
+        /// How many arguments it was called with.
+        arguments: usize,
+    },
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedCharacter(c) => write!(f, "unexpected character `{c}`"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            Self::UnexpectedToken(token) => write!(f, "unexpected `{token}`"),
+            Self::UnknownVariable(name) => write!(f, "unknown variable `{name}`"),
+            Self::UnknownFunction { name, arguments } => {
+                write!(
+                    f,
+                    "unknown function `{name}` taking {arguments} argument(s)"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+/// The variables an expression can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    Time,
+    Delta,
+    Frame,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Abs,
+    Sqrt,
+    Exp,
+    Floor,
+    Fract,
+    Min,
+    Max,
+    Pow,
+    Clamp,
+    Mix,
+}
+
+impl Function {
+    fn lookup(name: &str, arguments: usize) -> Option<Self> {
+        let function = match (name, arguments) {
+            ("sin", 1) => Self::Sin,
+            ("cos", 1) => Self::Cos,
+            ("tan", 1) => Self::Tan,
+            ("abs", 1) => Self::Abs,
+            ("sqrt", 1) => Self::Sqrt,
+            ("exp", 1) => Self::Exp,
+            ("floor", 1) => Self::Floor,
+            ("fract", 1) => Self::Fract,
+            ("min", 2) => Self::Min,
+            ("max", 2) => Self::Max,
+            ("pow", 2) => Self::Pow,
+            ("clamp", 3) => Self::Clamp,
+            ("mix", 3) => Self::Mix,
+            _ => return None,
+        };
+
+        Some(function)
+    }
+
+    fn call(self, args: &[f32]) -> f32 {
+        match self {
+            Self::Sin => args[0].sin(),
+            Self::Cos => args[0].cos(),
+            Self::Tan => args[0].tan(),
+            Self::Abs => args[0].abs(),
+            Self::Sqrt => args[0].sqrt(),
+            Self::Exp => args[0].exp(),
+            Self::Floor => args[0].floor(),
+            Self::Fract => args[0] - args[0].floor(),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            Self::Pow => args[0].powf(args[1]),
+            Self::Clamp => args[0].clamp(args[1].min(args[2]), args[2].max(args[1])),
+            Self::Mix => args[0] + (args[1] - args[0]) * args[2],
+        }
+    }
+}
+
+/// A parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression(Node);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f32),
+    Variable(Variable),
+    Negate(Box<Node>),
+    Binary(char, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+impl Expression {
+    /// Parse an expression, see [`ParamDriver::expression`] for the syntax.
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+        };
+
+        let node = parser.expression()?;
+        match parser.next() {
+            None => Ok(Self(node)),
+            Some(token) => Err(ExpressionError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    /// Evaluate the expression at the given point in time.
+    pub fn evaluate(&self, time: &VfxTime) -> f32 {
+        self.0.evaluate(time)
+    }
+}
+
+impl Node {
+    fn evaluate(&self, time: &VfxTime) -> f32 {
+        match self {
+            Node::Number(n) => *n,
+            Node::Variable(Variable::Time) => time.elapsed_seconds(),
+            Node::Variable(Variable::Delta) => time.delta_seconds(),
+            Node::Variable(Variable::Frame) => time.frame_count() as f32,
+            Node::Negate(node) => -node.evaluate(time),
+            Node::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(time), rhs.evaluate(time));
+                match op {
+                    '+' => lhs + rhs,
+                    '-' => lhs - rhs,
+                    '*' => lhs * rhs,
+                    '/' => lhs / rhs,
+                    '^' => lhs.powf(rhs),
+                    _ => unreachable!("The parser only creates known operators"),
+                }
+            }
+            Node::Call(function, args) => {
+                let args: Vec<f32> = args.iter().map(|arg| arg.evaluate(time)).collect();
+                function.call(&args)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Identifier(String),
+    Symbol(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{n}"),
+            Token::Identifier(name) => write!(f, "{name}"),
+            Token::Symbol(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let number = &source[start..end];
+            let number = number
+                .parse()
+                .map_err(|_| ExpressionError::UnexpectedToken(number.to_string()))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Identifier(source[start..end].to_string()));
+        } else if "+-*/^(),".contains(c) {
+            tokens.push(Token::Symbol(c));
+            chars.next();
+        } else {
+            return Err(ExpressionError::UnexpectedCharacter(c));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser.
+///
+/// From lowest to highest precedence: `+ -`, `* /`, unary `-`, `^` (right associative).
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ExpressionError> {
+        match self.next() {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            Some(token) => Err(ExpressionError::UnexpectedToken(token.to_string())),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, ExpressionError> {
+        let mut lhs = self.term()?;
+        while let Some(&Token::Symbol(op @ ('+' | '-'))) = self.peek() {
+            self.position += 1;
+            lhs = Node::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Node, ExpressionError> {
+        let mut lhs = self.unary()?;
+        while let Some(&Token::Symbol(op @ ('*' | '/'))) = self.peek() {
+            self.position += 1;
+            lhs = Node::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.eat('-') {
+            Ok(Node::Negate(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Node, ExpressionError> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            // Right associative, and binds tighter than a unary minus on its left: `-2^2` is `-4`.
+            Ok(Node::Binary('^', Box::new(base), Box::new(self.unary()?)))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node, ExpressionError> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Node::Number(n)),
+            Some(Token::Symbol('(')) => {
+                let node = self.expression()?;
+                self.expect(')')?;
+                Ok(node)
+            }
+            Some(Token::Identifier(name)) => {
+                if self.eat('(') {
+                    let mut args = vec![];
+                    if !self.eat(')') {
+                        loop {
+                            args.push(self.expression()?);
+                            if self.eat(')') {
+                                break;
+                            }
+                            self.expect(',')?;
+                        }
+                    }
+
+                    let function = Function::lookup(&name, args.len()).ok_or(
+                        ExpressionError::UnknownFunction {
+                            name,
+                            arguments: args.len(),
+                        },
+                    )?;
+                    Ok(Node::Call(function, args))
+                } else {
+                    match name.as_str() {
+                        "time" => Ok(Node::Variable(Variable::Time)),
+                        "delta" => Ok(Node::Variable(Variable::Delta)),
+                        "frame" => Ok(Node::Variable(Variable::Frame)),
+                        "pi" => Ok(Node::Number(std::f32::consts::PI)),
+                        _ => Err(ExpressionError::UnknownVariable(name)),
+                    }
+                }
+            }
+            Some(token) => Err(ExpressionError::UnexpectedToken(token.to_string())),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+}
+
+/// Parsed expressions, keyed by their source.
+///
+/// Expressions which failed to parse are kept too, and misconfigured drivers are remembered,
+/// so problems are only reported once instead of every frame.
+#[derive(Default)]
+struct ExpressionCache {
+    expressions: HashMap<String, Option<Expression>>,
+    warned: HashSet<(String, String)>,
+}
+
+impl ExpressionCache {
+    fn get(&mut self, source: &str) -> Option<&Expression> {
+        if !self.expressions.contains_key(source) {
+            let expression = Expression::parse(source)
+                .map_err(|e| warn!("Could not parse expression `{source}`: {e}"))
+                .ok();
+            self.expressions.insert(source.to_string(), expression);
+        }
+
+        self.expressions.get(source).and_then(Option::as_ref)
+    }
+
+    fn warn(&mut self, driver: &ParamDriver, reason: &str) {
+        if self
+            .warned
+            .insert((driver.component.clone(), driver.field.clone()))
+        {
+            warn!(
+                "Can't drive `{}` of `{}`: {reason}",
+                driver.field, driver.component
+            );
+        }
+    }
+}
+
+fn drive_params(world: &mut World, mut cache: Local<ExpressionCache>) {
+    let mut drivers = world.query::<(Entity, &ParamDrivers)>();
+    let drivers: Vec<(Entity, ParamDrivers)> = drivers
+        .iter(world)
+        .map(|(entity, drivers)| (entity, drivers.clone()))
+        .collect();
+
+    if drivers.is_empty() {
+        return;
+    }
+
+    let time = *world.resource::<VfxTime>();
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    for (entity, drivers) in drivers {
+        let mut entity = world.entity_mut(entity);
+
+        for driver in &drivers.0 {
+            let Some(expression) = cache.get(&driver.expression) else {
+                continue;
+            };
+
+            let value = expression.evaluate(&time);
+            if let Err(e) = util::set_component_number(
+                &type_registry,
+                &mut entity,
+                &driver.component,
+                &driver.field,
+                value,
+            ) {
+                cache.warn(driver, &e);
+            }
+        }
+    }
+}
+
+/// Evaluates [`ParamDrivers`] each frame.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ParamDriver>()
+            .register_type::<Vec<ParamDriver>>()
+            .register_type::<ParamDrivers>()
+            .add_systems(PostUpdate, drive_params);
+    }
+}
@@ -0,0 +1,360 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{ShaderRef, ShaderType},
+};
+
+use crate::{
+    post_processing::{
+        color_adjust::ColorAdjust,
+        composition::Composition,
+        custom::{CustomPostProcessingPlugin, PostProcessingEffect, SHADER_INTERFACE_VERSION},
+        masks::{Mask, MaskVariant},
+        Order,
+    },
+    time::VfxTime,
+};
+
+pub(crate) const FLASH_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6120498353027764185);
+
+/// Tints the whole image with a color which fades out, e.g. white when landing a hit.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::feedback::FlashColor;
+/// fn on_hit(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     commands
+///         .entity(camera.single())
+///         .insert(FlashColor::new(Color::rgba(1.0, 1.0, 1.0, 0.6), 0.15));
+/// }
+/// ```
+///
+/// The alpha of the color is how much it covers the image when the flash starts.
+/// The flash is drawn over all other effects. Inserting it again restarts it,
+/// and it removes itself once it is over.
+///
+/// Flashes follow the [`VfxTime`] clock, so they hold while a [`FreezeFrameDesaturate`]
+/// pauses the effects.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct FlashColor {
+    /// The color of the flash.
+    pub color: Color,
+
+    /// How many seconds the flash takes to fade out.
+    pub duration: f32,
+
+    elapsed: f32,
+}
+
+impl FlashColor {
+    /// A flash of the given color, fading out over the given seconds.
+    pub fn new(color: Color, duration: f32) -> Self {
+        Self {
+            color,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// How far along the flash is, from `0.0` when it starts to `1.0` when it is over.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        (self.elapsed / self.duration).min(1.0)
+    }
+}
+
+/// The overlay drawing a [`FlashColor`].
+#[derive(Debug, Clone, Component, ShaderType)]
+struct FlashOverlay {
+    color: Vec4,
+}
+
+impl PostProcessingEffect for FlashOverlay {
+    const SHADER_INTERFACE: u32 = SHADER_INTERFACE_VERSION;
+    const COMPOSITION: Composition = Composition::AlphaOver;
+
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Handle(FLASH_SHADER_HANDLE.typed())
+    }
+}
+
+fn flash(
+    mut commands: Commands,
+    time: Res<VfxTime>,
+    mut flashes: Query<(Entity, &mut FlashColor)>,
+    mut removed: RemovedComponents<FlashColor>,
+) {
+    for entity in removed.iter() {
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<(FlashOverlay, Order<FlashOverlay>)>();
+        }
+    }
+
+    for (entity, mut flash) in &mut flashes {
+        flash.elapsed += time.delta_seconds();
+
+        let progress = flash.progress();
+        if progress >= 1.0 {
+            commands
+                .entity(entity)
+                .remove::<(FlashColor, FlashOverlay, Order<FlashOverlay>)>();
+            continue;
+        }
+
+        // Easing out keeps the flash bright for a moment before it fades.
+        let fade = (1.0 - progress).powi(2);
+        let [r, g, b, a] = flash.color.as_linear_rgba_f32();
+
+        commands.entity(entity).insert((
+            FlashOverlay {
+                color: Vec4::new(r, g, b, a * fade),
+            },
+            Order::<FlashOverlay>::new(f32::MAX),
+        ));
+    }
+}
+
+/// Drains the color from the image and pauses the effects for a moment,
+/// e.g. for the hit-stop of a heavy blow.
+///
+/// While it lasts, the [`ColorAdjust`] of the camera has the given saturation, and if
+/// [`FreezeFrameDesaturate::pause_effects`] is set, the [`VfxTime`] clock is paused.
+/// Afterwards the camera gets back the [`ColorAdjust`] it had (or none), the clock runs again,
+/// and the component removes itself.
+///
+/// It follows the unscaled time of Bevy's [`Time`], so the game can stop its own clock for the
+/// hit-stop. Inserting it again restarts it.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct FreezeFrameDesaturate {
+    /// How many seconds the frame is frozen.
+    pub duration: f32,
+
+    /// The saturation of the frozen frame, `0.0` for black and white.
+    pub saturation: f32,
+
+    /// If true, the [`VfxTime`] clock is paused, so animated effects hold still.
+    pub pause_effects: bool,
+
+    elapsed: f32,
+}
+
+impl FreezeFrameDesaturate {
+    /// A black and white freeze frame of the given seconds, which pauses the effects.
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            saturation: 0.0,
+            pause_effects: true,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Keep some of the color, e.g. `0.3`.
+    pub fn with_saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    /// Keep the effects animating during the freeze frame.
+    pub fn without_pausing_effects(mut self) -> Self {
+        self.pause_effects = false;
+        self
+    }
+}
+
+/// The [`ColorAdjust`] a camera had before its [`FreezeFrameDesaturate`], if any.
+#[derive(Debug, Component)]
+struct BeforeFreeze(Option<ColorAdjust>);
+
+fn freeze_frame(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut vfx_time: ResMut<VfxTime>,
+    mut paused_clock: Local<bool>,
+    mut freezes: Query<(
+        Entity,
+        &mut FreezeFrameDesaturate,
+        Option<&mut ColorAdjust>,
+        Option<&BeforeFreeze>,
+    )>,
+) {
+    let mut pausing = false;
+
+    for (entity, mut freeze, color_adjust, before) in &mut freezes {
+        let before = match before {
+            Some(BeforeFreeze(before)) => *before,
+            None => {
+                let before = color_adjust.as_deref().copied();
+                commands.entity(entity).insert(BeforeFreeze(before));
+                before
+            }
+        };
+
+        freeze.elapsed += time.raw_delta_seconds();
+
+        if freeze.elapsed >= freeze.duration {
+            let mut entity = commands.entity(entity);
+            entity.remove::<(FreezeFrameDesaturate, BeforeFreeze)>();
+            match before {
+                Some(before) => {
+                    entity.insert(before);
+                }
+                None => {
+                    entity.remove::<ColorAdjust>();
+                }
+            }
+            continue;
+        }
+
+        pausing |= freeze.pause_effects;
+
+        match color_adjust {
+            Some(mut color_adjust) => color_adjust.saturation = freeze.saturation,
+            None => {
+                commands.entity(entity).insert(ColorAdjust {
+                    saturation: freeze.saturation,
+                    ..default()
+                });
+            }
+        }
+    }
+
+    // The clock is only run again if it was a freeze frame which paused it.
+    if pausing && !vfx_time.paused {
+        vfx_time.paused = true;
+        *paused_clock = true;
+    } else if !pausing && *paused_clock {
+        vfx_time.paused = false;
+        *paused_clock = false;
+    }
+}
+
+/// A vignette which closes in and pulses as health runs low, e.g. for a first person camera.
+///
+/// Keep [`DamageVignette::health`] up to date from the game. Below
+/// [`DamageVignette::threshold`] the vignette fades in, and the lower the health,
+/// the stronger it is and the faster it pulses.
+///
+/// This drives the [`Mask`] of the camera, and removes it again along with itself,
+/// so the camera should not have a [`Mask`] of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct DamageVignette {
+    /// The health of the player, from `0.0` (none left) to `1.0` (full).
+    pub health: f32,
+
+    /// The health below which the vignette shows.
+    pub threshold: f32,
+
+    /// The color of the vignette.
+    pub color: Color,
+
+    /// How many times a second the vignette pulses with no health left.
+    /// Just below the threshold it pulses half as often.
+    pub pulse_rate: f32,
+
+    /// The strength of the vignette, see [`MaskVariant::Vignette`].
+    pub strength: f32,
+
+    phase: f32,
+}
+
+impl Default for DamageVignette {
+    fn default() -> Self {
+        Self {
+            health: 1.0,
+            threshold: 0.35,
+            color: Color::rgb(0.6, 0.0, 0.0),
+            pulse_rate: 2.0,
+            strength: 1.0,
+            phase: 0.0,
+        }
+    }
+}
+
+impl DamageVignette {
+    /// A vignette for the given health, from `0.0` to `1.0`.
+    pub fn new(health: f32) -> Self {
+        Self {
+            health,
+            ..default()
+        }
+    }
+
+    /// How close to no health the vignette is, from `0.0` at the threshold to `1.0` with none left.
+    pub fn danger(&self) -> f32 {
+        ((self.threshold - self.health) / self.threshold.max(f32::EPSILON)).clamp(0.0, 1.0)
+    }
+}
+
+fn damage_vignette(
+    mut commands: Commands,
+    time: Res<VfxTime>,
+    mut vignettes: Query<(Entity, &mut DamageVignette, Option<&mut Mask>)>,
+    mut removed: RemovedComponents<DamageVignette>,
+) {
+    for entity in removed.iter() {
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<Mask>();
+        }
+    }
+
+    for (entity, mut vignette, mask) in &mut vignettes {
+        let danger = vignette.danger();
+        if danger <= 0.0 {
+            if mask.is_some() {
+                commands.entity(entity).remove::<Mask>();
+            }
+            vignette.phase = 0.0;
+            continue;
+        }
+
+        // The phase advances instead of being taken from the time, so changing the rate doesn't jump.
+        let rate = vignette.pulse_rate * (0.5 + 0.5 * danger);
+        vignette.phase = (vignette.phase + rate * time.delta_seconds()).fract();
+        let pulse = 0.5 - 0.5 * (vignette.phase * TAU).cos();
+
+        let fade = 1.0 - danger * (0.75 + 0.25 * pulse);
+
+        match mask {
+            Some(mut mask) => {
+                mask.variant = MaskVariant::Vignette;
+                mask.strength = vignette.strength;
+                mask.fade = fade;
+                mask.color = vignette.color;
+            }
+            None => {
+                commands.entity(entity).insert(Mask {
+                    strength: vignette.strength,
+                    fade,
+                    color: vignette.color,
+                    ..Mask::vignette()
+                });
+            }
+        }
+    }
+}
+
+/// Runs the [`FlashColor`], [`FreezeFrameDesaturate`] and [`DamageVignette`] helpers.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            FLASH_SHADER_HANDLE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "flash.wgsl"),
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(CustomPostProcessingPlugin::<FlashOverlay>::default())
+            .add_systems(Update, (flash, freeze_frame, damage_vignette));
+    }
+}
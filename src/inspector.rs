@@ -0,0 +1,344 @@
+use bevy::{prelude::*, reflect::GetPath, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContext, EguiPlugin};
+
+use crate::{
+    metadata::{EffectMetadata, ParameterInfo},
+    post_processing::external::{ExternalEffectEntry, ExternalEffects},
+    state::{EffectKind, EffectState},
+    util,
+};
+
+/// The effect being dragged to a new place in the stack of a camera.
+#[derive(Debug, Default, Resource)]
+struct InspectorDrag(Option<(Entity, usize)>);
+
+/// An effect of this crate, or of another crate by its name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Item {
+    Kind(EffectKind),
+    External(&'static str),
+}
+
+impl Item {
+    fn name(self) -> &'static str {
+        match self {
+            Item::Kind(kind) => kind.name(),
+            Item::External(name) => name,
+        }
+    }
+
+    fn parameters(
+        self,
+        metadata: &EffectMetadata,
+        external: &ExternalEffects,
+        state: &EffectState,
+    ) -> Vec<ParameterInfo> {
+        match self {
+            // The ranges may depend on the effect as it is, e.g. on the variant of a mask.
+            Item::Kind(kind) => state
+                .get(kind)
+                .map(|entry| metadata.parameters_for(&entry.effect))
+                .unwrap_or_else(|| metadata.get(kind).parameters.clone()),
+            Item::External(name) => external
+                .get(name)
+                .map(|effect| effect.parameters().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// A change made in the inspector, applied once it is drawn.
+enum Edit {
+    Set {
+        camera: Entity,
+        item: Item,
+        field: &'static str,
+        value: f32,
+    },
+    Add {
+        camera: Entity,
+        item: Item,
+    },
+    Remove {
+        camera: Entity,
+        item: Item,
+    },
+    Reorder {
+        camera: Entity,
+        stack: Vec<Item>,
+    },
+}
+
+/// The effects on a camera, first to last.
+fn stack(world: &World, external: &ExternalEffects, camera: Entity) -> Vec<Item> {
+    let entity = world.entity(camera);
+    let state = EffectState::capture(&entity);
+
+    let mut effects: Vec<(Item, Option<f32>)> = state
+        .effects()
+        .iter()
+        .map(|entry| (Item::Kind(entry.effect.kind()), entry.order))
+        .chain(external.iter().filter_map(|effect| {
+            let entry = effect.capture(&entity)?;
+            Some((Item::External(effect.name()), entry.order))
+        }))
+        .collect();
+
+    // Effects without an order are at `0.0`, and ties keep the order of the kinds.
+    effects.sort_by(|(_, a), (_, b)| a.unwrap_or_default().total_cmp(&b.unwrap_or_default()));
+    effects.into_iter().map(|(item, _)| item).collect()
+}
+
+/// The values of the given parameters of an effect on a camera.
+fn values(
+    world: &World,
+    external: &ExternalEffects,
+    camera: Entity,
+    item: Item,
+    parameters: &[ParameterInfo],
+) -> Vec<f32> {
+    let entity = world.entity(camera);
+    let effect = match item {
+        Item::Kind(kind) => kind.reflect(&entity),
+        Item::External(name) => external
+            .get(name)
+            .and_then(|effect| effect.reflect(&entity)),
+    };
+    let Some(effect) = effect else {
+        return vec![];
+    };
+
+    parameters
+        .iter()
+        .map(|parameter| {
+            effect
+                .reflect_path(parameter.name)
+                .ok()
+                .and_then(util::get_number)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn draw_inspector(world: &mut World) {
+    let Ok(mut context) = world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>()
+        .get_single_mut(world)
+    else {
+        return;
+    };
+    let context = context.get_mut().clone();
+
+    let metadata = world.resource::<EffectMetadata>().clone();
+    let external = world.resource::<ExternalEffects>().clone();
+    let mut drag = world.remove_resource::<InspectorDrag>().unwrap_or_default();
+    let mut edits = vec![];
+
+    let cameras: Vec<(Entity, Option<String>)> = world
+        .query_filtered::<(Entity, Option<&Name>), With<Camera>>()
+        .iter(world)
+        .map(|(entity, name)| (entity, name.map(|name| name.to_string())))
+        .collect();
+
+    egui::Window::new("Post processing").show(&context, |ui| {
+        for (camera, name) in cameras {
+            let title = name.unwrap_or_else(|| format!("Camera {camera:?}"));
+            let stack = stack(world, &external, camera);
+            let state = EffectState::capture(&world.entity(camera));
+
+            egui::CollapsingHeader::new(title)
+                .id_source(camera)
+                .default_open(true)
+                .show(ui, |ui| {
+                    let mut rows = vec![];
+
+                    for (index, &item) in stack.iter().enumerate() {
+                        let parameters = item.parameters(&metadata, &external, &state);
+                        let values = values(world, &external, camera, item, &parameters);
+
+                        let row = ui.group(|ui| {
+                            ui.horizontal(|ui| {
+                                let handle =
+                                    ui.add(egui::Label::new("☰").sense(egui::Sense::drag()));
+                                if handle.drag_started() {
+                                    drag.0 = Some((camera, index));
+                                }
+                                if handle.hovered() || drag.0 == Some((camera, index)) {
+                                    ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+                                }
+
+                                ui.strong(item.name());
+                                if ui.small_button("✖").clicked() {
+                                    edits.push(Edit::Remove { camera, item });
+                                }
+                            });
+
+                            for (parameter, mut value) in parameters.iter().zip(values) {
+                                let slider =
+                                    egui::Slider::new(&mut value, parameter.min..=parameter.max)
+                                        .text(parameter.name)
+                                        .clamp_to_range(false);
+                                let slider = if parameter.integer {
+                                    slider.step_by(1.0)
+                                } else {
+                                    slider
+                                };
+
+                                if ui.add(slider).changed() {
+                                    edits.push(Edit::Set {
+                                        camera,
+                                        item,
+                                        field: parameter.name,
+                                        value,
+                                    });
+                                }
+                            }
+                        });
+                        rows.push(row.response.rect);
+                    }
+
+                    // Dropping an effect puts it where the pointer is among the others.
+                    let released = ui.input(|input| input.pointer.any_released());
+                    let pointer = ui.input(|input| input.pointer.interact_pos());
+                    if let (true, Some((dragged, from)), Some(pointer)) =
+                        (released, drag.0, pointer)
+                    {
+                        if dragged == camera {
+                            let mut to =
+                                rows.iter().filter(|row| row.center().y < pointer.y).count();
+                            if to > from {
+                                to -= 1;
+                            }
+
+                            if to != from {
+                                let mut stack = stack.clone();
+                                let item = stack.remove(from);
+                                stack.insert(to, item);
+                                edits.push(Edit::Reorder { camera, stack });
+                            }
+                            drag.0 = None;
+                        }
+                    }
+
+                    egui::ComboBox::from_id_source(("add effect", camera))
+                        .selected_text("Add effect")
+                        .show_ui(ui, |ui| {
+                            let items = EffectKind::ALL
+                                .iter()
+                                .map(|&kind| Item::Kind(kind))
+                                .chain(external.iter().map(|effect| Item::External(effect.name())));
+
+                            for item in items {
+                                if !stack.contains(&item)
+                                    && ui.selectable_label(false, item.name()).clicked()
+                                {
+                                    edits.push(Edit::Add { camera, item });
+                                }
+                            }
+                        });
+                });
+        }
+    });
+
+    // A drag released outside of any camera is dropped.
+    if context.input(|input| input.pointer.any_released()) {
+        drag.0 = None;
+    }
+    world.insert_resource(drag);
+
+    for edit in edits {
+        apply(world, &external, edit);
+    }
+}
+
+fn apply(world: &mut World, external: &ExternalEffects, edit: Edit) {
+    match edit {
+        Edit::Set {
+            camera,
+            item,
+            field,
+            value,
+        } => {
+            let mut entity = world.entity_mut(camera);
+            let effect = match item {
+                Item::Kind(kind) => kind.reflect_mut(&mut entity),
+                Item::External(name) => external
+                    .get(name)
+                    .and_then(|effect| effect.reflect_mut(&mut entity)),
+            };
+            if let Some(mut effect) = effect {
+                let _ = util::set_field_number(&mut *effect, field, value);
+            }
+        }
+        Edit::Add { camera, item } => {
+            let mut stack = stack(world, external, camera);
+            stack.push(item);
+            reorder(world, external, camera, &stack);
+        }
+        Edit::Remove { camera, item } => {
+            let mut entity = world.entity_mut(camera);
+            match item {
+                Item::Kind(kind) => kind.remove(&mut entity),
+                Item::External(name) => {
+                    if let Some(effect) = external.get(name) {
+                        effect.remove(&mut entity);
+                    }
+                }
+            }
+        }
+        Edit::Reorder { camera, stack } => {
+            reorder(world, external, camera, &stack);
+        }
+    }
+}
+
+/// Orders the effects on the camera as in the stack, adding any which are missing with defaults.
+fn reorder(world: &mut World, external: &ExternalEffects, camera: Entity, stack: &[Item]) {
+    let current = EffectState::capture(&world.entity(camera));
+
+    let mut state = EffectState::default();
+    let mut external_entries = vec![];
+    for (index, item) in stack.iter().enumerate() {
+        let order = Some(index as f32);
+        match *item {
+            Item::Kind(kind) => {
+                let effect = current
+                    .get(kind)
+                    .map(|entry| entry.effect)
+                    .unwrap_or_else(|| kind.default_effect());
+                state = state.with(effect, order);
+            }
+            // Without parameters, the entry keeps the ones on the camera.
+            Item::External(name) => external_entries.push(ExternalEffectEntry {
+                name: name.to_string(),
+                order,
+                ..default()
+            }),
+        }
+    }
+
+    let mut entity = world.entity_mut(camera);
+    state.apply(&mut entity);
+    external.apply(&mut entity, &external_entries);
+}
+
+/// Draws a window listing the effects on each camera, with sliders for their parameters.
+///
+/// Effects can be added, removed, and dragged by their handle to reorder them.
+/// The ranges of the sliders come from the [`EffectMetadata`] resource, and for effects of other
+/// crates from the [`ExternalEffects`] resource.
+/// Effects which refer to assets, such as [`crate::post_processing::lut::Lut`], are not listed.
+///
+/// Adds the [`EguiPlugin`] if it isn't added yet. Needs the `egui` feature.
+#[derive(Debug, Default)]
+pub struct PostProcessingInspectorPlugin;
+
+impl Plugin for PostProcessingInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.add_systems(Update, draw_inspector);
+    }
+}
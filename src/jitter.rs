@@ -0,0 +1,157 @@
+use bevy::{
+    core_pipeline::experimental::taa::TemporalAntiAliasSettings, prelude::*,
+    render::camera::TemporalJitter,
+};
+
+/// The element of the Halton low discrepancy sequence of the given base at the given index,
+/// in the range `[0.0, 1.0)`.
+///
+/// Consecutive elements cover the range evenly, which is why the sequence is commonly used
+/// to place samples for temporal supersampling.
+/// Index zero is always `0.0`, so sequences usually start at index one.
+pub fn halton(mut index: u32, base: u32) -> f32 {
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+
+    result
+}
+
+/// Offsets the projection of a camera by a different subpixel amount every frame,
+/// following the Halton (2, 3) sequence.
+///
+/// Each frame then samples the scene at slightly different points within the pixels.
+/// Effects which accumulate frames over time, such as temporal supersampling or
+/// progressive refinement, average these into an image with more detail than a single frame has.
+///
+/// The jitter of the current frame is [`SubpixelJitter::offset`].
+/// Effects get it by copying it into their uniform, e.g. for a
+/// [`crate::post_processing::custom::PostProcessingEffect`]:
+///
+/// ```no_run
+/// # use bevy::{prelude::*, render::render_resource::ShaderType};
+/// # use bevy_vfx_bag::jitter::SubpixelJitter;
+/// #[derive(Debug, Clone, Component, ShaderType)]
+/// struct Accumulate {
+///     jitter: Vec2,
+///     sample: u32,
+/// }
+///
+/// fn update_accumulate(mut cameras: Query<(&mut Accumulate, &SubpixelJitter)>) {
+///     for (mut accumulate, jitter) in &mut cameras {
+///         accumulate.jitter = jitter.offset();
+///         accumulate.sample = jitter.sample();
+///     }
+/// }
+/// ```
+///
+/// The offset is applied via Bevy's [`TemporalJitter`], which this component inserts.
+/// Only perspective projections can be jittered, and Bevy's TAA
+/// ([`TemporalAntiAliasSettings`]) drives the jitter on its own, so don't combine the two.
+/// The jitter keeps going while [`crate::time::VfxTime`] is paused, so a still frame keeps refining.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct SubpixelJitter {
+    /// How many frames until the offsets repeat.
+    /// Accumulation effects which average this many frames see every sample once.
+    pub length: u32,
+
+    /// How far the samples spread, as a fraction of a pixel.
+    /// At `1.0` they cover the whole pixel, at `0.0` there is no jitter.
+    pub scale: f32,
+
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    sample: u32,
+}
+
+impl SubpixelJitter {
+    /// Jitter which repeats after the given number of frames.
+    pub fn new(length: u32) -> Self {
+        Self {
+            length,
+            ..default()
+        }
+    }
+
+    /// Which sample of the sequence the current frame is, from zero to [`SubpixelJitter::length`].
+    ///
+    /// Accumulation effects can weigh the current frame by `1.0 / (sample + 1.0)` while
+    /// refining a still image, for an exact average.
+    pub fn sample(&self) -> u32 {
+        self.sample
+    }
+
+    /// The offset of the current frame, in pixels, in the range `[-0.5, 0.5]` scaled by
+    /// [`SubpixelJitter::scale`].
+    /// This is the offset Bevy's [`TemporalJitter`] applies to the projection.
+    pub fn offset(&self) -> Vec2 {
+        // Halton starts at zero, so skip that to keep the samples centered around the pixel.
+        let index = self.sample % self.length.max(1) + 1;
+        let offset = Vec2::new(halton(index, 2), halton(index, 3)) - 0.5;
+
+        offset * self.scale
+    }
+
+    /// Start over from the first sample, e.g. when accumulation restarts after the camera moved.
+    pub fn reset(&mut self) {
+        self.sample = 0;
+    }
+}
+
+impl Default for SubpixelJitter {
+    fn default() -> Self {
+        Self {
+            length: 8,
+            scale: 1.0,
+            sample: 0,
+        }
+    }
+}
+
+/// Advances the jitter, and hands it to Bevy to offset the projection.
+fn update_jitter(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut SubpixelJitter, Option<&mut TemporalJitter>)>,
+) {
+    for (entity, mut jitter, temporal_jitter) in &mut cameras {
+        // The first frame uses the first sample.
+        if !jitter.is_added() {
+            jitter.sample = (jitter.sample + 1) % jitter.length.max(1);
+        }
+
+        let offset = jitter.offset();
+
+        match temporal_jitter {
+            Some(mut temporal_jitter) => temporal_jitter.offset = offset,
+            None => {
+                commands.entity(entity).insert(TemporalJitter { offset });
+            }
+        }
+    }
+}
+
+/// Bevy's TAA sets the jitter of its cameras in the render world, which overrides ours.
+fn warn_temporal_anti_aliasing(
+    cameras: Query<Entity, (Added<SubpixelJitter>, With<TemporalAntiAliasSettings>)>,
+) {
+    for entity in &cameras {
+        warn!("Camera {entity:?} has both `SubpixelJitter` and TAA, TAA overrides the jitter");
+    }
+}
+
+/// Drives the [`SubpixelJitter`] of cameras.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SubpixelJitter>()
+            .add_systems(PostUpdate, (update_jitter, warn_temporal_anti_aliasing));
+    }
+}
@@ -1,13 +1,85 @@
 #![allow(clippy::too_many_arguments)] // Bevy fns tend to have many args.
+#![allow(clippy::type_complexity)] // Bevy queries tend to have complex types.
 #![deny(clippy::unwrap_used)] // Let's try to explain invariants when we unwrap (so use expect).
 #![deny(missing_docs)] // Let's try to have good habits.
 #![doc = include_str!("../README.md")]
 
+/// Tweening of effect parameters from one value to another.
+pub mod animation;
+
+/// Applying effects to image files and saving the results, e.g. for pre-baking textures.
+#[cfg(feature = "batch")]
+pub mod batch;
+
+/// Drive effect parameters from MIDI and OSC control surfaces, e.g. for live visuals.
+#[cfg(feature = "control")]
+pub mod control;
+
+/// Curves of keyframes driving effect parameters over a timer, e.g. authored by artists in `.curve.ron` files.
+pub mod curve;
+
+/// Drive effect parameters with expressions, e.g. from scene files.
+pub mod expression;
+
+/// Canned gameplay feedback built on the effects, such as flashing the screen on hits.
+pub mod feedback;
+
+/// A window for inspecting and changing the effects on each camera, drawn with egui.
+#[cfg(feature = "egui")]
+pub mod inspector;
+
+/// Subpixel jitter of camera projections, for temporal supersampling and accumulation effects.
+pub mod jitter;
+
+/// The names and parameters of all effects, for tooling such as inspectors.
+pub mod metadata;
+
+/// Driving effect parameters from signals such as the audio level, e.g. for audio-reactive visuals.
+pub mod modulation;
+
+/// Effects which are added for a moment, e.g. on impacts, optionally with gamepad rumble.
+pub mod one_shot;
+
 /// Post processing effects.
 pub mod post_processing;
 
 mod plugin;
 
+/// Effect stacks authored in `.vfx.ron` files, loaded as assets.
+#[cfg(feature = "ron")]
+pub mod preset;
+
+/// Effect stacks saved in Bevy scenes, spawned onto existing cameras.
+#[cfg(feature = "scene")]
+pub mod scene;
+
+/// A bridge for scripting runtimes (such as Lua or WASM), so mods can control effects.
+///
+/// This does not depend on a particular runtime: bind the functions and [`scripting::ScriptCommand`]s
+/// in this module to script functions in the runtime of your choice.
+#[cfg(feature = "scripting")]
+pub mod scripting;
+
+/// Stress tests of effect stacks, for checking them against a frame budget.
+#[cfg(feature = "stress")]
+pub mod stress;
+
+/// Snapshots of the effects on cameras, for replication and replays.
+pub mod state;
+
+/// A reference scene for evaluating effects the same way across versions and hardware.
+#[cfg(feature = "test_fixtures")]
+pub mod test_fixtures;
+
+/// Keyframed animation of effect parameters.
+pub mod timeline;
+
+/// The clock driving animated effects.
+pub mod time;
+
+/// Cross-fading between sets of effects.
+pub mod transition;
+
 pub use plugin::BevyVfxBagPlugin;
 
 /// Utilities.
@@ -0,0 +1,661 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    prelude::*,
+    reflect::{reflect_trait, GetPath, ReflectRef, TypeInfo, TypePath},
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    post_processing::{
+        ascii::Ascii, auto_exposure::AutoExposure, blur::Blur, bulge::Bulge,
+        chromatic_aberration::ChromaticAberration, color_adjust::ColorAdjust,
+        color_blindness::ColorBlindness, day_for_night::DayForNight, dead_pixels::DeadPixels,
+        deband::Deband, depth_grade::DepthGrade, depth_of_field::DepthOfField, dither::Dither,
+        edge_fringe::EdgeFringe, false_color::FalseColor, film_projector::FilmProjector,
+        flip::Flip, fog::Fog, fog_2d::Fog2d, frosted_glass::FrostedGlass, ghosting::Ghosting,
+        glow::Glow, god_rays_2d::GodRays2d, halftone::Halftone, heat_haze::HeatHaze,
+        hologram::Hologram, kuwahara::Kuwahara, lens_distortion::LensDistortion,
+        lens_flare::LensFlare, letterbox::Letterbox, light_leak::LightLeak,
+        light_shafts::LightShafts, line_jitter::LineJitter, lut::Lut, magnifier::Magnifier,
+        masks::Mask, masks::MaskVariant, motion_blur::MotionBlur, old_film::OldFilm,
+        outline::Outline, palette_swap::PaletteSwap, pixelate::Pixelate, psx_wobble::PsxWobble,
+        raindrops::Raindrops, retro_fog::RetroFog, rolling_shutter::RollingShutter,
+        screen_door_fade::ScreenDoorFade, screen_transition::ScreenTransition, sharpen::Sharpen,
+        shockwave::Shockwave, sketch::Sketch, speed_lines::SpeedLines,
+        stable_regions::StableRegions, underwater::Underwater, vision_mode::VisionMode, wave::Wave,
+        weather_overlay::WeatherOverlay, wet_screen_sheen::WetScreenSheen,
+        white_balance::WhiteBalance,
+    },
+    state::{Effect, EffectKind},
+    util,
+};
+
+/// A numeric parameter of an effect, and the range it is useful in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterInfo {
+    /// The name of the field on the effect component, e.g. `"strength"`.
+    pub name: &'static str,
+
+    /// The lowest useful value.
+    pub min: f32,
+
+    /// The highest useful value.
+    pub max: f32,
+
+    /// The value of the parameter in the default effect.
+    pub default: f32,
+
+    /// True if the field holds whole numbers, e.g. a number of samples.
+    pub integer: bool,
+}
+
+/// An effect and its numeric parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectInfo {
+    /// Which effect this is. See [`EffectKind::name`] for its name.
+    pub kind: EffectKind,
+
+    /// The numeric parameters of the effect, in the order of the fields.
+    ///
+    /// Effects which are enums (such as [`crate::post_processing::flip::Flip`]) have none.
+    pub parameters: Vec<ParameterInfo>,
+}
+
+impl EffectInfo {
+    /// The parameter with the given name, if it is numeric.
+    pub fn parameter(&self, name: &str) -> Option<&ParameterInfo> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name == name)
+    }
+
+    fn of(kind: EffectKind) -> Self {
+        Self {
+            kind,
+            parameters: kind.default_effect().parameters(),
+        }
+    }
+}
+
+impl ParameterInfo {
+    /// Whether the value is further outside of the range than the range is wide.
+    ///
+    /// Such values are rarely deliberate, e.g. a strength meant for another variant of a [`Mask`],
+    /// or a value in the wrong units.
+    pub fn is_far_outside(&self, value: f32) -> bool {
+        let width = self.max - self.min;
+        value < self.min - width || value > self.max + width
+    }
+}
+
+/// The numeric parameters of an effect, with the ranges they are useful in and their defaults.
+///
+/// Implemented by each effect of this crate. The [`EffectMetadata`] and the inspector go by it,
+/// as does the [`ParameterValidation`] of effects set far outside of their ranges.
+/// Look it up for a reflected effect with the `ReflectParameterRange` type data.
+#[reflect_trait]
+pub trait ParameterRange {
+    /// The numeric parameters of the effect as it is, in the order of the fields.
+    ///
+    /// The ranges and defaults may depend on the other fields, e.g. the strength of a [`Mask`]
+    /// on its variant.
+    fn parameters(&self) -> Vec<ParameterInfo>;
+}
+
+/// Implements [`ParameterRange`] for effects with fixed ranges, given as `field: min..=max`.
+///
+/// The defaults are those of the [`Default`] effect, and fields which aren't listed get a range
+/// guessed from their default.
+macro_rules! parameter_ranges {
+    ($($effect:ident { $($field:ident: $range:expr),* $(,)? }),* $(,)?) => {
+        $(
+            impl ParameterRange for $effect {
+                fn parameters(&self) -> Vec<ParameterInfo> {
+                    parameters_with_ranges(
+                        &$effect::default(),
+                        &[$((stringify!($field), *$range.start(), *$range.end())),*],
+                    )
+                }
+            }
+        )*
+    };
+}
+
+parameter_ranges!(
+    Ascii {
+        cell_size: 4.0..=32.0
+    },
+    AutoExposure {
+        min_ev: -16.0..=0.0,
+        max_ev: 0.0..=16.0,
+        low_percentile: 0.0..=1.0,
+        high_percentile: 0.0..=1.0,
+        adapt_to_bright: 0.0..=10.0,
+        adapt_to_dark: 0.0..=10.0,
+        compensation: -4.0..=4.0
+    },
+    Blur {
+        amount: 0.0..=1.0,
+        kernel_radius: 0.0..=0.05
+    },
+    Bulge {
+        radius: 0.0..=1.0,
+        strength: -1.0..=1.0
+    },
+    ChromaticAberration {
+        magnitude_r: 0.0..=0.05,
+        magnitude_g: 0.0..=0.05,
+        magnitude_b: 0.0..=0.05,
+    },
+    ColorAdjust {
+        brightness: -0.5..=0.5,
+        contrast: 0.0..=2.0,
+        saturation: 0.0..=2.0,
+        hue_shift: -180.0..=180.0,
+        gamma: 0.2..=3.0,
+    },
+    ColorBlindness {
+        severity: 0.0..=1.0
+    },
+    DayForNight {
+        exposure: -4.0..=0.0,
+        highlight_crush: 0.0..=1.0,
+        contrast: 0.5..=2.0,
+        desaturation: 0.0..=1.0,
+        blue_shift: 0.0..=1.0,
+        rim: 0.0..=2.0,
+        rim_power: 1.0..=8.0,
+    },
+    DeadPixels {
+        count: 0.0..=100.0,
+        seed: 0.0..=100.0,
+        size: 1.0..=8.0,
+        scratches: 0.0..=8.0
+    },
+    Deband {
+        strength: 0.0..=4.0
+    },
+    DepthGrade {
+        near_distance: 0.0..=50.0,
+        far_distance: 0.0..=200.0,
+        transition: 0.0..=20.0
+    },
+    DepthOfField {
+        focal_distance: 0.0..=100.0,
+        aperture: 0.0..=2.0,
+        max_blur_radius: 0.0..=32.0,
+        sample_count: 1.0..=128.0,
+    },
+    Dither { levels: 2.0..=16.0 },
+    EdgeFringe {
+        strength: 0.0..=0.03,
+        start: 0.0..=1.0,
+        falloff: 1.0..=4.0
+    },
+    FalseColor {
+        exposure: -4.0..=4.0,
+        chart_size: 0.1..=0.5
+    },
+    FilmProjector {
+        frame_rate: 8.0..=30.0,
+        blades: 1.0..=3.0,
+        flicker: 0.0..=1.0,
+        weave: 0.0..=0.01,
+        splice_interval: 0.0..=30.0,
+        splice_duration: 0.0..=1.0,
+        splice_jump: 0.0..=1.0,
+    },
+    Flip {},
+    Fog {
+        start: 0.0..=100.0,
+        end: 0.0..=200.0,
+        density: 0.0..=1.0
+    },
+    Fog2d {
+        density: 0.0..=2.0,
+        background_depth: 0.0..=10.0
+    },
+    FrostedGlass {
+        distortion: 0.0..=40.0,
+        distortion_scale: 32.0..=1024.0,
+        blur_radius: 0.0..=16.0
+    },
+    Ghosting {
+        decay: 0.0..=1.0,
+        zoom: 0.9..=1.1
+    },
+    Glow {
+        intensity: 0.0..=4.0,
+        threshold: 0.0..=2.0,
+        knee: 0.0..=1.0,
+        radius: 0.0..=64.0
+    },
+    GodRays2d {
+        light_radius: 0.0..=0.5,
+        density: 0.0..=1.0,
+        decay: 0.9..=1.0,
+        exposure: 0.0..=4.0,
+        samples: 8.0..=128.0,
+    },
+    Halftone {
+        dot_size: 2.0..=24.0,
+        threshold: 0.0..=1.0,
+        cyan_angle: 0.0..=PI,
+        magenta_angle: 0.0..=PI,
+        yellow_angle: 0.0..=PI,
+        black_angle: 0.0..=PI,
+    },
+    HeatHaze {
+        strength: 0.0..=0.03,
+        scale: 4.0..=64.0,
+        speed: 0.0..=1.0,
+        feather: 0.0..=0.5
+    },
+    Hologram {
+        line_density: 50.0..=500.0,
+        jitter_amplitude: 0.0..=16.0,
+        dropout_frequency: 0.0..=2.0
+    },
+    Kuwahara {
+        radius: 0.0..=16.0,
+        samples_per_side: 1.0..=16.0
+    },
+    LensDistortion {
+        distortion: -1.0..=1.0,
+        fringing: 0.0..=0.2
+    },
+    LensFlare {
+        light_depth: 0.0..=1.0,
+        intensity: 0.0..=4.0,
+        ghosts: 0.0..=8.0,
+        ghost_spacing: 0.0..=1.0,
+        ghost_size: 0.0..=0.2,
+        halo_radius: 0.0..=0.5,
+        halo_width: 0.0..=0.1,
+        halo_intensity: 0.0..=1.0,
+        streak_length: 0.0..=1.0,
+        streak_intensity: 0.0..=1.0,
+        occlusion_radius: 0.0..=32.0,
+    },
+    Letterbox {
+        aspect_ratio: 1.0..=3.0,
+        progress: 0.0..=1.0
+    },
+    LightLeak {
+        intensity: 0.0..=1.0,
+        scale: 0.1..=1.0,
+        speed: 0.0..=4.0,
+        interval: 1.0..=20.0,
+        seed: 0.0..=100.0
+    },
+    LightShafts {
+        density: 0.0..=1.0,
+        decay: 0.9..=1.0,
+        weight: 0.0..=1.0,
+        exposure: 0.0..=1.0,
+        threshold: 0.0..=1.0,
+        samples: 8.0..=128.0,
+    },
+    LineJitter {
+        amplitude: 0.0..=16.0,
+        frequency: 0.0..=60.0,
+        line_height: 1.0..=16.0,
+        roll: -1.0..=1.0
+    },
+    Lut {},
+    Magnifier {
+        radius: 0.0..=0.5,
+        zoom: 1.0..=8.0,
+        border_width: 0.0..=16.0
+    },
+    MotionBlur {
+        shutter_angle: 0.0..=360.0,
+        max_samples: 4.0..=64.0,
+        max_blur: 0.0..=0.2
+    },
+    OldFilm {
+        sepia: 0.0..=1.0,
+        scratches: 0.0..=1.0,
+        dust: 0.0..=1.0,
+        flicker: 0.0..=1.0,
+        jitter: 0.0..=8.0,
+        frame_rate: 8.0..=30.0,
+    },
+    Outline {
+        width: 0.0..=8.0,
+        depth_threshold: 0.0..=1.0,
+        normal_threshold: 0.0..=1.0
+    },
+    PaletteSwap {
+        dither: 0.0..=1.0,
+        dither_scale: 1.0..=16.0
+    },
+    Pixelate {
+        block_size: 1.0..=64.0
+    },
+    PsxWobble {
+        snap: 1.0..=8.0,
+        wobble: 0.0..=8.0,
+        cell_size: 8.0..=128.0,
+        rate: 0.0..=30.0
+    },
+    Raindrops {
+        speed: 0.0..=2.0,
+        warping: 0.0..=0.1,
+        zoom: 0.25..=4.0,
+        exposure: 0.0..=1.0,
+        accumulation_rate: 0.0..=2.0,
+        dry_off_rate: 0.0..=2.0,
+        wetness: 0.0..=1.0,
+    },
+    RetroFog {
+        start: 0.0..=100.0,
+        end: 0.0..=200.0,
+        bands: 0.0..=16.0
+    },
+    RollingShutter {
+        readout: 0.0..=1.0,
+        bands: 0.0..=32.0,
+        skew: 0.0..=4.0,
+        max_skew: 0.0..=0.5
+    },
+    ScreenDoorFade {
+        progress: 0.0..=1.0,
+        scale: 1.0..=8.0
+    },
+    ScreenTransition {
+        progress: 0.0..=1.0,
+        softness: 0.0..=0.5
+    },
+    Sharpen {
+        strength: 0.0..=2.0,
+        radius: 0.5..=4.0
+    },
+    Shockwave {
+        radius: 0.0..=2.0,
+        thickness: 0.0..=0.5,
+        speed: 0.0..=4.0,
+        strength: 0.0..=2.0,
+        max_radius: 0.0..=3.0
+    },
+    Sketch {
+        edge_strength: 0.0..=1.0,
+        hatch_scale: 16.0..=512.0,
+        color: 0.0..=1.0
+    },
+    SpeedLines {
+        density: 4.0..=200.0,
+        length: 0.0..=1.0,
+        thickness: 0.0..=1.0,
+        intensity: 0.0..=1.0,
+        flicker: 0.0..=60.0
+    },
+    StableRegions {
+        feather: 0.0..=32.0
+    },
+    Underwater {},
+    VisionMode {
+        gain: 0.0..=10.0,
+        noise: 0.0..=1.0,
+        vignette: 0.0..=2.0
+    },
+    Wave {
+        waves_x: 0.0..=20.0,
+        waves_y: 0.0..=20.0,
+        speed_x: 0.0..=10.0,
+        speed_y: 0.0..=10.0,
+        amplitude_x: 0.0..=0.1,
+        amplitude_y: 0.0..=0.1,
+    },
+    WeatherOverlay {
+        density: 0.0..=1.0,
+        speed: 0.0..=3.0,
+        angle: -60.0..=60.0,
+        opacity: 0.0..=1.0
+    },
+    WetScreenSheen {
+        wetness: 0.0..=1.0,
+        threshold: 0.0..=1.0,
+        highlight_boost: 0.0..=2.0,
+        reflection_strength: 0.0..=1.0,
+        reflection_length: 0.0..=0.5,
+        horizon: 0.0..=1.0,
+    },
+    WhiteBalance {
+        temperature: 1667.0..=25000.0,
+        tint: -1.0..=1.0
+    },
+);
+
+/// The strength of a [`Mask`] means something else for each variant, see [`MaskVariant`],
+/// so its range and default go by the variant.
+impl ParameterRange for Mask {
+    fn parameters(&self) -> Vec<ParameterInfo> {
+        let (defaults, min, max) = match self.variant {
+            MaskVariant::Square => (Mask::square(), 3.0, 100.0),
+            MaskVariant::Crt => (Mask::crt(), 3000.0, 500000.0),
+            MaskVariant::Vignette => (Mask::vignette(), 0.1, 1.5),
+        };
+
+        parameters_with_ranges(
+            &defaults,
+            &[
+                ("strength", min, max),
+                ("fade", 0.0, 1.0),
+                ("nasal_offset", 0.0, 0.1),
+            ],
+        )
+    }
+}
+
+/// The numeric parameters of an effect component, with the given ranges by field name.
+///
+/// Parameters without a given range get one guessed from their default.
+pub(crate) fn parameters_with_ranges(
+    defaults: &dyn Reflect,
+    ranges: &[(&str, f32, f32)],
+) -> Vec<ParameterInfo> {
+    let mut parameters = parameters_of(defaults);
+    for &(name, min, max) in ranges {
+        let parameter = parameters.iter_mut().find(|p| p.name == name);
+        debug_assert!(parameter.is_some(), "No numeric parameter `{name}`");
+
+        if let Some(parameter) = parameter {
+            parameter.min = min;
+            parameter.max = max;
+        }
+    }
+    parameters
+}
+
+/// The numeric parameters of an effect component, with ranges guessed from the given values.
+///
+/// Effects which aren't structs have none.
+pub(crate) fn parameters_of(effect: &dyn Reflect) -> Vec<ParameterInfo> {
+    let (Some(TypeInfo::Struct(info)), ReflectRef::Struct(fields)) =
+        (effect.get_represented_type_info(), effect.reflect_ref())
+    else {
+        return vec![];
+    };
+
+    info.field_names()
+        .iter()
+        .zip(fields.iter_fields())
+        .filter_map(|(&name, field)| {
+            let default = util::get_number(field)?;
+            let (min, max) = guess_range(default);
+
+            Some(ParameterInfo {
+                name,
+                min,
+                max,
+                default,
+                integer: field.downcast_ref::<f32>().is_none()
+                    && field.downcast_ref::<f64>().is_none(),
+            })
+        })
+        .collect()
+}
+
+/// A range around the default value of a parameter, for parameters without a known range.
+fn guess_range(default: f32) -> (f32, f32) {
+    if (0.0..=1.0).contains(&default) {
+        (0.0, 1.0)
+    } else if default > 1.0 {
+        (0.0, default * 2.0)
+    } else {
+        (default * 2.0, -default * 2.0)
+    }
+}
+
+/// The names and parameters of all effects, for tooling such as inspectors and control mappings.
+///
+/// Parameters are found by reflection, and their ranges come from the [`ParameterRange`] of each
+/// effect. Parameters without a known range get one guessed from their default value:
+/// parameters between `0.0` and `1.0` go up to `1.0`, and others up to twice their default.
+/// Tools and apps can narrow or widen a range with [`EffectMetadata::set_range`].
+///
+/// Effects of other crates are described by [`crate::post_processing::external::ExternalEffects`].
+#[derive(Debug, Clone, Resource)]
+pub struct EffectMetadata {
+    effects: HashMap<EffectKind, EffectInfo>,
+    ranges: HashMap<(EffectKind, &'static str), (f32, f32)>,
+}
+
+impl Default for EffectMetadata {
+    fn default() -> Self {
+        Self {
+            effects: EffectKind::ALL
+                .iter()
+                .map(|&kind| (kind, EffectInfo::of(kind)))
+                .collect(),
+            ranges: HashMap::default(),
+        }
+    }
+}
+
+impl EffectMetadata {
+    /// The info of a kind of effect.
+    pub fn get(&self, kind: EffectKind) -> &EffectInfo {
+        self.effects
+            .get(&kind)
+            .expect("All kinds of effects should have info")
+    }
+
+    /// The info of all kinds of effects, in the order of [`EffectKind::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = &EffectInfo> {
+        EffectKind::ALL.iter().map(|&kind| self.get(kind))
+    }
+
+    /// The parameters of an effect as it is, e.g. with the strength range of the variant of a
+    /// [`Mask`] rather than that of the default variant.
+    ///
+    /// Ranges changed with [`EffectMetadata::set_range`] are kept.
+    pub fn parameters_for(&self, effect: &Effect) -> Vec<ParameterInfo> {
+        let mut parameters = effect.parameters();
+        for parameter in &mut parameters {
+            if let Some(&(min, max)) = self.ranges.get(&(effect.kind(), parameter.name)) {
+                parameter.min = min;
+                parameter.max = max;
+            }
+        }
+        parameters
+    }
+
+    /// Change the useful range of a parameter.
+    ///
+    /// This is for tools, the [`ParameterValidation`] keeps going by the [`ParameterRange`]
+    /// of the effect. Returns false if the effect has no numeric parameter with that name.
+    pub fn set_range(&mut self, kind: EffectKind, name: &str, min: f32, max: f32) -> bool {
+        let Some(parameter) = self
+            .effects
+            .get_mut(&kind)
+            .and_then(|info| info.parameters.iter_mut().find(|p| p.name == name))
+        else {
+            return false;
+        };
+
+        parameter.min = min;
+        parameter.max = max;
+        self.ranges.insert((kind, parameter.name), (min, max));
+        true
+    }
+}
+
+/// What happens when a parameter of an effect is set far outside of its [`ParameterRange`],
+/// see [`ParameterInfo::is_far_outside`].
+///
+/// Defaults to [`ParameterValidation::Warn`] in debug builds, and [`ParameterValidation::Off`]
+/// in release builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum ParameterValidation {
+    /// Parameters aren't checked.
+    Off,
+
+    /// A warning is logged the first time each parameter of an effect is far outside of its range.
+    Warn,
+
+    /// Like [`ParameterValidation::Warn`], and the parameter is clamped into its range.
+    Clamp,
+}
+
+impl Default for ParameterValidation {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Warn
+        } else {
+            Self::Off
+        }
+    }
+}
+
+/// Checks the parameters of changed effects against their ranges, see [`ParameterValidation`].
+pub(crate) fn validate_parameters<C: Component + Reflect + TypePath + ParameterRange>(
+    validation: Res<ParameterValidation>,
+    mut effects: Query<(Entity, &mut C), Changed<C>>,
+    mut warned: Local<HashSet<(Entity, &'static str)>>,
+) {
+    if *validation == ParameterValidation::Off {
+        return;
+    }
+
+    for (entity, mut effect) in &mut effects {
+        for parameter in effect.parameters() {
+            let Some(value) = effect
+                .reflect_path(parameter.name)
+                .ok()
+                .and_then(util::get_number)
+            else {
+                continue;
+            };
+
+            if !parameter.is_far_outside(value) {
+                continue;
+            }
+
+            if warned.insert((entity, parameter.name)) {
+                warn!(
+                    "`{}::{}` of {entity:?} is {value}, far outside of its useful range {}..={}",
+                    C::short_type_path(),
+                    parameter.name,
+                    parameter.min,
+                    parameter.max
+                );
+            }
+
+            if *validation == ParameterValidation::Clamp {
+                let value = value.clamp(parameter.min, parameter.max);
+                let _ = util::set_field_number(&mut *effect, parameter.name, value);
+            }
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EffectMetadata>()
+            .init_resource::<ParameterValidation>();
+    }
+}
@@ -0,0 +1,346 @@
+use std::{f32::consts::TAU, marker::PhantomData};
+
+use bevy::prelude::*;
+
+use crate::{post_processing::for_each_effect, time::VfxTime, util};
+
+/// The loudness of the audio the effects react to, from `0.0` (silent) to `1.0` (loud).
+///
+/// This crate does not analyze audio itself. Feed this resource each frame from the audio
+/// library of your choice, e.g. with [`AudioLevel::from_samples`] on the samples played last frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AudioLevel(pub f32);
+
+impl AudioLevel {
+    /// The level of a buffer of samples in `[-1.0, 1.0]`, as their root mean square.
+    pub fn from_samples(samples: &[f32]) -> Self {
+        if samples.is_empty() {
+            return Self(0.0);
+        }
+
+        let mean_square =
+            samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32;
+        Self(mean_square.sqrt().clamp(0.0, 1.0))
+    }
+}
+
+/// What a [`Signal`] can react to.
+#[derive(Debug, Clone, Copy)]
+pub struct SignalInput {
+    /// The clock of the effects.
+    pub time: VfxTime,
+
+    /// The current [`AudioLevel`].
+    pub audio_level: f32,
+}
+
+/// A value between `0.0` and `1.0` changing over time, which a [`Modulate`] maps onto a parameter.
+///
+/// The built-in signals are the variants of [`Modulator`].
+pub trait Signal: Send + Sync + 'static {
+    /// The value of the signal this frame.
+    ///
+    /// Called once per frame, so signals may keep state between frames.
+    fn sample(&mut self, input: &SignalInput) -> f32;
+}
+
+/// Oscillates smoothly between `0.0` and `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SineWave {
+    /// Oscillations per second.
+    pub frequency: f32,
+
+    /// Where in the oscillation the wave starts, from `0.0` to `1.0`.
+    pub phase: f32,
+}
+
+impl Default for SineWave {
+    fn default() -> Self {
+        Self {
+            frequency: 1.0,
+            phase: 0.0,
+        }
+    }
+}
+
+impl Signal for SineWave {
+    fn sample(&mut self, input: &SignalInput) -> f32 {
+        let turns = input.time.elapsed_seconds() * self.frequency + self.phase;
+        0.5 + 0.5 * (turns * TAU).sin()
+    }
+}
+
+/// Follows the [`AudioLevel`], rising and falling at most as fast as its attack and release allow.
+///
+/// A short attack with a longer release makes effects kick with the beat and then fade out.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Envelope {
+    /// Seconds to rise from `0.0` to `1.0`. Zero follows the level instantly.
+    pub attack: f32,
+
+    /// Seconds to fall from `1.0` to `0.0`. Zero follows the level instantly.
+    pub release: f32,
+
+    /// The value last frame.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    value: f32,
+}
+
+impl Envelope {
+    /// An envelope with the given attack and release, in seconds.
+    pub fn new(attack: f32, release: f32) -> Self {
+        Self {
+            attack,
+            release,
+            value: 0.0,
+        }
+    }
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self::new(0.02, 0.3)
+    }
+}
+
+impl Signal for Envelope {
+    fn sample(&mut self, input: &SignalInput) -> f32 {
+        let target = input.audio_level.clamp(0.0, 1.0);
+        let seconds = if target > self.value {
+            self.attack
+        } else {
+            self.release
+        };
+
+        self.value = if seconds <= 0.0 {
+            target
+        } else {
+            let step = input.time.delta_seconds() / seconds;
+            self.value + (target - self.value).clamp(-step, step)
+        };
+        self.value
+    }
+}
+
+/// Wanders smoothly between random values, e.g. for flicker.
+///
+/// The values only depend on the time and the seed, so they are the same each time a replay is played.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Random {
+    /// New random values per second.
+    pub rate: f32,
+
+    /// Picks a different sequence of values.
+    pub seed: u32,
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self { rate: 4.0, seed: 0 }
+    }
+}
+
+impl Random {
+    /// A random value in `[0.0, 1.0]` for the given step.
+    fn value_at(&self, step: i64) -> f32 {
+        // PCG hash, see https://www.jcgt.org/published/0009/03/02/
+        let state = (step as u32)
+            .wrapping_add(self.seed.wrapping_mul(0x9E37_79B9))
+            .wrapping_mul(747_796_405)
+            .wrapping_add(2_891_336_453);
+        let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277_803_737);
+        let hash = (word >> 22) ^ word;
+
+        hash as f32 / u32::MAX as f32
+    }
+}
+
+impl Signal for Random {
+    fn sample(&mut self, input: &SignalInput) -> f32 {
+        let x = input.time.elapsed_seconds() * self.rate;
+        let step = x.floor();
+        let t = x - step;
+
+        let from = self.value_at(step as i64);
+        let to = self.value_at(step as i64 + 1);
+        from + (to - from) * t * t * (3.0 - 2.0 * t)
+    }
+}
+
+/// One of the built-in [`Signal`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Modulator {
+    /// See [`SineWave`].
+    SineWave(SineWave),
+
+    /// See [`Envelope`].
+    Envelope(Envelope),
+
+    /// See [`Random`].
+    Random(Random),
+}
+
+impl Default for Modulator {
+    fn default() -> Self {
+        Self::Envelope(Envelope::default())
+    }
+}
+
+impl Signal for Modulator {
+    fn sample(&mut self, input: &SignalInput) -> f32 {
+        match self {
+            Self::SineWave(signal) => signal.sample(input),
+            Self::Envelope(signal) => signal.sample(input),
+            Self::Random(signal) => signal.sample(input),
+        }
+    }
+}
+
+impl From<SineWave> for Modulator {
+    fn from(signal: SineWave) -> Self {
+        Self::SineWave(signal)
+    }
+}
+
+impl From<Envelope> for Modulator {
+    fn from(signal: Envelope) -> Self {
+        Self::Envelope(signal)
+    }
+}
+
+impl From<Random> for Modulator {
+    fn from(signal: Random) -> Self {
+        Self::Random(signal)
+    }
+}
+
+/// Drives a numeric parameter of the effect `C` on the same entity from a [`Signal`].
+///
+/// For example, this makes chromatic aberration pulse with the music:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::{modulation::{Envelope, Modulate}, post_processing::chromatic_aberration::ChromaticAberration};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     ChromaticAberration::default(),
+///     Modulate::<ChromaticAberration>::new(Envelope::new(0.01, 0.25), "magnitude_r", 0.0, 0.02),
+/// ));
+/// ```
+///
+/// The signal is mapped from `0.0..=1.0` onto `from..=to` each frame, after `Update`.
+/// The built-in effects are modulated by the built-in [`Modulator`]s out of the box.
+/// For other reflected components or other signals, add [`ModulatePlugin`].
+#[derive(Debug, Clone, Component)]
+pub struct Modulate<C, S = Modulator> {
+    /// The signal driving the parameter.
+    pub signal: S,
+
+    /// The path of the parameter within the effect, e.g. `"strength"` or `"color.a"`.
+    /// See [`crate::expression::ParamDriver::field`] for which parameters can be driven.
+    pub field: String,
+
+    /// The value of the parameter when the signal is `0.0`.
+    pub from: f32,
+
+    /// The value of the parameter when the signal is `1.0`.
+    pub to: f32,
+
+    warned: bool,
+    _effect: PhantomData<fn() -> C>,
+}
+
+impl<C, S> Modulate<C, S> {
+    /// Maps the signal onto the parameter `field`, from `from` to `to`.
+    pub fn new(signal: impl Into<S>, field: impl Into<String>, from: f32, to: f32) -> Self {
+        Self {
+            signal: signal.into(),
+            field: field.into(),
+            from,
+            to,
+            warned: false,
+            _effect: PhantomData,
+        }
+    }
+}
+
+fn modulate<C: Component + Reflect, S: Signal>(
+    time: Res<VfxTime>,
+    audio_level: Res<AudioLevel>,
+    mut modulations: Query<(&mut Modulate<C, S>, &mut C)>,
+) {
+    let input = SignalInput {
+        time: *time,
+        audio_level: audio_level.0,
+    };
+
+    for (mut modulation, mut effect) in &mut modulations {
+        let modulation = &mut *modulation;
+        let signal = modulation.signal.sample(&input).clamp(0.0, 1.0);
+        let value = modulation.from + (modulation.to - modulation.from) * signal;
+
+        let effect: &mut dyn Reflect = &mut *effect;
+        if let Err(reason) = util::set_field_number(effect, &modulation.field, value) {
+            if !modulation.warned {
+                modulation.warned = true;
+                warn!(
+                    "Can't modulate `{}` of `{}`: {reason}",
+                    modulation.field,
+                    std::any::type_name::<C>()
+                );
+            }
+        }
+    }
+}
+
+/// Modulates the component `C` via [`Modulate<C, S>`].
+///
+/// This is already added for the built-in effects with the built-in [`Modulator`]s,
+/// so it is only needed for other components or other signals.
+#[derive(Debug)]
+pub struct ModulatePlugin<C, S = Modulator>(PhantomData<fn() -> (C, S)>);
+
+impl<C, S> Default for ModulatePlugin<C, S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: Component + Reflect, S: Signal> bevy::prelude::Plugin for ModulatePlugin<C, S> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioLevel>()
+            .add_systems(PostUpdate, modulate::<C, S>);
+    }
+}
+
+/// Adds a [`ModulatePlugin`] for each of the given effects.
+macro_rules! modulated {
+    ($app:expr, $($name:ident($effect:path) $meta:tt),* $(,)?) => {
+        $(
+            $app.add_plugins(ModulatePlugin::<$effect>::default());
+        )*
+    };
+}
+
+/// Modulates the built-in effects.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioLevel>()
+            .register_type::<AudioLevel>()
+            .register_type::<SineWave>()
+            .register_type::<Envelope>()
+            .register_type::<Random>()
+            .register_type::<Modulator>();
+
+        for_each_effect!(modulated, app);
+    }
+}
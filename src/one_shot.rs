@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::system::Command,
+    input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::{
+    state::{Effect, EffectKind},
+    time::VfxTime,
+};
+
+/// A command which adds an effect to an entity (typically a camera) for a while, then removes it.
+///
+/// Meant for impacts, such as a burst of chromatic aberration or a wave of distortion.
+/// If a [`Gamepad`] is given, it also rumbles according to the [`RumbleMapping`],
+/// so visual and haptic feedback come from the same trigger:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::{one_shot::OneShot, post_processing::chromatic_aberration::ChromaticAberration};
+/// fn on_hit(mut commands: Commands, camera: Query<Entity, With<Camera>>, gamepads: Res<Gamepads>) {
+///     commands.add(
+///         OneShot::new(camera.single(), ChromaticAberration::default(), 0.3)
+///             .with_rumble(gamepads.iter().next()),
+///     );
+/// }
+/// ```
+///
+/// The effect is removed after [`OneShot::duration`] seconds of the [`VfxTime`] clock.
+/// If the entity already had the effect, its parameters are replaced, and the effect is still removed afterwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OneShot {
+    /// The entity to add the effect to.
+    pub entity: Entity,
+
+    /// The effect and its parameters.
+    pub effect: Effect,
+
+    /// How many seconds the effect stays.
+    pub duration: f32,
+
+    /// The gamepad to rumble, if any.
+    pub rumble: Option<Gamepad>,
+}
+
+impl OneShot {
+    /// Creates a one-shot effect without rumble.
+    pub fn new(entity: Entity, effect: impl Into<Effect>, duration: f32) -> Self {
+        Self {
+            entity,
+            effect: effect.into(),
+            duration,
+            rumble: None,
+        }
+    }
+
+    /// Rumble the given gamepad along with the effect.
+    pub fn with_rumble(mut self, gamepad: impl Into<Option<Gamepad>>) -> Self {
+        self.rumble = gamepad.into();
+        self
+    }
+}
+
+impl Command for OneShot {
+    fn apply(self, world: &mut World) {
+        let Some(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+
+        let kind = self.effect.kind();
+        self.effect.insert(&mut entity, None);
+
+        match entity.get_mut::<OneShots>() {
+            Some(mut one_shots) => {
+                // Triggering the same effect again restarts it.
+                match one_shots.0.iter_mut().find(|(other, _)| *other == kind) {
+                    Some((_, remaining)) => *remaining = self.duration,
+                    None => one_shots.0.push((kind, self.duration)),
+                }
+            }
+            None => {
+                entity.insert(OneShots(vec![(kind, self.duration)]));
+            }
+        }
+
+        let Some(gamepad) = self.rumble else {
+            return;
+        };
+
+        let Some(intensity) = world
+            .get_resource::<RumbleMapping>()
+            .and_then(|mapping| mapping.0.get(&kind).copied())
+        else {
+            return;
+        };
+
+        // The events only exist if the input plugin was added.
+        if let Some(mut requests) = world.get_resource_mut::<Events<GamepadRumbleRequest>>() {
+            requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity,
+                duration: Duration::from_secs_f32(self.duration.max(0.0)),
+            });
+        }
+    }
+}
+
+/// How hard a gamepad rumbles for each kind of [`OneShot`] effect.
+///
+/// Effects without an entry don't rumble.
+/// Rumble needs Bevy's `bevy_gilrs` feature to reach the hardware.
+#[derive(Debug, Clone, Resource)]
+pub struct RumbleMapping(pub HashMap<EffectKind, GamepadRumbleIntensity>);
+
+impl RumbleMapping {
+    /// Set how hard the gamepad rumbles for the given kind of effect.
+    pub fn with(mut self, kind: EffectKind, intensity: GamepadRumbleIntensity) -> Self {
+        self.0.insert(kind, intensity);
+        self
+    }
+}
+
+impl Default for RumbleMapping {
+    /// Rumbles for the effects which suit impacts.
+    fn default() -> Self {
+        Self(HashMap::default())
+            .with(
+                EffectKind::ChromaticAberration,
+                GamepadRumbleIntensity::strong_motor(0.6),
+            )
+            .with(EffectKind::Wave, GamepadRumbleIntensity::MAX)
+            .with(EffectKind::Blur, GamepadRumbleIntensity::weak_motor(0.4))
+    }
+}
+
+/// Effects added by [`OneShot`], along with the seconds they have left.
+#[derive(Debug, Component)]
+struct OneShots(Vec<(EffectKind, f32)>);
+
+fn expire_one_shots(
+    mut commands: Commands,
+    time: Res<VfxTime>,
+    mut query: Query<(Entity, &mut OneShots)>,
+) {
+    for (entity, mut one_shots) in &mut query {
+        let mut entity = commands.entity(entity);
+
+        one_shots.0.retain_mut(|(kind, remaining)| {
+            *remaining -= time.delta_seconds();
+            if *remaining > 0.0 {
+                return true;
+            }
+
+            let kind = *kind;
+            entity.add(move |entity, world: &mut World| {
+                if let Some(mut entity) = world.get_entity_mut(entity) {
+                    kind.remove(&mut entity);
+                }
+            });
+            false
+        });
+
+        if one_shots.0.is_empty() {
+            entity.remove::<OneShots>();
+        }
+    }
+}
+
+/// Expires one-shot effects.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RumbleMapping>()
+            .add_systems(Update, expire_one_shots);
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::post_processing;
+use crate::{
+    animation, curve, expression, feedback, jitter, metadata, modulation, one_shot,
+    post_processing, time, timeline, transition,
+};
 
 /// The main plugin needed to use any effects.
 #[derive(Debug, Default)]
@@ -8,6 +11,33 @@ pub struct BevyVfxBagPlugin;
 
 impl Plugin for BevyVfxBagPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(post_processing::PostProcessingPlugin);
+        app.add_plugins((
+            time::Plugin,
+            expression::Plugin,
+            timeline::Plugin,
+            animation::Plugin,
+            modulation::Plugin,
+            curve::Plugin,
+            transition::Plugin,
+            one_shot::Plugin,
+            feedback::Plugin,
+            metadata::Plugin,
+            jitter::Plugin,
+            post_processing::PostProcessingPlugin,
+        ));
+
+        #[cfg(feature = "control")]
+        app.add_plugins(crate::control::Plugin);
+
+        #[cfg(feature = "ron")]
+        app.add_plugins(crate::preset::Plugin);
+
+        #[cfg(feature = "scene")]
+        app.add_plugins(crate::scene::Plugin);
+
+        // Added here rather than along with the effects, since `Material2dPlugin` does
+        // its setup in `finish`, which plugins added from `finish` don't get.
+        #[cfg(feature = "material2d")]
+        app.add_plugins(post_processing::material::Plugin);
     }
 }
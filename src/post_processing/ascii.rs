@@ -0,0 +1,294 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, CachedRenderPipelineId, FilterMode,
+            Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages, ShaderType,
+            TextureSampleType, TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        texture::{CompressedImageFormats, ImageType},
+        Render, RenderSet,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const ASCII_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1888142807011866326);
+const ASCII_ATLAS_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 884771255807324883);
+
+#[derive(Resource, ExtractResource, Deref, DerefMut, Clone, Hash)]
+struct AsciiAtlasHandle(Handle<Image>);
+
+#[derive(Resource)]
+pub(crate) struct AsciiData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for AsciiData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Ascii",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(AsciiUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+            ],
+            ASCII_SHADER_HANDLE.typed(),
+        );
+
+        // The glyphs are pixel art, so keep them crisp when scaled up.
+        let sampler = world
+            .get_resource::<RenderDevice>()
+            .expect("Should have render device")
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Ascii Atlas Sampler"),
+                mag_filter: FilterMode::Nearest,
+                min_filter: FilterMode::Nearest,
+                ..default()
+            });
+
+        AsciiData {
+            pipeline_id,
+            layout,
+            sampler,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            ASCII_SHADER_HANDLE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "ascii.wgsl"),
+            Shader::from_wgsl
+        );
+
+        let mut assets = app.world.resource_mut::<Assets<_>>();
+
+        let image = Image::from_buffer(
+            include_bytes!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/textures/",
+                "ascii.png"
+            )),
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            false,
+        )
+        .expect("Should load ascii atlas successfully");
+        assets.set_untracked(ASCII_ATLAS_IMAGE_HANDLE, image);
+
+        app.register_type::<Ascii>();
+        super::register_effect_types::<Ascii>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Ascii>::default(),
+            UniformComponentPlugin::<AsciiUniform>::default(),
+            ExtractResourcePlugin::<AsciiAtlasHandle>::default(),
+        ))
+        .insert_resource(AsciiAtlasHandle(
+            ASCII_ATLAS_IMAGE_HANDLE.clone_weak().typed(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Ascii>,
+            )
+            .init_resource::<AsciiData>()
+            .init_resource::<UniformBindGroup<AsciiUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<AsciiUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<AsciiData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Ascii>,
+        &Blend<Ascii>,
+        &Resolution<Ascii>,
+        &SourceSampler<Ascii>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::ascii::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<AsciiUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<AsciiData>,
+    atlas_handle: Res<AsciiAtlasHandle>,
+    mut bind_group: ResMut<UniformBindGroup<AsciiUniform>>,
+    uniforms: Res<ComponentUniforms<AsciiUniform>>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<Entity, With<AsciiUniform>>,
+) {
+    let _span = info_span!("vfx::ascii::queue").entered();
+
+    bind_group.inner = None;
+
+    if let (Some(uniforms), Some(atlas_image)) = (uniforms.binding(), images.get(&atlas_handle.0)) {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Ascii Uniform Bind Group"),
+                layout: &data.layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(&atlas_image.texture_view),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&data.sampler),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: uniforms.clone(),
+                    },
+                ],
+            }));
+        }
+    }
+}
+
+/// Turns the image into text art.
+///
+/// The image is split into cells, and each cell is drawn as a glyph
+/// picked by the brightness of the cell, from `' '` for dark cells to `'@'` for bright ones.
+/// The glyphs come from an atlas texture bundled with this crate.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Ascii {
+    /// The size of each cell, in pixels.
+    /// The glyphs are 8x8 pixels, so multiples of 8 look the crispest.
+    pub cell_size: f32,
+
+    /// The color of the glyphs.
+    ///
+    /// The alpha channel mixes between this color and the color of the cell in the image,
+    /// so `0.0` draws each glyph in the color of what it covers.
+    pub foreground: Color,
+
+    /// The color behind the glyphs.
+    pub background: Color,
+}
+
+impl Default for Ascii {
+    fn default() -> Self {
+        Self {
+            cell_size: 8.0,
+            foreground: Color::rgb(0.2, 1.0, 0.3),
+            background: Color::BLACK,
+        }
+    }
+}
+
+impl Display for Ascii {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Ascii cell size: {}, foreground: {:?}, background: {:?}",
+            self.cell_size, self.foreground, self.background
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Ascii`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct AsciiUniform {
+    pub(crate) foreground: Vec4,
+    pub(crate) background: Vec4,
+    pub(crate) cell_size: f32,
+}
+
+impl ExtractComponent for Ascii {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = AsciiUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::ascii::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(AsciiUniform {
+            foreground: Vec4::from(settings.foreground.as_linear_rgba_f32()),
+            background: Vec4::from(settings.background.as_linear_rgba_f32()),
+            cell_size: settings.cell_size,
+        })
+    }
+}
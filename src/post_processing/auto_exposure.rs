@@ -0,0 +1,678 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{core_2d, core_3d},
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner},
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+            BufferBindingType, BufferDescriptor, BufferUsages, CachedComputePipelineId,
+            CachedRenderPipelineId, ComputePassDescriptor, ComputePipelineDescriptor, Extent3d,
+            ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, Origin3d, PipelineCache,
+            ShaderStages, ShaderType, Texture, TextureAspect, TextureDescriptor, TextureDimension,
+            TextureFormat, TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor,
+            TextureViewDimension, TextureViewId,
+        },
+        renderer::{RenderContext, RenderDevice},
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::HashMap,
+};
+
+use super::{
+    blend::Blend, graph::node, resolution::Resolution, sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+use crate::time::{VfxGlobalsBuffer, VfxGlobalsUniform};
+
+pub(crate) const AUTO_EXPOSURE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16020385617238459104);
+
+pub(crate) const AUTO_EXPOSURE_COMPUTE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2893460136187207429);
+
+/// The bins of the luminance histogram, as in `auto_exposure_compute.wgsl`.
+const BINS: u64 = 64;
+
+/// The width and height of the workgroups of the histogram pass, as in `auto_exposure_compute.wgsl`.
+/// The adapt pass has a workgroup of [`BINS`] invocations.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// The metering passes, which are only there where the adapter supports compute shaders.
+struct MeteringPipelines {
+    layout: BindGroupLayout,
+    histogram: CachedComputePipelineId,
+    adapt: CachedComputePipelineId,
+}
+
+#[derive(Resource)]
+pub(crate) struct AutoExposureData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+
+    /// `None` if the adapter doesn't support compute shaders.
+    metering: Option<MeteringPipelines>,
+}
+
+impl FromWorld for AutoExposureData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Auto Exposure",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(AutoExposureUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The exposure the view has adapted to.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+            AUTO_EXPOSURE_SHADER_HANDLE.typed(),
+        );
+
+        let limits = world.resource::<RenderDevice>().limits();
+
+        // WebGL2 has no compute shaders, and reports zero for all of these.
+        let supported = limits.max_compute_workgroup_size_x >= WORKGROUP_SIZE.max(BINS as u32)
+            && limits.max_compute_workgroup_size_y >= WORKGROUP_SIZE
+            && limits.max_compute_invocations_per_workgroup >= WORKGROUP_SIZE * WORKGROUP_SIZE
+            && limits.max_storage_buffers_per_shader_stage >= 2;
+
+        if !supported {
+            info!("Compute shaders are not supported, auto exposure only applies its compensation");
+        }
+
+        let metering = supported.then(|| {
+            let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+                &BindGroupLayoutDescriptor {
+                    label: Some("Auto Exposure metering bind group layout"),
+                    entries: &[
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Texture {
+                                sample_type: TextureSampleType::Float { filterable: true },
+                                view_dimension: TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: true,
+                                min_binding_size: Some(AutoExposureUniform::min_size()),
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: Some(VfxGlobalsUniform::min_size()),
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Storage { read_only: false },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 4,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Storage { read_only: false },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                },
+            );
+
+            let pipeline_cache = world.resource::<PipelineCache>();
+            let queue = |label: &'static str, entry_point: &'static str| {
+                pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                    label: Some(label.into()),
+                    layout: vec![layout.clone()],
+                    push_constant_ranges: vec![],
+                    shader: AUTO_EXPOSURE_COMPUTE_SHADER_HANDLE.typed(),
+                    shader_defs: vec![],
+                    entry_point: entry_point.into(),
+                })
+            };
+
+            MeteringPipelines {
+                histogram: queue("Auto Exposure histogram pipeline", "histogram_pass"),
+                adapt: queue("Auto Exposure adapt pipeline", "adapt_pass"),
+                layout,
+            }
+        });
+
+        AutoExposureData {
+            pipeline_id,
+            layout,
+            metering,
+        }
+    }
+}
+
+/// The buffers a view meters into, and the exposure it adapted to, kept across frames.
+#[derive(Clone)]
+struct ExposureBuffers {
+    histogram: Buffer,
+    state: Buffer,
+    texture: Texture,
+    view: TextureView,
+}
+
+impl ExposureBuffers {
+    fn allocate(render_device: &RenderDevice) -> Self {
+        // New buffers and textures are zeroed, which is an exposure of zero EV not metered yet.
+        let histogram = render_device.create_buffer(&BufferDescriptor {
+            label: Some("Auto Exposure histogram"),
+            size: BINS * 4,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let state = render_device.create_buffer(&BufferDescriptor {
+            label: Some("Auto Exposure state"),
+            size: 8,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("Auto Exposure exposure"),
+            size: Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::R32Float,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        Self {
+            histogram,
+            state,
+            texture,
+            view,
+        }
+    }
+}
+
+/// The [`ExposureBuffers`] of each view, kept across frames so the exposure can adapt over time.
+#[derive(Default, Resource)]
+struct ViewExposures(HashMap<Entity, ExposureBuffers>);
+
+/// What the [`AutoExposureNode`] of a view meters with, in the render world.
+#[derive(Component)]
+struct ViewAutoExposure {
+    buffers: ExposureBuffers,
+
+    /// For each of the main textures as the image metered.
+    bind_groups: HashMap<TextureViewId, BindGroup>,
+    uniform_offset: u32,
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            AUTO_EXPOSURE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "auto_exposure.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            AUTO_EXPOSURE_COMPUTE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "auto_exposure_compute.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<AutoExposure>();
+        super::register_effect_types::<AutoExposure>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<AutoExposure>::default(),
+            UniformComponentPlugin::<AutoExposureUniform>::default(),
+        ));
+
+        let render_app = super::render_app(app);
+
+        // Meters the image as the main pass left it, before any effect runs.
+        add_node(
+            render_app,
+            core_3d::graph::NAME,
+            core_3d::graph::node::END_MAIN_PASS,
+        );
+        add_node(
+            render_app,
+            core_2d::graph::NAME,
+            core_2d::graph::node::MAIN_PASS,
+        );
+
+        render_app
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<AutoExposure>,
+            )
+            .init_resource::<AutoExposureData>()
+            .init_resource::<ViewExposures>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<AutoExposureUniform>>();
+    }
+}
+
+fn add_node(render_app: &mut App, graph: &str, after: &'static str) {
+    let metering = ViewNodeRunner::new(AutoExposureNode, &mut render_app.world);
+
+    let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+    let graph = render_graph
+        .get_sub_graph_mut(graph)
+        .expect("Graph should be available");
+
+    graph.add_node(node::AUTO_EXPOSURE, metering);
+    graph.add_node_edge(after, node::AUTO_EXPOSURE);
+    graph.add_node_edge(node::AUTO_EXPOSURE, node::BEFORE_TONEMAPPING);
+}
+
+fn prepare(
+    data: Res<AutoExposureData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<AutoExposure>,
+        &Blend<AutoExposure>,
+        &Resolution<AutoExposure>,
+        &SourceSampler<AutoExposure>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::auto_exposure::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<AutoExposureUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<AutoExposureData>,
+    globals: Res<VfxGlobalsBuffer>,
+    uniforms: Res<ComponentUniforms<AutoExposureUniform>>,
+    mut exposures: ResMut<ViewExposures>,
+    // Only the views drawing the effect, which are given a blend while extracting.
+    views: Query<
+        (
+            Entity,
+            &ViewTarget,
+            &DynamicUniformIndex<AutoExposureUniform>,
+        ),
+        With<Blend<AutoExposure>>,
+    >,
+) {
+    let _span = info_span!("vfx::auto_exposure::queue").entered();
+
+    // Views without the effect this frame start over once they have it again.
+    let mut previous = std::mem::take(&mut exposures.0);
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, view_target, uniform_index) in &views {
+        let buffers = previous
+            .remove(&entity)
+            .unwrap_or_else(|| ExposureBuffers::allocate(&render_device));
+        exposures.0.insert(entity, buffers.clone());
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Auto Exposure Uniform Bind Group"),
+            layout: &data.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&buffers.view),
+                },
+            ],
+        });
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<AutoExposureUniform>::new(bind_group));
+
+        let Some(metering) = data.metering.as_ref() else {
+            continue;
+        };
+
+        let bind_groups = [
+            view_target.main_texture_view(),
+            view_target.main_texture_other_view(),
+        ]
+        .into_iter()
+        .map(|source| {
+            let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Auto Exposure metering bind group"),
+                layout: &metering.layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: uniforms.clone(),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: globals
+                            .buffer
+                            .binding()
+                            .expect("Globals buffer should be available"),
+                    },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: buffers.histogram.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 4,
+                        resource: buffers.state.as_entire_binding(),
+                    },
+                ],
+            });
+
+            (source.id(), bind_group)
+        })
+        .collect();
+
+        commands.entity(entity).insert(ViewAutoExposure {
+            buffers,
+            bind_groups,
+            uniform_offset: uniform_index.index(),
+        });
+    }
+}
+
+/// Meters the image of each view with an [`AutoExposure`] into a histogram of its luminance,
+/// and adapts the exposure of the view to it, before the effects run.
+#[derive(Default)]
+struct AutoExposureNode;
+
+impl ViewNode for AutoExposureNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewAutoExposure);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, exposure): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(metering) = world.resource::<AutoExposureData>().metering.as_ref() else {
+            return Ok(());
+        };
+
+        // The exposure stays as it is while the pipelines compile.
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(histogram), Some(adapt)) = (
+            pipeline_cache.get_compute_pipeline(metering.histogram),
+            pipeline_cache.get_compute_pipeline(metering.adapt),
+        ) else {
+            return Ok(());
+        };
+
+        let Some(bind_group) = exposure
+            .bind_groups
+            .get(&view_target.main_texture_view().id())
+        else {
+            return Ok(());
+        };
+
+        let size = view_target.main_texture().size();
+
+        {
+            let mut compute_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("Auto Exposure metering"),
+                    });
+
+            compute_pass.set_bind_group(0, bind_group, &[exposure.uniform_offset]);
+
+            compute_pass.set_pipeline(histogram);
+            compute_pass.dispatch_workgroups(
+                size.width.div_ceil(WORKGROUP_SIZE),
+                size.height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+
+            compute_pass.set_pipeline(adapt);
+            compute_pass.dispatch_workgroups(1, 1, 1);
+        }
+
+        // The exposure is copied into a texture, so drawing the effect needs no storage buffers,
+        // which WebGL2 doesn't have.
+        render_context.command_encoder().copy_buffer_to_texture(
+            ImageCopyBuffer {
+                buffer: &exposure.buffers.state,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            ImageCopyTexture {
+                texture: &exposure.buffers.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// Adjusts the exposure of the view to how bright the scene is, like the automatic exposure of
+/// a camera or the eyes adjusting to the dark.
+///
+/// Each frame the luminance of the image is counted into a histogram, from which the average
+/// luminance is found, leaving out the darkest and brightest pixels (see
+/// [`AutoExposure::low_percentile`] and [`AutoExposure::high_percentile`]).
+/// The exposure moves towards exposing that average to middle grey at the adaptation speeds,
+/// so stepping out of a dark cave into daylight is blinding for a moment:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::auto_exposure::AutoExposure;
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle {
+///         camera: Camera {
+///             hdr: true,
+///             ..default()
+///         },
+///         ..default()
+///     },
+///     AutoExposure::default(),
+/// ));
+/// ```
+///
+/// Meant for cameras with [`Camera::hdr`], where the image is scene-referred and can be much
+/// brighter or darker than the screen. Unless given an [`Order`], the exposure is applied before
+/// all other effects and before tonemapping, so grading effects such as
+/// [`super::color_adjust::ColorAdjust`] work on the exposed image.
+///
+/// The image is metered as the main pass draws it, before any effect, whatever the order.
+/// Metering needs compute shaders, so on WebGL2 only [`AutoExposure::compensation`] is applied.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct AutoExposure {
+    /// The darkest luminance metered, in EV (the log2 of the luminance).
+    /// Darker pixels are metered as this, so the exposure never brightens past exposing it.
+    pub min_ev: f32,
+
+    /// The brightest luminance metered, in EV.
+    /// Brighter pixels are metered as this, so the exposure never darkens past exposing it.
+    pub max_ev: f32,
+
+    /// The part of the pixels, from the darkest up, which is left out of the average.
+    pub low_percentile: f32,
+
+    /// The part of the pixels, from the darkest up, which is averaged.
+    /// The brightest pixels above this are left out, so e.g. the sun doesn't darken the view.
+    pub high_percentile: f32,
+
+    /// How fast the exposure darkens when the scene gets brighter, in EV per second.
+    pub adapt_to_bright: f32,
+
+    /// How fast the exposure brightens when the scene gets darker, in EV per second.
+    /// Eyes take longer to adjust to the dark, so this is usually the slower one.
+    pub adapt_to_dark: f32,
+
+    /// Added to the exposure, in EV, e.g. `1.0` for twice as bright as metered.
+    pub compensation: f32,
+}
+
+impl Default for AutoExposure {
+    fn default() -> Self {
+        Self {
+            min_ev: -8.0,
+            max_ev: 8.0,
+            low_percentile: 0.1,
+            high_percentile: 0.9,
+            adapt_to_bright: 3.0,
+            adapt_to_dark: 1.0,
+            compensation: 0.0,
+        }
+    }
+}
+
+impl Display for AutoExposure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Auto Exposure range: {:.1}..{:.1} EV, percentiles: {:.2}..{:.2}, compensation: {:.1} EV",
+            self.min_ev, self.max_ev, self.low_percentile, self.high_percentile, self.compensation
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`AutoExposure`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct AutoExposureUniform {
+    pub(crate) min_ev: f32,
+    pub(crate) max_ev: f32,
+    pub(crate) low_percentile: f32,
+    pub(crate) high_percentile: f32,
+    pub(crate) adapt_to_bright: f32,
+    pub(crate) adapt_to_dark: f32,
+    pub(crate) compensation: f32,
+}
+
+impl ExtractComponent for AutoExposure {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = AutoExposureUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::auto_exposure::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        let low_percentile = settings.low_percentile.clamp(0.0, 1.0);
+
+        Some(AutoExposureUniform {
+            min_ev: settings.min_ev,
+            max_ev: settings.max_ev.max(settings.min_ev + 0.01),
+            low_percentile,
+            high_percentile: settings.high_percentile.clamp(low_percentile, 1.0),
+            adapt_to_bright: settings.adapt_to_bright.max(0.0),
+            adapt_to_dark: settings.adapt_to_dark.max(0.0),
+            compensation: settings.compensation,
+        })
+    }
+}
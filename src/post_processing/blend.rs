@@ -0,0 +1,584 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::prepass::ViewPrepassTextures,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        render_phase::{DrawFunctionId, RenderPhase},
+        render_resource::{
+            encase, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+            BufferInitDescriptor, BufferUsages, CachedRenderPipelineId, Extent3d, FilterMode,
+            PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+            Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType,
+            TextureUsages, TextureViewDimension,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{FallbackImage, TextureCache},
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::HashSet,
+};
+
+use super::{
+    effect_mask::{EffectMaskChannel, ViewEffectMask},
+    PostProcessingPhaseItem, PostProcessingSharedBindGroups, SharedBindGroupKey,
+};
+
+pub(crate) const BLEND_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7273846049281537318);
+
+/// How the output of an effect is combined with the image the effect started from.
+///
+/// The modes other than [`BlendMode::Normal`] and [`BlendMode::Additive`] expect colors between
+/// `0.0` and `1.0`, so they give odd results on bright parts of HDR images.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// The output of the effect replaces the image.
+    #[default]
+    Normal,
+
+    /// The output of the effect is added onto the image, which brightens it.
+    Additive,
+
+    /// The image is multiplied with the output of the effect, which darkens it.
+    Multiply,
+
+    /// The inverse of multiplying the inverses, which brightens the image.
+    Screen,
+
+    /// Multiplies the dark parts of the image and screens the bright parts, which adds contrast.
+    Overlay,
+}
+
+impl BlendMode {
+    pub(crate) fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            BlendMode::Normal => vec!["NORMAL".into()],
+            BlendMode::Additive => vec!["ADDITIVE".into()],
+            BlendMode::Multiply => vec!["MULTIPLY".into()],
+            BlendMode::Screen => vec!["SCREEN".into()],
+            BlendMode::Overlay => vec!["OVERLAY".into()],
+        }
+    }
+}
+
+/// Where the output of an effect is used, see [`Blend::scope`].
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectScope {
+    /// The effect applies to the whole image.
+    #[default]
+    Everywhere,
+
+    /// The effect only applies where nothing was rendered in the depth prepass,
+    /// which is the background: the sky, a [`bevy::core_pipeline::Skybox`] or the clear color.
+    /// Scene geometry is left as it was before the effect.
+    ///
+    /// Requires a [`bevy::core_pipeline::prepass::DepthPrepass`] on the camera and [`Msaa::Off`],
+    /// else the effect applies everywhere.
+    Background,
+
+    /// The effect only applies where the given channel of the camera's [`EffectMask`](super::effect_mask::EffectMask) is drawn,
+    /// and partly where it is between `0.0` and `1.0`.
+    ///
+    /// Without an `EffectMask` on the camera, the effect applies everywhere.
+    Mask(EffectMaskChannel),
+
+    /// The effect only applies where the given channel of the camera's [`EffectMask`](super::effect_mask::EffectMask) is not drawn.
+    ///
+    /// Without an `EffectMask` on the camera, the effect applies nowhere.
+    OutsideMask(EffectMaskChannel),
+}
+
+impl EffectScope {
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            EffectScope::Everywhere => vec![],
+            EffectScope::Background => vec!["BACKGROUND".into()],
+            EffectScope::Mask(channel) => vec![
+                "EFFECT_MASK".into(),
+                ShaderDefVal::UInt("EFFECT_MASK_CHANNEL".into(), channel.index()),
+            ],
+            EffectScope::OutsideMask(channel) => vec![
+                "EFFECT_MASK".into(),
+                "OUTSIDE_EFFECT_MASK".into(),
+                ShaderDefVal::UInt("EFFECT_MASK_CHANNEL".into(), channel.index()),
+            ],
+        }
+    }
+
+    /// What is missing from the camera for the scope to work, and what happens instead, if anything.
+    fn missing(self, has_depth: bool, has_mask: bool) -> Option<&'static str> {
+        match self {
+            EffectScope::Background if !has_depth => {
+                Some("is limited to the background, but the camera has no `DepthPrepass` so it applies everywhere")
+            }
+            EffectScope::Mask(_) if !has_mask => {
+                Some("is limited to an effect mask, but the camera has no `EffectMask` so it applies everywhere")
+            }
+            EffectScope::OutsideMask(_) if !has_mask => {
+                Some("is limited to outside an effect mask, but the camera has no `EffectMask` so it applies nowhere")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decide how the output of the effect `C` is combined with the image it started from.
+///
+/// Without this, the output of an effect replaces the image.
+/// Like [`super::Order`], this is per-camera and uses a generic marker to know which effect
+/// it applies to:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{blend::{Blend, BlendMode}, wave::Wave};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     Wave::default(),
+///     Blend::<Wave>::new(BlendMode::Normal, 0.5),
+/// ));
+/// ```
+///
+/// Effects made up of several passes are blended once, after their last pass.
+///
+/// The blend can also be limited to a part of the image, e.g. to only shift the hue of the sky:
+///
+/// ```no_run
+/// # use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+/// # use bevy_vfx_bag::post_processing::{blend::{Blend, EffectScope}, color_adjust::ColorAdjust};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     DepthPrepass,
+///     ColorAdjust {
+///         hue_shift: 180.0,
+///         ..default()
+///     },
+///     Blend::<ColorAdjust>::default().with_scope(EffectScope::Background),
+/// ));
+/// ```
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct Blend<C> {
+    /// How the output is combined with the image.
+    pub mode: BlendMode,
+
+    /// How much of the blended result is used, between `0.0` (the image as if the effect
+    /// was not there) and `1.0` (the full blended result).
+    pub factor: f32,
+
+    /// Where the blended result is used. Elsewhere, the image is kept as if the effect was not there.
+    pub scope: EffectScope,
+
+    #[reflect(ignore)]
+    marker: PhantomData<C>,
+}
+
+impl<C> Blend<C> {
+    /// Create a new blend.
+    pub fn new(mode: BlendMode, factor: f32) -> Self {
+        Self {
+            mode,
+            factor,
+            scope: EffectScope::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Only use the blended result within the given scope.
+    pub fn with_scope(mut self, scope: EffectScope) -> Self {
+        self.scope = scope;
+        self
+    }
+}
+
+impl<C> Default for Blend<C> {
+    fn default() -> Self {
+        Self::new(BlendMode::Normal, 1.0)
+    }
+}
+
+impl<C> Clone for Blend<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Blend<C> {}
+
+impl<C> Display for Blend<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Blend {:?}, factor: {:.2}, scope: {:?}",
+            self.mode, self.factor, self.scope
+        )
+    }
+}
+
+/// A [`Blend`] without its effect, as carried by phase items.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct EffectBlend {
+    mode: BlendMode,
+    factor: f32,
+    scope: EffectScope,
+}
+
+impl EffectBlend {
+    /// True if the output of the effect simply replaces the image, so there is nothing to blend.
+    pub(crate) fn replaces(&self) -> bool {
+        self.mode == BlendMode::Normal
+            && self.factor >= 1.0
+            && self.scope == EffectScope::Everywhere
+    }
+}
+
+impl<C> From<Blend<C>> for EffectBlend {
+    fn from(blend: Blend<C>) -> Self {
+        Self {
+            mode: blend.mode,
+            factor: blend.factor,
+            scope: blend.scope,
+        }
+    }
+}
+
+#[derive(Debug, ShaderType, Clone, Copy)]
+struct BlendUniform {
+    factor: f32,
+}
+
+#[derive(Resource)]
+pub(crate) struct BlendData {
+    layout: BindGroupLayout,
+    shared_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for BlendData {
+    fn from_world(world: &mut World) -> Self {
+        let layout = super::create_layout(
+            world,
+            "Blend",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(BlendUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The image from before the effect.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // The effect mask of the camera, see `EffectScope::Mask`.
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Blend previous sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        BlendData {
+            layout,
+            shared_layout,
+            sampler,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for BlendData {
+    /// The variant, where it applies, and the format of the camera it draws into.
+    type Key = (BlendMode, EffectScope, TextureFormat);
+
+    fn specialize(&self, (mode, scope, format): Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = mode.shader_defs();
+        shader_defs.extend(scope.shader_defs());
+
+        let descriptor = super::render_pipeline_descriptor(
+            "Blend",
+            &self.shared_layout,
+            &self.layout,
+            BLEND_SHADER_HANDLE.typed(),
+            shader_defs,
+        );
+
+        super::with_target_format(descriptor, format)
+    }
+}
+
+/// A blend pass, run after the last pass of an effect.
+struct BlendPass {
+    draw_function: DrawFunctionId,
+    pipeline_id: CachedRenderPipelineId,
+    bind_group: BindGroup,
+}
+
+/// The blend passes of a view, in the render world.
+#[derive(Component)]
+pub(crate) struct ViewBlends {
+    /// The image from before the effect being blended, copied by the node.
+    pub capture: Texture,
+
+    passes: Vec<BlendPass>,
+}
+
+impl ViewBlends {
+    /// True if the effect drawn with the given draw function is blended.
+    pub(crate) fn blends(&self, draw_function: DrawFunctionId) -> bool {
+        self.passes
+            .iter()
+            .any(|pass| pass.draw_function == draw_function)
+    }
+
+    /// Blends the output of the effect drawn with the given draw function with the captured image.
+    ///
+    /// Does nothing if the effect isn't blended, or the blend pipeline isn't ready yet.
+    pub(crate) fn apply(
+        &self,
+        render_context: &mut RenderContext,
+        pipeline_cache: &PipelineCache,
+        shared_bind_groups: &PostProcessingSharedBindGroups,
+        shared_key: SharedBindGroupKey,
+        camera: &ExtractedCamera,
+        view_target: &ViewTarget,
+        draw_function: DrawFunctionId,
+    ) {
+        let Some(pass) = self
+            .passes
+            .iter()
+            .find(|pass| pass.draw_function == draw_function)
+        else {
+            return;
+        };
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pass.pipeline_id) else {
+            return;
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let key = SharedBindGroupKey {
+            source: post_process.source.id(),
+            ..shared_key
+        };
+        let Some(shared_bind_group) = shared_bind_groups.cached_texture_bind_groups.get(&key)
+        else {
+            return;
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Blend"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: super::viewport::operations(camera),
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        super::viewport::clip(&mut render_pass, camera, 1);
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, shared_bind_group, &[]);
+        render_pass.set_bind_group(1, &pass.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<BlendData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlendData>>,
+    mut texture_cache: ResMut<TextureCache>,
+    fallback_image: Res<FallbackImage>,
+    mut warned: Local<HashSet<(Entity, EffectScope)>>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ViewTarget,
+        &RenderPhase<PostProcessingPhaseItem>,
+        Option<&ViewPrepassTextures>,
+        Option<&ViewEffectMask>,
+    )>,
+) {
+    let _span = info_span!("vfx::blend::queue").entered();
+
+    for (entity, camera, view_target, phase, prepass_textures, effect_mask) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        // The passes of an effect share its draw function, so there is one blend per draw function.
+        let mut blends: Vec<(DrawFunctionId, EffectBlend)> = vec![];
+        for item in &phase.items {
+            if !item.blend.replaces()
+                && !blends
+                    .iter()
+                    .any(|(draw_function, _)| *draw_function == item.draw_function)
+            {
+                blends.push((item.draw_function, item.blend));
+            }
+        }
+
+        if blends.is_empty() {
+            continue;
+        }
+
+        let has_depth = prepass_textures.is_some_and(|textures| textures.depth.is_some());
+        for (_, blend) in &blends {
+            if let Some(missing) = blend.scope.missing(has_depth, effect_mask.is_some()) {
+                if warned.insert((entity, blend.scope)) {
+                    warn!("An effect on camera {entity:?} {missing}");
+                }
+            }
+        }
+
+        // Without a mask, the white fallback makes the mask drawn everywhere.
+        let mask_view = effect_mask.map_or(&fallback_image.d2.texture_view, |mask| mask.view());
+
+        let capture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("Blend previous"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_target.main_texture_format(),
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+
+        let passes = blends
+            .into_iter()
+            .map(|(draw_function, blend)| {
+                let mut uniform = encase::UniformBuffer::new(vec![]);
+                uniform
+                    .write(&BlendUniform {
+                        factor: blend.factor,
+                    })
+                    .expect("Blend uniform should be writable");
+
+                let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("Blend Uniform Buffer"),
+                    contents: &uniform.into_inner(),
+                    usage: BufferUsages::UNIFORM,
+                });
+
+                let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("Blend Uniform Bind Group"),
+                    layout: &data.layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: buffer.as_entire_binding(),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::TextureView(&capture.default_view),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: BindingResource::Sampler(&data.sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 3,
+                            resource: BindingResource::TextureView(mask_view),
+                        },
+                    ],
+                });
+
+                BlendPass {
+                    draw_function,
+                    pipeline_id: pipelines.specialize(
+                        &pipeline_cache,
+                        &data,
+                        (blend.mode, blend.scope, view_target.main_texture_format()),
+                    ),
+                    bind_group,
+                }
+            })
+            .collect();
+
+        commands.entity(entity).insert(ViewBlends {
+            capture: capture.texture,
+            passes,
+        });
+    }
+}
+
+/// Blends the output of effects with a [`Blend`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            BLEND_SHADER_HANDLE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "blend.wgsl"),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<BlendMode>()
+            .register_type::<EffectScope>();
+
+        super::render_app(app)
+            .init_resource::<BlendData>()
+            .init_resource::<SpecializedRenderPipelines<BlendData>>()
+            .add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
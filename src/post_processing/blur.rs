@@ -12,7 +12,7 @@ use bevy::{
         render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
         render_resource::{
             BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
-            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderType,
         },
         renderer::RenderDevice,
         Render, RenderSet,
@@ -21,11 +21,17 @@ use bevy::{
 
 use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
 
-use super::{Order, PostProcessingPhaseItem};
+use super::{
+    blend::Blend, compute::ComputeData, resolution::Resolution, sampler::SourceSampler, Order,
+    PostProcessingPhaseItem,
+};
 
 pub(crate) const BLUR_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 11044253213698850613);
 
+pub(crate) const BLUR_COMPUTE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1922122331032740049);
+
 #[derive(Resource)]
 pub(crate) struct BlurData {
     pub pipeline_id: CachedRenderPipelineId,
@@ -34,6 +40,9 @@ pub(crate) struct BlurData {
 
 impl FromWorld for BlurData {
     fn from_world(world: &mut World) -> Self {
+        // The compute variant binds the uniform too, where supported.
+        let visibility = world.resource::<ComputeData>().visibility();
+
         let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
             world,
             "Blur",
@@ -44,12 +53,20 @@ impl FromWorld for BlurData {
                     has_dynamic_offset: true,
                     min_binding_size: Some(Blur::min_size()),
                 },
-                visibility: ShaderStages::FRAGMENT,
+                visibility,
                 count: None,
             }],
             BLUR_SHADER_HANDLE.typed(),
         );
 
+        super::compute::add_variant::<Blur>(
+            world,
+            "Blur",
+            pipeline_id,
+            &uniform_layout,
+            BLUR_COMPUTE_SHADER_HANDLE.typed(),
+        );
+
         BlurData {
             pipeline_id,
             uniform_layout,
@@ -66,6 +83,19 @@ impl bevy::prelude::Plugin for Plugin {
             concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "blur.wgsl"),
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            BLUR_COMPUTE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "blur_compute.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Blur>();
+        super::register_effect_types::<Blur>(app);
 
         // This puts the uniform into the render world.
         app.add_plugins((
@@ -92,15 +122,23 @@ fn prepare(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<Blur>,
+        &Blend<Blur>,
+        &Resolution<Blur>,
+        &SourceSampler<Blur>,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
+    let _span = info_span!("vfx::blur::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
         let draw_function = draw_functions.read().id::<DrawPostProcessingEffect<Blur>>();
 
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
             pipeline_id: data.pipeline_id,
         });
@@ -114,6 +152,8 @@ fn queue(
     uniforms: Res<ComponentUniforms<Blur>>,
     views: Query<Entity, With<Blur>>,
 ) {
+    let _span = info_span!("vfx::blur::queue").entered();
+
     bind_group.inner = None;
 
     if let Some(uniforms) = uniforms.binding() {
@@ -131,7 +171,12 @@ fn queue(
 }
 
 /// Blur settings.
-#[derive(Debug, Copy, Clone, Component, ShaderType)]
+///
+/// Where supported, the blur runs as a compute shader, see [`super::compute::ComputePath`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
 pub struct Blur {
     /// How blurry the output image should be.
     /// If `0.0`, no blur is applied.
@@ -169,6 +214,8 @@ impl ExtractComponent for Blur {
     type Out = Self;
 
     fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::blur::extract").entered();
+
         if !camera.is_active {
             return None;
         }
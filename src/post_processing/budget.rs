@@ -0,0 +1,270 @@
+use std::{
+    any::TypeId,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{for_each_effect, lut::Lut};
+
+/// How important an effect is, when effects are shed to stay within an [`EffectBudget`].
+///
+/// Effects with a lower priority are shed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum EffectPriority {
+    /// Polish which is barely missed, e.g. [`Dither`](super::dither::Dither) or
+    /// [`Raindrops`](super::raindrops::Raindrops).
+    Low,
+
+    /// Effects which add to the mood, e.g. [`Glow`](super::glow::Glow) or
+    /// [`Fog`](super::fog::Fog).
+    Medium,
+
+    /// Effects which are missed, e.g. [`DepthOfField`](super::depth_of_field::DepthOfField).
+    High,
+
+    /// Effects which define the look of the game or matter for gameplay,
+    /// e.g. [`VisionMode`](super::vision_mode::VisionMode) or
+    /// [`Pixelate`](super::pixelate::Pixelate). These are never shed.
+    Essential,
+}
+
+/// The approximate cost and default priority of an effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectCost {
+    /// The rough GPU cost of the effect,
+    /// relative to a simple single pass effect such as [`Flip`](super::flip::Flip) at `1.0`.
+    pub cost: f32,
+
+    /// The priority of the effect, unless overridden via [`EffectBudget::with_priority`].
+    pub priority: EffectPriority,
+}
+
+impl EffectCost {
+    /// The declared cost of an effect component, e.g. `EffectCost::of::<Glow>()`.
+    ///
+    /// `None` if the type is not an effect of this crate. Effects of other crates declare theirs
+    /// via [`super::external::EffectPlugin::COST`].
+    pub fn of<C: 'static>() -> Option<Self> {
+        let id = TypeId::of::<C>();
+
+        macro_rules! costs {
+            (@cost None) => {
+                None
+            };
+            (@cost ($cost:expr, $priority:ident)) => {
+                Some(Self {
+                    cost: $cost,
+                    priority: EffectPriority::$priority,
+                })
+            };
+            (
+                $($name:ident($effect:path) {
+                    state: $state:tt,
+                    category: $category:ident,
+                    cost: $cost:tt
+                    $(, $($meta:tt)*)?
+                }),* $(,)?
+            ) => {
+                $(
+                    if id == TypeId::of::<$effect>() {
+                        return costs!(@cost $cost);
+                    }
+                )*
+            };
+        }
+
+        if id == TypeId::of::<Lut>() {
+            return costs!(@cost (1.0, Essential));
+        }
+        for_each_effect!(costs);
+
+        None
+    }
+}
+
+/// An effect which rendered on a camera, as of the last extracted frame.
+#[derive(Debug, Clone, Copy)]
+struct InUse {
+    name: &'static str,
+    cost: EffectCost,
+}
+
+/// Sheds effects when the frame time exceeds a target, and brings them back when there is headroom.
+///
+/// Not inserted by default. Insert it to keep the effects within a frame budget:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{budget::{EffectBudget, EffectPriority}, fog::Fog};
+/// # let mut app = App::new();
+/// app.insert_resource(EffectBudget::new(1000.0 / 60.0).with_priority::<Fog>(EffectPriority::High));
+/// ```
+///
+/// While the smoothed frame time is above the target, one effect is shed (not rendered)
+/// each [`EffectBudget::cooldown`] seconds:
+/// the lowest priority first, and of those the costliest first.
+/// Once the frame time is below the target by [`EffectBudget::headroom`],
+/// the effects come back one at a time, in the opposite order.
+///
+/// The frame time covers the whole app, so this also sheds effects when something else is slow.
+/// [`EffectPriority::Essential`] effects are never shed.
+#[derive(Debug, Clone, Resource)]
+pub struct EffectBudget {
+    /// The frame time to stay within, in milliseconds.
+    pub target_frame_time_ms: f32,
+
+    /// How far below the target the frame time must be before an effect comes back,
+    /// as a fraction of the target. Keeps effects from coming and going every few seconds.
+    pub headroom: f32,
+
+    /// The least time between shedding or bringing back effects, in seconds.
+    /// Gives the frame time a moment to settle after each change.
+    pub cooldown: f32,
+
+    /// Priorities overriding the ones declared by [`EffectCost::of`].
+    priorities: HashMap<TypeId, EffectPriority>,
+
+    /// The smoothed frame time, in milliseconds.
+    frame_time_ms: f32,
+
+    /// Time since the last change, in seconds.
+    since_change: f32,
+
+    /// The shed effects, in the order they were shed.
+    shed: Vec<(TypeId, &'static str)>,
+
+    /// The effects which rendered, filled while extracting.
+    in_use: Arc<Mutex<HashMap<TypeId, InUse>>>,
+}
+
+impl EffectBudget {
+    /// A budget with the given target frame time in milliseconds, e.g. `1000.0 / 60.0`.
+    pub fn new(target_frame_time_ms: f32) -> Self {
+        Self {
+            target_frame_time_ms,
+            headroom: 0.15,
+            cooldown: 1.0,
+            priorities: default(),
+            frame_time_ms: target_frame_time_ms,
+            since_change: 0.0,
+            shed: vec![],
+            in_use: default(),
+        }
+    }
+
+    /// Override the priority of the effect component `C`.
+    pub fn with_priority<C: Component>(mut self, priority: EffectPriority) -> Self {
+        self.priorities.insert(TypeId::of::<C>(), priority);
+        self
+    }
+
+    /// The smoothed frame time the budget acts on, in milliseconds.
+    pub fn frame_time_ms(&self) -> f32 {
+        self.frame_time_ms
+    }
+
+    /// The names of the shed effects, e.g. `"Glow"`, in the order they were shed.
+    pub fn shed_effects(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.shed.iter().map(|(_, name)| *name)
+    }
+
+    /// True if the effect component `C` is shed.
+    pub fn is_shed<C: 'static>(&self) -> bool {
+        let id = TypeId::of::<C>();
+        self.shed.iter().any(|(shed, _)| *shed == id)
+    }
+
+    /// Note that the effect component `C`, which has the given cost, renders on a camera this frame.
+    pub(crate) fn track<C: 'static>(&self, cost: Option<EffectCost>) {
+        let Some(cost) = cost else {
+            return;
+        };
+
+        let name = std::any::type_name::<C>();
+        let name = name.rsplit("::").next().unwrap_or(name);
+
+        self.in_use
+            .lock()
+            .expect("Effects in use should not be poisoned")
+            .insert(TypeId::of::<C>(), InUse { name, cost });
+    }
+
+    /// The next effect to shed, if any is left.
+    fn next_to_shed(&self, in_use: &HashMap<TypeId, InUse>) -> Option<(TypeId, &'static str)> {
+        in_use
+            .iter()
+            .map(|(id, effect)| {
+                let priority = self
+                    .priorities
+                    .get(id)
+                    .copied()
+                    .unwrap_or(effect.cost.priority);
+                (id, effect, priority)
+            })
+            .filter(|(id, _, priority)| {
+                *priority != EffectPriority::Essential && !self.shed.iter().any(|(s, _)| s == *id)
+            })
+            .min_by(|(_, a, a_priority), (_, b, b_priority)| {
+                a_priority
+                    .cmp(b_priority)
+                    .then(b.cost.cost.total_cmp(&a.cost.cost))
+            })
+            .map(|(id, effect, _)| (*id, effect.name))
+    }
+}
+
+/// How quickly the smoothed frame time follows the measured one.
+const SMOOTHING: f32 = 0.05;
+
+fn apply_budget(time: Res<Time>, budget: Option<ResMut<EffectBudget>>) {
+    let Some(mut budget) = budget else {
+        return;
+    };
+
+    let _span = info_span!("vfx::budget").entered();
+
+    // Slow motion and pausing don't make frames cheaper, so the raw time is measured.
+    let frame_time_ms = time.raw_delta_seconds() * 1000.0;
+    budget.frame_time_ms += (frame_time_ms - budget.frame_time_ms) * SMOOTHING;
+    budget.since_change += time.raw_delta_seconds();
+
+    let in_use = std::mem::take(
+        &mut *budget
+            .in_use
+            .lock()
+            .expect("Effects in use should not be poisoned"),
+    );
+
+    if budget.since_change < budget.cooldown {
+        return;
+    }
+
+    if budget.frame_time_ms > budget.target_frame_time_ms {
+        if let Some(effect) = budget.next_to_shed(&in_use) {
+            info!(
+                "Frame time {:.2}ms is over budget, shedding {}",
+                budget.frame_time_ms, effect.1
+            );
+            budget.shed.push(effect);
+            budget.since_change = 0.0;
+        }
+    } else if budget.frame_time_ms < budget.target_frame_time_ms * (1.0 - budget.headroom) {
+        if let Some((_, name)) = budget.shed.pop() {
+            info!(
+                "Frame time {:.2}ms has headroom, bringing back {name}",
+                budget.frame_time_ms
+            );
+            budget.since_change = 0.0;
+        }
+    }
+}
+
+/// Sheds effects to stay within an [`EffectBudget`], if one is inserted.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EffectPriority>()
+            .add_systems(Last, apply_budget);
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const BULGE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 12918236560281734420);
+
+#[derive(Resource)]
+pub(crate) struct BulgeData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for BulgeData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Bulge",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(BulgeUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            BULGE_SHADER_HANDLE.typed(),
+        );
+
+        BulgeData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            BULGE_SHADER_HANDLE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "bulge.wgsl"),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Bulge>();
+        super::register_effect_types::<Bulge>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Bulge>::default(),
+            UniformComponentPlugin::<BulgeUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Bulge>,
+            )
+            .init_resource::<BulgeData>()
+            .init_resource::<UniformBindGroup<BulgeUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<BulgeUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<BulgeData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Bulge>,
+        &Blend<Bulge>,
+        &Resolution<Bulge>,
+        &SourceSampler<Bulge>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::bulge::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<BulgeUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<BulgeData>,
+    mut bind_group: ResMut<UniformBindGroup<BulgeUniform>>,
+    uniforms: Res<ComponentUniforms<BulgeUniform>>,
+    views: Query<Entity, With<BulgeUniform>>,
+) {
+    let _span = info_span!("vfx::bulge::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Bulge Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Bulges or pinches the image around a point, like a bubble under the image.
+///
+/// Useful for impacts, heartbeat pulses (by animating [`Bulge::strength`] with a large
+/// [`Bulge::radius`]), and cartoon squash moments.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Bulge {
+    /// The middle of the bulge, in UV coordinates where `(0, 0)` is the top left of the image.
+    pub center: Vec2,
+
+    /// How far the bulge reaches from the middle, as a fraction of the height of the image.
+    pub radius: f32,
+
+    /// How strongly the image bulges out, between `-1.0` and `1.0`.
+    /// Negative values pinch the image in instead. `0.0` leaves the image unchanged.
+    pub strength: f32,
+}
+
+impl Bulge {
+    /// A pinch at the given point, in UV coordinates.
+    pub fn pinch(center: Vec2) -> Self {
+        Self {
+            center,
+            strength: -0.5,
+            ..default()
+        }
+    }
+}
+
+impl Default for Bulge {
+    fn default() -> Self {
+        Self {
+            center: Vec2::splat(0.5),
+            radius: 0.4,
+            strength: 0.5,
+        }
+    }
+}
+
+impl Display for Bulge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Bulge center: {:.2}, radius: {:.2}, strength: {:.2}",
+            self.center, self.radius, self.strength
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Bulge`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct BulgeUniform {
+    pub(crate) center: Vec2,
+    pub(crate) radius: f32,
+    pub(crate) strength: f32,
+}
+
+impl From<Bulge> for BulgeUniform {
+    fn from(bulge: Bulge) -> Self {
+        Self {
+            center: bulge.center,
+            radius: bulge.radius,
+            strength: bulge.strength,
+        }
+    }
+}
+
+impl ExtractComponent for Bulge {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = BulgeUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::bulge::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -0,0 +1,200 @@
+use std::any::TypeId;
+
+use bevy::{prelude::*, utils::HashSet};
+
+use super::{for_each_effect, lut::Lut};
+
+/// A group of related effects, which can be bypassed together via [`EffectBypass`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum EffectCategory {
+    /// Effects which grade the colors, such as [`Lut`] and
+    /// [`VisionMode`](super::vision_mode::VisionMode).
+    Color,
+
+    /// Effects which blur or sharpen, such as [`Blur`](super::blur::Blur) and
+    /// [`DepthOfField`](super::depth_of_field::DepthOfField).
+    Focus,
+
+    /// Effects which displace the image, such as [`Raindrops`](super::raindrops::Raindrops) and
+    /// [`Wave`](super::wave::Wave).
+    Distortion,
+
+    /// Effects which mimic a lens, such as
+    /// [`ChromaticAberration`](super::chromatic_aberration::ChromaticAberration) and
+    /// [`LensFlare`](super::lens_flare::LensFlare).
+    Lens,
+
+    /// Effects of the air between the camera and the scene, and of the weather, such as
+    /// [`Fog`](super::fog::Fog) and [`LightShafts`](super::light_shafts::LightShafts).
+    Atmosphere,
+
+    /// Effects which give the image a stylized look, such as
+    /// [`Halftone`](super::halftone::Halftone) and [`Pixelate`](super::pixelate::Pixelate).
+    Stylize,
+}
+
+impl EffectCategory {
+    /// All categories.
+    pub const ALL: &'static [EffectCategory] = &[
+        Self::Color,
+        Self::Focus,
+        Self::Distortion,
+        Self::Lens,
+        Self::Atmosphere,
+        Self::Stylize,
+    ];
+
+    /// The category of an effect component, e.g. `EffectCategory::of::<Fog>()`.
+    ///
+    /// `None` if the type is not an effect of this crate, or is a debug view, accessibility
+    /// option or fix for image quality such as [`super::false_color::FalseColor`],
+    /// [`super::color_blindness::ColorBlindness`] and [`super::deband::Deband`], or part of the game rather than its look such as
+    /// [`super::letterbox::Letterbox`], [`super::screen_door_fade::ScreenDoorFade`] and
+    /// [`super::screen_transition::ScreenTransition`], which are only bypassed along with all effects.
+    /// Effects of other crates declare theirs via [`super::external::EffectPlugin::CATEGORY`].
+    pub fn of<C: 'static>() -> Option<Self> {
+        let id = TypeId::of::<C>();
+
+        macro_rules! categories {
+            (@category None) => {
+                None
+            };
+            (@category $category:ident) => {
+                Some(Self::$category)
+            };
+            (
+                $($name:ident($effect:path) {
+                    state: $state:tt, category: $category:ident $(, $($meta:tt)*)?
+                }),* $(,)?
+            ) => {
+                $(
+                    if id == TypeId::of::<$effect>() {
+                        return categories!(@category $category);
+                    }
+                )*
+            };
+        }
+
+        if id == TypeId::of::<Lut>() {
+            return categories!(@category Color);
+        }
+        for_each_effect!(categories);
+
+        None
+    }
+}
+
+/// Bypasses effects at runtime, without removing their components.
+///
+/// Meant for before and after comparisons, e.g. during art review.
+/// Bypassed effects are not rendered at all, so they don't cost anything either.
+///
+/// See [`BypassKeys`] for toggling this from the keyboard.
+#[derive(Debug, Clone, Default, PartialEq, Resource)]
+pub struct EffectBypass {
+    /// If true, all effects are bypassed.
+    pub all: bool,
+
+    /// The categories of effects which are bypassed.
+    pub categories: HashSet<EffectCategory>,
+}
+
+impl EffectBypass {
+    /// Toggle bypassing all effects.
+    pub fn toggle_all(&mut self) {
+        self.all = !self.all;
+    }
+
+    /// Toggle bypassing the given category of effects.
+    pub fn toggle(&mut self, category: EffectCategory) {
+        if !self.categories.remove(&category) {
+            self.categories.insert(category);
+        }
+    }
+
+    /// True if effects of the given category are bypassed.
+    pub fn is_bypassed(&self, category: EffectCategory) -> bool {
+        self.all || self.categories.contains(&category)
+    }
+
+    /// True if effects of the given category are bypassed, or effects without one if `None`.
+    pub(crate) fn bypasses_category(&self, category: Option<EffectCategory>) -> bool {
+        match category {
+            Some(category) => self.is_bypassed(category),
+            None => self.all,
+        }
+    }
+}
+
+/// Key bindings for toggling [`EffectBypass`].
+///
+/// Not inserted by default. Insert it to enable the key bindings:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::bypass::{BypassKeys, EffectCategory};
+/// # let mut app = App::new();
+/// app.insert_resource(BypassKeys::default().with_category(KeyCode::F10, EffectCategory::Color));
+/// ```
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct BypassKeys {
+    /// Toggles bypassing all effects.
+    pub all: Option<KeyCode>,
+
+    /// Toggle bypassing a category of effects each.
+    pub categories: Vec<(KeyCode, EffectCategory)>,
+}
+
+impl Default for BypassKeys {
+    fn default() -> Self {
+        Self {
+            all: Some(KeyCode::F9),
+            categories: vec![],
+        }
+    }
+}
+
+impl BypassKeys {
+    /// Toggle bypassing the given category of effects with the given key.
+    pub fn with_category(mut self, key: KeyCode, category: EffectCategory) -> Self {
+        self.categories.push((key, category));
+        self
+    }
+}
+
+fn bypass_hotkeys(
+    keys: Option<Res<BypassKeys>>,
+    keyboard_input: Option<Res<Input<KeyCode>>>,
+    mut bypass: ResMut<EffectBypass>,
+) {
+    let (Some(keys), Some(keyboard_input)) = (keys, keyboard_input) else {
+        return;
+    };
+
+    if let Some(key) = keys.all {
+        if keyboard_input.just_pressed(key) {
+            bypass.toggle_all();
+            info!("Bypassing all effects: {}", bypass.all);
+        }
+    }
+
+    for (key, category) in &keys.categories {
+        if keyboard_input.just_pressed(*key) {
+            bypass.toggle(*category);
+            info!(
+                "Bypassing {category:?} effects: {}",
+                bypass.categories.contains(category)
+            );
+        }
+    }
+}
+
+/// Bypasses effects via [`EffectBypass`], optionally toggled via [`BypassKeys`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EffectCategory>()
+            .init_resource::<EffectBypass>()
+            .add_systems(PreUpdate, bypass_hotkeys.after(bevy::input::InputSystem));
+    }
+}
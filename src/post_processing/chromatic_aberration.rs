@@ -21,7 +21,9 @@ pub(crate) use bevy::{
 
 use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
 
-use super::{Order, PostProcessingPhaseItem};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
 
 pub(crate) const CHROMATIC_ABERRATION_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4357337502039082134);
@@ -71,6 +73,9 @@ impl bevy::prelude::Plugin for Plugin {
             Shader::from_wgsl
         );
 
+        app.register_type::<ChromaticAberration>();
+        super::register_effect_types::<ChromaticAberration>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins((
             ExtractComponentPlugin::<ChromaticAberration>::default(),
@@ -94,10 +99,15 @@ fn prepare(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<ChromaticAberration>,
+        &Blend<ChromaticAberration>,
+        &Resolution<ChromaticAberration>,
+        &SourceSampler<ChromaticAberration>,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
+    let _span = info_span!("vfx::chromatic_aberration::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
         let draw_function = draw_functions
             .read()
             .id::<DrawPostProcessingEffect<ChromaticAberration>>();
@@ -105,6 +115,9 @@ fn prepare(
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
             pipeline_id: data.pipeline_id,
         });
@@ -118,6 +131,8 @@ fn queue(
     uniforms: Res<ComponentUniforms<ChromaticAberration>>,
     views: Query<Entity, With<ChromaticAberration>>,
 ) {
+    let _span = info_span!("vfx::chromatic_aberration::queue").entered();
+
     bind_group.inner = None;
 
     if let Some(uniforms) = uniforms.binding() {
@@ -135,7 +150,10 @@ fn queue(
 }
 
 /// Chromatic Aberration settings.
-#[derive(Debug, Copy, Clone, Component, ShaderType)]
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
 pub struct ChromaticAberration {
     /// The direction (in UV space) the red channel is offset in.
     /// Will be normalized.
@@ -207,6 +225,8 @@ impl ExtractComponent for ChromaticAberration {
     type Out = Self;
 
     fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::chromatic_aberration::extract").entered();
+
         if !camera.is_active {
             return None;
         }
@@ -214,3 +234,22 @@ impl ExtractComponent for ChromaticAberration {
         Some(*settings)
     }
 }
+
+#[cfg(feature = "material2d")]
+impl super::material::MaterialEffect for ChromaticAberration {
+    type Uniform = Self;
+    type Key = ();
+
+    const MATERIAL_UUID: bevy::utils::Uuid =
+        bevy::utils::Uuid::from_u128(0x759e2954a4914cdf98a2059c5d0c7dcb);
+
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        bevy::render::render_resource::ShaderRef::Handle(CHROMATIC_ABERRATION_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        *self
+    }
+
+    fn key(&self) {}
+}
@@ -0,0 +1,218 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const COLOR_ADJUST_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16004858205252902549);
+
+#[derive(Resource)]
+pub(crate) struct ColorAdjustData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for ColorAdjustData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Color Adjust",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ColorAdjust::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            COLOR_ADJUST_SHADER_HANDLE.typed(),
+        );
+
+        ColorAdjustData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            COLOR_ADJUST_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "color_adjust.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ColorAdjust>();
+        super::register_effect_types::<ColorAdjust>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<ColorAdjust>::default(),
+            UniformComponentPlugin::<ColorAdjust>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<ColorAdjust>,
+            )
+            .init_resource::<ColorAdjustData>()
+            .init_resource::<UniformBindGroup<ColorAdjust>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<ColorAdjust>>();
+    }
+}
+
+fn prepare(
+    data: Res<ColorAdjustData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<ColorAdjust>,
+        &Blend<ColorAdjust>,
+        &Resolution<ColorAdjust>,
+        &SourceSampler<ColorAdjust>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::color_adjust::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<ColorAdjust>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<ColorAdjustData>,
+    mut bind_group: ResMut<UniformBindGroup<ColorAdjust>>,
+    uniforms: Res<ComponentUniforms<ColorAdjust>>,
+    views: Query<Entity, With<ColorAdjust>>,
+) {
+    let _span = info_span!("vfx::color_adjust::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Color Adjust Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Simple color grading, without having to make a [`super::lut::Lut`].
+///
+/// The adjustments are applied in the order of the fields.
+/// The defaults leave the image unchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct ColorAdjust {
+    /// How much is added to each color channel. `0.0` leaves the image unchanged,
+    /// negative values darken it.
+    pub brightness: f32,
+
+    /// How far colors are pushed away from middle gray. `1.0` leaves the image unchanged,
+    /// `0.0` makes it all gray.
+    pub contrast: f32,
+
+    /// How colorful the image is. `1.0` leaves the image unchanged,
+    /// `0.0` makes it black and white.
+    pub saturation: f32,
+
+    /// How far the hue is turned, in degrees. `180.0` swaps colors for their opposites.
+    pub hue_shift: f32,
+
+    /// The gamma correction. `1.0` leaves the image unchanged,
+    /// higher values brighten the dark parts of the image.
+    pub gamma: f32,
+}
+
+impl Default for ColorAdjust {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            hue_shift: 0.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl Display for ColorAdjust {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Color Adjust brightness: {:.2}, contrast: {:.2}, saturation: {:.2}, hue shift: {:.0}°, gamma: {:.2}",
+            self.brightness, self.contrast, self.saturation, self.hue_shift, self.gamma
+        )
+    }
+}
+
+impl ExtractComponent for ColorAdjust {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::color_adjust::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const COLOR_BLINDNESS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 919658945446973588);
+
+#[derive(Resource)]
+pub(crate) struct ColorBlindnessData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+}
+
+impl FromWorld for ColorBlindnessData {
+    fn from_world(world: &mut World) -> Self {
+        let uniform_layout = super::create_layout(
+            world,
+            "Color Blindness",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ColorBlindnessUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+        ColorBlindnessData {
+            uniform_layout,
+            shared_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            COLOR_BLINDNESS_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "color_blindness.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ColorBlindness>();
+        app.register_type::<ColorBlindnessVariant>();
+        app.register_type::<ColorBlindnessMode>();
+        super::register_effect_types::<ColorBlindness>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<ColorBlindness>::default(),
+            UniformComponentPlugin::<ColorBlindnessUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<ColorBlindness>,
+            )
+            .init_resource::<ColorBlindnessData>()
+            .init_resource::<UniformBindGroup<ColorBlindnessUniform>>()
+            .init_resource::<SpecializedRenderPipelines<ColorBlindnessData>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<ColorBlindnessUniform>>();
+    }
+}
+
+impl SpecializedRenderPipeline for ColorBlindnessData {
+    type Key = (ColorBlindnessVariant, ColorBlindnessMode);
+
+    fn specialize(&self, (variant, mode): Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = vec![variant.into()];
+        if mode == ColorBlindnessMode::Daltonize {
+            shader_defs.push("DALTONIZE".into());
+        }
+
+        super::render_pipeline_descriptor(
+            "Color Blindness",
+            &self.shared_layout,
+            &self.uniform_layout,
+            COLOR_BLINDNESS_SHADER_HANDLE.typed(),
+            shader_defs,
+        )
+    }
+}
+
+fn prepare(
+    data: Res<ColorBlindnessData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ColorBlindnessData>>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<ColorBlindness>,
+        &Blend<ColorBlindness>,
+        &Resolution<ColorBlindness>,
+        &SourceSampler<ColorBlindness>,
+        &ColorBlindnessVariant,
+        &ColorBlindnessMode,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::color_blindness::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, variant, mode) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<ColorBlindnessUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, (*variant, *mode));
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<ColorBlindnessData>,
+    mut bind_group: ResMut<UniformBindGroup<ColorBlindnessUniform>>,
+    uniforms: Res<ComponentUniforms<ColorBlindnessUniform>>,
+    views: Query<Entity, With<ColorBlindnessUniform>>,
+) {
+    let _span = info_span!("vfx::color_blindness::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Color Blindness Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Which kind of color blindness a [`ColorBlindness`] is about.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorBlindnessVariant {
+    /// The red cones are missing, so reds look dark and are confused with greens.
+    #[default]
+    Protanopia,
+
+    /// The green cones are missing, so reds and greens are confused.
+    /// The most common kind.
+    Deuteranopia,
+
+    /// The blue cones are missing, so blues are confused with greens, and yellows with pinks.
+    Tritanopia,
+}
+
+impl From<ColorBlindnessVariant> for ShaderDefVal {
+    fn from(variant: ColorBlindnessVariant) -> Self {
+        match variant {
+            ColorBlindnessVariant::Protanopia => "PROTANOPIA",
+            ColorBlindnessVariant::Deuteranopia => "DEUTERANOPIA",
+            ColorBlindnessVariant::Tritanopia => "TRITANOPIA",
+        }
+        .into()
+    }
+}
+
+/// What a [`ColorBlindness`] does about its [`ColorBlindnessVariant`].
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorBlindnessMode {
+    /// Shows the image as it looks with the color blindness, e.g. for checking that the
+    /// colors which matter for gameplay can still be told apart.
+    #[default]
+    Simulate,
+
+    /// Corrects the image for players with the color blindness ("daltonization"),
+    /// by moving the differences they can't see into colors they can.
+    /// Players without it see shifted colors.
+    Daltonize,
+}
+
+/// Simulates color blindness, or corrects the image for it.
+///
+/// Simulating is for testing the game with the eyes of color blind players,
+/// daltonizing is an accessibility option to ship to them:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::color_blindness::{ColorBlindness, ColorBlindnessVariant};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     ColorBlindness::daltonize(ColorBlindnessVariant::Deuteranopia),
+/// ));
+/// ```
+///
+/// Either way the effect should see the image as it is shown, so give it an [`Order`]
+/// after the other effects.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct ColorBlindness {
+    /// How strong the color blindness is, from `0.0` (normal vision) to `1.0`
+    /// (the cones are missing).
+    /// Lower values are closer to the milder, more common anomalous trichromacy.
+    pub severity: f32,
+
+    /// Which [`ColorBlindnessVariant`] to simulate or correct for.
+    pub variant: ColorBlindnessVariant,
+
+    /// Whether the image is simulated or corrected, see [`ColorBlindnessMode`].
+    pub mode: ColorBlindnessMode,
+}
+
+impl ColorBlindness {
+    /// Shows the image as it looks with the given color blindness.
+    pub fn simulate(variant: ColorBlindnessVariant) -> Self {
+        Self {
+            variant,
+            mode: ColorBlindnessMode::Simulate,
+            ..default()
+        }
+    }
+
+    /// Corrects the image for players with the given color blindness.
+    pub fn daltonize(variant: ColorBlindnessVariant) -> Self {
+        Self {
+            variant,
+            mode: ColorBlindnessMode::Daltonize,
+            ..default()
+        }
+    }
+}
+
+impl Default for ColorBlindness {
+    fn default() -> Self {
+        Self {
+            severity: 1.0,
+            variant: ColorBlindnessVariant::default(),
+            mode: ColorBlindnessMode::default(),
+        }
+    }
+}
+
+impl Display for ColorBlindness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Color Blindness {:?} ({:?}), severity: {:.2}",
+            self.variant, self.mode, self.severity
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`ColorBlindness`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct ColorBlindnessUniform {
+    pub(crate) severity: f32,
+}
+
+impl From<ColorBlindness> for ColorBlindnessUniform {
+    fn from(color_blindness: ColorBlindness) -> Self {
+        Self {
+            severity: color_blindness.severity.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl ExtractComponent for ColorBlindness {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (
+        ColorBlindnessUniform,
+        ColorBlindnessVariant,
+        ColorBlindnessMode,
+    );
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::color_blindness::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(((*settings).into(), settings.variant, settings.mode))
+    }
+}
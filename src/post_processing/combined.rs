@@ -0,0 +1,473 @@
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{ComponentUniforms, ExtractComponent, UniformComponentPlugin},
+        render_phase::{AddRenderCommand, DrawFunctionId, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        },
+        renderer::RenderDevice,
+        Extract, Render, RenderSet,
+    },
+};
+
+use super::{
+    color_adjust::ColorAdjust,
+    flip::{Flip, FlipUniform},
+    masks::{Mask, MaskUniform, MaskVariant},
+    pixelate::{Pixelate, PixelateSampling, PixelateUniform},
+    white_balance::{WhiteBalance, WhiteBalanceUniform},
+    DrawPostProcessingEffect, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const COMBINED_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3306473516497208561);
+
+/// Draws chains of simple effects on the camera in a single pass.
+///
+/// Each effect normally reads and writes the whole image in a pass of its own.
+/// For effects which only pick where the image is read, or only change colors,
+/// this bandwidth is mostly wasted, which hurts on mobile and the web.
+/// With this, effects next to each other in the order are instead compiled into one shader
+/// and drawn at once, in the same order.
+///
+/// These effects can be combined:
+///
+/// - [`Flip`] and [`Pixelate`] with [`PixelateSampling::Point`], which pick where the image
+///   is read. They must come before the other effects of a chain, so e.g. a [`Pixelate`] after a
+///   [`ColorAdjust`] starts a new chain.
+/// - [`ColorAdjust`], [`WhiteBalance`] and [`Mask`], which change colors.
+///
+/// Effects which are blended (see [`super::blend::Blend`]) or softened
+/// (see [`super::intensity::Intensity`]) are drawn on their own, as are all other effects.
+///
+/// The combined shader is compiled the first time a chain is seen,
+/// and the effects are drawn in separate passes until it is ready.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct CombinedPass;
+
+/// An effect which can be drawn as part of a [`CombinedPass`].
+///
+/// The values are known by the shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Stage {
+    Flip = 0,
+    Pixelate = 1,
+    ColorAdjust = 2,
+    WhiteBalance = 3,
+    Mask = 4,
+}
+
+impl Stage {
+    /// True if the stage picks where the image is read, rather than changing colors.
+    fn samples(self) -> bool {
+        matches!(self, Stage::Flip | Stage::Pixelate)
+    }
+}
+
+/// The effects of a chain in the order they are applied, along with the variant of the mask.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CombinedPassKey {
+    stages: Vec<Stage>,
+    mask: MaskVariant,
+}
+
+impl CombinedPassKey {
+    fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        let (sampling, coloring): (Vec<_>, Vec<_>) = self
+            .stages
+            .iter()
+            .copied()
+            .partition(|stage| stage.samples());
+
+        // Each sampling stage picks where the stage before it is read, so the last one goes first.
+        let sampling = sampling.into_iter().rev().enumerate().map(|(slot, stage)| {
+            ShaderDefVal::UInt(format!("SAMPLING_STAGE_{slot}"), stage as u32)
+        });
+        let coloring = coloring
+            .into_iter()
+            .enumerate()
+            .map(|(slot, stage)| ShaderDefVal::UInt(format!("COLOR_STAGE_{slot}"), stage as u32));
+
+        sampling
+            .chain(coloring)
+            .chain(std::iter::once(self.mask.into()))
+            .collect()
+    }
+
+    fn label(&self) -> String {
+        let stages: Vec<_> = self
+            .stages
+            .iter()
+            .map(|stage| format!("{stage:?}"))
+            .collect();
+
+        format!("Combined Pass ({})", stages.join(", "))
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct CombinedPassData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+}
+
+impl FromWorld for CombinedPassData {
+    fn from_world(world: &mut World) -> Self {
+        let uniform_layout = super::create_layout(
+            world,
+            "Combined Pass",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(CombinedPassUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+
+        CombinedPassData {
+            uniform_layout,
+            shared_layout,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for CombinedPassData {
+    type Key = CombinedPassKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            &key.label(),
+            &self.shared_layout,
+            &self.uniform_layout,
+            COMBINED_SHADER_HANDLE.typed(),
+            key.shader_defs(),
+        )
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            COMBINED_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "combined.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<CombinedPass>();
+
+        // This puts the uniform into the render world.
+        app.add_plugins(UniformComponentPlugin::<CombinedPassUniform>::default());
+
+        super::render_app(app)
+            .add_systems(ExtractSchedule, extract)
+            .init_resource::<CombinedPassData>()
+            .init_resource::<UniformBindGroup<CombinedPassUniform>>()
+            .init_resource::<SpecializedRenderPipelines<CombinedPassData>>()
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            // The chains are found in the sorted phase.
+            .add_systems(
+                Render,
+                combine
+                    .in_set(RenderSet::PhaseSort)
+                    .after(bevy::render::render_phase::sort_phase_system::<PostProcessingPhaseItem>),
+            )
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<CombinedPassUniform>>();
+    }
+}
+
+fn extract(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<
+            (
+                Entity,
+                &Camera,
+                Option<&Flip>,
+                Option<&Pixelate>,
+                Option<&ColorAdjust>,
+                Option<&WhiteBalance>,
+                Option<&Mask>,
+            ),
+            With<CombinedPass>,
+        >,
+    >,
+) {
+    for (entity, camera, flip, pixelate, color_adjust, white_balance, mask) in &cameras {
+        if !camera.is_active {
+            continue;
+        }
+
+        // The effects are extracted like they are on their own, so they look the same combined.
+        let flip = flip.and_then(|flip| Flip::extract_component((flip, camera)));
+        let pixelate =
+            pixelate.and_then(|pixelate| Pixelate::extract_component((pixelate, camera)));
+        let color_adjust = color_adjust
+            .and_then(|color_adjust| ColorAdjust::extract_component((color_adjust, camera)));
+        let white_balance = white_balance
+            .and_then(|white_balance| WhiteBalance::extract_component((white_balance, camera)));
+        let mask = mask.and_then(|mask| Mask::extract_component((mask, camera)));
+
+        commands
+            .get_or_spawn(entity)
+            .insert(CombinedPassUniform::new(
+                flip,
+                pixelate.map(|(pixelate, _)| pixelate),
+                color_adjust,
+                white_balance,
+                mask.map(|(mask, _)| mask),
+            ));
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<CombinedPassData>,
+    mut bind_group: ResMut<UniformBindGroup<CombinedPassUniform>>,
+    uniforms: Res<ComponentUniforms<CombinedPassUniform>>,
+    views: Query<Entity, With<CombinedPassUniform>>,
+) {
+    let _span = info_span!("vfx::combined::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Combined Pass Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Replaces chains of effects in the phase which can be combined with a single item each.
+fn combine(
+    data: Res<CombinedPassData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<CombinedPassData>>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+    mut views: Query<
+        (
+            &mut RenderPhase<PostProcessingPhaseItem>,
+            Option<&PixelateSampling>,
+            Option<&MaskVariant>,
+        ),
+        With<CombinedPassUniform>,
+    >,
+) {
+    let _span = info_span!("vfx::combined::combine").entered();
+
+    let (stages, draw_function) = {
+        let draw_functions = draw_functions.read();
+
+        let stages: Vec<(DrawFunctionId, Stage)> = [
+            (
+                draw_functions.get_id::<DrawPostProcessingEffect<FlipUniform>>(),
+                Stage::Flip,
+            ),
+            (
+                draw_functions.get_id::<DrawPostProcessingEffect<PixelateUniform>>(),
+                Stage::Pixelate,
+            ),
+            (
+                draw_functions.get_id::<DrawPostProcessingEffect<ColorAdjust>>(),
+                Stage::ColorAdjust,
+            ),
+            (
+                draw_functions.get_id::<DrawPostProcessingEffect<WhiteBalanceUniform>>(),
+                Stage::WhiteBalance,
+            ),
+            (
+                draw_functions.get_id::<DrawPostProcessingEffect<MaskUniform>>(),
+                Stage::Mask,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(id, stage)| Some((id?, stage)))
+        .collect();
+
+        let Some(draw_function) =
+            draw_functions.get_id::<DrawPostProcessingEffect<CombinedPassUniform>>()
+        else {
+            return;
+        };
+
+        (stages, draw_function)
+    };
+
+    for (mut phase, sampling, mask) in &mut views {
+        let mask = mask.copied().unwrap_or(MaskVariant::Vignette);
+
+        let stage_of = |item: &PostProcessingPhaseItem| {
+            let stage = stages
+                .iter()
+                .find(|(id, _)| *id == item.draw_function)
+                .map(|(_, stage)| *stage)?;
+
+            // Blending and scaling need the output of the effect on its own,
+            // and the combined pass samples the image as effects do by default.
+            if !item.blend.replaces() || !item.resolution.is_full() || !item.sampler.is_default() {
+                return None;
+            }
+
+            if stage == Stage::Pixelate && sampling != Some(&PixelateSampling::Point) {
+                return None;
+            }
+
+            Some(stage)
+        };
+
+        let items = std::mem::take(&mut phase.items);
+        let mut chain: Vec<(PostProcessingPhaseItem, Stage)> = vec![];
+
+        for item in items {
+            let stage = stage_of(&item);
+
+            // A sampling stage can't read the colors changed by the stages before it,
+            // and a chain can't span tonemapping or compositing, which happen between the passes.
+            let continues = stage.is_some_and(|stage| {
+                (!stage.samples() || chain.iter().all(|(_, other)| other.samples()))
+                    && chain
+                        .iter()
+                        .all(|(other, _)| other.sort_key.pass == item.sort_key.pass)
+            });
+
+            if !continues {
+                let chain = std::mem::take(&mut chain);
+                phase.items.extend(combine_chain(
+                    chain,
+                    mask,
+                    draw_function,
+                    &data,
+                    &pipeline_cache,
+                    &mut pipelines,
+                ));
+            }
+
+            match stage {
+                Some(stage) => chain.push((item, stage)),
+                None => phase.items.push(item),
+            }
+        }
+
+        phase.items.extend(combine_chain(
+            chain,
+            mask,
+            draw_function,
+            &data,
+            &pipeline_cache,
+            &mut pipelines,
+        ));
+    }
+}
+
+/// The items to draw for a chain of effects which can be combined:
+/// a single item if the combined pipeline is ready, else the items of the effects.
+fn combine_chain(
+    chain: Vec<(PostProcessingPhaseItem, Stage)>,
+    mask: MaskVariant,
+    draw_function: DrawFunctionId,
+    data: &CombinedPassData,
+    pipeline_cache: &PipelineCache,
+    pipelines: &mut SpecializedRenderPipelines<CombinedPassData>,
+) -> Vec<PostProcessingPhaseItem> {
+    if chain.len() < 2 {
+        return chain.into_iter().map(|(item, _)| item).collect();
+    }
+
+    let key = CombinedPassKey {
+        stages: chain.iter().map(|(_, stage)| *stage).collect(),
+        mask,
+    };
+    let pipeline_id = pipelines.specialize(pipeline_cache, data, key);
+
+    if super::ready_pipeline(pipeline_cache, pipeline_id).is_none() {
+        return chain.into_iter().map(|(item, _)| item).collect();
+    }
+
+    // The chain takes the place of its first effect.
+    let (first, _) = &chain[0];
+
+    vec![PostProcessingPhaseItem {
+        entity: first.entity,
+        sort_key: first.sort_key,
+        blend: first.blend,
+        resolution: first.resolution,
+        sampler: first.sampler,
+        draw_function,
+        pipeline_id,
+    }]
+}
+
+#[doc(hidden)]
+/// The uniforms of all effects which can be combined, see [`CombinedPass`].
+///
+/// Effects which are not on the camera are left at values which do nothing.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct CombinedPassUniform {
+    pub(crate) flip: Vec2,
+    pub(crate) block_size: f32,
+    pub(crate) brightness: f32,
+    pub(crate) contrast: f32,
+    pub(crate) saturation: f32,
+    pub(crate) hue_shift: f32,
+    pub(crate) gamma: f32,
+    pub(crate) white_balance: Vec3,
+    pub(crate) mask_strength: f32,
+    pub(crate) mask_fade: f32,
+    pub(crate) mask_nasal_offset: f32,
+    pub(crate) mask_color: Vec4,
+}
+
+impl CombinedPassUniform {
+    fn new(
+        flip: Option<FlipUniform>,
+        pixelate: Option<PixelateUniform>,
+        color_adjust: Option<ColorAdjust>,
+        white_balance: Option<WhiteBalanceUniform>,
+        mask: Option<MaskUniform>,
+    ) -> Self {
+        let color_adjust = color_adjust.unwrap_or_default();
+
+        Self {
+            flip: flip.map_or(Vec2::ZERO, |flip| Vec2::new(flip.x, flip.y)),
+            block_size: pixelate.map_or(1.0, |pixelate| pixelate.block_size),
+            brightness: color_adjust.brightness,
+            contrast: color_adjust.contrast,
+            saturation: color_adjust.saturation,
+            hue_shift: color_adjust.hue_shift,
+            gamma: color_adjust.gamma,
+            white_balance: white_balance.map_or(Vec3::ONE, |white_balance| white_balance.balance),
+            mask_strength: mask.map_or(0.0, |mask| mask.strength),
+            mask_fade: mask.map_or(1.0, |mask| mask.fade),
+            mask_nasal_offset: mask.map_or(0.0, |mask| mask.nasal_offset),
+            mask_color: mask.map_or(Vec4::ZERO, |mask| mask.color),
+        }
+    }
+}
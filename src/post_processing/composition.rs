@@ -0,0 +1,117 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{
+        BindGroupLayout, BindGroupLayoutEntry, BlendComponent, BlendFactor, BlendOperation,
+        BlendState, CachedRenderPipelineId, PipelineCache, RenderPipelineDescriptor,
+    },
+};
+
+/// How the output of an effect's pass ends up in the image.
+///
+/// Effects normally read the whole image and draw it anew into the other main texture of the
+/// camera, so every pixel is read and written even where the effect changes nothing.
+/// Overlays such as flashes, light leaks or speed lines only add something on top of the image,
+/// so they can instead be composed onto it where it is: the pass keeps (loads) the image,
+/// and the GPU blends the output of the effect onto it. This saves a read of the whole image,
+/// and the effect doesn't need to sample it at all.
+///
+/// Effects composed in place must not read the image: the source texture they see is the
+/// other main texture, which holds an older image. They are always drawn at full resolution,
+/// whatever their [`super::resolution::Resolution`].
+/// [`super::custom::PostProcessingEffect::COMPOSITION`] picks it for custom effects,
+/// and [`super::light_leak::LightLeak`] is screened onto the image this way.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Composition {
+    /// The effect reads the image and draws all of it anew.
+    #[default]
+    Replace,
+
+    /// The output of the effect is added onto the image.
+    Additive,
+
+    /// The output of the effect is screened onto the image, which brightens it like
+    /// [`Composition::Additive`], but never past white.
+    Screen,
+
+    /// The output of the effect is drawn over the image by its alpha.
+    AlphaOver,
+}
+
+impl Composition {
+    /// True if the effect is composed onto the image where it is, instead of drawing it anew.
+    pub fn in_place(self) -> bool {
+        self != Composition::Replace
+    }
+
+    fn blend_state(self) -> Option<BlendState> {
+        let color = |src_factor, dst_factor| BlendComponent {
+            src_factor,
+            dst_factor,
+            operation: BlendOperation::Add,
+        };
+
+        match self {
+            Composition::Replace => None,
+            Composition::Additive => Some(BlendState {
+                color: color(BlendFactor::One, BlendFactor::One),
+                alpha: BlendComponent::OVER,
+            }),
+            // `a + b - a * b`, written as `a + b * (1 - a)`.
+            Composition::Screen => Some(BlendState {
+                color: color(BlendFactor::One, BlendFactor::OneMinusSrc),
+                alpha: BlendComponent::OVER,
+            }),
+            Composition::AlphaOver => Some(BlendState::ALPHA_BLENDING),
+        }
+    }
+}
+
+/// The same pipeline, composing its output onto the image as given.
+pub(crate) fn with_composition(
+    mut descriptor: RenderPipelineDescriptor,
+    composition: Composition,
+) -> RenderPipelineDescriptor {
+    if let Some(fragment) = descriptor.fragment.as_mut() {
+        for target in fragment.targets.iter_mut().flatten() {
+            target.blend = composition.blend_state();
+        }
+    }
+
+    descriptor
+}
+
+/// Like [`super::create_layout_and_pipeline`], for an effect composed onto the image as given.
+pub(crate) fn create_layout_and_pipeline(
+    world: &mut World,
+    label: &str,
+    layout_entries: &[BindGroupLayoutEntry],
+    shader: Handle<Shader>,
+    composition: Composition,
+) -> (BindGroupLayout, CachedRenderPipelineId) {
+    let uniform_layout = super::create_layout(world, label, layout_entries);
+    let shared_layout = &world
+        .resource::<super::PostProcessingSharedLayout>()
+        .shared_layout;
+
+    let descriptor =
+        super::render_pipeline_descriptor(label, shared_layout, &uniform_layout, shader, vec![]);
+    let pipeline_id = world
+        .resource::<PipelineCache>()
+        .queue_render_pipeline(with_composition(descriptor, composition));
+    super::precompile::register_pipeline(world, pipeline_id);
+
+    (uniform_layout, pipeline_id)
+}
+
+/// True if the pipeline composes its output onto the image where it is, see [`Composition`].
+///
+/// Only such pipelines blend their output, so this is known from the pipeline alone.
+pub(crate) fn draws_in_place(descriptor: &RenderPipelineDescriptor) -> bool {
+    descriptor
+        .fragment
+        .as_ref()
+        .and_then(|fragment| fragment.targets.first())
+        .and_then(|target| target.as_ref())
+        .is_some_and(|target| target.blend.is_some())
+}
@@ -0,0 +1,586 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{
+        clear_color::ClearColorConfig,
+        core_2d::{self, Camera2d},
+        core_3d::Camera3d,
+        fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+        tonemapping::{DebandDither, Tonemapping},
+    },
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        query::QueryItem,
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::{CameraUpdateSystem, RenderTarget},
+        render_asset::RenderAssets,
+        render_graph::{NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner},
+        render_resource::{
+            encase, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, Buffer, BufferBindingType, BufferInitDescriptor,
+            BufferUsages, CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d,
+            FilterMode, FragmentState, MultisampleState, Operations, PipelineCache, PrimitiveState,
+            RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Sampler,
+            SamplerBindingType, SamplerDescriptor, ShaderStages, ShaderType,
+            SpecializedRenderPipeline, SpecializedRenderPipelines, TextureFormat,
+            TextureSampleType, TextureViewDimension,
+        },
+        renderer::{RenderContext, RenderDevice},
+        view::{RenderLayers, ViewTarget},
+        Extract, Render, RenderSet,
+    },
+    utils::HashSet,
+};
+
+use super::{blend::BlendMode, graph::node, process_image::output_image};
+
+pub(crate) const COMPOSITE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4447163410319239597);
+
+/// Composites the images of several cameras, bottom to top, e.g. the world, a view model,
+/// a portal and an in-world UI, each with effects of its own.
+///
+/// Without this, cameras sharing a render target draw over each other in the order of
+/// [`Camera::order`], and what ends up in the image depends on their clear colors.
+/// Here the order is spelled out instead: each layer camera renders into an image of its own,
+/// and this camera blends the images together, so effects on a layer camera only apply to
+/// that layer, and effects on this camera apply to the composited image.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{
+/// #     blend::BlendMode,
+/// #     compositor::{Compositor, CompositorLayer},
+/// #     pixelate::Pixelate,
+/// # };
+/// # let mut commands: Commands = panic!();
+/// let world = commands.spawn(Camera3dBundle::default()).id();
+/// let portal = commands
+///     .spawn((Camera3dBundle::default(), Pixelate::default()))
+///     .id();
+///
+/// commands.spawn(Compositor::new(vec![
+///     CompositorLayer::new(world),
+///     CompositorLayer::new(portal).with_blend(BlendMode::Screen, 0.8),
+/// ]));
+/// ```
+///
+/// The entity is given a 2d camera drawing into the primary window, unless it already has one,
+/// e.g. to composite into an image. The layer cameras are kept rendering into images the size
+/// of its target, and before it. Layers other than the first are cleared to transparent,
+/// so only what they render covers the layers below.
+#[derive(Debug, Default, Clone, Component, Reflect)]
+#[reflect(Component, Default, MapEntities)]
+pub struct Compositor {
+    /// The layers, from the bottom up.
+    pub layers: Vec<CompositorLayer>,
+
+    /// The image each layer camera renders into.
+    #[reflect(ignore)]
+    images: Vec<Handle<Image>>,
+}
+
+impl Compositor {
+    /// Composites the given layers, from the bottom up.
+    pub fn new(layers: Vec<CompositorLayer>) -> Self {
+        Self {
+            layers,
+            images: vec![],
+        }
+    }
+
+    /// The image the camera of the layer at the given index renders into, e.g. to show it on its own.
+    ///
+    /// `None` until the camera of the compositor knows the size of its target.
+    pub fn image(&self, index: usize) -> Option<&Handle<Image>> {
+        self.images.get(index)
+    }
+}
+
+impl MapEntities for Compositor {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for layer in &mut self.layers {
+            layer.camera = entity_mapper.get_or_reserve(layer.camera);
+        }
+    }
+}
+
+impl Display for Compositor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Compositor")?;
+        for (index, layer) in self.layers.iter().enumerate() {
+            write!(f, "\n  {index}: {layer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A camera whose image is a layer of a [`Compositor`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompositorLayer {
+    /// The camera rendering the layer.
+    pub camera: Entity,
+
+    /// How the layer is combined with the layers below it.
+    pub blend: BlendMode,
+
+    /// How much of the blended result is used, between `0.0` (the layer is hidden)
+    /// and `1.0`. Multiplied with the alpha of the layer.
+    pub opacity: f32,
+}
+
+impl CompositorLayer {
+    /// The image of the given camera, drawn over the layers below it.
+    pub fn new(camera: Entity) -> Self {
+        Self {
+            camera,
+            blend: BlendMode::Normal,
+            opacity: 1.0,
+        }
+    }
+
+    /// Combine the layer with the layers below it in another way.
+    pub fn with_blend(mut self, blend: BlendMode, opacity: f32) -> Self {
+        self.blend = blend;
+        self.opacity = opacity;
+        self
+    }
+}
+
+impl Default for CompositorLayer {
+    fn default() -> Self {
+        // Scenes set the actual entity when they are spawned.
+        Self::new(Entity::PLACEHOLDER)
+    }
+}
+
+impl Display for CompositorLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?}, {:?}, opacity: {:.2}",
+            self.camera, self.blend, self.opacity
+        )
+    }
+}
+
+/// Gives new [`Compositor`]s their camera, if they have none.
+fn add_cameras(mut commands: Commands, added: Query<Entity, (Added<Compositor>, Without<Camera>)>) {
+    for entity in &added {
+        commands.entity(entity).insert((
+            Camera2dBundle {
+                camera_2d: Camera2d {
+                    clear_color: ClearColorConfig::Custom(Color::BLACK),
+                },
+                // The layer cameras have tonemapped their images already.
+                tonemapping: Tonemapping::None,
+                deband_dither: DebandDither::Disabled,
+                ..default()
+            },
+            // The camera sees nothing of the app, the layers are drawn in its place.
+            RenderLayers::none(),
+        ));
+    }
+}
+
+type LayerCameraItem = (
+    &'static mut Camera,
+    Option<&'static mut Camera2d>,
+    Option<&'static mut Camera3d>,
+);
+
+/// Keeps the layer cameras rendering into images the size of the target of their compositor.
+///
+/// Only changes what differs, so nothing is marked as changed each frame.
+fn sync_compositors(
+    mut compositors: Query<(Entity, &mut Compositor, &mut Camera)>,
+    mut cameras: Query<LayerCameraItem, Without<Compositor>>,
+    mut images: ResMut<Assets<Image>>,
+    mut warned: Local<HashSet<Entity>>,
+) {
+    for (entity, mut compositor, mut compositor_camera) in &mut compositors {
+        let Some(size) = compositor_camera.physical_target_size() else {
+            continue;
+        };
+        let extent = Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        };
+
+        if compositor.images.len() != compositor.layers.len() {
+            let count = compositor.layers.len();
+            let compositor = compositor.bypass_change_detection();
+            compositor.images.truncate(count);
+            while compositor.images.len() < count {
+                compositor.images.push(images.add(output_image(size)));
+            }
+        }
+
+        let mut max_order = None;
+
+        for (index, (layer, handle)) in compositor.layers.iter().zip(&compositor.images).enumerate()
+        {
+            // Getting the image mutably marks it as modified, which uploads it again.
+            let resized = images
+                .get(handle)
+                .is_some_and(|image| image.texture_descriptor.size != extent);
+            if resized {
+                if let Some(image) = images.get_mut(handle) {
+                    image.resize(extent);
+                }
+            }
+
+            let Ok((mut camera, camera_2d, camera_3d)) = cameras.get_mut(layer.camera) else {
+                if warned.insert(layer.camera) {
+                    warn!(
+                        "Layer {index} of the compositor {entity:?} needs {:?} to be a camera, other than a compositor",
+                        layer.camera
+                    );
+                }
+                continue;
+            };
+
+            if !matches!(&camera.target, RenderTarget::Image(image) if image == handle) {
+                camera.target = RenderTarget::Image(handle.clone());
+            }
+            max_order = max_order.max(Some(camera.order));
+
+            // Only what the layer renders covers the layers below it.
+            if index == 0 {
+                continue;
+            }
+            if let Some(mut camera_2d) = camera_2d {
+                if !is_transparent(&camera_2d.clear_color) {
+                    camera_2d.clear_color = ClearColorConfig::Custom(Color::NONE);
+                }
+            }
+            if let Some(mut camera_3d) = camera_3d {
+                if !is_transparent(&camera_3d.clear_color) {
+                    camera_3d.clear_color = ClearColorConfig::Custom(Color::NONE);
+                }
+            }
+        }
+
+        if let Some(max_order) = max_order {
+            if compositor_camera.order <= max_order {
+                compositor_camera.order = max_order + 1;
+            }
+        }
+    }
+}
+
+fn is_transparent(clear_color: &ClearColorConfig) -> bool {
+    matches!(clear_color, ClearColorConfig::Custom(color) if *color == Color::NONE)
+}
+
+#[derive(Debug, ShaderType, Clone, Copy)]
+struct CompositeUniform {
+    opacity: f32,
+}
+
+/// A layer of a [`Compositor`] camera, in the render world.
+struct ExtractedLayer {
+    image: Handle<Image>,
+    blend: BlendMode,
+    opacity: f32,
+    pipeline_id: Option<CachedRenderPipelineId>,
+    uniform: Option<Buffer>,
+}
+
+/// The layers of a [`Compositor`] camera, in the render world.
+#[derive(Component)]
+struct ViewComposite {
+    layers: Vec<ExtractedLayer>,
+}
+
+fn extract(
+    mut commands: Commands,
+    compositors: Extract<Query<(Entity, &Camera, &Compositor)>>,
+    cameras: Extract<Query<&Camera>>,
+) {
+    for (entity, camera, compositor) in &compositors {
+        if !camera.is_active {
+            continue;
+        }
+
+        // Inactive cameras would leave their last image in the layer.
+        let layers = compositor
+            .layers
+            .iter()
+            .zip(&compositor.images)
+            .filter(|(layer, _)| {
+                cameras
+                    .get(layer.camera)
+                    .is_ok_and(|camera| camera.is_active)
+            })
+            .map(|(layer, image)| ExtractedLayer {
+                image: image.clone(),
+                blend: layer.blend,
+                opacity: layer.opacity,
+                pipeline_id: None,
+                uniform: None,
+            })
+            .collect();
+
+        commands
+            .get_or_spawn(entity)
+            .insert(ViewComposite { layers });
+    }
+}
+
+#[derive(Resource)]
+struct CompositeData {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for CompositeData {
+    fn from_world(world: &mut World) -> Self {
+        let texture = BindingType::Texture {
+            sample_type: TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        };
+
+        let layout = super::create_layout(
+            world,
+            "Composite",
+            &[
+                // The layers composited so far.
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: texture,
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // The layer drawn over them.
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: texture,
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(CompositeUniform::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        );
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Composite sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        CompositeData { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for CompositeData {
+    /// How the layer is blended, and the format of the camera it draws into.
+    type Key = (BlendMode, TextureFormat);
+
+    fn specialize(&self, (blend, format): Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("Composite Render Pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+            fragment: Some(FragmentState {
+                shader: COMPOSITE_SHADER_HANDLE.typed(),
+                shader_defs: blend.shader_defs(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+        }
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    pipeline_cache: Res<PipelineCache>,
+    data: Res<CompositeData>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<CompositeData>>,
+    mut views: Query<(&ViewTarget, &mut ViewComposite)>,
+) {
+    let _span = info_span!("vfx::compositor::queue").entered();
+
+    for (view_target, mut composite) in &mut views {
+        for layer in &mut composite.layers {
+            layer.pipeline_id = Some(pipelines.specialize(
+                &pipeline_cache,
+                &data,
+                (layer.blend, view_target.main_texture_format()),
+            ));
+
+            let mut uniform = encase::UniformBuffer::new(vec![]);
+            uniform
+                .write(&CompositeUniform {
+                    opacity: layer.opacity,
+                })
+                .expect("Composite uniform should be writable");
+
+            layer.uniform = Some(
+                render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("Composite Uniform Buffer"),
+                    contents: &uniform.into_inner(),
+                    usage: BufferUsages::UNIFORM,
+                }),
+            );
+        }
+    }
+}
+
+/// Draws the layers of a [`Compositor`] camera into its main texture, before the effects run.
+#[derive(Default)]
+struct CompositeNode;
+
+impl ViewNode for CompositeNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewComposite);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, composite): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let images = world.resource::<RenderAssets<Image>>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let data = world.resource::<CompositeData>();
+
+        for layer in &composite.layers {
+            // Layers are left out until their image and pipeline are ready.
+            let Some(image) = images.get(&layer.image) else {
+                continue;
+            };
+            let Some(pipeline) = layer
+                .pipeline_id
+                .and_then(|id| pipeline_cache.get_render_pipeline(id))
+            else {
+                continue;
+            };
+            let Some(uniform) = &layer.uniform else {
+                continue;
+            };
+
+            let post_process = view_target.post_process_write();
+
+            let bind_group =
+                render_context
+                    .render_device()
+                    .create_bind_group(&BindGroupDescriptor {
+                        label: Some("Composite bind group"),
+                        layout: &data.layout,
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(post_process.source),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(&data.sampler),
+                            },
+                            BindGroupEntry {
+                                binding: 2,
+                                resource: BindingResource::TextureView(&image.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 3,
+                                resource: uniform.as_entire_binding(),
+                            },
+                        ],
+                    });
+
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("Composite"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: post_process.destination,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+            });
+
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
+
+/// Composites the layers of [`Compositor`]s.
+///
+/// Must be added after the post processing nodes are in the graphs.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            COMPOSITE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "composite.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Compositor>()
+            .register_type::<CompositorLayer>()
+            .register_type::<Vec<CompositorLayer>>()
+            .add_systems(Update, add_cameras)
+            .add_systems(PostUpdate, sync_compositors.before(CameraUpdateSystem));
+
+        let render_app = super::render_app(app);
+
+        // The compositor cameras are 2d cameras, so only the 2d graph needs the node.
+        let composite = ViewNodeRunner::new(CompositeNode, &mut render_app.world);
+        let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+        let graph = render_graph
+            .get_sub_graph_mut(core_2d::graph::NAME)
+            .expect("Graph should be available");
+
+        graph.add_node(node::COMPOSITE, composite);
+        graph.add_node_edge(core_2d::graph::node::MAIN_PASS, node::COMPOSITE);
+        graph.add_node_edge(node::COMPOSITE, node::BEFORE_TONEMAPPING);
+
+        render_app
+            .init_resource::<CompositeData>()
+            .init_resource::<SpecializedRenderPipelines<CompositeData>>()
+            .add_systems(ExtractSchedule, extract)
+            .add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
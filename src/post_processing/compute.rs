@@ -0,0 +1,531 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{
+        blit::{BlitPipeline, BlitPipelineKey},
+        prepass::ViewPrepassTextures,
+    },
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::DynamicUniformIndex,
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_phase::RenderPhase,
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+            CachedComputePipelineId, CachedPipelineState, CachedRenderPipelineId,
+            ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, FilterMode,
+            Pipeline, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            RenderPipeline, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+            ShaderType, SpecializedRenderPipelines, StorageTextureAccess, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureView,
+            TextureViewDimension, TextureViewId,
+        },
+        renderer::{RenderAdapter, RenderContext, RenderDevice},
+        texture::{CachedTexture, FallbackImagesDepth, TextureCache},
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::HashMap,
+};
+
+use super::{PostProcessingPhaseItem, UniformBindGroup};
+
+pub(crate) const COMPUTE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4506897541396492159);
+
+/// The format compute variants write their output in, before it is drawn into the camera.
+/// Unlike the sRGB format of cameras without HDR, it can be written as a storage texture.
+const OUTPUT_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The width and height of a workgroup, as in `compute.wgsl`.
+const WORKGROUP_SIZE: u32 = 16;
+
+/// The shared memory a workgroup keeps its tile of the image in, as in `compute.wgsl`.
+const TILE_BYTES: u32 = 44 * 44 * 8;
+
+/// Whether kernel-heavy effects run as compute shaders, where the adapter supports them.
+///
+/// [`super::blur::Blur`], [`super::kuwahara::Kuwahara`] and the bokeh of
+/// [`super::depth_of_field::DepthOfField`] read many pixels around each pixel they draw.
+/// As compute shaders, each workgroup reads its tile of the image into shared memory once,
+/// and its pixels gather from there instead of from the texture. Neighboring pixels read mostly
+/// the same parts of the image, so large kernels save a lot of memory bandwidth.
+///
+/// The compute variants are used when the adapter supports compute shaders with enough shared
+/// memory, which rules out WebGL2. Elsewhere, and while the compute pipelines compile, the effects
+/// are drawn with fragment shaders. Both look the same, so this is only worth turning off to
+/// compare the two, or to work around a driver:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::compute::ComputePath;
+/// # let mut app = App::new();
+/// app.insert_resource(ComputePath { enabled: false });
+/// ```
+///
+/// Effects drawn at a lower [`super::resolution::Resolution`] always use fragment shaders.
+#[derive(Debug, Clone, Copy, Resource, ExtractResource)]
+pub struct ComputePath {
+    /// Whether the compute variants are used where supported.
+    pub enabled: bool,
+}
+
+impl Default for ComputePath {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// The bind group of an effect and its dynamic offset for a view, for the compute variant.
+type EffectBindGroup = for<'w> fn(&'w World, Entity) -> Option<(&'w BindGroup, u32)>;
+
+/// The compute variant of the render pipeline of an effect.
+struct ComputeVariant {
+    pipeline_id: CachedComputePipelineId,
+    bind_group: EffectBindGroup,
+}
+
+/// The compute variants of effects, in the render world.
+#[derive(Resource)]
+pub(crate) struct ComputeData {
+    /// The bindings of the image, the depth prepass and the output.
+    /// `None` if the adapter doesn't support compute variants.
+    layout: Option<BindGroupLayout>,
+    sampler: Sampler,
+
+    /// By the render pipeline they replace.
+    variants: HashMap<CachedRenderPipelineId, ComputeVariant>,
+}
+
+impl FromWorld for ComputeData {
+    fn from_world(world: &mut World) -> Self {
+        let adapter = world.resource::<RenderAdapter>();
+        let limits = world.resource::<RenderDevice>().limits();
+
+        // WebGL2 has no compute shaders, and reports zero for all of these.
+        let supported = limits.max_compute_workgroup_size_x >= WORKGROUP_SIZE
+            && limits.max_compute_workgroup_size_y >= WORKGROUP_SIZE
+            && limits.max_compute_invocations_per_workgroup >= WORKGROUP_SIZE * WORKGROUP_SIZE
+            && limits.max_compute_workgroup_storage_size >= TILE_BYTES
+            && limits.max_storage_textures_per_shader_stage >= 1
+            && adapter
+                .get_texture_format_features(OUTPUT_FORMAT)
+                .allowed_usages
+                .contains(TextureUsages::STORAGE_BINDING);
+
+        if !supported {
+            info!("Compute shaders are not supported, kernel-heavy effects use fragment shaders");
+        }
+
+        let layout = supported.then(|| {
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("PostProcessing compute bind group layout"),
+                    entries: &[
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Texture {
+                                sample_type: TextureSampleType::Float { filterable: true },
+                                view_dimension: TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::Texture {
+                                sample_type: TextureSampleType::Depth,
+                                view_dimension: TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: ShaderStages::COMPUTE,
+                            ty: BindingType::StorageTexture {
+                                access: StorageTextureAccess::WriteOnly,
+                                format: OUTPUT_FORMAT,
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                    ],
+                })
+        });
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("PostProcessing compute sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        ComputeData {
+            layout,
+            sampler,
+            variants: HashMap::default(),
+        }
+    }
+}
+
+impl ComputeData {
+    /// The stages the effect bind group of an effect with a compute variant is visible to.
+    pub(crate) fn visibility(&self) -> ShaderStages {
+        if self.layout.is_some() {
+            ShaderStages::FRAGMENT | ShaderStages::COMPUTE
+        } else {
+            ShaderStages::FRAGMENT
+        }
+    }
+
+    /// Queues the compute pipeline of an effect, with the entry point `gather`.
+    ///
+    /// The effect bind group is group 1, as for the render pipeline.
+    /// `None` if the adapter doesn't support compute variants.
+    pub(crate) fn queue_pipeline(
+        &self,
+        pipeline_cache: &PipelineCache,
+        label: &str,
+        uniform_layout: &BindGroupLayout,
+        shader: Handle<Shader>,
+    ) -> Option<CachedComputePipelineId> {
+        let layout = self.layout.as_ref()?;
+
+        Some(
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(format!("{label} Compute Pipeline").into()),
+                layout: vec![layout.clone(), uniform_layout.clone()],
+                push_constant_ranges: vec![],
+                shader,
+                shader_defs: vec![],
+                entry_point: "gather".into(),
+            }),
+        )
+    }
+
+    /// Runs the effect drawn by the given render pipeline with the compute pipeline instead,
+    /// where the effect bind group is the [`UniformBindGroup`] of `U`.
+    pub(crate) fn add_variant<U: Component + ShaderType>(
+        &mut self,
+        render_pipeline: CachedRenderPipelineId,
+        compute_pipeline: CachedComputePipelineId,
+    ) {
+        self.variants.insert(
+            render_pipeline,
+            ComputeVariant {
+                pipeline_id: compute_pipeline,
+                bind_group: uniform_bind_group::<U>,
+            },
+        );
+    }
+}
+
+/// Queues the compute variant of an effect drawn by a single render pipeline, if supported.
+pub(crate) fn add_variant<U: Component + ShaderType>(
+    world: &mut World,
+    label: &str,
+    render_pipeline: CachedRenderPipelineId,
+    uniform_layout: &BindGroupLayout,
+    shader: Handle<Shader>,
+) {
+    let compute_pipeline = world.resource::<ComputeData>().queue_pipeline(
+        world.resource::<PipelineCache>(),
+        label,
+        uniform_layout,
+        shader,
+    );
+
+    if let Some(compute_pipeline) = compute_pipeline {
+        world
+            .resource_mut::<ComputeData>()
+            .add_variant::<U>(render_pipeline, compute_pipeline);
+    }
+}
+
+fn uniform_bind_group<U: Component + ShaderType>(
+    world: &World,
+    view: Entity,
+) -> Option<(&BindGroup, u32)> {
+    let bind_group = world
+        .get_resource::<UniformBindGroup<U>>()?
+        .inner
+        .as_ref()?;
+    let index = world.get::<DynamicUniformIndex<U>>(view)?;
+
+    Some((bind_group, index.index()))
+}
+
+/// The compute pipeline, if it has finished compiling. Like [`super::ready_pipeline`].
+fn ready_compute_pipeline(
+    pipeline_cache: &PipelineCache,
+    id: CachedComputePipelineId,
+) -> Option<&ComputePipeline> {
+    match &pipeline_cache.pipelines().nth(id.id())?.state {
+        CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline)) => Some(pipeline),
+        _ => None,
+    }
+}
+
+/// Where the effects of a view running as compute shaders write, in the render world.
+#[derive(Component)]
+pub(crate) struct ViewCompute {
+    output: CachedTexture,
+
+    /// The bindings of the image, the depth prepass and the output,
+    /// for each of the main textures as the source.
+    bind_groups: HashMap<TextureViewId, BindGroup>,
+
+    /// Draws the output into the camera.
+    blit_pipeline_id: CachedRenderPipelineId,
+    blit_bind_group: BindGroup,
+}
+
+impl ViewCompute {
+    /// The dispatch running the effect drawn by the given render pipeline as a compute shader.
+    ///
+    /// `None` if the effect has no compute variant, or it isn't ready yet,
+    /// in which case the effect is drawn with its render pipeline.
+    pub(crate) fn dispatch<'w>(
+        &'w self,
+        world: &'w World,
+        view: Entity,
+        render_pipeline: CachedRenderPipelineId,
+        source: &TextureView,
+    ) -> Option<ComputeDispatch<'w>> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let variant = world
+            .resource::<ComputeData>()
+            .variants
+            .get(&render_pipeline)?;
+
+        let blit_pipeline = pipeline_cache.get_render_pipeline(self.blit_pipeline_id)?;
+
+        Some(ComputeDispatch {
+            view: self,
+            pipeline: ready_compute_pipeline(pipeline_cache, variant.pipeline_id)?,
+            label: pipeline_cache
+                .get_compute_pipeline_descriptor(variant.pipeline_id)
+                .label
+                .as_deref()
+                .unwrap_or("PostProcessing Compute"),
+            blit_pipeline,
+            bind_group: self.bind_groups.get(&source.id())?,
+            effect_bind_group: (variant.bind_group)(world, view)?,
+        })
+    }
+}
+
+/// An effect ready to run as a compute shader, see [`ViewCompute::dispatch`].
+pub(crate) struct ComputeDispatch<'w> {
+    view: &'w ViewCompute,
+    pipeline: &'w ComputePipeline,
+    label: &'w str,
+    blit_pipeline: &'w RenderPipeline,
+    bind_group: &'w BindGroup,
+    effect_bind_group: (&'w BindGroup, u32),
+}
+
+impl ComputeDispatch<'_> {
+    /// Runs the effect over the whole image, and draws its output into the destination.
+    pub(crate) fn run(
+        &self,
+        render_context: &mut RenderContext,
+        camera: &ExtractedCamera,
+        destination: &TextureView,
+    ) {
+        let size = self.view.output.texture.size();
+
+        {
+            let mut compute_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some(self.label),
+                    });
+
+            let (effect_bind_group, offset) = self.effect_bind_group;
+            compute_pass.set_pipeline(self.pipeline);
+            compute_pass.set_bind_group(0, self.bind_group, &[]);
+            compute_pass.set_bind_group(1, effect_bind_group, &[offset]);
+            compute_pass.dispatch_workgroups(
+                size.width.div_ceil(WORKGROUP_SIZE),
+                size.height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("PostProcessing compute output"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: destination,
+                resolve_target: None,
+                ops: super::viewport::operations(camera),
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        super::viewport::clip(&mut render_pass, camera, 1);
+
+        render_pass.set_render_pipeline(self.blit_pipeline);
+        render_pass.set_bind_group(0, &self.view.blit_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<ComputeData>,
+    compute_path: Option<Res<ComputePath>>,
+    pipeline_cache: Res<PipelineCache>,
+    blit: Res<BlitPipeline>,
+    mut blit_pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    mut texture_cache: ResMut<TextureCache>,
+    mut fallback_depth: FallbackImagesDepth,
+    views: Query<(
+        Entity,
+        &ViewTarget,
+        &RenderPhase<PostProcessingPhaseItem>,
+        Option<&ViewPrepassTextures>,
+    )>,
+) {
+    let _span = info_span!("vfx::compute::queue").entered();
+
+    let Some(layout) = data.layout.as_ref() else {
+        return;
+    };
+
+    if !compute_path.is_some_and(|path| path.enabled) {
+        return;
+    }
+
+    for (entity, view_target, phase, prepass) in &views {
+        if !phase
+            .items
+            .iter()
+            .any(|item| data.variants.contains_key(&item.pipeline_id))
+        {
+            continue;
+        }
+
+        let output = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("PostProcessing compute output"),
+                size: view_target.main_texture().size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: OUTPUT_FORMAT,
+                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        let depth_view = super::prepass_depth_view(prepass, &mut fallback_depth);
+
+        let bind_groups = [
+            view_target.main_texture_view(),
+            view_target.main_texture_other_view(),
+        ]
+        .into_iter()
+        .map(|source| {
+            let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("PostProcessing compute bind group"),
+                layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&data.sampler),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: BindingResource::TextureView(&depth_view),
+                    },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: BindingResource::TextureView(&output.default_view),
+                    },
+                ],
+            });
+
+            (source.id(), bind_group)
+        })
+        .collect();
+
+        let blit_pipeline_id = blit_pipelines.specialize(
+            &pipeline_cache,
+            &blit,
+            BlitPipelineKey {
+                texture_format: view_target.main_texture_format(),
+                blend_state: None,
+                samples: 1,
+            },
+        );
+
+        let blit_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("PostProcessing compute output bind group"),
+            layout: &blit.texture_bind_group,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&output.default_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&blit.sampler),
+                },
+            ],
+        });
+
+        commands.entity(entity).insert(ViewCompute {
+            output,
+            bind_groups,
+            blit_pipeline_id,
+            blit_bind_group,
+        });
+    }
+}
+
+/// Runs kernel-heavy effects as compute shaders where supported, see [`ComputePath`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            COMPUTE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "compute.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.init_resource::<ComputePath>()
+            .add_plugins(ExtractResourcePlugin::<ComputePath>::default());
+
+        super::render_app(app)
+            .init_resource::<ComputeData>()
+            .add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
@@ -0,0 +1,377 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{ComponentUniforms, UniformComponentPlugin},
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            encase::internal::WriteInto, BindGroupLayout, CachedRenderPipelineId, PipelineCache,
+            ShaderDefVal, ShaderRef, ShaderType,
+        },
+        renderer::RenderDevice,
+        texture::{FallbackImage, FallbackImageZero},
+        Extract, Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    composition::{self, Composition},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, PostProcessingSharedLayout,
+    ViewUniformBindGroup,
+};
+
+pub(crate) const EFFECT_INTERFACE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8316200528745392717);
+
+/// The version of the shader interface of [`PostProcessingEffect`]s: the bindings they get
+/// and the input of their fragment shader.
+///
+/// It only changes when the interface changes.
+pub const SHADER_INTERFACE_VERSION: u32 = 1;
+
+/// The oldest [`PostProcessingEffect::SHADER_INTERFACE`] which is still supported.
+pub const MIN_SHADER_INTERFACE_VERSION: u32 = 1;
+
+/// A post processing effect defined outside of this crate, made up of a WGSL fragment shader
+/// and a component which is passed to the shader as a uniform.
+///
+/// Add a [`CustomPostProcessingPlugin`] for the effect, and it works like the effects
+/// of this crate: it is applied to cameras with the component, and supports [`Order`],
+/// [`Blend`] and [`super::intensity::Intensity`].
+/// It has no [`super::bypass::EffectCategory`], so it is only bypassed along with all effects,
+/// and it has no cost, so an [`super::budget::EffectBudget`] never sheds it.
+/// Effects meant to be shipped in crates of their own can declare these, and show up in tools
+/// and presets, by implementing [`super::external::EffectPlugin`] as well.
+///
+/// ```no_run
+/// # use bevy::{prelude::*, render::render_resource::{ShaderRef, ShaderType}};
+/// # use bevy_vfx_bag::{post_processing::custom::{CustomPostProcessingPlugin, PostProcessingEffect}, BevyVfxBagPlugin};
+/// #[derive(Debug, Clone, Component, ShaderType)]
+/// struct Sepia {
+///     strength: f32,
+/// }
+///
+/// impl PostProcessingEffect for Sepia {
+///     const SHADER_INTERFACE: u32 = 1;
+///
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/sepia.wgsl".into()
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         BevyVfxBagPlugin::default(),
+///         CustomPostProcessingPlugin::<Sepia>::default(),
+///     ))
+///     .run();
+/// ```
+///
+/// The shader has an entry point called `fragment` which takes the output of Bevy's
+/// fullscreen vertex shader, and gets the same bindings as the effects of this crate:
+///
+/// ```wgsl
+/// #import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+///
+/// // The image so far, a linear sampler for it, and the time of the `VfxTime` clock.
+/// #import bevy_vfx_bag::effect source, source_sampler, globals
+///
+/// // The effect component.
+/// struct Sepia {
+///     strength: f32,
+/// };
+/// @group(1) @binding(0)
+/// var<uniform> sepia: Sepia;
+///
+/// @fragment
+/// fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+///     let color = textureSample(source, source_sampler, in.uv);
+///     let gray = dot(color.rgb, vec3<f32>(0.299, 0.587, 0.114));
+///     let sepia_color = gray * vec3<f32>(1.2, 1.0, 0.8);
+///     return vec4<f32>(mix(color.rgb, sepia_color, sepia.strength), color.a);
+/// }
+/// ```
+///
+/// The shared group also has the depth prepass (`depth_prepass`), the normal prepass
+/// (`normal_prepass`), and the eye (`eye`, see [`super::stereo`]).
+///
+/// # Shader interface
+///
+/// The shared bindings, the group of the component and the input of the fragment shader make up
+/// the shader interface, which is versioned by [`SHADER_INTERFACE_VERSION`].
+/// Effects state the version they were written against in [`PostProcessingEffect::SHADER_INTERFACE`],
+/// and an effect written against a version this release doesn't support, i.e. one outside of
+/// [`MIN_SHADER_INTERFACE_VERSION`] to [`SHADER_INTERFACE_VERSION`], fails to compile instead of
+/// failing to render at runtime:
+///
+/// ```compile_fail
+/// # use bevy::{prelude::*, render::render_resource::{ShaderRef, ShaderType}};
+/// # use bevy_vfx_bag::post_processing::custom::{CustomPostProcessingPlugin, PostProcessingEffect};
+/// #[derive(Debug, Clone, Component, ShaderType)]
+/// struct Sepia {
+///     strength: f32,
+/// }
+///
+/// impl PostProcessingEffect for Sepia {
+///     const SHADER_INTERFACE: u32 = 0;
+///
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/sepia.wgsl".into()
+///     }
+/// }
+///
+/// App::new().add_plugins(CustomPostProcessingPlugin::<Sepia>::default());
+/// ```
+///
+/// The shared bindings are declared in the `bevy_vfx_bag::effect` shader module, so effects import
+/// them rather than declaring them. Effects also get their version as the `VFX_SHADER_INTERFACE`
+/// shader def.
+///
+/// # Images
+///
+/// Effects which need images, such as a dirt mask, set [`PostProcessingEffect::IMAGES`]
+/// and return the handles from [`PostProcessingEffect::image`].
+/// The handles are extracted with the component, and each image gets a texture and a sampler
+/// binding after the uniform, so the first image is at bindings 1 and 2 of group 1:
+///
+/// ```wgsl
+/// @group(1) @binding(1)
+/// var dirt: texture_2d<f32>;
+/// @group(1) @binding(2)
+/// var dirt_sampler: sampler;
+/// ```
+///
+/// Images without a handle are bound as plain white, so e.g. a missing mask which is multiplied in
+/// changes nothing. Images which are still loading are bound as transparent black.
+///
+/// # Overlays
+///
+/// Effects which only add something on top of the image, such as a flash, can set
+/// [`PostProcessingEffect::COMPOSITION`] to have their output composed onto the image,
+/// instead of sampling `source` and drawing the whole image anew. See [`Composition`].
+pub trait PostProcessingEffect: Component + ShaderType + WriteInto + Clone {
+    /// The [`SHADER_INTERFACE_VERSION`] the shader of the effect is written against.
+    const SHADER_INTERFACE: u32;
+
+    /// How many images the effect binds.
+    const IMAGES: usize = 0;
+
+    /// How the output of the effect ends up in the image.
+    const COMPOSITION: Composition = Composition::Replace;
+
+    /// The fragment shader of the effect, as a path to a `.wgsl` asset or a handle.
+    fn fragment_shader() -> ShaderRef;
+
+    /// The image to bind for the given index, which is less than [`PostProcessingEffect::IMAGES`].
+    fn image(&self, _index: usize) -> Option<&Handle<Image>> {
+        None
+    }
+}
+
+/// The render data of a [`PostProcessingEffect`].
+#[derive(Resource)]
+struct CustomEffectData<T> {
+    pipeline_id: CachedRenderPipelineId,
+    layout: BindGroupLayout,
+    marker: PhantomData<T>,
+}
+
+impl<T: PostProcessingEffect> FromWorld for CustomEffectData<T> {
+    fn from_world(world: &mut World) -> Self {
+        let shader = match T::fragment_shader() {
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => world.resource::<AssetServer>().load(path),
+            ShaderRef::Default => panic!(
+                "Post processing effect `{}` should have a fragment shader",
+                label::<T>()
+            ),
+        };
+
+        let layout = super::create_layout(
+            world,
+            label::<T>(),
+            &super::uniform_and_images_layout_entries(T::min_size(), T::IMAGES),
+        );
+        let descriptor = super::render_pipeline_descriptor(
+            label::<T>(),
+            &world.resource::<PostProcessingSharedLayout>().shared_layout,
+            &layout,
+            shader,
+            vec![ShaderDefVal::UInt(
+                "VFX_SHADER_INTERFACE".into(),
+                T::SHADER_INTERFACE,
+            )],
+        );
+        let pipeline_id = world
+            .resource::<PipelineCache>()
+            .queue_render_pipeline(composition::with_composition(descriptor, T::COMPOSITION));
+        super::precompile::register_pipeline(world, pipeline_id);
+
+        Self {
+            pipeline_id,
+            layout,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The name of the effect, e.g. `"Sepia"`, used to label its render resources.
+fn label<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Adds a [`PostProcessingEffect`] defined outside of this crate.
+///
+/// Needs the [`crate::BevyVfxBagPlugin`] as well.
+pub struct CustomPostProcessingPlugin<T>(PhantomData<T>);
+
+impl<T> Default for CustomPostProcessingPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Fails to compile for effects written against a shader interface which isn't supported.
+struct ShaderInterfaceCheck<T>(PhantomData<T>);
+
+impl<T: PostProcessingEffect> ShaderInterfaceCheck<T> {
+    const SUPPORTED: () = assert!(
+        T::SHADER_INTERFACE >= MIN_SHADER_INTERFACE_VERSION
+            && T::SHADER_INTERFACE <= SHADER_INTERFACE_VERSION,
+        "The post processing effect is written against an unsupported shader interface version, see `SHADER_INTERFACE_VERSION`"
+    );
+}
+
+impl<T: PostProcessingEffect> bevy::prelude::Plugin for CustomPostProcessingPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let () = ShaderInterfaceCheck::<T>::SUPPORTED;
+
+        // This puts the uniform into the render world.
+        app.add_plugins(UniformComponentPlugin::<T>::default());
+
+        super::render_app(app).add_systems(
+            ExtractSchedule,
+            (
+                extract::<T>,
+                super::extract_post_processing_camera_phases::<T>,
+            ),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        // The render resources need the render device, so they are created here like the effects
+        // of this crate. These are shared with them, and whichever plugin finishes first creates them.
+        super::render_app(app)
+            .init_resource::<DrawFunctions<PostProcessingPhaseItem>>()
+            .init_resource::<PostProcessingSharedLayout>()
+            .init_resource::<CustomEffectData<T>>()
+            .add_systems(Render, prepare::<T>.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue::<T>.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<T>>();
+    }
+}
+
+fn extract<T: PostProcessingEffect>(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera, &T)>>,
+) {
+    for (entity, camera, effect) in &cameras {
+        if camera.is_active {
+            commands.get_or_spawn(entity).insert(effect.clone());
+        }
+    }
+}
+
+fn prepare<T: PostProcessingEffect>(
+    data: Res<CustomEffectData<T>>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<T>,
+        &Blend<T>,
+        &Resolution<T>,
+        &SourceSampler<T>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::custom::prepare", effect = label::<T>()).entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<T>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue<T: PostProcessingEffect>(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<CustomEffectData<T>>,
+    uniforms: Res<ComponentUniforms<T>>,
+    images: Res<RenderAssets<Image>>,
+    fallback: Res<FallbackImage>,
+    fallback_zero: Res<FallbackImageZero>,
+    views: Query<(Entity, &T)>,
+) {
+    let _span = info_span!("vfx::custom::queue", effect = label::<T>()).entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, effect) in &views {
+        let images: Vec<_> = (0..T::IMAGES)
+            .map(|index| {
+                super::optional_image(&images, effect.image(index), &fallback, &fallback_zero)
+            })
+            .collect();
+
+        let bind_group = super::uniform_and_images_bind_group(
+            &render_device,
+            &format!("{} Uniform Bind Group", label::<T>()),
+            &data.layout,
+            uniforms.clone(),
+            &images,
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<T>::new(bind_group));
+    }
+}
+
+/// Loads the `bevy_vfx_bag::effect` shader module, see [`SHADER_INTERFACE_VERSION`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            EFFECT_INTERFACE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "effect.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+    }
+}
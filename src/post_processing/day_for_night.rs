@@ -0,0 +1,309 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::prepass::{DepthPrepass, NormalPrepass},
+    ecs::query::{Has, QueryItem},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingEffect, Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const DAY_FOR_NIGHT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8137564267047520371);
+
+#[derive(Resource)]
+pub(crate) struct DayForNightData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for DayForNightData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Day For Night",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(DayForNightUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            DAY_FOR_NIGHT_SHADER_HANDLE.typed(),
+        );
+
+        DayForNightData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DAY_FOR_NIGHT_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "day_for_night.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<DayForNight>();
+        super::register_effect_types::<DayForNight>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<DayForNight>::default(),
+            UniformComponentPlugin::<DayForNightUniform>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            super::prepass::configure_prepasses::<DayForNight>,
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<DayForNight>,
+            )
+            .init_resource::<DayForNightData>()
+            .init_resource::<UniformBindGroup<DayForNightUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<DayForNightUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<DayForNightData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<DayForNight>,
+        &Blend<DayForNight>,
+        &Resolution<DayForNight>,
+        &SourceSampler<DayForNight>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::day_for_night::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<DayForNightUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<DayForNightData>,
+    mut bind_group: ResMut<UniformBindGroup<DayForNightUniform>>,
+    uniforms: Res<ComponentUniforms<DayForNightUniform>>,
+    views: Query<Entity, With<DayForNightUniform>>,
+) {
+    let _span = info_span!("vfx::day_for_night::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Day For Night Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Turns a scene lit for the day into one which reads as night, the way films shot
+/// "day for night" do: darker, bluer, less saturated and with the highlights held down.
+///
+/// The image is darkened by scaling it rather than by subtracting from it, which keeps the
+/// ratios between light and dark. Crushing the highlights flattens what is left, which
+/// [`DayForNight::contrast`] wins back, so shapes still read in the dark.
+///
+/// Surfaces facing [`DayForNight::moon_direction`] get a rim of moonlight along their silhouettes.
+/// The rim needs the world normals of a [`NormalPrepass`] and the depth of a [`DepthPrepass`],
+/// with `Msaa::Off` as for other effects reading the prepass. Without them, it is left out.
+/// Both prepasses are added along with the effect, see [`super::prepass::AutoPrepass`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct DayForNight {
+    /// How many stops the image is darkened by. Negative values darken it.
+    pub exposure: f32,
+
+    /// How much the highlights are held down, from `0.0` (not at all) to `1.0`.
+    /// Keeps a bright daytime sky from giving the trick away.
+    pub highlight_crush: f32,
+
+    /// How far light and dark parts are pushed apart, around the middle of the darkened image.
+    /// `1.0` leaves the contrast as the darkening left it.
+    pub contrast: f32,
+
+    /// How much color is taken out of the image, from `0.0` (none) to `1.0` (black and white).
+    pub desaturation: f32,
+
+    /// How far the image is shifted towards [`DayForNight::moonlight`], from `0.0` (not at all)
+    /// to `1.0`, as eyes see blue more than red in the dark.
+    pub blue_shift: f32,
+
+    /// The color of the moonlight, for the blue shift and the rim.
+    /// The alpha channel scales the rim.
+    pub moonlight: Color,
+
+    /// The direction towards the moon, in world space. Need not be normalized.
+    pub moon_direction: Vec3,
+
+    /// How bright the rim of moonlight is. `0.0` turns it off.
+    pub rim: f32,
+
+    /// How tight the rim hugs the silhouettes. Higher values give a thinner rim.
+    pub rim_power: f32,
+}
+
+impl Default for DayForNight {
+    fn default() -> Self {
+        Self {
+            exposure: -1.5,
+            highlight_crush: 0.6,
+            contrast: 1.2,
+            desaturation: 0.6,
+            blue_shift: 0.8,
+            moonlight: Color::rgb(0.55, 0.7, 1.0),
+            moon_direction: Vec3::new(-0.4, 0.6, -0.7),
+            rim: 0.5,
+            rim_power: 3.0,
+        }
+    }
+}
+
+impl Display for DayForNight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day For Night exposure: {:.2}, highlight crush: {:.2}, contrast: {:.2}, desaturation: {:.2}, blue shift: {:.2}, rim: {:.2}, rim power: {:.1}",
+            self.exposure,
+            self.highlight_crush,
+            self.contrast,
+            self.desaturation,
+            self.blue_shift,
+            self.rim,
+            self.rim_power
+        )
+    }
+}
+
+impl EffectPrepasses for DayForNight {
+    const WITHOUT: &'static str = "the moonlight rim is left out";
+
+    fn prepasses(&self) -> Prepasses {
+        if self.rim > 0.0 {
+            Prepasses::DEPTH_AND_NORMAL
+        } else {
+            Prepasses::NONE
+        }
+    }
+}
+
+#[doc(hidden)]
+/// [`DayForNight`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct DayForNightUniform {
+    pub(crate) moonlight: Vec4,
+    /// Towards the moon, normalized.
+    pub(crate) moon_direction: Vec3,
+    pub(crate) exposure: f32,
+    /// The way the camera looks, for telling which surfaces are seen edge on.
+    pub(crate) view_forward: Vec3,
+    pub(crate) highlight_crush: f32,
+    pub(crate) contrast: f32,
+    pub(crate) desaturation: f32,
+    pub(crate) blue_shift: f32,
+    /// Zero without a prepass to draw the rim with.
+    pub(crate) rim: f32,
+    pub(crate) rim_power: f32,
+}
+
+impl ExtractComponent for DayForNight {
+    type Query = (
+        &'static Self,
+        &'static Camera,
+        &'static GlobalTransform,
+        Has<DepthPrepass>,
+        Has<NormalPrepass>,
+    );
+    type Filter = ();
+    type Out = DayForNightUniform;
+
+    fn extract_component(
+        (settings, camera, transform, has_depth, has_normals): QueryItem<'_, Self::Query>,
+    ) -> Option<Self::Out> {
+        let _span = info_span!("vfx::day_for_night::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(DayForNightUniform {
+            moonlight: Vec4::from(settings.moonlight.as_linear_rgba_f32()),
+            moon_direction: settings.moon_direction.normalize_or_zero(),
+            exposure: settings.exposure,
+            view_forward: transform.forward(),
+            highlight_crush: settings.highlight_crush,
+            contrast: settings.contrast,
+            desaturation: settings.desaturation,
+            blue_shift: settings.blue_shift,
+            rim: if has_depth && has_normals {
+                settings.rim
+            } else {
+                0.0
+            },
+            rim_power: settings.rim_power,
+        })
+    }
+}
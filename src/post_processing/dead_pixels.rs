@@ -0,0 +1,357 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const DEAD_PIXELS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3478006129561240187);
+
+#[derive(Resource)]
+pub(crate) struct DeadPixelsData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for DeadPixelsData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Dead Pixels",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(DeadPixelsUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            DEAD_PIXELS_SHADER_HANDLE.typed(),
+        );
+
+        DeadPixelsData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DEAD_PIXELS_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "dead_pixels.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<DeadPixel>()
+            .register_type::<Vec<DeadPixel>>()
+            .register_type::<DeadPixels>();
+        super::register_effect_types::<DeadPixels>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<DeadPixels>::default(),
+            UniformComponentPlugin::<DeadPixelsUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<DeadPixels>,
+            )
+            .init_resource::<DeadPixelsData>()
+            .init_resource::<UniformBindGroup<DeadPixelsUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<DeadPixelsUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<DeadPixelsData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<DeadPixels>,
+        &Blend<DeadPixels>,
+        &Resolution<DeadPixels>,
+        &SourceSampler<DeadPixels>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::dead_pixels::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<DeadPixelsUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<DeadPixelsData>,
+    mut bind_group: ResMut<UniformBindGroup<DeadPixelsUniform>>,
+    uniforms: Res<ComponentUniforms<DeadPixelsUniform>>,
+    views: Query<Entity, With<DeadPixelsUniform>>,
+) {
+    let _span = info_span!("vfx::dead_pixels::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Dead Pixels Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// How many pixels [`DeadPixels`] draws at most. Pixels past this are ignored.
+pub const MAX_DEAD_PIXELS: usize = 64;
+
+/// How many scratches [`DeadPixels`] draws at most.
+pub const MAX_SCRATCHES: usize = 8;
+
+/// A pixel of a [`DeadPixels`] effect.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeadPixel {
+    /// Where the pixel is, in UV coordinates where `(0, 0)` is the top left of the image.
+    pub position: Vec2,
+
+    /// The color the pixel is stuck at. Black for a dead pixel.
+    pub color: Color,
+}
+
+impl DeadPixel {
+    /// A dead (black) pixel at the given position, in UV coordinates.
+    pub fn dead(position: Vec2) -> Self {
+        Self {
+            position,
+            color: Color::BLACK,
+        }
+    }
+
+    /// A pixel stuck at the given color, at the given position in UV coordinates.
+    pub fn stuck(position: Vec2, color: Color) -> Self {
+        Self { position, color }
+    }
+}
+
+/// Dead and stuck pixels, and small scratches, as on a damaged display.
+///
+/// Useful for damaged robot vision and horror beats.
+/// The pixels are placed on the pixel grid of the display, so they stay put as the view moves,
+/// and stay the same size at any resolution.
+///
+/// Random pixels are placed according to [`DeadPixels::count`] and [`DeadPixels::seed`],
+/// and specific ones can be given in [`DeadPixels::pixels`].
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct DeadPixels {
+    /// How many random pixels are dead or stuck.
+    pub count: u32,
+
+    /// Different seeds place the random pixels and scratches differently.
+    pub seed: u32,
+
+    /// Pixels to place in addition to the random ones.
+    ///
+    /// Together with the random pixels, at most [`MAX_DEAD_PIXELS`] are drawn.
+    pub pixels: Vec<DeadPixel>,
+
+    /// How large each dead pixel is, in pixels of the display.
+    pub size: f32,
+
+    /// How many random scratches there are, at most [`MAX_SCRATCHES`].
+    pub scratches: u32,
+}
+
+impl DeadPixels {
+    /// Only the given pixels, without random ones or scratches.
+    pub fn new(pixels: impl IntoIterator<Item = DeadPixel>) -> Self {
+        Self {
+            count: 0,
+            pixels: pixels.into_iter().collect(),
+            scratches: 0,
+            ..default()
+        }
+    }
+}
+
+impl Default for DeadPixels {
+    fn default() -> Self {
+        Self {
+            count: 12,
+            seed: 0,
+            pixels: vec![],
+            size: 2.0,
+            scratches: 2,
+        }
+    }
+}
+
+impl Display for DeadPixels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Dead Pixels count: {}, seed: {}, pixels: {}, size: {:.1}, scratches: {}",
+            self.count,
+            self.seed,
+            self.pixels.len(),
+            self.size,
+            self.scratches
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`DeadPixels`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct DeadPixelsUniform {
+    /// The positions of the pixels in `xy`.
+    pub(crate) positions: [Vec4; MAX_DEAD_PIXELS],
+    pub(crate) colors: [Vec4; MAX_DEAD_PIXELS],
+    /// The start of each scratch in `xy`, and the end in `zw`.
+    pub(crate) scratches: [Vec4; MAX_SCRATCHES],
+    pub(crate) pixel_count: u32,
+    pub(crate) scratch_count: u32,
+    pub(crate) size: f32,
+}
+
+/// A small hash, so the same seed places pixels the same way everywhere.
+fn hash(seed: u32, i: u32) -> u32 {
+    let mut x = seed
+        .wrapping_mul(0x9E37_79B9)
+        .wrapping_add(i.wrapping_mul(0x85EB_CA6B));
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^ (x >> 16)
+}
+
+/// A random number between `0.0` and `1.0`.
+fn random(seed: u32, i: u32) -> f32 {
+    (hash(seed, i) >> 8) as f32 / (1 << 24) as f32
+}
+
+impl From<&DeadPixels> for DeadPixelsUniform {
+    fn from(dead_pixels: &DeadPixels) -> Self {
+        // Mostly dead pixels, with the occasional stuck one.
+        const STUCK_COLORS: [Color; 4] = [Color::RED, Color::GREEN, Color::BLUE, Color::WHITE];
+
+        let seed = dead_pixels.seed;
+        let random_pixels = (0..dead_pixels.count).map(|i| {
+            let position = Vec2::new(random(seed, i * 4), random(seed, i * 4 + 1));
+            if random(seed, i * 4 + 2) < 0.7 {
+                DeadPixel::dead(position)
+            } else {
+                let color = STUCK_COLORS[hash(seed, i * 4 + 3) as usize % STUCK_COLORS.len()];
+                DeadPixel::stuck(position, color)
+            }
+        });
+
+        let mut positions = [Vec4::ZERO; MAX_DEAD_PIXELS];
+        let mut colors = [Vec4::ZERO; MAX_DEAD_PIXELS];
+        let mut pixel_count = 0;
+        for (i, pixel) in dead_pixels
+            .pixels
+            .iter()
+            .copied()
+            .chain(random_pixels)
+            .take(MAX_DEAD_PIXELS)
+            .enumerate()
+        {
+            positions[i] = pixel.position.extend(0.0).extend(0.0);
+            colors[i] = Vec4::from(pixel.color.as_linear_rgba_f32());
+            pixel_count += 1;
+        }
+
+        // Short scratches at random angles. The hashes are offset from those of the pixels.
+        let mut scratches = [Vec4::ZERO; MAX_SCRATCHES];
+        let scratch_count = (dead_pixels.scratches as usize).min(MAX_SCRATCHES);
+        for (i, scratch) in scratches.iter_mut().take(scratch_count).enumerate() {
+            let i = 0x1000 + i as u32 * 4;
+            let start = Vec2::new(random(seed, i), random(seed, i + 1));
+            let angle = random(seed, i + 2) * std::f32::consts::TAU;
+            let length = 0.05 + 0.15 * random(seed, i + 3);
+            let end = start + Vec2::from_angle(angle) * length;
+            *scratch = Vec4::new(start.x, start.y, end.x, end.y);
+        }
+
+        Self {
+            positions,
+            colors,
+            scratches,
+            pixel_count,
+            scratch_count: scratch_count as u32,
+            size: dead_pixels.size,
+        }
+    }
+}
+
+impl ExtractComponent for DeadPixels {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = DeadPixelsUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::dead_pixels::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(settings.into())
+    }
+}
@@ -0,0 +1,243 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const DEBAND_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4578224812556582620);
+
+#[derive(Resource)]
+pub(crate) struct DebandData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for DebandData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Deband",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(DebandUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            DEBAND_SHADER_HANDLE.typed(),
+        );
+
+        DebandData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DEBAND_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "deband.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Deband>();
+        super::register_effect_types::<Deband>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Deband>::default(),
+            UniformComponentPlugin::<DebandUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Deband>,
+            )
+            .init_resource::<DebandData>()
+            .init_resource::<UniformBindGroup<DebandUniform>>()
+            .add_systems(
+                Render,
+                prepare
+                    .in_set(RenderSet::Prepare)
+                    // Last among the effects drawn last, since anything after it can band again.
+                    .after(super::letterbox::prepare)
+                    .after(super::screen_door_fade::prepare),
+            )
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<DebandUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<DebandData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Deband>,
+        &Blend<Deband>,
+        &Resolution<Deband>,
+        &SourceSampler<Deband>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::deband::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<DebandUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<DebandData>,
+    mut bind_group: ResMut<UniformBindGroup<DebandUniform>>,
+    uniforms: Res<ComponentUniforms<DebandUniform>>,
+    views: Query<Entity, With<DebandUniform>>,
+) {
+    let _span = info_span!("vfx::deband::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Deband Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Hides color banding by adding a little noise before the image is quantized to 8 bits.
+///
+/// Smooth, dark gradients such as those of [`super::masks::Mask`] vignettes, [`super::fog::Fog`]
+/// and [`super::glow::Glow`] show visible steps once they are stored with 256 levels per channel.
+/// The noise is about one level strong and changes every frame, so the steps blend into each
+/// other without the noise itself being noticeable.
+///
+/// A single cheap pass, meant to be on all the time, e.g. via [`QualityBundle`]:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::deband::QualityBundle;
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((Camera3dBundle::default(), QualityBundle::default()));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Deband {
+    /// How strong the noise is, in levels of 8 bit color.
+    /// `1.0` hides the banding of 8 bit color, higher values also hide banding of effects
+    /// working with fewer levels, at the cost of visible noise.
+    pub strength: f32,
+
+    /// If true and the camera has no [`Order`] for this effect, it is drawn after all the other
+    /// effects, so none of them can band again after it.
+    /// Otherwise it is ordered like any other effect, e.g. to deband before a
+    /// [`super::dither::Dither`] reduces the colors on purpose.
+    pub final_pass: bool,
+}
+
+impl Default for Deband {
+    fn default() -> Self {
+        Self {
+            strength: 1.0,
+            final_pass: true,
+        }
+    }
+}
+
+impl Display for Deband {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Deband strength: {:.2}, final pass: {}",
+            self.strength, self.final_pass
+        )
+    }
+}
+
+/// Effects which improve the quality of the image without changing its look,
+/// to add to cameras along with their other effects.
+#[derive(Debug, Default, Clone, Bundle)]
+pub struct QualityBundle {
+    /// See [`Deband`]. On by default.
+    pub deband: Deband,
+}
+
+#[doc(hidden)]
+/// [`Deband`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct DebandUniform {
+    pub(crate) strength: f32,
+}
+
+impl ExtractComponent for Deband {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = DebandUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::deband::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(DebandUniform {
+            strength: settings.strength.max(0.0),
+        })
+    }
+}
@@ -0,0 +1,323 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    core_pipeline::blit::{BlitPipeline, BlitPipelineKey},
+    prelude::*,
+    render::{
+        camera::ExtractedCamera,
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_phase::RenderPhase,
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindingResource, BlendState,
+            CachedRenderPipelineId, Extent3d, LoadOp, Operations, PipelineCache,
+            RenderPassColorAttachment, RenderPassDescriptor, SpecializedRenderPipelines,
+            TextureDescriptor, TextureDimension, TextureUsages, TextureView,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::HashMap,
+};
+
+use super::PostProcessingPhaseItem;
+
+/// A debug mode which shows the image after each effect, tiled in a contact sheet over the view.
+///
+/// The first tile is the image before any effects, followed by one tile per effect in the order
+/// they run, left to right and top to bottom. The last tile is the final image.
+/// This makes it obvious which effect in a long stack produces an artifact.
+///
+/// Insert this as a resource, and toggle [`EffectDebugView::enabled`]:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::debug_view::EffectDebugView;
+/// fn toggle(keyboard_input: Res<Input<KeyCode>>, mut debug_view: ResMut<EffectDebugView>) {
+///     if keyboard_input.just_pressed(KeyCode::F3) {
+///         debug_view.enabled = !debug_view.enabled;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Resource, ExtractResource)]
+pub struct EffectDebugView {
+    /// Whether the contact sheet is shown.
+    pub enabled: bool,
+
+    /// What each tile shows, per camera.
+    tiles: Arc<Mutex<HashMap<Entity, Vec<String>>>>,
+}
+
+impl EffectDebugView {
+    /// The contact sheet, shown on all cameras with effects.
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..default()
+        }
+    }
+
+    /// What each tile on the camera shows, as of the last rendered frame.
+    ///
+    /// The first tile is `"Input"`, the rest are named after the effects, e.g. `"Fog"`.
+    /// Empty if the contact sheet is disabled, or the camera has no effects.
+    pub fn tiles(&self, camera: Entity) -> Vec<String> {
+        self.tiles
+            .lock()
+            .expect("Tiles should not be poisoned")
+            .get(&camera)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// The contact sheet of a view, in the render world.
+#[derive(Component)]
+pub(crate) struct DebugViewSheet {
+    texture: CachedTexture,
+
+    /// Copies an image into a tile.
+    tile_pipeline: CachedRenderPipelineId,
+
+    /// Draws the sheet over the view, leaving unused tiles transparent.
+    overlay_pipeline: CachedRenderPipelineId,
+
+    /// The part of the sheet covered by the camera, in pixels.
+    viewport: UVec4,
+}
+
+fn prepare_debug_view(
+    mut commands: Commands,
+    debug_view: Option<Res<EffectDebugView>>,
+    render_device: Res<RenderDevice>,
+    pipeline_cache: Res<PipelineCache>,
+    blit_pipeline: Res<BlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ViewTarget,
+        &RenderPhase<PostProcessingPhaseItem>,
+    )>,
+) {
+    let Some(debug_view) = debug_view else {
+        return;
+    };
+
+    if !debug_view.enabled {
+        debug_view
+            .tiles
+            .lock()
+            .expect("Tiles should not be poisoned")
+            .clear();
+        return;
+    }
+
+    for (entity, camera, view_target, phase) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        if phase.items.is_empty() {
+            continue;
+        }
+
+        let format = view_target.main_texture_format();
+
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("Effect Debug View Sheet"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        let tile_pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &blit_pipeline,
+            BlitPipelineKey {
+                texture_format: format,
+                blend_state: None,
+                samples: 1,
+            },
+        );
+        let overlay_pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &blit_pipeline,
+            BlitPipelineKey {
+                texture_format: format,
+                blend_state: Some(BlendState::ALPHA_BLENDING),
+                samples: 1,
+            },
+        );
+
+        let viewport = match &camera.viewport {
+            Some(viewport) => UVec4::new(
+                viewport.physical_position.x,
+                viewport.physical_position.y,
+                viewport.physical_size.x,
+                viewport.physical_size.y,
+            ),
+            None => UVec4::new(0, 0, size.x, size.y),
+        };
+
+        commands.entity(entity).insert(DebugViewSheet {
+            texture,
+            tile_pipeline,
+            overlay_pipeline,
+            viewport,
+        });
+    }
+}
+
+/// Draws `source` into tile `index` of `count`, and remembers what it shows.
+pub(crate) fn draw_tile(
+    world: &World,
+    render_context: &mut RenderContext,
+    view_entity: Entity,
+    source: &TextureView,
+    name: &str,
+    index: usize,
+    count: usize,
+) {
+    let Some(sheet) = world.get::<DebugViewSheet>(view_entity) else {
+        return;
+    };
+
+    let pipeline_cache = world.resource::<PipelineCache>();
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(sheet.tile_pipeline) else {
+        return;
+    };
+
+    {
+        let debug_view = world.resource::<EffectDebugView>();
+        let mut tiles = debug_view
+            .tiles
+            .lock()
+            .expect("Tiles should not be poisoned");
+        let tiles = tiles.entry(view_entity).or_default();
+        tiles.truncate(index);
+        tiles.push(name.to_string());
+    }
+
+    let columns = (count as f32).sqrt().ceil() as u32;
+    let rows = (count as u32).div_ceil(columns);
+    let tile_size = UVec2::new(sheet.viewport.z / columns, sheet.viewport.w / rows).max(UVec2::ONE);
+    let tile = UVec2::new(index as u32 % columns, index as u32 / columns);
+    let position = UVec2::new(sheet.viewport.x, sheet.viewport.y) + tile * tile_size;
+
+    let bind_group = blit_bind_group(world, render_context, source);
+
+    let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("Effect Debug View Tile"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &sheet.texture.default_view,
+            resolve_target: None,
+            ops: Operations {
+                // Unused tiles stay transparent, so the overlay doesn't cover the view there.
+                load: if index == 0 {
+                    LoadOp::Clear(Color::NONE.into())
+                } else {
+                    LoadOp::Load
+                },
+                store: true,
+            },
+        })],
+        depth_stencil_attachment: None,
+    });
+
+    render_pass.set_viewport(
+        position.x as f32,
+        position.y as f32,
+        tile_size.x as f32,
+        tile_size.y as f32,
+        0.0,
+        1.0,
+    );
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, &bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+}
+
+/// Draws the contact sheet over the final image of the view.
+pub(crate) fn draw_overlay(
+    world: &World,
+    render_context: &mut RenderContext,
+    view_entity: Entity,
+    view_target: &ViewTarget,
+) {
+    let Some(sheet) = world.get::<DebugViewSheet>(view_entity) else {
+        return;
+    };
+
+    let pipeline_cache = world.resource::<PipelineCache>();
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(sheet.overlay_pipeline) else {
+        return;
+    };
+
+    let bind_group = blit_bind_group(world, render_context, &sheet.texture.default_view);
+
+    let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("Effect Debug View Overlay"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: view_target.main_texture_view(),
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Load,
+                store: true,
+            },
+        })],
+        depth_stencil_attachment: None,
+    });
+
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, &bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+}
+
+fn blit_bind_group(
+    world: &World,
+    render_context: &RenderContext,
+    source: &TextureView,
+) -> BindGroup {
+    let blit_pipeline = world.resource::<BlitPipeline>();
+
+    render_context
+        .render_device()
+        .create_bind_group(&BindGroupDescriptor {
+            label: Some("Effect Debug View Bind Group"),
+            layout: &blit_pipeline.texture_bind_group,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(source),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&blit_pipeline.sampler),
+                },
+            ],
+        })
+}
+
+/// Shows the image after each effect, when enabled via [`EffectDebugView`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractResourcePlugin::<EffectDebugView>::default());
+
+        super::render_app(app).add_systems(Render, prepare_debug_view.in_set(RenderSet::Prepare));
+    }
+}
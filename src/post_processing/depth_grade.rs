@@ -0,0 +1,323 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingEffect, Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const DEPTH_GRADE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 15498663946380901062);
+
+#[derive(Resource)]
+pub(crate) struct DepthGradeData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for DepthGradeData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Depth Grade",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(DepthGradeUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            DEPTH_GRADE_SHADER_HANDLE.typed(),
+        );
+
+        DepthGradeData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DEPTH_GRADE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "depth_grade.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<DepthGrade>();
+        app.register_type::<DepthGradeZone>();
+        super::register_effect_types::<DepthGrade>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<DepthGrade>::default(),
+            UniformComponentPlugin::<DepthGradeUniform>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            super::prepass::configure_prepasses::<DepthGrade>,
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<DepthGrade>,
+            )
+            .init_resource::<DepthGradeData>()
+            .init_resource::<UniformBindGroup<DepthGradeUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<DepthGradeUniform>>(
+            );
+    }
+}
+
+impl EffectPrepasses for DepthGrade {
+    const WITHOUT: &'static str = "the zones will not look right";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+fn prepare(
+    data: Res<DepthGradeData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<DepthGrade>,
+        &Blend<DepthGrade>,
+        &Resolution<DepthGrade>,
+        &SourceSampler<DepthGrade>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::depth_grade::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<DepthGradeUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<DepthGradeData>,
+    mut bind_group: ResMut<UniformBindGroup<DepthGradeUniform>>,
+    uniforms: Res<ComponentUniforms<DepthGradeUniform>>,
+    views: Query<Entity, With<DepthGradeUniform>>,
+) {
+    let _span = info_span!("vfx::depth_grade::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Depth Grade Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// The grading of one zone of a [`DepthGrade`].
+///
+/// The defaults leave the image unchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Default)]
+pub struct DepthGradeZone {
+    /// Multiplies the colors of the zone. White leaves the image unchanged.
+    pub tint: Color,
+
+    /// How colorful the zone is. `1.0` leaves the image unchanged,
+    /// `0.0` makes it black and white.
+    pub saturation: f32,
+
+    /// How far colors are pushed away from middle gray. `1.0` leaves the image unchanged,
+    /// `0.0` makes it all gray.
+    pub contrast: f32,
+}
+
+impl DepthGradeZone {
+    /// A zone which leaves the image unchanged.
+    pub const NEUTRAL: Self = Self {
+        tint: Color::WHITE,
+        saturation: 1.0,
+        contrast: 1.0,
+    };
+}
+
+impl Default for DepthGradeZone {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+/// Grades the near, middle and far parts of the scene separately, e.g. a warm foreground
+/// against a cool, washed out distance.
+///
+/// Each zone has its own [`DepthGradeZone`]. The near zone ends around
+/// [`DepthGrade::near_distance`] and the far zone starts around [`DepthGrade::far_distance`],
+/// with the middle zone in between. The zones fade into each other over
+/// [`DepthGrade::transition`], so there are no hard edges.
+///
+/// The distance is read from the depth prepass, so the camera needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) component, which is added along with the grade
+/// (see [`super::prepass::AutoPrepass`]). MSAA must also be off (`Msaa::Off`),
+/// since a multisampled depth texture can't be read by post processing effects.
+/// The sky, where nothing was rendered, counts as far.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct DepthGrade {
+    /// The grading of what is nearer than [`DepthGrade::near_distance`].
+    pub near: DepthGradeZone,
+
+    /// The grading of what is between the near and far zones.
+    pub mid: DepthGradeZone,
+
+    /// The grading of what is further than [`DepthGrade::far_distance`].
+    pub far: DepthGradeZone,
+
+    /// The distance (in world units) from the camera where the near zone meets the middle zone.
+    pub near_distance: f32,
+
+    /// The distance (in world units) from the camera where the middle zone meets the far zone.
+    pub far_distance: f32,
+
+    /// How far (in world units) the zones fade into each other, centered on where they meet.
+    pub transition: f32,
+}
+
+impl Default for DepthGrade {
+    fn default() -> Self {
+        Self {
+            near: DepthGradeZone::NEUTRAL,
+            mid: DepthGradeZone::NEUTRAL,
+            far: DepthGradeZone::NEUTRAL,
+            near_distance: 8.0,
+            far_distance: 25.0,
+            transition: 4.0,
+        }
+    }
+}
+
+impl Display for DepthGrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Depth Grade near distance: {}, far distance: {}, transition: {}",
+            self.near_distance, self.far_distance, self.transition
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`DepthGradeZone`] as part of a uniform.
+#[derive(Debug, ShaderType, Clone, Copy)]
+pub struct DepthGradeZoneUniform {
+    pub(crate) tint: Vec3,
+    pub(crate) saturation: f32,
+    pub(crate) contrast: f32,
+}
+
+impl From<DepthGradeZone> for DepthGradeZoneUniform {
+    fn from(zone: DepthGradeZone) -> Self {
+        let [r, g, b, _] = zone.tint.as_linear_rgba_f32();
+
+        Self {
+            tint: Vec3::new(r, g, b),
+            saturation: zone.saturation,
+            contrast: zone.contrast,
+        }
+    }
+}
+
+#[doc(hidden)]
+/// [`DepthGrade`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct DepthGradeUniform {
+    pub(crate) near: DepthGradeZoneUniform,
+    pub(crate) mid: DepthGradeZoneUniform,
+    pub(crate) far: DepthGradeZoneUniform,
+    pub(crate) near_distance: f32,
+    pub(crate) far_distance: f32,
+    pub(crate) transition: f32,
+
+    /// Used to turn depth values into view space distances.
+    pub(crate) inverse_projection: Mat4,
+}
+
+impl ExtractComponent for DepthGrade {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = DepthGradeUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::depth_grade::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(DepthGradeUniform {
+            near: settings.near.into(),
+            mid: settings.mid.into(),
+            far: settings.far.into(),
+            near_distance: settings.near_distance,
+            far_distance: settings.far_distance.max(settings.near_distance),
+            transition: settings.transition.max(0.0001),
+            inverse_projection: camera.projection_matrix().inverse(),
+        })
+    }
+}
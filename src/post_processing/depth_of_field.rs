@@ -0,0 +1,349 @@
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedComputePipelineId, PipelineCache,
+            RenderPipelineDescriptor, ShaderDefVal, ShaderType, SpecializedRenderPipeline,
+            SpecializedRenderPipelines,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+use std::fmt::Display;
+
+use crate::post_processing::DrawPostProcessingEffect;
+
+use super::{
+    blend::Blend,
+    compute::ComputeData,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const DEPTH_OF_FIELD_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 12988032347500603891);
+
+pub(crate) const DEPTH_OF_FIELD_COMPUTE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16167324147781089251);
+
+#[derive(Resource)]
+pub(crate) struct DepthOfFieldData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+    /// The compute variant of the gather pass, where supported.
+    pub gather_compute_pipeline_id: Option<CachedComputePipelineId>,
+}
+
+impl FromWorld for DepthOfFieldData {
+    fn from_world(world: &mut World) -> Self {
+        // The compute variant of the gather pass reads the uniform too.
+        let visibility = world.resource::<ComputeData>().visibility();
+
+        let uniform_layout = super::create_layout(
+            world,
+            "Depth of Field",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(DepthOfFieldUniform::min_size()),
+                },
+                visibility,
+                count: None,
+            }],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+
+        let gather_compute_pipeline_id = world.resource::<ComputeData>().queue_pipeline(
+            world.resource::<PipelineCache>(),
+            "Depth of Field",
+            &uniform_layout,
+            DEPTH_OF_FIELD_COMPUTE_SHADER_HANDLE.typed(),
+        );
+
+        DepthOfFieldData {
+            uniform_layout,
+            shared_layout,
+            gather_compute_pipeline_id,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DEPTH_OF_FIELD_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "depth-of-field.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            DEPTH_OF_FIELD_COMPUTE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "depth-of-field_compute.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<DepthOfField>();
+        super::register_effect_types::<DepthOfField>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<DepthOfField>::default(),
+            UniformComponentPlugin::<DepthOfFieldUniform>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            super::prepass::configure_prepasses::<DepthOfField>,
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<DepthOfField>,
+            )
+            .init_resource::<DepthOfFieldData>()
+            .init_resource::<UniformBindGroup<DepthOfFieldUniform>>()
+            .init_resource::<SpecializedRenderPipelines<DepthOfFieldData>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<DepthOfFieldUniform>>();
+    }
+}
+
+/// The passes making up the depth of field effect.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub(crate) enum DepthOfFieldPass {
+    /// Computes how blurry each pixel should be (the circle of confusion),
+    /// and stores it in the alpha channel.
+    CircleOfConfusion,
+
+    /// Gathers samples in a disc around each pixel, using the circle of confusion
+    /// of each sample to decide if it contributes.
+    Gather,
+}
+
+impl From<DepthOfFieldPass> for ShaderDefVal {
+    fn from(pass: DepthOfFieldPass) -> Self {
+        match pass {
+            DepthOfFieldPass::CircleOfConfusion => "CIRCLE_OF_CONFUSION",
+            DepthOfFieldPass::Gather => "GATHER",
+        }
+        .into()
+    }
+}
+
+impl SpecializedRenderPipeline for DepthOfFieldData {
+    type Key = DepthOfFieldPass;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Depth of Field",
+            &self.shared_layout,
+            &self.uniform_layout,
+            DEPTH_OF_FIELD_SHADER_HANDLE.typed(),
+            vec![key.into()],
+        )
+    }
+}
+
+impl EffectPrepasses for DepthOfField {
+    const WITHOUT: &'static str = "the effect will not look right";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+fn prepare(
+    data: Res<DepthOfFieldData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<DepthOfFieldData>>,
+    mut compute: ResMut<ComputeData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<DepthOfField>,
+        &Blend<DepthOfField>,
+        &Resolution<DepthOfField>,
+        &SourceSampler<DepthOfField>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::depth_of_field::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<DepthOfFieldUniform>>();
+
+        // Both passes share the sort key, and are kept in this order when sorted.
+        for pass in [
+            DepthOfFieldPass::CircleOfConfusion,
+            DepthOfFieldPass::Gather,
+        ] {
+            let pipeline_id = pipelines.specialize(&pipeline_cache, &data, pass);
+
+            if let Some(compute_pipeline_id) = data
+                .gather_compute_pipeline_id
+                .filter(|_| pass == DepthOfFieldPass::Gather)
+            {
+                compute.add_variant::<DepthOfFieldUniform>(pipeline_id, compute_pipeline_id);
+            }
+
+            phase.add(PostProcessingPhaseItem {
+                entity,
+                sort_key: (*order).into(),
+                blend: (*blend).into(),
+                resolution: (*resolution).into(),
+                sampler: (*sampler).into(),
+                draw_function,
+                pipeline_id,
+            });
+        }
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<DepthOfFieldData>,
+    mut bind_group: ResMut<UniformBindGroup<DepthOfFieldUniform>>,
+    uniforms: Res<ComponentUniforms<DepthOfFieldUniform>>,
+    views: Query<Entity, With<DepthOfFieldUniform>>,
+) {
+    let _span = info_span!("vfx::depth_of_field::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Depth of Field Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Depth of field, with a gather based bokeh blur.
+///
+/// Things at the focal distance are kept sharp, and the image gets blurrier
+/// further away from it (both in front of and behind the focal distance).
+///
+/// The distance is read from the depth prepass, so the camera needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) component, which is added along with the effect
+/// (see [`super::prepass::AutoPrepass`]). MSAA must also be off (`Msaa::Off`),
+/// since a multisampled depth texture can't be read by post processing effects.
+///
+/// The effect runs in two passes. The first stores the blurriness of each pixel
+/// in the alpha channel, and the second uses that to blur the image.
+/// Where supported, the second runs as a compute shader, see [`super::compute::ComputePath`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct DepthOfField {
+    /// The distance (in world units) from the camera which is in perfect focus.
+    pub focal_distance: f32,
+
+    /// How quickly things go out of focus away from the focal distance.
+    /// Larger values give a shallower depth of field.
+    ///
+    /// `0.0` disables the blur, `1.0` means things twice as far away as the
+    /// focal distance are fully blurred.
+    pub aperture: f32,
+
+    /// The largest blur radius, in pixels.
+    pub max_blur_radius: f32,
+
+    /// How many samples are gathered per pixel.
+    /// More samples give smoother bokeh at a higher cost.
+    ///
+    /// The shader sets a lower bound of 1.
+    pub sample_count: u32,
+}
+
+impl Default for DepthOfField {
+    fn default() -> Self {
+        Self {
+            focal_distance: 10.0,
+            aperture: 0.5,
+            max_blur_radius: 12.0,
+            sample_count: 48,
+        }
+    }
+}
+
+impl Display for DepthOfField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Depth of Field focal distance: {}, aperture: {}, max blur radius: {}, samples: {}",
+            self.focal_distance, self.aperture, self.max_blur_radius, self.sample_count
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`DepthOfField`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct DepthOfFieldUniform {
+    pub(crate) focal_distance: f32,
+    pub(crate) aperture: f32,
+    pub(crate) max_blur_radius: f32,
+    pub(crate) sample_count: u32,
+
+    /// Used to turn depth values into view space distances.
+    pub(crate) inverse_projection: Mat4,
+}
+
+impl ExtractComponent for DepthOfField {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = DepthOfFieldUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::depth_of_field::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(DepthOfFieldUniform {
+            focal_distance: settings.focal_distance,
+            aperture: settings.aperture,
+            max_blur_radius: settings.max_blur_radius,
+            sample_count: settings.sample_count,
+            inverse_projection: camera.projection_matrix().inverse(),
+        })
+    }
+}
@@ -0,0 +1,364 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsStore, RegisterDiagnostic},
+    prelude::*,
+    render::{
+        render_resource::{
+            Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, MapMode, WgpuFeatures,
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        Render, RenderSet,
+    },
+    utils::HashMap,
+};
+
+/// The most effects timed per frame, over all cameras.
+const MAX_SPANS: u32 = 128;
+
+/// The size of the timestamps of all spans, in bytes.
+const TIMESTAMPS_SIZE: u64 = MAX_SPANS as u64 * 2 * wgpu::QUERY_SIZE as u64;
+
+/// How many frames the diagnostics average over.
+const HISTORY_LENGTH: usize = 20;
+
+/// The GPU time of the effects, reported as Bevy diagnostics.
+///
+/// Each effect drawn by a camera reports the milliseconds its passes took on the GPU as
+/// `bevy_vfx_bag/<effect>`, e.g. `bevy_vfx_bag/chromatic_aberration`, summed over the cameras
+/// drawing it. `bevy_vfx_bag/total` is the time of all effects together. They show up in
+/// e.g. [`bevy::diagnostic::LogDiagnosticsPlugin`], and can be read at runtime to pick which
+/// effects to turn off on slow hardware:
+///
+/// ```no_run
+/// # use bevy::{diagnostic::DiagnosticsStore, prelude::*};
+/// # use bevy_vfx_bag::post_processing::{diagnostics::EffectDiagnostics, glow::Glow};
+/// fn drop_glow(
+///     mut commands: Commands,
+///     diagnostics: Res<DiagnosticsStore>,
+///     cameras: Query<Entity, With<Glow>>,
+/// ) {
+///     let glow_ms = diagnostics
+///         .get(EffectDiagnostics::effect("glow"))
+///         .and_then(|glow| glow.smoothed())
+///         .unwrap_or_default();
+///
+///     if glow_ms > 2.0 {
+///         for camera in &cameras {
+///             commands.entity(camera).remove::<Glow>();
+///         }
+///     }
+/// }
+/// ```
+///
+/// The times are measured with timestamp queries, which are not supported everywhere, e.g. not
+/// on WebGL2. Without them, no times are reported. They arrive a frame or two late, once they
+/// have made their way back from the GPU. Effects which aren't drawn report `0.0`.
+///
+/// The effect is named after its render pipeline, so effects of other crates report
+/// under the label they gave it.
+#[derive(Debug)]
+pub struct EffectDiagnostics;
+
+impl EffectDiagnostics {
+    /// The GPU time of all effects, in milliseconds.
+    pub const TOTAL: DiagnosticId =
+        DiagnosticId::from_u128(231476917420635186153520474226839418113);
+
+    /// The id of the diagnostic of an effect, given its name as in the diagnostic path,
+    /// e.g. `"chromatic_aberration"`.
+    pub fn effect(name: &str) -> DiagnosticId {
+        // FNV-1a, so the id is the same for the same name across runs.
+        let hash = name.bytes().fold(
+            0x6c62_272e_07bb_0142_62b8_2175_6295_c58d_u128,
+            |hash, byte| {
+                (hash ^ byte as u128).wrapping_mul(0x0000_0000_0100_0000_0000_0000_0000_013b)
+            },
+        );
+        DiagnosticId::from_u128(hash)
+    }
+}
+
+/// The diagnostic path part of an effect, from the label of its pipeline,
+/// e.g. `"chromatic_aberration"` from `"Chromatic Aberration Render Pipeline"`.
+fn effect_name(label: &str) -> String {
+    label
+        .strip_suffix(" Render Pipeline")
+        .unwrap_or(label)
+        .trim()
+        .to_lowercase()
+        .replace(' ', "_")
+}
+
+/// The GPU times read back, from the render world to the main world.
+#[derive(Debug, Default, Clone, Resource)]
+struct SharedEffectTimes(Arc<Mutex<Vec<HashMap<String, f64>>>>);
+
+/// The effects timed this frame, with the index of their first timestamp.
+#[derive(Debug, Default)]
+struct Spans {
+    next: u32,
+    effects: Vec<(String, u32)>,
+}
+
+/// Writes timestamps around the effects as they are drawn.
+#[derive(Resource)]
+pub(crate) struct EffectTimestamps {
+    /// `None` if timestamp queries aren't supported.
+    query_set: Option<wgpu::QuerySet>,
+    resolve_buffer: Buffer,
+    spans: Mutex<Spans>,
+}
+
+impl FromWorld for EffectTimestamps {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let query_set = render_device
+            .features()
+            .contains(WgpuFeatures::TIMESTAMP_QUERY)
+            .then(|| {
+                render_device
+                    .wgpu_device()
+                    .create_query_set(&wgpu::QuerySetDescriptor {
+                        label: Some("Effect timestamps"),
+                        ty: wgpu::QueryType::Timestamp,
+                        count: MAX_SPANS * 2,
+                    })
+            });
+        if query_set.is_none() {
+            info!("Timestamp queries are not supported, so effects report no GPU times");
+        }
+
+        let resolve_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("Effect timestamps resolve buffer"),
+            size: TIMESTAMPS_SIZE,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            query_set,
+            resolve_buffer,
+            spans: default(),
+        }
+    }
+}
+
+impl EffectTimestamps {
+    /// Writes the timestamp before the effect with the given pipeline label, and returns its index.
+    /// `None` if timestamps aren't supported or there are too many effects this frame.
+    pub(crate) fn begin(&self, render_context: &mut RenderContext, label: &str) -> Option<u32> {
+        let query_set = self.query_set.as_ref()?;
+        let mut spans = self.spans.lock().expect("Spans should not be poisoned");
+        if spans.next >= MAX_SPANS * 2 {
+            return None;
+        }
+
+        let index = spans.next;
+        spans.next += 2;
+        spans.effects.push((effect_name(label), index));
+        render_context
+            .command_encoder()
+            .write_timestamp(query_set, index);
+        Some(index)
+    }
+
+    /// Writes the timestamp after the effect which began at `index`.
+    pub(crate) fn end(&self, render_context: &mut RenderContext, index: Option<u32>) {
+        if let (Some(query_set), Some(index)) = (&self.query_set, index) {
+            render_context
+                .command_encoder()
+                .write_timestamp(query_set, index + 1);
+        }
+    }
+}
+
+/// A buffer the timestamps of a frame are copied into, which is read once it is mapped.
+struct PendingTimestamps {
+    effects: Vec<(String, u32)>,
+    buffer: Buffer,
+    mapped: Arc<AtomicBool>,
+}
+
+#[derive(Default, Resource)]
+struct ReadbackBuffers {
+    pending: Vec<PendingTimestamps>,
+    free: Vec<Buffer>,
+}
+
+/// Reads the timestamps mapped since the last frame, and copies the ones of this frame.
+fn read_timestamps(
+    shared: Res<SharedEffectTimes>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    timestamps: Res<EffectTimestamps>,
+    mut buffers: ResMut<ReadbackBuffers>,
+) {
+    let Some(query_set) = &timestamps.query_set else {
+        return;
+    };
+
+    let _span = info_span!("vfx::diagnostics::read").entered();
+
+    // The length of a tick of the timestamps, in milliseconds.
+    let period_ms = render_queue.get_timestamp_period() as f64 / 1_000_000.0;
+
+    let buffers = &mut *buffers;
+    let (mapped, still_pending): (Vec<_>, Vec<_>) = buffers
+        .pending
+        .drain(..)
+        .partition(|pending| pending.mapped.load(Ordering::Acquire));
+    buffers.pending = still_pending;
+
+    for pending in mapped {
+        let ticks: Vec<u64> = pending
+            .buffer
+            .slice(..)
+            .get_mapped_range()
+            .chunks_exact(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("Chunks should be 8 bytes")))
+            .collect();
+        pending.buffer.unmap();
+        buffers.free.push(pending.buffer);
+
+        let mut times: HashMap<String, f64> = HashMap::default();
+        for (effect, index) in pending.effects {
+            let (start, end) = (ticks[index as usize], ticks[index as usize + 1]);
+            *times.entry(effect).or_default() += end.saturating_sub(start) as f64 * period_ms;
+        }
+
+        shared
+            .0
+            .lock()
+            .expect("Effect times should not be poisoned")
+            .push(times);
+    }
+
+    let spans = std::mem::take(
+        &mut *timestamps
+            .spans
+            .lock()
+            .expect("Spans should not be poisoned"),
+    );
+    if spans.effects.is_empty() {
+        // Nothing was drawn, so the effects took no time this frame.
+        shared
+            .0
+            .lock()
+            .expect("Effect times should not be poisoned")
+            .push(default());
+        return;
+    }
+
+    let buffer = buffers.free.pop().unwrap_or_else(|| {
+        render_device.create_buffer(&BufferDescriptor {
+            label: Some("Effect timestamps readback buffer"),
+            size: TIMESTAMPS_SIZE,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    });
+
+    // This runs after the frame is submitted, so the timestamps of this frame are written first.
+    let size = spans.next as u64 * wgpu::QUERY_SIZE as u64;
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("Effect timestamps readback"),
+    });
+    encoder.resolve_query_set(query_set, 0..spans.next, &timestamps.resolve_buffer, 0);
+    encoder.copy_buffer_to_buffer(&timestamps.resolve_buffer, 0, &buffer, 0, size);
+    render_queue.submit([encoder.finish()]);
+
+    let mapped = Arc::new(AtomicBool::new(false));
+    let on_mapped = mapped.clone();
+    render_device.map_buffer(&buffer.slice(..), MapMode::Read, move |result| {
+        if let Err(err) = result {
+            error!("Could not read back effect timestamps: {err}");
+        }
+        on_mapped.store(true, Ordering::Release);
+    });
+
+    buffers.pending.push(PendingTimestamps {
+        effects: spans.effects,
+        buffer,
+        mapped,
+    });
+}
+
+/// Registers a diagnostic for each effect the first time it is timed.
+fn register_diagnostics(shared: Res<SharedEffectTimes>, mut store: ResMut<DiagnosticsStore>) {
+    let shared = shared
+        .0
+        .lock()
+        .expect("Effect times should not be poisoned");
+
+    for effect in shared.iter().flat_map(|times| times.keys()) {
+        let id = EffectDiagnostics::effect(effect);
+        if store.get(id).is_none() {
+            store.add(
+                Diagnostic::new(id, format!("bevy_vfx_bag/{effect}"), HISTORY_LENGTH)
+                    .with_suffix("ms"),
+            );
+        }
+    }
+}
+
+/// Reports the GPU times read back since the last frame.
+fn measure(
+    shared: Res<SharedEffectTimes>,
+    mut diagnostics: Diagnostics,
+    mut seen: Local<Vec<String>>,
+) {
+    let frames = std::mem::take(
+        &mut *shared
+            .0
+            .lock()
+            .expect("Effect times should not be poisoned"),
+    );
+
+    for times in frames {
+        for effect in times.keys() {
+            if !seen.contains(effect) {
+                seen.push(effect.clone());
+            }
+        }
+
+        // Effects which weren't drawn took no time, so their averages fall off.
+        for effect in seen.iter() {
+            let ms = times.get(effect).copied().unwrap_or_default();
+            diagnostics.add_measurement(EffectDiagnostics::effect(effect), || ms);
+        }
+
+        let total: f64 = times.values().sum();
+        diagnostics.add_measurement(EffectDiagnostics::TOTAL, || total);
+    }
+}
+
+/// Times the effects on the GPU, see [`EffectDiagnostics`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedEffectTimes::default();
+
+        app.register_diagnostic(
+            Diagnostic::new(
+                EffectDiagnostics::TOTAL,
+                "bevy_vfx_bag/total",
+                HISTORY_LENGTH,
+            )
+            .with_suffix("ms"),
+        )
+        .insert_resource(shared.clone())
+        .add_systems(PreUpdate, (register_diagnostics, measure).chain());
+
+        // Reading goes after rendering, so the timestamps of the frame are submitted.
+        super::render_app(app)
+            .insert_resource(shared)
+            .init_resource::<EffectTimestamps>()
+            .init_resource::<ReadbackBuffers>()
+            .add_systems(Render, read_timestamps.in_set(RenderSet::Cleanup));
+    }
+}
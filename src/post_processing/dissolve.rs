@@ -0,0 +1,203 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{ShaderRef, ShaderType},
+    utils::Uuid,
+};
+
+use crate::time::VfxTime;
+
+use super::material::{EffectMaterial2d, MaterialEffect};
+
+pub(crate) const DISSOLVE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3387016457201873624);
+
+/// Burns a sprite or mesh away along a noise pattern, with a glowing edge.
+///
+/// Only drawn as an [`EffectMaterial2d`], not on cameras:
+///
+/// ```no_run
+/// # use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+/// # use bevy_vfx_bag::post_processing::{dissolve::{Dissolve, DissolveAway}, material::EffectMaterial2d};
+/// # let mut commands: Commands = panic!();
+/// # let quad: Handle<Mesh> = panic!();
+/// # let mut materials: ResMut<Assets<EffectMaterial2d<Dissolve>>> = panic!();
+/// # let image: Handle<Image> = panic!();
+/// commands.spawn((
+///     MaterialMesh2dBundle {
+///         mesh: quad.into(),
+///         material: materials.add(EffectMaterial2d::new(Dissolve::default(), image)),
+///         ..default()
+///     },
+///     // Burns away over a second, then the entity is despawned.
+///     DissolveAway::new(1.0),
+/// ));
+/// ```
+///
+/// The noise is generated in the shader, so the pattern tiles with [`Dissolve::noise_scale`]
+/// instead of coming from a texture.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Default)]
+pub struct Dissolve {
+    /// How much is dissolved, from `0.0` (nothing) to `1.0` (everything).
+    pub threshold: f32,
+
+    /// The color of the burning edge. Its alpha is how strongly the edge is colored.
+    pub edge_color: Color,
+
+    /// How wide the burning edge is. The noise is between `0.0` and `1.0`,
+    /// so `0.1` means the tenth of the noise just above the threshold burns.
+    pub edge_width: f32,
+
+    /// How many cells of noise there are across the image.
+    /// Higher values burn away in smaller, more scattered patches.
+    pub noise_scale: f32,
+}
+
+impl Default for Dissolve {
+    fn default() -> Self {
+        Self {
+            threshold: 0.0,
+            edge_color: Color::rgb(1.0, 0.45, 0.1),
+            edge_width: 0.08,
+            noise_scale: 8.0,
+        }
+    }
+}
+
+impl Display for Dissolve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Dissolve threshold: {:.2}, edge width: {:.2}, noise scale: {:.1}",
+            self.threshold, self.edge_width, self.noise_scale
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Dissolve`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Copy)]
+pub struct DissolveUniform {
+    pub(crate) edge_color: Vec4,
+    pub(crate) threshold: f32,
+    pub(crate) edge_width: f32,
+    pub(crate) noise_scale: f32,
+}
+
+impl From<Dissolve> for DissolveUniform {
+    fn from(dissolve: Dissolve) -> Self {
+        Self {
+            edge_color: Vec4::from(dissolve.edge_color.as_linear_rgba_f32()),
+            threshold: dissolve.threshold,
+            edge_width: dissolve.edge_width,
+            noise_scale: dissolve.noise_scale,
+        }
+    }
+}
+
+impl MaterialEffect for Dissolve {
+    type Uniform = DissolveUniform;
+    type Key = ();
+
+    const MATERIAL_UUID: Uuid = Uuid::from_u128(0x3f5bd2c6e0a84b1f9c7d52a1e6f04b93);
+
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Handle(DISSOLVE_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        (*self).into()
+    }
+
+    fn key(&self) {}
+}
+
+/// Dissolves the [`EffectMaterial2d<Dissolve>`] of this entity over a duration,
+/// and despawns the entity once it is gone, e.g. for defeated enemies.
+///
+/// The material is changed in place, so give each dissolving entity a material of its own,
+/// or every entity sharing it dissolves along with it.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct DissolveAway {
+    /// How long it takes to dissolve, in seconds.
+    pub duration: f32,
+
+    /// How long it has been dissolving, in seconds.
+    pub elapsed: f32,
+}
+
+impl DissolveAway {
+    /// Dissolve over the given number of seconds.
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// How far along the dissolve is, from `0.0` when it starts to `1.0` when it is over.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        (self.elapsed / self.duration).min(1.0)
+    }
+}
+
+impl Default for DissolveAway {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+fn dissolve_away(
+    mut commands: Commands,
+    time: Res<VfxTime>,
+    mut materials: ResMut<Assets<EffectMaterial2d<Dissolve>>>,
+    mut entities: Query<(
+        Entity,
+        &mut DissolveAway,
+        &Handle<EffectMaterial2d<Dissolve>>,
+    )>,
+) {
+    for (entity, mut dissolve, material) in &mut entities {
+        dissolve.elapsed += time.delta_seconds();
+
+        let progress = dissolve.progress();
+        if let Some(material) = materials.get_mut(material) {
+            material.effect.threshold = progress;
+        }
+
+        if progress >= 1.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DISSOLVE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "dissolve.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Dissolve>()
+            .register_type::<DissolveAway>()
+            .add_systems(Update, dissolve_away);
+    }
+}
@@ -0,0 +1,318 @@
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, PipelineCache,
+            RenderPipelineDescriptor, ShaderDefVal, ShaderStages, ShaderType,
+            SpecializedRenderPipeline, SpecializedRenderPipelines, TextureSampleType,
+            TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        texture::{CompressedImageFormats, ImageType},
+        Render, RenderSet,
+    },
+};
+use std::fmt::Display;
+
+use crate::post_processing::DrawPostProcessingEffect;
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+    UniformBindGroup,
+};
+
+pub(crate) const DITHER_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8476915106217149186);
+const BLUE_NOISE_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 15194228209884061854);
+
+#[derive(Resource, ExtractResource, Deref, DerefMut, Clone, Hash)]
+struct BlueNoiseTextureHandle(Handle<Image>);
+
+#[derive(Resource)]
+pub(crate) struct DitherData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+}
+
+impl FromWorld for DitherData {
+    fn from_world(world: &mut World) -> Self {
+        let uniform_layout = super::create_layout(
+            world,
+            "Dither",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(DitherUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The blue noise is read texel by texel, so no sampler is needed.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+        DitherData {
+            uniform_layout,
+            shared_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DITHER_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "dither.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        let mut assets = app.world.resource_mut::<Assets<_>>();
+
+        let image = Image::from_buffer(
+            include_bytes!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/textures/",
+                "blue-noise.png"
+            )),
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            false,
+        )
+        .expect("Should load blue noise successfully");
+        assets.set_untracked(BLUE_NOISE_IMAGE_HANDLE, image);
+
+        app.register_type::<Dither>();
+        app.register_type::<DitherMode>();
+        super::register_effect_types::<Dither>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Dither>::default(),
+            UniformComponentPlugin::<DitherUniform>::default(),
+            ExtractResourcePlugin::<BlueNoiseTextureHandle>::default(),
+        ))
+        .insert_resource(BlueNoiseTextureHandle(
+            BLUE_NOISE_IMAGE_HANDLE.clone_weak().typed(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Dither>,
+            )
+            .init_resource::<DitherData>()
+            .init_resource::<UniformBindGroup<DitherUniform>>()
+            .init_resource::<SpecializedRenderPipelines<DitherData>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<DitherUniform>>(
+            );
+    }
+}
+
+impl SpecializedRenderPipeline for DitherData {
+    type Key = DitherMode;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Dither",
+            &self.shared_layout,
+            &self.uniform_layout,
+            DITHER_SHADER_HANDLE.typed(),
+            key.shader_defs(),
+        )
+    }
+}
+
+fn prepare(
+    data: Res<DitherData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<DitherData>>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Dither>,
+        &Blend<Dither>,
+        &Resolution<Dither>,
+        &SourceSampler<Dither>,
+        &DitherMode,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::dither::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<DitherUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, *key);
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<DitherData>,
+    texture_handle: Res<BlueNoiseTextureHandle>,
+    mut bind_group: ResMut<UniformBindGroup<DitherUniform>>,
+    uniforms: Res<ComponentUniforms<DitherUniform>>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<Entity, With<DitherUniform>>,
+) {
+    let _span = info_span!("vfx::dither::queue").entered();
+
+    bind_group.inner = None;
+
+    if let (Some(uniforms), Some(blue_noise)) = (uniforms.binding(), images.get(&texture_handle.0))
+    {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Dither Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: uniforms.clone(),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::TextureView(&blue_noise.texture_view),
+                    },
+                ],
+            }));
+        }
+    }
+}
+
+/// Which pattern of thresholds is used to dither.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum DitherMode {
+    /// An ordered 2x2 Bayer matrix. Very coarse, with an obvious cross-hatch pattern.
+    Bayer2,
+
+    /// An ordered 4x4 Bayer matrix. The classic retro look.
+    #[default]
+    Bayer4,
+
+    /// An ordered 8x8 Bayer matrix. Smoother gradients than the smaller matrices.
+    Bayer8,
+
+    /// A tiling blue noise texture.
+    /// Looks less structured than the Bayer matrices, more like film grain.
+    BlueNoise,
+}
+
+impl DitherMode {
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            DitherMode::Bayer2 => vec!["BAYER".into(), ShaderDefVal::UInt("BAYER_BITS".into(), 1)],
+            DitherMode::Bayer4 => vec!["BAYER".into(), ShaderDefVal::UInt("BAYER_BITS".into(), 2)],
+            DitherMode::Bayer8 => vec!["BAYER".into(), ShaderDefVal::UInt("BAYER_BITS".into(), 3)],
+            DitherMode::BlueNoise => vec!["BLUE_NOISE".into()],
+        }
+    }
+}
+
+/// Reduces the number of colors, using dithering to hide the banding this would otherwise cause.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Dither {
+    /// How many levels each color channel is reduced to.
+    /// For example `2` gives 8 colors in total, and `4` gives 64.
+    ///
+    /// The shader sets a lower bound of 2.
+    pub levels: u32,
+
+    /// Which [`DitherMode`] to use.
+    pub mode: DitherMode,
+}
+
+impl Default for Dither {
+    fn default() -> Self {
+        Self {
+            levels: 4,
+            mode: DitherMode::default(),
+        }
+    }
+}
+
+impl Display for Dither {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Dither {:?}, levels: {}", self.mode, self.levels)
+    }
+}
+
+#[doc(hidden)]
+/// [`Dither`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct DitherUniform {
+    pub(crate) levels: u32,
+}
+
+impl ExtractComponent for Dither {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (DitherUniform, DitherMode);
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::dither::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((
+            DitherUniform {
+                levels: settings.levels,
+            },
+            settings.mode,
+        ))
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const EDGE_FRINGE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9416254802311738405);
+
+#[derive(Resource)]
+pub(crate) struct EdgeFringeData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for EdgeFringeData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Edge Fringe",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(EdgeFringeUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            EDGE_FRINGE_SHADER_HANDLE.typed(),
+        );
+
+        EdgeFringeData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            EDGE_FRINGE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "edge_fringe.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<EdgeFringe>();
+        super::register_effect_types::<EdgeFringe>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<EdgeFringe>::default(),
+            UniformComponentPlugin::<EdgeFringeUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<EdgeFringe>,
+            )
+            .init_resource::<EdgeFringeData>()
+            .init_resource::<UniformBindGroup<EdgeFringeUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<EdgeFringeUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<EdgeFringeData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<EdgeFringe>,
+        &Blend<EdgeFringe>,
+        &Resolution<EdgeFringe>,
+        &SourceSampler<EdgeFringe>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::edge_fringe::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<EdgeFringeUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<EdgeFringeData>,
+    mut bind_group: ResMut<UniformBindGroup<EdgeFringeUniform>>,
+    uniforms: Res<ComponentUniforms<EdgeFringeUniform>>,
+    views: Query<Entity, With<EdgeFringeUniform>>,
+) {
+    let _span = info_span!("vfx::edge_fringe::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Edge Fringe Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Separates the color channels near the edges of the image only, like a cheap lens.
+///
+/// Unlike [`super::chromatic_aberration::ChromaticAberration`] which shifts the channels
+/// across the whole image, the middle stays sharp and the fringes grow towards the corners,
+/// pointing away from the middle. Small strengths add subtle realism.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct EdgeFringe {
+    /// How far (in UV space) the red and blue channels are displaced in the corners.
+    pub strength: f32,
+
+    /// Where the fringes start, from `0.0` (the middle of the image) to `1.0` (the corners).
+    pub start: f32,
+
+    /// How quickly the fringes grow past [`EdgeFringe::start`].
+    /// `1.0` grows linearly, higher values keep the fringes tighter to the corners.
+    pub falloff: f32,
+}
+
+impl Default for EdgeFringe {
+    fn default() -> Self {
+        Self {
+            strength: 0.006,
+            start: 0.4,
+            falloff: 2.0,
+        }
+    }
+}
+
+impl Display for EdgeFringe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Edge Fringe strength: {:.4}, start: {:.2}, falloff: {:.2}",
+            self.strength, self.start, self.falloff
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`EdgeFringe`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct EdgeFringeUniform {
+    pub(crate) strength: f32,
+    pub(crate) start: f32,
+    pub(crate) falloff: f32,
+}
+
+impl From<EdgeFringe> for EdgeFringeUniform {
+    fn from(fringe: EdgeFringe) -> Self {
+        Self {
+            strength: fringe.strength,
+            start: fringe.start,
+            falloff: fringe.falloff,
+        }
+    }
+}
+
+impl ExtractComponent for EdgeFringe {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = EdgeFringeUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::edge_fringe::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -0,0 +1,331 @@
+use bevy::{
+    core_pipeline::{
+        blit::{BlitPipeline, BlitPipelineKey},
+        core_2d, core_3d,
+    },
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        camera::ExtractedCamera,
+        render_asset::RenderAssets,
+        render_graph::{
+            EmptyNode, NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindingResource, CachedRenderPipelineId, Extent3d,
+            LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            SpecializedRenderPipelines, Texture, TextureDescriptor, TextureDimension,
+            TextureFormat, TextureUsages, TextureView,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        Extract, Render, RenderSet,
+    },
+};
+
+use super::graph::node;
+
+/// Gives the camera a mask texture, which render passes of the app draw into
+/// to limit effects to parts of the image, e.g. spell areas or zone boundaries.
+///
+/// The mask is the [`ViewEffectMask`] of the view in the render world.
+/// It is cleared to transparent black each frame, and render graph nodes of the app draw
+/// into it between [`node::START_EFFECT_MASK`] and [`node::END_EFFECT_MASK`]:
+///
+/// ```no_run
+/// # use bevy::{prelude::*, render::{render_graph::RenderGraphApp, RenderApp}, core_pipeline::core_3d};
+/// # use bevy_vfx_bag::post_processing::graph::node;
+/// # let mut app = App::new();
+/// # type MyMaskNode = bevy::render::render_graph::EmptyNode;
+/// app.sub_app_mut(RenderApp)
+///     .add_render_graph_node::<MyMaskNode>(core_3d::graph::NAME, "my_mask")
+///     .add_render_graph_edges(
+///         core_3d::graph::NAME,
+///         &[node::START_EFFECT_MASK, "my_mask", node::END_EFFECT_MASK],
+///     );
+/// ```
+///
+/// Effects are limited to the mask with [`super::blend::EffectScope::Mask`],
+/// each using one channel of it, so up to four masks can be drawn at once.
+///
+/// Masks which don't need render passes, e.g. the region of a monitor in the UI,
+/// can be given as an image with an [`EffectMaskImage`] instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct EffectMask;
+
+/// An image the [`EffectMask`] of the camera starts out as each frame, instead of transparent black.
+///
+/// White is the full effect and black is none, so e.g. an image which is white where a monitor
+/// is in the UI limits an effect with [`super::blend::EffectScope::Mask`] to the monitor:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{blend::{Blend, EffectScope}, effect_mask::{EffectMaskChannel, EffectMaskImage}, pixelate::Pixelate};
+/// # let mut commands: Commands = panic!();
+/// # let asset_server: AssetServer = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     EffectMaskImage(asset_server.load("masks/monitor.png")),
+///     Pixelate::default(),
+///     Blend::<Pixelate>::default().with_scope(EffectScope::Mask(EffectMaskChannel::Red)),
+/// ));
+/// ```
+///
+/// The image is stretched over the whole render target of the camera, and each channel of it
+/// is the same channel of the mask. Render passes of the app can still draw into the mask on top.
+/// The camera doesn't need an [`EffectMask`] as well.
+/// While the image is loading, the mask is transparent black, so masked effects apply nowhere.
+#[derive(Debug, Default, Clone, PartialEq, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct EffectMaskImage(pub Handle<Image>);
+
+/// A channel of the [`ViewEffectMask`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectMaskChannel {
+    /// The red channel.
+    #[default]
+    Red,
+
+    /// The green channel.
+    Green,
+
+    /// The blue channel.
+    Blue,
+
+    /// The alpha channel.
+    Alpha,
+}
+
+impl EffectMaskChannel {
+    /// The index of the channel, as known by the shader.
+    pub(crate) fn index(self) -> u32 {
+        match self {
+            EffectMaskChannel::Red => 0,
+            EffectMaskChannel::Green => 1,
+            EffectMaskChannel::Blue => 2,
+            EffectMaskChannel::Alpha => 3,
+        }
+    }
+}
+
+/// The mask texture of a camera with an [`EffectMask`], in the render world.
+///
+/// It has the size of the camera's render target and the [`ViewEffectMask::FORMAT`] format,
+/// and is never multisampled. Each channel holds how much of an effect limited to it applies,
+/// from `0.0` to `1.0`, so masks can have soft edges.
+#[derive(Component)]
+pub struct ViewEffectMask {
+    texture: CachedTexture,
+
+    /// The [`EffectMaskImage`], and the pipeline drawing it into the mask.
+    image: Option<(Handle<Image>, CachedRenderPipelineId)>,
+}
+
+impl ViewEffectMask {
+    /// The format of the mask texture, which pipelines drawing into it must use.
+    pub const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+    /// The mask texture.
+    pub fn texture(&self) -> &Texture {
+        &self.texture.texture
+    }
+
+    /// The view to draw into the mask with.
+    pub fn view(&self) -> &TextureView {
+        &self.texture.default_view
+    }
+}
+
+fn extract(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<
+            (Entity, &Camera, Option<&EffectMaskImage>),
+            Or<(With<EffectMask>, With<EffectMaskImage>)>,
+        >,
+    >,
+) {
+    for (entity, camera, image) in &cameras {
+        if camera.is_active {
+            let mut entity = commands.get_or_spawn(entity);
+            entity.insert(EffectMask);
+
+            if let Some(image) = image {
+                entity.insert(image.clone());
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    pipeline_cache: Res<PipelineCache>,
+    blit: Res<BlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    views: Query<(Entity, &ExtractedCamera, Option<&EffectMaskImage>), With<EffectMask>>,
+) {
+    let _span = info_span!("vfx::effect_mask::prepare").entered();
+
+    for (entity, camera, image) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("Effect mask"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: ViewEffectMask::FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        let image = image.map(|image| {
+            let pipeline_id = pipelines.specialize(
+                &pipeline_cache,
+                &blit,
+                BlitPipelineKey {
+                    texture_format: ViewEffectMask::FORMAT,
+                    blend_state: None,
+                    samples: 1,
+                },
+            );
+
+            (image.0.clone(), pipeline_id)
+        });
+
+        commands
+            .entity(entity)
+            .insert(ViewEffectMask { texture, image });
+    }
+}
+
+/// Clears the mask, since textures from the cache keep what was drawn into them last,
+/// and draws the [`EffectMaskImage`] into it.
+#[derive(Default)]
+struct ClearEffectMaskNode;
+
+impl ViewNode for ClearEffectMaskNode {
+    type ViewQuery = &'static ViewEffectMask;
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        mask: QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        // Nothing is drawn while the image is loading, or the pipeline is compiling.
+        let image = mask.image.as_ref().and_then(|(image, pipeline_id)| {
+            let image = world.resource::<RenderAssets<Image>>().get(image)?;
+            let pipeline = world
+                .resource::<PipelineCache>()
+                .get_render_pipeline(*pipeline_id)?;
+            Some((image, pipeline))
+        });
+
+        let bind_group = image.map(|(image, _)| {
+            let blit = world.resource::<BlitPipeline>();
+
+            render_context
+                .render_device()
+                .create_bind_group(&BindGroupDescriptor {
+                    label: Some("Effect mask image bind group"),
+                    layout: &blit.texture_bind_group,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(&image.texture_view),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&blit.sampler),
+                        },
+                    ],
+                })
+        });
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Clear effect mask"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: mask.view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::NONE.into()),
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        if let (Some((_, pipeline)), Some(bind_group)) = (image, bind_group.as_ref()) {
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds the mask nodes to a graph, so the mask is ready before the effects run.
+fn add_nodes(render_app: &mut App, graph: &str, after: &'static str) {
+    let start = ViewNodeRunner::new(ClearEffectMaskNode, &mut render_app.world);
+
+    let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+    let graph = render_graph
+        .get_sub_graph_mut(graph)
+        .expect("Graph should be available");
+
+    graph.add_node(node::START_EFFECT_MASK, start);
+    graph.add_node(node::END_EFFECT_MASK, EmptyNode);
+
+    graph.add_node_edge(after, node::START_EFFECT_MASK);
+    graph.add_node_edge(node::START_EFFECT_MASK, node::END_EFFECT_MASK);
+    graph.add_node_edge(node::END_EFFECT_MASK, node::BEFORE_TONEMAPPING);
+}
+
+/// Adds the effect mask of cameras with an [`EffectMask`] or an [`EffectMaskImage`].
+///
+/// Must be added after the post processing nodes are in the graphs.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EffectMask>()
+            .register_type::<EffectMaskImage>()
+            .register_type::<EffectMaskChannel>();
+
+        let render_app = super::render_app(app);
+
+        // In 3d, the mask is drawn after the prepass, so nodes drawing it can test against its depth.
+        add_nodes(
+            render_app,
+            core_3d::graph::NAME,
+            core_3d::graph::node::PREPASS,
+        );
+        add_nodes(
+            render_app,
+            core_2d::graph::NAME,
+            core_2d::graph::node::MAIN_PASS,
+        );
+
+        render_app
+            .add_systems(ExtractSchedule, extract)
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare));
+    }
+}
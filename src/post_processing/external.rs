@@ -0,0 +1,411 @@
+use std::{any::TypeId, collections::BTreeMap, fmt, marker::PhantomData};
+
+use bevy::{
+    ecs::world::EntityMut,
+    prelude::*,
+    reflect::{GetPath, GetTypeRegistration},
+};
+
+use crate::{
+    metadata::{self, ParameterInfo},
+    state::EffectKind,
+    util,
+};
+
+use super::{
+    budget::EffectCost,
+    bypass::EffectCategory,
+    custom::{CustomPostProcessingPlugin, PostProcessingEffect},
+    Order,
+};
+
+/// A [`PostProcessingEffect`] shipped by another crate, described so that it is handled like the
+/// effects of this crate.
+///
+/// Add an [`ExternalEffectPlugin`] for the effect instead of a [`CustomPostProcessingPlugin`].
+/// On top of being rendered, the effect then:
+///
+/// - is bypassed along with its [`EffectPlugin::CATEGORY`], see [`super::bypass::EffectBypass`].
+/// - is shed by an [`super::budget::EffectBudget`] according to its [`EffectPlugin::COST`].
+/// - is listed in the inspector of the `egui` feature, with sliders for its numeric parameters.
+/// - is stored in effect presets of the `ron` feature by its [`EffectPlugin::NAME`].
+///
+/// ```no_run
+/// # use bevy::{prelude::*, render::render_resource::{ShaderRef, ShaderType}};
+/// # use bevy_vfx_bag::{post_processing::{budget::{EffectCost, EffectPriority}, bypass::EffectCategory, custom::PostProcessingEffect, external::{EffectPlugin, ExternalEffectPlugin}}, BevyVfxBagPlugin};
+/// #[derive(Debug, Default, Clone, Component, ShaderType, Reflect)]
+/// #[reflect(Component, Default)]
+/// struct Sepia {
+///     strength: f32,
+/// }
+///
+/// impl PostProcessingEffect for Sepia {
+///     const SHADER_INTERFACE: u32 = 1;
+///
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/sepia.wgsl".into()
+///     }
+/// }
+///
+/// impl EffectPlugin for Sepia {
+///     const NAME: &'static str = "Sepia";
+///     const CATEGORY: Option<EffectCategory> = Some(EffectCategory::Color);
+///     const COST: Option<EffectCost> = Some(EffectCost {
+///         cost: 1.0,
+///         priority: EffectPriority::Medium,
+///     });
+/// }
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         BevyVfxBagPlugin::default(),
+///         ExternalEffectPlugin::<Sepia>::default(),
+///     ))
+///     .run();
+/// ```
+///
+/// The effect is drawn in a pass of its own: a [`super::combined::CombinedPass`] only combines
+/// the effects its shader knows, so the effect ends a chain and the chain after it starts anew.
+/// Effects which only add something on top of the image are cheapest as overlays,
+/// see [`PostProcessingEffect::COMPOSITION`].
+pub trait EffectPlugin:
+    PostProcessingEffect + Reflect + FromReflect + GetTypeRegistration + Default
+{
+    /// The name of the effect, e.g. `"Sepia"`.
+    ///
+    /// Presets refer to the effect by this name, so it must differ from the names of all other
+    /// effects, and should not change between releases.
+    const NAME: &'static str;
+
+    /// The category the effect is bypassed with.
+    ///
+    /// Effects without a category are only bypassed along with all effects.
+    const CATEGORY: Option<EffectCategory> = None;
+
+    /// The cost and default priority of the effect, for shedding it to stay within a budget.
+    ///
+    /// Effects without a cost are never shed.
+    const COST: Option<EffectCost> = None;
+
+    /// The useful ranges of numeric parameters, by field name, e.g. `&[("strength", 0.0, 2.0)]`.
+    ///
+    /// Parameters which aren't listed get a range guessed from their default value,
+    /// like the effects of this crate do in [`crate::metadata::EffectMetadata`].
+    const RANGES: &'static [(&'static str, f32, f32)] = &[];
+}
+
+/// An [`EffectPlugin`] on an entity, along with its [`Order`] and numeric parameters.
+///
+/// This is how presets store effects of other crates.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalEffectEntry {
+    /// The [`EffectPlugin::NAME`] of the effect.
+    pub name: String,
+
+    /// The order of the effect, see [`Order`].
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub order: Option<f32>,
+
+    /// The numeric parameters of the effect, by field name.
+    ///
+    /// Parameters which are left out keep their defaults, as do parameters which aren't numbers.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub parameters: BTreeMap<String, f32>,
+}
+
+/// An effect registered via an [`ExternalEffectPlugin`].
+#[derive(Clone)]
+pub struct ExternalEffect {
+    name: &'static str,
+    type_id: TypeId,
+    category: Option<EffectCategory>,
+    cost: Option<EffectCost>,
+    parameters: Vec<ParameterInfo>,
+    reflect: for<'a> fn(&'a EntityRef) -> Option<&'a dyn Reflect>,
+    reflect_mut: for<'a> fn(&'a mut EntityMut) -> Option<Mut<'a, dyn Reflect>>,
+    order: fn(&EntityRef) -> Option<f32>,
+    insert: fn(&mut EntityMut, Option<f32>),
+    remove: fn(&mut EntityMut),
+}
+
+impl fmt::Debug for ExternalEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalEffect")
+            .field("name", &self.name)
+            .field("category", &self.category)
+            .field("cost", &self.cost)
+            .field("parameters", &self.parameters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ExternalEffect {
+    fn of<T: EffectPlugin>() -> Self {
+        let mut parameters = metadata::parameters_of(&T::default());
+        for (name, min, max) in T::RANGES {
+            if let Some(parameter) = parameters.iter_mut().find(|p| p.name == *name) {
+                parameter.min = *min;
+                parameter.max = *max;
+            } else {
+                warn!("Effect `{}` has no numeric parameter `{name}`", T::NAME);
+            }
+        }
+
+        Self {
+            name: T::NAME,
+            type_id: TypeId::of::<T>(),
+            category: T::CATEGORY,
+            cost: T::COST,
+            parameters,
+            reflect: reflect::<T>,
+            reflect_mut: reflect_mut::<T>,
+            order: order::<T>,
+            insert: insert::<T>,
+            remove: remove::<T>,
+        }
+    }
+
+    /// The [`EffectPlugin::NAME`] of the effect.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The [`EffectPlugin::CATEGORY`] of the effect.
+    pub fn category(&self) -> Option<EffectCategory> {
+        self.category
+    }
+
+    /// The [`EffectPlugin::COST`] of the effect.
+    pub fn cost(&self) -> Option<EffectCost> {
+        self.cost
+    }
+
+    /// The numeric parameters of the effect, in the order of the fields.
+    pub fn parameters(&self) -> &[ParameterInfo] {
+        &self.parameters
+    }
+
+    /// The parameter with the given name, if it is numeric.
+    pub fn parameter(&self, name: &str) -> Option<&ParameterInfo> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name == name)
+    }
+
+    /// The effect component on the entity, if it is there.
+    pub fn reflect<'a>(&self, entity: &'a EntityRef) -> Option<&'a dyn Reflect> {
+        (self.reflect)(entity)
+    }
+
+    /// The effect component on the entity, if it is there.
+    pub fn reflect_mut<'a>(&self, entity: &'a mut EntityMut) -> Option<Mut<'a, dyn Reflect>> {
+        (self.reflect_mut)(entity)
+    }
+
+    /// The effect on the entity, if it is there.
+    pub fn capture(&self, entity: &EntityRef) -> Option<ExternalEffectEntry> {
+        let effect = self.reflect(entity)?;
+
+        let parameters = self
+            .parameters
+            .iter()
+            .filter_map(|parameter| {
+                let value = effect
+                    .reflect_path(parameter.name)
+                    .ok()
+                    .and_then(util::get_number)?;
+                Some((parameter.name.to_string(), value))
+            })
+            .collect();
+
+        Some(ExternalEffectEntry {
+            name: self.name.to_string(),
+            order: (self.order)(entity),
+            parameters,
+        })
+    }
+
+    /// Put the effect on the entity with the parameters and order of the entry.
+    ///
+    /// An effect which isn't on the entity yet starts out with its defaults.
+    pub fn apply(&self, entity: &mut EntityMut, entry: &ExternalEffectEntry) {
+        (self.insert)(entity, entry.order);
+
+        let Some(mut effect) = self.reflect_mut(entity) else {
+            return;
+        };
+        for (name, value) in &entry.parameters {
+            if util::set_field_number(&mut *effect, name, *value).is_err() {
+                warn!("Effect `{}` has no numeric parameter `{name}`", self.name);
+            }
+        }
+    }
+
+    /// Remove the effect and its [`Order`] from the entity.
+    pub fn remove(&self, entity: &mut EntityMut) {
+        (self.remove)(entity);
+    }
+}
+
+fn reflect<'a, T: EffectPlugin>(entity: &'a EntityRef) -> Option<&'a dyn Reflect> {
+    entity.get::<T>().map(|effect| effect as &dyn Reflect)
+}
+
+fn reflect_mut<'a, T: EffectPlugin>(entity: &'a mut EntityMut) -> Option<Mut<'a, dyn Reflect>> {
+    entity
+        .get_mut::<T>()
+        .map(|effect| effect.map_unchanged(|effect| effect as &mut dyn Reflect))
+}
+
+fn order<T: EffectPlugin>(entity: &EntityRef) -> Option<f32> {
+    entity.get::<Order<T>>().map(|order| order.order)
+}
+
+fn insert<T: EffectPlugin>(entity: &mut EntityMut, order: Option<f32>) {
+    if !entity.contains::<T>() {
+        entity.insert(T::default());
+    }
+
+    match order {
+        Some(order) => {
+            // Entries don't have the stage, so the one on the camera is kept.
+            let stage = entity
+                .get::<Order<T>>()
+                .map(|order| order.stage)
+                .unwrap_or_default();
+            entity.insert(Order::<T>::new(order).with_stage(stage));
+        }
+        None => {
+            entity.remove::<Order<T>>();
+        }
+    }
+}
+
+fn remove<T: EffectPlugin>(entity: &mut EntityMut) {
+    entity.remove::<(T, Order<T>)>();
+}
+
+/// The effects of other crates, registered via [`ExternalEffectPlugin`]s.
+///
+/// Tools such as inspectors list these next to the [`EffectKind`]s of this crate.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ExternalEffects {
+    effects: Vec<ExternalEffect>,
+}
+
+impl ExternalEffects {
+    /// The registered effects, in the order they were registered.
+    pub fn iter(&self) -> impl Iterator<Item = &ExternalEffect> {
+        self.effects.iter()
+    }
+
+    /// The effect with the given [`EffectPlugin::NAME`], if it is registered.
+    pub fn get(&self, name: &str) -> Option<&ExternalEffect> {
+        self.effects.iter().find(|effect| effect.name == name)
+    }
+
+    /// Change the useful range of a parameter, overriding [`EffectPlugin::RANGES`].
+    ///
+    /// Returns false if there is no such effect, or it has no numeric parameter with that name.
+    pub fn set_range(&mut self, effect: &str, name: &str, min: f32, max: f32) -> bool {
+        let Some(parameter) = self
+            .effects
+            .iter_mut()
+            .find(|e| e.name == effect)
+            .and_then(|effect| effect.parameters.iter_mut().find(|p| p.name == name))
+        else {
+            return false;
+        };
+
+        parameter.min = min;
+        parameter.max = max;
+        true
+    }
+
+    /// The registered effects on the entity.
+    pub fn capture(&self, entity: &EntityRef) -> Vec<ExternalEffectEntry> {
+        self.effects
+            .iter()
+            .filter_map(|effect| effect.capture(entity))
+            .collect()
+    }
+
+    /// Make the registered effects on the entity match the entries.
+    ///
+    /// Registered effects which aren't in the entries are removed from the entity.
+    /// Entries of effects which aren't registered are skipped with a warning.
+    pub fn apply(&self, entity: &mut EntityMut, entries: &[ExternalEffectEntry]) {
+        for entry in entries {
+            if self.get(&entry.name).is_none() {
+                warn!(
+                    "Effect `{}` is not registered, add its `ExternalEffectPlugin`",
+                    entry.name
+                );
+            }
+        }
+
+        for effect in &self.effects {
+            match entries.iter().find(|entry| entry.name == effect.name) {
+                Some(entry) => effect.apply(entity, entry),
+                None => effect.remove(entity),
+            }
+        }
+    }
+
+    /// The category of the effect component `C`, if it is a registered effect with one.
+    pub(crate) fn category<C: 'static>(&self) -> Option<EffectCategory> {
+        self.by_type::<C>().and_then(|effect| effect.category)
+    }
+
+    /// The cost of the effect component `C`, if it is a registered effect with one.
+    pub(crate) fn cost<C: 'static>(&self) -> Option<EffectCost> {
+        self.by_type::<C>().and_then(|effect| effect.cost)
+    }
+
+    fn by_type<C: 'static>(&self) -> Option<&ExternalEffect> {
+        let id = TypeId::of::<C>();
+        self.effects.iter().find(|effect| effect.type_id == id)
+    }
+
+    fn register<T: EffectPlugin>(&mut self) {
+        assert!(
+            EffectKind::from_name(T::NAME).is_none() && self.get(T::NAME).is_none(),
+            "An effect called `{}` is already registered, effect names should be unique",
+            T::NAME
+        );
+
+        self.effects.push(ExternalEffect::of::<T>());
+    }
+}
+
+/// Adds an [`EffectPlugin`] defined outside of this crate, and registers it in [`ExternalEffects`].
+///
+/// This adds the [`CustomPostProcessingPlugin`] of the effect, so don't add that as well.
+/// Needs the [`crate::BevyVfxBagPlugin`] as well.
+pub struct ExternalEffectPlugin<T>(PhantomData<T>);
+
+impl<T> Default for ExternalEffectPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: EffectPlugin> bevy::prelude::Plugin for ExternalEffectPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(CustomPostProcessingPlugin::<T>::default())
+            .register_type::<T>()
+            .init_resource::<ExternalEffects>();
+
+        app.world.resource_mut::<ExternalEffects>().register::<T>();
+    }
+}
+
+/// Keeps the [`ExternalEffects`] registry, which is empty unless other crates add effects.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExternalEffects>();
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const FALSE_COLOR_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8361094725571203486);
+
+#[derive(Resource)]
+pub(crate) struct FalseColorData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for FalseColorData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "False Color",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(FalseColorUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            FALSE_COLOR_SHADER_HANDLE.typed(),
+        );
+
+        FalseColorData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            FALSE_COLOR_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "false_color.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<FalseColor>();
+        super::register_effect_types::<FalseColor>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<FalseColor>::default(),
+            UniformComponentPlugin::<FalseColorUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<FalseColor>,
+            )
+            .init_resource::<FalseColorData>()
+            .init_resource::<UniformBindGroup<FalseColorUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<FalseColorUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<FalseColorData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<FalseColor>,
+        &Blend<FalseColor>,
+        &Resolution<FalseColor>,
+        &SourceSampler<FalseColor>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::false_color::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<FalseColorUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<FalseColorData>,
+    mut bind_group: ResMut<UniformBindGroup<FalseColorUniform>>,
+    uniforms: Res<ComponentUniforms<FalseColorUniform>>,
+    views: Query<Entity, With<FalseColorUniform>>,
+) {
+    let _span = info_span!("vfx::false_color::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("False Color Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// A debug view which shows the exposure of the image in false colors, like the monitors of
+/// film cameras, optionally along with a color checker chart.
+///
+/// The brightness of each pixel, as shown on an sRGB display, is mapped to a band:
+///
+/// | Brightness | Color | Meaning |
+/// |---|---|---|
+/// | Below 2.5% | Purple | Crushed to black |
+/// | 2.5% to 5% | Blue | Just above black |
+/// | 43% to 49% | Green | Middle gray, i.e. 18% reflectance |
+/// | 58% to 64% | Pink | Light skin, one stop above middle gray |
+/// | 95% to 99% | Yellow | Just below white |
+/// | Above 99% | Red | Clipped to white |
+///
+/// Everything else is shown in grayscale.
+///
+/// The chart shows the 24 patches of the classic color checker in the bottom left corner.
+/// It is drawn before the bands, so with the bands shown, it tells which band each patch lands in.
+/// Effects after this one in the stack grade the chart too, so put this effect last to compare
+/// the chart against a graded scene, e.g. the reference scene of the `test_fixtures` module.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct FalseColor {
+    /// Stops to brighten (or with negative values, darken) the image by before mapping it to bands,
+    /// for checking how much room there is before it clips.
+    pub exposure: f32,
+
+    /// Whether the image is mapped to the false color bands.
+    pub show_bands: bool,
+
+    /// Whether the color checker chart is shown.
+    pub show_chart: bool,
+
+    /// The height of the chart, as a fraction of the height of the image.
+    pub chart_size: f32,
+}
+
+impl FalseColor {
+    /// Only the color checker chart, without the bands.
+    pub fn chart() -> Self {
+        Self {
+            show_bands: false,
+            show_chart: true,
+            ..default()
+        }
+    }
+}
+
+impl Default for FalseColor {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            show_bands: true,
+            show_chart: false,
+            chart_size: 0.25,
+        }
+    }
+}
+
+impl Display for FalseColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "False Color exposure: {:.1}, bands: {}, chart: {}, chart size: {:.2}",
+            self.exposure, self.show_bands, self.show_chart, self.chart_size
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`FalseColor`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct FalseColorUniform {
+    pub(crate) exposure: f32,
+    pub(crate) show_bands: u32,
+    pub(crate) show_chart: u32,
+    pub(crate) chart_size: f32,
+}
+
+impl From<FalseColor> for FalseColorUniform {
+    fn from(false_color: FalseColor) -> Self {
+        Self {
+            exposure: false_color.exposure,
+            show_bands: false_color.show_bands.into(),
+            show_chart: false_color.show_chart.into(),
+            chart_size: false_color.chart_size,
+        }
+    }
+}
+
+impl ExtractComponent for FalseColor {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = FalseColorUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::false_color::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+    utils::HashMap,
+};
+
+use crate::time::VfxTime;
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const FILM_PROJECTOR_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3312271738043735842);
+
+#[derive(Resource)]
+pub(crate) struct FilmProjectorData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for FilmProjectorData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Film Projector",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(FilmProjectorUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            FILM_PROJECTOR_SHADER_HANDLE.typed(),
+        );
+
+        FilmProjectorData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            FILM_PROJECTOR_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "film_projector.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<FilmProjector>();
+        super::register_effect_types::<FilmProjector>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<FilmProjector>::default(),
+            UniformComponentPlugin::<FilmProjectorUniform>::default(),
+        ))
+        .add_event::<FilmProjectorEvent>()
+        .add_systems(PreUpdate, send_projector_events);
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<FilmProjector>,
+            )
+            .init_resource::<FilmProjectorData>()
+            .init_resource::<UniformBindGroup<FilmProjectorUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<FilmProjectorUniform>>(
+            );
+    }
+}
+
+/// What happened in a [`FilmProjector`], see [`FilmProjectorEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilmProjectorEventKind {
+    /// The projector pulled down the next frame, which is when a real projector clicks.
+    Frame,
+
+    /// A splice passed through the gate, so the image slips and the projector thumps.
+    Splice,
+}
+
+/// Sent when a [`FilmProjector`] advances a frame or passes a splice,
+/// so sound effects can be played in sync with the image.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::film_projector::{FilmProjectorEvent, FilmProjectorEventKind};
+/// fn projector_sounds(mut events: EventReader<FilmProjectorEvent>) {
+///     for event in events.iter() {
+///         match event.kind {
+///             FilmProjectorEventKind::Frame => { /* play a click */ }
+///             FilmProjectorEventKind::Splice => { /* play a thump */ }
+///         }
+///     }
+/// }
+/// ```
+///
+/// The events follow the [`crate::time::VfxTime`] clock, same as the image.
+/// At most one event of each kind is sent per camera and update, even if the clock jumped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct FilmProjectorEvent {
+    /// The camera with the projector.
+    pub camera: Entity,
+
+    /// What happened.
+    pub kind: FilmProjectorEventKind,
+}
+
+/// Sends [`FilmProjectorEvent`]s, keeping which frame and splice each projector was at
+/// as of the last update.
+fn send_projector_events(
+    time: Res<VfxTime>,
+    mut positions: Local<HashMap<Entity, (i64, i64)>>,
+    mut events: EventWriter<FilmProjectorEvent>,
+    projectors: Query<(Entity, &FilmProjector, &Camera)>,
+) {
+    let mut current = HashMap::default();
+
+    for (entity, projector, camera) in &projectors {
+        if !camera.is_active {
+            continue;
+        }
+
+        let position = projector.position(time.elapsed_seconds());
+
+        // Projectors which just started don't click until their next frame.
+        if let Some(&(frame, splice)) = positions.get(&entity) {
+            if position.0 != frame {
+                events.send(FilmProjectorEvent {
+                    camera: entity,
+                    kind: FilmProjectorEventKind::Frame,
+                });
+            }
+
+            if position.1 != splice {
+                events.send(FilmProjectorEvent {
+                    camera: entity,
+                    kind: FilmProjectorEventKind::Splice,
+                });
+            }
+        }
+
+        current.insert(entity, position);
+    }
+
+    *positions = current;
+}
+
+fn prepare(
+    data: Res<FilmProjectorData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<FilmProjector>,
+        &Blend<FilmProjector>,
+        &Resolution<FilmProjector>,
+        &SourceSampler<FilmProjector>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::film_projector::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<FilmProjectorUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<FilmProjectorData>,
+    mut bind_group: ResMut<UniformBindGroup<FilmProjectorUniform>>,
+    uniforms: Res<ComponentUniforms<FilmProjectorUniform>>,
+    views: Query<Entity, With<FilmProjectorUniform>>,
+) {
+    let _span = info_span!("vfx::film_projector::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Film Projector Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Projects the image like an old film projector, with shutter flicker, gate weave and splices.
+///
+/// Unlike a worn print, the image itself stays clean. This is about the projector:
+/// the light flickers as the shutter blades cut it at twice or three times the frame rate,
+/// the film weaves in the gate from frame to frame,
+/// and every so often a splice passes through and the image slips.
+///
+/// Play sounds in sync with the image by reading [`FilmProjectorEvent`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct FilmProjector {
+    /// How many frames of film are projected per second.
+    /// Decides how often the projector clicks, and how fast the film weaves.
+    pub frame_rate: f32,
+
+    /// How many shutter blades the projector has. Each blade cuts the light once per frame,
+    /// so two blades flicker at 48 Hz and three at 72 Hz for 24 frames per second.
+    pub blades: u32,
+
+    /// How much the brightness flickers, between `0.0` (steady) and `1.0`.
+    pub flicker: f32,
+
+    /// How far the film weaves in the gate, in UV space. `0.0` holds the image still.
+    pub weave: f32,
+
+    /// The seconds between splices. `0.0` means the film has no splices.
+    pub splice_interval: f32,
+
+    /// How long the image takes to settle after a splice, in seconds.
+    pub splice_duration: f32,
+
+    /// How far the image slips at a splice, in heights of the image.
+    pub splice_jump: f32,
+}
+
+impl FilmProjector {
+    /// The frame and the splice the projector is at, at the given time of the
+    /// [`VfxTime`] clock. The shader works these out the same way.
+    fn position(&self, elapsed: f32) -> (i64, i64) {
+        let frame = (elapsed * self.frame_rate.max(0.0)).floor() as i64;
+        let splice = if self.splice_interval > 0.0 && self.splice_duration > 0.0 {
+            (elapsed / self.splice_interval).floor() as i64
+        } else {
+            0
+        };
+
+        (frame, splice)
+    }
+}
+
+impl Default for FilmProjector {
+    fn default() -> Self {
+        Self {
+            frame_rate: 24.0,
+            blades: 2,
+            flicker: 0.15,
+            weave: 0.002,
+            splice_interval: 8.0,
+            splice_duration: 0.3,
+            splice_jump: 0.25,
+        }
+    }
+}
+
+impl Display for FilmProjector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Film Projector frame rate: {:.0}, blades: {}, flicker: {:.2}, weave: {:.4}, splice interval: {:.1}, splice duration: {:.2}, splice jump: {:.2}",
+            self.frame_rate,
+            self.blades,
+            self.flicker,
+            self.weave,
+            self.splice_interval,
+            self.splice_duration,
+            self.splice_jump
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`FilmProjector`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct FilmProjectorUniform {
+    pub(crate) frame_rate: f32,
+    pub(crate) blades: f32,
+    pub(crate) flicker: f32,
+    pub(crate) weave: f32,
+    pub(crate) splice_interval: f32,
+    pub(crate) splice_duration: f32,
+    pub(crate) splice_jump: f32,
+}
+
+impl From<FilmProjector> for FilmProjectorUniform {
+    fn from(projector: FilmProjector) -> Self {
+        Self {
+            frame_rate: projector.frame_rate,
+            blades: projector.blades as f32,
+            flicker: projector.flicker,
+            weave: projector.weave,
+            splice_interval: projector.splice_interval,
+            splice_duration: projector.splice_duration,
+            splice_jump: projector.splice_jump,
+        }
+    }
+}
+
+impl ExtractComponent for FilmProjector {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = FilmProjectorUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::film_projector::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
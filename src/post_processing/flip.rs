@@ -20,7 +20,9 @@ use std::fmt::Display;
 
 use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
 
-use super::{Order, PostProcessingPhaseItem};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
 
 pub(crate) const FLIP_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1649866799156783187);
@@ -66,6 +68,9 @@ impl bevy::prelude::Plugin for Plugin {
             Shader::from_wgsl
         );
 
+        app.register_type::<Flip>();
+        super::register_effect_types::<Flip>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins((
             ExtractComponentPlugin::<Flip>::default(),
@@ -91,10 +96,15 @@ fn prepare(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<Flip>,
+        &Blend<Flip>,
+        &Resolution<Flip>,
+        &SourceSampler<Flip>,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
+    let _span = info_span!("vfx::flip::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
         let draw_function = draw_functions
             .read()
             .id::<DrawPostProcessingEffect<FlipUniform>>();
@@ -102,6 +112,9 @@ fn prepare(
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
             pipeline_id: data.pipeline_id,
         });
@@ -115,6 +128,8 @@ fn queue(
     uniforms: Res<ComponentUniforms<FlipUniform>>,
     views: Query<Entity, With<FlipUniform>>,
 ) {
+    let _span = info_span!("vfx::flip::queue").entered();
+
     bind_group.inner = None;
 
     if let Some(uniforms) = uniforms.binding() {
@@ -153,7 +168,9 @@ impl From<Flip> for FlipUniform {
 }
 
 /// Which way to flip the texture.
-#[derive(Debug, Default, Copy, Clone, Component)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
 pub enum Flip {
     /// Don't flip.
     None,
@@ -181,6 +198,8 @@ impl ExtractComponent for Flip {
     type Out = FlipUniform;
 
     fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::flip::extract").entered();
+
         if !camera.is_active {
             return None;
         }
@@ -188,3 +207,22 @@ impl ExtractComponent for Flip {
         Some((*settings).into())
     }
 }
+
+#[cfg(feature = "material2d")]
+impl super::material::MaterialEffect for Flip {
+    type Uniform = FlipUniform;
+    type Key = ();
+
+    const MATERIAL_UUID: bevy::utils::Uuid =
+        bevy::utils::Uuid::from_u128(0xce2a2751ba0247b0a55443c7c2998a6b);
+
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        bevy::render::render_resource::ShaderRef::Handle(FLIP_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        (*self).into()
+    }
+
+    fn key(&self) {}
+}
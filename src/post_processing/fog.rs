@@ -0,0 +1,338 @@
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+use std::fmt::Display;
+
+use crate::post_processing::DrawPostProcessingEffect;
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const FOG_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13890173572322388799);
+
+#[derive(Resource)]
+pub(crate) struct FogData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+}
+
+impl FromWorld for FogData {
+    fn from_world(world: &mut World) -> Self {
+        let uniform_layout = super::create_layout(
+            world,
+            "Fog",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(FogUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+        FogData {
+            uniform_layout,
+            shared_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            FOG_SHADER_HANDLE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "fog.wgsl"),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Fog>();
+        app.register_type::<FogFalloff>();
+        super::register_effect_types::<Fog>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Fog>::default(),
+            UniformComponentPlugin::<FogUniform>::default(),
+        ))
+        .add_systems(PostUpdate, super::prepass::configure_prepasses::<Fog>);
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Fog>,
+            )
+            .init_resource::<FogData>()
+            .init_resource::<UniformBindGroup<FogUniform>>()
+            .init_resource::<SpecializedRenderPipelines<FogData>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<FogUniform>>();
+    }
+}
+
+impl SpecializedRenderPipeline for FogData {
+    type Key = FogFalloff;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Fog",
+            &self.shared_layout,
+            &self.uniform_layout,
+            FOG_SHADER_HANDLE.typed(),
+            vec![key.into()],
+        )
+    }
+}
+
+impl EffectPrepasses for Fog {
+    const WITHOUT: &'static str = "the fog will not look right";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+fn prepare(
+    data: Res<FogData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<FogData>>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Fog>,
+        &Blend<Fog>,
+        &Resolution<Fog>,
+        &SourceSampler<Fog>,
+        &FogFalloff,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::fog::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<FogUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, *key);
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<FogData>,
+    mut bind_group: ResMut<UniformBindGroup<FogUniform>>,
+    uniforms: Res<ComponentUniforms<FogUniform>>,
+    views: Query<Entity, With<FogUniform>>,
+) {
+    let _span = info_span!("vfx::fog::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Fog Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// How the fog thickens with distance from the camera.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FogFalloff {
+    /// The fog goes from no effect at [`Fog::start`] to full effect at [`Fog::end`].
+    #[default]
+    Linear,
+
+    /// The fog starts at [`Fog::start`] and thickens exponentially according to [`Fog::density`].
+    /// [`Fog::end`] is not used.
+    Exponential,
+
+    /// Like [`FogFalloff::Exponential`], but the exponent is squared.
+    /// This keeps the area near [`Fog::start`] clearer, and then thickens more abruptly.
+    ExponentialSquared,
+}
+
+impl From<FogFalloff> for ShaderDefVal {
+    fn from(falloff: FogFalloff) -> Self {
+        match falloff {
+            FogFalloff::Linear => "LINEAR",
+            FogFalloff::Exponential => "EXPONENTIAL",
+            FogFalloff::ExponentialSquared => "EXPONENTIAL_SQUARED",
+        }
+        .into()
+    }
+}
+
+/// Depth-aware fog.
+///
+/// Blends the scene towards a fog color based on the distance from the camera.
+///
+/// The distance is read from the depth prepass, so the camera needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) component, which is added along with the fog
+/// (see [`super::prepass::AutoPrepass`]). MSAA must also be off (`Msaa::Off`),
+/// since a multisampled depth texture can't be read by post processing effects.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Fog {
+    /// The color of the fog.
+    /// The alpha channel controls how opaque the fog is at its thickest.
+    pub color: Color,
+
+    /// The distance (in world units) from the camera where the fog starts.
+    pub start: f32,
+
+    /// The distance (in world units) from the camera where the fog is at its thickest.
+    /// Only used by [`FogFalloff::Linear`].
+    pub end: f32,
+
+    /// How quickly the fog thickens.
+    /// Only used by [`FogFalloff::Exponential`] and [`FogFalloff::ExponentialSquared`].
+    pub density: f32,
+
+    /// Which [`FogFalloff`] to use.
+    pub falloff: FogFalloff,
+}
+
+impl Fog {
+    /// Create a new linear fog between the given distances.
+    pub fn linear(color: Color, start: f32, end: f32) -> Self {
+        Self {
+            color,
+            start,
+            end,
+            falloff: FogFalloff::Linear,
+            ..default()
+        }
+    }
+
+    /// Create a new exponential fog with the given density.
+    pub fn exponential(color: Color, density: f32) -> Self {
+        Self {
+            color,
+            density,
+            falloff: FogFalloff::Exponential,
+            ..default()
+        }
+    }
+
+    /// Create a new exponential squared fog with the given density.
+    pub fn exponential_squared(color: Color, density: f32) -> Self {
+        Self {
+            color,
+            density,
+            falloff: FogFalloff::ExponentialSquared,
+            ..default()
+        }
+    }
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba(0.7, 0.75, 0.8, 1.0),
+            start: 5.0,
+            end: 30.0,
+            density: 0.1,
+            falloff: FogFalloff::Linear,
+        }
+    }
+}
+
+impl Display for Fog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Fog {:?}, start: {}, end: {}, density: {}",
+            self.falloff, self.start, self.end, self.density
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Fog`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct FogUniform {
+    pub(crate) color: Vec4,
+    pub(crate) start: f32,
+    pub(crate) end: f32,
+    pub(crate) density: f32,
+
+    /// Used to turn depth values into view space distances.
+    pub(crate) inverse_projection: Mat4,
+}
+
+impl ExtractComponent for Fog {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (FogUniform, FogFalloff);
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::fog::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((
+            FogUniform {
+                color: Vec4::from(settings.color.as_linear_rgba_f32()),
+                start: settings.start,
+                end: settings.end,
+                density: settings.density,
+                inverse_projection: camera.projection_matrix().inverse(),
+            },
+            settings.falloff,
+        ))
+    }
+}
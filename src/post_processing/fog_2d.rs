@@ -0,0 +1,358 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{BindGroupLayout, CachedRenderPipelineId, ShaderType},
+        renderer::RenderDevice,
+        texture::FallbackImageZero,
+        view::{Layer, RenderLayers},
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    layer_images::{LayerImages, LayerImagesEffect, LayerImagesPlugin},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const FOG_2D_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9317760240582310946);
+
+/// How many bands and layers the shader handles, the rest are ignored.
+const MAX_BANDS: usize = 4;
+const MAX_LAYERS: usize = 4;
+
+#[derive(Resource)]
+pub(crate) struct Fog2dData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for Fog2dData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Fog 2d",
+            &super::uniform_and_images_layout_entries(Fog2dUniform::min_size(), MAX_LAYERS),
+            FOG_2D_SHADER_HANDLE.typed(),
+        );
+
+        Fog2dData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            FOG_2D_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "fog_2d.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Fog2d>()
+            .register_type::<Fog2dBand>()
+            .register_type::<Fog2dLayer>();
+        super::register_effect_types::<Fog2d>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Fog2d>::default(),
+            UniformComponentPlugin::<Fog2dUniform>::default(),
+            LayerImagesPlugin::<Fog2d>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Fog2d>,
+            )
+            .init_resource::<Fog2dData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<Fog2dUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<Fog2dData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Fog2d>,
+        &Blend<Fog2d>,
+        &Resolution<Fog2d>,
+        &SourceSampler<Fog2d>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::fog_2d::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<Fog2dUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<Fog2dData>,
+    uniforms: Res<ComponentUniforms<Fog2dUniform>>,
+    images: Res<RenderAssets<Image>>,
+    fallback_zero: Res<FallbackImageZero>,
+    views: Query<(Entity, &LayerImages<Fog2d>)>,
+) {
+    let _span = info_span!("vfx::fog_2d::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, layers) in &views {
+        // Until the layers are drawn, everything is as far away as the background.
+        let layers = layers.gpu_images(MAX_LAYERS, &images, &fallback_zero);
+
+        let bind_group = super::uniform_and_images_bind_group(
+            &render_device,
+            "Fog 2d Uniform Bind Group",
+            &data.layout,
+            uniforms.clone(),
+            &layers,
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<Fog2dUniform>::new(bind_group));
+    }
+}
+
+/// A band of fog over a range of heights of the screen, see [`Fog2d::bands`].
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fog2dBand {
+    /// Where the fog starts, from `0.0` (the top of the screen) to `1.0` (the bottom).
+    pub start: f32,
+
+    /// Where the fog is at its thickest, from `0.0` (the top of the screen) to `1.0` (the bottom).
+    /// The fog stays at its thickest past this point.
+    ///
+    /// Greater than [`Fog2dBand::start`] for fog rising from the ground,
+    /// less than it for fog hanging from the top of the screen.
+    pub end: f32,
+
+    /// How thick the fog is at its thickest, from `0.0` to `1.0`.
+    pub amount: f32,
+}
+
+impl Fog2dBand {
+    /// A band of fog thickening from `start` to `end`, up to `amount`.
+    pub fn new(start: f32, end: f32, amount: f32) -> Self {
+        Self { start, end, amount }
+    }
+}
+
+/// A render layer whose sprites are at a given depth, see [`Fog2d::layers`].
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fog2dLayer {
+    /// The render layer of the sprites.
+    pub layer: Layer,
+
+    /// How far away the sprites on the layer are, in the same units as [`Fog2d::background_depth`].
+    pub depth: f32,
+}
+
+impl Fog2dLayer {
+    /// The sprites on `layer` are at `depth`.
+    pub fn new(layer: Layer, depth: f32) -> Self {
+        Self { layer, depth }
+    }
+}
+
+/// Fog for 2d scenes, which have no depth for [`super::fog::Fog`] to go by.
+///
+/// The fog comes from two sources, which add up:
+///
+/// - [`Fog2d::bands`] are ranges of heights of the screen, e.g. mist rising from the ground
+///   or hanging over the treetops.
+/// - [`Fog2d::layers`] give the sprites on some render layers a depth, e.g. the parallax
+///   layers of a side-scroller. Where nothing on these layers is drawn, the depth is
+///   [`Fog2d::background_depth`]. The fog thickens with depth according to [`Fog2d::density`],
+///   so far layers fade into the fog while near layers stay clear.
+///
+/// The layers are drawn by cameras of their own, which are added as children of this camera
+/// and kept in sync with it. Only the alpha of what they draw matters. Sprites which should
+/// both be seen and have a depth need both layers, e.g. `RenderLayers::from_layers(&[0, 2])`.
+///
+/// Needs an [`OrthographicProjection`], as 2d cameras have.
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Fog2d {
+    /// The color of the fog.
+    /// The alpha channel controls how opaque the fog is at its thickest.
+    pub color: Color,
+
+    /// Bands of fog over ranges of heights of the screen, up to four.
+    pub bands: Vec<Fog2dBand>,
+
+    /// Render layers with a depth, up to four, from the furthest to the nearest.
+    /// Where the sprites of several layers overlap, the later layer is in front.
+    pub layers: Vec<Fog2dLayer>,
+
+    /// How quickly the fog thickens with depth.
+    /// Only used when there are [`Fog2d::layers`].
+    pub density: f32,
+
+    /// The depth of what isn't on any of [`Fog2d::layers`].
+    pub background_depth: f32,
+}
+
+impl Fog2d {
+    /// No fog yet, in the given color.
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            bands: Vec::new(),
+            ..default()
+        }
+    }
+
+    /// The same fog, with another band.
+    pub fn with_band(mut self, band: Fog2dBand) -> Self {
+        self.bands.push(band);
+        self
+    }
+
+    /// The same fog, with another layer in front of the others.
+    pub fn with_layer(mut self, layer: Fog2dLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+}
+
+impl Default for Fog2d {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba(0.75, 0.8, 0.85, 1.0),
+            bands: vec![Fog2dBand::new(0.6, 1.0, 0.6)],
+            layers: Vec::new(),
+            density: 0.5,
+            background_depth: 4.0,
+        }
+    }
+}
+
+impl Display for Fog2d {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Fog 2d bands: {}, layers: {}, density: {:.2}, background depth: {:.2}",
+            self.bands.len(),
+            self.layers.len(),
+            self.density,
+            self.background_depth
+        )
+    }
+}
+
+impl LayerImagesEffect for Fog2d {
+    const LABEL: &'static str = "Fog 2d Layer";
+
+    // The fog is soft, so its edges don't need the full resolution.
+    const RESOLUTION_DIVISOR: u32 = 2;
+
+    fn layers(&self) -> Vec<RenderLayers> {
+        self.layers
+            .iter()
+            .take(MAX_LAYERS)
+            .map(|layer| RenderLayers::layer(layer.layer))
+            .collect()
+    }
+}
+
+#[doc(hidden)]
+/// [`Fog2d`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct Fog2dUniform {
+    pub(crate) color: Vec4,
+    /// Start, end and amount of each band.
+    pub(crate) bands: [Vec4; MAX_BANDS],
+    pub(crate) depths: Vec4,
+    pub(crate) band_count: u32,
+    pub(crate) layer_count: u32,
+    pub(crate) density: f32,
+    pub(crate) background_depth: f32,
+}
+
+impl ExtractComponent for Fog2d {
+    type Query = (&'static Self, &'static Camera, &'static LayerImages<Fog2d>);
+    type Filter = ();
+    type Out = (Fog2dUniform, LayerImages<Fog2d>);
+
+    fn extract_component(
+        (settings, camera, layers): QueryItem<'_, Self::Query>,
+    ) -> Option<Self::Out> {
+        let _span = info_span!("vfx::fog_2d::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        let mut bands = [Vec4::ZERO; MAX_BANDS];
+        for (band, settings) in bands.iter_mut().zip(&settings.bands) {
+            *band = Vec4::new(settings.start, settings.end, settings.amount, 0.0);
+        }
+
+        let mut depths = [0.0; MAX_LAYERS];
+        for (depth, settings) in depths.iter_mut().zip(&settings.layers) {
+            *depth = settings.depth;
+        }
+
+        Some((
+            Fog2dUniform {
+                color: Vec4::from(settings.color.as_linear_rgba_f32()),
+                bands,
+                depths: Vec4::from(depths),
+                band_count: settings.bands.len().min(MAX_BANDS) as u32,
+                layer_count: settings.layers.len().min(MAX_LAYERS) as u32,
+                density: settings.density,
+                background_depth: settings.background_depth,
+            },
+            layers.clone(),
+        ))
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_phase::RenderPhase,
+        render_resource::{CachedRenderPipelineId, PipelineCache, TextureFormat},
+        renderer::render_system,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::HashMap,
+};
+
+use super::PostProcessingPhaseItem;
+
+/// The pipelines effects are drawn with on cameras of each texture format.
+///
+/// Effects make their pipelines for one format (see [`super::default_target_format`]),
+/// but cameras with [`Camera::hdr`] have a different main texture format than those without.
+/// Drawing into a texture of another format is an error, so for each camera format an effect is
+/// used on, a variant of its pipeline is made which only differs in the format it draws into.
+///
+/// Items in the phase keep the pipeline the effect made, so it can still be matched against
+/// e.g. a [`super::history::FrameHistory`]. The node draws them with the variant instead.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct PipelineFormats {
+    variants: HashMap<(CachedRenderPipelineId, TextureFormat), CachedRenderPipelineId>,
+}
+
+impl PipelineFormats {
+    /// The pipeline to draw with into a texture of the given format, in place of the given one.
+    ///
+    /// `None` until the variant has been made, which happens once the pipeline itself is known.
+    pub(crate) fn pipeline(
+        &self,
+        id: CachedRenderPipelineId,
+        format: TextureFormat,
+    ) -> Option<CachedRenderPipelineId> {
+        self.variants.get(&(id, format)).copied()
+    }
+
+    /// The pipeline to draw with into a texture of the given format, in place of the given one,
+    /// which is made if it wasn't already.
+    ///
+    /// `None` if the given pipeline isn't known to the cache yet, since its descriptor is needed
+    /// for the variant.
+    pub(crate) fn queue_variant(
+        &mut self,
+        pipeline_cache: &PipelineCache,
+        id: CachedRenderPipelineId,
+        format: TextureFormat,
+    ) -> Option<CachedRenderPipelineId> {
+        if let Some(variant) = self.pipeline(id, format) {
+            return Some(variant);
+        }
+
+        // Pipelines specialized this frame are only known once the cache processes its queue.
+        super::pipeline_state(pipeline_cache, id)?;
+
+        let descriptor = pipeline_cache.get_render_pipeline_descriptor(id);
+        let made_for = descriptor
+            .fragment
+            .as_ref()
+            .and_then(|fragment| fragment.targets.first())
+            .and_then(|target| target.as_ref())
+            .map(|target| target.format);
+
+        let variant = if made_for == Some(format) {
+            id
+        } else {
+            debug!(
+                "Making a {format:?} variant of {}",
+                descriptor
+                    .label
+                    .as_deref()
+                    .unwrap_or("a post processing pipeline")
+            );
+            pipeline_cache
+                .queue_render_pipeline(super::with_target_format(descriptor.clone(), format))
+        };
+
+        self.variants.insert((id, format), variant);
+        Some(variant)
+    }
+}
+
+/// Makes the variants of the pipelines of the effects on each camera, for the camera's format.
+fn queue_variants(
+    pipeline_cache: Res<PipelineCache>,
+    mut formats: ResMut<PipelineFormats>,
+    views: Query<(&ViewTarget, &RenderPhase<PostProcessingPhaseItem>)>,
+) {
+    let _span = info_span!("vfx::formats::queue_variants").entered();
+
+    for (view_target, phase) in &views {
+        let format = view_target.main_texture_format();
+
+        for item in &phase.items {
+            formats.queue_variant(&pipeline_cache, item.pipeline_id, format);
+        }
+    }
+}
+
+/// Makes variants of effect pipelines for cameras of other texture formats.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        super::render_app(app)
+            .init_resource::<PipelineFormats>()
+            // After the phases are sorted, since that is where effects are combined into new pipelines.
+            .add_systems(
+                Render,
+                queue_variants
+                    .in_set(RenderSet::Render)
+                    .before(render_system),
+            );
+    }
+}
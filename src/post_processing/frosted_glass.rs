@@ -0,0 +1,344 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            AddressMode, BindGroupLayout, CachedRenderPipelineId, Extent3d, SamplerDescriptor,
+            ShaderType, TextureDimension, TextureFormat,
+        },
+        renderer::RenderDevice,
+        texture::ImageSampler,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, raindrops::RefractionMode, resolution::Resolution, sampler::SourceSampler,
+    DrawPostProcessingEffect, Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const FROSTED_GLASS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13053146916090895184);
+const FROSTED_GLASS_NORMALS_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 11573996065850338623);
+
+/// A tiling normal map of the bumps of frosted glass, with the `x` and `y` of the normals
+/// in the red and green channels.
+///
+/// Generated rather than shipped as a file, so it tiles seamlessly.
+fn normals_image() -> Image {
+    const SIZE: usize = 128;
+
+    /// A random value at a point of a lattice which repeats every `period` points.
+    fn lattice(x: usize, y: usize, period: usize) -> f32 {
+        let mut hash = ((x % period) as u32).wrapping_mul(0x27d4_eb2d)
+            ^ ((y % period) as u32).wrapping_mul(0x1656_67b1);
+        hash ^= hash >> 15;
+        hash = hash.wrapping_mul(0x85eb_ca6b);
+        hash ^= hash >> 13;
+
+        (hash & 0xffff) as f32 / 0xffff as f32
+    }
+
+    /// Smoothly interpolated lattice values, with the given number of cells across the image.
+    fn value_noise(x: f32, y: f32, cells: usize) -> f32 {
+        let (x, y) = (
+            x * cells as f32 / SIZE as f32,
+            y * cells as f32 / SIZE as f32,
+        );
+        let (cell_x, cell_y) = (x.floor() as usize, y.floor() as usize);
+        let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+        let (tx, ty) = (smooth(x.fract()), smooth(y.fract()));
+
+        let top =
+            lattice(cell_x, cell_y, cells) * (1.0 - tx) + lattice(cell_x + 1, cell_y, cells) * tx;
+        let bottom = lattice(cell_x, cell_y + 1, cells) * (1.0 - tx)
+            + lattice(cell_x + 1, cell_y + 1, cells) * tx;
+
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    // Fine grain over a few larger bumps, like the surface of frosted glass.
+    let height = |x: f32, y: f32| {
+        value_noise(x, y, 8) * 0.25 + value_noise(x, y, 32) * 0.35 + value_noise(x, y, 64) * 0.4
+    };
+
+    let mut data = vec![0; SIZE * SIZE * 4];
+    for (index, texel) in data.chunks_exact_mut(4).enumerate() {
+        let (x, y) = ((index % SIZE) as f32, (index / SIZE) as f32);
+
+        // The slope, wrapped around the edges like the noise.
+        let wrap = |v: f32| v.rem_euclid(SIZE as f32);
+        let dx = height(wrap(x + 1.0), y) - height(wrap(x - 1.0), y);
+        let dy = height(x, wrap(y + 1.0)) - height(x, wrap(y - 1.0));
+        let normal = Vec3::new(-dx * 8.0, -dy * 8.0, 1.0).normalize();
+
+        texel[0] = ((normal.x * 0.5 + 0.5) * 255.0) as u8;
+        texel[1] = ((normal.y * 0.5 + 0.5) * 255.0) as u8;
+        texel[2] = ((normal.z * 0.5 + 0.5) * 255.0) as u8;
+        texel[3] = 255;
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE as u32,
+            height: SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+    );
+
+    image.sampler_descriptor = ImageSampler::Descriptor(SamplerDescriptor {
+        label: Some("Frosted Glass Normals Sampler"),
+        address_mode_u: AddressMode::Repeat,
+        address_mode_v: AddressMode::Repeat,
+        ..ImageSampler::linear_descriptor()
+    });
+
+    image
+}
+
+#[derive(Resource)]
+pub(crate) struct FrostedGlassData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for FrostedGlassData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Frosted Glass",
+            &super::uniform_and_images_layout_entries(FrostedGlassUniform::min_size(), 1),
+            FROSTED_GLASS_SHADER_HANDLE.typed(),
+        );
+
+        FrostedGlassData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            FROSTED_GLASS_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "frosted_glass.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.world
+            .resource_mut::<Assets<Image>>()
+            .set_untracked(FROSTED_GLASS_NORMALS_IMAGE_HANDLE, normals_image());
+
+        app.register_type::<FrostedGlass>();
+        super::register_effect_types::<FrostedGlass>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<FrostedGlass>::default(),
+            UniformComponentPlugin::<FrostedGlassUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<FrostedGlass>,
+            )
+            .init_resource::<FrostedGlassData>()
+            .init_resource::<UniformBindGroup<FrostedGlassUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<FrostedGlassUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<FrostedGlassData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<FrostedGlass>,
+        &Blend<FrostedGlass>,
+        &Resolution<FrostedGlass>,
+        &SourceSampler<FrostedGlass>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::frosted_glass::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<FrostedGlassUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<FrostedGlassData>,
+    mut bind_group: ResMut<UniformBindGroup<FrostedGlassUniform>>,
+    uniforms: Res<ComponentUniforms<FrostedGlassUniform>>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<Entity, With<FrostedGlassUniform>>,
+) {
+    let _span = info_span!("vfx::frosted_glass::queue").entered();
+
+    bind_group.inner = None;
+
+    let normals = images.get(&FROSTED_GLASS_NORMALS_IMAGE_HANDLE.typed_weak());
+    if let (Some(uniforms), Some(normals)) = (uniforms.binding(), normals) {
+        if !views.is_empty() {
+            bind_group.inner = Some(super::uniform_and_images_bind_group(
+                &render_device,
+                "Frosted Glass Uniform Bind Group",
+                &data.layout,
+                uniforms,
+                &[normals],
+            ));
+        }
+    }
+}
+
+/// Looks through a pane of frosted glass: the image is refracted by the bumps of the glass
+/// and slightly blurred.
+///
+/// Useful behind pause menus, or as an ice/freeze status effect (see [`FrostedGlass::ice`]).
+/// The bumps come from a tiling noise normal map, which can slowly drift across the image.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct FrostedGlass {
+    /// How far the bumps of the glass displace the image, in pixels.
+    pub distortion: f32,
+
+    /// How large the bumps are, in pixels per repeat of the normal map.
+    pub distortion_scale: f32,
+
+    /// How far the image is blurred, in pixels.
+    pub blur_radius: f32,
+
+    /// How fast the bumps move across the image, in repeats of the normal map per second.
+    pub drift: Vec2,
+
+    /// The color of the glass. The alpha is how much of it is mixed into the image.
+    pub tint: Color,
+
+    /// How the glass bends the image. The default is tuned for 3d cameras,
+    /// use [`RefractionMode::flat`] for 2d scenes.
+    pub refraction: RefractionMode,
+}
+
+impl FrostedGlass {
+    /// Thick, bluish ice, e.g. for a freeze status effect.
+    pub fn ice() -> Self {
+        Self {
+            distortion: 24.0,
+            distortion_scale: 384.0,
+            blur_radius: 6.0,
+            drift: Vec2::ZERO,
+            tint: Color::rgba(0.7, 0.85, 1.0, 0.35),
+            refraction: RefractionMode::Perspective,
+        }
+    }
+}
+
+impl Default for FrostedGlass {
+    fn default() -> Self {
+        Self {
+            distortion: 10.0,
+            distortion_scale: 256.0,
+            blur_radius: 4.0,
+            drift: Vec2::new(0.01, 0.02),
+            tint: Color::rgba(0.95, 0.97, 1.0, 0.1),
+            refraction: RefractionMode::Perspective,
+        }
+    }
+}
+
+impl Display for FrostedGlass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Frosted Glass distortion: {:.1}, distortion scale: {:.1}, blur radius: {:.1}, drift: [{:.3}, {:.3}]",
+            self.distortion, self.distortion_scale, self.blur_radius, self.drift.x, self.drift.y
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`FrostedGlass`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct FrostedGlassUniform {
+    pub(crate) tint: Vec4,
+    pub(crate) drift: Vec2,
+    pub(crate) distortion: f32,
+    pub(crate) distortion_scale: f32,
+    pub(crate) blur_radius: f32,
+    pub(crate) parallax: f32,
+    pub(crate) scroll: Vec2,
+}
+
+impl ExtractComponent for FrostedGlass {
+    type Query = (
+        &'static Self,
+        &'static Camera,
+        &'static GlobalTransform,
+        Option<&'static OrthographicProjection>,
+    );
+    type Filter = ();
+    type Out = FrostedGlassUniform;
+
+    fn extract_component(
+        (settings, camera, transform, projection): QueryItem<'_, Self::Query>,
+    ) -> Option<Self::Out> {
+        let _span = info_span!("vfx::frosted_glass::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        // The bumps are in pixels, so they keep their shape in 2d scenes either way.
+        let (_, parallax, scroll) = settings.refraction.uniform(transform, projection);
+
+        Some(FrostedGlassUniform {
+            tint: Vec4::from(settings.tint.as_linear_rgba_f32()),
+            drift: settings.drift,
+            distortion: settings.distortion,
+            distortion_scale: settings.distortion_scale,
+            blur_radius: settings.blur_radius,
+            parallax,
+            scroll,
+        })
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, CachedRenderPipelineId, FilterMode,
+            Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages, ShaderType,
+            TextureSampleType, TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, history::FrameHistories, resolution::Resolution, sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const GHOSTING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 18385838300738682028);
+
+#[derive(Resource)]
+pub(crate) struct GhostingData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for GhostingData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Ghosting",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(Ghosting::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The image as of the previous frame.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            GHOSTING_SHADER_HANDLE.typed(),
+        );
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Ghosting history sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        GhostingData {
+            pipeline_id,
+            layout,
+            sampler,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            GHOSTING_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "ghosting.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Ghosting>();
+        super::register_effect_types::<Ghosting>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Ghosting>::default(),
+            UniformComponentPlugin::<Ghosting>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Ghosting>,
+            )
+            .init_resource::<GhostingData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<Ghosting>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<GhostingData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Ghosting>,
+        &Blend<Ghosting>,
+        &Resolution<Ghosting>,
+        &SourceSampler<Ghosting>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::ghosting::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<Ghosting>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<GhostingData>,
+    uniforms: Res<ComponentUniforms<Ghosting>>,
+    mut histories: ResMut<FrameHistories>,
+    views: Query<(Entity, &ExtractedCamera, &ViewTarget), With<Ghosting>>,
+) {
+    let _span = info_span!("vfx::ghosting::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, camera, view_target) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let history = histories.request(
+            &render_device,
+            entity,
+            size,
+            view_target.main_texture_format(),
+            data.pipeline_id,
+        );
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Ghosting Uniform Bind Group"),
+            layout: &data.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&history.view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&data.sampler),
+                },
+            ],
+        });
+
+        commands
+            .entity(entity)
+            .insert((ViewUniformBindGroup::<Ghosting>::new(bind_group), history));
+    }
+}
+
+/// Trails behind everything that moves, like being drunk, dreaming, or worse.
+///
+/// Each frame is blended with the previous output, so the image fades out slowly
+/// instead of changing at once. The previous output can drift and zoom each frame,
+/// making the trails flow away from what leaves them.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Ghosting {
+    /// How much of the previous output remains each frame, between `0.0` (none) and `1.0`.
+    /// Higher values give longer trails.
+    pub decay: f32,
+
+    /// How far the previous output moves each frame, in UV space.
+    pub drift: Vec2,
+
+    /// How much the previous output is scaled each frame, around the center of the view.
+    /// Above `1.0` the trails grow outwards, below they shrink inwards.
+    pub zoom: f32,
+}
+
+impl Default for Ghosting {
+    fn default() -> Self {
+        Self {
+            decay: 0.85,
+            drift: Vec2::ZERO,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Display for Ghosting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Ghosting decay: {:.2}, drift: [{:.4}, {:.4}], zoom: {:.3}",
+            self.decay, self.drift.x, self.drift.y, self.zoom
+        )
+    }
+}
+
+impl ExtractComponent for Ghosting {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::ghosting::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
@@ -0,0 +1,416 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, CachedRenderPipelineId, Extent3d,
+            FilterMode, Sampler, SamplerBindingType, SamplerDescriptor, ShaderDefVal, ShaderStages,
+            ShaderType, TextureDescriptor, TextureDimension, TextureSampleType, TextureUsages,
+            TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        texture::{FallbackImage, TextureCache},
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::HashSet,
+};
+
+use super::{
+    blend::Blend,
+    effect_mask::{EffectMaskChannel, ViewEffectMask},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingViewEffect, ImageCapture, Order, PostProcessingPhaseItem,
+    ViewUniformBindGroup,
+};
+
+pub(crate) const GLOW_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 17583239922405721162);
+
+/// The passes making up the glow effect, in the order they run.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub(crate) enum GlowPass {
+    /// Keeps the parts of the image brighter than the threshold.
+    Threshold,
+
+    /// Averages blocks of pixels, so the blur works on a smoother image.
+    Downsample,
+
+    /// Blurs horizontally.
+    BlurHorizontal,
+
+    /// Blurs vertically.
+    BlurVertical,
+
+    /// Adds the blurred bright parts onto the image from before the glow.
+    Composite,
+}
+
+impl GlowPass {
+    const ALL: [GlowPass; 5] = [
+        Self::Threshold,
+        Self::Downsample,
+        Self::BlurHorizontal,
+        Self::BlurVertical,
+        Self::Composite,
+    ];
+
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            Self::Threshold => vec!["THRESHOLD".into()],
+            Self::Downsample => vec!["DOWNSAMPLE".into()],
+            Self::BlurHorizontal => vec!["BLUR".into()],
+            Self::BlurVertical => vec!["BLUR".into(), "BLUR_VERTICAL".into()],
+            Self::Composite => vec!["COMPOSITE".into()],
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct GlowData {
+    /// The pipelines of the passes, in the order of [`GlowPass::ALL`].
+    pub pipeline_ids: [CachedRenderPipelineId; 5],
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for GlowData {
+    fn from_world(world: &mut World) -> Self {
+        let layout = super::create_layout(
+            world,
+            "Glow",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(GlowUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The image from before the glow, for the composite pass.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // The effect mask of the camera, for the threshold pass.
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        );
+
+        // The passes have no variants, so they are all compiled up front.
+        let pipeline_ids = GlowPass::ALL.map(|pass| {
+            super::create_pipeline(
+                world,
+                "Glow",
+                &layout,
+                GLOW_SHADER_HANDLE.typed(),
+                pass.shader_defs(),
+            )
+        });
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Glow original sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        GlowData {
+            pipeline_ids,
+            layout,
+            sampler,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            GLOW_SHADER_HANDLE,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/shaders/", "glow.wgsl"),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Glow>();
+        super::register_effect_types::<Glow>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Glow>::default(),
+            UniformComponentPlugin::<GlowUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Glow>,
+            )
+            .init_resource::<GlowData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<GlowUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<GlowData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Glow>,
+        &Blend<Glow>,
+        &Resolution<Glow>,
+        &SourceSampler<Glow>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::glow::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<GlowUniform>>();
+
+        // All passes share the sort key, and are kept in this order when sorted.
+        for pipeline_id in data.pipeline_ids {
+            phase.add(PostProcessingPhaseItem {
+                entity,
+                sort_key: (*order).into(),
+                blend: (*blend).into(),
+                resolution: (*resolution).into(),
+                sampler: (*sampler).into(),
+                draw_function,
+                pipeline_id,
+            });
+        }
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<GlowData>,
+    uniforms: Res<ComponentUniforms<GlowUniform>>,
+    mut texture_cache: ResMut<TextureCache>,
+    fallback_image: Res<FallbackImage>,
+    mut warned: Local<HashSet<Entity>>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ViewTarget,
+        &GlowUniform,
+        Option<&ViewEffectMask>,
+    )>,
+) {
+    let _span = info_span!("vfx::glow::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, camera, view_target, uniform, effect_mask) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        if uniform.masked != 0 && effect_mask.is_none() && warned.insert(entity) {
+            warn!("The glow on camera {entity:?} comes from an emitter mask, but the camera has no `EffectMask` so it comes from everywhere");
+        }
+
+        // Without a mask, the white fallback lets the glow come from everywhere.
+        let mask_view = effect_mask.map_or(&fallback_image.d2.texture_view, |mask| mask.view());
+
+        // The passes in between overwrite the image, so it's kept for the composite pass.
+        let original = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("Glow original"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_target.main_texture_format(),
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Glow Uniform Bind Group"),
+            layout: &data.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&original.default_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&data.sampler),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(mask_view),
+                },
+            ],
+        });
+
+        commands.entity(entity).insert((
+            ViewUniformBindGroup::<GlowUniform>::new(bind_group),
+            ImageCapture {
+                pipeline_id: data.pipeline_ids[0],
+                texture: original.texture,
+            },
+        ));
+    }
+}
+
+/// A bloom-style glow around the bright parts of the image.
+///
+/// The parts brighter than [`Glow::threshold`] are blurred and added back onto the image.
+/// Unlike Bevy's bloom, this runs among the other effects, so it can be ordered
+/// relative to them, e.g. before a [`super::vision_mode::VisionMode`].
+///
+/// The effect runs in several passes: threshold, downsample, a horizontal and a vertical blur,
+/// and the composite.
+///
+/// Brightness alone can't tell an emitter from a white wall or white UI text.
+/// To only let the intended emitters glow, set [`Glow::emitter_mask`] to a channel of the
+/// camera's [`EffectMask`](super::effect_mask::EffectMask), and draw the emitters into it,
+/// e.g. by tagging them with a `SelectiveEffect` (with the `selective` feature).
+/// The glow then only comes from where the channel is drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Glow {
+    /// How strongly the glow is added onto the image.
+    pub intensity: f32,
+
+    /// How bright a pixel must be to glow, where `1.0` is full brightness in non-HDR images.
+    pub threshold: f32,
+
+    /// How gradually pixels below the threshold start to glow, as a fraction of the threshold.
+    /// `0.0` is a hard cutoff.
+    pub knee: f32,
+
+    /// The color the glow is multiplied with.
+    pub tint: Color,
+
+    /// How far the glow spreads, in pixels.
+    pub radius: f32,
+
+    /// The channel of the camera's [`EffectMask`](super::effect_mask::EffectMask) the glow
+    /// comes from, if it should only come from there.
+    ///
+    /// With a mask, a [`Glow::threshold`] of `0.0` lets what is drawn in the channel glow
+    /// however bright it is. `SelectiveEffect` entities are drawn into the mask even behind
+    /// other geometry, so with a threshold that low, what is in front of them glows too.
+    pub emitter_mask: Option<EffectMaskChannel>,
+}
+
+impl Default for Glow {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            threshold: 0.8,
+            knee: 0.5,
+            tint: Color::WHITE,
+            radius: 16.0,
+            emitter_mask: None,
+        }
+    }
+}
+
+impl Display for Glow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Glow intensity: {:.2}, threshold: {:.2}, knee: {:.2}, tint: {:?}, radius: {:.1}, emitter mask: {:?}",
+            self.intensity, self.threshold, self.knee, self.tint, self.radius, self.emitter_mask
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Glow`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct GlowUniform {
+    pub(crate) tint: Vec4,
+    pub(crate) intensity: f32,
+    pub(crate) threshold: f32,
+    pub(crate) knee: f32,
+    pub(crate) radius: f32,
+    pub(crate) masked: u32,
+    pub(crate) mask_channel: u32,
+}
+
+impl ExtractComponent for Glow {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = GlowUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::glow::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(GlowUniform {
+            tint: Vec4::from(settings.tint.as_linear_rgba_f32()),
+            intensity: settings.intensity,
+            threshold: settings.threshold,
+            knee: settings.knee,
+            radius: settings.radius,
+            masked: settings.emitter_mask.is_some().into(),
+            mask_channel: settings.emitter_mask.unwrap_or_default().index(),
+        })
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{BindGroupLayout, CachedRenderPipelineId, ShaderType},
+        renderer::RenderDevice,
+        texture::FallbackImageZero,
+        view::RenderLayers,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    layer_images::{LayerImages, LayerImagesEffect, LayerImagesPlugin},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const GOD_RAYS_2D_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 12614882011432035212);
+
+#[derive(Resource)]
+pub(crate) struct GodRays2dData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for GodRays2dData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "God Rays 2d",
+            &super::uniform_and_images_layout_entries(GodRays2dUniform::min_size(), 1),
+            GOD_RAYS_2D_SHADER_HANDLE.typed(),
+        );
+
+        GodRays2dData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            GOD_RAYS_2D_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "god_rays_2d.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<GodRays2d>();
+        super::register_effect_types::<GodRays2d>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<GodRays2d>::default(),
+            UniformComponentPlugin::<GodRays2dUniform>::default(),
+            LayerImagesPlugin::<GodRays2d>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<GodRays2d>,
+            )
+            .init_resource::<GodRays2dData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<GodRays2dUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<GodRays2dData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<GodRays2d>,
+        &Blend<GodRays2d>,
+        &Resolution<GodRays2d>,
+        &SourceSampler<GodRays2d>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::god_rays_2d::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<GodRays2dUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<GodRays2dData>,
+    uniforms: Res<ComponentUniforms<GodRays2dUniform>>,
+    images: Res<RenderAssets<Image>>,
+    fallback_zero: Res<FallbackImageZero>,
+    views: Query<(Entity, &LayerImages<GodRays2d>)>,
+) {
+    let _span = info_span!("vfx::god_rays_2d::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, occluders) in &views {
+        // Until the occluders are drawn, nothing blocks the light.
+        let occluders = occluders.gpu_images(1, &images, &fallback_zero);
+
+        let bind_group = super::uniform_and_images_bind_group(
+            &render_device,
+            "God Rays 2d Uniform Bind Group",
+            &data.layout,
+            uniforms.clone(),
+            &occluders,
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<GodRays2dUniform>::new(bind_group));
+    }
+}
+
+/// Light shafts (god rays) streaming from a light in a 2d scene, blocked by sprites.
+///
+/// Unlike [`super::light_shafts::LightShafts`], which lets the bright parts of the image cast
+/// the shafts, the light is a disk at [`GodRays2d::light_position`], and whatever is drawn on
+/// [`GodRays2d::occluders`] blocks it. So a light behind the platforms of a side-scroller
+/// casts shafts through the gaps between them.
+///
+/// The occluders are drawn by a camera of their own, which is added as a child of this camera
+/// and kept in sync with it. It draws the entities on the occluder layers, and only their
+/// alpha matters. Entities which should both block the light and be seen need both layers,
+/// e.g. `RenderLayers::from_layers(&[0, 1])`.
+///
+/// Needs an [`OrthographicProjection`], as 2d cameras have.
+/// Keep the position on a light entity with [`Camera::world_to_viewport`], divided by
+/// the size of the viewport.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct GodRays2d {
+    /// The position of the light, in UV space (`(0, 0)` is the top left corner).
+    pub light_position: Vec2,
+
+    /// The radius of the light, as a fraction of the height of the image.
+    pub light_radius: f32,
+
+    /// The color of the rays, in which the alpha scales their brightness.
+    pub color: Color,
+
+    /// The render layers of the entities which block the light.
+    ///
+    /// Render layers can't be serialized, so this is left at the default layer `1` when deserialized.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub occluders: RenderLayers,
+
+    /// How far towards the light the samples reach, where `1.0` is all the way.
+    pub density: f32,
+
+    /// How much each sample counts compared to the one before it.
+    /// Below `1.0`, the rays fade with distance from the light.
+    pub decay: f32,
+
+    /// The overall brightness of the rays.
+    pub exposure: f32,
+
+    /// How many samples are taken towards the light, up to `128`.
+    /// More samples give smoother rays, but cost more.
+    pub samples: u32,
+}
+
+impl Default for GodRays2d {
+    fn default() -> Self {
+        Self {
+            light_position: Vec2::new(0.5, 0.2),
+            light_radius: 0.08,
+            color: Color::rgb(1.0, 0.9, 0.7),
+            occluders: RenderLayers::layer(1),
+            density: 0.9,
+            decay: 0.97,
+            exposure: 1.5,
+            samples: 64,
+        }
+    }
+}
+
+impl Display for GodRays2d {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "God Rays 2d at [{:.2}, {:.2}], radius: {:.2}, density: {:.2}, decay: {:.3}, exposure: {:.2}, samples: {}",
+            self.light_position.x,
+            self.light_position.y,
+            self.light_radius,
+            self.density,
+            self.decay,
+            self.exposure,
+            self.samples
+        )
+    }
+}
+
+impl LayerImagesEffect for GodRays2d {
+    const LABEL: &'static str = "God Rays 2d Occluders";
+
+    // The rays are blurry anyway.
+    const RESOLUTION_DIVISOR: u32 = 2;
+
+    fn layers(&self) -> Vec<RenderLayers> {
+        vec![self.occluders]
+    }
+}
+
+#[doc(hidden)]
+/// [`GodRays2d`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct GodRays2dUniform {
+    pub(crate) color: Vec4,
+    pub(crate) light_position: Vec2,
+    pub(crate) light_radius: f32,
+    pub(crate) density: f32,
+    pub(crate) decay: f32,
+    pub(crate) exposure: f32,
+    pub(crate) samples: u32,
+}
+
+impl ExtractComponent for GodRays2d {
+    type Query = (
+        &'static Self,
+        &'static Camera,
+        &'static LayerImages<GodRays2d>,
+    );
+    type Filter = ();
+    type Out = (GodRays2dUniform, LayerImages<GodRays2d>);
+
+    fn extract_component(
+        (settings, camera, occluders): QueryItem<'_, Self::Query>,
+    ) -> Option<Self::Out> {
+        let _span = info_span!("vfx::god_rays_2d::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((
+            GodRays2dUniform {
+                color: Vec4::from(settings.color.as_linear_rgba_f32()),
+                light_position: settings.light_position,
+                light_radius: settings.light_radius,
+                density: settings.density,
+                decay: settings.decay,
+                exposure: settings.exposure,
+                samples: settings.samples.min(128),
+            },
+            occluders.clone(),
+        ))
+    }
+}
@@ -0,0 +1,204 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use bevy::{prelude::*, utils::HashMap};
+
+/// Puts the effect `C` on a camera into a named group, e.g. `"damage"` or `"underwater"`,
+/// which is enabled, disabled and faded as a whole via [`EffectGroups`].
+///
+/// Like [`super::intensity::Intensity`], this is per-camera and uses a generic marker to know
+/// which effect it applies to:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{
+/// #     chromatic_aberration::ChromaticAberration,
+/// #     group::{EffectGroup, EffectGroups},
+/// #     shockwave::Shockwave,
+/// # };
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     Shockwave::default(),
+///     EffectGroup::<Shockwave>::new("damage"),
+///     ChromaticAberration::default(),
+///     EffectGroup::<ChromaticAberration>::new("damage"),
+/// ));
+///
+/// // Later, e.g. when the player is hit.
+/// # let mut groups = EffectGroups::default();
+/// groups.enable("damage");
+/// ```
+///
+/// Members keep their [`super::Order`], so the group draws in the same order each time it is
+/// enabled again. An effect can only be in one group.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct EffectGroup<C> {
+    /// The name of the group.
+    pub name: String,
+
+    #[reflect(ignore)]
+    marker: PhantomData<C>,
+}
+
+impl<C> EffectGroup<C> {
+    /// Put the effect into the group with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for EffectGroup<C> {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl<C> Clone for EffectGroup<C> {
+    fn clone(&self) -> Self {
+        Self::new(self.name.clone())
+    }
+}
+
+impl<C> Display for EffectGroup<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Group {:?}", self.name)
+    }
+}
+
+/// Whether a group of effects is used, and how much.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Default)]
+pub struct GroupSettings {
+    /// How much of the effects is used, between `0.0` (none) and `1.0` (the full effects).
+    /// Applied on top of the [`super::intensity::Intensity`] of each effect.
+    pub intensity: f32,
+
+    /// If false, the effects of the group are skipped.
+    pub enabled: bool,
+}
+
+impl Default for GroupSettings {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            enabled: true,
+        }
+    }
+}
+
+impl Display for GroupSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "intensity: {:.2}, enabled: {}",
+            self.intensity, self.enabled
+        )
+    }
+}
+
+/// The settings of [`EffectGroup`]s by name.
+///
+/// As a resource, this applies to the groups on all cameras. As a component on a camera,
+/// it applies to the groups on that camera only, on top of the resource.
+/// Groups which aren't listed are enabled at full intensity.
+#[derive(Debug, Clone, Default, PartialEq, Resource, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource, Component, Default)]
+pub struct EffectGroups {
+    /// The settings of each group.
+    pub groups: HashMap<String, GroupSettings>,
+}
+
+impl EffectGroups {
+    /// The settings of the group with the given name.
+    pub fn settings(&self, name: &str) -> GroupSettings {
+        self.groups.get(name).copied().unwrap_or_default()
+    }
+
+    /// The settings of the group with the given name, to change them.
+    pub fn settings_mut(&mut self, name: impl Into<String>) -> &mut GroupSettings {
+        self.groups.entry(name.into()).or_default()
+    }
+
+    /// Enable the group with the given name.
+    pub fn enable(&mut self, name: impl Into<String>) {
+        self.settings_mut(name).enabled = true;
+    }
+
+    /// Disable the group with the given name, skipping its effects.
+    pub fn disable(&mut self, name: impl Into<String>) {
+        self.settings_mut(name).enabled = false;
+    }
+
+    /// Toggle the group with the given name.
+    pub fn toggle(&mut self, name: impl Into<String>) {
+        let settings = self.settings_mut(name);
+        settings.enabled = !settings.enabled;
+    }
+
+    /// Set the intensity of the group with the given name.
+    pub fn set_intensity(&mut self, name: impl Into<String>, intensity: f32) {
+        self.settings_mut(name).intensity = intensity;
+    }
+
+    /// Start with the group with the given name disabled.
+    pub fn with_disabled(mut self, name: impl Into<String>) -> Self {
+        self.disable(name);
+        self
+    }
+}
+
+impl Display for EffectGroups {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&String> = self.groups.keys().collect();
+        names.sort();
+
+        write!(f, "Effect groups")?;
+        for name in names {
+            write!(f, "\n  {name}: {}", self.groups[name])?;
+        }
+        Ok(())
+    }
+}
+
+/// How much of the effect `C` is used, given its group, between `0.0` and `1.0`.
+///
+/// `0.0` if its group is disabled, and `1.0` if it isn't in a group.
+pub(crate) fn group_factor<C>(
+    group: Option<&EffectGroup<C>>,
+    groups: &EffectGroups,
+    camera_groups: Option<&EffectGroups>,
+) -> f32 {
+    let Some(group) = group else {
+        return 1.0;
+    };
+
+    [Some(groups), camera_groups]
+        .into_iter()
+        .flatten()
+        .map(|groups| {
+            let settings = groups.settings(&group.name);
+            if settings.enabled {
+                settings.intensity.clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        })
+        .product()
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GroupSettings>()
+            .register_type::<EffectGroups>()
+            .register_type::<HashMap<String, GroupSettings>>()
+            .init_resource::<EffectGroups>();
+    }
+}
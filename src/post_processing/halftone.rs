@@ -0,0 +1,230 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const HALFTONE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4005216181675379009);
+
+#[derive(Resource)]
+pub(crate) struct HalftoneData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for HalftoneData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Halftone",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(Halftone::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            HALFTONE_SHADER_HANDLE.typed(),
+        );
+
+        HalftoneData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HALFTONE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "halftone.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Halftone>();
+        super::register_effect_types::<Halftone>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Halftone>::default(),
+            UniformComponentPlugin::<Halftone>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Halftone>,
+            )
+            .init_resource::<HalftoneData>()
+            .init_resource::<UniformBindGroup<Halftone>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Halftone>>();
+    }
+}
+
+fn prepare(
+    data: Res<HalftoneData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Halftone>,
+        &Blend<Halftone>,
+        &Resolution<Halftone>,
+        &SourceSampler<Halftone>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::halftone::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<Halftone>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<HalftoneData>,
+    mut bind_group: ResMut<UniformBindGroup<Halftone>>,
+    uniforms: Res<ComponentUniforms<Halftone>>,
+    views: Query<Entity, With<Halftone>>,
+) {
+    let _span = info_span!("vfx::halftone::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Halftone Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// A halftone effect, which prints the image as a pattern of CMYK-style dots like a comic book.
+///
+/// The image is split into cyan, magenta, yellow and black (key) ink channels.
+/// Each channel is drawn as a grid of dots rotated by its own angle,
+/// where the dot sizes follow the amount of ink at that spot.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Halftone {
+    /// The distance between dots, in pixels.
+    /// A dot fills its whole cell at full ink.
+    pub dot_size: f32,
+
+    /// Ink amounts below this value (in range `[0.0, 1.0]`) are not printed.
+    ///
+    /// Since bright areas need little ink, raising this leaves them as clean paper
+    /// instead of covering them in tiny dots.
+    pub threshold: f32,
+
+    /// The angle of the cyan dot grid, in radians.
+    pub cyan_angle: f32,
+
+    /// The angle of the magenta dot grid, in radians.
+    pub magenta_angle: f32,
+
+    /// The angle of the yellow dot grid, in radians.
+    pub yellow_angle: f32,
+
+    /// The angle of the black dot grid, in radians.
+    pub black_angle: f32,
+}
+
+impl Default for Halftone {
+    /// The screen angles commonly used in print, which avoid moiré patterns between the channels.
+    fn default() -> Self {
+        Self {
+            dot_size: 6.0,
+            threshold: 0.1,
+            cyan_angle: 15f32.to_radians(),
+            magenta_angle: 75f32.to_radians(),
+            yellow_angle: 0.0,
+            black_angle: 45f32.to_radians(),
+        }
+    }
+}
+
+impl Display for Halftone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Halftone dot size: {}, threshold: {}, angles (CMYK): {:.2}, {:.2}, {:.2}, {:.2}",
+            self.dot_size,
+            self.threshold,
+            self.cyan_angle,
+            self.magenta_angle,
+            self.yellow_angle,
+            self.black_angle
+        )
+    }
+}
+
+impl ExtractComponent for Halftone {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::halftone::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
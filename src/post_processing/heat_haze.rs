@@ -0,0 +1,277 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{BindGroupLayout, CachedRenderPipelineId, ShaderType},
+        renderer::RenderDevice,
+        texture::{FallbackImage, FallbackImageZero},
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingViewEffect,
+    Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const HEAT_HAZE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6759587630613825357);
+
+#[derive(Resource)]
+pub(crate) struct HeatHazeData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for HeatHazeData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Heat Haze",
+            // The mask is optional, see `super::optional_image`.
+            &super::uniform_and_images_layout_entries(HeatHazeUniform::min_size(), 1),
+            HEAT_HAZE_SHADER_HANDLE.typed(),
+        );
+
+        HeatHazeData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HEAT_HAZE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "heat_haze.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<HeatHaze>();
+        super::register_effect_types::<HeatHaze>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<HeatHaze>::default(),
+            UniformComponentPlugin::<HeatHazeUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<HeatHaze>,
+            )
+            .init_resource::<HeatHazeData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<HeatHazeUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<HeatHazeData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<HeatHaze>,
+        &Blend<HeatHaze>,
+        &Resolution<HeatHaze>,
+        &SourceSampler<HeatHaze>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::heat_haze::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<HeatHazeUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<HeatHazeData>,
+    uniforms: Res<ComponentUniforms<HeatHazeUniform>>,
+    images: Res<RenderAssets<Image>>,
+    fallback: Res<FallbackImage>,
+    fallback_zero: Res<FallbackImageZero>,
+    views: Query<(Entity, &HeatHazeMask)>,
+) {
+    let _span = info_span!("vfx::heat_haze::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, mask) in &views {
+        let mask = super::optional_image(&images, mask.0.as_ref(), &fallback, &fallback_zero);
+
+        let bind_group = super::uniform_and_images_bind_group(
+            &render_device,
+            "Heat Haze Uniform Bind Group",
+            &data.layout,
+            uniforms.clone(),
+            &[mask],
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<HeatHazeUniform>::new(bind_group));
+    }
+}
+
+/// Shimmering distortion, like the air above a fire or hot sand.
+///
+/// The image is displaced by animated noise rising upwards.
+/// The haze can be confined to a part of the view with [`HeatHaze::rect`],
+/// and further shaped by a [`HeatHaze::mask`] texture.
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct HeatHaze {
+    /// How far the image is displaced at most, in UV space.
+    pub strength: f32,
+
+    /// How many noise cells fit across the height of the view.
+    /// Higher values give finer shimmer.
+    pub scale: f32,
+
+    /// How fast the noise rises, in view heights per second.
+    pub speed: f32,
+
+    /// The part of the view with haze, in UV space (`(0, 0)` is the top left corner).
+    pub rect: Rect,
+
+    /// How far the haze fades out at the edges of the rect, in UV space.
+    pub feather: f32,
+
+    /// A texture which shapes the haze, covering the whole view.
+    /// The red channel is the amount of haze, where `0.0` is none and `1.0` is full strength.
+    ///
+    /// Without a mask, the whole rect has haze.
+    ///
+    /// Masks are assets, so they are not serialized.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub mask: Option<Handle<Image>>,
+}
+
+impl HeatHaze {
+    /// Haze in the given part of the view, in UV space.
+    pub fn in_rect(rect: Rect) -> Self {
+        Self { rect, ..default() }
+    }
+
+    /// Haze shaped by the given mask texture.
+    pub fn with_mask(mask: Handle<Image>) -> Self {
+        Self {
+            mask: Some(mask),
+            ..default()
+        }
+    }
+}
+
+impl Default for HeatHaze {
+    fn default() -> Self {
+        Self {
+            strength: 0.006,
+            scale: 24.0,
+            speed: 0.15,
+            rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+            feather: 0.05,
+            mask: None,
+        }
+    }
+}
+
+impl Display for HeatHaze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Heat Haze strength: {:.4}, scale: {:.1}, speed: {:.2}, rect: [{:.2}, {:.2}] to [{:.2}, {:.2}], feather: {:.2}, mask: {}",
+            self.strength,
+            self.scale,
+            self.speed,
+            self.rect.min.x,
+            self.rect.min.y,
+            self.rect.max.x,
+            self.rect.max.y,
+            self.feather,
+            self.mask.is_some()
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`HeatHaze`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct HeatHazeUniform {
+    pub(crate) rect: Vec4,
+    pub(crate) strength: f32,
+    pub(crate) scale: f32,
+    pub(crate) speed: f32,
+    pub(crate) feather: f32,
+}
+
+#[doc(hidden)]
+/// The mask of a [`HeatHaze`], in the render world.
+#[derive(Debug, Clone, Component)]
+pub struct HeatHazeMask(pub(crate) Option<Handle<Image>>);
+
+impl ExtractComponent for HeatHaze {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (HeatHazeUniform, HeatHazeMask);
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::heat_haze::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((
+            HeatHazeUniform {
+                rect: Vec4::new(
+                    settings.rect.min.x,
+                    settings.rect.min.y,
+                    settings.rect.max.x,
+                    settings.rect.max.y,
+                ),
+                strength: settings.strength,
+                scale: settings.scale,
+                speed: settings.speed,
+                feather: settings.feather,
+            },
+            HeatHazeMask(settings.mask.clone()),
+        ))
+    }
+}
@@ -0,0 +1,204 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{
+            CachedRenderPipelineId, Extent3d, Texture, TextureDescriptor, TextureDimension,
+            TextureFormat, TextureUsages, TextureView, TextureViewDescriptor,
+        },
+        renderer::RenderDevice,
+        Extract, Render, RenderSet,
+    },
+    utils::{HashMap, HashSet},
+};
+
+/// The view projection of a camera in the current and the previous frame, in the render world.
+///
+/// Temporal effects use this to find where a point on screen was in the previous frame,
+/// e.g. for blurring along the motion of the camera.
+///
+/// In the first frame of a camera both are the same, as there is no history yet.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ViewHistory {
+    /// Transforms world space to clip space, in the current frame.
+    pub(crate) view_projection: Mat4,
+
+    /// Transforms world space to clip space, as of the previous frame.
+    pub(crate) previous_view_projection: Mat4,
+}
+
+/// The view projection of each camera as of the last frame, kept across frames.
+#[derive(Debug, Default, Resource)]
+struct ViewHistories(HashMap<Entity, Mat4>);
+
+fn extract_view_history(
+    mut commands: Commands,
+    mut histories: ResMut<ViewHistories>,
+    cameras: Extract<Query<(Entity, &Camera, &GlobalTransform)>>,
+) {
+    let mut current = HashMap::default();
+
+    for (entity, camera, transform) in &cameras {
+        if !camera.is_active {
+            continue;
+        }
+
+        let view_projection = camera.projection_matrix() * transform.compute_matrix().inverse();
+        let previous_view_projection = histories.0.get(&entity).copied().unwrap_or(view_projection);
+
+        commands.get_or_spawn(entity).insert(ViewHistory {
+            view_projection,
+            previous_view_projection,
+        });
+
+        current.insert(entity, view_projection);
+    }
+
+    // Cameras which were removed or deactivated start over without history.
+    histories.0 = current;
+}
+
+/// The image of a view as of the previous frame, in the render world.
+///
+/// Effects which need it request it via `FrameHistories::request` while queueing,
+/// and the post processing node keeps it up to date:
+/// the image right after the requesting effect is copied into the history each frame,
+/// or the image right before it for histories of the effect's input.
+#[doc(hidden)]
+#[derive(Clone, Component)]
+pub struct FrameHistory {
+    pub(crate) texture: Texture,
+    pub(crate) view: TextureView,
+
+    /// The effect which reads the history.
+    pub(crate) pipeline_id: CachedRenderPipelineId,
+
+    /// True if the history was just allocated, so it doesn't hold a previous frame yet.
+    /// The node fills it with the current image first.
+    pub(crate) fresh: bool,
+
+    /// True if the history keeps the input of the effect instead of its output,
+    /// so the effect doesn't feed back into itself.
+    pub(crate) input: bool,
+}
+
+impl FrameHistory {
+    /// Keep the input of the effect instead of its output.
+    pub(crate) fn of_input(self) -> Self {
+        Self {
+            input: true,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+struct HistoryTexture {
+    texture: Texture,
+    view: TextureView,
+    size: UVec2,
+    format: TextureFormat,
+}
+
+/// The history textures of each camera, kept across frames.
+///
+/// Unlike the [`bevy::render::texture::TextureCache`], the same texture is handed out every frame,
+/// so it still holds what was written to it the previous frame.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct FrameHistories {
+    textures: HashMap<Entity, HistoryTexture>,
+
+    /// Cameras which requested their history this frame. The rest are dropped.
+    requested: HashSet<Entity>,
+}
+
+impl FrameHistories {
+    /// The history of the given view, for the effect with the given pipeline.
+    ///
+    /// The texture is (re)allocated if the view changed size or format.
+    pub(crate) fn request(
+        &mut self,
+        render_device: &RenderDevice,
+        entity: Entity,
+        size: UVec2,
+        format: TextureFormat,
+        pipeline_id: CachedRenderPipelineId,
+    ) -> FrameHistory {
+        self.requested.insert(entity);
+
+        let mut fresh = false;
+        let history = self
+            .textures
+            .entry(entity)
+            .and_modify(|history| {
+                if history.size != size || history.format != format {
+                    *history = Self::allocate(render_device, size, format);
+                    fresh = true;
+                }
+            })
+            .or_insert_with(|| {
+                fresh = true;
+                Self::allocate(render_device, size, format)
+            });
+
+        FrameHistory {
+            texture: history.texture.clone(),
+            view: history.view.clone(),
+            pipeline_id,
+            fresh,
+            input: false,
+        }
+    }
+
+    fn allocate(
+        render_device: &RenderDevice,
+        size: UVec2,
+        format: TextureFormat,
+    ) -> HistoryTexture {
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("PostProcessing frame history"),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        HistoryTexture {
+            texture,
+            view,
+            size,
+            format,
+        }
+    }
+}
+
+fn drop_unused_frame_histories(mut histories: ResMut<FrameHistories>) {
+    let histories = &mut *histories;
+    histories
+        .textures
+        .retain(|entity, _| histories.requested.contains(entity));
+    histories.requested.clear();
+}
+
+/// Keeps the previous frame state of cameras in the render world, see [`ViewHistory`] and [`FrameHistory`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        super::render_app(app)
+            .init_resource::<ViewHistories>()
+            .init_resource::<FrameHistories>()
+            .add_systems(ExtractSchedule, extract_view_history)
+            .add_systems(
+                Render,
+                drop_unused_frame_histories.in_set(RenderSet::Cleanup),
+            );
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const HOLOGRAM_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2947163508812437751);
+
+#[derive(Resource)]
+pub(crate) struct HologramData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for HologramData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Hologram",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(HologramUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            HOLOGRAM_SHADER_HANDLE.typed(),
+        );
+
+        HologramData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HOLOGRAM_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "hologram.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Hologram>();
+        super::register_effect_types::<Hologram>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Hologram>::default(),
+            UniformComponentPlugin::<HologramUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Hologram>,
+            )
+            .init_resource::<HologramData>()
+            .init_resource::<UniformBindGroup<HologramUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<HologramUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<HologramData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Hologram>,
+        &Blend<Hologram>,
+        &Resolution<Hologram>,
+        &SourceSampler<Hologram>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::hologram::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<HologramUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<HologramData>,
+    mut bind_group: ResMut<UniformBindGroup<HologramUniform>>,
+    uniforms: Res<ComponentUniforms<HologramUniform>>,
+    views: Query<Entity, With<HologramUniform>>,
+) {
+    let _span = info_span!("vfx::hologram::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Hologram Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Makes the image look like a holographic transmission, e.g. for a camera feed shown on an
+/// in-world monitor or projection.
+///
+/// The image is tinted by adding a color, crossed by scanlines, and bands of it jitter sideways.
+/// Every so often the signal drops out for a moment, and the image breaks up into static.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Hologram {
+    /// How many scanlines cross the height of the image.
+    pub line_density: f32,
+
+    /// How far bands of the image jitter sideways at most, in pixels.
+    pub jitter_amplitude: f32,
+
+    /// How many times per second the signal may drop out, on average.
+    /// `0.0` means it never does.
+    pub dropout_frequency: f32,
+
+    /// The color added to the image. Its alpha is how much of it is added.
+    pub tint: Color,
+}
+
+impl Default for Hologram {
+    fn default() -> Self {
+        Self {
+            line_density: 180.0,
+            jitter_amplitude: 4.0,
+            dropout_frequency: 0.3,
+            tint: Color::rgba(0.0, 0.8, 1.0, 0.35),
+        }
+    }
+}
+
+impl Display for Hologram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Hologram line density: {:.0}, jitter amplitude: {:.1}, dropout frequency: {:.2}, tint: {:?}",
+            self.line_density, self.jitter_amplitude, self.dropout_frequency, self.tint
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Hologram`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct HologramUniform {
+    pub(crate) line_density: f32,
+    pub(crate) jitter_amplitude: f32,
+    pub(crate) dropout_frequency: f32,
+    pub(crate) tint: Vec4,
+}
+
+impl From<Hologram> for HologramUniform {
+    fn from(hologram: Hologram) -> Self {
+        Self {
+            line_density: hologram.line_density,
+            jitter_amplitude: hologram.jitter_amplitude,
+            dropout_frequency: hologram.dropout_frequency,
+            tint: hologram.tint.as_linear_rgba_f32().into(),
+        }
+    }
+}
+
+impl ExtractComponent for Hologram {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = HologramUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::hologram::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
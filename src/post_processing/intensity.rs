@@ -0,0 +1,149 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use bevy::prelude::*;
+
+use super::blend::Blend;
+
+/// Soften or disable the effect `C` on a camera, without removing the effect.
+///
+/// Removing an effect component, or adding it back, recreates its bind groups which can hitch.
+/// Changing this instead is cheap, and the effect keeps its settings while disabled.
+/// Like [`super::Order`], this is per-camera and uses a generic marker to know which effect
+/// it applies to:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{intensity::Intensity, wave::Wave};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     Wave::default(),
+///     Intensity::<Wave>::new(0.5),
+/// ));
+/// ```
+///
+/// The intensity mixes the output of the effect with the image it started from, on top of
+/// any [`Blend`] of the effect.
+/// Effects which are disabled or have an intensity of `0.0` are skipped entirely.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct Intensity<C> {
+    /// How much of the effect is used, between `0.0` (the image as if the effect was not there)
+    /// and `1.0` (the full effect).
+    pub intensity: f32,
+
+    /// If false, the effect is skipped.
+    pub enabled: bool,
+
+    #[reflect(ignore)]
+    marker: PhantomData<C>,
+}
+
+impl<C> Intensity<C> {
+    /// Create a new enabled intensity.
+    pub fn new(intensity: f32) -> Self {
+        Self {
+            intensity,
+            enabled: true,
+            marker: PhantomData,
+        }
+    }
+
+    /// A full intensity, but disabled.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new(1.0)
+        }
+    }
+}
+
+impl<C> Default for Intensity<C> {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl<C> Clone for Intensity<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Intensity<C> {}
+
+impl<C> Display for Intensity<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Intensity {:.2}, enabled: {}",
+            self.intensity, self.enabled
+        )
+    }
+}
+
+/// Settings for all the effects on a camera.
+///
+/// The master intensity is applied on top of the [`Intensity`] of each effect, so this can fade
+/// all effects in or out at once, e.g. for a pause menu or an accessibility setting.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct PostProcessingSettings {
+    /// How much of the effects is used, between `0.0` (no effects) and `1.0` (the full effects).
+    pub intensity: f32,
+
+    /// If false, all effects on the camera are skipped.
+    pub enabled: bool,
+}
+
+impl Default for PostProcessingSettings {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            enabled: true,
+        }
+    }
+}
+
+impl Display for PostProcessingSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Post Processing intensity: {:.2}, enabled: {}",
+            self.intensity, self.enabled
+        )
+    }
+}
+
+/// The blend an effect is drawn with, once its intensity, the master intensity and the factor of
+/// its [`super::group::EffectGroup`] are applied.
+///
+/// Returns `None` if the effect should be skipped.
+pub(crate) fn effective_blend<C>(
+    blend: Option<&Blend<C>>,
+    intensity: Option<&Intensity<C>>,
+    settings: Option<&PostProcessingSettings>,
+    group_factor: f32,
+) -> Option<Blend<C>> {
+    let intensity = intensity.copied().unwrap_or_default();
+    let settings = settings.copied().unwrap_or_default();
+
+    if !intensity.enabled || !settings.enabled {
+        return None;
+    }
+
+    let mut blend = blend.copied().unwrap_or_default();
+    blend.factor *=
+        intensity.intensity.clamp(0.0, 1.0) * settings.intensity.clamp(0.0, 1.0) * group_factor;
+
+    (blend.factor > 0.0).then_some(blend)
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PostProcessingSettings>();
+    }
+}
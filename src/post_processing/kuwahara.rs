@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend, compute::ComputeData, resolution::Resolution, sampler::SourceSampler, Order,
+    PostProcessingPhaseItem,
+};
+
+pub(crate) const KUWAHARA_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9340924694799201616);
+
+pub(crate) const KUWAHARA_COMPUTE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8383376607461692595);
+
+#[derive(Resource)]
+pub(crate) struct KuwaharaData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for KuwaharaData {
+    fn from_world(world: &mut World) -> Self {
+        let visibility = world.resource::<ComputeData>().visibility();
+
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Kuwahara",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(Kuwahara::min_size()),
+                },
+                visibility,
+                count: None,
+            }],
+            KUWAHARA_SHADER_HANDLE.typed(),
+        );
+
+        super::compute::add_variant::<Kuwahara>(
+            world,
+            "Kuwahara",
+            pipeline_id,
+            &uniform_layout,
+            KUWAHARA_COMPUTE_SHADER_HANDLE.typed(),
+        );
+
+        KuwaharaData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            KUWAHARA_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "kuwahara.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            KUWAHARA_COMPUTE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "kuwahara_compute.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Kuwahara>();
+        super::register_effect_types::<Kuwahara>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Kuwahara>::default(),
+            UniformComponentPlugin::<Kuwahara>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Kuwahara>,
+            )
+            .init_resource::<KuwaharaData>()
+            .init_resource::<UniformBindGroup<Kuwahara>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Kuwahara>>();
+    }
+}
+
+fn prepare(
+    data: Res<KuwaharaData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Kuwahara>,
+        &Blend<Kuwahara>,
+        &Resolution<Kuwahara>,
+        &SourceSampler<Kuwahara>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::kuwahara::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<Kuwahara>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<KuwaharaData>,
+    mut bind_group: ResMut<UniformBindGroup<Kuwahara>>,
+    uniforms: Res<ComponentUniforms<Kuwahara>>,
+    views: Query<Entity, With<Kuwahara>>,
+) {
+    let _span = info_span!("vfx::kuwahara::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Kuwahara Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// A Kuwahara filter, which gives the image a painterly, oil on canvas look.
+///
+/// The area around each pixel is split into four overlapping square regions.
+/// The pixel takes the average color of the region with the least variance,
+/// which smooths flat areas into brush stroke like blobs while keeping edges sharp.
+///
+/// This is a heavy effect, since each of the four regions is sampled for every pixel.
+/// Use [`Kuwahara::samples_per_side`] to trade quality for speed,
+/// especially at high resolutions where large radii are needed.
+///
+/// Where supported, the filter runs as a compute shader, which reads the regions from shared
+/// memory for radii up to 13 pixels, see [`super::compute::ComputePath`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Kuwahara {
+    /// The size of each region, in pixels.
+    /// Larger values give larger "brush strokes".
+    /// If `0.0`, the image is left unchanged.
+    pub radius: f32,
+
+    /// How many samples are taken along each side of a region,
+    /// so each pixel samples `4 * (samples_per_side + 1)^2` times.
+    ///
+    /// When this is lower than [`Kuwahara::radius`], samples are spread out
+    /// to cover the whole region, which is faster but may look blocky.
+    /// The shader clamps this to between 1 and 16.
+    pub samples_per_side: u32,
+}
+
+impl Default for Kuwahara {
+    fn default() -> Self {
+        Self {
+            radius: 5.0,
+            samples_per_side: 5,
+        }
+    }
+}
+
+impl Display for Kuwahara {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Kuwahara radius: {}, samples per side: {}",
+            self.radius, self.samples_per_side
+        )
+    }
+}
+
+impl ExtractComponent for Kuwahara {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::kuwahara::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
@@ -0,0 +1,267 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    core_pipeline::{clear_color::ClearColorConfig, tonemapping::Tonemapping},
+    prelude::*,
+    render::{
+        camera::{CameraUpdateSystem, RenderTarget, ScalingMode},
+        render_asset::RenderAssets,
+        render_resource::Extent3d,
+        texture::{FallbackImageZero, GpuImage},
+        view::RenderLayers,
+    },
+};
+
+use super::process_image::output_image;
+
+/// An effect on 2d cameras which needs some render layers drawn into images of their own,
+/// e.g. to know where the sprites on a layer are, since 2d cameras have no depth.
+pub(crate) trait LayerImagesEffect: Component {
+    /// Labels the images and cameras.
+    const LABEL: &'static str;
+
+    /// The images are drawn at this fraction of the resolution of the camera.
+    const RESOLUTION_DIVISOR: u32;
+
+    /// The layers to draw, each into an image of its own.
+    fn layers(&self) -> Vec<RenderLayers>;
+}
+
+/// The cameras drawing the layers of a [`LayerImagesEffect`] on a camera, and their images.
+///
+/// The cameras are children of the camera, and see what it sees, but only on their layers.
+/// Only the alpha of the images is meaningful, as the cameras don't tonemap.
+#[doc(hidden)]
+#[derive(Debug, Component)]
+pub struct LayerImages<T> {
+    pub(crate) cameras: Vec<Entity>,
+    pub(crate) images: Vec<Handle<Image>>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Clone for LayerImages<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cameras: self.cameras.clone(),
+            images: self.images.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> LayerImages<T> {
+    /// The images in the render world, padded with transparent images up to the given count.
+    /// Until the layers are drawn, their images are transparent too.
+    pub(crate) fn gpu_images<'a>(
+        &self,
+        count: usize,
+        images: &'a RenderAssets<Image>,
+        fallback_zero: &'a FallbackImageZero,
+    ) -> Vec<&'a GpuImage> {
+        (0..count)
+            .map(|index| {
+                self.images
+                    .get(index)
+                    .and_then(|handle| images.get(handle))
+                    .unwrap_or(fallback_zero)
+            })
+            .collect()
+    }
+}
+
+/// The size of the layer images of a camera.
+fn image_size<T: LayerImagesEffect>(camera: &Camera) -> UVec2 {
+    let size = camera.physical_viewport_size().unwrap_or(UVec2::ONE);
+    (size / T::RESOLUTION_DIVISOR).max(UVec2::ONE)
+}
+
+/// Gives new cameras with the effect their [`LayerImages`], which are filled in by
+/// [`sync_layer_cameras`].
+fn add_layer_images<T: LayerImagesEffect>(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<T>, Without<LayerImages<T>>)>,
+) {
+    for entity in &cameras {
+        commands.entity(entity).insert(LayerImages::<T> {
+            cameras: Vec::new(),
+            images: Vec::new(),
+            marker: PhantomData,
+        });
+    }
+}
+
+type LayerCameraItem = (
+    &'static mut Camera,
+    &'static mut OrthographicProjection,
+    &'static mut RenderLayers,
+);
+
+/// Keeps a camera drawing each layer of the effect, seeing what the camera sees,
+/// at the size of its image.
+///
+/// Only changes what differs, so the cameras aren't marked as changed each frame.
+fn sync_layer_cameras<T: LayerImagesEffect>(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<(
+        Entity,
+        &Camera,
+        Option<&OrthographicProjection>,
+        &T,
+        &mut LayerImages<T>,
+    )>,
+    mut layer_cameras: Query<LayerCameraItem, Without<LayerImages<T>>>,
+    mut warned: Local<bevy::utils::HashSet<Entity>>,
+) {
+    for (entity, camera, projection, effect, mut layer_images) in &mut cameras {
+        let Some(projection) = projection else {
+            if warned.insert(entity) {
+                warn!(
+                    "Camera {entity:?} has `{}` but no `OrthographicProjection`, its layers are not drawn",
+                    T::LABEL
+                );
+            }
+            continue;
+        };
+
+        let layers = effect.layers();
+        let size = image_size::<T>(camera);
+
+        // Layers which were removed.
+        while layer_images.cameras.len() > layers.len() {
+            if let Some(layer_camera) = layer_images.cameras.pop() {
+                commands.entity(layer_camera).despawn_recursive();
+            }
+            layer_images.images.pop();
+        }
+
+        // Layers which were added. Their cameras are synced from the next frame on.
+        for layers in layers.iter().skip(layer_images.cameras.len()) {
+            let mut image = output_image(size);
+            image.texture_descriptor.label = Some(T::LABEL);
+            let image = images.add(image);
+
+            let layer_camera = commands
+                .spawn((
+                    Camera2dBundle {
+                        camera: Camera {
+                            target: RenderTarget::Image(image.clone()),
+                            order: camera.order - 1,
+                            ..default()
+                        },
+                        camera_2d: Camera2d {
+                            clear_color: ClearColorConfig::Custom(Color::NONE),
+                        },
+                        // Moves along with the camera as its child.
+                        transform: Transform::IDENTITY,
+                        tonemapping: Tonemapping::None,
+                        ..default()
+                    },
+                    *layers,
+                    Name::new(T::LABEL),
+                ))
+                .id();
+
+            commands.entity(entity).add_child(layer_camera);
+            layer_images.cameras.push(layer_camera);
+            layer_images.images.push(image);
+        }
+
+        // The images are smaller than the view, so the cameras are given the area to cover directly.
+        let (width, height) = (projection.area.width(), projection.area.height());
+
+        for ((layer_camera, image), layers) in layer_images
+            .cameras
+            .iter()
+            .zip(&layer_images.images)
+            .zip(&layers)
+        {
+            let Ok((mut layer_camera, mut layer_projection, mut layer_layers)) =
+                layer_cameras.get_mut(*layer_camera)
+            else {
+                continue;
+            };
+
+            if layer_camera.is_active != camera.is_active {
+                layer_camera.is_active = camera.is_active;
+            }
+            if layer_camera.order != camera.order - 1 {
+                layer_camera.order = camera.order - 1;
+            }
+            if *layer_layers != *layers {
+                *layer_layers = *layers;
+            }
+
+            let same_area = matches!(
+                layer_projection.scaling_mode,
+                ScalingMode::Fixed { width: w, height: h } if w == width && h == height
+            );
+            if !same_area
+                || layer_projection.viewport_origin != projection.viewport_origin
+                || layer_projection.near != projection.near
+                || layer_projection.far != projection.far
+            {
+                layer_projection.scaling_mode = ScalingMode::Fixed { width, height };
+                layer_projection.viewport_origin = projection.viewport_origin;
+                layer_projection.near = projection.near;
+                layer_projection.far = projection.far;
+                layer_projection.scale = 1.0;
+            }
+
+            if images
+                .get(image)
+                .is_some_and(|image| image.size() != size.as_vec2())
+            {
+                images
+                    .get_mut(image)
+                    .expect("The image was just found")
+                    .resize(Extent3d {
+                        width: size.x,
+                        height: size.y,
+                        depth_or_array_layers: 1,
+                    });
+            }
+        }
+    }
+}
+
+/// Removes the layer cameras of cameras which no longer have the effect.
+fn remove_layer_cameras<T: LayerImagesEffect>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<T>,
+    cameras: Query<&LayerImages<T>, Without<T>>,
+) {
+    for entity in removed.iter() {
+        if let Ok(layer_images) = cameras.get(entity) {
+            for layer_camera in &layer_images.cameras {
+                commands.entity(*layer_camera).despawn_recursive();
+            }
+            commands.entity(entity).remove::<LayerImages<T>>();
+        }
+    }
+}
+
+/// Draws the layers of the [`LayerImagesEffect`] `T` on each camera into images.
+pub(crate) struct LayerImagesPlugin<T>(PhantomData<T>);
+
+impl<T> Default for LayerImagesPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: LayerImagesEffect> Plugin for LayerImagesPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                add_layer_images::<T>,
+                apply_deferred,
+                sync_layer_cameras::<T>,
+                remove_layer_cameras::<T>,
+            )
+                .chain()
+                .after(CameraUpdateSystem),
+        );
+    }
+}
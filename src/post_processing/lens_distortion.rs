@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const LENS_DISTORTION_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1110454157283377691);
+
+#[derive(Resource)]
+pub(crate) struct LensDistortionData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LensDistortionData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Lens Distortion",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(LensDistortionUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            LENS_DISTORTION_SHADER_HANDLE.typed(),
+        );
+
+        LensDistortionData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LENS_DISTORTION_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "lens_distortion.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<LensDistortion>();
+        super::register_effect_types::<LensDistortion>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<LensDistortion>::default(),
+            UniformComponentPlugin::<LensDistortionUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<LensDistortion>,
+            )
+            .init_resource::<LensDistortionData>()
+            .init_resource::<UniformBindGroup<LensDistortionUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<LensDistortionUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<LensDistortionData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<LensDistortion>,
+        &Blend<LensDistortion>,
+        &Resolution<LensDistortion>,
+        &SourceSampler<LensDistortion>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::lens_distortion::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<LensDistortionUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<LensDistortionData>,
+    mut bind_group: ResMut<UniformBindGroup<LensDistortionUniform>>,
+    uniforms: Res<ComponentUniforms<LensDistortionUniform>>,
+    views: Query<Entity, With<LensDistortionUniform>>,
+) {
+    let _span = info_span!("vfx::lens_distortion::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Lens Distortion Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Bends the image like a wide angle or a telephoto lens, e.g. for security cameras and scopes.
+///
+/// Barrel distortion bulges the middle of the image out, like a fisheye lens.
+/// Pincushion distortion pinches it in, stretching the corners.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct LensDistortion {
+    /// How strongly the image is bent.
+    /// Positive values give barrel distortion, negative values give pincushion distortion.
+    ///
+    /// Values beyond `1.0` fold the image over itself at the edges.
+    pub distortion: f32,
+
+    /// How much the color channels are bent differently, giving colored fringes towards the edges.
+    /// `0.0` is none, `0.1` is already obvious.
+    pub fringing: f32,
+
+    /// If true, the image is zoomed in so it still covers the view where the distortion
+    /// would pull its edges in. Otherwise the edge pixels are smeared there.
+    pub auto_scale: bool,
+}
+
+impl Default for LensDistortion {
+    fn default() -> Self {
+        Self {
+            distortion: 0.3,
+            fringing: 0.0,
+            auto_scale: true,
+        }
+    }
+}
+
+impl Display for LensDistortion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Lens Distortion: {:.2}, fringing: {:.2}, auto scale: {}",
+            self.distortion, self.fringing, self.auto_scale
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`LensDistortion`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct LensDistortionUniform {
+    pub(crate) coefficient: f32,
+    pub(crate) fringing: f32,
+    pub(crate) scale: f32,
+}
+
+impl ExtractComponent for LensDistortion {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = LensDistortionUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::lens_distortion::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        // The shader samples further out where the coefficient is positive.
+        let coefficient = -settings.distortion;
+
+        // The corners are bent the most. If any channel samples beyond them, zoom in until it doesn't.
+        let scale = if settings.auto_scale {
+            let most = (coefficient * (1.0 + settings.fringing))
+                .max(coefficient * (1.0 - settings.fringing))
+                .max(0.0);
+            1.0 / (1.0 + most)
+        } else {
+            1.0
+        };
+
+        Some(LensDistortionUniform {
+            coefficient,
+            fringing: settings.fringing,
+            scale,
+        })
+    }
+}
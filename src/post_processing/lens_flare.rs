@@ -0,0 +1,396 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::CameraUpdateSystem,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+    transform::TransformSystem,
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const LENS_FLARE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13082819746130148350);
+
+#[derive(Resource)]
+pub(crate) struct LensFlareData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LensFlareData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Lens Flare",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(LensFlareUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            LENS_FLARE_SHADER_HANDLE.typed(),
+        );
+
+        LensFlareData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LENS_FLARE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "lens_flare.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<LensFlare>()
+            .register_type::<LensFlareFollow>();
+        super::register_effect_types::<LensFlare>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<LensFlare>::default(),
+            UniformComponentPlugin::<LensFlareUniform>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            (
+                follow_light
+                    .after(TransformSystem::TransformPropagate)
+                    .after(CameraUpdateSystem),
+                super::prepass::configure_prepasses::<LensFlare>,
+            ),
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<LensFlare>,
+            )
+            .init_resource::<LensFlareData>()
+            .init_resource::<UniformBindGroup<LensFlareUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<LensFlareUniform>>();
+    }
+}
+
+impl EffectPrepasses for LensFlare {
+    const WITHOUT: &'static str = "the flare will show through geometry";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+fn prepare(
+    data: Res<LensFlareData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<LensFlare>,
+        &Blend<LensFlare>,
+        &Resolution<LensFlare>,
+        &SourceSampler<LensFlare>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::lens_flare::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<LensFlareUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<LensFlareData>,
+    mut bind_group: ResMut<UniformBindGroup<LensFlareUniform>>,
+    uniforms: Res<ComponentUniforms<LensFlareUniform>>,
+    views: Query<Entity, With<LensFlareUniform>>,
+) {
+    let _span = info_span!("vfx::lens_flare::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Lens Flare Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// A lens flare from a bright light on screen.
+///
+/// Draws a glow and a horizontal streak on the light, a halo around it,
+/// and ghosts along the line from the light through the center of the view.
+///
+/// The depth around the light is compared against [`LensFlare::light_depth`],
+/// so the flare disappears behind geometry. This needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) on the camera, which is added
+/// along with the flare, and `Msaa::Off`.
+///
+/// Add [`LensFlareFollow`] to the camera to keep the light position and depth on a light entity.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct LensFlare {
+    /// The position of the light, in UV space (`(0, 0)` is the top left corner).
+    ///
+    /// The flare fades out as the light leaves the screen.
+    pub light_position: Vec2,
+
+    /// The depth of the light, in normalized device coordinates.
+    ///
+    /// Bevy uses reverse z, so `1.0` is on the near plane and `0.0` is infinitely far away.
+    /// Geometry closer than this hides the flare.
+    pub light_depth: f32,
+
+    /// The color of the flare.
+    pub color: Color,
+
+    /// The overall brightness of the flare.
+    pub intensity: f32,
+
+    /// How many ghosts are drawn.
+    pub ghosts: u32,
+
+    /// How far apart the ghosts are, as a fraction of the distance from the light to the center.
+    pub ghost_spacing: f32,
+
+    /// The radius of the ghosts, in view heights.
+    pub ghost_size: f32,
+
+    /// The radius of the halo around the light, in view heights.
+    pub halo_radius: f32,
+
+    /// How thick the halo is, in view heights.
+    pub halo_width: f32,
+
+    /// The brightness of the halo.
+    pub halo_intensity: f32,
+
+    /// How far the streak reaches to each side of the light, in view heights.
+    pub streak_length: f32,
+
+    /// The brightness of the streak.
+    pub streak_intensity: f32,
+
+    /// How far around the light the depth is tested, in pixels.
+    /// Larger values make the flare fade more gradually as the light goes behind geometry.
+    pub occlusion_radius: f32,
+}
+
+impl Default for LensFlare {
+    fn default() -> Self {
+        Self {
+            light_position: Vec2::new(0.5, 0.3),
+            light_depth: 0.0,
+            color: Color::rgb(1.0, 0.9, 0.7),
+            intensity: 1.0,
+            ghosts: 5,
+            ghost_spacing: 0.5,
+            ghost_size: 0.06,
+            halo_radius: 0.3,
+            halo_width: 0.02,
+            halo_intensity: 0.1,
+            streak_length: 0.6,
+            streak_intensity: 0.5,
+            occlusion_radius: 8.0,
+        }
+    }
+}
+
+impl Display for LensFlare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Lens Flare at [{:.2}, {:.2}], depth: {:.4}, intensity: {:.2}, ghosts: {}, ghost spacing: {:.2}, ghost size: {:.3}, halo radius: {:.2}, halo width: {:.3}, halo intensity: {:.2}, streak length: {:.2}, streak intensity: {:.2}, occlusion radius: {:.1}",
+            self.light_position.x,
+            self.light_position.y,
+            self.light_depth,
+            self.intensity,
+            self.ghosts,
+            self.ghost_spacing,
+            self.ghost_size,
+            self.halo_radius,
+            self.halo_width,
+            self.halo_intensity,
+            self.streak_length,
+            self.streak_intensity,
+            self.occlusion_radius
+        )
+    }
+}
+
+/// Keeps the [`LensFlare::light_position`] and [`LensFlare::light_depth`] of a camera on a light entity.
+///
+/// The position is projected from the light's [`GlobalTransform`] each frame.
+///
+/// A mesh around the light is closer to the camera than the light itself, so it hides the flare.
+/// Follow an entity just in front of such a mesh instead.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct LensFlareFollow {
+    /// The light entity.
+    pub light: Entity,
+
+    /// If true, the light is treated as infinitely far away, shining along its forward direction
+    /// (like a directional light, e.g. the sun). Otherwise its position is used.
+    pub directional: bool,
+}
+
+impl LensFlareFollow {
+    /// Follow a light with a position, such as a point light or a lamp.
+    pub fn point(light: Entity) -> Self {
+        Self {
+            light,
+            directional: false,
+        }
+    }
+
+    /// Follow a light which shines along its forward direction, such as the sun.
+    pub fn directional(light: Entity) -> Self {
+        Self {
+            light,
+            directional: true,
+        }
+    }
+}
+
+impl FromWorld for LensFlareFollow {
+    fn from_world(_world: &mut World) -> Self {
+        Self::point(Entity::PLACEHOLDER)
+    }
+}
+
+fn follow_light(
+    mut cameras: Query<(&Camera, &GlobalTransform, &LensFlareFollow, &mut LensFlare)>,
+    lights: Query<&GlobalTransform>,
+) {
+    for (camera, camera_transform, follow, mut lens_flare) in &mut cameras {
+        let Ok(light_transform) = lights.get(follow.light) else {
+            continue;
+        };
+
+        let (position, depth) = super::light_shafts::light_ndc(
+            camera,
+            camera_transform,
+            light_transform,
+            follow.directional,
+        )
+        .map(|ndc| (Vec2::new(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5), ndc.z))
+        // Behind the camera, put the light far off screen so the flare fades out.
+        .unwrap_or((Vec2::splat(-100.0), 0.0));
+
+        if lens_flare.light_position != position || lens_flare.light_depth != depth {
+            lens_flare.light_position = position;
+            lens_flare.light_depth = depth;
+        }
+    }
+}
+
+#[doc(hidden)]
+/// [`LensFlare`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct LensFlareUniform {
+    pub(crate) color: Vec4,
+    pub(crate) light_position: Vec2,
+    pub(crate) light_depth: f32,
+    pub(crate) intensity: f32,
+    pub(crate) ghosts: u32,
+    pub(crate) ghost_spacing: f32,
+    pub(crate) ghost_size: f32,
+    pub(crate) halo_radius: f32,
+    pub(crate) halo_width: f32,
+    pub(crate) halo_intensity: f32,
+    pub(crate) streak_length: f32,
+    pub(crate) streak_intensity: f32,
+    pub(crate) occlusion_radius: f32,
+}
+
+impl ExtractComponent for LensFlare {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = LensFlareUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::lens_flare::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(LensFlareUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            light_position: settings.light_position,
+            light_depth: settings.light_depth,
+            intensity: settings.intensity,
+            ghosts: settings.ghosts,
+            ghost_spacing: settings.ghost_spacing,
+            ghost_size: settings.ghost_size,
+            halo_radius: settings.halo_radius,
+            halo_width: settings.halo_width,
+            halo_intensity: settings.halo_intensity,
+            streak_length: settings.streak_length,
+            streak_intensity: settings.streak_intensity,
+            occlusion_radius: settings.occlusion_radius,
+        })
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+use crate::animation::Animate;
+
+pub(crate) const LETTERBOX_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 12898568782521290190);
+
+#[derive(Resource)]
+pub(crate) struct LetterboxData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LetterboxData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Letterbox",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(LetterboxUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            LETTERBOX_SHADER_HANDLE.typed(),
+        );
+
+        LetterboxData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LETTERBOX_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "letterbox.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Letterbox>();
+        super::register_effect_types::<Letterbox>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Letterbox>::default(),
+            UniformComponentPlugin::<LetterboxUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Letterbox>,
+            )
+            .init_resource::<LetterboxData>()
+            .init_resource::<UniformBindGroup<LetterboxUniform>>()
+            .add_systems(
+                Render,
+                prepare
+                    .in_set(RenderSet::Prepare)
+                    // The bars frame the picture, so they stay over a transition.
+                    .after(super::screen_transition::prepare),
+            )
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<LetterboxUniform>>();
+    }
+}
+
+pub(crate) fn prepare(
+    data: Res<LetterboxData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Letterbox>,
+        &Blend<Letterbox>,
+        &Resolution<Letterbox>,
+        &SourceSampler<Letterbox>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::letterbox::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<LetterboxUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<LetterboxData>,
+    mut bind_group: ResMut<UniformBindGroup<LetterboxUniform>>,
+    uniforms: Res<ComponentUniforms<LetterboxUniform>>,
+    views: Query<Entity, With<LetterboxUniform>>,
+) {
+    let _span = info_span!("vfx::letterbox::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Letterbox Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Bars at the top and bottom of the screen which frame the picture to a wider aspect ratio,
+/// as in films, e.g. for cutscenes.
+///
+/// On screens wider than [`Letterbox::aspect_ratio`], the bars are at the sides instead (a pillarbox).
+///
+/// Use [`Letterbox::animate_in`] and [`Letterbox::animate_out`] to move the bars in and out:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::letterbox::Letterbox;
+/// fn start_cutscene(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     commands
+///         .entity(camera.single())
+///         .insert(Letterbox::default().animate_in(1.0));
+/// }
+/// ```
+///
+/// Unless given an [`Order`], the bars are drawn after all other effects,
+/// so nothing is drawn over them.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Letterbox {
+    /// The width over the height of the picture between the bars, e.g. `2.39` for widescreen films.
+    pub aspect_ratio: f32,
+
+    /// The color of the bars.
+    /// The alpha channel controls how opaque they are.
+    pub color: Color,
+
+    /// How far the bars are in, from `0.0` (not at all) to `1.0` (framing the picture to
+    /// [`Letterbox::aspect_ratio`]).
+    pub progress: f32,
+}
+
+impl Letterbox {
+    /// Bars framing the picture to the given aspect ratio.
+    pub fn new(aspect_ratio: f32) -> Self {
+        Self {
+            aspect_ratio,
+            ..default()
+        }
+    }
+
+    /// Moves these bars in from nothing, over the given seconds.
+    pub fn animate_in(self, seconds: f32) -> Animate<Letterbox> {
+        Animate::new(
+            Self {
+                progress: 0.0,
+                ..self
+            },
+            self,
+            seconds,
+        )
+    }
+
+    /// Moves these bars out until there are none, over the given seconds.
+    /// The effect stays, with a [`Letterbox::progress`] of `0.0`.
+    pub fn animate_out(self, seconds: f32) -> Animate<Letterbox> {
+        Animate::new(
+            self,
+            Self {
+                progress: 0.0,
+                ..self
+            },
+            seconds,
+        )
+    }
+}
+
+impl Default for Letterbox {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: 2.39,
+            color: Color::BLACK,
+            progress: 1.0,
+        }
+    }
+}
+
+impl Display for Letterbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Letterbox aspect ratio: {:.2}, progress: {:.2}",
+            self.aspect_ratio, self.progress
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Letterbox`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct LetterboxUniform {
+    pub(crate) color: Vec4,
+    pub(crate) aspect_ratio: f32,
+    pub(crate) progress: f32,
+}
+
+impl ExtractComponent for Letterbox {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = LetterboxUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::letterbox::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(LetterboxUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            aspect_ratio: settings.aspect_ratio.max(0.01),
+            progress: settings.progress.clamp(0.0, 1.0),
+        })
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    composition::{self, Composition},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingEffect, Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const LIGHT_LEAK_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16601738022093650921);
+
+#[derive(Resource)]
+pub(crate) struct LightLeakData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LightLeakData {
+    fn from_world(world: &mut World) -> Self {
+        // The leaks are screened onto the image where it is, see `Composition`.
+        let (uniform_layout, pipeline_id) = composition::create_layout_and_pipeline(
+            world,
+            "Light Leak",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(LightLeakUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            LIGHT_LEAK_SHADER_HANDLE.typed(),
+            Composition::Screen,
+        );
+
+        LightLeakData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LIGHT_LEAK_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "light_leak.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<LightLeak>();
+        super::register_effect_types::<LightLeak>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<LightLeak>::default(),
+            UniformComponentPlugin::<LightLeakUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<LightLeak>,
+            )
+            .init_resource::<LightLeakData>()
+            .init_resource::<UniformBindGroup<LightLeakUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<LightLeakUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<LightLeakData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<LightLeak>,
+        &Blend<LightLeak>,
+        &Resolution<LightLeak>,
+        &SourceSampler<LightLeak>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::light_leak::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<LightLeakUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<LightLeakData>,
+    mut bind_group: ResMut<UniformBindGroup<LightLeakUniform>>,
+    uniforms: Res<ComponentUniforms<LightLeakUniform>>,
+    views: Query<Entity, With<LightLeakUniform>>,
+) {
+    let _span = info_span!("vfx::light_leak::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Light Leak Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Warm glowing blobs which slowly drift over the image, like light leaking into a film camera.
+///
+/// The blobs are screened onto the image, so they only ever brighten it.
+/// They are composed onto the image where it is (see [`Composition::Screen`]),
+/// so the effect is always drawn at full resolution.
+/// Every [`LightLeak::interval`] seconds a new set of blobs fades in at random places
+/// while the previous set fades out.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct LightLeak {
+    /// The color of the leaks. The blobs vary slightly in hue around it.
+    pub color: Color,
+
+    /// How bright the leaks are, between `0.0` and `1.0`.
+    pub intensity: f32,
+
+    /// How large the blobs are, as a fraction of the height of the image.
+    pub scale: f32,
+
+    /// How fast the blobs drift.
+    pub speed: f32,
+
+    /// How many seconds a set of blobs lasts before the next set has replaced it.
+    pub interval: f32,
+
+    /// Different seeds place the blobs differently, e.g. to vary leaks between cameras.
+    pub seed: u32,
+}
+
+impl Default for LightLeak {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(1.0, 0.45, 0.15),
+            intensity: 0.6,
+            scale: 0.5,
+            speed: 1.0,
+            interval: 6.0,
+            seed: 0,
+        }
+    }
+}
+
+impl Display for LightLeak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Light Leak color: {:?}, intensity: {:.2}, scale: {:.2}, speed: {:.2}, interval: {:.1}, seed: {}",
+            self.color, self.intensity, self.scale, self.speed, self.interval, self.seed
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`LightLeak`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct LightLeakUniform {
+    pub(crate) color: Vec4,
+    pub(crate) intensity: f32,
+    pub(crate) scale: f32,
+    pub(crate) speed: f32,
+    pub(crate) interval: f32,
+    pub(crate) seed: f32,
+}
+
+impl From<LightLeak> for LightLeakUniform {
+    fn from(leak: LightLeak) -> Self {
+        Self {
+            color: Vec4::from(leak.color.as_linear_rgba_f32()),
+            intensity: leak.intensity,
+            scale: leak.scale,
+            speed: leak.speed,
+            interval: leak.interval,
+            // Kept small, so the hashes in the shader stay precise.
+            seed: (leak.seed % 1024) as f32,
+        }
+    }
+}
+
+impl ExtractComponent for LightLeak {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = LightLeakUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::light_leak::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
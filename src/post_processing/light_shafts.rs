@@ -0,0 +1,334 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::CameraUpdateSystem,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+    transform::TransformSystem,
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const LIGHT_SHAFTS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16237187859081219740);
+
+#[derive(Resource)]
+pub(crate) struct LightShaftsData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LightShaftsData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Light Shafts",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(LightShafts::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            LIGHT_SHAFTS_SHADER_HANDLE.typed(),
+        );
+
+        LightShaftsData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LIGHT_SHAFTS_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "light_shafts.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<LightShafts>()
+            .register_type::<LightShaftsFollow>();
+        super::register_effect_types::<LightShafts>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<LightShafts>::default(),
+            UniformComponentPlugin::<LightShafts>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            follow_light
+                .after(TransformSystem::TransformPropagate)
+                .after(CameraUpdateSystem),
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<LightShafts>,
+            )
+            .init_resource::<LightShaftsData>()
+            .init_resource::<UniformBindGroup<LightShafts>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<LightShafts>>();
+    }
+}
+
+fn prepare(
+    data: Res<LightShaftsData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<LightShafts>,
+        &Blend<LightShafts>,
+        &Resolution<LightShafts>,
+        &SourceSampler<LightShafts>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::light_shafts::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<LightShafts>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<LightShaftsData>,
+    mut bind_group: ResMut<UniformBindGroup<LightShafts>>,
+    uniforms: Res<ComponentUniforms<LightShafts>>,
+    views: Query<Entity, With<LightShafts>>,
+) {
+    let _span = info_span!("vfx::light_shafts::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Light Shafts Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Light shafts (god rays) streaming from a light on screen.
+///
+/// Bright parts of the image are smeared radially away from [`LightShafts::light_position`],
+/// so objects in front of the light cast visible shafts through the air.
+///
+/// Add [`LightShaftsFollow`] to the camera to keep the position on a light entity.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct LightShafts {
+    /// The position of the light, in UV space (`(0, 0)` is the top left corner).
+    ///
+    /// The shafts fade out as the light leaves the screen.
+    pub light_position: Vec2,
+
+    /// How far towards the light the samples reach, where `1.0` is all the way.
+    pub density: f32,
+
+    /// How much each sample counts compared to the one before it.
+    /// Below `1.0`, the shafts fade with distance from the light.
+    pub decay: f32,
+
+    /// How much each sample counts to begin with.
+    pub weight: f32,
+
+    /// The overall brightness of the shafts.
+    pub exposure: f32,
+
+    /// How bright a pixel must be to cast shafts.
+    pub threshold: f32,
+
+    /// How many samples are taken towards the light.
+    /// More samples give smoother shafts, but cost more.
+    pub samples: u32,
+}
+
+impl Default for LightShafts {
+    fn default() -> Self {
+        Self {
+            light_position: Vec2::new(0.5, 0.3),
+            density: 0.85,
+            decay: 0.96,
+            weight: 0.5,
+            exposure: 0.1,
+            threshold: 0.7,
+            samples: 64,
+        }
+    }
+}
+
+impl Display for LightShafts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Light Shafts at [{:.2}, {:.2}], density: {:.2}, decay: {:.3}, weight: {:.2}, exposure: {:.2}, threshold: {:.2}, samples: {}",
+            self.light_position.x,
+            self.light_position.y,
+            self.density,
+            self.decay,
+            self.weight,
+            self.exposure,
+            self.threshold,
+            self.samples
+        )
+    }
+}
+
+/// Keeps the [`LightShafts::light_position`] of a camera on a light entity.
+///
+/// The position is projected from the light's [`GlobalTransform`] each frame.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct LightShaftsFollow {
+    /// The light entity.
+    pub light: Entity,
+
+    /// If true, the light is treated as infinitely far away, shining along its forward direction
+    /// (like a directional light, e.g. the sun). Otherwise its position is used.
+    pub directional: bool,
+}
+
+impl LightShaftsFollow {
+    /// Follow a light with a position, such as a point light or a lamp.
+    pub fn point(light: Entity) -> Self {
+        Self {
+            light,
+            directional: false,
+        }
+    }
+
+    /// Follow a light which shines along its forward direction, such as the sun.
+    pub fn directional(light: Entity) -> Self {
+        Self {
+            light,
+            directional: true,
+        }
+    }
+}
+
+impl FromWorld for LightShaftsFollow {
+    fn from_world(_world: &mut World) -> Self {
+        Self::point(Entity::PLACEHOLDER)
+    }
+}
+
+/// Projects a light to normalized device coordinates of the camera.
+///
+/// Directional lights are treated as infinitely far away, shining along their forward direction.
+/// `None` if the light is behind the camera.
+pub(crate) fn light_ndc(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    light_transform: &GlobalTransform,
+    directional: bool,
+) -> Option<Vec3> {
+    let world_position = if directional {
+        camera_transform.translation() - light_transform.forward() * 1_000_000.0
+    } else {
+        light_transform.translation()
+    };
+
+    camera
+        .world_to_ndc(camera_transform, world_position)
+        .filter(|ndc| ndc.z > 0.0)
+}
+
+fn follow_light(
+    mut cameras: Query<(
+        &Camera,
+        &GlobalTransform,
+        &LightShaftsFollow,
+        &mut LightShafts,
+    )>,
+    lights: Query<&GlobalTransform>,
+) {
+    for (camera, camera_transform, follow, mut light_shafts) in &mut cameras {
+        let Ok(light_transform) = lights.get(follow.light) else {
+            continue;
+        };
+
+        let position = light_ndc(
+            camera,
+            camera_transform,
+            light_transform,
+            follow.directional,
+        )
+        .map(|ndc| Vec2::new(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5))
+        // Behind the camera, put the light far off screen so the shafts fade out.
+        .unwrap_or(Vec2::splat(-100.0));
+
+        if light_shafts.light_position != position {
+            light_shafts.light_position = position;
+        }
+    }
+}
+
+impl ExtractComponent for LightShafts {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::light_shafts::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
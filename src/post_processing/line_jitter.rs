@@ -0,0 +1,264 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const LINE_JITTER_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5830417720958812364);
+
+#[derive(Resource)]
+pub(crate) struct LineJitterData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for LineJitterData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Line Jitter",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(LineJitterUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            LINE_JITTER_SHADER_HANDLE.typed(),
+        );
+
+        LineJitterData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LINE_JITTER_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "line_jitter.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<LineJitter>();
+        super::register_effect_types::<LineJitter>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<LineJitter>::default(),
+            UniformComponentPlugin::<LineJitterUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<LineJitter>,
+            )
+            .init_resource::<LineJitterData>()
+            .init_resource::<UniformBindGroup<LineJitterUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<LineJitterUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<LineJitterData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<LineJitter>,
+        &Blend<LineJitter>,
+        &Resolution<LineJitter>,
+        &SourceSampler<LineJitter>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::line_jitter::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<LineJitterUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<LineJitterData>,
+    mut bind_group: ResMut<UniformBindGroup<LineJitterUniform>>,
+    uniforms: Res<ComponentUniforms<LineJitterUniform>>,
+    views: Query<Entity, With<LineJitterUniform>>,
+) {
+    let _span = info_span!("vfx::line_jitter::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Line Jitter Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Offsets each line of the image sideways by a small random amount, like an analog display
+/// with an unstable horizontal hold.
+///
+/// The offsets change a few times per second rather than every frame, so the image shakes
+/// instead of shimmering. Optionally the image also rolls vertically, like a display which has
+/// lost vertical sync.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct LineJitter {
+    /// How far lines are offset at most, in pixels.
+    pub amplitude: f32,
+
+    /// How many times per second the offsets change.
+    pub frequency: f32,
+
+    /// How tall each line is, in pixels.
+    pub line_height: f32,
+
+    /// How fast the image rolls upwards, in heights of the image per second.
+    /// `0.0` means no roll, negative values roll downwards.
+    pub roll: f32,
+}
+
+impl LineJitter {
+    /// Jitter which also rolls the image, like a display which has lost vertical sync.
+    pub fn rolling() -> Self {
+        Self {
+            roll: 0.25,
+            ..default()
+        }
+    }
+}
+
+impl Default for LineJitter {
+    fn default() -> Self {
+        Self {
+            amplitude: 3.0,
+            frequency: 12.0,
+            line_height: 2.0,
+            roll: 0.0,
+        }
+    }
+}
+
+impl Display for LineJitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Line Jitter amplitude: {:.1}, frequency: {:.1}, line height: {:.1}, roll: {:.2}",
+            self.amplitude, self.frequency, self.line_height, self.roll
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`LineJitter`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct LineJitterUniform {
+    pub(crate) amplitude: f32,
+    pub(crate) frequency: f32,
+    pub(crate) line_height: f32,
+    pub(crate) roll: f32,
+}
+
+impl From<LineJitter> for LineJitterUniform {
+    fn from(jitter: LineJitter) -> Self {
+        Self {
+            amplitude: jitter.amplitude,
+            frequency: jitter.frequency,
+            line_height: jitter.line_height,
+            roll: jitter.roll,
+        }
+    }
+}
+
+impl ExtractComponent for LineJitter {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = LineJitterUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::line_jitter::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
+
+#[cfg(feature = "material2d")]
+impl super::material::MaterialEffect for LineJitter {
+    type Uniform = LineJitterUniform;
+    type Key = ();
+
+    const MATERIAL_UUID: bevy::utils::Uuid =
+        bevy::utils::Uuid::from_u128(0x5a511f814fbf48e6a82113f5c09a2845);
+
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        bevy::render::render_resource::ShaderRef::Handle(LINE_JITTER_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        (*self).into()
+    }
+
+    fn key(&self) {}
+}
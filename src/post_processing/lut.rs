@@ -1,5 +1,5 @@
 use bevy::{
-    asset::load_internal_asset,
+    asset::{load_internal_asset, HandleId},
     ecs::{
         query::{QueryItem, ROQueryItem},
         system::{lifetimeless::Read, SystemParamItem},
@@ -25,7 +25,10 @@ use bevy::{
     },
 };
 
-use super::{DrawPostProcessing, Order, PostProcessingPhaseItem, SetTextureSamplerGlobals};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessing, Order,
+    PostProcessingPhaseItem, SetTextureSamplerGlobals,
+};
 
 pub(crate) const LUT_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3719875149378986812);
@@ -167,6 +170,9 @@ impl bevy::prelude::Plugin for Plugin {
         .expect("Should load LUT successfully");
         assets.set_untracked(LUT_ARCTIC_IMAGE_HANDLE, image);
 
+        app.register_type::<Lut>();
+        super::register_effect_types::<Lut>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins(ExtractComponentPlugin::<Lut>::default())
             .add_systems(PostUpdate, adapt_image_for_lut_use);
@@ -183,18 +189,17 @@ impl bevy::prelude::Plugin for Plugin {
     }
 }
 
-fn adapt_image_for_lut_use(
-    mut assets: ResMut<Assets<Image>>,
-    mut luts: Query<&mut Lut, Changed<Lut>>,
-) {
+fn adapt_image_for_lut_use(mut assets: ResMut<Assets<Image>>, mut luts: Query<&mut Lut>) {
     for mut lut in luts.iter_mut() {
-        if lut.prepared {
+        if lut.is_prepared() {
             continue;
         }
 
-        let image = assets
-            .get_mut(&lut.texture)
-            .expect("Handle should point to asset");
+        // The texture may be swapped out via reflection or loaded from a scene,
+        // in which case it may still be loading.
+        let Some(image) = assets.get_mut(&lut.texture) else {
+            continue;
+        };
 
         // The LUT is a 3d texture. It has 64 layers, each of which is a 64x64 image.
         image.texture_descriptor.size = Extent3d {
@@ -213,11 +218,10 @@ fn adapt_image_for_lut_use(
         });
 
         debug!("LUT prepared for handle {:?}", lut.texture);
-        lut.prepared = true;
+        lut.prepared = Some(lut.texture.id());
     }
 }
 
-#[allow(clippy::type_complexity)]
 fn prepare(
     data: Res<LutData>,
     mut views: Query<
@@ -225,17 +229,25 @@ fn prepare(
             Entity,
             &mut RenderPhase<PostProcessingPhaseItem>,
             &Order<Lut>,
+            &Blend<Lut>,
+            &Resolution<Lut>,
+            &SourceSampler<Lut>,
         ),
         With<Lut>,
     >,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
+    let _span = info_span!("vfx::lut::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
         let draw_function = draw_functions.read().id::<DrawLut>();
 
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
             pipeline_id: data.pipeline_id,
         });
@@ -249,6 +261,8 @@ fn queue(
     images: Res<RenderAssets<Image>>,
     luts: Query<(Entity, &Lut)>,
 ) {
+    let _span = info_span!("vfx::lut::queue").entered();
+
     for (entity, lut) in luts.iter() {
         if let Some(lut_image) = images.get(&lut.texture) {
             let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
@@ -274,12 +288,15 @@ fn queue(
 }
 
 /// A look-up texture. Maps colors to colors. Useful for colorschemes.
-#[derive(Debug, Component, Clone)]
+#[derive(Debug, Component, Clone, Reflect)]
+#[reflect(Component, Default)]
 pub struct Lut {
     /// The 3D look-up texture
     texture: Handle<Image>,
 
-    prepared: bool,
+    /// The texture which was last prepared for use as a LUT, if any.
+    #[reflect(ignore)]
+    prepared: Option<HandleId>,
 }
 
 impl Lut {
@@ -289,10 +306,14 @@ impl Lut {
     pub fn new(texture: Handle<Image>) -> Self {
         Self {
             texture,
-            prepared: false,
+            prepared: None,
         }
     }
 
+    fn is_prepared(&self) -> bool {
+        self.prepared == Some(self.texture.id())
+    }
+
     /// The arctic color scheme LUT.
     pub fn arctic() -> Self {
         Self::new(LUT_ARCTIC_IMAGE_HANDLE.typed_weak())
@@ -321,7 +342,9 @@ impl ExtractComponent for Lut {
     type Out = Self;
 
     fn extract_component((lut, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
-        if !camera.is_active || !lut.prepared {
+        let _span = info_span!("vfx::lut::extract").entered();
+
+        if !camera.is_active || !lut.is_prepared() {
             return None;
         }
 
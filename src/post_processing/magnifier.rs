@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const MAGNIFIER_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4431921693287452179);
+
+#[derive(Resource)]
+pub(crate) struct MagnifierData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for MagnifierData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Magnifier",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(MagnifierUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            MAGNIFIER_SHADER_HANDLE.typed(),
+        );
+
+        MagnifierData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            MAGNIFIER_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "magnifier.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Magnifier>();
+        super::register_effect_types::<Magnifier>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Magnifier>::default(),
+            UniformComponentPlugin::<MagnifierUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Magnifier>,
+            )
+            .init_resource::<MagnifierData>()
+            .init_resource::<UniformBindGroup<MagnifierUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<MagnifierUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<MagnifierData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Magnifier>,
+        &Blend<Magnifier>,
+        &Resolution<Magnifier>,
+        &SourceSampler<Magnifier>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::magnifier::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<MagnifierUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<MagnifierData>,
+    mut bind_group: ResMut<UniformBindGroup<MagnifierUniform>>,
+    uniforms: Res<ComponentUniforms<MagnifierUniform>>,
+    views: Query<Entity, With<MagnifierUniform>>,
+) {
+    let _span = info_span!("vfx::magnifier::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Magnifier Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Enlarges the image within a circle, like a loupe.
+///
+/// Useful for accessibility zoom, or for scopes and inspecting things,
+/// without rendering the scene again with a second camera.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Magnifier {
+    /// The middle of the circle, in UV coordinates where `(0, 0)` is the top left of the image.
+    pub center: Vec2,
+
+    /// The radius of the circle, as a fraction of the height of the image.
+    pub radius: f32,
+
+    /// How much the image within the circle is enlarged. `1.0` leaves it unchanged.
+    pub zoom: f32,
+
+    /// The width of the border around the circle, in pixels. `0.0` has no border.
+    pub border_width: f32,
+
+    /// The color of the border.
+    pub border_color: Color,
+}
+
+impl Magnifier {
+    /// A magnifier at the given point, in UV coordinates.
+    pub fn at(center: Vec2) -> Self {
+        Self {
+            center,
+            ..default()
+        }
+    }
+}
+
+impl Default for Magnifier {
+    fn default() -> Self {
+        Self {
+            center: Vec2::splat(0.5),
+            radius: 0.2,
+            zoom: 2.0,
+            border_width: 3.0,
+            border_color: Color::BLACK,
+        }
+    }
+}
+
+impl Display for Magnifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Magnifier center: {:.2}, radius: {:.2}, zoom: {:.1}, border width: {:.1}, border color: {:?}",
+            self.center, self.radius, self.zoom, self.border_width, self.border_color
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Magnifier`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct MagnifierUniform {
+    pub(crate) border_color: Vec4,
+    pub(crate) center: Vec2,
+    pub(crate) radius: f32,
+    pub(crate) zoom: f32,
+    pub(crate) border_width: f32,
+}
+
+impl From<Magnifier> for MagnifierUniform {
+    fn from(magnifier: Magnifier) -> Self {
+        Self {
+            border_color: Vec4::from(magnifier.border_color.as_linear_rgba_f32()),
+            center: magnifier.center,
+            radius: magnifier.radius,
+            zoom: magnifier.zoom,
+            border_width: magnifier.border_width,
+        }
+    }
+}
+
+impl ExtractComponent for Magnifier {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = MagnifierUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::magnifier::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -21,7 +21,10 @@ use std::fmt::Display;
 
 use crate::post_processing::DrawPostProcessingEffect;
 
-use super::{Order, PostProcessingPhaseItem, UniformBindGroup};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+    UniformBindGroup,
+};
 pub(crate) const MASK_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1059400090272595510);
 
@@ -69,6 +72,10 @@ impl bevy::prelude::Plugin for Plugin {
             Shader::from_wgsl
         );
 
+        app.register_type::<Mask>();
+        app.register_type::<MaskVariant>();
+        super::register_effect_types::<Mask>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins((
             ExtractComponentPlugin::<Mask>::default(),
@@ -111,11 +118,16 @@ fn prepare(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<Mask>,
+        &Blend<Mask>,
+        &Resolution<Mask>,
+        &SourceSampler<Mask>,
         &MaskVariant,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order, key) in views.iter_mut() {
+    let _span = info_span!("vfx::masks::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
         let draw_function = draw_functions
             .read()
             .id::<DrawPostProcessingEffect<MaskUniform>>();
@@ -125,6 +137,9 @@ fn prepare(
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
             pipeline_id,
         });
@@ -138,6 +153,8 @@ fn queue(
     uniforms: Res<ComponentUniforms<MaskUniform>>,
     views: Query<Entity, With<MaskUniform>>,
 ) {
+    let _span = info_span!("vfx::masks::queue").entered();
+
     bind_group.inner = None;
 
     if let Some(uniforms) = uniforms.binding() {
@@ -155,7 +172,8 @@ fn queue(
 }
 
 /// This controls the parameters of the effect.
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Component)]
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaskVariant {
     /// Rounded square type mask.
     ///
@@ -203,8 +221,11 @@ impl From<MaskVariant> for ShaderDefVal {
     }
 }
 
-/// A darkening mask on the outer edges of the image.
-#[derive(Debug, Copy, Clone, Component)]
+/// A mask on the outer edges of the image, which darkens them unless given another color.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
 pub struct Mask {
     /// The strength parameter of the mask in use.
     ///
@@ -219,14 +240,26 @@ pub struct Mask {
 
     /// Which [`MaskVariant`] to produce.
     pub variant: MaskVariant,
+
+    /// How far the middle of the vignette moves toward the nose on cameras with a
+    /// [`super::stereo::StereoEye`], in UV coordinates.
+    ///
+    /// Each eye sees the middle of the scene off to the nose side of its image, so a vignette
+    /// centered on each image looks lopsided once the eyes are fused. Around `0.05` is comfortable.
+    /// Has no effect on other variants, or cameras without an eye.
+    pub nasal_offset: f32,
+
+    /// The color the masked parts of the image fade to.
+    /// Black, the default, darkens them, and e.g. red makes a vignette for taking damage.
+    pub color: Color,
 }
 
 impl Display for Mask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Mask {:?}, strength: {} fade: {}",
-            self.variant, self.strength, self.fade
+            "Mask {:?}, strength: {} fade: {} nasal offset: {}",
+            self.variant, self.strength, self.fade, self.nasal_offset
         )
     }
 }
@@ -238,6 +271,8 @@ impl Mask {
             strength: 20.,
             fade: 0.,
             variant: MaskVariant::Square,
+            nasal_offset: 0.,
+            color: Color::BLACK,
         }
     }
 
@@ -247,6 +282,8 @@ impl Mask {
             strength: 80000.,
             fade: 0.,
             variant: MaskVariant::Crt,
+            nasal_offset: 0.,
+            color: Color::BLACK,
         }
     }
 
@@ -256,6 +293,8 @@ impl Mask {
             strength: 0.66,
             fade: 0.,
             variant: MaskVariant::Vignette,
+            nasal_offset: 0.,
+            color: Color::BLACK,
         }
     }
 }
@@ -272,6 +311,8 @@ impl Default for Mask {
 pub struct MaskUniform {
     pub(crate) strength: f32,
     pub(crate) fade: f32,
+    pub(crate) nasal_offset: f32,
+    pub(crate) color: Vec4,
 }
 
 impl From<Mask> for MaskUniform {
@@ -279,6 +320,8 @@ impl From<Mask> for MaskUniform {
         Self {
             strength: mask.strength,
             fade: mask.fade,
+            nasal_offset: mask.nasal_offset,
+            color: Vec4::from(mask.color.as_linear_rgba_f32()),
         }
     }
 }
@@ -289,6 +332,8 @@ impl ExtractComponent for Mask {
     type Out = (MaskUniform, MaskVariant);
 
     fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::masks::extract").entered();
+
         if !camera.is_active {
             return None;
         }
@@ -296,3 +341,28 @@ impl ExtractComponent for Mask {
         Some(((*settings).into(), settings.variant))
     }
 }
+
+#[cfg(feature = "material2d")]
+impl super::material::MaterialEffect for Mask {
+    type Uniform = MaskUniform;
+    type Key = MaskVariant;
+
+    const MATERIAL_UUID: bevy::utils::Uuid =
+        bevy::utils::Uuid::from_u128(0xa7c1aadab6944121a26afdc9e5b5c664);
+
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        bevy::render::render_resource::ShaderRef::Handle(MASK_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        (*self).into()
+    }
+
+    fn key(&self) -> Self::Key {
+        self.variant
+    }
+
+    fn shader_defs(key: &Self::Key) -> Vec<ShaderDefVal> {
+        vec![(*key).into()]
+    }
+}
@@ -0,0 +1,314 @@
+use std::hash::Hash;
+
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    render::{
+        mesh::MeshVertexBufferLayout,
+        render_asset::RenderAssets,
+        render_resource::{
+            encase::{self, internal::WriteInto},
+            AsBindGroup, AsBindGroupError, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+            BufferBindingType, BufferInitDescriptor, BufferUsages, OwnedBindingResource,
+            PreparedBindGroup, RenderPipelineDescriptor, SamplerBindingType, ShaderDefVal,
+            ShaderRef, ShaderStages, ShaderType, SpecializedMeshPipelineError, TextureSampleType,
+            TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        texture::FallbackImage,
+    },
+    sprite::{Material2d, Material2dKey, Material2dPlugin},
+    utils::Uuid,
+};
+
+use super::{
+    chromatic_aberration::ChromaticAberration, dissolve::Dissolve, flip::Flip,
+    line_jitter::LineJitter, masks::Mask, pixelate::Pixelate, wave::Wave,
+};
+
+pub(crate) const MATERIAL_INTERFACE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6148210417253904473);
+
+/// An effect which can also be drawn as an [`EffectMaterial2d`].
+///
+/// The shader of the effect is the same one it uses for post processing.
+/// With the `EFFECT_MATERIAL` shader def, it imports its bindings from `bevy_vfx_bag::material`
+/// instead of declaring them, and takes the output of Bevy's 2d mesh vertex shader:
+///
+/// ```wgsl
+/// #ifdef EFFECT_MATERIAL
+/// #import bevy_vfx_bag::material VertexOutput, source, source_sampler, globals, finish
+/// #endif
+///
+/// // The uniform of the effect is at binding 2 of the material.
+/// #ifdef EFFECT_MATERIAL
+/// @group(1) @binding(2)
+/// #else
+/// @group(1) @binding(0)
+/// #endif
+/// var<uniform> sepia: Sepia;
+///
+/// #ifdef EFFECT_MATERIAL
+/// @fragment
+/// fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+///     // `finish` applies the `fade` of the material.
+///     return finish(in.uv, effect(in.uv));
+/// }
+/// #endif
+/// ```
+///
+/// Register the material of effects of other crates with
+/// `Material2dPlugin::<EffectMaterial2d<Sepia>>::default()`.
+pub trait MaterialEffect: Clone + Send + Sync + TypePath + 'static {
+    /// The effect as it is passed to its shader.
+    type Uniform: ShaderType + WriteInto;
+
+    /// The variant of the shader the effect needs, e.g. [`super::masks::MaskVariant`].
+    type Key: Clone + PartialEq + Eq + Hash + Send + Sync;
+
+    /// Identifies the [`EffectMaterial2d`] of this effect as an asset.
+    const MATERIAL_UUID: Uuid;
+
+    /// The shader of the effect.
+    fn fragment_shader() -> ShaderRef;
+
+    /// The effect as it is passed to its shader.
+    fn uniform(&self) -> Self::Uniform;
+
+    /// The variant of the shader these settings need.
+    fn key(&self) -> Self::Key;
+
+    /// The shader defs of the variant, on top of `EFFECT_MATERIAL`.
+    fn shader_defs(_key: &Self::Key) -> Vec<ShaderDefVal> {
+        vec![]
+    }
+}
+
+/// Draws the effect `C` on an image, as the material of a sprite or mesh of its own,
+/// instead of on everything a camera sees.
+///
+/// ```no_run
+/// # use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+/// # use bevy_vfx_bag::post_processing::{material::EffectMaterial2d, pixelate::Pixelate};
+/// # let mut commands: Commands = panic!();
+/// # let mut meshes: ResMut<Assets<Mesh>> = panic!();
+/// # let mut materials: ResMut<Assets<EffectMaterial2d<Pixelate>>> = panic!();
+/// # let image: Handle<Image> = panic!();
+/// commands.spawn(MaterialMesh2dBundle {
+///     mesh: meshes.add(shape::Quad::new(Vec2::splat(256.0)).into()).into(),
+///     material: materials.add(EffectMaterial2d::new(Pixelate::default(), image)),
+///     ..default()
+/// });
+/// ```
+///
+/// Effects keep the alpha of the image, so sprites keep their shape.
+/// Animated effects follow Bevy's [`Time`] here, not [`crate::time::VfxTime`].
+///
+/// UI nodes can't have materials of their own in this version of Bevy. For those, draw the effect
+/// into an image with a [`super::process_image::ProcessImage`], and show that with a `UiImage`.
+#[derive(Debug, Clone, TypePath)]
+pub struct EffectMaterial2d<C: MaterialEffect> {
+    /// The settings of the effect.
+    pub effect: C,
+
+    /// The image the effect is drawn on.
+    pub image: Handle<Image>,
+
+    /// How much the effect is faded: `0.0` is the full effect, `1.0` is the image as is.
+    pub fade: f32,
+}
+
+impl<C: MaterialEffect> EffectMaterial2d<C> {
+    /// Draw the effect on the given image.
+    pub fn new(effect: C, image: Handle<Image>) -> Self {
+        Self {
+            effect,
+            image,
+            fade: 0.0,
+        }
+    }
+
+    /// Start with the effect faded by the given amount.
+    pub fn with_fade(mut self, fade: f32) -> Self {
+        self.fade = fade;
+        self
+    }
+}
+
+impl<C: MaterialEffect> TypeUuid for EffectMaterial2d<C> {
+    const TYPE_UUID: Uuid = C::MATERIAL_UUID;
+}
+
+/// The part of [`EffectMaterial2d`] which is the same for all effects, as a uniform.
+#[derive(Debug, ShaderType, Clone)]
+struct EffectMaterialUniform {
+    fade: f32,
+}
+
+fn uniform_buffer<T: ShaderType + WriteInto>(
+    render_device: &RenderDevice,
+    label: &'static str,
+    value: &T,
+) -> Buffer {
+    let mut uniform = encase::UniformBuffer::new(vec![]);
+    uniform
+        .write(value)
+        .expect("Effect material uniform should be writable");
+
+    render_device.create_buffer_with_data(&BufferInitDescriptor {
+        label: Some(label),
+        contents: &uniform.into_inner(),
+        usage: BufferUsages::UNIFORM,
+    })
+}
+
+fn uniform_layout_entry(binding: u32, min_size: std::num::NonZeroU64) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::FRAGMENT,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: Some(min_size),
+        },
+        count: None,
+    }
+}
+
+impl<C: MaterialEffect> AsBindGroup for EffectMaterial2d<C> {
+    type Data = C::Key;
+
+    fn as_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<Image>,
+        _fallback_image: &FallbackImage,
+    ) -> Result<PreparedBindGroup<Self::Data>, AsBindGroupError> {
+        let Some(image) = images.get(&self.image) else {
+            return Err(AsBindGroupError::RetryNextUpdate);
+        };
+
+        let effect = uniform_buffer(
+            render_device,
+            "Effect Material Effect Uniform Buffer",
+            &self.effect.uniform(),
+        );
+        let material = uniform_buffer(
+            render_device,
+            "Effect Material Uniform Buffer",
+            &EffectMaterialUniform { fade: self.fade },
+        );
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Effect Material Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&image.texture_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&image.sampler),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: effect.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: material.as_entire_binding(),
+                },
+            ],
+        });
+
+        Ok(PreparedBindGroup {
+            bindings: vec![
+                OwnedBindingResource::TextureView(image.texture_view.clone()),
+                OwnedBindingResource::Sampler(image.sampler.clone()),
+                OwnedBindingResource::Buffer(effect),
+                OwnedBindingResource::Buffer(material),
+            ],
+            bind_group,
+            data: self.effect.key(),
+        })
+    }
+
+    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Effect Material Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                uniform_layout_entry(2, C::Uniform::min_size()),
+                uniform_layout_entry(3, EffectMaterialUniform::min_size()),
+            ],
+        })
+    }
+}
+
+impl<C: MaterialEffect> Material2d for EffectMaterial2d<C> {
+    fn fragment_shader() -> ShaderRef {
+        C::fragment_shader()
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.push("EFFECT_MATERIAL".into());
+            fragment
+                .shader_defs
+                .extend(C::shader_defs(&key.bind_group_data));
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            MATERIAL_INTERFACE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "material.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(super::dissolve::Plugin);
+
+        app.add_plugins((
+            Material2dPlugin::<EffectMaterial2d<ChromaticAberration>>::default(),
+            Material2dPlugin::<EffectMaterial2d<Dissolve>>::default(),
+            Material2dPlugin::<EffectMaterial2d<Flip>>::default(),
+            Material2dPlugin::<EffectMaterial2d<LineJitter>>::default(),
+            Material2dPlugin::<EffectMaterial2d<Mask>>::default(),
+            Material2dPlugin::<EffectMaterial2d<Pixelate>>::default(),
+            Material2dPlugin::<EffectMaterial2d<Wave>>::default(),
+        ));
+    }
+}
@@ -1,20 +1,24 @@
-use std::{marker::PhantomData, sync::Mutex};
+use std::{any::Any, marker::PhantomData, num::NonZeroU64, sync::Mutex};
 
 use bevy::{
-    core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    core_pipeline::{
+        blit::BlitPipeline, core_2d, core_3d,
+        fullscreen_vertex_shader::fullscreen_shader_vertex_state, prepass::ViewPrepassTextures,
+    },
     ecs::{
-        query::ROQueryItem,
+        query::{Has, ROQueryItem},
         system::{
             lifetimeless::{Read, SRes},
             SystemParamItem,
         },
     },
     prelude::*,
+    reflect::{GetTypeRegistration, TypePath},
     render::{
         camera::ExtractedCamera,
         extract_component::DynamicUniformIndex,
-        globals::{GlobalsBuffer, GlobalsUniform},
-        render_graph::{Node, NodeRunError, RenderGraphContext},
+        render_asset::RenderAssets,
+        render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
         render_phase::{
             sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions,
             PhaseItem, RenderCommand, RenderCommandResult, RenderPhase, SetItemPipeline,
@@ -23,44 +27,445 @@ use bevy::{
         render_resource::{
             BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
             BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
-            BufferBindingType, CachedRenderPipelineId, FilterMode, FragmentState, MultisampleState,
-            Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
-            RenderPassDescriptor, RenderPipelineDescriptor, SamplerBindingType, SamplerDescriptor,
-            ShaderDefVal, ShaderStages, ShaderType, TextureFormat, TextureSampleType,
-            TextureViewDimension, TextureViewId,
+            BufferBindingType, CachedPipelineState, CachedRenderPipelineId, FragmentState, LoadOp,
+            MultisampleState, Operations, Pipeline, PipelineCache, PrimitiveState,
+            RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline,
+            RenderPipelineDescriptor, SamplerBindingType, ShaderDefVal, ShaderStages, ShaderType,
+            Texture, TextureFormat, TextureSampleType, TextureView, TextureViewDimension,
+            TextureViewId,
         },
         renderer::{RenderContext, RenderDevice},
-        texture::BevyDefault,
+        texture::{BevyDefault, FallbackImage, FallbackImageZero, FallbackImagesDepth, GpuImage},
         view::{ExtractedView, ViewTarget},
         Extract, Render, RenderApp, RenderSet,
     },
     utils::{FloatOrd, HashMap},
 };
 
+use crate::{
+    metadata::{self, ParameterRange, ReflectParameterRange},
+    time::{VfxGlobalsBuffer, VfxGlobalsUniform},
+};
+
+/// Ascii
+pub mod ascii;
+
+/// Auto Exposure
+pub mod auto_exposure;
+
+/// Blending the output of effects with the image they started from.
+pub mod blend;
+
 /// Blur
 pub mod blur;
 
+/// Bulge
+pub mod bulge;
+
+/// Shedding effects when the frame time exceeds a budget.
+pub mod budget;
+
+/// Bypassing effects at runtime, e.g. for before and after comparisons.
+pub mod bypass;
+
 /// Chromatic Aberration
 pub mod chromatic_aberration;
 
+/// Color Adjust
+pub mod color_adjust;
+
+/// Color Blindness
+pub mod color_blindness;
+
+/// Drawing chains of simple effects in a single pass.
+pub mod combined;
+
+/// Composing overlay effects onto the image, instead of drawing the image anew.
+pub mod composition;
+
+/// Compositing the images of several cameras in an explicit order.
+pub mod compositor;
+
+/// Running kernel-heavy effects as compute shaders, where the adapter supports them.
+pub mod compute;
+
+/// User-defined post processing effects, written in WGSL.
+pub mod custom;
+
+/// Day For Night
+pub mod day_for_night;
+
+/// Dead Pixels
+pub mod dead_pixels;
+
+/// Deband
+pub mod deband;
+
+/// Showing the image after each effect, for finding which effect produces an artifact.
+pub mod debug_view;
+
+/// Timing the effects on the GPU, reported as Bevy diagnostics.
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+
+/// Depth Grade
+pub mod depth_grade;
+
+/// Depth of Field
+pub mod depth_of_field;
+
+/// Dissolving sprites and meshes away, drawn as an [`material::EffectMaterial2d`].
+#[cfg(feature = "material2d")]
+pub mod dissolve;
+
+/// Dither
+pub mod dither;
+
+/// Edge Fringe
+pub mod edge_fringe;
+
+/// Effects shipped by other crates, handled like the effects of this crate.
+pub mod external;
+
+/// False Color
+pub mod false_color;
+
+/// Film Projector
+pub mod film_projector;
+
 /// Flip
 pub mod flip;
 
+/// Fog
+pub mod fog;
+
+/// Fog 2d
+pub mod fog_2d;
+
+/// A mask texture which render passes of the app draw into, for limiting effects to parts of the image.
+pub mod effect_mask;
+
+/// Drawing effects into cameras of any texture format, e.g. HDR cameras.
+pub(crate) mod formats;
+
+/// Frosted Glass
+pub mod frosted_glass;
+
+/// Ghosting
+pub mod ghosting;
+
+/// Glow
+pub mod glow;
+
+/// God Rays 2d
+pub mod god_rays_2d;
+
+/// Enabling, disabling and fading named groups of effects together.
+pub mod group;
+
+/// Halftone
+pub mod halftone;
+
+/// Heat Haze
+pub mod heat_haze;
+
+/// Hologram
+pub mod hologram;
+
+/// Previous frame state of cameras, for temporal effects.
+pub mod history;
+
+/// Softening and disabling effects without removing them.
+pub mod intensity;
+
+/// Kuwahara
+pub mod kuwahara;
+
+/// Drawing render layers into images of their own, for 2d effects which need to know where sprites are.
+pub(crate) mod layer_images;
+
+/// Lens Distortion
+pub mod lens_distortion;
+
+/// Lens Flare
+pub mod lens_flare;
+
+/// Letterbox
+pub mod letterbox;
+
+/// Light Leak
+pub mod light_leak;
+
+/// Light Shafts
+pub mod light_shafts;
+
+/// Line Jitter
+pub mod line_jitter;
+
 /// LUT
 pub mod lut;
 
+/// Magnifier
+pub mod magnifier;
+
+/// Old Film
+pub mod old_film;
+
+/// Outline
+pub mod outline;
+
+/// Keeping the effects of overlay cameras to what they draw, and compositing them.
+pub mod overlay;
+
 /// Masks
 pub mod masks;
 
+/// Drawing effects on sprites and meshes of their own, as a `Material2d`.
+#[cfg(feature = "material2d")]
+pub mod material;
+
+/// Motion Blur
+pub mod motion_blur;
+
+/// Palette Swap
+pub mod palette_swap;
+
 /// Pixelate
 pub mod pixelate;
 
+/// Precompiling effect pipelines, e.g. during loading screens.
+pub mod precompile;
+
+/// Adding the prepasses effects read to the cameras they are on.
+pub mod prepass;
+
+/// Applying effects to images instead of what a camera sees, e.g. for UI thumbnails.
+pub mod process_image;
+
+/// PSX Wobble
+pub mod psx_wobble;
+
+/// Quality tiers which scale the cost of the heavier effects.
+pub mod quality;
+
 /// Raindrops
 pub mod raindrops;
 
+/// Knowing when effects are ready to render, or failed to compile.
+pub mod readiness;
+
+/// Drawing effects at a lower resolution.
+pub mod resolution;
+
+/// Retro Fog
+pub mod retro_fog;
+
+/// Rolling Shutter
+pub mod rolling_shutter;
+
+/// Choosing how effects sample the image, e.g. nearest pixels for pixel art.
+pub mod sampler;
+
+/// Screen Door Fade
+pub mod screen_door_fade;
+
+/// Screen Transition
+pub mod screen_transition;
+
+/// Limiting effects to entities, by drawing them into the effect mask.
+#[cfg(feature = "selective")]
+pub mod selective;
+
+/// Sharpen
+pub mod sharpen;
+
+/// Sketch
+pub mod sketch;
+
+/// Shockwave
+pub mod shockwave;
+
+/// Speed Lines
+pub mod speed_lines;
+
+/// Reading the image of a camera back with its effects, e.g. for photo modes.
+pub mod snapshot;
+
+/// Keeping regions of the image, such as the HUD, free of the effects.
+pub mod stable_regions;
+
+/// Ordering effects by their position in a chain, instead of by hand.
+pub mod stack;
+
+/// Stereo rendering, with effects knowing which eye they render.
+pub mod stereo;
+
+/// Applying effects to the images of UI nodes.
+#[cfg(feature = "ui")]
+pub mod ui;
+
+/// Underwater
+pub mod underwater;
+
+/// Drawing a first person view model, e.g. arms, over the world with its own camera.
+pub mod view_model;
+
+/// Keeping the effects of cameras with a viewport inside of it, e.g. for split-screen.
+pub(crate) mod viewport;
+
+/// Vision Mode
+pub mod vision_mode;
+
 /// Wave
 pub mod wave;
 
+/// Weather Overlay
+pub mod weather_overlay;
+
+/// Wet Screen Sheen
+pub mod wet_screen_sheen;
+
+/// White Balance
+pub mod white_balance;
+
+/// Calls the given macro with all built-in effects, so the lists of effects around the crate
+/// are generated from this one and can't miss any.
+///
+/// The arguments after the macro name are passed on first, e.g. `for_each_effect!(plugins, app)`
+/// expands to `plugins!(app, Blur(path::to::Blur) { .. }, ..)`. Each effect comes with:
+///
+/// - `state`: whether it can be part of an [`crate::state::EffectState`].
+/// - `category`: its [`bypass::EffectCategory`], or `None`.
+/// - `cost`: its [`budget::EffectCost`] as `(cost, priority)`, or `None`.
+///
+/// [`lut::Lut`] refers to an asset rather than having parameters, so it is handled on its own.
+macro_rules! for_each_effect {
+    ($callback:ident $(, $arg:expr)* $(,)?) => {
+        $crate::post_processing::for_each_effect!(
+            @paths $callback [$($arg),*]
+            ascii::Ascii { state: true, category: Stylize, cost: (1.0, Essential) },
+            auto_exposure::AutoExposure { state: true, category: Color, cost: (2.0, Essential) },
+            blur::Blur { state: true, category: Focus, cost: (2.0, Medium) },
+            bulge::Bulge { state: true, category: Distortion, cost: (1.0, Medium) },
+            chromatic_aberration::ChromaticAberration { state: true, category: Lens, cost: (1.0, Low) },
+            color_adjust::ColorAdjust { state: true, category: Color, cost: (1.0, Essential) },
+            color_blindness::ColorBlindness { state: true, category: None, cost: (1.0, Essential) },
+            day_for_night::DayForNight { state: true, category: Color, cost: (1.0, Essential) },
+            dead_pixels::DeadPixels { state: false, category: Stylize, cost: (1.0, Low) },
+            deband::Deband { state: true, category: None, cost: (0.5, Low) },
+            depth_grade::DepthGrade { state: true, category: Color, cost: (1.0, Medium) },
+            depth_of_field::DepthOfField { state: true, category: Focus, cost: (4.0, High) },
+            dither::Dither { state: true, category: Stylize, cost: (1.0, Low) },
+            edge_fringe::EdgeFringe { state: true, category: Lens, cost: (1.0, Low) },
+            false_color::FalseColor { state: true, category: None, cost: (1.0, Essential) },
+            film_projector::FilmProjector { state: true, category: Stylize, cost: (1.0, Medium) },
+            flip::Flip { state: true, category: Distortion, cost: (1.0, Essential) },
+            fog::Fog { state: true, category: Atmosphere, cost: (1.0, Medium) },
+            fog_2d::Fog2d { state: false, category: Atmosphere, cost: (2.0, Medium) },
+            frosted_glass::FrostedGlass { state: true, category: Focus, cost: (2.0, Medium) },
+            ghosting::Ghosting { state: true, category: Distortion, cost: (1.0, Low) },
+            glow::Glow { state: true, category: Lens, cost: (5.0, Medium) },
+            god_rays_2d::GodRays2d { state: false, category: Atmosphere, cost: (2.0, Medium) },
+            halftone::Halftone { state: true, category: Stylize, cost: (1.0, Essential) },
+            heat_haze::HeatHaze { state: false, category: Distortion, cost: (1.0, Low) },
+            hologram::Hologram { state: true, category: Stylize, cost: (1.0, Medium) },
+            kuwahara::Kuwahara { state: true, category: Stylize, cost: (4.0, Essential) },
+            lens_distortion::LensDistortion { state: true, category: Lens, cost: (1.0, Essential) },
+            lens_flare::LensFlare { state: true, category: Lens, cost: (2.0, Low) },
+            letterbox::Letterbox { state: true, category: None, cost: (1.0, Essential) },
+            light_leak::LightLeak { state: true, category: Lens, cost: (1.0, Low) },
+            light_shafts::LightShafts { state: true, category: Atmosphere, cost: (3.0, Medium) },
+            line_jitter::LineJitter { state: true, category: Distortion, cost: (1.0, Low) },
+            magnifier::Magnifier { state: true, category: Lens, cost: (1.0, Essential) },
+            masks::Mask { state: true, category: Lens, cost: (1.0, Essential) },
+            motion_blur::MotionBlur { state: true, category: Focus, cost: (3.0, Medium) },
+            old_film::OldFilm { state: true, category: Stylize, cost: (1.0, Medium) },
+            outline::Outline { state: true, category: Stylize, cost: (2.0, Essential) },
+            palette_swap::PaletteSwap { state: false, category: Stylize, cost: (2.0, Essential) },
+            pixelate::Pixelate { state: true, category: Stylize, cost: (1.0, Essential) },
+            psx_wobble::PsxWobble { state: true, category: Stylize, cost: (1.0, Essential) },
+            raindrops::Raindrops { state: true, category: Distortion, cost: (1.0, Low) },
+            retro_fog::RetroFog { state: true, category: Atmosphere, cost: (1.0, Medium) },
+            rolling_shutter::RollingShutter { state: true, category: Distortion, cost: (1.0, Low) },
+            screen_door_fade::ScreenDoorFade { state: true, category: None, cost: (1.0, Essential) },
+            screen_transition::ScreenTransition { state: true, category: None, cost: (1.0, Essential) },
+            sharpen::Sharpen { state: true, category: Focus, cost: (1.0, Low) },
+            sketch::Sketch { state: true, category: Stylize, cost: (2.0, Essential) },
+            shockwave::Shockwave { state: true, category: Distortion, cost: (1.0, Medium) },
+            speed_lines::SpeedLines { state: true, category: Stylize, cost: (1.0, Medium) },
+            stable_regions::StableRegions { state: false, category: None, cost: None },
+            underwater::Underwater { state: true, category: Distortion, cost: (2.0, Medium) },
+            vision_mode::VisionMode { state: true, category: Color, cost: (1.0, Essential) },
+            wave::Wave { state: true, category: Distortion, cost: (1.0, Medium) },
+            weather_overlay::WeatherOverlay { state: true, category: Atmosphere, cost: (2.0, Low) },
+            wet_screen_sheen::WetScreenSheen { state: true, category: Atmosphere, cost: (1.0, Low) },
+            white_balance::WhiteBalance { state: true, category: Color, cost: (1.0, Essential) },
+        );
+    };
+    (@paths $callback:ident [$($arg:expr),*] $($module:ident::$name:ident $meta:tt),* $(,)?) => {
+        $callback!($($arg,)* $($name($crate::post_processing::$module::$name) $meta),*);
+    };
+}
+pub(crate) use for_each_effect;
+
+/// Names of the nodes added to the 2d and 3d render graphs, for ordering nodes of the app around them.
+pub mod graph {
+    /// The nodes, which are named the same in the 2d and 3d graphs.
+    pub mod node {
+        /// Draws the effects which run before tonemapping, see [`crate::post_processing::EffectStage`].
+        pub const BEFORE_TONEMAPPING: &str = "vfx_before_tonemapping";
+
+        /// Draws the effects which run after tonemapping, see [`crate::post_processing::EffectStage`].
+        pub const AFTER_TONEMAPPING: &str = "vfx_after_tonemapping";
+
+        /// Draws the effects which run after compositing,
+        /// see [`crate::post_processing::overlay::ApplyAfterCompositing`].
+        pub const AFTER_COMPOSITING: &str = "vfx_after_compositing";
+
+        /// Keeps what the cameras below an overlay camera drew, and clears the image for the
+        /// overlay to draw on its own, before its main pass.
+        /// See [`crate::post_processing::overlay`].
+        pub const OVERLAY_BACKDROP: &str = "vfx_overlay_backdrop";
+
+        /// Keeps what an overlay camera drew, before its effects.
+        /// See [`crate::post_processing::overlay`].
+        pub const OVERLAY_COVERAGE: &str = "vfx_overlay_coverage";
+
+        /// Composites the image of an overlay camera over what the cameras below it drew,
+        /// after its effects. See [`crate::post_processing::overlay`].
+        pub const OVERLAY_COMPOSITE: &str = "vfx_overlay_composite";
+
+        /// Clears the [`crate::post_processing::effect_mask::ViewEffectMask`] of each view.
+        /// Nodes drawing into the mask go after this.
+        pub const START_EFFECT_MASK: &str = "vfx_start_effect_mask";
+
+        /// Nodes drawing into the [`crate::post_processing::effect_mask::ViewEffectMask`]
+        /// go before this, which runs before the effects.
+        pub const END_EFFECT_MASK: &str = "vfx_end_effect_mask";
+
+        /// Draws the [`crate::post_processing::selective::SelectiveEffect`] entities into the
+        /// [`crate::post_processing::effect_mask::ViewEffectMask`]. Only in the 3d graph.
+        #[cfg(feature = "selective")]
+        pub const SELECTIVE_EFFECT_MASK: &str = "vfx_selective_effect_mask";
+
+        /// Draws the input image of a [`crate::post_processing::process_image::ProcessImage`]
+        /// camera, before the effects. Only in the 2d graph.
+        pub const IMAGE_INPUT: &str = "vfx_image_input";
+
+        /// Draws the layers of a [`crate::post_processing::compositor::Compositor`] camera,
+        /// before the effects. Only in the 2d graph.
+        pub const COMPOSITE: &str = "vfx_composite";
+
+        /// Meters the image of each [`crate::post_processing::auto_exposure::AutoExposure`]
+        /// camera, after the main pass and before the effects.
+        pub const AUTO_EXPOSURE: &str = "vfx_auto_exposure";
+
+        /// Copies the image of each [`crate::post_processing::snapshot::TakeSnapshot`] camera
+        /// for reading back, after the effects.
+        pub const SNAPSHOT: &str = "vfx_snapshot";
+    }
+}
+
 #[derive(Resource)]
 pub(crate) struct UniformBindGroup<U: ShaderType> {
     pub inner: Option<BindGroup>,
@@ -90,6 +495,11 @@ pub trait PostProcessingOrder: Sized {
     /// as well as the order as a bundle.
     /// This is therefore meant as a helper when inserting the effect component.
     fn order(self, order: f32) -> (Self, Order<Self>);
+
+    /// Like [`PostProcessingOrder::order`], but also sets the stage the effect runs in.
+    ///
+    /// See [`EffectStage`] for more information.
+    fn order_in(self, stage: EffectStage, order: f32) -> (Self, Order<Self>);
 }
 
 impl<U> PostProcessingOrder for U
@@ -99,6 +509,10 @@ where
     fn order(self, order: f32) -> (Self, Order<Self>) {
         (self, Order::new(order))
     }
+
+    fn order_in(self, stage: EffectStage, order: f32) -> (Self, Order<Self>) {
+        (self, Order::new(order).with_stage(stage))
+    }
 }
 
 struct SetEffectBindGroup<U: Component + ShaderType, const I: usize>(PhantomData<U>);
@@ -144,6 +558,173 @@ type DrawPostProcessingEffect<U> = (
     DrawPostProcessing,
 );
 
+/// The bind group of an effect, for effects where it differs per camera.
+///
+/// This is the case when the bind group has more than the uniform in it which the user picks,
+/// such as an optional texture. See [`optional_image`].
+#[derive(Component)]
+pub(crate) struct ViewUniformBindGroup<U: ShaderType> {
+    pub bind_group: BindGroup,
+    marker: PhantomData<U>,
+}
+
+impl<U: ShaderType> ViewUniformBindGroup<U> {
+    pub(crate) fn new(bind_group: BindGroup) -> Self {
+        Self {
+            bind_group,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A copy of the image right before the pass of the given pipeline, for effects made up of
+/// several passes which need the image they started from again in a later pass.
+///
+/// Inserted on views in the render world while queueing. There can be one per view.
+#[derive(Component)]
+pub(crate) struct ImageCapture {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub texture: Texture,
+}
+
+/// A copy of the image before any of the effects of a pass, for effects which bring parts of it back.
+///
+/// Inserted on views in the render world while queueing.
+#[derive(Component)]
+pub(crate) struct InputCapture {
+    pub pass: EffectPass,
+    pub texture: Texture,
+}
+
+struct SetViewEffectBindGroup<U: Component + ShaderType, const I: usize>(PhantomData<U>);
+impl<P: PhaseItem, U: Component + ShaderType, const I: usize> RenderCommand<P>
+    for SetViewEffectBindGroup<U, I>
+{
+    type Param = ();
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = (
+        Read<DynamicUniformIndex<U>>,
+        Option<Read<ViewUniformBindGroup<U>>>,
+    );
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        (uniform_index, bind_group): ROQueryItem<'w, Self::ItemWorldQuery>,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        if let Some(bind_group) = bind_group {
+            pass.set_bind_group(I, &bind_group.bind_group, &[uniform_index.index()]);
+            RenderCommandResult::Success
+        } else {
+            RenderCommandResult::Failure
+        }
+    }
+}
+
+/// Like [`DrawPostProcessingEffect`], but the effect bind group is a [`ViewUniformBindGroup`].
+type DrawPostProcessingViewEffect<U> = (
+    SetItemPipeline,
+    SetTextureSamplerGlobals<0>,
+    SetViewEffectBindGroup<U, 1>,
+    DrawPostProcessing,
+);
+
+/// The image to bind for an optional texture binding of an effect.
+///
+/// Without a handle, the white `fallback` is used, so the binding is a no-op
+/// for e.g. masks which are multiplied in.
+/// While the image is loading the transparent black `fallback_zero` is used instead,
+/// so e.g. a mask doesn't let the effect through everywhere until it is loaded.
+pub(crate) fn optional_image<'a>(
+    images: &'a RenderAssets<Image>,
+    handle: Option<&Handle<Image>>,
+    fallback: &'a FallbackImage,
+    fallback_zero: &'a FallbackImageZero,
+) -> &'a GpuImage {
+    match handle {
+        Some(handle) => images.get(handle).unwrap_or(fallback_zero),
+        None => &fallback.d2,
+    }
+}
+
+/// The layout of an effect bind group with the uniform at binding 0, followed by a filterable
+/// 2D texture and a sampler for each of its images, so the first image is at bindings 1 and 2.
+pub(crate) fn uniform_and_images_layout_entries(
+    uniform_size: NonZeroU64,
+    images: usize,
+) -> Vec<BindGroupLayoutEntry> {
+    let uniform = BindGroupLayoutEntry {
+        binding: 0,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: true,
+            min_binding_size: Some(uniform_size),
+        },
+        visibility: ShaderStages::FRAGMENT,
+        count: None,
+    };
+
+    let images = (0..images as u32).flat_map(|index| {
+        [
+            BindGroupLayoutEntry {
+                binding: 1 + 2 * index,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            BindGroupLayoutEntry {
+                binding: 2 + 2 * index,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            },
+        ]
+    });
+
+    std::iter::once(uniform).chain(images).collect()
+}
+
+/// A bind group for a layout made by [`uniform_and_images_layout_entries`].
+///
+/// Each image is bound with its own sampler. Use [`optional_image`] to get images
+/// which may be missing or still loading.
+pub(crate) fn uniform_and_images_bind_group(
+    render_device: &RenderDevice,
+    label: &str,
+    layout: &BindGroupLayout,
+    uniforms: BindingResource,
+    images: &[&GpuImage],
+) -> BindGroup {
+    let mut entries = vec![BindGroupEntry {
+        binding: 0,
+        resource: uniforms,
+    }];
+
+    for (index, image) in (0..).zip(images) {
+        entries.push(BindGroupEntry {
+            binding: 1 + 2 * index,
+            resource: BindingResource::TextureView(&image.texture_view),
+        });
+        entries.push(BindGroupEntry {
+            binding: 2 + 2 * index,
+            resource: BindingResource::Sampler(&image.sampler),
+        });
+    }
+
+    render_device.create_bind_group(&BindGroupDescriptor {
+        label: Some(label),
+        layout,
+        entries: &entries,
+    })
+}
+
 pub(crate) fn create_layout(
     world: &mut World,
     label: &str,
@@ -175,17 +756,64 @@ pub(crate) fn render_pipeline_descriptor(
             shader,
             shader_defs,
             entry_point: "fragment".into(),
-
-            #[cfg(feature = "sdr")]
-            targets: vec![Some(TextureFormat::bevy_default().into())],
-
-            #[cfg(feature = "hdr")]
-            targets: vec![Some(ViewTarget::TEXTURE_FORMAT_HDR.into())],
+            targets: vec![Some(default_target_format().into())],
         }),
         push_constant_ranges: vec![],
     }
 }
 
+/// The texture format effect pipelines are made for.
+///
+/// Cameras whose main texture has another format get variants of the pipelines,
+/// see [`formats::PipelineFormats`]. The `hdr` and `sdr` features pick which is made first.
+pub(crate) fn default_target_format() -> TextureFormat {
+    if cfg!(feature = "hdr") {
+        ViewTarget::TEXTURE_FORMAT_HDR
+    } else {
+        TextureFormat::bevy_default()
+    }
+}
+
+/// The same pipeline, drawing into a texture of the given format.
+pub(crate) fn with_target_format(
+    mut descriptor: RenderPipelineDescriptor,
+    format: TextureFormat,
+) -> RenderPipelineDescriptor {
+    if let Some(fragment) = descriptor.fragment.as_mut() {
+        for target in fragment.targets.iter_mut().flatten() {
+            target.format = format;
+        }
+    }
+
+    descriptor
+}
+
+/// The state of a render pipeline.
+///
+/// `None` if the pipeline was queued after the pipeline cache last processed its queue,
+/// e.g. when it was specialized earlier in the same frame.
+/// Unlike [`PipelineCache::get_render_pipeline_state`], this doesn't panic for such pipelines.
+pub(crate) fn pipeline_state(
+    pipeline_cache: &PipelineCache,
+    id: CachedRenderPipelineId,
+) -> Option<&CachedPipelineState> {
+    pipeline_cache
+        .pipelines()
+        .nth(id.id())
+        .map(|pipeline| &pipeline.state)
+}
+
+/// The render pipeline, if it has finished compiling. See [`pipeline_state`].
+pub(crate) fn ready_pipeline(
+    pipeline_cache: &PipelineCache,
+    id: CachedRenderPipelineId,
+) -> Option<&RenderPipeline> {
+    match pipeline_state(pipeline_cache, id)? {
+        CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline)) => Some(pipeline),
+        _ => None,
+    }
+}
+
 pub(crate) fn create_pipeline(
     world: &mut World,
     label: &str,
@@ -197,13 +825,16 @@ pub(crate) fn create_pipeline(
 
     let pipeline_cache = world.resource::<PipelineCache>();
 
-    pipeline_cache.queue_render_pipeline(render_pipeline_descriptor(
+    let pipeline_id = pipeline_cache.queue_render_pipeline(render_pipeline_descriptor(
         label,
         shared_layout,
         uniform_layout,
         shader,
         shader_definitions,
-    ))
+    ));
+
+    precompile::register_pipeline(world, pipeline_id);
+    pipeline_id
 }
 
 pub(crate) fn create_layout_and_pipeline(
@@ -218,14 +849,38 @@ pub(crate) fn create_layout_and_pipeline(
     (uniform_layout, pipeline_id)
 }
 
+/// Identifies a shared bind group.
+///
+/// The source texture alternates between effects, the prepass textures and the eye
+/// differ between views, and the sampler between effects, so all are needed to find the right bind group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct SharedBindGroupKey {
+    source: TextureViewId,
+    depth: TextureViewId,
+    normal: TextureViewId,
+    eye: Option<stereo::StereoEye>,
+    sampler: sampler::EffectSampler,
+}
+
 /// Bind groups.
 #[derive(Resource, Default, Debug)]
-struct PostProcessingSharedBindGroups {
-    cached_texture_bind_groups: HashMap<TextureViewId, BindGroup>,
-    current_source_texture: Mutex<Option<TextureViewId>>,
+pub(crate) struct PostProcessingSharedBindGroups {
+    cached_texture_bind_groups: HashMap<SharedBindGroupKey, BindGroup>,
+    current_key: Mutex<Option<SharedBindGroupKey>>,
 }
 
-/// Render command which sets the shared bind group containing the source texture and sampler as well as the globals.
+/// The prepass texture views a post processing camera binds in the shared bind group.
+///
+/// These are the prepass textures if the camera has them (and they are not multisampled),
+/// else fallback textures.
+#[derive(Debug, Component, Clone, Copy)]
+struct PostProcessingPrepassViews {
+    depth: TextureViewId,
+    normal: TextureViewId,
+}
+
+/// Render command which sets the shared bind group containing the source texture and sampler,
+/// the globals, the prepass textures, and the eye.
 struct SetTextureSamplerGlobals<const I: usize>;
 
 impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetTextureSamplerGlobals<I> {
@@ -241,21 +896,21 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetTextureSamplerGlobals
         bind_groups: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let id = {
+        let key = {
             let lock = bind_groups
-                .current_source_texture
+                .current_key
                 .try_lock()
                 .expect("Mutex should be available");
-            *lock.as_ref().expect("Source view id should be set")
+            *lock.as_ref().expect("Bind group key should be set")
         };
 
         let bind_groups = bind_groups.into_inner();
 
-        if let Some(bind_group) = bind_groups.cached_texture_bind_groups.get(&id) {
+        if let Some(bind_group) = bind_groups.cached_texture_bind_groups.get(&key) {
             pass.set_bind_group(I, bind_group, &[]);
             RenderCommandResult::Success
         } else {
-            info!("No bind group for texture view id: {id:?} on {bind_groups:?}");
+            info!("No bind group for key: {key:?} on {bind_groups:?}");
             RenderCommandResult::Failure
         }
     }
@@ -286,24 +941,86 @@ impl<P: PhaseItem> RenderCommand<P> for DrawPostProcessing {
 #[derive(Debug, Component)]
 struct PostProcessingCamera;
 
-#[allow(clippy::type_complexity)]
+/// The depth texture view effects bind: the depth prepass of the view, or a fallback.
+///
+/// The shared layout expects a single sampled depth texture.
+/// Multisampled depth prepasses can't be bound there, so those views get the fallback.
+pub(crate) fn prepass_depth_view(
+    prepass: Option<&ViewPrepassTextures>,
+    fallback_depth: &mut FallbackImagesDepth,
+) -> TextureView {
+    match prepass.and_then(|prepass| prepass.depth.as_ref()) {
+        Some(depth) if depth.texture.sample_count() == 1 => depth.default_view.clone(),
+        Some(_) => {
+            debug!("Multisampled depth prepass can't be used by post processing, use `Msaa::Off`");
+            fallback_depth.image_for_samplecount(1).texture_view.clone()
+        }
+        None => fallback_depth.image_for_samplecount(1).texture_view.clone(),
+    }
+}
+
 fn queue_post_processing_shared_bind_groups(
+    mut commands: Commands,
     render_device: Res<RenderDevice>,
-    globals: Res<GlobalsBuffer>,
+    globals: Res<VfxGlobalsBuffer>,
+    eye_buffers: Res<stereo::VfxEyeBuffers>,
     layout: Res<PostProcessingSharedLayout>,
     mut bind_groups: ResMut<PostProcessingSharedBindGroups>,
+    mut samplers: ResMut<sampler::EffectSamplers>,
+    mut fallback_depth: FallbackImagesDepth,
+    fallback_image: Res<FallbackImage>,
 
-    views: Query<(Entity, &ViewTarget), With<PostProcessingCamera>>,
+    views: Query<
+        (
+            Entity,
+            &ViewTarget,
+            &RenderPhase<PostProcessingPhaseItem>,
+            Option<&ViewPrepassTextures>,
+            Option<&stereo::StereoEye>,
+        ),
+        With<PostProcessingCamera>,
+    >,
 ) {
-    for (_, view_target) in &views {
-        for texture_view in [
+    for (entity, view_target, phase, maybe_prepass, eye) in &views {
+        let eye = eye.copied();
+
+        let depth_view = prepass_depth_view(maybe_prepass, &mut fallback_depth);
+
+        let normal_view = match maybe_prepass.and_then(|prepass| prepass.normal.as_ref()) {
+            Some(normal) if normal.texture.sample_count() == 1 => normal.default_view.clone(),
+            _ => fallback_image.d2.texture_view.clone(),
+        };
+
+        commands.entity(entity).insert(PostProcessingPrepassViews {
+            depth: depth_view.id(),
+            normal: normal_view.id(),
+        });
+
+        // A bind group for each sampler the effects of the view use.
+        let mut effect_samplers: Vec<sampler::EffectSampler> = vec![];
+        for item in &phase.items {
+            if !effect_samplers.contains(&item.sampler) {
+                effect_samplers.push(item.sampler);
+            }
+        }
+
+        for (texture_view, effect_sampler) in [
             view_target.main_texture_view(),
             view_target.main_texture_other_view(),
-        ] {
-            let id = &texture_view.id();
-            if !bind_groups.cached_texture_bind_groups.contains_key(id) {
+        ]
+        .into_iter()
+        .flat_map(|view| effect_samplers.iter().map(move |effect| (view, *effect)))
+        {
+            let key = SharedBindGroupKey {
+                source: texture_view.id(),
+                depth: depth_view.id(),
+                normal: normal_view.id(),
+                eye,
+                sampler: effect_sampler,
+            };
+            if !bind_groups.cached_texture_bind_groups.contains_key(&key) {
                 bind_groups.cached_texture_bind_groups.insert(
-                    *id,
+                    key,
                     render_device.create_bind_group(&BindGroupDescriptor {
                         label: Some("PostProcessing texture bind group"),
                         layout: &layout.shared_layout,
@@ -314,15 +1031,9 @@ fn queue_post_processing_shared_bind_groups(
                             },
                             BindGroupEntry {
                                 binding: 1,
-                                resource: BindingResource::Sampler(&render_device.create_sampler(
-                                    &SamplerDescriptor {
-                                        label: Some("PostProcessing texture sampler"),
-                                        mag_filter: FilterMode::Linear,
-                                        min_filter: FilterMode::Linear,
-                                        mipmap_filter: FilterMode::Linear,
-                                        ..default()
-                                    },
-                                )),
+                                resource: BindingResource::Sampler(
+                                    samplers.get(&render_device, effect_sampler),
+                                ),
                             },
                             BindGroupEntry {
                                 binding: 2,
@@ -331,6 +1042,18 @@ fn queue_post_processing_shared_bind_groups(
                                     .binding()
                                     .expect("Globals buffer should be available"),
                             },
+                            BindGroupEntry {
+                                binding: 3,
+                                resource: BindingResource::TextureView(&depth_view),
+                            },
+                            BindGroupEntry {
+                                binding: 4,
+                                resource: BindingResource::TextureView(&normal_view),
+                            },
+                            BindGroupEntry {
+                                binding: 5,
+                                resource: eye_buffers.binding(eye),
+                            },
                         ],
                     }),
                 );
@@ -343,15 +1066,21 @@ fn queue_post_processing_shared_bind_groups(
 /// Contains a draw function which is specialized for a specific material.
 /// Points to a matching pipeline- it will for example point to a specific fragment shader as well as
 /// having a bind group specialized for the material.
-struct PostProcessingPhaseItem {
+pub(crate) struct PostProcessingPhaseItem {
     entity: Entity,
-    sort_key: FloatOrd,
+    sort_key: PostProcessingSortKey,
     draw_function: DrawFunctionId,
     pipeline_id: CachedRenderPipelineId,
+    /// How the output of the effect is combined with the image it started from.
+    blend: blend::EffectBlend,
+    /// How much smaller than the camera the effect is drawn.
+    resolution: resolution::EffectResolution,
+    /// How the effect samples the image.
+    sampler: sampler::EffectSampler,
 }
 
 impl PhaseItem for PostProcessingPhaseItem {
-    type SortKey = FloatOrd;
+    type SortKey = PostProcessingSortKey;
 
     #[inline]
     fn sort_key(&self) -> Self::SortKey {
@@ -366,6 +1095,12 @@ impl PhaseItem for PostProcessingPhaseItem {
     fn entity(&self) -> Entity {
         self.entity
     }
+
+    fn sort(items: &mut [Self]) {
+        // Effects made up of several passes add one item per pass, all with the same sort key.
+        // A stable sort keeps those passes in the order they were added.
+        items.sort_by_key(|item| item.sort_key());
+    }
 }
 
 impl CachedRenderPipelinePhaseItem for PostProcessingPhaseItem {
@@ -375,7 +1110,11 @@ impl CachedRenderPipelinePhaseItem for PostProcessingPhaseItem {
 }
 
 /// The bind group layout common to post processing effects.
-/// This includes the texture and sampler bind group entries and the globals uniform.
+/// This includes the texture and sampler bind group entries, the globals uniform,
+/// the depth and normal prepass textures, and the eye uniform (see [`stereo::StereoEye`]).
+///
+/// The prepass textures are only meaningful if the camera has a [`bevy::core_pipeline::prepass::DepthPrepass`]
+/// or [`bevy::core_pipeline::prepass::NormalPrepass`] respectively, and MSAA is off.
 #[derive(Debug, Resource, Clone)]
 struct PostProcessingSharedLayout {
     pub(crate) shared_layout: BindGroupLayout,
@@ -412,7 +1151,37 @@ impl FromWorld for PostProcessingSharedLayout {
                     ty: BindingType::Buffer {
                         ty: BufferBindingType::Uniform,
                         has_dynamic_offset: false,
-                        min_binding_size: Some(GlobalsUniform::min_size()),
+                        min_binding_size: Some(VfxGlobalsUniform::min_size()),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Depth,
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(stereo::VfxEyeUniform::min_size()),
                     },
                     count: None,
                 },
@@ -429,58 +1198,132 @@ impl FromWorld for PostProcessingSharedLayout {
 /// has the given component `C` in the render world.
 ///
 /// A `Order<C>` component can be added to the camera to control the ordering of the effect.
-/// Else a default is inserted.
+/// Else the [`Order::implicit`] of the effect, or a default, is inserted. The same goes for a
+/// [`blend::Blend<C>`], which also carries the [`intensity::Intensity<C>`],
+/// [`intensity::PostProcessingSettings`] and [`group::EffectGroup<C>`] of the camera, for a
+/// [`resolution::Resolution<C>`], which falls back to the one of the [`quality::QualitySettings`],
+/// and for a [`sampler::SourceSampler<C>`].
+/// Cameras where the effect is disabled get no order, so the effect is skipped.
 ///
 /// A `PostProcessingCamera` component is added in order to identify cameras that have any effect applied.
-#[allow(clippy::type_complexity)]
+/// Each camera gets its own phase, so cameras drawing into the same target, e.g. split-screen
+/// players, have independent effects. See [`viewport`] for how they stay in their viewports.
 pub(crate) fn extract_post_processing_camera_phases<C: Component>(
     mut commands: Commands,
-    cameras: Extract<Query<(Entity, &Camera, Option<&Order<C>>), With<C>>>,
+    bypass: Extract<Res<bypass::EffectBypass>>,
+    budget: Extract<Option<Res<budget::EffectBudget>>>,
+    external: Extract<Res<external::ExternalEffects>>,
+    groups: Extract<Res<group::EffectGroups>>,
+    quality: Extract<Res<quality::QualitySettings>>,
+    cameras: Extract<
+        Query<(
+            Entity,
+            &Camera,
+            Option<&Order<C>>,
+            Option<&blend::Blend<C>>,
+            Option<&intensity::Intensity<C>>,
+            Option<&intensity::PostProcessingSettings>,
+            Option<&resolution::Resolution<C>>,
+            Option<&sampler::SourceSampler<C>>,
+            Option<&group::EffectGroup<C>>,
+            Option<&group::EffectGroups>,
+            Has<overlay::ApplyAfterCompositing<C>>,
+            &C,
+        )>,
+    >,
 ) {
-    for (entity, camera, maybe_ordering) in &cameras {
+    // Effects of other crates declare their category and cost when they are registered.
+    let category = bypass::EffectCategory::of::<C>().or_else(|| external.category::<C>());
+
+    // Without an order, the effect's prepare doesn't add it to the phase.
+    if bypass.bypasses_category(category) {
+        return;
+    }
+
+    if let Some(budget) = budget.as_ref() {
+        if cameras.iter().any(|(_, camera, ..)| camera.is_active) {
+            budget.track::<C>(budget::EffectCost::of::<C>().or_else(|| external.cost::<C>()));
+        }
+
+        if budget.is_shed::<C>() {
+            return;
+        }
+    }
+
+    for (
+        entity,
+        camera,
+        maybe_ordering,
+        maybe_blend,
+        maybe_intensity,
+        maybe_settings,
+        maybe_resolution,
+        maybe_sampler,
+        maybe_group,
+        maybe_camera_groups,
+        after_compositing,
+        effect,
+    ) in &cameras
+    {
         if camera.is_active {
-            let ordering = if let Some(o) = maybe_ordering {
+            let group_factor = group::group_factor(maybe_group, &groups, maybe_camera_groups);
+            let Some(blend) = intensity::effective_blend(
+                maybe_blend,
+                maybe_intensity,
+                maybe_settings,
+                group_factor,
+            ) else {
+                continue;
+            };
+
+            let mut ordering = if let Some(o) = maybe_ordering {
                 o.clone()
             } else {
-                Order::new(0.0)
+                Order::implicit(effect).unwrap_or_default()
             };
+            ordering.after_compositing = after_compositing;
 
             commands.get_or_spawn(entity).insert((
                 RenderPhase::<PostProcessingPhaseItem>::default(),
                 ordering,
+                blend,
+                maybe_resolution
+                    .copied()
+                    .unwrap_or_else(|| quality.resolution::<C>()),
+                maybe_sampler.copied().unwrap_or_default(),
                 PostProcessingCamera,
             ));
         }
     }
 }
 
-/// The post processing node.
+/// The post processing node, which draws the effects of one pass.
+///
+/// There is a node for each [`EffectPass`], in each of the 2d and 3d graphs.
 struct PostProcessingNode {
+    pass: EffectPass,
     query: QueryState<
         (
             &'static ExtractedCamera,
             &'static ViewTarget,
             &'static RenderPhase<PostProcessingPhaseItem>,
+            Option<&'static PostProcessingPrepassViews>,
+            Option<&'static stereo::StereoEye>,
         ),
         With<ExtractedView>,
     >,
 }
 
 impl PostProcessingNode {
-    /// Create a a new post processing node.
-    pub fn new(world: &mut World) -> Self {
+    /// Create a a new post processing node for the effects of the given pass.
+    pub fn new(world: &mut World, pass: EffectPass) -> Self {
         Self {
+            pass,
             query: QueryState::new(world),
         }
     }
 }
 
-impl FromWorld for PostProcessingNode {
-    fn from_world(world: &mut World) -> Self {
-        Self::new(world)
-    }
-}
-
 impl Node for PostProcessingNode {
     fn update(&mut self, world: &mut World) {
         self.query.update_archetypes(world);
@@ -495,50 +1338,341 @@ impl Node for PostProcessingNode {
         let shared_bind_groups = world.resource::<PostProcessingSharedBindGroups>();
         let view_entity = graph.view_entity();
 
-        let (camera, view_target, phase) = match self.query.get_manual(world, view_entity) {
-            Ok(result) => result,
-            Err(_) => return Ok(()),
-        };
+        let (camera, view_target, phase, prepass_views, eye) =
+            match self.query.get_manual(world, view_entity) {
+                Ok((camera, view_target, phase, Some(prepass_views), eye)) => {
+                    (camera, view_target, phase, prepass_views, eye.copied())
+                }
+                _ => return Ok(()),
+            };
 
         let draw_functions = world.resource::<DrawFunctions<PostProcessingPhaseItem>>();
         let mut draw_functions = draw_functions.write();
         draw_functions.prepare(world);
 
-        for (_index, item) in phase.items.iter().enumerate() {
-            let post_process = view_target.post_process_write();
-            let source = post_process.source;
-            let destination = post_process.destination;
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let formats = world.resource::<formats::PipelineFormats>();
+        let format = view_target.main_texture_format();
+
+        // Skip effects whose pipeline isn't available (still compiling, or failed to compile).
+        // Drawing them would leave the destination texture empty, so the camera shows garbage.
+        // The pipeline drawn is the variant for the format of the camera, which may take longer.
+        let ready = |item: &PostProcessingPhaseItem| {
+            formats
+                .pipeline(item.pipeline_id, format)
+                .filter(|id| ready_pipeline(pipeline_cache, *id).is_some())
+        };
+
+        // The items of this pass, along with the pipelines they are drawn with.
+        let items: Vec<(&PostProcessingPhaseItem, CachedRenderPipelineId)> = phase
+            .items
+            .iter()
+            .filter(|item| item.sort_key.pass == self.pass)
+            .filter_map(|item| Some((item, ready(item)?)))
+            .collect();
+
+        // Effects only draw inside the viewport of the camera, so what is outside of it
+        // must be the same in both main textures.
+        if let Some(copy) = world
+            .get::<viewport::ViewportCopy>(view_entity)
+            .filter(|_| !items.is_empty())
+        {
+            copy.copy(
+                render_context,
+                pipeline_cache,
+                world.resource::<BlitPipeline>(),
+                view_target,
+            );
+        }
+
+        if let Some(capture) = world
+            .get::<InputCapture>(view_entity)
+            .filter(|capture| capture.pass == self.pass)
+        {
+            copy_main_texture(render_context, view_target, &capture.texture);
+        }
+
+        // The input, followed by the output of each effect of all passes.
+        // The effects of earlier passes come first.
+        let tiles = phase
+            .items
+            .iter()
+            .filter(|item| ready(item).is_some())
+            .count()
+            + 1;
+        let first_tile = phase
+            .items
+            .iter()
+            .filter(|item| item.sort_key.pass < self.pass && ready(item).is_some())
+            .count()
+            + 1;
+
+        if self.pass == EffectPass::Stage(EffectStage::BeforeTonemapping) {
+            debug_view::draw_tile(
+                world,
+                render_context,
+                view_entity,
+                view_target.main_texture_view(),
+                "Input",
+                0,
+                tiles,
+            );
+        }
+
+        let view_blends = world.get::<blend::ViewBlends>(view_entity);
+        let view_resolutions = world.get::<resolution::ViewResolutions>(view_entity);
+        let view_compute = world.get::<compute::ViewCompute>(view_entity);
+
+        #[cfg(feature = "diagnostics")]
+        let timestamps = world.get_resource::<diagnostics::EffectTimestamps>();
+        // The timestamp before the first pass of the effect being drawn.
+        #[cfg(feature = "diagnostics")]
+        let mut timestamp = None;
+
+        for (index, (item, pipeline_id)) in items.iter().copied().enumerate() {
+            // Named after the effect, so profilers and GPU debuggers can tell the effects apart.
+            let descriptor = pipeline_cache.get_render_pipeline_descriptor(item.pipeline_id);
+            let effect = descriptor.label.as_deref().unwrap_or("PostProcessing");
+            let in_place = composition::draws_in_place(descriptor);
+            let _span = info_span!("vfx::render", effect).entered();
+
+            // The effect reading the history gets the current image if there is no previous frame yet.
+            let frame_history = world
+                .get::<history::FrameHistory>(view_entity)
+                .filter(|history| history.pipeline_id == item.pipeline_id);
+            if let Some(frame_history) = frame_history.filter(|history| history.fresh) {
+                copy_main_texture(render_context, view_target, &frame_history.texture);
+            }
+
+            if let Some(capture) = world
+                .get::<ImageCapture>(view_entity)
+                .filter(|capture| capture.pipeline_id == item.pipeline_id)
+            {
+                copy_main_texture(render_context, view_target, &capture.texture);
+            }
+
+            // The passes of an effect share its draw function, and the effect is blended as a whole.
+            let blends = view_blends.filter(|blends| blends.blends(item.draw_function));
+            let first_pass = index == 0 || items[index - 1].0.draw_function != item.draw_function;
+            let last_pass = !items
+                .get(index + 1)
+                .is_some_and(|(next, _)| next.draw_function == item.draw_function);
+
+            #[cfg(feature = "diagnostics")]
+            if let Some(timestamps) = timestamps.filter(|_| first_pass) {
+                timestamp = timestamps.begin(render_context, effect);
+            }
+
+            if let Some(blends) = blends.filter(|_| first_pass) {
+                copy_main_texture(render_context, view_target, &blends.capture);
+            }
+
+            // The effect reads the input from the main texture, which is kept as is during its pass.
+            let input = view_target.main_texture().clone();
+
+            // Effects composed in place draw onto the main texture as it is, and can't read it
+            // while they draw into it, so they get the other one as their source.
+            let (source, destination, ops) = if in_place {
+                (
+                    view_target.main_texture_other_view(),
+                    view_target.main_texture_view(),
+                    Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                )
+            } else {
+                let post_process = view_target.post_process_write();
+                (
+                    post_process.source,
+                    post_process.destination,
+                    viewport::operations(camera),
+                )
+            };
 
+            let key = SharedBindGroupKey {
+                source: source.id(),
+                depth: prepass_views.depth,
+                normal: prepass_views.normal,
+                eye,
+                sampler: item.sampler,
+            };
             shared_bind_groups
-                .current_source_texture
+                .current_key
                 .lock()
                 .expect("Mutex should be unused")
-                .replace(source.id());
-
-            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
-                label: Some("PostProcessing pass"),
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: destination,
-                    resolve_target: None,
-                    ops: Operations::default(),
-                })],
-                depth_stencil_attachment: None,
-            });
-
-            if let Some(viewport) = camera.viewport.as_ref() {
-                render_pass.set_camera_viewport(viewport);
+                .replace(key);
+
+            // Effects drawn at a lower resolution draw into a smaller texture,
+            // which is then scaled up into the destination.
+            let scaled = view_resolutions
+                .filter(|_| !in_place)
+                .and_then(|resolutions| resolutions.target(item.resolution, pipeline_cache));
+
+            // Kernel-heavy effects run as compute shaders where supported, at full resolution,
+            // with a sampler of their own.
+            let dispatch = view_compute
+                .filter(|_| !in_place && scaled.is_none() && item.sampler.is_default())
+                .and_then(|compute| compute.dispatch(world, view_entity, item.pipeline_id, source));
+
+            if let Some(dispatch) = dispatch {
+                dispatch.run(render_context, camera, destination);
+            } else {
+                let mut render_pass =
+                    render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                        label: Some(effect),
+                        color_attachments: &[Some(RenderPassColorAttachment {
+                            view: scaled.map_or(destination, |scaled| scaled.view()),
+                            resolve_target: None,
+                            ops,
+                        })],
+                        depth_stencil_attachment: None,
+                    });
+
+                viewport::clip(
+                    &mut render_pass,
+                    camera,
+                    scaled.map_or(1, |scaled| scaled.divisor()),
+                );
+
+                let item = PostProcessingPhaseItem {
+                    pipeline_id,
+                    ..*item
+                };
+                draw_functions
+                    .get_mut(item.draw_function)
+                    .expect("Draw function should exist")
+                    .draw(world, &mut render_pass, view_entity, &item);
+                drop(render_pass);
+
+                if let Some(scaled) = scaled {
+                    scaled.upsample(
+                        render_context,
+                        pipeline_cache,
+                        shared_bind_groups,
+                        key,
+                        camera,
+                        destination,
+                    );
+                }
+            }
+
+            // Keep the input of this effect for the next frame, before blending reuses its texture.
+            if let Some(frame_history) = frame_history.filter(|history| history.input) {
+                copy_texture(render_context, &input, &frame_history.texture);
             }
 
-            draw_functions
-                .get_mut(item.draw_function)
-                .expect("Draw function should exist")
-                .draw(world, &mut render_pass, view_entity, item);
+            if let Some(blends) = blends.filter(|_| last_pass) {
+                blends.apply(
+                    render_context,
+                    pipeline_cache,
+                    shared_bind_groups,
+                    key,
+                    camera,
+                    view_target,
+                    item.draw_function,
+                );
+            }
+
+            // Keep the image as of this effect for the next frame.
+            if let Some(frame_history) = frame_history.filter(|history| !history.input) {
+                copy_main_texture(render_context, view_target, &frame_history.texture);
+            }
+
+            #[cfg(feature = "diagnostics")]
+            if let Some(timestamps) = timestamps.filter(|_| last_pass) {
+                timestamps.end(render_context, timestamp.take());
+            }
+
+            debug_view::draw_tile(
+                world,
+                render_context,
+                view_entity,
+                view_target.main_texture_view(),
+                effect.strip_suffix(" Render Pipeline").unwrap_or(effect),
+                first_tile + index,
+                tiles,
+            );
+        }
+
+        if self.pass == EffectPass::AfterCompositing {
+            debug_view::draw_overlay(world, render_context, view_entity, view_target);
         }
 
         Ok(())
     }
 }
 
+/// Copies the current main texture of the view into the given texture, which must be the same size.
+fn copy_main_texture(render_context: &mut RenderContext, view_target: &ViewTarget, to: &Texture) {
+    copy_texture(render_context, view_target.main_texture(), to);
+}
+
+fn copy_texture(render_context: &mut RenderContext, from: &Texture, to: &Texture) {
+    render_context.command_encoder().copy_texture_to_texture(
+        from.as_image_copy(),
+        to.as_image_copy(),
+        from.size(),
+    );
+}
+
+/// Where in the frame an effect runs, relative to Bevy's tonemapping.
+///
+/// Before tonemapping, the image is scene-referred: on cameras with [`Camera::hdr`],
+/// colors can go far above one, so e.g. a glow can tell the sun apart from a white wall.
+/// After tonemapping, the image is display-referred and stays between zero and one,
+/// which is what e.g. grain, masks and color grading expect.
+///
+/// Effects before tonemapping also run before Bevy's bloom, so e.g. fog is bloomed along with the scene.
+///
+/// Cameras without HDR are tonemapped during the main pass,
+/// so effects in both stages see the display-referred image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectStage {
+    /// Runs on the scene-referred image, before tonemapping.
+    BeforeTonemapping,
+
+    /// Runs on the display-referred image, after tonemapping.
+    #[default]
+    AfterTonemapping,
+}
+
+impl std::fmt::Display for EffectStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EffectStage::BeforeTonemapping => write!(f, "before tonemapping"),
+            EffectStage::AfterTonemapping => write!(f, "after tonemapping"),
+        }
+    }
+}
+
+/// Which of the post processing nodes draws an effect, in the order they run.
+///
+/// Effects with an [`overlay::ApplyAfterCompositing`] run after all the stages,
+/// once the image of an overlay camera is composited over the cameras below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum EffectPass {
+    Stage(EffectStage),
+    AfterCompositing,
+}
+
+/// Sorts the effects of a camera by their pass first, and then by their order within it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct PostProcessingSortKey {
+    pass: EffectPass,
+    order: FloatOrd,
+}
+
+impl<C> From<Order<C>> for PostProcessingSortKey {
+    fn from(ordering: Order<C>) -> Self {
+        Self {
+            pass: ordering.pass(),
+            order: ordering.into(),
+        }
+    }
+}
+
 /// Decide on ordering for post processing effects.
 /// Lower numbers means run earlier.
 ///
@@ -546,10 +1680,19 @@ impl Node for PostProcessingNode {
 /// effect to change the order of.
 /// It's easier to use this via the [`PostProcessingOrder`] trait
 /// which adds a helper method for using this.
-#[derive(Debug, Component, Copy)]
+///
+/// Effects are first ordered by their [`EffectStage`], so the order only matters within a stage.
+#[derive(Debug, Component, Reflect, Copy)]
+#[reflect(Component, Default)]
 pub struct Order<C> {
     /// Priority
     pub order: f32,
+    /// Whether the effect runs before or after tonemapping.
+    pub stage: EffectStage,
+    /// Set on the extracted order of effects with an [`overlay::ApplyAfterCompositing`].
+    #[reflect(ignore)]
+    after_compositing: bool,
+    #[reflect(ignore)]
     marker: PhantomData<C>,
 }
 
@@ -563,6 +1706,8 @@ impl<C> Clone for Order<C> {
     fn clone(&self) -> Self {
         Self {
             order: self.order,
+            stage: self.stage,
+            after_compositing: self.after_compositing,
             marker: self.marker,
         }
     }
@@ -573,9 +1718,88 @@ impl<C> Order<C> {
     pub fn new(priority: f32) -> Self {
         Self {
             order: priority,
+            stage: EffectStage::default(),
+            after_compositing: false,
             marker: PhantomData,
         }
     }
+
+    /// Runs the effect in the given stage.
+    pub fn with_stage(mut self, stage: EffectStage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    /// The node drawing the effect.
+    pub(crate) fn pass(&self) -> EffectPass {
+        if self.after_compositing {
+            EffectPass::AfterCompositing
+        } else {
+            EffectPass::Stage(self.stage)
+        }
+    }
+}
+
+impl<C> Default for Order<C> {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl<C: Component> Order<C> {
+    /// The order of an effect without an [`Order`] of its own, for the effects which don't fit in
+    /// at the default `0.0`.
+    ///
+    /// Both the drawn order and the checks of a [`stack::PostProcessingStack`] go by this.
+    pub(crate) fn implicit(effect: &C) -> Option<Self> {
+        let effect: &dyn Any = effect;
+
+        if effect.is::<auto_exposure::AutoExposure>() {
+            // The exposure is applied before all the other effects, and before tonemapping,
+            // as a camera would.
+            Some(Self::new(f32::NEG_INFINITY).with_stage(EffectStage::BeforeTonemapping))
+        } else if effect.is::<stable_regions::StableRegions>() {
+            // The regions are brought back after all the other effects.
+            Some(Self::new(f32::MAX))
+        } else if effect.is::<letterbox::Letterbox>()
+            || effect.is::<screen_transition::ScreenTransition>()
+            || effect.is::<screen_door_fade::ScreenDoorFade>()
+        {
+            // These cover the screen after all the other effects, even the stable regions.
+            // How they are ordered among each other is up to their prepare systems.
+            Some(Self::new(f32::INFINITY))
+        } else if effect
+            .downcast_ref::<deband::Deband>()
+            .is_some_and(|deband| deband.final_pass)
+        {
+            // A final pass is drawn after all the other effects, since anything after it can band
+            // again.
+            Some(Self::new(f32::INFINITY))
+        } else {
+            None
+        }
+    }
+}
+
+/// Registers the per-camera components which are generic over the effect `C`,
+/// so they can be inspected and used in scenes alongside the effect itself.
+///
+/// Also registers the [`ParameterRange`] of the effect for tools, and checks its parameters
+/// against it, see [`metadata::ParameterValidation`].
+pub(crate) fn register_effect_types<C>(app: &mut App)
+where
+    C: Component + Reflect + TypePath + GetTypeRegistration + ParameterRange,
+{
+    app.register_type::<C>()
+        .register_type_data::<C, ReflectParameterRange>()
+        .register_type::<Order<C>>()
+        .register_type::<blend::Blend<C>>()
+        .register_type::<intensity::Intensity<C>>()
+        .register_type::<group::EffectGroup<C>>()
+        .register_type::<resolution::Resolution<C>>()
+        .register_type::<sampler::SourceSampler<C>>()
+        .register_type::<overlay::ApplyAfterCompositing<C>>()
+        .add_systems(PostUpdate, metadata::validate_parameters::<C>);
 }
 
 pub(crate) fn render_app(app: &mut App) -> &mut App {
@@ -587,20 +1811,73 @@ pub(crate) fn render_app(app: &mut App) -> &mut App {
 pub(crate) struct PostProcessingPlugin;
 
 impl Plugin for PostProcessingPlugin {
-    fn build(&self, _app: &mut App) {
-    }
+    fn build(&self, _app: &mut App) {}
 
     fn finish(&self, app: &mut App) {
-
         let render_app = app
             .get_sub_app_mut(RenderApp)
             .expect("Need a render app for post processing");
 
-        // All effects share this node.
-        crate::util::add_nodes::<PostProcessingNode>(
+        // All effects share these nodes, one for each pass.
+        add_node(
+            render_app,
+            core_3d::graph::NAME,
+            graph::node::BEFORE_TONEMAPPING,
+            EffectPass::Stage(EffectStage::BeforeTonemapping),
+            &[core_3d::graph::node::END_MAIN_PASS],
+            &[
+                core_3d::graph::node::BLOOM,
+                core_3d::graph::node::TONEMAPPING,
+            ],
+        );
+        add_node(
+            render_app,
+            core_3d::graph::NAME,
+            graph::node::AFTER_TONEMAPPING,
+            EffectPass::Stage(EffectStage::AfterTonemapping),
+            &[
+                core_3d::graph::node::TONEMAPPING,
+                core_3d::graph::node::FXAA,
+            ],
+            &[core_3d::graph::node::END_MAIN_PASS_POST_PROCESSING],
+        );
+        add_node(
             render_app,
-            "PostProcessing2d",
-            "PostProcessing3d",
+            core_3d::graph::NAME,
+            graph::node::AFTER_COMPOSITING,
+            EffectPass::AfterCompositing,
+            &[graph::node::AFTER_TONEMAPPING],
+            &[core_3d::graph::node::END_MAIN_PASS_POST_PROCESSING],
+        );
+        add_node(
+            render_app,
+            core_2d::graph::NAME,
+            graph::node::BEFORE_TONEMAPPING,
+            EffectPass::Stage(EffectStage::BeforeTonemapping),
+            &[core_2d::graph::node::MAIN_PASS],
+            &[
+                core_2d::graph::node::BLOOM,
+                core_2d::graph::node::TONEMAPPING,
+            ],
+        );
+        add_node(
+            render_app,
+            core_2d::graph::NAME,
+            graph::node::AFTER_TONEMAPPING,
+            EffectPass::Stage(EffectStage::AfterTonemapping),
+            &[
+                core_2d::graph::node::TONEMAPPING,
+                core_2d::graph::node::FXAA,
+            ],
+            &[core_2d::graph::node::END_MAIN_PASS_POST_PROCESSING],
+        );
+        add_node(
+            render_app,
+            core_2d::graph::NAME,
+            graph::node::AFTER_COMPOSITING,
+            EffectPass::AfterCompositing,
+            &[graph::node::AFTER_TONEMAPPING],
+            &[core_2d::graph::node::END_MAIN_PASS_POST_PROCESSING],
         );
 
         render_app
@@ -617,19 +1894,148 @@ impl Plugin for PostProcessingPlugin {
                 sort_phase_system::<PostProcessingPhaseItem>.in_set(RenderSet::PhaseSort),
             );
 
+        app.register_type::<EffectStage>()
+            .register_type::<composition::Composition>();
+
+        app.add_plugins((
+            custom::Plugin,
+            compute::Plugin,
+            external::Plugin,
+            effect_mask::Plugin,
+            process_image::Plugin,
+            formats::Plugin,
+            history::Plugin,
+            bypass::Plugin,
+            budget::Plugin,
+            stereo::Plugin,
+            view_model::Plugin,
+            viewport::Plugin,
+            blend::Plugin,
+            intensity::Plugin,
+            resolution::Plugin,
+        ))
+        .add_plugins((
+            compositor::Plugin,
+            group::Plugin,
+            prepass::Plugin,
+            sampler::Plugin,
+            overlay::Plugin,
+            snapshot::Plugin,
+            quality::Plugin,
+        ));
+
+        #[cfg(feature = "diagnostics")]
+        app.add_plugins(diagnostics::Plugin);
+
+        #[cfg(feature = "selective")]
+        app.add_plugins(selective::Plugin);
+
+        #[cfg(feature = "ui")]
+        app.add_plugins(ui::Plugin);
+
+        // Plugin tuples are limited in size, so the effects are added in groups.
         app.add_plugins((
+            ascii::Plugin,
             blur::Plugin,
             chromatic_aberration::Plugin,
+            color_adjust::Plugin,
+            depth_of_field::Plugin,
+            dither::Plugin,
             flip::Plugin,
+            fog::Plugin,
+            ghosting::Plugin,
+            glow::Plugin,
+            halftone::Plugin,
+            heat_haze::Plugin,
+            kuwahara::Plugin,
+            lens_distortion::Plugin,
+            lens_flare::Plugin,
+        ))
+        .add_plugins((
+            light_shafts::Plugin,
             lut::Plugin,
+            magnifier::Plugin,
             masks::Plugin,
+            motion_blur::Plugin,
+            outline::Plugin,
+            palette_swap::Plugin,
             raindrops::Plugin,
             pixelate::Plugin,
+            sharpen::Plugin,
+            shockwave::Plugin,
+            stable_regions::Plugin,
+            underwater::Plugin,
+            vision_mode::Plugin,
             wave::Plugin,
+        ))
+        .add_plugins((
+            bulge::Plugin,
+            dead_pixels::Plugin,
+            depth_grade::Plugin,
+            edge_fringe::Plugin,
+            false_color::Plugin,
+            film_projector::Plugin,
+            hologram::Plugin,
+            light_leak::Plugin,
+            line_jitter::Plugin,
+            psx_wobble::Plugin,
+            retro_fog::Plugin,
+            rolling_shutter::Plugin,
+            weather_overlay::Plugin,
+            wet_screen_sheen::Plugin,
+            white_balance::Plugin,
+        ))
+        .add_plugins((
+            auto_exposure::Plugin,
+            color_blindness::Plugin,
+            day_for_night::Plugin,
+            deband::Plugin,
+            fog_2d::Plugin,
+            frosted_glass::Plugin,
+            god_rays_2d::Plugin,
+            letterbox::Plugin,
+            old_film::Plugin,
+            screen_door_fade::Plugin,
+            screen_transition::Plugin,
+            sketch::Plugin,
+            speed_lines::Plugin,
+        ))
+        // This uses the render data of the effects, so it goes last.
+        .add_plugins((
+            combined::Plugin,
+            precompile::Plugin,
+            readiness::Plugin,
+            debug_view::Plugin,
         ));
     }
 }
 
+/// Adds the post processing node of a pass to a graph, after and before the given nodes.
+fn add_node(
+    render_app: &mut App,
+    graph: &str,
+    name: &'static str,
+    pass: EffectPass,
+    after: &[&'static str],
+    before: &[&'static str],
+) {
+    let node = PostProcessingNode::new(&mut render_app.world, pass);
+    let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+    let graph = render_graph
+        .get_sub_graph_mut(graph)
+        .expect("Graph should be available");
+
+    graph.add_node(name, node);
+
+    // Nodes such as bloom and FXAA are missing when their plugins are disabled, which is fine.
+    for node in after {
+        let _ = graph.try_add_node_edge(*node, name);
+    }
+    for node in before {
+        let _ = graph.try_add_node_edge(name, *node);
+    }
+}
+
 fn extract_camera_phases(mut commands: Commands, cameras: Extract<Query<(Entity, &Camera)>>) {
     for (entity, camera) in &cameras {
         if camera.is_active {
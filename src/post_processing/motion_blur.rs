@@ -0,0 +1,312 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::prepass::{MotionVectorPrepass, ViewPrepassTextures},
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages,
+            ShaderType, TextureSampleType, TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        texture::FallbackImageZero,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    history::ViewHistory,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const MOTION_BLUR_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 15158024443648775087);
+
+#[derive(Resource)]
+pub(crate) struct MotionBlurData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for MotionBlurData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Motion Blur",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(MotionBlurUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The motion vector prepass, or a fallback if the camera has none.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+            MOTION_BLUR_SHADER_HANDLE.typed(),
+        );
+
+        MotionBlurData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            MOTION_BLUR_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "motion_blur.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<MotionBlur>();
+        super::register_effect_types::<MotionBlur>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<MotionBlur>::default(),
+            UniformComponentPlugin::<MotionBlurUniform>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            super::prepass::configure_prepasses::<MotionBlur>,
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<MotionBlur>,
+            )
+            .init_resource::<MotionBlurData>()
+            // The uniforms are written to the GPU during prepare, so the history must be in by then.
+            .add_systems(
+                Render,
+                apply_view_history
+                    .after(RenderSet::ExtractCommands)
+                    .before(RenderSet::Prepare),
+            )
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<MotionBlurUniform>>();
+    }
+}
+
+impl EffectPrepasses for MotionBlur {
+    const WITHOUT: &'static str = "only camera rotation will be blurred";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+fn apply_view_history(
+    msaa: Res<Msaa>,
+    mut views: Query<(
+        &ViewHistory,
+        &mut MotionBlurUniform,
+        Option<&MotionVectorPrepass>,
+    )>,
+) {
+    for (history, mut uniform, motion_vectors) in &mut views {
+        uniform.inverse_view_projection = history.view_projection.inverse();
+        uniform.previous_view_projection = history.previous_view_projection;
+
+        // Multisampled motion vectors can't be read by post processing effects.
+        uniform.motion_vectors = (motion_vectors.is_some() && *msaa == Msaa::Off).into();
+    }
+}
+
+fn prepare(
+    data: Res<MotionBlurData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<MotionBlur>,
+        &Blend<MotionBlur>,
+        &Resolution<MotionBlur>,
+        &SourceSampler<MotionBlur>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::motion_blur::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<MotionBlurUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<MotionBlurData>,
+    uniforms: Res<ComponentUniforms<MotionBlurUniform>>,
+    fallback_zero: Res<FallbackImageZero>,
+    views: Query<(Entity, Option<&ViewPrepassTextures>), With<MotionBlurUniform>>,
+) {
+    let _span = info_span!("vfx::motion_blur::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, prepass) in &views {
+        let motion_vectors = match prepass.and_then(|prepass| prepass.motion_vectors.as_ref()) {
+            Some(motion_vectors) if motion_vectors.texture.sample_count() == 1 => {
+                &motion_vectors.default_view
+            }
+            _ => &fallback_zero.texture_view,
+        };
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Motion Blur Uniform Bind Group"),
+            layout: &data.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(motion_vectors),
+                },
+            ],
+        });
+
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<MotionBlurUniform>::new(bind_group));
+    }
+}
+
+/// Blurs the image along the motion of what is seen, like a camera with a slow shutter.
+///
+/// If the camera has a [`MotionVectorPrepass`] (and `Msaa::Off`),
+/// the motion of each mesh is used, so moving objects are blurred too.
+/// Otherwise the motion of the camera is reconstructed from the depth prepass
+/// and the camera's view in the previous frame, so the camera needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass). It is added along with the blur,
+/// unless [`super::prepass::AutoPrepass`] is disabled.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct MotionBlur {
+    /// For how much of each frame the shutter is open, in degrees.
+    ///
+    /// `360.0` blurs over the whole motion since the previous frame,
+    /// while the common `180.0` blurs over half of it.
+    pub shutter_angle: f32,
+
+    /// The most samples taken along the motion of a pixel.
+    /// Fast motion needs more samples to look smooth, but costs more.
+    pub max_samples: u32,
+
+    /// The longest blur, in UV space.
+    /// Keeps sudden jumps such as camera cuts from smearing the whole view.
+    pub max_blur: f32,
+}
+
+impl Default for MotionBlur {
+    fn default() -> Self {
+        Self {
+            shutter_angle: 180.0,
+            max_samples: 16,
+            max_blur: 0.05,
+        }
+    }
+}
+
+impl Display for MotionBlur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Motion Blur shutter angle: {:.0}°, max samples: {}, max blur: {:.3}",
+            self.shutter_angle, self.max_samples, self.max_blur
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`MotionBlur`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct MotionBlurUniform {
+    /// Set from the [`ViewHistory`] of the view.
+    pub(crate) inverse_view_projection: Mat4,
+    pub(crate) previous_view_projection: Mat4,
+
+    pub(crate) shutter: f32,
+    pub(crate) max_blur: f32,
+    pub(crate) max_samples: u32,
+
+    /// Non-zero if the motion vector prepass can be used.
+    pub(crate) motion_vectors: u32,
+}
+
+impl ExtractComponent for MotionBlur {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = MotionBlurUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::motion_blur::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(MotionBlurUniform {
+            // No motion until the history is applied.
+            inverse_view_projection: Mat4::IDENTITY,
+            previous_view_projection: Mat4::IDENTITY,
+            shutter: settings.shutter_angle / 360.0,
+            max_blur: settings.max_blur,
+            max_samples: settings.max_samples,
+            motion_vectors: 0,
+        })
+    }
+}
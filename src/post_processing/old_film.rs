@@ -0,0 +1,223 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const OLD_FILM_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 17071183815690246723);
+
+#[derive(Resource)]
+pub(crate) struct OldFilmData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for OldFilmData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Old Film",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(OldFilm::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            OLD_FILM_SHADER_HANDLE.typed(),
+        );
+
+        OldFilmData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OLD_FILM_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "old_film.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<OldFilm>();
+        super::register_effect_types::<OldFilm>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<OldFilm>::default(),
+            UniformComponentPlugin::<OldFilm>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<OldFilm>,
+            )
+            .init_resource::<OldFilmData>()
+            .init_resource::<UniformBindGroup<OldFilm>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<OldFilm>>();
+    }
+}
+
+fn prepare(
+    data: Res<OldFilmData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<OldFilm>,
+        &Blend<OldFilm>,
+        &Resolution<OldFilm>,
+        &SourceSampler<OldFilm>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::old_film::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<OldFilm>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<OldFilmData>,
+    mut bind_group: ResMut<UniformBindGroup<OldFilm>>,
+    uniforms: Res<ComponentUniforms<OldFilm>>,
+    views: Query<Entity, With<OldFilm>>,
+) {
+    let _span = info_span!("vfx::old_film::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Old Film Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Makes the image look like an old, worn film print: sepia toned, scratched, dusty,
+/// flickering and jittering from frame to frame.
+///
+/// All of this is drawn in a single pass, which is much cheaper than combining effects for each.
+/// Each part can be turned off by setting it to `0.0`.
+///
+/// Unlike [`super::film_projector::FilmProjector`], which is about the projector,
+/// this is about the print. The two can be combined.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct OldFilm {
+    /// How much the image is toned sepia, from `0.0` (not at all) to `1.0`.
+    pub sepia: f32,
+
+    /// How many vertical scratches run down the film, from `0.0` (none) to `1.0` (plenty).
+    pub scratches: f32,
+
+    /// How much dust and hair is on the film, from `0.0` (none) to `1.0` (plenty).
+    pub dust: f32,
+
+    /// How much the brightness changes from frame to frame, from `0.0` (not at all) to `1.0`.
+    pub flicker: f32,
+
+    /// How far the image jitters from frame to frame at most, in pixels.
+    pub jitter: f32,
+
+    /// How many frames per second the film runs at. The dust, flicker and jitter change
+    /// with each frame, so old film is best shown at a low rate.
+    pub frame_rate: f32,
+}
+
+impl Default for OldFilm {
+    fn default() -> Self {
+        Self {
+            sepia: 0.8,
+            scratches: 0.5,
+            dust: 0.5,
+            flicker: 0.15,
+            jitter: 1.5,
+            frame_rate: 18.0,
+        }
+    }
+}
+
+impl Display for OldFilm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Old Film sepia: {:.2}, scratches: {:.2}, dust: {:.2}, flicker: {:.2}, jitter: {:.1}, frame rate: {:.0}",
+            self.sepia, self.scratches, self.dust, self.flicker, self.jitter, self.frame_rate
+        )
+    }
+}
+
+impl ExtractComponent for OldFilm {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::old_film::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const OUTLINE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 15377135737298228988);
+
+#[derive(Resource)]
+pub(crate) struct OutlineData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for OutlineData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Outline",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(OutlineUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            OUTLINE_SHADER_HANDLE.typed(),
+        );
+
+        OutlineData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OUTLINE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "outline.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Outline>();
+        super::register_effect_types::<Outline>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Outline>::default(),
+            UniformComponentPlugin::<OutlineUniform>::default(),
+        ))
+        .add_systems(PostUpdate, super::prepass::configure_prepasses::<Outline>);
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Outline>,
+            )
+            .init_resource::<OutlineData>()
+            .init_resource::<UniformBindGroup<OutlineUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<OutlineUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<OutlineData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Outline>,
+        &Blend<Outline>,
+        &Resolution<Outline>,
+        &SourceSampler<Outline>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::outline::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<OutlineUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<OutlineData>,
+    mut bind_group: ResMut<UniformBindGroup<OutlineUniform>>,
+    uniforms: Res<ComponentUniforms<OutlineUniform>>,
+    views: Query<Entity, With<OutlineUniform>>,
+) {
+    let _span = info_span!("vfx::outline::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Outline Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+impl EffectPrepasses for Outline {
+    const WITHOUT: &'static str = "no outlines will be drawn";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+/// Draws outlines along edges in the scene, for toon or blueprint looks.
+///
+/// Edges are found by running a Sobel filter over the depth and normal prepass textures.
+/// Depth edges appear where objects overlap, and normal edges appear
+/// where surfaces bend sharply (e.g. the edges of a cube).
+///
+/// The camera needs a [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) component,
+/// which is added along with the outline. A [`bevy::core_pipeline::prepass::NormalPrepass`]
+/// is optional, without it only depth edges are drawn.
+/// MSAA must also be off (`Msaa::Off`),
+/// since multisampled prepass textures can't be read by post processing effects.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Outline {
+    /// The color of the outlines.
+    /// The alpha channel controls how opaque the outlines are.
+    pub color: Color,
+
+    /// How wide the outlines are, in pixels.
+    pub width: f32,
+
+    /// How large the change in distance (relative to the distance itself) must be to be considered an edge.
+    /// Lower values find more edges.
+    pub depth_threshold: f32,
+
+    /// How large the change in normal direction must be to be considered an edge.
+    /// Lower values find more edges.
+    pub normal_threshold: f32,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            width: 1.0,
+            depth_threshold: 0.1,
+            normal_threshold: 0.4,
+        }
+    }
+}
+
+impl Display for Outline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Outline width: {}, depth threshold: {}, normal threshold: {}",
+            self.width, self.depth_threshold, self.normal_threshold
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Outline`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct OutlineUniform {
+    pub(crate) color: Vec4,
+    pub(crate) width: f32,
+    pub(crate) depth_threshold: f32,
+    pub(crate) normal_threshold: f32,
+
+    /// Used to turn depth values into view space distances.
+    pub(crate) inverse_projection: Mat4,
+}
+
+impl ExtractComponent for Outline {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = OutlineUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::outline::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(OutlineUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            width: settings.width,
+            depth_threshold: settings.depth_threshold,
+            normal_threshold: settings.normal_threshold,
+            inverse_projection: camera.projection_matrix().inverse(),
+        })
+    }
+}
@@ -0,0 +1,595 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{
+        blit::{BlitPipeline, BlitPipelineKey},
+        clear_color::ClearColorConfig,
+        core_2d::{self, Camera2d},
+        core_3d::{self, Camera3d},
+        fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    },
+    ecs::query::{Has, QueryItem},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        render_graph::{NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner},
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d, FilterMode,
+            FragmentState, LoadOp, MultisampleState, Operations, PipelineCache, PrimitiveState,
+            RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline,
+            RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+            SpecializedRenderPipeline, SpecializedRenderPipelines, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureView,
+            TextureViewDimension,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+        Extract, Render, RenderSet,
+    },
+};
+
+use super::{graph::node, view_model::ViewModel, viewport, PostProcessingCamera};
+
+pub(crate) const OVERLAY_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7204416695180348917);
+
+/// Runs the effect `C` of a camera on the image composited from it and the cameras below it,
+/// instead of only on what the camera draws.
+///
+/// Cameras which draw over the image of the cameras before them into the same target,
+/// such as a view model or a HUD camera, are overlays: their [`ClearColorConfig`] is
+/// [`ClearColorConfig::None`], and a camera with a lower [`Camera::order`] draws into the
+/// target first. The effects of an overlay only apply to what it draws. The image so far is
+/// kept aside before its main pass, the overlay draws on a transparent image of its own,
+/// and after its effects that image is composited over the one kept aside.
+/// The effects of the cameras below are done by then, so they never apply to the overlay either.
+/// Overlays without effects or a [`super::view_model::ViewModel`] draw over the image as usual.
+///
+/// Effects meant for the whole image, e.g. grading, go on the topmost camera with this.
+/// Like [`super::Order`], it is per-camera and uses a generic marker to know which effect
+/// it applies to:
+///
+/// ```no_run
+/// # use bevy::{core_pipeline::clear_color::ClearColorConfig, prelude::*};
+/// # use bevy_vfx_bag::post_processing::{
+/// #     chromatic_aberration::ChromaticAberration,
+/// #     color_adjust::ColorAdjust,
+/// #     overlay::ApplyAfterCompositing,
+/// # };
+/// # let mut commands: Commands = panic!();
+/// commands.spawn(Camera3dBundle::default());
+///
+/// // Drawn over the world: the aberration only applies to the weapon,
+/// // the grading to the world and the weapon.
+/// commands.spawn((
+///     Camera3dBundle {
+///         camera: Camera {
+///             order: 1,
+///             ..default()
+///         },
+///         camera_3d: Camera3d {
+///             clear_color: ClearColorConfig::None,
+///             ..default()
+///         },
+///         ..default()
+///     },
+///     ChromaticAberration::default(),
+///     ColorAdjust::default(),
+///     ApplyAfterCompositing::<ColorAdjust>::new(),
+/// ));
+/// ```
+///
+/// The effect runs after all the effects of the camera which aren't composited first,
+/// whatever their [`super::EffectStage`], and the image is tonemapped by then.
+/// On cameras which aren't overlays there is nothing to composite,
+/// so the effect just runs after the others.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct ApplyAfterCompositing<C> {
+    #[reflect(ignore)]
+    marker: PhantomData<C>,
+}
+
+impl<C> ApplyAfterCompositing<C> {
+    /// Run the effect after compositing.
+    pub fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for ApplyAfterCompositing<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for ApplyAfterCompositing<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for ApplyAfterCompositing<C> {}
+
+impl<C> Display for ApplyAfterCompositing<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Applied after compositing")
+    }
+}
+
+/// Blends away whatever is drawn, so drawing clears the texture to transparent,
+/// but only inside the scissor rectangle, unlike clearing when the pass begins.
+const CLEAR: BlendState = BlendState {
+    color: BlendComponent {
+        src_factor: BlendFactor::Zero,
+        dst_factor: BlendFactor::Zero,
+        operation: BlendOperation::Add,
+    },
+    alpha: BlendComponent {
+        src_factor: BlendFactor::Zero,
+        dst_factor: BlendFactor::Zero,
+        operation: BlendOperation::Add,
+    },
+};
+
+/// A camera which doesn't clear the image, in the render world.
+///
+/// Whether it has a camera below it is only known once the cameras are sorted.
+#[derive(Component)]
+struct ExtractedOverlay {
+    view_model: bool,
+}
+
+/// The image the cameras below an overlay camera drew, and how it is put back, in the render world.
+#[derive(Component)]
+struct ViewOverlay {
+    backdrop: CachedTexture,
+    /// The image of the overlay before its effects, for what it covers.
+    coverage: CachedTexture,
+    clear_pipeline: CachedRenderPipelineId,
+    backdrop_pipeline: CachedRenderPipelineId,
+    over_pipeline: CachedRenderPipelineId,
+}
+
+impl ViewOverlay {
+    /// The pipelines for clearing, drawing the backdrop and compositing the overlay over it,
+    /// or `None` while any of them is compiling, in which case the overlay is drawn as usual.
+    fn pipelines<'a>(
+        &self,
+        pipeline_cache: &'a PipelineCache,
+    ) -> Option<(&'a RenderPipeline, &'a RenderPipeline, &'a RenderPipeline)> {
+        Some((
+            pipeline_cache.get_render_pipeline(self.clear_pipeline)?,
+            pipeline_cache.get_render_pipeline(self.backdrop_pipeline)?,
+            pipeline_cache.get_render_pipeline(self.over_pipeline)?,
+        ))
+    }
+}
+
+#[derive(Resource)]
+struct OverlayData {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for OverlayData {
+    fn from_world(world: &mut World) -> Self {
+        let texture = BindingType::Texture {
+            sample_type: TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        };
+
+        let layout = super::create_layout(
+            world,
+            "Overlay",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: texture,
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: texture,
+                    count: None,
+                },
+            ],
+        );
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Overlay sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        OverlayData { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for OverlayData {
+    /// The format of the camera.
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("Overlay Render Pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+            fragment: Some(FragmentState {
+                shader: OVERLAY_SHADER_HANDLE.typed(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+        }
+    }
+}
+
+fn blit_bind_group(
+    render_context: &RenderContext,
+    blit: &BlitPipeline,
+    view: &TextureView,
+) -> BindGroup {
+    render_context
+        .render_device()
+        .create_bind_group(&BindGroupDescriptor {
+            label: Some("Overlay bind group"),
+            layout: &blit.texture_bind_group,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&blit.sampler),
+                },
+            ],
+        })
+}
+
+/// Keeps the image as the cameras below drew it, and clears it inside the viewport,
+/// before the main pass of the overlay.
+#[derive(Default)]
+struct OverlayBackdropNode;
+
+impl ViewNode for OverlayBackdropNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewOverlay,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, view_target, overlay): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some((clear, ..)) = overlay.pipelines(world.resource::<PipelineCache>()) else {
+            return Ok(());
+        };
+
+        super::copy_main_texture(render_context, view_target, &overlay.backdrop.texture);
+
+        let bind_group = blit_bind_group(
+            render_context,
+            world.resource::<BlitPipeline>(),
+            &overlay.backdrop.default_view,
+        );
+
+        // With MSAA this clears the multisampled texture the main pass draws into as well.
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Overlay clear"),
+            color_attachments: &[Some(view_target.get_color_attachment(Operations {
+                load: LoadOp::Load,
+                store: true,
+            }))],
+            depth_stencil_attachment: None,
+        });
+
+        viewport::clip(&mut render_pass, camera, 1);
+        render_pass.set_render_pipeline(clear);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+/// Keeps the image of the overlay as its main pass left it, before its effects.
+#[derive(Default)]
+struct OverlayCoverageNode;
+
+impl ViewNode for OverlayCoverageNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewOverlay);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, overlay): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if overlay
+            .pipelines(world.resource::<PipelineCache>())
+            .is_some()
+        {
+            super::copy_main_texture(render_context, view_target, &overlay.coverage.texture);
+        }
+
+        Ok(())
+    }
+}
+
+/// Composites the image of the overlay over the backdrop, after the effects of the overlay.
+#[derive(Default)]
+struct OverlayCompositeNode;
+
+impl ViewNode for OverlayCompositeNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewOverlay,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, view_target, overlay): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some((_, backdrop, over)) = overlay.pipelines(world.resource::<PipelineCache>()) else {
+            return Ok(());
+        };
+
+        let data = world.resource::<OverlayData>();
+        let post_process = view_target.post_process_write();
+        let backdrop_bind_group = blit_bind_group(
+            render_context,
+            world.resource::<BlitPipeline>(),
+            &overlay.backdrop.default_view,
+        );
+        let over_bind_group =
+            render_context
+                .render_device()
+                .create_bind_group(&BindGroupDescriptor {
+                    label: Some("Overlay composite bind group"),
+                    layout: &data.layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(post_process.source),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&data.sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: BindingResource::TextureView(&overlay.coverage.default_view),
+                        },
+                    ],
+                });
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Overlay composite"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        // All of the backdrop, so what is outside the viewport stays as the cameras below left it.
+        render_pass.set_render_pipeline(backdrop);
+        render_pass.set_bind_group(0, &backdrop_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        viewport::clip(&mut render_pass, camera, 1);
+        render_pass.set_render_pipeline(over);
+        render_pass.set_bind_group(0, &over_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+fn extract(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<(
+            Entity,
+            &Camera,
+            Option<&Camera3d>,
+            Option<&Camera2d>,
+            Has<ViewModel>,
+        )>,
+    >,
+) {
+    for (entity, camera, camera_3d, camera_2d, view_model) in &cameras {
+        let clear_color = camera_3d
+            .map(|camera_3d| &camera_3d.clear_color)
+            .or(camera_2d.map(|camera_2d| &camera_2d.clear_color));
+
+        if camera.is_active && matches!(clear_color, Some(ClearColorConfig::None)) {
+            commands
+                .get_or_spawn(entity)
+                .insert(ExtractedOverlay { view_model });
+        }
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    msaa: Res<Msaa>,
+    pipeline_cache: Res<PipelineCache>,
+    blit: Res<BlitPipeline>,
+    data: Res<OverlayData>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    mut overlay_pipelines: ResMut<SpecializedRenderPipelines<OverlayData>>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ViewTarget,
+        &ExtractedOverlay,
+        Has<PostProcessingCamera>,
+    )>,
+) {
+    let _span = info_span!("vfx::overlay::queue").entered();
+
+    for (entity, camera, view_target, overlay, has_effects) in &views {
+        // The first camera of a target has nothing to draw over.
+        if camera.sorted_camera_index_for_target == 0 || !(has_effects || overlay.view_model) {
+            continue;
+        }
+
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let format = view_target.main_texture_format();
+        let descriptor = |label| TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        };
+        let backdrop = texture_cache.get(&render_device, descriptor("Overlay backdrop"));
+        let coverage = texture_cache.get(&render_device, descriptor("Overlay coverage"));
+
+        let mut specialize = |blend_state, samples| {
+            pipelines.specialize(
+                &pipeline_cache,
+                &blit,
+                BlitPipelineKey {
+                    texture_format: format,
+                    blend_state,
+                    samples,
+                },
+            )
+        };
+
+        commands.entity(entity).insert(ViewOverlay {
+            backdrop,
+            coverage,
+            clear_pipeline: specialize(Some(CLEAR), msaa.samples()),
+            backdrop_pipeline: specialize(None, 1),
+            over_pipeline: overlay_pipelines.specialize(&pipeline_cache, &data, format),
+        });
+    }
+}
+
+/// Adds the overlay nodes to a graph, around the main pass and the effects.
+fn add_nodes(
+    render_app: &mut App,
+    graph: &str,
+    msaa_writeback: &'static str,
+    start_main_pass: &'static str,
+    end_main_pass: &'static str,
+) {
+    let backdrop = ViewNodeRunner::new(OverlayBackdropNode, &mut render_app.world);
+    let coverage = ViewNodeRunner::new(OverlayCoverageNode, &mut render_app.world);
+    let composite = ViewNodeRunner::new(OverlayCompositeNode, &mut render_app.world);
+
+    let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+    let graph = render_graph
+        .get_sub_graph_mut(graph)
+        .expect("Graph should be available");
+
+    graph.add_node(node::OVERLAY_BACKDROP, backdrop);
+    graph.add_node(node::OVERLAY_COVERAGE, coverage);
+    graph.add_node(node::OVERLAY_COMPOSITE, composite);
+
+    // The MSAA writeback is missing when its plugin is disabled, which is fine.
+    let _ = graph.try_add_node_edge(msaa_writeback, node::OVERLAY_BACKDROP);
+    graph.add_node_edge(node::OVERLAY_BACKDROP, start_main_pass);
+
+    graph.add_node_edge(end_main_pass, node::OVERLAY_COVERAGE);
+    graph.add_node_edge(node::OVERLAY_COVERAGE, node::BEFORE_TONEMAPPING);
+
+    graph.add_node_edge(node::AFTER_TONEMAPPING, node::OVERLAY_COMPOSITE);
+    graph.add_node_edge(node::OVERLAY_COMPOSITE, node::AFTER_COMPOSITING);
+}
+
+/// Keeps the effects of overlay cameras to what they draw.
+///
+/// Must be added after the post processing nodes are in the graphs.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OVERLAY_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "overlay.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        let render_app = super::render_app(app);
+
+        add_nodes(
+            render_app,
+            core_3d::graph::NAME,
+            core_3d::graph::node::MSAA_WRITEBACK,
+            core_3d::graph::node::START_MAIN_PASS,
+            core_3d::graph::node::END_MAIN_PASS,
+        );
+        add_nodes(
+            render_app,
+            core_2d::graph::NAME,
+            core_2d::graph::node::MSAA_WRITEBACK,
+            core_2d::graph::node::MAIN_PASS,
+            core_2d::graph::node::MAIN_PASS,
+        );
+
+        render_app
+            .init_resource::<OverlayData>()
+            .init_resource::<SpecializedRenderPipelines<OverlayData>>()
+            .add_systems(ExtractSchedule, extract)
+            .add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
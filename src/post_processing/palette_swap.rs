@@ -0,0 +1,392 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupLayout, CachedRenderPipelineId, Extent3d, ShaderType, TextureDimension,
+            TextureFormat,
+        },
+        renderer::RenderDevice,
+        texture::FallbackImageZero,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingViewEffect,
+    Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const PALETTE_SWAP_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4417290386152093176);
+
+const PALETTE_GAME_BOY_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 13056712394820175549);
+const PALETTE_NES_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 7292038461275530982);
+const PALETTE_CGA_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 16634920517840287013);
+
+/// The four shades of green of the original Game Boy.
+const GAME_BOY: &[[u8; 3]] = &[[15, 56, 15], [48, 98, 48], [139, 172, 15], [155, 188, 15]];
+
+/// The colors of the NES, without the duplicate blacks.
+const NES: &[[u8; 3]] = &[
+    [124, 124, 124],
+    [0, 0, 252],
+    [0, 0, 188],
+    [68, 40, 188],
+    [148, 0, 132],
+    [168, 0, 32],
+    [168, 16, 0],
+    [136, 20, 0],
+    [80, 48, 0],
+    [0, 120, 0],
+    [0, 104, 0],
+    [0, 88, 0],
+    [0, 64, 88],
+    [0, 0, 0],
+    [188, 188, 188],
+    [0, 120, 248],
+    [0, 88, 248],
+    [104, 68, 252],
+    [216, 0, 204],
+    [228, 0, 88],
+    [248, 56, 0],
+    [228, 92, 16],
+    [172, 124, 0],
+    [0, 184, 0],
+    [0, 168, 0],
+    [0, 168, 68],
+    [0, 136, 136],
+    [248, 248, 248],
+    [60, 188, 252],
+    [104, 136, 252],
+    [152, 120, 248],
+    [248, 120, 248],
+    [248, 88, 152],
+    [248, 120, 88],
+    [252, 160, 68],
+    [248, 184, 0],
+    [184, 248, 24],
+    [88, 216, 84],
+    [88, 248, 152],
+    [0, 232, 216],
+    [120, 120, 120],
+    [252, 252, 252],
+    [164, 228, 252],
+    [184, 184, 248],
+    [216, 184, 248],
+    [248, 184, 248],
+    [248, 164, 192],
+    [240, 208, 176],
+    [252, 224, 168],
+    [248, 216, 120],
+    [216, 248, 120],
+    [184, 248, 184],
+    [184, 248, 216],
+    [0, 252, 252],
+    [248, 216, 248],
+];
+
+/// The high intensity cyan, magenta and white palette of CGA, along with black.
+const CGA: &[[u8; 3]] = &[[0, 0, 0], [85, 255, 255], [255, 85, 255], [255, 255, 255]];
+
+/// A palette texture of the given sRGB colors, in a single row.
+fn palette_image(colors: &[[u8; 3]]) -> Image {
+    let data = colors
+        .iter()
+        .flat_map(|&[r, g, b]| [r, g, b, 255])
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: colors.len() as u32,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+#[derive(Resource)]
+pub(crate) struct PaletteSwapData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for PaletteSwapData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Palette Swap",
+            &super::uniform_and_images_layout_entries(PaletteSwapUniform::min_size(), 1),
+            PALETTE_SWAP_SHADER_HANDLE.typed(),
+        );
+
+        PaletteSwapData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            PALETTE_SWAP_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "palette_swap.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        let mut assets = app.world.resource_mut::<Assets<_>>();
+        assets.set_untracked(PALETTE_GAME_BOY_IMAGE_HANDLE, palette_image(GAME_BOY));
+        assets.set_untracked(PALETTE_NES_IMAGE_HANDLE, palette_image(NES));
+        assets.set_untracked(PALETTE_CGA_IMAGE_HANDLE, palette_image(CGA));
+
+        app.register_type::<PaletteSwap>();
+        super::register_effect_types::<PaletteSwap>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<PaletteSwap>::default(),
+            UniformComponentPlugin::<PaletteSwapUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<PaletteSwap>,
+            )
+            .init_resource::<PaletteSwapData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<PaletteSwapUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<PaletteSwapData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<PaletteSwap>,
+        &Blend<PaletteSwap>,
+        &Resolution<PaletteSwap>,
+        &SourceSampler<PaletteSwap>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::palette_swap::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<PaletteSwapUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<PaletteSwapData>,
+    uniforms: Res<ComponentUniforms<PaletteSwapUniform>>,
+    images: Res<RenderAssets<Image>>,
+    fallback_zero: Res<FallbackImageZero>,
+    views: Query<(Entity, &PaletteSwapPalette)>,
+) {
+    let _span = info_span!("vfx::palette_swap::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, palette) in &views {
+        // While the palette is loading, its only texel is transparent, which the shader skips,
+        // so the image is left as is.
+        let palette = images.get(&palette.0).unwrap_or(&fallback_zero);
+
+        let bind_group = super::uniform_and_images_bind_group(
+            &render_device,
+            "Palette Swap Uniform Bind Group",
+            &data.layout,
+            uniforms.clone(),
+            &[palette],
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewUniformBindGroup::<PaletteSwapUniform>::new(bind_group));
+    }
+}
+
+/// Limits the image to the colors of a small palette, like the graphics of old consoles.
+///
+/// Each pixel is swapped for the nearest color in [`PaletteSwap::palette`], a texture whose texels
+/// are the colors. Transparent texels are skipped, and palettes of up to 256 colors are supported.
+/// The Game Boy, NES and CGA palettes are built in, see e.g. [`PaletteSwap::game_boy`].
+///
+/// Optionally, the image is dithered first, which mixes the palette colors to approximate
+/// the colors in between. Pairs well with [`super::pixelate::Pixelate`].
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct PaletteSwap {
+    /// The texture with the colors of the palette, e.g. a single row of pixels.
+    ///
+    /// Palettes are assets, so they are not serialized.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub palette: Handle<Image>,
+
+    /// How much to dither, from `0.0` (none) to `1.0`.
+    pub dither: f32,
+
+    /// How large the cells of the dither pattern are, in pixels.
+    /// Match this to the cell size of a [`super::pixelate::Pixelate`] before this effect.
+    pub dither_scale: f32,
+}
+
+impl PaletteSwap {
+    /// The colors of one of the built-in palettes.
+    pub fn built_in(palette: BuiltInPalette) -> Self {
+        Self::new(palette.image())
+    }
+
+    /// The colors of the given palette texture.
+    pub fn new(palette: Handle<Image>) -> Self {
+        Self {
+            palette,
+            ..default()
+        }
+    }
+
+    /// See [`BuiltInPalette::GameBoy`].
+    pub fn game_boy() -> Self {
+        Self::built_in(BuiltInPalette::GameBoy)
+    }
+
+    /// See [`BuiltInPalette::Nes`].
+    pub fn nes() -> Self {
+        Self::built_in(BuiltInPalette::Nes)
+    }
+
+    /// See [`BuiltInPalette::Cga`].
+    pub fn cga() -> Self {
+        Self::built_in(BuiltInPalette::Cga)
+    }
+
+    /// The same palette, dithered by the given amount.
+    pub fn with_dither(mut self, dither: f32) -> Self {
+        self.dither = dither;
+        self
+    }
+}
+
+/// The palettes built into [`PaletteSwap`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum BuiltInPalette {
+    /// The four shades of green of the original Game Boy.
+    #[default]
+    GameBoy,
+
+    /// The colors of the NES.
+    Nes,
+
+    /// Black, cyan, magenta and white, the high intensity palette of CGA graphics.
+    Cga,
+}
+
+impl BuiltInPalette {
+    /// The texture with the colors of the palette.
+    pub fn image(self) -> Handle<Image> {
+        match self {
+            Self::GameBoy => PALETTE_GAME_BOY_IMAGE_HANDLE.typed_weak(),
+            Self::Nes => PALETTE_NES_IMAGE_HANDLE.typed_weak(),
+            Self::Cga => PALETTE_CGA_IMAGE_HANDLE.typed_weak(),
+        }
+    }
+}
+
+impl Default for PaletteSwap {
+    fn default() -> Self {
+        Self {
+            palette: BuiltInPalette::default().image(),
+            dither: 0.0,
+            dither_scale: 1.0,
+        }
+    }
+}
+
+impl Display for PaletteSwap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Palette Swap dither: {:.2}, dither scale: {:.1}",
+            self.dither, self.dither_scale
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`PaletteSwap`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct PaletteSwapUniform {
+    pub(crate) dither: f32,
+    pub(crate) dither_scale: f32,
+}
+
+#[doc(hidden)]
+/// The palette of a [`PaletteSwap`], in the render world.
+#[derive(Debug, Clone, Component)]
+pub struct PaletteSwapPalette(pub(crate) Handle<Image>);
+
+impl ExtractComponent for PaletteSwap {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (PaletteSwapUniform, PaletteSwapPalette);
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::palette_swap::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((
+            PaletteSwapUniform {
+                dither: settings.dither,
+                dither_scale: settings.dither_scale,
+            },
+            PaletteSwapPalette(settings.palette.clone()),
+        ))
+    }
+}
@@ -13,7 +13,8 @@ pub(crate) use bevy::{
         render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
         render_resource::{
             BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
-            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
         },
         renderer::RenderDevice,
     },
@@ -21,20 +22,22 @@ pub(crate) use bevy::{
 
 use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
 
-use super::{Order, PostProcessingPhaseItem};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
 
 pub(crate) const PIXELATE_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 11093977931118718560);
 
 #[derive(Resource)]
 pub(crate) struct PixelateData {
-    pub pipeline_id: CachedRenderPipelineId,
     pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
 }
 
 impl FromWorld for PixelateData {
     fn from_world(world: &mut World) -> Self {
-        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+        let uniform_layout = super::create_layout(
             world,
             "Pixelate",
             &[BindGroupLayoutEntry {
@@ -42,17 +45,20 @@ impl FromWorld for PixelateData {
                 ty: BindingType::Buffer {
                     ty: BufferBindingType::Uniform,
                     has_dynamic_offset: true,
-                    min_binding_size: Some(Pixelate::min_size()),
+                    min_binding_size: Some(PixelateUniform::min_size()),
                 },
                 visibility: ShaderStages::FRAGMENT,
                 count: None,
             }],
-            PIXELATE_SHADER_HANDLE.typed(),
         );
 
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
         PixelateData {
-            pipeline_id,
             uniform_layout,
+            shared_layout,
         }
     }
 }
@@ -71,10 +77,14 @@ impl bevy::prelude::Plugin for Plugin {
             Shader::from_wgsl
         );
 
+        app.register_type::<Pixelate>();
+        app.register_type::<PixelateSampling>();
+        super::register_effect_types::<Pixelate>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins((
             ExtractComponentPlugin::<Pixelate>::default(),
-            UniformComponentPlugin::<Pixelate>::default(),
+            UniformComponentPlugin::<PixelateUniform>::default(),
         ));
 
         super::render_app(app)
@@ -83,32 +93,60 @@ impl bevy::prelude::Plugin for Plugin {
                 super::extract_post_processing_camera_phases::<Pixelate>,
             )
             .init_resource::<PixelateData>()
-            .init_resource::<UniformBindGroup<Pixelate>>()
+            .init_resource::<UniformBindGroup<PixelateUniform>>()
+            .init_resource::<SpecializedRenderPipelines<PixelateData>>()
             .add_systems(Render, prepare.in_set(RenderSet::Prepare))
             .add_systems(Render, queue.in_set(RenderSet::Queue))
-            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Pixelate>>();
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<PixelateUniform>>();
+    }
+}
+
+impl SpecializedRenderPipeline for PixelateData {
+    type Key = PixelateSampling;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Pixelate",
+            &self.shared_layout,
+            &self.uniform_layout,
+            PIXELATE_SHADER_HANDLE.typed(),
+            key.shader_defs(),
+        )
     }
 }
 
 fn prepare(
     data: Res<PixelateData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PixelateData>>,
     mut views: Query<(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<Pixelate>,
+        &Blend<Pixelate>,
+        &Resolution<Pixelate>,
+        &SourceSampler<Pixelate>,
+        &PixelateSampling,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
+    let _span = info_span!("vfx::pixelate::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
         let draw_function = draw_functions
             .read()
-            .id::<DrawPostProcessingEffect<Pixelate>>();
+            .id::<DrawPostProcessingEffect<PixelateUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, *key);
 
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
-            pipeline_id: data.pipeline_id,
+            pipeline_id,
         });
     }
 }
@@ -116,10 +154,12 @@ fn prepare(
 fn queue(
     render_device: Res<RenderDevice>,
     data: Res<PixelateData>,
-    mut bind_group: ResMut<UniformBindGroup<Pixelate>>,
-    uniforms: Res<ComponentUniforms<Pixelate>>,
-    views: Query<Entity, With<Pixelate>>,
+    mut bind_group: ResMut<UniformBindGroup<PixelateUniform>>,
+    uniforms: Res<ComponentUniforms<PixelateUniform>>,
+    views: Query<Entity, With<PixelateUniform>>,
 ) {
+    let _span = info_span!("vfx::pixelate::queue").entered();
+
     bind_group.inner = None;
 
     if let Some(uniforms) = uniforms.binding() {
@@ -136,37 +176,115 @@ fn queue(
     }
 }
 
+/// How the color of each block is picked.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelateSampling {
+    /// The color at one corner of the block. Cheap, but thin features
+    /// pop in and out as they move across the sampled points.
+    #[default]
+    Point,
+
+    /// The color most like the rest of the block, out of a grid of 16 taps.
+    /// Thin features don't flicker as the camera moves, at the cost of more texture reads.
+    Dominant,
+}
+
+impl PixelateSampling {
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            PixelateSampling::Point => vec![],
+            PixelateSampling::Dominant => vec!["DOMINANT".into()],
+        }
+    }
+}
+
 /// Pixelate settings.
-#[derive(Debug, ShaderType, Component, Clone, Copy)]
+#[derive(Debug, PartialEq, Component, Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
 pub struct Pixelate {
     /// How many pixels in the width and height in a block after pixelation. One block has a constant color within it.
     ///
     /// The shader sets a lower bound to 1.0, since that would not change the outcome.
     pub block_size: f32,
+
+    /// Which [`PixelateSampling`] picks the color of each block.
+    pub sampling: PixelateSampling,
 }
 
 impl Default for Pixelate {
     fn default() -> Self {
-        Self { block_size: 8.0 }
+        Self {
+            block_size: 8.0,
+            sampling: PixelateSampling::default(),
+        }
     }
 }
 
 impl Display for Pixelate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Pixelate block size: {}", self.block_size)
+        write!(
+            f,
+            "Pixelate block size: {}, sampling: {:?}",
+            self.block_size, self.sampling
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Pixelate`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct PixelateUniform {
+    pub(crate) block_size: f32,
+}
+
+impl From<Pixelate> for PixelateUniform {
+    fn from(pixelate: Pixelate) -> Self {
+        Self {
+            block_size: pixelate.block_size,
+        }
     }
 }
 
 impl ExtractComponent for Pixelate {
     type Query = (&'static Self, &'static Camera);
     type Filter = ();
-    type Out = Self;
+    type Out = (PixelateUniform, PixelateSampling);
 
     fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::pixelate::extract").entered();
+
         if !camera.is_active {
             return None;
         }
 
-        Some(*settings)
+        Some(((*settings).into(), settings.sampling))
+    }
+}
+
+#[cfg(feature = "material2d")]
+impl super::material::MaterialEffect for Pixelate {
+    type Uniform = PixelateUniform;
+    type Key = PixelateSampling;
+
+    const MATERIAL_UUID: bevy::utils::Uuid =
+        bevy::utils::Uuid::from_u128(0xa2ffa9d29ec54e9ea528375a7a08da77);
+
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        bevy::render::render_resource::ShaderRef::Handle(PIXELATE_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        (*self).into()
+    }
+
+    fn key(&self) -> Self::Key {
+        self.sampling
+    }
+
+    fn shader_defs(key: &Self::Key) -> Vec<ShaderDefVal> {
+        key.shader_defs()
     }
 }
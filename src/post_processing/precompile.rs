@@ -0,0 +1,498 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use bevy::{
+    ecs::{query::QueryItem, system::SystemParam},
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_resource::{
+            CachedPipelineState, CachedRenderPipelineId, PipelineCache, SpecializedRenderPipelines,
+            TextureFormat,
+        },
+        texture::BevyDefault,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::{BlendData, BlendMode, EffectScope},
+    depth_of_field::{DepthOfFieldData, DepthOfFieldPass},
+    dither::{DitherData, DitherMode},
+    fog::{FogData, FogFalloff},
+    formats::PipelineFormats,
+    masks::{MaskData, MaskVariant},
+    pixelate::{PixelateData, PixelateSampling},
+    vision_mode::{VisionModeData, VisionModeVariant},
+    wave::{WaveData, WaveProfile},
+    weather_overlay::{WeatherOverlayData, WeatherVariant},
+};
+
+/// A variant of an effect which has a render pipeline of its own.
+///
+/// These pipelines are compiled when the variant is first used,
+/// which may cause a hitch. See [`PrecompileEffects`].
+///
+/// Effects without variants are compiled when the app starts, so they are not listed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectVariant {
+    /// The pass blending the output of an effect with the given [`super::blend::BlendMode`].
+    Blend(BlendMode),
+
+    /// The [`super::depth_of_field::DepthOfField`] effect.
+    DepthOfField,
+
+    /// A [`super::dither::Dither`] effect with the given mode.
+    Dither(DitherMode),
+
+    /// A [`super::fog::Fog`] effect with the given falloff.
+    Fog(FogFalloff),
+
+    /// A [`super::masks::Mask`] effect with the given variant.
+    Mask(MaskVariant),
+
+    /// A [`super::pixelate::Pixelate`] effect with the given sampling.
+    Pixelate(PixelateSampling),
+
+    /// A [`super::vision_mode::VisionMode`] effect with the given variant.
+    VisionMode(VisionModeVariant),
+
+    /// A [`super::wave::Wave`] effect with the given profile.
+    Wave(WaveProfile),
+
+    /// A [`super::weather_overlay::WeatherOverlay`] effect with the given variant.
+    WeatherOverlay(WeatherVariant),
+}
+
+impl EffectVariant {
+    /// All effect variants.
+    pub fn all() -> Vec<Self> {
+        let mut variants: Vec<Self> = [
+            BlendMode::Normal,
+            BlendMode::Additive,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Overlay,
+        ]
+        .map(Self::Blend)
+        .into();
+        variants.push(Self::DepthOfField);
+
+        variants.extend(
+            [
+                DitherMode::Bayer2,
+                DitherMode::Bayer4,
+                DitherMode::Bayer8,
+                DitherMode::BlueNoise,
+            ]
+            .map(Self::Dither),
+        );
+        variants.extend(
+            [
+                FogFalloff::Linear,
+                FogFalloff::Exponential,
+                FogFalloff::ExponentialSquared,
+            ]
+            .map(Self::Fog),
+        );
+        variants
+            .extend([MaskVariant::Square, MaskVariant::Crt, MaskVariant::Vignette].map(Self::Mask));
+        variants.extend([PixelateSampling::Point, PixelateSampling::Dominant].map(Self::Pixelate));
+        variants.extend(
+            [VisionModeVariant::NightVision, VisionModeVariant::Thermal].map(Self::VisionMode),
+        );
+        variants.extend(
+            [
+                WaveProfile::Sine,
+                WaveProfile::Triangle,
+                WaveProfile::Sawtooth,
+                WaveProfile::Noise,
+            ]
+            .map(Self::Wave),
+        );
+        variants.extend([WeatherVariant::Rain, WeatherVariant::Snow].map(Self::WeatherOverlay));
+
+        variants
+    }
+
+    /// All variants of the same effect as this one, including this one.
+    ///
+    /// ```
+    /// # use bevy_vfx_bag::post_processing::{masks::MaskVariant, precompile::EffectVariant};
+    /// let masks = EffectVariant::Mask(MaskVariant::Square).siblings();
+    /// assert_eq!(masks.len(), 3);
+    /// ```
+    pub fn siblings(self) -> Vec<Self> {
+        let effect = std::mem::discriminant(&self);
+
+        Self::all()
+            .into_iter()
+            .filter(|variant| std::mem::discriminant(variant) == effect)
+            .collect()
+    }
+}
+
+/// Compiles the render pipelines of effect variants ahead of time,
+/// e.g. during a loading screen, so they don't cause a hitch when they first appear mid-gameplay.
+///
+/// Insert this as a resource, and wait for the [`EffectsPrecompiled`] event,
+/// or for [`PrecompileEffects::is_ready`]:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{masks::MaskVariant, precompile::{EffectVariant, EffectsPrecompiled, PrecompileEffects}};
+/// fn start_loading(mut commands: Commands) {
+///     commands.insert_resource(PrecompileEffects::new([
+///         EffectVariant::Mask(MaskVariant::Crt),
+///         EffectVariant::DepthOfField,
+///     ]));
+/// }
+///
+/// fn finish_loading(mut precompiled: EventReader<EffectsPrecompiled>) {
+///     if precompiled.iter().next().is_some() {
+///         // Leave the loading screen.
+///     }
+/// }
+/// ```
+///
+/// Pipelines compile in the background where the platform allows, so the app keeps running
+/// meanwhile. Readiness also waits for the pipelines of the effects without variants,
+/// which start compiling when the app starts.
+///
+/// Pipelines are compiled for the texture format picked by the `hdr` and `sdr` features.
+/// Cameras of the other format still compile their own variants when the effects first appear,
+/// unless [`PrecompileEffects::with_other_format`] is used.
+///
+/// To only compile the variants of effects which are used, see [`PrecompileVariants`].
+#[derive(Debug, Clone, Resource, ExtractResource)]
+pub struct PrecompileEffects {
+    /// The effect variants to compile.
+    pub variants: Vec<EffectVariant>,
+
+    /// Whether the pipelines of all effects are also compiled for cameras of the other
+    /// texture format than the one picked by the `hdr` and `sdr` features.
+    pub other_format: bool,
+
+    ready: Arc<AtomicBool>,
+
+    /// Whether [`EffectsPrecompiled`] was sent, in the main world.
+    announced: bool,
+}
+
+impl PrecompileEffects {
+    /// Compile the given effect variants.
+    pub fn new(variants: impl IntoIterator<Item = EffectVariant>) -> Self {
+        Self {
+            variants: variants.into_iter().collect(),
+            other_format: false,
+            ready: default(),
+            announced: false,
+        }
+    }
+
+    /// Compile all effect variants.
+    pub fn all() -> Self {
+        Self::new(EffectVariant::all())
+    }
+
+    /// Also compile the pipelines of all effects for the other texture format,
+    /// e.g. when the app has both HDR cameras and cameras without [`Camera::hdr`].
+    ///
+    /// This doubles the number of pipelines to compile.
+    pub fn with_other_format(mut self) -> Self {
+        self.other_format = true;
+        self
+    }
+
+    /// True once all the pipelines have finished compiling.
+    ///
+    /// Pipelines which failed to compile count as finished, the error is logged by Bevy.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+}
+
+/// Sent once the pipelines requested by a [`PrecompileEffects`] have finished compiling.
+///
+/// Sent again if another [`PrecompileEffects`] is inserted, once it is ready too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct EffectsPrecompiled;
+
+/// The pipelines of the effects without variants, which are made when the app starts.
+#[derive(Debug, Default, Resource)]
+struct EffectPipelines(Vec<CachedRenderPipelineId>);
+
+/// Keeps the pipeline of an effect without variants, so [`PrecompileEffects`] can wait for it,
+/// and compile it for the other texture format.
+pub(crate) fn register_pipeline(world: &mut World, pipeline_id: CachedRenderPipelineId) {
+    world
+        .get_resource_or_insert_with(EffectPipelines::default)
+        .0
+        .push(pipeline_id);
+}
+
+/// The texture format the `hdr` and `sdr` features didn't pick.
+fn other_target_format() -> TextureFormat {
+    if super::default_target_format() == ViewTarget::TEXTURE_FORMAT_HDR {
+        TextureFormat::bevy_default()
+    } else {
+        ViewTarget::TEXTURE_FORMAT_HDR
+    }
+}
+
+/// The specialized pipelines of all effects with variants.
+#[derive(SystemParam)]
+struct VariantPipelines<'w> {
+    pipeline_cache: Res<'w, PipelineCache>,
+    blend: (
+        Res<'w, BlendData>,
+        ResMut<'w, SpecializedRenderPipelines<BlendData>>,
+    ),
+    depth_of_field: (
+        Res<'w, DepthOfFieldData>,
+        ResMut<'w, SpecializedRenderPipelines<DepthOfFieldData>>,
+    ),
+    dither: (
+        Res<'w, DitherData>,
+        ResMut<'w, SpecializedRenderPipelines<DitherData>>,
+    ),
+    fog: (
+        Res<'w, FogData>,
+        ResMut<'w, SpecializedRenderPipelines<FogData>>,
+    ),
+    mask: (
+        Res<'w, MaskData>,
+        ResMut<'w, SpecializedRenderPipelines<MaskData>>,
+    ),
+    pixelate: (
+        Res<'w, PixelateData>,
+        ResMut<'w, SpecializedRenderPipelines<PixelateData>>,
+    ),
+    vision_mode: (
+        Res<'w, VisionModeData>,
+        ResMut<'w, SpecializedRenderPipelines<VisionModeData>>,
+    ),
+    wave: (
+        Res<'w, WaveData>,
+        ResMut<'w, SpecializedRenderPipelines<WaveData>>,
+    ),
+    weather_overlay: (
+        Res<'w, WeatherOverlayData>,
+        ResMut<'w, SpecializedRenderPipelines<WeatherOverlayData>>,
+    ),
+}
+
+impl VariantPipelines<'_> {
+    /// The pipelines of the variant, which are queued for compiling if they weren't already.
+    ///
+    /// Specializing is cached, so asking again for a variant is cheap.
+    fn specialize(&mut self, variant: EffectVariant) -> Vec<CachedRenderPipelineId> {
+        let pipeline_cache = &self.pipeline_cache;
+
+        match variant {
+            EffectVariant::Blend(mode) => {
+                let (data, pipelines) = &mut self.blend;
+                vec![pipelines.specialize(
+                    pipeline_cache,
+                    data,
+                    (
+                        mode,
+                        EffectScope::Everywhere,
+                        super::default_target_format(),
+                    ),
+                )]
+            }
+            EffectVariant::DepthOfField => {
+                let (data, pipelines) = &mut self.depth_of_field;
+                [
+                    DepthOfFieldPass::CircleOfConfusion,
+                    DepthOfFieldPass::Gather,
+                ]
+                .into_iter()
+                .map(|pass| pipelines.specialize(pipeline_cache, data, pass))
+                .collect()
+            }
+            EffectVariant::Dither(mode) => {
+                let (data, pipelines) = &mut self.dither;
+                vec![pipelines.specialize(pipeline_cache, data, mode)]
+            }
+            EffectVariant::Fog(falloff) => {
+                let (data, pipelines) = &mut self.fog;
+                vec![pipelines.specialize(pipeline_cache, data, falloff)]
+            }
+            EffectVariant::Mask(variant) => {
+                let (data, pipelines) = &mut self.mask;
+                vec![pipelines.specialize(pipeline_cache, data, variant)]
+            }
+            EffectVariant::Pixelate(sampling) => {
+                let (data, pipelines) = &mut self.pixelate;
+                vec![pipelines.specialize(pipeline_cache, data, sampling)]
+            }
+            EffectVariant::VisionMode(variant) => {
+                let (data, pipelines) = &mut self.vision_mode;
+                vec![pipelines.specialize(pipeline_cache, data, variant)]
+            }
+            EffectVariant::Wave(profile) => {
+                let (data, pipelines) = &mut self.wave;
+                vec![pipelines.specialize(pipeline_cache, data, profile)]
+            }
+            EffectVariant::WeatherOverlay(variant) => {
+                let (data, pipelines) = &mut self.weather_overlay;
+                vec![pipelines.specialize(pipeline_cache, data, variant)]
+            }
+        }
+    }
+}
+
+fn precompile_effects(
+    precompile: Option<Res<PrecompileEffects>>,
+    effect_pipelines: Option<Res<EffectPipelines>>,
+    mut formats: ResMut<PipelineFormats>,
+    mut pipelines: VariantPipelines,
+) {
+    let Some(precompile) = precompile else {
+        return;
+    };
+
+    if precompile.is_ready() {
+        return;
+    }
+
+    // Specializing is cached, so asking again each frame until the pipelines are ready is cheap.
+    let mut pipeline_ids: Vec<CachedRenderPipelineId> = precompile
+        .variants
+        .iter()
+        .flat_map(|variant| pipelines.specialize(*variant))
+        .collect();
+    if let Some(effect_pipelines) = &effect_pipelines {
+        pipeline_ids.extend(&effect_pipelines.0);
+    }
+
+    // The variants for the other format are made from the descriptors of the pipelines,
+    // which are only known to the cache once it processed them.
+    let mut variants_known = true;
+    if precompile.other_format {
+        let format = other_target_format();
+        let variants: Vec<Option<CachedRenderPipelineId>> = pipeline_ids
+            .iter()
+            .map(|id| formats.queue_variant(&pipelines.pipeline_cache, *id, format))
+            .collect();
+
+        variants_known = variants.iter().all(Option::is_some);
+        pipeline_ids.extend(variants.into_iter().flatten());
+    }
+
+    // Pipelines specialized just now are not known to the cache yet, so they are not ready either.
+    let ready = variants_known
+        && pipeline_ids.into_iter().all(|id| {
+            super::pipeline_state(&pipelines.pipeline_cache, id)
+                .is_some_and(|state| !matches!(state, CachedPipelineState::Queued))
+        });
+
+    if ready {
+        debug!("Precompiled {} effect variants", precompile.variants.len());
+        precompile.ready.store(true, Ordering::Release);
+    }
+}
+
+fn send_precompiled_event(
+    precompile: Option<ResMut<PrecompileEffects>>,
+    mut events: EventWriter<EffectsPrecompiled>,
+) {
+    let Some(mut precompile) = precompile else {
+        return;
+    };
+
+    // Not a change of the resource, which would extract it to the render world again.
+    if precompile.is_ready() && !precompile.announced {
+        precompile.bypass_change_detection().announced = true;
+        events.send(EffectsPrecompiled);
+    }
+}
+
+/// Compiles the other variants of the effects used by cameras with [`PrecompileVariants`].
+fn precompile_variants_in_use(
+    mut pipelines: VariantPipelines,
+    views: Query<
+        (
+            Option<&MaskVariant>,
+            Option<&DitherMode>,
+            Option<&FogFalloff>,
+            Option<&PixelateSampling>,
+            Option<&VisionModeVariant>,
+            Option<&WaveProfile>,
+            Option<&WeatherVariant>,
+        ),
+        With<PrecompileVariants>,
+    >,
+) {
+    for (mask, dither, fog, pixelate, vision_mode, wave, weather_overlay) in &views {
+        let in_use = [
+            mask.copied().map(EffectVariant::Mask),
+            dither.copied().map(EffectVariant::Dither),
+            fog.copied().map(EffectVariant::Fog),
+            pixelate.copied().map(EffectVariant::Pixelate),
+            vision_mode.copied().map(EffectVariant::VisionMode),
+            wave.copied().map(EffectVariant::Wave),
+            weather_overlay.copied().map(EffectVariant::WeatherOverlay),
+        ];
+
+        for variant in in_use
+            .into_iter()
+            .flatten()
+            .flat_map(EffectVariant::siblings)
+        {
+            pipelines.specialize(variant);
+        }
+    }
+}
+
+/// Compiles all variants of the effects on a camera as soon as one of them is used,
+/// so switching between them at runtime, e.g. from [`MaskVariant::Square`] to [`MaskVariant::Crt`],
+/// finds a compiled pipeline instead of compiling it mid-gameplay.
+///
+/// The first variant still compiles when the effect first appears, which [`PrecompileEffects`]
+/// can do ahead of time. This compiles pipelines which may never be used, so it is opt-in.
+#[derive(Debug, Default, Clone, Copy, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct PrecompileVariants;
+
+impl ExtractComponent for PrecompileVariants {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((precompile, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*precompile)
+    }
+}
+
+/// Compiles the effect variants requested via [`PrecompileEffects`] and [`PrecompileVariants`].
+///
+/// Must be added after the effects, since it uses their render data.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PrecompileVariants>()
+            .add_event::<EffectsPrecompiled>()
+            .add_systems(First, send_precompiled_event);
+
+        app.add_plugins((
+            ExtractResourcePlugin::<PrecompileEffects>::default(),
+            ExtractComponentPlugin::<PrecompileVariants>::default(),
+        ));
+
+        super::render_app(app).add_systems(
+            Render,
+            (precompile_effects, precompile_variants_in_use).in_set(RenderSet::Prepare),
+        );
+    }
+}
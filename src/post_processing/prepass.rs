@@ -0,0 +1,166 @@
+use bevy::{
+    core_pipeline::{
+        core_3d::Camera3d,
+        prepass::{DepthPrepass, NormalPrepass},
+    },
+    ecs::query::Has,
+    prelude::*,
+    utils::HashSet,
+};
+
+/// Which prepasses of the camera an effect reads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Prepasses {
+    /// The depth prepass, see [`DepthPrepass`].
+    pub depth: bool,
+
+    /// The normal prepass, see [`NormalPrepass`].
+    pub normal: bool,
+}
+
+impl Prepasses {
+    /// No prepasses.
+    pub const NONE: Self = Self {
+        depth: false,
+        normal: false,
+    };
+
+    /// The depth prepass.
+    pub const DEPTH: Self = Self {
+        depth: true,
+        normal: false,
+    };
+
+    /// The depth and normal prepasses.
+    pub const DEPTH_AND_NORMAL: Self = Self {
+        depth: true,
+        normal: true,
+    };
+}
+
+/// An effect which reads prepasses of the camera it is on.
+///
+/// With [`AutoPrepass::enabled`], the prepasses are added to the camera along with the effect.
+/// Effects of other crates can implement this and add [`configure_prepasses`] to get the same.
+pub trait EffectPrepasses: Component {
+    /// What goes wrong without the prepasses, e.g. `"the fog will not look right"`.
+    const WITHOUT: &'static str;
+
+    /// The prepasses the effect reads with these settings.
+    fn prepasses(&self) -> Prepasses;
+}
+
+/// Whether the prepasses effects read are added to the cameras they are on.
+///
+/// Effects such as [`super::fog::Fog`] and [`super::depth_of_field::DepthOfField`] read the
+/// depth prepass, which is easy to forget. When enabled, the missing prepasses are added
+/// to the camera once the effect is. When disabled, a warning is logged instead.
+///
+/// Post processing only reads the prepasses with `Msaa::Off`, and only 3d cameras have them,
+/// which is warned about either way.
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct AutoPrepass {
+    /// If true, missing prepasses are added.
+    pub enabled: bool,
+}
+
+impl Default for AutoPrepass {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// The name of the effect, e.g. `"Fog"`, for diagnostics.
+fn effect_name<C>() -> &'static str {
+    let name = std::any::type_name::<C>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Adds the prepasses the effect `C` reads to the cameras it is on, or warns about them.
+///
+/// Runs when the effect is added or changed, since what it reads may depend on its settings.
+pub fn configure_prepasses<C: EffectPrepasses>(
+    mut commands: Commands,
+    auto_prepass: Res<AutoPrepass>,
+    msaa: Res<Msaa>,
+    cameras: Query<(
+        Entity,
+        Ref<C>,
+        Has<Camera3d>,
+        Has<DepthPrepass>,
+        Has<NormalPrepass>,
+    )>,
+    mut warned: Local<HashSet<Entity>>,
+    mut warned_msaa: Local<bool>,
+) {
+    for (entity, effect, is_3d, has_depth, has_normal) in &cameras {
+        if !effect.is_changed() {
+            continue;
+        }
+
+        let needs = effect.prepasses();
+        if needs == Prepasses::NONE {
+            continue;
+        }
+
+        let name = effect_name::<C>();
+
+        if *msaa != Msaa::Off && !*warned_msaa {
+            *warned_msaa = true;
+            warn!(
+                "Camera {entity:?} has `{name}`, which reads the prepass, but post processing can only read it with `Msaa::Off`, so {}",
+                C::WITHOUT
+            );
+        }
+
+        let missing_depth = needs.depth && !has_depth;
+        let missing_normal = needs.normal && !has_normal;
+        if !missing_depth && !missing_normal {
+            continue;
+        }
+
+        if !is_3d {
+            if warned.insert(entity) {
+                warn!(
+                    "Camera {entity:?} has `{name}`, which reads the prepass, but only 3d cameras have one, so {}",
+                    C::WITHOUT
+                );
+            }
+            continue;
+        }
+
+        let missing = match (missing_depth, missing_normal) {
+            (true, true) => "`DepthPrepass` and `NormalPrepass`",
+            (true, false) => "`DepthPrepass`",
+            _ => "`NormalPrepass`",
+        };
+
+        if !auto_prepass.enabled {
+            if warned.insert(entity) {
+                warn!(
+                    "Camera {entity:?} has `{name}` but no {missing}, {}",
+                    C::WITHOUT
+                );
+            }
+            continue;
+        }
+
+        debug!("Adding {missing} to camera {entity:?}, which `{name}` reads");
+        let mut camera = commands.entity(entity);
+        if missing_depth {
+            camera.insert(DepthPrepass);
+        }
+        if missing_normal {
+            camera.insert(NormalPrepass);
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AutoPrepass>()
+            .init_resource::<AutoPrepass>();
+    }
+}
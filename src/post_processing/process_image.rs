@@ -0,0 +1,330 @@
+use bevy::{
+    core_pipeline::{
+        blit::{BlitPipeline, BlitPipelineKey},
+        clear_color::ClearColorConfig,
+        core_2d::{self, Camera2d},
+    },
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssets,
+        render_graph::{NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindingResource, CachedRenderPipelineId, Extent3d,
+            Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            SpecializedRenderPipelines, TextureDescriptor, TextureDimension, TextureFormat,
+            TextureUsages,
+        },
+        renderer::RenderContext,
+        texture::BevyDefault,
+        view::{RenderLayers, ViewTarget},
+        Extract, Render, RenderSet,
+    },
+};
+
+use super::{graph::node, readiness::EffectReadiness};
+
+/// Applies the effects on this entity to an image, and writes the result into another image,
+/// e.g. for UI thumbnails, portraits or procedural textures.
+///
+/// The entity is given a camera which sees nothing but the input image, and renders into
+/// the output image. Effects are added to the entity like to any other camera:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{pixelate::Pixelate, process_image::{output_image, ProcessImage}};
+/// fn pixelate_portrait(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+///     mut images: ResMut<Assets<Image>>,
+/// ) {
+///     let portrait = asset_server.load("portrait.png");
+///     let output = images.add(output_image(UVec2::new(256, 256)));
+///
+///     commands.spawn((ProcessImage::once(portrait, output), Pixelate::default()));
+/// }
+/// ```
+///
+/// The input is stretched over the output. The camera renders before the cameras of the app,
+/// so the output can be shown in the same frame.
+#[derive(Debug, Default, Clone, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct ProcessImage {
+    /// The image the effects are applied to.
+    pub input: Handle<Image>,
+
+    /// The image the result is written into. See [`output_image`].
+    pub output: Handle<Image>,
+
+    /// Whether the effects are applied once, or every frame.
+    pub mode: ProcessImageMode,
+
+    /// How many rendered frames the input and effects were ready for, in [`ProcessImageMode::Once`].
+    #[reflect(ignore)]
+    frames_ready: u32,
+}
+
+impl ProcessImage {
+    /// Applies the effects once, as soon as the input is loaded and the effects are ready.
+    ///
+    /// The camera is deactivated afterwards, and a [`ImageProcessed`] event is sent.
+    pub fn once(input: Handle<Image>, output: Handle<Image>) -> Self {
+        Self {
+            input,
+            output,
+            mode: ProcessImageMode::Once,
+            frames_ready: 0,
+        }
+    }
+
+    /// Applies the effects every frame, e.g. for animated effects or an input which changes,
+    /// such as an in-world screen showing what another camera renders into the input.
+    pub fn every_frame(input: Handle<Image>, output: Handle<Image>) -> Self {
+        Self {
+            mode: ProcessImageMode::EveryFrame,
+            ..Self::once(input, output)
+        }
+    }
+
+    /// Applies the effects once more, e.g. after changing them or the input.
+    ///
+    /// Only needed in [`ProcessImageMode::Once`].
+    pub fn again(&mut self) {
+        self.frames_ready = 0;
+    }
+}
+
+/// How often a [`ProcessImage`] applies its effects.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ProcessImageMode {
+    /// The effects are applied once. See [`ProcessImage::once`].
+    #[default]
+    Once,
+
+    /// The effects are applied every frame. See [`ProcessImage::every_frame`].
+    EveryFrame,
+}
+
+/// Sent when a [`ProcessImage`] in [`ProcessImageMode::Once`] has written its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ImageProcessed {
+    /// The entity with the [`ProcessImage`].
+    pub entity: Entity,
+}
+
+/// An empty image which a [`ProcessImage`] can write into.
+///
+/// Images which are rendered into need [`TextureUsages::RENDER_ATTACHMENT`], which images
+/// loaded from files don't have. The image can also be copied from, e.g. to read it back.
+pub fn output_image(size: UVec2) -> Image {
+    let size = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("Processed image"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::bevy_default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+
+    // Fills the image with zeroes.
+    image.resize(size);
+    image
+}
+
+/// Gives new [`ProcessImage`]s their camera.
+fn add_cameras(mut commands: Commands, added: Query<(Entity, &ProcessImage), Added<ProcessImage>>) {
+    for (entity, process) in &added {
+        commands.entity(entity).insert((
+            Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(process.output.clone()),
+                    order: -1,
+                    ..default()
+                },
+                camera_2d: Camera2d {
+                    clear_color: ClearColorConfig::Custom(Color::NONE),
+                },
+                ..default()
+            },
+            // The camera sees nothing of the app, the input is drawn in its place.
+            RenderLayers::none(),
+        ));
+    }
+}
+
+/// Deactivates the cameras of [`ProcessImage`]s in [`ProcessImageMode::Once`] which are done,
+/// and activates those which should run again.
+fn deactivate_finished(
+    images: Res<Assets<Image>>,
+    readiness: Res<EffectReadiness>,
+    mut processed: EventWriter<ImageProcessed>,
+    mut cameras: Query<(Entity, &mut ProcessImage, &mut Camera)>,
+) {
+    for (entity, mut process, mut camera) in &mut cameras {
+        let done = process.mode == ProcessImageMode::Once && process.frames_ready > 1;
+
+        // Only written when it changes, so the camera isn't marked as changed every frame.
+        if camera.is_active == done {
+            camera.is_active = !done;
+        }
+
+        if done || process.mode == ProcessImageMode::EveryFrame {
+            continue;
+        }
+
+        // Readiness is as of the last rendered frame, which may have started before the input
+        // was loaded, so a frame more is waited for.
+        if readiness.is_ready(entity) && images.contains(&process.input) {
+            process.frames_ready += 1;
+
+            if process.frames_ready > 1 {
+                processed.send(ImageProcessed { entity });
+            }
+        }
+    }
+}
+
+/// The input of a [`ProcessImage`] camera, in the render world.
+#[derive(Component)]
+struct ViewImageInput {
+    image: Handle<Image>,
+    pipeline_id: Option<CachedRenderPipelineId>,
+}
+
+fn extract(mut commands: Commands, cameras: Extract<Query<(Entity, &Camera, &ProcessImage)>>) {
+    for (entity, camera, process) in &cameras {
+        if camera.is_active {
+            commands.get_or_spawn(entity).insert(ViewImageInput {
+                image: process.input.clone(),
+                pipeline_id: None,
+            });
+        }
+    }
+}
+
+fn queue(
+    pipeline_cache: Res<PipelineCache>,
+    blit: Res<BlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    mut views: Query<(&ViewTarget, &mut ViewImageInput)>,
+) {
+    let _span = info_span!("vfx::process_image::queue").entered();
+
+    for (view_target, mut input) in &mut views {
+        input.pipeline_id = Some(pipelines.specialize(
+            &pipeline_cache,
+            &blit,
+            BlitPipelineKey {
+                texture_format: view_target.main_texture_format(),
+                blend_state: None,
+                samples: 1,
+            },
+        ));
+    }
+}
+
+/// Draws the input of a [`ProcessImage`] camera into its main texture, before the effects run.
+#[derive(Default)]
+struct ImageInputNode;
+
+impl ViewNode for ImageInputNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewImageInput);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, input): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        // The camera shows its clear color until the input is loaded.
+        let Some(image) = world.resource::<RenderAssets<Image>>().get(&input.image) else {
+            return Ok(());
+        };
+        let Some(pipeline) = input
+            .pipeline_id
+            .and_then(|id| world.resource::<PipelineCache>().get_render_pipeline(id))
+        else {
+            return Ok(());
+        };
+
+        let blit = world.resource::<BlitPipeline>();
+        let bind_group = render_context
+            .render_device()
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("Image input bind group"),
+                layout: &blit.texture_bind_group,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(&image.texture_view),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&blit.sampler),
+                    },
+                ],
+            });
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Image input"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: view_target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+/// Applies effects to images with [`ProcessImage`].
+///
+/// Must be added after the post processing nodes are in the graphs.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ProcessImage>()
+            .register_type::<ProcessImageMode>()
+            .add_event::<ImageProcessed>()
+            .add_systems(Update, (add_cameras, deactivate_finished));
+
+        let render_app = super::render_app(app);
+
+        // The cameras are 2d cameras, so only the 2d graph needs the node.
+        let input = ViewNodeRunner::new(ImageInputNode, &mut render_app.world);
+        let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+        let graph = render_graph
+            .get_sub_graph_mut(core_2d::graph::NAME)
+            .expect("Graph should be available");
+
+        graph.add_node(node::IMAGE_INPUT, input);
+        graph.add_node_edge(core_2d::graph::node::MAIN_PASS, node::IMAGE_INPUT);
+        graph.add_node_edge(node::IMAGE_INPUT, node::BEFORE_TONEMAPPING);
+
+        render_app
+            .add_systems(ExtractSchedule, extract)
+            .add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
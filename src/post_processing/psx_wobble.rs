@@ -0,0 +1,239 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const PSX_WOBBLE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10248632973075434751);
+
+#[derive(Resource)]
+pub(crate) struct PsxWobbleData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for PsxWobbleData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "PSX Wobble",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(PsxWobbleUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            PSX_WOBBLE_SHADER_HANDLE.typed(),
+        );
+
+        PsxWobbleData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            PSX_WOBBLE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "psx_wobble.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<PsxWobble>();
+        super::register_effect_types::<PsxWobble>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<PsxWobble>::default(),
+            UniformComponentPlugin::<PsxWobbleUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<PsxWobble>,
+            )
+            .init_resource::<PsxWobbleData>()
+            .init_resource::<UniformBindGroup<PsxWobbleUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<PsxWobbleUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<PsxWobbleData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<PsxWobble>,
+        &Blend<PsxWobble>,
+        &Resolution<PsxWobble>,
+        &SourceSampler<PsxWobble>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::psx_wobble::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<PsxWobbleUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<PsxWobbleData>,
+    mut bind_group: ResMut<UniformBindGroup<PsxWobbleUniform>>,
+    uniforms: Res<ComponentUniforms<PsxWobbleUniform>>,
+    views: Query<Entity, With<PsxWobbleUniform>>,
+) {
+    let _span = info_span!("vfx::psx_wobble::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("PSX Wobble Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Wobbles and snaps the image like the textures of the original PlayStation.
+///
+/// That console drew textures without perspective correction and snapped vertices to whole
+/// pixels, so textures warped along polygon edges and jittered as the camera moved.
+/// This approximates the look in post, without changing any materials: the image is cut into
+/// a grid of screen space triangles whose corners jitter a little at a time, the image is
+/// warped linearly across each triangle, and the positions it is read from are snapped to
+/// a coarse grid.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct PsxWobble {
+    /// The size in pixels of the grid the read positions are snapped to.
+    /// `1.0` turns snapping off.
+    pub snap: f32,
+
+    /// How far the corners of the triangles jitter, in pixels.
+    /// `0.0` turns the wobble off.
+    pub wobble: f32,
+
+    /// The size in pixels of the cells of the grid of triangles.
+    /// Each cell is split into two triangles.
+    pub cell_size: f32,
+
+    /// How many times a second the corners jump to a new place.
+    pub rate: f32,
+}
+
+impl Default for PsxWobble {
+    fn default() -> Self {
+        Self {
+            snap: 2.0,
+            wobble: 1.5,
+            cell_size: 48.0,
+            rate: 12.0,
+        }
+    }
+}
+
+impl Display for PsxWobble {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PSX Wobble snap: {:.1}, wobble: {:.2}, cell size: {:.0}, rate: {:.1}",
+            self.snap, self.wobble, self.cell_size, self.rate
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`PsxWobble`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct PsxWobbleUniform {
+    pub(crate) snap: f32,
+    pub(crate) wobble: f32,
+    pub(crate) cell_size: f32,
+    pub(crate) rate: f32,
+}
+
+impl From<PsxWobble> for PsxWobbleUniform {
+    fn from(wobble: PsxWobble) -> Self {
+        Self {
+            snap: wobble.snap.max(1.0),
+            wobble: wobble.wobble.max(0.0),
+            cell_size: wobble.cell_size.max(1.0),
+            rate: wobble.rate.max(0.0),
+        }
+    }
+}
+
+impl ExtractComponent for PsxWobble {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = PsxWobbleUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::psx_wobble::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -0,0 +1,239 @@
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    render::{
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blur::Blur,
+    depth_of_field::{DepthOfField, DepthOfFieldUniform},
+    frosted_glass::FrostedGlass,
+    glow::Glow,
+    god_rays_2d::{GodRays2d, GodRays2dUniform},
+    kuwahara::Kuwahara,
+    light_shafts::LightShafts,
+    motion_blur::{MotionBlur, MotionBlurUniform},
+    resolution::{Resolution, ResolutionScale, Upsampling},
+};
+
+/// A preset of [`QualitySettings`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum QualityTier {
+    /// For low-end hardware: half the samples, and the heavy effects at half resolution.
+    Low,
+
+    /// Three quarters of the samples.
+    Medium,
+
+    /// The effects as they are configured.
+    #[default]
+    High,
+
+    /// Settings which are not one of the presets.
+    Custom,
+}
+
+/// How much the heavier effects may cut back to run faster, in one place.
+///
+/// The built-in effects consult these instead of each having quality knobs of their own:
+///
+/// - [`QualitySettings::samples`] scales the samples of [`DepthOfField`], [`GodRays2d`],
+///   [`LightShafts`] and [`MotionBlur`].
+/// - [`QualitySettings::kernel`] scales the samples per side of the regions of [`Kuwahara`].
+/// - [`QualitySettings::resolution`] is the resolution [`Blur`], [`DepthOfField`],
+///   [`FrostedGlass`], [`Glow`], [`GodRays2d`], [`Kuwahara`], [`LightShafts`] and [`MotionBlur`]
+///   are drawn at, on cameras without a [`Resolution`] for them.
+///
+/// The parameters of the effect components are left as they are; the scaled ones are only drawn.
+/// Switch tiers at runtime by sending [`SetQuality`], e.g. from a settings menu:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::quality::{QualitySettings, SetQuality};
+/// fn on_low_quality_picked(mut set_quality: EventWriter<SetQuality>) {
+///     set_quality.send(SetQuality(QualitySettings::low()));
+/// }
+/// ```
+///
+/// The effects pick up the new settings from the next frame on, and pipelines for the new
+/// resolutions are specialized as needed. Use [`super::precompile`] to compile them up front.
+#[derive(Debug, Clone, Copy, PartialEq, Resource, ExtractResource, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource, Default)]
+pub struct QualitySettings {
+    /// The preset these settings are, or [`QualityTier::Custom`].
+    pub tier: QualityTier,
+
+    /// The fraction of their samples the effects take, e.g. `0.5` for half.
+    /// Effects take at least one sample.
+    pub samples: f32,
+
+    /// The fraction of the size of their sampling kernels the effects use.
+    pub kernel: f32,
+
+    /// The resolution the heavy effects are drawn at.
+    pub resolution: ResolutionScale,
+}
+
+impl QualitySettings {
+    /// The settings of the given tier. [`QualityTier::Custom`] gives the settings of
+    /// [`QualityTier::High`], marked as custom.
+    pub fn from_tier(tier: QualityTier) -> Self {
+        match tier {
+            QualityTier::Low => Self::low(),
+            QualityTier::Medium => Self::medium(),
+            QualityTier::High => Self::high(),
+            QualityTier::Custom => Self {
+                tier,
+                ..Self::high()
+            },
+        }
+    }
+
+    /// See [`QualityTier::Low`].
+    pub fn low() -> Self {
+        Self {
+            tier: QualityTier::Low,
+            samples: 0.5,
+            kernel: 0.5,
+            resolution: ResolutionScale::Half,
+        }
+    }
+
+    /// See [`QualityTier::Medium`].
+    pub fn medium() -> Self {
+        Self {
+            tier: QualityTier::Medium,
+            samples: 0.75,
+            kernel: 0.75,
+            resolution: ResolutionScale::Full,
+        }
+    }
+
+    /// See [`QualityTier::High`].
+    pub fn high() -> Self {
+        Self {
+            tier: QualityTier::High,
+            samples: 1.0,
+            kernel: 1.0,
+            resolution: ResolutionScale::Full,
+        }
+    }
+
+    /// Settings of their own, as [`QualityTier::Custom`].
+    pub fn custom(samples: f32, kernel: f32, resolution: ResolutionScale) -> Self {
+        Self {
+            tier: QualityTier::Custom,
+            samples,
+            kernel,
+            resolution,
+        }
+    }
+
+    /// The resolution the effect `C` is drawn at on cameras without a [`Resolution<C>`].
+    pub(crate) fn resolution<C: 'static>(&self) -> Resolution<C> {
+        let id = TypeId::of::<C>();
+        let heavy = [
+            TypeId::of::<Blur>(),
+            TypeId::of::<DepthOfField>(),
+            TypeId::of::<FrostedGlass>(),
+            TypeId::of::<Glow>(),
+            TypeId::of::<GodRays2d>(),
+            TypeId::of::<Kuwahara>(),
+            TypeId::of::<LightShafts>(),
+            TypeId::of::<MotionBlur>(),
+        ];
+
+        if heavy.contains(&id) {
+            Resolution::new(self.resolution, Upsampling::Bilinear)
+        } else {
+            Resolution::default()
+        }
+    }
+}
+
+impl Default for QualitySettings {
+    fn default() -> Self {
+        Self::high()
+    }
+}
+
+/// Switches the [`QualitySettings`] of all effects.
+#[derive(Debug, Clone, Copy, PartialEq, Event)]
+pub struct SetQuality(pub QualitySettings);
+
+impl From<QualityTier> for SetQuality {
+    fn from(tier: QualityTier) -> Self {
+        Self(QualitySettings::from_tier(tier))
+    }
+}
+
+fn set_quality(mut events: EventReader<SetQuality>, mut settings: ResMut<QualitySettings>) {
+    if let Some(SetQuality(quality)) = events.iter().last() {
+        if *settings != *quality {
+            info!("Switching effects to {:?} quality", quality.tier);
+            *settings = *quality;
+        }
+    }
+}
+
+/// A sample count scaled by the given fraction, of at least one.
+fn scale(count: u32, fraction: f32) -> u32 {
+    ((count as f32 * fraction).round() as u32).max(1)
+}
+
+/// Scales the samples of the extracted effects, before their uniforms are written.
+fn scale_samples(
+    quality: Res<QualitySettings>,
+    mut depth_of_field: Query<&mut DepthOfFieldUniform>,
+    mut god_rays: Query<&mut GodRays2dUniform>,
+    mut light_shafts: Query<&mut LightShafts>,
+    mut motion_blur: Query<&mut MotionBlurUniform>,
+    mut kuwahara: Query<&mut Kuwahara>,
+) {
+    if quality.samples == 1.0 && quality.kernel == 1.0 {
+        return;
+    }
+
+    for mut uniform in &mut depth_of_field {
+        uniform.sample_count = scale(uniform.sample_count, quality.samples);
+    }
+    for mut uniform in &mut god_rays {
+        uniform.samples = scale(uniform.samples, quality.samples);
+    }
+    for mut settings in &mut light_shafts {
+        settings.samples = scale(settings.samples, quality.samples);
+    }
+    for mut uniform in &mut motion_blur {
+        uniform.max_samples = scale(uniform.max_samples, quality.samples);
+    }
+    for mut settings in &mut kuwahara {
+        settings.samples_per_side = scale(settings.samples_per_side, quality.kernel);
+    }
+}
+
+/// Applies the [`QualitySettings`].
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QualitySettings>()
+            .register_type::<QualityTier>()
+            .register_type::<QualitySettings>()
+            .add_event::<SetQuality>()
+            .add_plugins(ExtractResourcePlugin::<QualitySettings>::default())
+            .add_systems(PreUpdate, set_quality);
+
+        // The uniforms are written to the GPU during prepare, so they are scaled before.
+        super::render_app(app).add_systems(
+            Render,
+            scale_samples
+                .after(RenderSet::ExtractCommands)
+                .before(RenderSet::Prepare),
+        );
+    }
+}
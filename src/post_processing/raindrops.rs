@@ -29,9 +29,14 @@ pub(crate) use bevy::{
     },
 };
 
-use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+use crate::{
+    post_processing::{DrawPostProcessingEffect, UniformBindGroup},
+    time::VfxTime,
+};
 
-use super::{Order, PostProcessingPhaseItem};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
 
 pub(crate) const RAINDROPS_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3481202994982538867);
@@ -75,7 +80,7 @@ impl FromWorld for RaindropsData {
                     ty: BindingType::Buffer {
                         ty: BufferBindingType::Uniform,
                         has_dynamic_offset: true,
-                        min_binding_size: Some(Raindrops::min_size()),
+                        min_binding_size: Some(RaindropsUniform::min_size()),
                     },
                     visibility: ShaderStages::FRAGMENT,
                     count: None,
@@ -132,15 +137,20 @@ impl bevy::prelude::Plugin for Plugin {
         .expect("Should load raindrops successfully");
         assets.set_untracked(RAINDROPS_IMAGE_HANDLE, image);
 
+        app.register_type::<Raindrops>()
+            .register_type::<RefractionMode>();
+        super::register_effect_types::<Raindrops>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins((
             ExtractComponentPlugin::<Raindrops>::default(),
-            UniformComponentPlugin::<Raindrops>::default(),
+            UniformComponentPlugin::<RaindropsUniform>::default(),
             ExtractResourcePlugin::<RaindropsTextureHandle>::default(),
         ))
         .insert_resource(RaindropsTextureHandle(
             RAINDROPS_IMAGE_HANDLE.clone_weak().typed(),
-        ));
+        ))
+        .add_systems(Update, update_wetness);
 
         super::render_app(app)
             .add_systems(
@@ -148,10 +158,37 @@ impl bevy::prelude::Plugin for Plugin {
                 super::extract_post_processing_camera_phases::<Raindrops>,
             )
             .init_resource::<RaindropsData>()
-            .init_resource::<UniformBindGroup<Raindrops>>()
+            .init_resource::<UniformBindGroup<RaindropsUniform>>()
             .add_systems(Render, prepare.in_set(RenderSet::Prepare))
             .add_systems(Render, queue.in_set(RenderSet::Queue))
-            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Raindrops>>();
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<RaindropsUniform>>(
+            );
+    }
+}
+
+/// Moves the wetness of each camera towards its exposure.
+fn update_wetness(time: Res<VfxTime>, mut query: Query<&mut Raindrops>) {
+    let delta = time.delta_seconds();
+
+    for mut raindrops in &mut query {
+        // Avoids change detection when settled.
+        if raindrops.wetness == raindrops.exposure {
+            continue;
+        }
+
+        let Raindrops {
+            exposure,
+            accumulation_rate,
+            dry_off_rate,
+            wetness,
+            ..
+        } = *raindrops;
+
+        raindrops.wetness = if wetness < exposure {
+            (wetness + accumulation_rate * delta).min(exposure)
+        } else {
+            (wetness - dry_off_rate * delta).max(exposure)
+        };
     }
 }
 
@@ -161,17 +198,25 @@ fn prepare(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<Raindrops>,
+        &Blend<Raindrops>,
+        &Resolution<Raindrops>,
+        &SourceSampler<Raindrops>,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
+    let _span = info_span!("vfx::raindrops::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
         let draw_function = draw_functions
             .read()
-            .id::<DrawPostProcessingEffect<Raindrops>>();
+            .id::<DrawPostProcessingEffect<RaindropsUniform>>();
 
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
             pipeline_id: data.pipeline_id,
         });
@@ -182,11 +227,13 @@ fn queue(
     render_device: Res<RenderDevice>,
     data: Res<RaindropsData>,
     texture_handle: Res<RaindropsTextureHandle>,
-    mut bind_group: ResMut<UniformBindGroup<Raindrops>>,
-    uniforms: Res<ComponentUniforms<Raindrops>>,
+    mut bind_group: ResMut<UniformBindGroup<RaindropsUniform>>,
+    uniforms: Res<ComponentUniforms<RaindropsUniform>>,
     images: Res<RenderAssets<Image>>,
-    views: Query<Entity, With<Raindrops>>,
+    views: Query<Entity, With<RaindropsUniform>>,
 ) {
+    let _span = info_span!("vfx::raindrops::queue").entered();
+
     bind_group.inner = None;
 
     if let (Some(uniforms), Some(raindrops_image)) =
@@ -215,8 +262,76 @@ fn queue(
     }
 }
 
+/// How refracting effects, such as [`Raindrops`] and [`super::frosted_glass::FrostedGlass`],
+/// bend the image.
+#[derive(Debug, Default, PartialEq, Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefractionMode {
+    /// For 3d cameras with a perspective projection.
+    ///
+    /// The glass is on the lens, so it stays fixed to the screen, and the image is bent
+    /// by the full amount of the effect.
+    #[default]
+    Perspective,
+
+    /// For 2d scenes seen through an [`OrthographicProjection`].
+    ///
+    /// The glass is a pane between the camera and the scene, so the refraction keeps its shape
+    /// regardless of the aspect ratio, and the glass scrolls against the scene as the camera moves.
+    /// See [`RefractionMode::flat`] for a default.
+    Flat {
+        /// How far the glass is in front of the scene, from `0.0` (on the scene) to `1.0`
+        /// (on the camera).
+        ///
+        /// Glass on the scene moves along with it and barely bends it, like a window in a wall.
+        /// Glass on the camera stays fixed to the screen and bends the scene the most.
+        parallax: f32,
+    },
+}
+
+impl RefractionMode {
+    /// Glass halfway between the camera and a 2d scene, which works well for side-scrollers.
+    pub fn flat() -> Self {
+        Self::Flat { parallax: 0.5 }
+    }
+
+    /// Whether the refraction is flat, how much it bends the image, and how far the glass has
+    /// scrolled against the scene in heights of the view, as passed to shaders.
+    ///
+    /// Without an orthographic projection, flat glass doesn't scroll.
+    pub(crate) fn uniform(
+        self,
+        transform: &GlobalTransform,
+        projection: Option<&OrthographicProjection>,
+    ) -> (u32, f32, Vec2) {
+        match self {
+            Self::Perspective => (0, 1.0, Vec2::ZERO),
+            Self::Flat { parallax } => {
+                let parallax = parallax.clamp(0.0, 1.0);
+                let scroll = projection
+                    .filter(|projection| projection.area.height() > 0.0)
+                    .map(|projection| {
+                        let position =
+                            transform.translation().truncate() / projection.area.height();
+                        // The y axis points up in the world, but down in UV space.
+                        Vec2::new(position.x, -position.y) * (1.0 - parallax)
+                    })
+                    .unwrap_or(Vec2::ZERO);
+
+                (1, parallax, scroll)
+            }
+        }
+    }
+}
+
 /// Raindrops settings.
-#[derive(Debug, Component, Clone, Copy, ShaderType)]
+///
+/// Drops accumulate while the camera is exposed to rain, and dry off when it's not,
+/// e.g. under a roof. Set [`Raindrops::exposure`] from gameplay to control this.
+#[derive(Debug, PartialEq, Component, Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
 pub struct Raindrops {
     /// How quickly the raindrops animate.
     pub speed: f32,
@@ -226,6 +341,26 @@ pub struct Raindrops {
 
     /// How zoomed in the raindrops texture is.
     pub zoom: f32,
+
+    /// How exposed the camera is to rain, from `0.0` (sheltered) to `1.0` (out in the open).
+    /// Drops accumulate until the wetness reaches this.
+    pub exposure: f32,
+
+    /// How quickly drops accumulate while exposed, in wetness per second.
+    pub accumulation_rate: f32,
+
+    /// How quickly drops dry off while sheltered, in wetness per second.
+    pub dry_off_rate: f32,
+
+    /// How many of the drops are on the camera, from `0.0` (dry) to `1.0` (all of them).
+    ///
+    /// Follows [`Raindrops::exposure`] at the accumulation and dry-off rates.
+    /// Set it directly to make the camera wet or dry at once, e.g. when respawning.
+    pub wetness: f32,
+
+    /// How the drops bend the image. The default is tuned for 3d cameras,
+    /// use [`RefractionMode::flat`] for 2d scenes.
+    pub refraction: RefractionMode,
 }
 
 impl Default for Raindrops {
@@ -234,6 +369,11 @@ impl Default for Raindrops {
             speed: 0.8,
             warping: 0.03,
             zoom: 1.0,
+            exposure: 1.0,
+            accumulation_rate: 0.5,
+            dry_off_rate: 0.2,
+            wetness: 1.0,
+            refraction: RefractionMode::Perspective,
         }
     }
 }
@@ -242,22 +382,54 @@ impl Display for Raindrops {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Raindrops speed: {}, warping: {}, zoom: {}",
-            self.speed, self.warping, self.zoom
+            "Raindrops speed: {}, warping: {}, zoom: {}, exposure: {:.2}, wetness: {:.2}, refraction: {:?}",
+            self.speed, self.warping, self.zoom, self.exposure, self.wetness, self.refraction
         )
     }
 }
 
+#[doc(hidden)]
+/// [`Raindrops`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct RaindropsUniform {
+    pub(crate) speed: f32,
+    pub(crate) warping: f32,
+    pub(crate) zoom: f32,
+    pub(crate) wetness: f32,
+    pub(crate) scroll: Vec2,
+    pub(crate) flat: u32,
+    pub(crate) parallax: f32,
+}
+
 impl ExtractComponent for Raindrops {
-    type Query = (&'static Self, &'static Camera);
+    type Query = (
+        &'static Self,
+        &'static Camera,
+        &'static GlobalTransform,
+        Option<&'static OrthographicProjection>,
+    );
     type Filter = ();
-    type Out = Self;
+    type Out = RaindropsUniform;
+
+    fn extract_component(
+        (settings, camera, transform, projection): QueryItem<'_, Self::Query>,
+    ) -> Option<Self::Out> {
+        let _span = info_span!("vfx::raindrops::extract").entered();
 
-    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
         if !camera.is_active {
             return None;
         }
 
-        Some(*settings)
+        let (flat, parallax, scroll) = settings.refraction.uniform(transform, projection);
+
+        Some(RaindropsUniform {
+            speed: settings.speed,
+            warping: settings.warping,
+            zoom: settings.zoom,
+            wetness: settings.wetness,
+            scroll,
+            flat,
+            parallax,
+        })
     }
 }
@@ -0,0 +1,217 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_phase::RenderPhase,
+        render_resource::{
+            CachedPipelineState, CachedRenderPipelineId, PipelineCache, PipelineCacheError,
+        },
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+    utils::{HashMap, HashSet},
+};
+
+use super::{formats::PipelineFormats, PostProcessingPhaseItem};
+
+/// Sent when the effects on a camera changed, and all of their pipelines have finished compiling.
+///
+/// From then on the effects actually render, so this is the moment to reveal something which
+/// depends on them (e.g. a glitch during a boss intro), instead of showing an unprocessed frame.
+///
+/// Effects which are used for the first time may take a while to compile.
+/// See also [`super::precompile::PrecompileEffects`] for compiling them ahead of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct EffectsReady {
+    /// The camera with the effects.
+    pub camera: Entity,
+}
+
+/// Sent when the pipeline of an effect failed to compile, e.g. because of an error in a shader,
+/// or a feature the platform doesn't support.
+///
+/// The effect is skipped on the camera, and the rest of the effects still render.
+/// Bevy also logs the error.
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub struct EffectFailed {
+    /// The camera with the effect.
+    pub camera: Entity,
+
+    /// The name of the effect, e.g. `"Fog"`.
+    pub effect: String,
+
+    /// What went wrong.
+    pub error: String,
+}
+
+/// Whether the effects on each camera will render.
+///
+/// Also see [`EffectsReady`], which is sent when this changes.
+#[derive(Debug, Default, Resource)]
+pub struct EffectReadiness {
+    /// The pipelines of each camera which were ready, as of the last [`EffectsReady`].
+    reported: HashMap<Entity, HashSet<CachedRenderPipelineId>>,
+
+    /// Cameras whose pipelines are all ready, as of the last rendered frame.
+    ready: HashSet<Entity>,
+
+    /// The effects which failed on each camera, as of the last [`EffectFailed`].
+    failed: HashSet<(Entity, String)>,
+}
+
+impl EffectReadiness {
+    /// True if all the effects on the camera had their pipelines compiled, as of the last rendered frame.
+    ///
+    /// An effect which was just added takes a frame to reach the renderer,
+    /// so prefer waiting for [`EffectsReady`] after adding effects.
+    pub fn is_ready(&self, camera: Entity) -> bool {
+        self.ready.contains(&camera)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ViewPipelines {
+    pipelines: HashSet<CachedRenderPipelineId>,
+    ready: bool,
+
+    /// The effects whose pipelines failed to compile, and the errors.
+    failed: Vec<(String, String)>,
+}
+
+/// Pipeline states of the post processing cameras, shared between the render world and the main world.
+#[derive(Debug, Default, Clone, Resource)]
+struct SharedReadiness(Arc<Mutex<HashMap<Entity, ViewPipelines>>>);
+
+/// The name of the effect a pipeline belongs to, from the pipeline label, e.g. `"Fog Render Pipeline"`.
+fn effect_name(pipeline_cache: &PipelineCache, id: CachedRenderPipelineId) -> String {
+    let label = pipeline_cache
+        .get_render_pipeline_descriptor(id)
+        .label
+        .as_deref()
+        .unwrap_or("Unknown");
+
+    label
+        .strip_suffix(" Render Pipeline")
+        .unwrap_or(label)
+        .to_string()
+}
+
+fn check_readiness(
+    shared: Res<SharedReadiness>,
+    pipeline_cache: Res<PipelineCache>,
+    formats: Res<PipelineFormats>,
+    views: Query<(Entity, &ViewTarget, &RenderPhase<PostProcessingPhaseItem>)>,
+) {
+    let mut shared = shared.0.lock().expect("Readiness should not be poisoned");
+    shared.clear();
+
+    for (entity, view_target, phase) in &views {
+        // The effects are drawn with the variants of their pipelines for the format of the camera,
+        // which are made a little later.
+        let format = view_target.main_texture_format();
+        let drawn: Vec<Option<CachedRenderPipelineId>> = phase
+            .items
+            .iter()
+            .map(|item| formats.pipeline(item.pipeline_id, format))
+            .collect();
+        let pipelines: HashSet<CachedRenderPipelineId> = drawn.iter().flatten().copied().collect();
+
+        // A pipeline which failed to compile never renders, so it is never ready.
+        let ready = drawn.iter().all(|id| {
+            id.is_some_and(|id| {
+                matches!(
+                    super::pipeline_state(&pipeline_cache, id),
+                    Some(CachedPipelineState::Ok(_))
+                )
+            })
+        });
+
+        let failed = pipelines
+            .iter()
+            .filter_map(|id| match super::pipeline_state(&pipeline_cache, *id)? {
+                // Bevy retries these, so they are not failures (yet).
+                CachedPipelineState::Err(
+                    PipelineCacheError::ShaderNotLoaded(_)
+                    | PipelineCacheError::ShaderImportNotYetAvailable,
+                ) => None,
+                CachedPipelineState::Err(error) => {
+                    Some((effect_name(&pipeline_cache, *id), error.to_string()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        shared.insert(
+            entity,
+            ViewPipelines {
+                pipelines,
+                ready,
+                failed,
+            },
+        );
+    }
+}
+
+fn send_ready_events(
+    shared: Res<SharedReadiness>,
+    mut readiness: ResMut<EffectReadiness>,
+    mut events: EventWriter<EffectsReady>,
+    mut failed_events: EventWriter<EffectFailed>,
+) {
+    let shared = shared.0.lock().expect("Readiness should not be poisoned");
+    let readiness = &mut *readiness;
+
+    readiness.ready.clear();
+    readiness
+        .reported
+        .retain(|camera, _| shared.contains_key(camera));
+
+    readiness
+        .failed
+        .retain(|(camera, _)| shared.contains_key(camera));
+
+    for (camera, view) in shared.iter() {
+        for (effect, error) in &view.failed {
+            // Only report each failure once, instead of every frame.
+            if readiness.failed.insert((*camera, effect.clone())) {
+                failed_events.send(EffectFailed {
+                    camera: *camera,
+                    effect: effect.clone(),
+                    error: error.clone(),
+                });
+            }
+        }
+
+        if !view.ready {
+            continue;
+        }
+
+        readiness.ready.insert(*camera);
+
+        // Cameras start out without effects, so they don't get an event until some are added.
+        let reported = readiness.reported.entry(*camera).or_default();
+        if *reported != view.pipelines {
+            *reported = view.pipelines.clone();
+            events.send(EffectsReady { camera: *camera });
+        }
+    }
+}
+
+/// Reports when the pipelines of the effects on each camera are ready, or failed.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedReadiness::default();
+
+        app.add_event::<EffectsReady>()
+            .add_event::<EffectFailed>()
+            .init_resource::<EffectReadiness>()
+            .insert_resource(shared.clone())
+            .add_systems(First, send_ready_events);
+
+        super::render_app(app)
+            .insert_resource(shared)
+            .add_systems(Render, check_readiness.in_set(RenderSet::Queue));
+    }
+}
@@ -0,0 +1,473 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        render_phase::RenderPhase,
+        render_resource::{
+            encase, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType,
+            BufferInitDescriptor, BufferUsages, CachedRenderPipelineId, Extent3d, FilterMode,
+            PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+            TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+            TextureView, TextureViewDimension,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::{PostProcessingPhaseItem, PostProcessingSharedBindGroups, SharedBindGroupKey};
+
+pub(crate) const UPSAMPLE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 11762960395166431803);
+
+/// How much smaller than the camera an effect is drawn.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResolutionScale {
+    /// The effect is drawn at the resolution of the camera.
+    #[default]
+    Full,
+
+    /// The effect is drawn at half the width and height, so a quarter of the pixels.
+    Half,
+
+    /// The effect is drawn at a quarter of the width and height, so a sixteenth of the pixels.
+    Quarter,
+}
+
+impl ResolutionScale {
+    /// How many times smaller the width and height are.
+    fn divisor(self) -> u32 {
+        match self {
+            ResolutionScale::Full => 1,
+            ResolutionScale::Half => 2,
+            ResolutionScale::Quarter => 4,
+        }
+    }
+}
+
+/// How the output of an effect drawn at a lower resolution is scaled back up.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Upsampling {
+    /// Blends the four nearest pixels. Cheap, but a little blurry.
+    #[default]
+    Bilinear,
+
+    /// Blends the sixteen nearest pixels along smooth curves, which keeps edges sharper.
+    Bicubic,
+}
+
+impl Upsampling {
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            Upsampling::Bilinear => vec!["BILINEAR".into()],
+            Upsampling::Bicubic => vec!["BICUBIC".into()],
+        }
+    }
+}
+
+/// Draw the effect `C` at a lower resolution, and scale its output back up.
+///
+/// Heavy effects such as [`super::blur::Blur`], [`super::depth_of_field::DepthOfField`] or
+/// [`super::kuwahara::Kuwahara`] cost about as much as the pixels they draw, so half resolution
+/// makes them around four times cheaper. They are blurry or soft already, so the loss of detail
+/// is hard to see.
+/// Like [`super::Order`], this is per-camera and uses a generic marker to know which effect
+/// it applies to:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{blur::Blur, resolution::{Resolution, ResolutionScale, Upsampling}};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     Blur::default(),
+///     Resolution::<Blur>::new(ResolutionScale::Half, Upsampling::Bilinear),
+/// ));
+/// ```
+///
+/// The effect still reads the image, and the depth and normal prepasses, at full resolution.
+/// Patterns tied to pixels, such as the dots of [`super::dither::Dither`], get coarser.
+/// Effects made up of several passes are scaled back up after each pass.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct Resolution<C> {
+    /// How much smaller the effect is drawn.
+    pub scale: ResolutionScale,
+
+    /// How the output is scaled back up.
+    pub upsampling: Upsampling,
+
+    #[reflect(ignore)]
+    marker: PhantomData<C>,
+}
+
+impl<C> Resolution<C> {
+    /// Create a new resolution.
+    pub fn new(scale: ResolutionScale, upsampling: Upsampling) -> Self {
+        Self {
+            scale,
+            upsampling,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for Resolution<C> {
+    fn default() -> Self {
+        Self::new(ResolutionScale::Full, Upsampling::Bilinear)
+    }
+}
+
+impl<C> Clone for Resolution<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Resolution<C> {}
+
+impl<C> Display for Resolution<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Resolution {:?}, upsampling: {:?}",
+            self.scale, self.upsampling
+        )
+    }
+}
+
+/// A [`Resolution`] without its effect, as carried by phase items.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct EffectResolution {
+    scale: ResolutionScale,
+    upsampling: Upsampling,
+}
+
+impl EffectResolution {
+    /// True if the effect is drawn at the resolution of the camera.
+    pub(crate) fn is_full(&self) -> bool {
+        self.scale == ResolutionScale::Full
+    }
+}
+
+impl<C> From<Resolution<C>> for EffectResolution {
+    fn from(resolution: Resolution<C>) -> Self {
+        Self {
+            scale: resolution.scale,
+            upsampling: resolution.upsampling,
+        }
+    }
+}
+
+#[derive(Debug, ShaderType, Clone, Copy)]
+struct UpsampleUniform {
+    divisor: f32,
+}
+
+#[derive(Resource)]
+pub(crate) struct UpsampleData {
+    layout: BindGroupLayout,
+    shared_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for UpsampleData {
+    fn from_world(world: &mut World) -> Self {
+        let layout = super::create_layout(
+            world,
+            "Upsample",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(UpsampleUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The output of the effect, at the lower resolution.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Upsample sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        UpsampleData {
+            layout,
+            shared_layout,
+            sampler,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for UpsampleData {
+    /// The variant, and the format of the camera it draws into.
+    type Key = (Upsampling, TextureFormat);
+
+    fn specialize(&self, (key, format): Self::Key) -> RenderPipelineDescriptor {
+        let descriptor = super::render_pipeline_descriptor(
+            "Upsample",
+            &self.shared_layout,
+            &self.layout,
+            UPSAMPLE_SHADER_HANDLE.typed(),
+            key.shader_defs(),
+        );
+
+        super::with_target_format(descriptor, format)
+    }
+}
+
+/// Where effects drawn at one lower resolution are drawn, and how they are scaled back up.
+pub(crate) struct ScaledTarget {
+    resolution: EffectResolution,
+    texture: CachedTexture,
+    pipeline_id: CachedRenderPipelineId,
+    bind_group: BindGroup,
+}
+
+impl ScaledTarget {
+    /// The texture to draw the effect into.
+    pub(crate) fn view(&self) -> &TextureView {
+        &self.texture.default_view
+    }
+
+    /// How many times smaller the texture is than the camera's target.
+    pub(crate) fn divisor(&self) -> u32 {
+        self.resolution.scale.divisor()
+    }
+
+    /// Scales the output of the effect back up into the destination.
+    pub(crate) fn upsample(
+        &self,
+        render_context: &mut RenderContext,
+        pipeline_cache: &PipelineCache,
+        shared_bind_groups: &PostProcessingSharedBindGroups,
+        shared_key: SharedBindGroupKey,
+        camera: &ExtractedCamera,
+        destination: &TextureView,
+    ) {
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(self.pipeline_id) else {
+            return;
+        };
+
+        let Some(shared_bind_group) = shared_bind_groups
+            .cached_texture_bind_groups
+            .get(&shared_key)
+        else {
+            return;
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Upsample"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: destination,
+                resolve_target: None,
+                ops: super::viewport::operations(camera),
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        super::viewport::clip(&mut render_pass, camera, 1);
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, shared_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// The lower resolution targets of a view, in the render world.
+#[derive(Component)]
+pub(crate) struct ViewResolutions {
+    targets: Vec<ScaledTarget>,
+}
+
+impl ViewResolutions {
+    /// The target to draw an effect at the given resolution into.
+    ///
+    /// `None` if the effect is drawn at full resolution, or the upsample pipeline isn't ready yet,
+    /// in which case the effect is drawn at full resolution.
+    pub(crate) fn target(
+        &self,
+        resolution: EffectResolution,
+        pipeline_cache: &PipelineCache,
+    ) -> Option<&ScaledTarget> {
+        self.targets
+            .iter()
+            .find(|target| target.resolution == resolution)
+            .filter(|target| {
+                pipeline_cache
+                    .get_render_pipeline(target.pipeline_id)
+                    .is_some()
+            })
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<UpsampleData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UpsampleData>>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ViewTarget,
+        &RenderPhase<PostProcessingPhaseItem>,
+    )>,
+) {
+    let _span = info_span!("vfx::resolution::queue").entered();
+
+    for (entity, camera, view_target, phase) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let mut resolutions: Vec<EffectResolution> = vec![];
+        for item in &phase.items {
+            if !item.resolution.is_full() && !resolutions.contains(&item.resolution) {
+                resolutions.push(item.resolution);
+            }
+        }
+
+        if resolutions.is_empty() {
+            continue;
+        }
+
+        let targets = resolutions
+            .into_iter()
+            .map(|resolution| {
+                let divisor = resolution.scale.divisor();
+
+                let texture = texture_cache.get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some("Scaled effect"),
+                        size: Extent3d {
+                            width: size.x.div_ceil(divisor).max(1),
+                            height: size.y.div_ceil(divisor).max(1),
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: TextureDimension::D2,
+                        format: view_target.main_texture_format(),
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                        view_formats: &[],
+                    },
+                );
+
+                let mut uniform = encase::UniformBuffer::new(vec![]);
+                uniform
+                    .write(&UpsampleUniform {
+                        divisor: divisor as f32,
+                    })
+                    .expect("Upsample uniform should be writable");
+
+                let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("Upsample Uniform Buffer"),
+                    contents: &uniform.into_inner(),
+                    usage: BufferUsages::UNIFORM,
+                });
+
+                let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("Upsample Uniform Bind Group"),
+                    layout: &data.layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: buffer.as_entire_binding(),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::TextureView(&texture.default_view),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: BindingResource::Sampler(&data.sampler),
+                        },
+                    ],
+                });
+
+                ScaledTarget {
+                    resolution,
+                    texture,
+                    pipeline_id: pipelines.specialize(
+                        &pipeline_cache,
+                        &data,
+                        (resolution.upsampling, view_target.main_texture_format()),
+                    ),
+                    bind_group,
+                }
+            })
+            .collect();
+
+        commands.entity(entity).insert(ViewResolutions { targets });
+    }
+}
+
+/// Draws effects with a [`Resolution`] at a lower resolution.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            UPSAMPLE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "upsample.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ResolutionScale>()
+            .register_type::<Upsampling>();
+
+        super::render_app(app)
+            .init_resource::<UpsampleData>()
+            .init_resource::<SpecializedRenderPipelines<UpsampleData>>()
+            .add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
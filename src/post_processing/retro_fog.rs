@@ -0,0 +1,257 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingEffect, Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const RETRO_FOG_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4062046109791246194);
+
+#[derive(Resource)]
+pub(crate) struct RetroFogData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for RetroFogData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Retro Fog",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(RetroFogUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            RETRO_FOG_SHADER_HANDLE.typed(),
+        );
+
+        RetroFogData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            RETRO_FOG_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "retro_fog.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<RetroFog>();
+        super::register_effect_types::<RetroFog>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<RetroFog>::default(),
+            UniformComponentPlugin::<RetroFogUniform>::default(),
+        ))
+        .add_systems(PostUpdate, super::prepass::configure_prepasses::<RetroFog>);
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<RetroFog>,
+            )
+            .init_resource::<RetroFogData>()
+            .init_resource::<UniformBindGroup<RetroFogUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<RetroFogUniform>>(
+            );
+    }
+}
+
+impl EffectPrepasses for RetroFog {
+    const WITHOUT: &'static str = "the fog will not look right";
+
+    fn prepasses(&self) -> Prepasses {
+        Prepasses::DEPTH
+    }
+}
+
+fn prepare(
+    data: Res<RetroFogData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<RetroFog>,
+        &Blend<RetroFog>,
+        &Resolution<RetroFog>,
+        &SourceSampler<RetroFog>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::retro_fog::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<RetroFogUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<RetroFogData>,
+    mut bind_group: ResMut<UniformBindGroup<RetroFogUniform>>,
+    uniforms: Res<ComponentUniforms<RetroFogUniform>>,
+    views: Query<Entity, With<RetroFogUniform>>,
+) {
+    let _span = info_span!("vfx::retro_fog::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Retro Fog Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Distance fog in a few flat bands, like the fog of 3D games on early consoles.
+///
+/// The fog thickens linearly from [`RetroFog::start`] to [`RetroFog::end`], but instead of
+/// a smooth gradient it steps through [`RetroFog::bands`] flat shades, optionally dithered
+/// where they meet. Pairs well with [`super::pixelate::Pixelate`] and [`super::dither::Dither`]
+/// for a retro look.
+///
+/// The distance is read from the depth prepass, so the camera needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) component, which is added along with the fog
+/// (see [`super::prepass::AutoPrepass`]). MSAA must also be off (`Msaa::Off`),
+/// since a multisampled depth texture can't be read by post processing effects.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct RetroFog {
+    /// The color of the fog.
+    /// The alpha channel controls how opaque the fog is at its thickest.
+    pub color: Color,
+
+    /// The distance (in world units) from the camera where the fog starts.
+    pub start: f32,
+
+    /// The distance (in world units) from the camera where the fog is at its thickest.
+    pub end: f32,
+
+    /// How many steps the fog takes from clear to thickest.
+    /// `0` gives a smooth gradient instead.
+    pub bands: u32,
+
+    /// Whether to dither where the bands meet, which softens the steps between them.
+    pub dither: bool,
+}
+
+impl Default for RetroFog {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.55, 0.6, 0.7),
+            start: 5.0,
+            end: 30.0,
+            bands: 4,
+            dither: true,
+        }
+    }
+}
+
+impl Display for RetroFog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Retro Fog start: {}, end: {}, bands: {}, dither: {}",
+            self.start, self.end, self.bands, self.dither
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`RetroFog`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct RetroFogUniform {
+    pub(crate) color: Vec4,
+    pub(crate) start: f32,
+    pub(crate) end: f32,
+    pub(crate) bands: u32,
+    pub(crate) dither: u32,
+
+    /// Used to turn depth values into view space distances.
+    pub(crate) inverse_projection: Mat4,
+}
+
+impl ExtractComponent for RetroFog {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = RetroFogUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::retro_fog::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(RetroFogUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            start: settings.start,
+            end: settings.end,
+            bands: settings.bands,
+            dither: settings.dither.into(),
+            inverse_projection: camera.projection_matrix().inverse(),
+        })
+    }
+}
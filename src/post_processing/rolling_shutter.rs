@@ -0,0 +1,322 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, CachedRenderPipelineId, FilterMode,
+            Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages, ShaderType,
+            TextureSampleType, TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    history::{FrameHistories, ViewHistory},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingViewEffect, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const ROLLING_SHUTTER_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8002673274098683320);
+
+#[derive(Resource)]
+pub(crate) struct RollingShutterData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for RollingShutterData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Rolling Shutter",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(RollingShutterUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The input of the effect as of the previous frame.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            ROLLING_SHUTTER_SHADER_HANDLE.typed(),
+        );
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Rolling Shutter history sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        RollingShutterData {
+            pipeline_id,
+            layout,
+            sampler,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            ROLLING_SHUTTER_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "rolling_shutter.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<RollingShutter>();
+        super::register_effect_types::<RollingShutter>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<RollingShutter>::default(),
+            UniformComponentPlugin::<RollingShutterUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<RollingShutter>,
+            )
+            .init_resource::<RollingShutterData>()
+            // The uniforms are written to the GPU during prepare, so the history must be in by then.
+            .add_systems(
+                Render,
+                apply_view_history
+                    .after(RenderSet::ExtractCommands)
+                    .before(RenderSet::Prepare),
+            )
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<RollingShutterUniform>>();
+    }
+}
+
+fn apply_view_history(mut views: Query<(&ViewHistory, &mut RollingShutterUniform)>) {
+    for (history, mut uniform) in &mut views {
+        uniform.inverse_view_projection = history.view_projection.inverse();
+        uniform.previous_view_projection = history.previous_view_projection;
+    }
+}
+
+fn prepare(
+    data: Res<RollingShutterData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<RollingShutter>,
+        &Blend<RollingShutter>,
+        &Resolution<RollingShutter>,
+        &SourceSampler<RollingShutter>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::rolling_shutter::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<RollingShutterUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<RollingShutterData>,
+    uniforms: Res<ComponentUniforms<RollingShutterUniform>>,
+    mut histories: ResMut<FrameHistories>,
+    views: Query<(Entity, &ExtractedCamera, &ViewTarget), With<RollingShutterUniform>>,
+) {
+    let _span = info_span!("vfx::rolling_shutter::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, camera, view_target) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        // The input is kept, since sampling the skewed output again would skew it further each frame.
+        let history = histories
+            .request(
+                &render_device,
+                entity,
+                size,
+                view_target.main_texture_format(),
+                data.pipeline_id,
+            )
+            .of_input();
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Rolling Shutter Uniform Bind Group"),
+            layout: &data.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&history.view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&data.sampler),
+                },
+            ],
+        });
+
+        commands.entity(entity).insert((
+            ViewUniformBindGroup::<RollingShutterUniform>::new(bind_group),
+            history,
+        ));
+    }
+}
+
+/// Skews and wobbles the image while the camera turns, like the rolling shutter of a CMOS camera.
+///
+/// Such cameras read the image out from top to bottom, so each row is seen at a slightly
+/// different time. This samples each row from the time it was read out, between the previous
+/// and the current frame, shifted along the rotation of the camera since the previous frame.
+/// Fast pans lean vertical lines, and shaky cameras make the image wobble like jelly.
+///
+/// Only the rotation of the camera is measured, so moving the camera without turning it
+/// leaves the image unchanged, and things moving in view are partly mixed with where they were
+/// in the previous frame rather than skewed.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct RollingShutter {
+    /// How long it takes to read out the image from top to bottom, as a fraction of a frame
+    /// between `0.0` (a global shutter, no skew) and `1.0`.
+    pub readout: f32,
+
+    /// How many bands the image is read out in. Each band is seen at a single time,
+    /// which gives a stepped look. `0` reads out each row on its own.
+    pub bands: u32,
+
+    /// Exaggerates the skew, where `1.0` is as strong as a real camera with the same readout.
+    pub skew: f32,
+
+    /// The largest skew, in UV space.
+    /// Keeps sudden jumps such as camera cuts from tearing the whole view.
+    pub max_skew: f32,
+}
+
+impl Default for RollingShutter {
+    fn default() -> Self {
+        Self {
+            readout: 0.5,
+            bands: 0,
+            skew: 1.0,
+            max_skew: 0.1,
+        }
+    }
+}
+
+impl Display for RollingShutter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rolling Shutter readout: {:.2}, bands: {}, skew: {:.2}, max skew: {:.3}",
+            self.readout, self.bands, self.skew, self.max_skew
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`RollingShutter`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct RollingShutterUniform {
+    /// Set from the [`ViewHistory`] of the view.
+    pub(crate) inverse_view_projection: Mat4,
+    pub(crate) previous_view_projection: Mat4,
+
+    pub(crate) readout: f32,
+    pub(crate) skew: f32,
+    pub(crate) max_skew: f32,
+    pub(crate) bands: u32,
+}
+
+impl ExtractComponent for RollingShutter {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = RollingShutterUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::rolling_shutter::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(RollingShutterUniform {
+            // No rotation until the history is applied.
+            inverse_view_projection: Mat4::IDENTITY,
+            previous_view_projection: Mat4::IDENTITY,
+            readout: settings.readout.clamp(0.0, 1.0),
+            skew: settings.skew,
+            max_skew: settings.max_skew,
+            bands: settings.bands,
+        })
+    }
+}
@@ -0,0 +1,199 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{AddressMode, FilterMode, Sampler, SamplerDescriptor},
+        renderer::RenderDevice,
+    },
+    utils::HashMap,
+};
+
+/// How an effect blends the pixels of the image around the point it reads.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum SamplerFilter {
+    /// Blends the four nearest pixels, so distortions stay smooth.
+    #[default]
+    Linear,
+
+    /// Takes the nearest pixel as it is, so pixel art stays crisp when it is distorted.
+    Nearest,
+}
+
+impl From<SamplerFilter> for FilterMode {
+    fn from(filter: SamplerFilter) -> Self {
+        match filter {
+            SamplerFilter::Linear => FilterMode::Linear,
+            SamplerFilter::Nearest => FilterMode::Nearest,
+        }
+    }
+}
+
+/// What an effect reads when it reads past the edge of the image.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum SamplerAddress {
+    /// The pixels at the edge, stretched outwards.
+    #[default]
+    ClampToEdge,
+
+    /// The image again from the other side, so the screen tiles.
+    Repeat,
+
+    /// The image again, mirrored at the edge.
+    MirrorRepeat,
+}
+
+impl From<SamplerAddress> for AddressMode {
+    fn from(address: SamplerAddress) -> Self {
+        match address {
+            SamplerAddress::ClampToEdge => AddressMode::ClampToEdge,
+            SamplerAddress::Repeat => AddressMode::Repeat,
+            SamplerAddress::MirrorRepeat => AddressMode::MirrorRepeat,
+        }
+    }
+}
+
+/// How the effect `C` samples the image it reads.
+///
+/// By default effects blend neighboring pixels and clamp at the edges of the image.
+/// Pixel art games want [`SamplerFilter::Nearest`], so distortions such as
+/// [`super::wave::Wave`] move whole pixels around instead of smearing them,
+/// and effects shifting the image around, such as [`super::line_jitter::LineJitter`],
+/// can wrap it around the screen with [`SamplerAddress::Repeat`].
+/// Like [`super::Order`], this is per-camera and uses a generic marker to know which effect
+/// it applies to:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{wave::Wave, sampler::{SourceSampler, SamplerAddress, SamplerFilter}};
+/// # let mut commands: Commands = panic!();
+/// commands.spawn((
+///     Camera2dBundle::default(),
+///     Wave::default(),
+///     SourceSampler::<Wave>::new(SamplerFilter::Nearest, SamplerAddress::Repeat),
+/// ));
+/// ```
+///
+/// Only the image is sampled this way, not the textures an effect brings along, such as the
+/// lookup table of a [`super::lut::Lut`].
+/// Effects which run as compute shaders where supported, such as [`super::blur::Blur`],
+/// are drawn as fragment shaders instead while they sample the image differently.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct SourceSampler<C> {
+    /// How the pixels around the point read are blended.
+    pub filter: SamplerFilter,
+
+    /// What is read past the edge of the image.
+    pub address: SamplerAddress,
+
+    #[reflect(ignore)]
+    marker: PhantomData<C>,
+}
+
+impl<C> SourceSampler<C> {
+    /// Create a new sampler.
+    pub fn new(filter: SamplerFilter, address: SamplerAddress) -> Self {
+        Self {
+            filter,
+            address,
+            marker: PhantomData,
+        }
+    }
+
+    /// Nearest pixels, clamped at the edges.
+    pub fn nearest() -> Self {
+        Self::new(SamplerFilter::Nearest, SamplerAddress::ClampToEdge)
+    }
+
+    /// Blended pixels, with the image repeating past the edges.
+    pub fn repeat() -> Self {
+        Self::new(SamplerFilter::Linear, SamplerAddress::Repeat)
+    }
+}
+
+impl<C> Default for SourceSampler<C> {
+    fn default() -> Self {
+        Self::new(SamplerFilter::Linear, SamplerAddress::ClampToEdge)
+    }
+}
+
+impl<C> Clone for SourceSampler<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for SourceSampler<C> {}
+
+impl<C> Display for SourceSampler<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Source sampler filter: {:?}, address: {:?}",
+            self.filter, self.address
+        )
+    }
+}
+
+/// A [`SourceSampler`] without its effect, as carried by phase items.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct EffectSampler {
+    filter: SamplerFilter,
+    address: SamplerAddress,
+}
+
+impl EffectSampler {
+    /// True if the effect samples the image as effects do by default.
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl<C> From<SourceSampler<C>> for EffectSampler {
+    fn from(sampler: SourceSampler<C>) -> Self {
+        Self {
+            filter: sampler.filter,
+            address: sampler.address,
+        }
+    }
+}
+
+/// The samplers for the image of the shared bind groups, one for each [`EffectSampler`] in use.
+#[derive(Resource, Default)]
+pub(crate) struct EffectSamplers {
+    samplers: HashMap<EffectSampler, Sampler>,
+}
+
+impl EffectSamplers {
+    /// The sampler for the given settings, created the first time they are used.
+    pub(crate) fn get(&mut self, render_device: &RenderDevice, key: EffectSampler) -> &Sampler {
+        self.samplers.entry(key).or_insert_with(|| {
+            let filter = key.filter.into();
+            let address = key.address.into();
+
+            render_device.create_sampler(&SamplerDescriptor {
+                label: Some("PostProcessing texture sampler"),
+                address_mode_u: address,
+                address_mode_v: address,
+                address_mode_w: address,
+                mag_filter: filter,
+                min_filter: filter,
+                mipmap_filter: filter,
+                ..default()
+            })
+        })
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SamplerFilter>()
+            .register_type::<SamplerAddress>();
+
+        super::render_app(app).init_resource::<EffectSamplers>();
+    }
+}
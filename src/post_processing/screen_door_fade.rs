@@ -0,0 +1,279 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+use crate::animation::Animate;
+
+pub(crate) const SCREEN_DOOR_FADE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5741093268415530217);
+
+#[derive(Resource)]
+pub(crate) struct ScreenDoorFadeData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for ScreenDoorFadeData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Screen Door Fade",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ScreenDoorFadeUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            SCREEN_DOOR_FADE_SHADER_HANDLE.typed(),
+        );
+
+        ScreenDoorFadeData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SCREEN_DOOR_FADE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "screen_door_fade.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ScreenDoorFade>();
+        super::register_effect_types::<ScreenDoorFade>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<ScreenDoorFade>::default(),
+            UniformComponentPlugin::<ScreenDoorFadeUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<ScreenDoorFade>,
+            )
+            .init_resource::<ScreenDoorFadeData>()
+            .init_resource::<UniformBindGroup<ScreenDoorFadeUniform>>()
+            .add_systems(
+                Render,
+                prepare
+                    .in_set(RenderSet::Prepare)
+                    // Fades the covered screen too, but not the bars framing it.
+                    .after(super::screen_transition::prepare)
+                    .before(super::letterbox::prepare),
+            )
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<ScreenDoorFadeUniform>>();
+    }
+}
+
+pub(crate) fn prepare(
+    data: Res<ScreenDoorFadeData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<ScreenDoorFade>,
+        &Blend<ScreenDoorFade>,
+        &Resolution<ScreenDoorFade>,
+        &SourceSampler<ScreenDoorFade>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::screen_door_fade::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<ScreenDoorFadeUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<ScreenDoorFadeData>,
+    mut bind_group: ResMut<UniformBindGroup<ScreenDoorFadeUniform>>,
+    uniforms: Res<ComponentUniforms<ScreenDoorFadeUniform>>,
+    views: Query<Entity, With<ScreenDoorFadeUniform>>,
+) {
+    let _span = info_span!("vfx::screen_door_fade::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Screen Door Fade Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Fades the whole view to a color through an ordered dither pattern, pixel by pixel,
+/// instead of blending it, e.g. for pixel art games where a smooth fade looks out of place.
+///
+/// Each cell of the pattern is either the picture or the color, so the picture keeps its hard
+/// edges and palette throughout the fade.
+/// Use [`ScreenDoorFade::fade_out`] and [`ScreenDoorFade::fade_in`] to fade over time:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::screen_door_fade::ScreenDoorFade;
+/// fn leave_room(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     commands
+///         .entity(camera.single())
+///         .insert(ScreenDoorFade::default().fade_out(0.5));
+/// }
+/// ```
+///
+/// Unless given an [`Order`], the fade is drawn after all other effects,
+/// except the bars of a [`super::letterbox::Letterbox`].
+///
+/// For smooth fades, irises and wipes, see [`super::screen_transition::ScreenTransition`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct ScreenDoorFade {
+    /// The color the view fades to.
+    /// The alpha channel controls how opaque the covered pixels are.
+    pub color: Color,
+
+    /// How far along the fade is, from `0.0` (the view is seen) to `1.0` (the view is covered).
+    pub progress: f32,
+
+    /// How many pixels wide and high each cell of the pattern is.
+    /// Match it to the size of a pixel of the art, e.g. `3` for art upscaled three times.
+    pub scale: u32,
+}
+
+impl ScreenDoorFade {
+    /// Fades the view out to the color, over the given seconds.
+    pub fn fade_out(self, seconds: f32) -> Animate<ScreenDoorFade> {
+        Animate::new(
+            Self {
+                progress: 0.0,
+                ..self
+            },
+            Self {
+                progress: 1.0,
+                ..self
+            },
+            seconds,
+        )
+    }
+
+    /// Fades the view back in from the color, over the given seconds.
+    /// The effect stays, with a [`ScreenDoorFade::progress`] of `0.0`.
+    pub fn fade_in(self, seconds: f32) -> Animate<ScreenDoorFade> {
+        Animate::new(
+            Self {
+                progress: 1.0,
+                ..self
+            },
+            Self {
+                progress: 0.0,
+                ..self
+            },
+            seconds,
+        )
+    }
+}
+
+impl Default for ScreenDoorFade {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            progress: 0.0,
+            scale: 1,
+        }
+    }
+}
+
+impl Display for ScreenDoorFade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Screen Door Fade progress: {:.2}, scale: {}",
+            self.progress, self.scale
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`ScreenDoorFade`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct ScreenDoorFadeUniform {
+    pub(crate) color: Vec4,
+    pub(crate) progress: f32,
+    pub(crate) scale: u32,
+}
+
+impl ExtractComponent for ScreenDoorFade {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = ScreenDoorFadeUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::screen_door_fade::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(ScreenDoorFadeUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            progress: settings.progress.clamp(0.0, 1.0),
+            scale: settings.scale.max(1),
+        })
+    }
+}
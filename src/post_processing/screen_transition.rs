@@ -0,0 +1,406 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+use crate::time::VfxTime;
+
+pub(crate) const SCREEN_TRANSITION_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9908450605687404472);
+
+#[derive(Resource)]
+pub(crate) struct ScreenTransitionData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for ScreenTransitionData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Screen Transition",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ScreenTransitionUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            SCREEN_TRANSITION_SHADER_HANDLE.typed(),
+        );
+
+        ScreenTransitionData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SCREEN_TRANSITION_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "screen_transition.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ScreenTransition>()
+            .register_type::<ScreenTransitionKind>()
+            .register_type::<ScreenTransitionTimer>();
+        super::register_effect_types::<ScreenTransition>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<ScreenTransition>::default(),
+            UniformComponentPlugin::<ScreenTransitionUniform>::default(),
+        ))
+        .add_event::<ScreenTransitionFinished>()
+        .add_systems(Update, advance);
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<ScreenTransition>,
+            )
+            .init_resource::<ScreenTransitionData>()
+            .init_resource::<UniformBindGroup<ScreenTransitionUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<ScreenTransitionUniform>>();
+    }
+}
+
+pub(crate) fn prepare(
+    data: Res<ScreenTransitionData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<ScreenTransition>,
+        &Blend<ScreenTransition>,
+        &Resolution<ScreenTransition>,
+        &SourceSampler<ScreenTransition>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::screen_transition::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<ScreenTransitionUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<ScreenTransitionData>,
+    mut bind_group: ResMut<UniformBindGroup<ScreenTransitionUniform>>,
+    uniforms: Res<ComponentUniforms<ScreenTransitionUniform>>,
+    views: Query<Entity, With<ScreenTransitionUniform>>,
+) {
+    let _span = info_span!("vfx::screen_transition::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Screen Transition Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// How a [`ScreenTransition`] covers the screen.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScreenTransitionKind {
+    /// The whole screen fades to the color at once.
+    Fade,
+
+    /// A circle closes in on a point of the screen, covering what is outside of it.
+    Iris {
+        /// The point the circle closes in on, in UV space (`(0, 0)` is the top left corner).
+        center: Vec2,
+    },
+
+    /// A straight edge sweeps across the screen, covering what is behind it.
+    Wipe {
+        /// The way the edge moves across the screen, e.g. [`Vec2::X`] wipes from left to right.
+        /// In UV space, so [`Vec2::Y`] wipes from the top down. Need not be normalized.
+        direction: Vec2,
+    },
+}
+
+/// Moves the [`ScreenTransition::progress`] along by itself.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScreenTransitionTimer {
+    /// Covers the screen, moving the progress up to `1.0`.
+    /// Going all the way from `0.0` takes the given seconds.
+    Cover(f32),
+
+    /// Reveals the screen, moving the progress down to `0.0`.
+    /// Going all the way from `1.0` takes the given seconds.
+    Reveal(f32),
+}
+
+/// Covers the screen with a color, e.g. for level transitions or to punctuate a cutscene.
+///
+/// How the screen is covered depends on the [`ScreenTransitionKind`]: a fade, a closing iris or a wipe.
+/// The game drives [`ScreenTransition::progress`], from `0.0` (the scene is seen) to `1.0`
+/// (the screen is covered), either by setting it directly or via a [`ScreenTransitionTimer`]:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::screen_transition::{ScreenTransition, ScreenTransitionFinished};
+/// fn leave_level(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     commands
+///         .entity(camera.single())
+///         .insert(ScreenTransition::iris(Vec2::splat(0.5)).covering(0.75));
+/// }
+///
+/// fn load_next_level(
+///     mut events: EventReader<ScreenTransitionFinished>,
+///     mut transitions: Query<&mut ScreenTransition>,
+/// ) {
+///     for event in events.iter().filter(|event| event.covered) {
+///         // Load the next level behind the covered screen, then open the iris again.
+///         if let Ok(mut transition) = transitions.get_mut(event.camera) {
+///             *transition = transition.revealing(0.75);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Opening the iris (or wiping the color away) is the same transition with the progress going
+/// back down, so an iris out and an iris in are covering and revealing.
+///
+/// Unless given an [`Order`], the transition is drawn after all other effects,
+/// except a [`super::screen_door_fade::ScreenDoorFade`] and the bars of a
+/// [`super::letterbox::Letterbox`], which frame the picture.
+///
+/// To cross-fade between sets of effects instead, see [`crate::transition::Transition`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct ScreenTransition {
+    /// How the screen is covered.
+    pub kind: ScreenTransitionKind,
+
+    /// The color the screen is covered with.
+    /// The alpha channel controls how opaque it is once the screen is covered.
+    pub color: Color,
+
+    /// How far along the transition is, from `0.0` (the scene is seen) to `1.0` (the screen is covered).
+    pub progress: f32,
+
+    /// How soft the edge of an iris or a wipe is, as a part of the whole transition.
+    /// `0.0` gives a hard edge. Has no effect on a fade.
+    pub softness: f32,
+
+    /// Moves the progress along by itself, following the [`VfxTime`] clock.
+    /// Set back to `None` once done, sending a [`ScreenTransitionFinished`].
+    pub timer: Option<ScreenTransitionTimer>,
+}
+
+impl ScreenTransition {
+    /// A fade to black, not started yet.
+    pub fn fade() -> Self {
+        Self::default()
+    }
+
+    /// An iris closing in on the given point, in UV space, not started yet.
+    pub fn iris(center: Vec2) -> Self {
+        Self {
+            kind: ScreenTransitionKind::Iris { center },
+            ..default()
+        }
+    }
+
+    /// A wipe moving the given way across the screen, in UV space, not started yet.
+    pub fn wipe(direction: Vec2) -> Self {
+        Self {
+            kind: ScreenTransitionKind::Wipe { direction },
+            ..default()
+        }
+    }
+
+    /// The same transition, covering the screen with the given color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The same transition, covering the screen over the given seconds from where it is now.
+    pub fn covering(mut self, seconds: f32) -> Self {
+        self.timer = Some(ScreenTransitionTimer::Cover(seconds));
+        self
+    }
+
+    /// The same transition, revealing the screen over the given seconds from where it is now.
+    pub fn revealing(mut self, seconds: f32) -> Self {
+        self.timer = Some(ScreenTransitionTimer::Reveal(seconds));
+        self
+    }
+}
+
+impl Default for ScreenTransition {
+    fn default() -> Self {
+        Self {
+            kind: ScreenTransitionKind::Fade,
+            color: Color::BLACK,
+            progress: 0.0,
+            softness: 0.05,
+            timer: None,
+        }
+    }
+}
+
+impl Display for ScreenTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Screen Transition {:?}, progress: {:.2}, softness: {:.2}",
+            self.kind, self.progress, self.softness
+        )
+    }
+}
+
+/// Sent when the [`ScreenTransitionTimer`] of a [`ScreenTransition`] is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ScreenTransitionFinished {
+    /// The camera with the transition.
+    pub camera: Entity,
+
+    /// Whether the screen was covered, rather than revealed.
+    pub covered: bool,
+}
+
+fn advance(
+    time: Res<VfxTime>,
+    mut events: EventWriter<ScreenTransitionFinished>,
+    mut query: Query<(Entity, &mut ScreenTransition)>,
+) {
+    for (entity, mut transition) in &mut query {
+        // Avoids change detection when the game drives the progress.
+        let Some(timer) = transition.timer else {
+            continue;
+        };
+
+        let (target, seconds) = match timer {
+            ScreenTransitionTimer::Cover(seconds) => (1.0, seconds),
+            ScreenTransitionTimer::Reveal(seconds) => (0.0, seconds),
+        };
+
+        let step = if seconds > 0.0 {
+            time.delta_seconds() / seconds
+        } else {
+            1.0
+        };
+
+        transition.progress = if transition.progress < target {
+            (transition.progress + step).min(target)
+        } else {
+            (transition.progress - step).max(target)
+        };
+
+        if transition.progress == target {
+            transition.timer = None;
+            events.send(ScreenTransitionFinished {
+                camera: entity,
+                covered: target == 1.0,
+            });
+        }
+    }
+}
+
+#[doc(hidden)]
+/// [`ScreenTransition`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct ScreenTransitionUniform {
+    pub(crate) color: Vec4,
+    pub(crate) center: Vec2,
+    /// Normalized.
+    pub(crate) direction: Vec2,
+    pub(crate) progress: f32,
+    pub(crate) softness: f32,
+    /// `0` for a fade, `1` for an iris and `2` for a wipe.
+    pub(crate) kind: u32,
+}
+
+impl ExtractComponent for ScreenTransition {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = ScreenTransitionUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::screen_transition::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        let (kind, center, direction) = match settings.kind {
+            ScreenTransitionKind::Fade => (0, Vec2::ZERO, Vec2::ZERO),
+            ScreenTransitionKind::Iris { center } => (1, center, Vec2::ZERO),
+            ScreenTransitionKind::Wipe { direction } => {
+                (2, Vec2::ZERO, direction.try_normalize().unwrap_or(Vec2::X))
+            }
+        };
+
+        Some(ScreenTransitionUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            center,
+            direction,
+            progress: settings.progress.clamp(0.0, 1.0),
+            softness: settings.softness.max(0.0),
+            kind,
+        })
+    }
+}
@@ -0,0 +1,348 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::core_3d,
+    ecs::query::QueryItem,
+    pbr::{DrawMesh, MeshPipeline, MeshPipelineKey, SetMeshBindGroup, SetMeshViewBindGroup},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        mesh::MeshVertexBufferLayout,
+        render_asset::RenderAssets,
+        render_graph::{NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner},
+        render_phase::{
+            sort_phase_system, AddRenderCommand, CachedRenderPipelinePhaseItem, DrawFunctionId,
+            DrawFunctions, PhaseItem, RenderPhase, SetItemPipeline,
+        },
+        render_resource::{
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, FragmentState, LoadOp,
+            MultisampleState, Operations, PipelineCache, RenderPassColorAttachment,
+            RenderPassDescriptor, RenderPipelineDescriptor, SpecializedMeshPipeline,
+            SpecializedMeshPipelineError, SpecializedMeshPipelines,
+        },
+        renderer::RenderContext,
+        view::{ExtractedView, VisibleEntities},
+        Extract, Render, RenderSet,
+    },
+};
+
+use super::{
+    effect_mask::{EffectMask, EffectMaskChannel, ViewEffectMask},
+    graph::node,
+};
+
+pub(crate) const SELECTIVE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4729212923053354777);
+
+/// Limits effects to this entity, e.g. for highlighting characters or x-ray vision.
+///
+/// The mesh of the entity is drawn into the [`EffectMask`] of each camera which sees it,
+/// in the given channel, so effects limited to that channel with
+/// [`super::blend::EffectScope::Mask`] only apply where the entity is:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{blend::{Blend, EffectScope}, effect_mask::{EffectMask, EffectMaskChannel}, glow::Glow, selective::SelectiveEffect};
+/// # let mut commands: Commands = panic!();
+/// # let player: Handle<Mesh> = panic!();
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     EffectMask,
+///     Glow::default(),
+///     Blend::<Glow>::default().with_scope(EffectScope::Mask(EffectMaskChannel::Red)),
+/// ));
+///
+/// commands.spawn((
+///     PbrBundle {
+///         mesh: player,
+///         ..default()
+///     },
+///     SelectiveEffect::default(),
+/// ));
+/// ```
+///
+/// Entities are drawn into the mask without testing against the depth of the scene,
+/// so they are marked even where something is in front of them, which is what x-ray vision needs.
+/// Use [`bevy::render::view::RenderLayers`] to pick which cameras see them as usual.
+///
+/// Skinned and morphed meshes are drawn in their current pose.
+/// Only 3d cameras draw entities into their mask. Needs the `selective` feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct SelectiveEffect {
+    /// The channel of the mask the entity is drawn into.
+    pub channel: EffectMaskChannel,
+}
+
+impl ExtractComponent for SelectiveEffect {
+    type Query = (&'static Self, &'static ComputedVisibility);
+    type Filter = With<Handle<Mesh>>;
+    type Out = Self;
+
+    fn extract_component((selective, visibility): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        if !visibility.is_visible() {
+            return None;
+        }
+
+        Some(*selective)
+    }
+}
+
+/// The pipeline drawing the meshes of [`SelectiveEffect`] entities into the mask.
+#[derive(Resource)]
+struct SelectivePipeline {
+    mesh_pipeline: MeshPipeline,
+}
+
+impl FromWorld for SelectivePipeline {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for SelectivePipeline {
+    type Key = (MeshPipelineKey, EffectMaskChannel);
+
+    fn specialize(
+        &self,
+        (key, channel): Self::Key,
+        layout: &MeshVertexBufferLayout,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        // Bevy's mesh pipeline places, skins and morphs the mesh, only the output differs.
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+
+        descriptor.label = Some("Selective Effect Render Pipeline".into());
+        descriptor.fragment = Some(FragmentState {
+            shader: SELECTIVE_SHADER_HANDLE.typed(),
+            shader_defs: vec![],
+            entry_point: "fragment".into(),
+            targets: vec![Some(ColorTargetState {
+                format: ViewEffectMask::FORMAT,
+                blend: None,
+                write_mask: write_mask(channel),
+            })],
+        });
+
+        // The mask has neither depth nor samples.
+        descriptor.depth_stencil = None;
+        descriptor.multisample = MultisampleState::default();
+
+        Ok(descriptor)
+    }
+}
+
+/// Only the channel of the entity is written, so entities in other channels are kept.
+fn write_mask(channel: EffectMaskChannel) -> ColorWrites {
+    match channel {
+        EffectMaskChannel::Red => ColorWrites::RED,
+        EffectMaskChannel::Green => ColorWrites::GREEN,
+        EffectMaskChannel::Blue => ColorWrites::BLUE,
+        EffectMaskChannel::Alpha => ColorWrites::ALPHA,
+    }
+}
+
+/// An entity drawn into the mask of a view.
+struct SelectivePhaseItem {
+    entity: Entity,
+    draw_function: DrawFunctionId,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+impl PhaseItem for SelectivePhaseItem {
+    // The entities only ever add to the mask, so the order doesn't matter.
+    type SortKey = Entity;
+
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        self.entity
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for SelectivePhaseItem {
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline_id
+    }
+}
+
+type DrawSelective = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    DrawMesh,
+);
+
+fn extract_phases(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera), (With<Camera3d>, With<EffectMask>)>>,
+) {
+    for (entity, camera) in &cameras {
+        if camera.is_active {
+            commands
+                .get_or_spawn(entity)
+                .insert(RenderPhase::<SelectivePhaseItem>::default());
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue(
+    draw_functions: Res<DrawFunctions<SelectivePhaseItem>>,
+    pipeline: Res<SelectivePipeline>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<SelectivePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    meshes: Res<RenderAssets<Mesh>>,
+    selective: Query<(&Handle<Mesh>, &SelectiveEffect)>,
+    mut views: Query<(&VisibleEntities, &mut RenderPhase<SelectivePhaseItem>), With<ExtractedView>>,
+) {
+    let _span = info_span!("vfx::selective::queue").entered();
+
+    let draw_function = draw_functions.read().id::<DrawSelective>();
+
+    // The view bind group of the mesh pipeline depends on the samples of the main pass.
+    let view_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+
+    for (visible_entities, mut phase) in &mut views {
+        for &entity in &visible_entities.entities {
+            let Ok((mesh, selective)) = selective.get(entity) else {
+                continue;
+            };
+            let Some(mesh) = meshes.get(mesh) else {
+                continue;
+            };
+
+            let mut key =
+                view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
+            if mesh.morph_targets.is_some() {
+                key |= MeshPipelineKey::MORPH_TARGETS;
+            }
+
+            let pipeline_id = match pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                (key, selective.channel),
+                &mesh.layout,
+            ) {
+                Ok(id) => id,
+                Err(err) => {
+                    error!("Selective effect mesh can't be drawn into the mask: {err}");
+                    continue;
+                }
+            };
+
+            phase.add(SelectivePhaseItem {
+                entity,
+                draw_function,
+                pipeline_id,
+            });
+        }
+    }
+}
+
+/// Draws the [`SelectiveEffect`] entities a view sees into its mask.
+#[derive(Default)]
+struct SelectiveNode;
+
+impl ViewNode for SelectiveNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static RenderPhase<SelectivePhaseItem>,
+        &'static ViewEffectMask,
+    );
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, phase, mask): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Selective effect mask"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: mask.view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        phase.render(&mut render_pass, world, graph.view_entity());
+
+        Ok(())
+    }
+}
+
+/// Draws [`SelectiveEffect`] entities into the effect masks of 3d cameras.
+///
+/// Must be added after the effect mask nodes are in the graphs.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SELECTIVE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "selective.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<SelectiveEffect>();
+
+        app.add_plugins(ExtractComponentPlugin::<SelectiveEffect>::default());
+
+        let render_app = super::render_app(app);
+
+        let selective = ViewNodeRunner::new(SelectiveNode, &mut render_app.world);
+        let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+        let graph = render_graph
+            .get_sub_graph_mut(core_3d::graph::NAME)
+            .expect("Graph should be available");
+
+        graph.add_node(node::SELECTIVE_EFFECT_MASK, selective);
+        graph.add_node_edge(node::START_EFFECT_MASK, node::SELECTIVE_EFFECT_MASK);
+        graph.add_node_edge(node::SELECTIVE_EFFECT_MASK, node::END_EFFECT_MASK);
+
+        render_app
+            .init_resource::<DrawFunctions<SelectivePhaseItem>>()
+            .init_resource::<SelectivePipeline>()
+            .init_resource::<SpecializedMeshPipelines<SelectivePipeline>>()
+            .add_render_command::<SelectivePhaseItem, DrawSelective>()
+            .add_systems(ExtractSchedule, extract_phases)
+            .add_systems(
+                Render,
+                (
+                    queue.in_set(RenderSet::Queue),
+                    sort_phase_system::<SelectivePhaseItem>.in_set(RenderSet::PhaseSort),
+                ),
+            );
+    }
+}
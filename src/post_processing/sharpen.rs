@@ -0,0 +1,210 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const SHARPEN_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3518278363069672574);
+
+#[derive(Resource)]
+pub(crate) struct SharpenData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for SharpenData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Sharpen",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(Sharpen::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            SHARPEN_SHADER_HANDLE.typed(),
+        );
+
+        SharpenData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SHARPEN_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "sharpen.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Sharpen>();
+        super::register_effect_types::<Sharpen>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Sharpen>::default(),
+            UniformComponentPlugin::<Sharpen>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Sharpen>,
+            )
+            .init_resource::<SharpenData>()
+            .init_resource::<UniformBindGroup<Sharpen>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Sharpen>>();
+    }
+}
+
+fn prepare(
+    data: Res<SharpenData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Sharpen>,
+        &Blend<Sharpen>,
+        &Resolution<Sharpen>,
+        &SourceSampler<Sharpen>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::sharpen::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<Sharpen>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<SharpenData>,
+    mut bind_group: ResMut<UniformBindGroup<Sharpen>>,
+    uniforms: Res<ComponentUniforms<Sharpen>>,
+    views: Query<Entity, With<Sharpen>>,
+) {
+    let _span = info_span!("vfx::sharpen::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Sharpen Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Sharpen settings.
+///
+/// This is an unsharp mask: a blurred version of the image is subtracted from the original,
+/// and the difference (the "detail") is added back on top of the original.
+///
+/// Useful to counteract softness introduced by other effects such as [`super::blur::Blur`],
+/// or by rendering at a lower resolution and upscaling.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Sharpen {
+    /// How much of the detail is added back to the image.
+    /// If `0.0`, no sharpening is applied.
+    /// Values above `2.0` tend to produce visible halos around edges.
+    pub strength: f32,
+
+    /// How far away the blur used for the unsharp mask should sample points
+    /// away from the origin point.
+    /// This is in pixels, so `1.0` samples the immediate neighbours.
+    pub radius: f32,
+}
+
+impl Default for Sharpen {
+    fn default() -> Self {
+        Self {
+            strength: 0.8,
+            radius: 1.0,
+        }
+    }
+}
+
+impl Display for Sharpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Sharpen strength: {}, radius: {}",
+            self.strength, self.radius
+        )
+    }
+}
+
+impl ExtractComponent for Sharpen {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::sharpen::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
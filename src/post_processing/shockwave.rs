@@ -0,0 +1,293 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::CameraUpdateSystem,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+    transform::TransformSystem,
+};
+
+use crate::{
+    post_processing::{DrawPostProcessingEffect, UniformBindGroup},
+    time::VfxTime,
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const SHOCKWAVE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8006513647655945801);
+
+#[derive(Resource)]
+pub(crate) struct ShockwaveData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for ShockwaveData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Shockwave",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(Shockwave::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            SHOCKWAVE_SHADER_HANDLE.typed(),
+        );
+
+        ShockwaveData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SHOCKWAVE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "shockwave.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Shockwave>()
+            .register_type::<ShockwaveOrigin>();
+        super::register_effect_types::<Shockwave>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Shockwave>::default(),
+            UniformComponentPlugin::<Shockwave>::default(),
+        ))
+        .add_systems(Update, expand)
+        .add_systems(
+            PostUpdate,
+            follow_origin
+                .after(TransformSystem::TransformPropagate)
+                .after(CameraUpdateSystem),
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Shockwave>,
+            )
+            .init_resource::<ShockwaveData>()
+            .init_resource::<UniformBindGroup<Shockwave>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Shockwave>>();
+    }
+}
+
+fn prepare(
+    data: Res<ShockwaveData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Shockwave>,
+        &Blend<Shockwave>,
+        &Resolution<Shockwave>,
+        &SourceSampler<Shockwave>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::shockwave::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<Shockwave>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<ShockwaveData>,
+    mut bind_group: ResMut<UniformBindGroup<Shockwave>>,
+    uniforms: Res<ComponentUniforms<Shockwave>>,
+    views: Query<Entity, With<Shockwave>>,
+) {
+    let _span = info_span!("vfx::shockwave::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Shockwave Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// A ring of distortion expanding from a point, like the blast of an explosion.
+///
+/// The ring grows by [`Shockwave::speed`] each second, and fades out as it reaches
+/// [`Shockwave::max_radius`]. Use [`Shockwave::restart`] to send another one.
+///
+/// Add [`ShockwaveOrigin`] to the camera to send it from a point in the world.
+#[derive(Debug, Copy, Clone, PartialEq, Component, ShaderType, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Shockwave {
+    /// The center of the ring, in UV space (`(0, 0)` is the top left corner).
+    pub center: Vec2,
+
+    /// The current radius of the ring, relative to the height of the view.
+    pub radius: f32,
+
+    /// How wide the ring is, relative to the height of the view.
+    pub thickness: f32,
+
+    /// How much the radius grows each second.
+    pub speed: f32,
+
+    /// How strongly the ring refracts the image behind it.
+    pub strength: f32,
+
+    /// The radius at which the ring has faded out completely.
+    pub max_radius: f32,
+}
+
+impl Shockwave {
+    /// Send the ring out again from its center.
+    pub fn restart(&mut self) {
+        self.radius = 0.0;
+    }
+}
+
+impl Default for Shockwave {
+    fn default() -> Self {
+        Self {
+            center: Vec2::splat(0.5),
+            radius: 0.0,
+            thickness: 0.1,
+            speed: 0.8,
+            strength: 0.5,
+            max_radius: 1.5,
+        }
+    }
+}
+
+impl Display for Shockwave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Shockwave at [{:.2}, {:.2}], radius: {:.2}, thickness: {:.2}, speed: {:.2}, strength: {:.2}, max radius: {:.2}",
+            self.center.x,
+            self.center.y,
+            self.radius,
+            self.thickness,
+            self.speed,
+            self.strength,
+            self.max_radius
+        )
+    }
+}
+
+/// Keeps the [`Shockwave::center`] of a camera on a point in the world.
+///
+/// The point is projected with the camera each frame, so the ring stays on it as the camera moves.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct ShockwaveOrigin {
+    /// The point the ring expands from, in world space.
+    pub origin: Vec3,
+}
+
+impl ShockwaveOrigin {
+    /// Send the ring from the given point in world space.
+    pub fn new(origin: Vec3) -> Self {
+        Self { origin }
+    }
+}
+
+fn expand(time: Res<VfxTime>, mut query: Query<&mut Shockwave>) {
+    for mut shockwave in &mut query {
+        // Avoids change detection once faded out.
+        if shockwave.radius >= shockwave.max_radius {
+            continue;
+        }
+
+        shockwave.radius =
+            (shockwave.radius + shockwave.speed * time.delta_seconds()).min(shockwave.max_radius);
+    }
+}
+
+fn follow_origin(
+    mut cameras: Query<(&Camera, &GlobalTransform, &ShockwaveOrigin, &mut Shockwave)>,
+) {
+    for (camera, camera_transform, origin, mut shockwave) in &mut cameras {
+        let center = camera
+            .world_to_ndc(camera_transform, origin.origin)
+            .filter(|ndc| ndc.z > 0.0)
+            .map(|ndc| Vec2::new(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5))
+            // Behind the camera, put the center far off screen so the ring isn't seen.
+            .unwrap_or(Vec2::splat(-100.0));
+
+        if shockwave.center != center {
+            shockwave.center = center;
+        }
+    }
+}
+
+impl ExtractComponent for Shockwave {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::shockwave::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*settings)
+    }
+}
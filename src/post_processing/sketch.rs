@@ -0,0 +1,352 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            AddressMode, BindGroupLayout, CachedRenderPipelineId, Extent3d, SamplerDescriptor,
+            ShaderType, TextureDimension, TextureFormat,
+        },
+        renderer::RenderDevice,
+        texture::ImageSampler,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    DrawPostProcessingEffect, Order, PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const SKETCH_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9154027736108264371);
+const SKETCH_HATCHING_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 3380912465907712548);
+
+/// The hatching strokes, one layer per channel from the lightest tones to the darkest:
+/// diagonal, crossing diagonal, horizontal and dense diagonal strokes.
+///
+/// Generated rather than shipped as a file, so the strokes tile seamlessly at any size.
+fn hatching_image() -> Image {
+    const SIZE: usize = 128;
+
+    // The spacing of the strokes of each layer, in texels, and whether they run along
+    // the diagonal, the other diagonal, or horizontally.
+    const LAYERS: [(f32, [f32; 2]); 4] = [
+        (8.0, [1.0, 1.0]),
+        (8.0, [1.0, -1.0]),
+        (8.0, [0.0, 1.0]),
+        (4.0, [1.0, 1.0]),
+    ];
+
+    let mut data = vec![0; SIZE * SIZE * 4];
+    for (index, texel) in data.chunks_exact_mut(4).enumerate() {
+        let (x, y) = ((index % SIZE) as f32, (index / SIZE) as f32);
+
+        for (channel, (spacing, [dx, dy])) in LAYERS.into_iter().enumerate() {
+            // A slow wobble along the stroke, so the lines look drawn by hand.
+            let along = x * dy - y * dx;
+            let wobble = 0.6 * (along * std::f32::consts::TAU * 2.0 / SIZE as f32).sin();
+
+            let across = (x * dx + y * dy + wobble).rem_euclid(spacing);
+            let distance = (across - spacing / 2.0).abs();
+
+            // Strokes are about a texel and a half wide, with soft edges.
+            let coverage = (1.5 - distance).clamp(0.0, 1.0);
+            texel[channel] = (coverage * 255.0) as u8;
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE as u32,
+            height: SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+    );
+
+    image.sampler_descriptor = ImageSampler::Descriptor(SamplerDescriptor {
+        label: Some("Sketch Hatching Sampler"),
+        address_mode_u: AddressMode::Repeat,
+        address_mode_v: AddressMode::Repeat,
+        ..ImageSampler::linear_descriptor()
+    });
+
+    image
+}
+
+#[derive(Resource)]
+pub(crate) struct SketchData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+}
+
+impl FromWorld for SketchData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Sketch",
+            &super::uniform_and_images_layout_entries(SketchUniform::min_size(), 1),
+            SKETCH_SHADER_HANDLE.typed(),
+        );
+
+        SketchData {
+            pipeline_id,
+            layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SKETCH_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "sketch.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.world
+            .resource_mut::<Assets<Image>>()
+            .set_untracked(SKETCH_HATCHING_IMAGE_HANDLE, hatching_image());
+
+        app.register_type::<Sketch>()
+            .register_type::<SketchAlignment>();
+        super::register_effect_types::<Sketch>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Sketch>::default(),
+            UniformComponentPlugin::<SketchUniform>::default(),
+        ))
+        .add_systems(PostUpdate, super::prepass::configure_prepasses::<Sketch>);
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Sketch>,
+            )
+            .init_resource::<SketchData>()
+            .init_resource::<UniformBindGroup<SketchUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<SketchUniform>>(
+            );
+    }
+}
+
+impl EffectPrepasses for Sketch {
+    const WITHOUT: &'static str = "the hatching will be screen aligned";
+
+    fn prepasses(&self) -> Prepasses {
+        match self.alignment {
+            SketchAlignment::World => Prepasses::DEPTH_AND_NORMAL,
+            SketchAlignment::Screen => Prepasses::NONE,
+        }
+    }
+}
+
+fn prepare(
+    data: Res<SketchData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Sketch>,
+        &Blend<Sketch>,
+        &Resolution<Sketch>,
+        &SourceSampler<Sketch>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::sketch::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<SketchUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<SketchData>,
+    mut bind_group: ResMut<UniformBindGroup<SketchUniform>>,
+    uniforms: Res<ComponentUniforms<SketchUniform>>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<Entity, With<SketchUniform>>,
+) {
+    let _span = info_span!("vfx::sketch::queue").entered();
+
+    bind_group.inner = None;
+
+    let hatching = images.get(&SKETCH_HATCHING_IMAGE_HANDLE.typed_weak());
+    if let (Some(uniforms), Some(hatching)) = (uniforms.binding(), hatching) {
+        if !views.is_empty() {
+            bind_group.inner = Some(super::uniform_and_images_bind_group(
+                &render_device,
+                "Sketch Uniform Bind Group",
+                &data.layout,
+                uniforms,
+                &[hatching],
+            ));
+        }
+    }
+}
+
+/// What the hatching strokes of a [`Sketch`] are drawn along.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum SketchAlignment {
+    /// The strokes are fixed to the screen, like drawn on paper over the scene.
+    /// They stay put as the camera moves, so the scene slides under them.
+    #[default]
+    Screen,
+
+    /// The strokes are fixed to the surfaces of the scene, so they move along with it.
+    ///
+    /// Needs a [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) on the camera, and a
+    /// [`bevy::core_pipeline::prepass::NormalPrepass`] for the strokes to follow the surfaces.
+    /// Both are added when this alignment is picked, see [`super::prepass::AutoPrepass`].
+    /// Where nothing was rendered (e.g. the sky), the strokes are fixed to the screen.
+    World,
+}
+
+/// Makes the image look drawn in pencil: darkened edges, and hatching strokes
+/// which get denser the darker the image is.
+///
+/// Up to four layers of strokes cross each other in the darkest parts.
+/// The strokes are drawn with [`Sketch::ink`] on [`Sketch::paper`], which can take on
+/// some of the colors of the image via [`Sketch::color`].
+///
+/// For world aligned strokes (see [`SketchAlignment::World`]), MSAA must be off (`Msaa::Off`),
+/// since multisampled prepass textures can't be read by post processing effects.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Sketch {
+    /// How much edges in the image are darkened, from `0.0` (not at all) to `1.0`.
+    pub edge_strength: f32,
+
+    /// How large the hatching is.
+    /// In pixels per repeat of the strokes when screen aligned, in world units when world aligned.
+    pub hatch_scale: f32,
+
+    /// What the strokes are drawn along.
+    pub alignment: SketchAlignment,
+
+    /// The color of the strokes and edges.
+    pub ink: Color,
+
+    /// The color of the paper.
+    pub paper: Color,
+
+    /// How much the paper takes on the colors of the image, from `0.0` (plain paper) to `1.0`.
+    pub color: f32,
+}
+
+impl Sketch {
+    /// Strokes fixed to the surfaces of the scene, see [`SketchAlignment::World`].
+    pub fn world_aligned() -> Self {
+        Self {
+            alignment: SketchAlignment::World,
+            hatch_scale: 0.5,
+            ..default()
+        }
+    }
+}
+
+impl Default for Sketch {
+    fn default() -> Self {
+        Self {
+            edge_strength: 0.8,
+            hatch_scale: 128.0,
+            alignment: SketchAlignment::Screen,
+            ink: Color::rgb(0.12, 0.12, 0.15),
+            paper: Color::rgb(0.96, 0.94, 0.88),
+            color: 0.0,
+        }
+    }
+}
+
+impl Display for Sketch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Sketch edge strength: {:.2}, hatch scale: {:.2}, alignment: {:?}, color: {:.2}",
+            self.edge_strength, self.hatch_scale, self.alignment, self.color
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Sketch`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct SketchUniform {
+    pub(crate) ink: Vec4,
+    pub(crate) paper: Vec4,
+    pub(crate) edge_strength: f32,
+    pub(crate) hatch_scale: f32,
+    pub(crate) world_aligned: u32,
+    pub(crate) color: f32,
+
+    /// Used to turn depth values into world space positions.
+    pub(crate) inverse_view_projection: Mat4,
+}
+
+impl ExtractComponent for Sketch {
+    type Query = (&'static Self, &'static Camera, &'static GlobalTransform);
+    type Filter = ();
+    type Out = SketchUniform;
+
+    fn extract_component(
+        (settings, camera, transform): QueryItem<'_, Self::Query>,
+    ) -> Option<Self::Out> {
+        let _span = info_span!("vfx::sketch::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        let view_projection = camera.projection_matrix() * transform.compute_matrix().inverse();
+
+        Some(SketchUniform {
+            ink: Vec4::from(settings.ink.as_linear_rgba_f32()),
+            paper: Vec4::from(settings.paper.as_linear_rgba_f32()),
+            edge_strength: settings.edge_strength,
+            hatch_scale: settings.hatch_scale,
+            world_aligned: (settings.alignment == SketchAlignment::World).into(),
+            color: settings.color,
+            inverse_view_projection: view_projection.inverse(),
+        })
+    }
+}
@@ -0,0 +1,374 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use bevy::{
+    core_pipeline::{core_2d, core_3d},
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        camera::ExtractedCamera,
+        render_graph::{NodeRunError, RenderGraph, RenderGraphContext, ViewNode, ViewNodeRunner},
+        render_resource::{
+            Buffer, BufferDescriptor, BufferUsages, Extent3d, ImageCopyBuffer, ImageDataLayout,
+            MapMode, Origin3d, TextureDimension, TextureFormat,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::TextureFormatPixelInfo,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::graph::node;
+
+/// Reads the image of this camera back from the GPU once, with all of its effects applied,
+/// e.g. for photo modes or sharing screenshots which should look like the game does.
+///
+/// The component is removed once the image is copied, and [`SnapshotTaken`] is sent a frame or
+/// two later, when the copy has made its way back from the GPU:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::snapshot::{SnapshotTaken, TakeSnapshot};
+/// fn take_photo(
+///     mut commands: Commands,
+///     keyboard_input: Res<Input<KeyCode>>,
+///     cameras: Query<Entity, With<Camera>>,
+/// ) {
+///     if keyboard_input.just_pressed(KeyCode::P) {
+///         commands.entity(cameras.single()).insert(TakeSnapshot::new());
+///     }
+/// }
+///
+/// fn save_photo(mut snapshots: EventReader<SnapshotTaken>) {
+///     for snapshot in snapshots.iter() {
+///         let bytes: &[u8] = &snapshot.image.data;
+///         info!("Took a photo of {} bytes", bytes.len());
+///     }
+/// }
+/// ```
+///
+/// Only what the camera draws within its viewport is read, after the effects and before the UI.
+/// The image has the format of the camera, so cameras with [`Camera::hdr`] give
+/// [`TextureFormat::Rgba16Float`] images.
+#[derive(Debug, Default, Clone, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct TakeSnapshot {
+    /// If set, the snapshot is also written into this image asset, e.g. to show it in the UI.
+    pub image: Option<Handle<Image>>,
+}
+
+impl TakeSnapshot {
+    /// Take a snapshot, only sent as [`SnapshotTaken`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a snapshot, and also write it into the given image asset.
+    /// The asset is added if it doesn't exist yet.
+    pub fn into_image(image: Handle<Image>) -> Self {
+        Self { image: Some(image) }
+    }
+}
+
+/// Sent when the image requested by a [`TakeSnapshot`] is read back.
+#[derive(Debug, Clone, Event)]
+pub struct SnapshotTaken {
+    /// The camera the snapshot was taken of.
+    pub camera: Entity,
+
+    /// The image of the camera. Its pixels are in [`Image::data`], row by row without padding.
+    pub image: Image,
+
+    /// The image asset the snapshot was written into, see [`TakeSnapshot::into_image`].
+    pub target: Option<Handle<Image>>,
+}
+
+/// A [`TakeSnapshot`] which the render world has yet to copy.
+#[derive(Debug)]
+struct SnapshotRequest {
+    camera: Entity,
+    target: Option<Handle<Image>>,
+}
+
+/// Snapshots requested by the main world, and the images read back by the render world.
+#[derive(Debug, Default, Clone, Resource)]
+struct SharedSnapshots(Arc<Mutex<Snapshots>>);
+
+#[derive(Debug, Default)]
+struct Snapshots {
+    requested: Vec<SnapshotRequest>,
+    taken: Vec<SnapshotTaken>,
+}
+
+/// Hands the snapshots of active cameras over to the render world.
+fn request_snapshots(
+    mut commands: Commands,
+    shared: Res<SharedSnapshots>,
+    cameras: Query<(Entity, &Camera, &TakeSnapshot)>,
+) {
+    let mut shared = shared.0.lock().expect("Snapshots should not be poisoned");
+
+    // Inactive cameras don't render, so their snapshots wait until they do.
+    for (camera, _, snapshot) in cameras.iter().filter(|(_, camera, _)| camera.is_active) {
+        shared.requested.push(SnapshotRequest {
+            camera,
+            target: snapshot.image.clone(),
+        });
+        commands.entity(camera).remove::<TakeSnapshot>();
+    }
+}
+
+/// Sends the snapshots which were read back, and writes them into their image assets.
+fn send_snapshots(
+    shared: Res<SharedSnapshots>,
+    mut images: ResMut<Assets<Image>>,
+    mut taken: EventWriter<SnapshotTaken>,
+) {
+    let snapshots = std::mem::take(
+        &mut shared
+            .0
+            .lock()
+            .expect("Snapshots should not be poisoned")
+            .taken,
+    );
+
+    for snapshot in snapshots {
+        if let Some(target) = &snapshot.target {
+            let _ = images.set(target.clone(), snapshot.image.clone());
+        }
+        taken.send(snapshot);
+    }
+}
+
+/// Where the image of a view is copied to, in the render world.
+#[derive(Component)]
+struct ViewSnapshot {
+    buffer: Buffer,
+    origin: UVec2,
+    size: UVec2,
+    padded_bytes_per_row: u32,
+}
+
+/// A buffer a snapshot is copied into, which is read once it is mapped.
+struct PendingSnapshot {
+    request: SnapshotRequest,
+    format: TextureFormat,
+    size: UVec2,
+    padded_bytes_per_row: u32,
+    buffer: Buffer,
+    /// Set once mapping is requested, which is after the copy is submitted.
+    mapped: Option<Arc<AtomicBool>>,
+}
+
+#[derive(Default, Resource)]
+struct PendingSnapshots(Vec<PendingSnapshot>);
+
+/// Gives the views with requested snapshots a buffer to copy into.
+fn prepare(
+    mut commands: Commands,
+    shared: Res<SharedSnapshots>,
+    render_device: Res<RenderDevice>,
+    mut pending: ResMut<PendingSnapshots>,
+    views: Query<(&ExtractedCamera, &ViewTarget)>,
+) {
+    let mut shared = shared.0.lock().expect("Snapshots should not be poisoned");
+
+    for request in std::mem::take(&mut shared.requested) {
+        // The camera may have been requested after this frame was extracted.
+        let Ok((camera, view_target)) = views.get(request.camera) else {
+            shared.requested.push(request);
+            continue;
+        };
+        let Some(size) = camera.physical_viewport_size else {
+            continue;
+        };
+        let origin = camera
+            .viewport
+            .as_ref()
+            .map_or(UVec2::ZERO, |viewport| viewport.physical_position);
+
+        let format = view_target.main_texture_format();
+        let padded_bytes_per_row =
+            RenderDevice::align_copy_bytes_per_row(size.x as usize * format.pixel_size()) as u32;
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("Snapshot buffer"),
+            size: padded_bytes_per_row as u64 * size.y as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        commands.entity(request.camera).insert(ViewSnapshot {
+            buffer: buffer.clone(),
+            origin,
+            size,
+            padded_bytes_per_row,
+        });
+        pending.0.push(PendingSnapshot {
+            request,
+            format,
+            size,
+            padded_bytes_per_row,
+            buffer,
+            mapped: None,
+        });
+    }
+}
+
+/// Copies the image of the view into its snapshot buffer, after the effects.
+#[derive(Default)]
+struct SnapshotNode;
+
+impl ViewNode for SnapshotNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewSnapshot);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, snapshot): QueryItem<Self::ViewQuery>,
+        _world: &World,
+    ) -> Result<(), NodeRunError> {
+        let mut source = view_target.main_texture().as_image_copy();
+        source.origin = Origin3d {
+            x: snapshot.origin.x,
+            y: snapshot.origin.y,
+            z: 0,
+        };
+
+        render_context.command_encoder().copy_texture_to_buffer(
+            source,
+            ImageCopyBuffer {
+                buffer: &snapshot.buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(snapshot.padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            Extent3d {
+                width: snapshot.size.x,
+                height: snapshot.size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// Maps the snapshot buffers copied this frame, and reads the ones mapped since.
+fn read(
+    shared: Res<SharedSnapshots>,
+    render_device: Res<RenderDevice>,
+    mut pending: ResMut<PendingSnapshots>,
+) {
+    let _span = info_span!("vfx::snapshot::read").entered();
+
+    // Buffers are mapped while the device is polled, which happens when the queue is submitted,
+    // so they are read in a later frame than they are copied in.
+    let (mapped, still_pending): (Vec<_>, Vec<_>) = pending.0.drain(..).partition(|snapshot| {
+        snapshot
+            .mapped
+            .as_ref()
+            .is_some_and(|mapped| mapped.load(Ordering::Acquire))
+    });
+    pending.0 = still_pending;
+
+    let mut shared = shared.0.lock().expect("Snapshots should not be poisoned");
+    for snapshot in mapped {
+        let bytes_per_row = snapshot.size.x as usize * snapshot.format.pixel_size();
+        let data = snapshot
+            .buffer
+            .slice(..)
+            .get_mapped_range()
+            .chunks(snapshot.padded_bytes_per_row as usize)
+            .flat_map(|row| &row[..bytes_per_row])
+            .copied()
+            .collect();
+        snapshot.buffer.unmap();
+
+        shared.taken.push(SnapshotTaken {
+            camera: snapshot.request.camera,
+            image: Image::new(
+                Extent3d {
+                    width: snapshot.size.x,
+                    height: snapshot.size.y,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                data,
+                snapshot.format,
+            ),
+            target: snapshot.request.target,
+        });
+    }
+
+    // This runs after the frame is submitted, so the copies of this frame are in the queue.
+    for snapshot in pending
+        .0
+        .iter_mut()
+        .filter(|snapshot| snapshot.mapped.is_none())
+    {
+        let mapped = Arc::new(AtomicBool::new(false));
+        let on_mapped = mapped.clone();
+        render_device.map_buffer(&snapshot.buffer.slice(..), MapMode::Read, move |result| {
+            if let Err(err) = result {
+                error!("Could not read back a snapshot: {err}");
+            }
+            on_mapped.store(true, Ordering::Release);
+        });
+        snapshot.mapped = Some(mapped);
+    }
+}
+
+fn add_node(render_app: &mut App, graph: &str, end_main_pass_post_processing: &'static str) {
+    let snapshot = ViewNodeRunner::new(SnapshotNode, &mut render_app.world);
+    let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+    let graph = render_graph
+        .get_sub_graph_mut(graph)
+        .expect("Graph should be available");
+
+    graph.add_node(node::SNAPSHOT, snapshot);
+    graph.add_node_edge(node::AFTER_COMPOSITING, node::SNAPSHOT);
+    graph.add_node_edge(node::SNAPSHOT, end_main_pass_post_processing);
+}
+
+/// Reads the images of cameras back, see [`TakeSnapshot`].
+///
+/// Must be added after the post processing nodes are in the graphs.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedSnapshots::default();
+
+        app.register_type::<TakeSnapshot>()
+            .add_event::<SnapshotTaken>()
+            .insert_resource(shared.clone())
+            .add_systems(First, send_snapshots)
+            // Last, so snapshots requested during the frame are extracted with it.
+            .add_systems(Last, request_snapshots);
+
+        let render_app = super::render_app(app);
+
+        add_node(
+            render_app,
+            core_3d::graph::NAME,
+            core_3d::graph::node::END_MAIN_PASS_POST_PROCESSING,
+        );
+        add_node(
+            render_app,
+            core_2d::graph::NAME,
+            core_2d::graph::node::END_MAIN_PASS_POST_PROCESSING,
+        );
+
+        // Reading goes after rendering, so the copies of the frame are submitted.
+        render_app
+            .insert_resource(shared)
+            .init_resource::<PendingSnapshots>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, read.in_set(RenderSet::Cleanup));
+    }
+}
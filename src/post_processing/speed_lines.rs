@@ -0,0 +1,284 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+use crate::animation::Animate;
+
+pub(crate) const SPEED_LINES_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5719482037309426142);
+
+#[derive(Resource)]
+pub(crate) struct SpeedLinesData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for SpeedLinesData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Speed Lines",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(SpeedLinesUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            SPEED_LINES_SHADER_HANDLE.typed(),
+        );
+
+        SpeedLinesData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SPEED_LINES_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "speed_lines.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<SpeedLines>();
+        super::register_effect_types::<SpeedLines>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<SpeedLines>::default(),
+            UniformComponentPlugin::<SpeedLinesUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<SpeedLines>,
+            )
+            .init_resource::<SpeedLinesData>()
+            .init_resource::<UniformBindGroup<SpeedLinesUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<SpeedLinesUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<SpeedLinesData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<SpeedLines>,
+        &Blend<SpeedLines>,
+        &Resolution<SpeedLines>,
+        &SourceSampler<SpeedLines>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::speed_lines::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<SpeedLinesUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<SpeedLinesData>,
+    mut bind_group: ResMut<UniformBindGroup<SpeedLinesUniform>>,
+    uniforms: Res<ComponentUniforms<SpeedLinesUniform>>,
+    views: Query<Entity, With<SpeedLinesUniform>>,
+) {
+    let _span = info_span!("vfx::speed_lines::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Speed Lines Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Anime-style speed lines: streaks reaching in from the edges of the view towards a center,
+/// e.g. for dashes, boosts or special moves.
+///
+/// The lines are drawn anew with other lengths and thicknesses [`SpeedLines::flicker`] times
+/// a second. Pulse [`SpeedLines::intensity`] to show them, or use [`SpeedLines::burst`]:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::speed_lines::SpeedLines;
+/// fn dash(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     commands
+///         .entity(camera.single())
+///         .insert(SpeedLines::default().burst(0.4));
+/// }
+/// ```
+///
+/// The lines are drawn over the image without sampling around, so they stack with blurs such as
+/// [`super::motion_blur::MotionBlur`] or a zooming [`super::bulge::Bulge`]. Give the lines an
+/// [`Order`] after a blur to keep them crisp, or before it to smear them along.
+/// With a bulge, use the same center for both.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct SpeedLines {
+    /// Where the lines point to, in UV coordinates, where `(0.5, 0.5)` is the middle of the view.
+    pub center: Vec2,
+
+    /// How many lines there are around the center.
+    pub density: f32,
+
+    /// How far the lines reach in from the edges, from `0.0` (not at all) to `1.0`
+    /// (the longest lines reach the center).
+    pub length: f32,
+
+    /// How thick the lines are at the edges, from `0.0` to `1.0`, where `1.0` leaves
+    /// no gap between the thickest lines.
+    pub thickness: f32,
+
+    /// The color of the lines.
+    /// The alpha channel controls how opaque they are.
+    pub color: Color,
+
+    /// How strongly the lines are shown, from `0.0` (hidden) to `1.0`.
+    /// Meant to be pulsed by the game.
+    pub intensity: f32,
+
+    /// How many times a second the lines are drawn anew.
+    /// `0.0` keeps the same lines.
+    pub flicker: f32,
+}
+
+impl SpeedLines {
+    /// Shows the lines at full intensity, and fades them out over the given seconds.
+    /// The effect stays, with an [`SpeedLines::intensity`] of `0.0`.
+    pub fn burst(self, seconds: f32) -> Animate<SpeedLines> {
+        Animate::new(
+            Self {
+                intensity: 1.0,
+                ..self
+            },
+            Self {
+                intensity: 0.0,
+                ..self
+            },
+            seconds,
+        )
+    }
+}
+
+impl Default for SpeedLines {
+    fn default() -> Self {
+        Self {
+            center: Vec2::splat(0.5),
+            density: 64.0,
+            length: 0.4,
+            thickness: 0.3,
+            color: Color::WHITE,
+            intensity: 1.0,
+            flicker: 12.0,
+        }
+    }
+}
+
+impl Display for SpeedLines {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Speed Lines intensity: {:.2}, density: {:.0}, length: {:.2}, thickness: {:.2}",
+            self.intensity, self.density, self.length, self.thickness
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`SpeedLines`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct SpeedLinesUniform {
+    pub(crate) color: Vec4,
+    pub(crate) center: Vec2,
+    pub(crate) density: f32,
+    pub(crate) length: f32,
+    pub(crate) thickness: f32,
+    pub(crate) intensity: f32,
+    pub(crate) flicker: f32,
+}
+
+impl ExtractComponent for SpeedLines {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = SpeedLinesUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::speed_lines::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(SpeedLinesUniform {
+            color: Vec4::from(settings.color.as_linear_rgba_f32()),
+            center: settings.center,
+            // Whole lines, so they don't split at the seam of the circle.
+            density: settings.density.round().max(1.0),
+            length: settings.length.clamp(0.0, 1.0),
+            thickness: settings.thickness.clamp(0.0, 1.0),
+            intensity: settings.intensity.clamp(0.0, 1.0),
+            flicker: settings.flicker.max(0.0),
+        })
+    }
+}
@@ -0,0 +1,331 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingResource, BindingType, BufferBindingType, CachedRenderPipelineId, Extent3d,
+            FilterMode, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages, ShaderType,
+            TextureDescriptor, TextureDimension, TextureSampleType, TextureUsages,
+            TextureViewDimension,
+        },
+        renderer::RenderDevice,
+        texture::TextureCache,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingViewEffect,
+    InputCapture, Order, PostProcessingPhaseItem, ViewUniformBindGroup,
+};
+
+pub(crate) const STABLE_REGIONS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2485309735136653856);
+
+/// How many regions the shader keeps clean. Regions past this are ignored.
+pub const MAX_STABLE_REGIONS: usize = 8;
+
+#[derive(Resource)]
+pub(crate) struct StableRegionsData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for StableRegionsData {
+    fn from_world(world: &mut World) -> Self {
+        let (layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Stable Regions",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(StableRegionsUniform::min_size()),
+                    },
+                    visibility: ShaderStages::FRAGMENT,
+                    count: None,
+                },
+                // The image from before any of the effects.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            STABLE_REGIONS_SHADER_HANDLE.typed(),
+        );
+
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("Stable Regions input sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+        StableRegionsData {
+            pipeline_id,
+            layout,
+            sampler,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            STABLE_REGIONS_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "stable_regions.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<StableRegions>();
+        super::register_effect_types::<StableRegions>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<StableRegions>::default(),
+            UniformComponentPlugin::<StableRegionsUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<StableRegions>,
+            )
+            .init_resource::<StableRegionsData>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingViewEffect<StableRegionsUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<StableRegionsData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<StableRegions>,
+        &Blend<StableRegions>,
+        &Resolution<StableRegions>,
+        &SourceSampler<StableRegions>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::stable_regions::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingViewEffect<StableRegionsUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: order.clone().into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    data: Res<StableRegionsData>,
+    uniforms: Res<ComponentUniforms<StableRegionsUniform>>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<
+        (Entity, &ExtractedCamera, &ViewTarget, &Order<StableRegions>),
+        With<StableRegionsUniform>,
+    >,
+) {
+    let _span = info_span!("vfx::stable_regions::queue").entered();
+
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (entity, camera, view_target, order) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+
+        // The effects overwrite the image, so it's kept from before the first of them.
+        // Effects of an earlier stage (see `EffectStage`) are kept in it, so its colors match.
+        let input = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("Stable Regions input"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_target.main_texture_format(),
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Stable Regions Uniform Bind Group"),
+            layout: &data.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&input.default_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&data.sampler),
+                },
+            ],
+        });
+
+        commands.entity(entity).insert((
+            ViewUniformBindGroup::<StableRegionsUniform>::new(bind_group),
+            InputCapture {
+                pass: order.pass(),
+                texture: input.texture,
+            },
+        ));
+    }
+}
+
+/// Regions of the image which are kept free of the effects, such as HUD elements
+/// which must stay readable under heavy distortion.
+///
+/// The image from before any of the effects is brought back inside the regions,
+/// so effects like a [`super::wave::Wave`] can stay full-screen everywhere else.
+///
+/// Bevy's UI is drawn after the effects, so it is never affected by them. This is for HUDs
+/// which are drawn as part of the image, such as world space HUDs.
+///
+/// By default this runs after all other effects on the camera.
+/// When given an [`Order`], effects ordered after it also apply within the regions,
+/// as do effects in an earlier [`super::EffectStage`].
+#[derive(Debug, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct StableRegions {
+    /// The regions, in UV coordinates where `(0, 0)` is the top left of the image.
+    ///
+    /// At most [`MAX_STABLE_REGIONS`] are used.
+    pub rects: Vec<Rect>,
+
+    /// How far the regions fade into the effects, in pixels.
+    pub feather: f32,
+}
+
+impl StableRegions {
+    /// Regions of the image to keep free of the effects, in UV coordinates.
+    pub fn new(rects: impl IntoIterator<Item = Rect>) -> Self {
+        Self {
+            rects: rects.into_iter().collect(),
+            ..default()
+        }
+    }
+
+    /// Adds a region, in UV coordinates.
+    pub fn with_rect(mut self, rect: Rect) -> Self {
+        self.rects.push(rect);
+        self
+    }
+}
+
+impl Default for StableRegions {
+    fn default() -> Self {
+        Self {
+            rects: vec![],
+            feather: 4.0,
+        }
+    }
+}
+
+impl Display for StableRegions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Stable Regions regions: {}, feather: {:.1}",
+            self.rects.len(),
+            self.feather
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`StableRegions`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct StableRegionsUniform {
+    pub(crate) rects: [Vec4; MAX_STABLE_REGIONS],
+    pub(crate) count: u32,
+    pub(crate) feather: f32,
+}
+
+impl ExtractComponent for StableRegions {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = StableRegionsUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::stable_regions::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        let mut rects = [Vec4::ZERO; MAX_STABLE_REGIONS];
+        for (uniform, rect) in rects.iter_mut().zip(&settings.rects) {
+            *uniform = Vec4::new(rect.min.x, rect.min.y, rect.max.x, rect.max.y);
+        }
+
+        Some(StableRegionsUniform {
+            rects,
+            count: settings.rects.len().min(MAX_STABLE_REGIONS) as u32,
+            feather: settings.feather,
+        })
+    }
+}
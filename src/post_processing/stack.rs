@@ -0,0 +1,244 @@
+use std::{any::TypeId, fmt::Display};
+
+use bevy::{
+    ecs::{system::Command, world::EntityMut},
+    prelude::*,
+};
+
+use super::{for_each_effect, lut::Lut, EffectStage, Order};
+
+type InsertEffect = Box<dyn FnOnce(&mut EntityMut, EffectStage, f32) + Send + Sync>;
+
+type EffectStageOf = Box<dyn Fn(&EntityMut) -> EffectStage + Send + Sync>;
+
+struct StackEntry {
+    id: TypeId,
+    name: &'static str,
+    stage: EffectStageOf,
+    insert: InsertEffect,
+}
+
+/// A chain of effects, which are ordered by their position in the chain.
+///
+/// Instead of picking an [`Order`] for each effect by hand, list the effects in the order
+/// they should be applied:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::{
+/// #     masks::Mask, pixelate::Pixelate, stack::{InsertPostProcessingStack, PostProcessingStack},
+/// # };
+/// fn setup(mut commands: Commands) {
+///     let camera = commands.spawn(Camera3dBundle::default()).id();
+///
+///     commands.add(InsertPostProcessingStack {
+///         entity: camera,
+///         stack: PostProcessingStack::new()
+///             .then::<Pixelate>()
+///             .then_with(Mask::vignette()),
+///     });
+/// }
+/// ```
+///
+/// The first effect gets an order of `0.0`, the next `1.0`, and so on.
+/// When inserted, the stack checks that every effect on the entity ends up with a position of
+/// its own, including effects which are not part of the stack.
+/// Effects without an [`Order`] are at `0.0`, so they tie with the first effect of the stack,
+/// unless they are drawn at a fixed point without one, e.g.
+/// [`Letterbox`](super::letterbox::Letterbox) after all the other effects.
+#[derive(Default)]
+pub struct PostProcessingStack {
+    effects: Vec<StackEntry>,
+}
+
+impl PostProcessingStack {
+    /// An empty stack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the effect `C` with default parameters after the effects before it.
+    pub fn then<C: Component + Default>(self) -> Self {
+        self.then_with(C::default())
+    }
+
+    /// Apply the given effect after the effects before it.
+    pub fn then_with<C: Component>(mut self, effect: C) -> Self {
+        let name = std::any::type_name::<C>();
+        let name = name.rsplit("::").next().unwrap_or(name);
+
+        // The stage isn't part of the stack, so the one on the camera is kept.
+        let implicit = Order::implicit(&effect).map(|order| order.stage);
+
+        self.effects.push(StackEntry {
+            id: TypeId::of::<C>(),
+            name,
+            stage: Box::new(move |entity| {
+                entity
+                    .get::<Order<C>>()
+                    .map(|order| order.stage)
+                    .or(implicit)
+                    .unwrap_or_default()
+            }),
+            insert: Box::new(move |entity, stage, order| {
+                entity.insert((effect, Order::<C>::new(order).with_stage(stage)));
+            }),
+        });
+        self
+    }
+
+    /// The names of the effects in the stack, e.g. `"Pixelate"`, in the order they are applied.
+    pub fn effects(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.effects.iter().map(|entry| entry.name)
+    }
+
+    /// Insert the effects and their orders into the entity.
+    ///
+    /// Nothing is inserted if the stack has the same effect twice, or if two effects on the entity
+    /// would end up with the same order in the same [`EffectStage`].
+    pub fn apply(self, entity: &mut EntityMut) -> Result<(), StackError> {
+        let mut orders: Vec<(&'static str, EffectStage, f32)> = vec![];
+
+        for (position, entry) in self.effects.iter().enumerate() {
+            if self.effects[..position]
+                .iter()
+                .any(|other| other.id == entry.id)
+            {
+                return Err(StackError::Duplicate(entry.name));
+            }
+
+            orders.push((entry.name, (entry.stage)(entity), position as f32));
+        }
+
+        // The effects already on the entity keep their orders, unless the stack replaces them.
+        for (name, stage, order) in effect_orders(entity) {
+            if self.effects().all(|stacked| stacked != name) {
+                orders.push((name, stage, order));
+            }
+        }
+
+        for (index, (name, stage, order)) in orders.iter().enumerate() {
+            if let Some((other, ..)) = orders[..index]
+                .iter()
+                .find(|(_, other_stage, other)| other_stage == stage && other == order)
+            {
+                return Err(StackError::Tie {
+                    first: other,
+                    second: name,
+                    order: *order,
+                });
+            }
+        }
+
+        for (position, entry) in self.effects.into_iter().enumerate() {
+            let stage = (entry.stage)(entity);
+            (entry.insert)(entity, stage, position as f32);
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for PostProcessingStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.effects()).finish()
+    }
+}
+
+/// The effects on the entity along with their stages and orders, where effects without an
+/// [`Order`] are at their [`Order::implicit`] or `0.0`.
+fn effect_orders(entity: &EntityMut) -> Vec<(&'static str, EffectStage, f32)> {
+    macro_rules! orders {
+        ($orders:expr, $($name:ident($effect:path) $meta:tt),* $(,)?) => {
+            $(
+                $orders.extend(
+                    effect_order::<$effect>(entity)
+                        .map(|(stage, order)| (stringify!($name), stage, order)),
+                );
+            )*
+        };
+    }
+
+    let mut orders = vec![];
+    orders.extend(effect_order::<Lut>(entity).map(|(stage, order)| ("Lut", stage, order)));
+    for_each_effect!(orders, &mut orders);
+
+    orders
+}
+
+/// The stage and order of the effect `C` on the entity, if the effect is there.
+fn effect_order<C: Component>(entity: &EntityMut) -> Option<(EffectStage, f32)> {
+    let effect = entity.get::<C>()?;
+    let order = entity
+        .get::<Order<C>>()
+        .cloned()
+        .or_else(|| Order::implicit(effect))
+        .unwrap_or_default();
+
+    Some((order.stage, order.order))
+}
+
+/// Why a [`PostProcessingStack`] could not be inserted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackError {
+    /// The stack has the same effect more than once.
+    /// An entity can only have one of each effect.
+    Duplicate(&'static str),
+
+    /// Two effects would have the same order, so which one is applied first is undefined.
+    Tie {
+        /// The name of one of the effects.
+        first: &'static str,
+
+        /// The name of the other effect.
+        second: &'static str,
+
+        /// The order both effects would have, within the same [`EffectStage`].
+        order: f32,
+    },
+}
+
+impl Display for StackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Duplicate(name) => write!(f, "`{name}` is in the stack more than once"),
+            Self::Tie {
+                first,
+                second,
+                order,
+            } => write!(
+                f,
+                "`{first}` and `{second}` would both have order {order}, give one of them an `Order`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StackError {}
+
+/// A command which inserts a [`PostProcessingStack`] into an entity.
+///
+/// If the stack can't be inserted, nothing is inserted and the reason is logged as an error.
+#[derive(Debug)]
+pub struct InsertPostProcessingStack {
+    /// The entity to insert the stack into, typically a camera.
+    pub entity: Entity,
+
+    /// The stack to insert.
+    pub stack: PostProcessingStack,
+}
+
+impl Command for InsertPostProcessingStack {
+    fn apply(self, world: &mut World) {
+        let Some(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+
+        if let Err(error) = self.stack.apply(&mut entity) {
+            error!(
+                "Could not insert the post processing stack into {:?}: {error}",
+                self.entity
+            );
+        }
+    }
+}
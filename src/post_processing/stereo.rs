@@ -0,0 +1,126 @@
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        render_resource::{BindingResource, ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderSet,
+    },
+};
+
+/// Which eye a camera renders, for stereo and XR rendering.
+///
+/// Stereo rendering uses one camera per eye, so the effects already run once per eye with the
+/// settings on that eye's camera. Put this on each eye's camera as well, so effects which must
+/// differ slightly between the eyes know which one they are rendering,
+/// e.g. the vignette of a [`super::masks::Mask`] with a [`super::masks::Mask::nasal_offset`].
+///
+/// Cameras without this render both eyes at once, as on a regular screen.
+///
+/// Shaders see the eye as `@group(0) @binding(5)`, with an `index` of `0` for the left eye
+/// (and cameras without an eye) and `1` for the right eye, and a `side` of `-1.0` for the left eye,
+/// `1.0` for the right eye and `0.0` for cameras without an eye.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum StereoEye {
+    /// The left eye.
+    Left,
+
+    /// The right eye.
+    Right,
+}
+
+impl ExtractComponent for StereoEye {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component((eye, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(*eye)
+    }
+}
+
+/// The uniform the shaders see as the eye.
+#[derive(Debug, Default, Clone, ShaderType)]
+pub(crate) struct VfxEyeUniform {
+    index: u32,
+    side: f32,
+}
+
+impl From<Option<StereoEye>> for VfxEyeUniform {
+    fn from(eye: Option<StereoEye>) -> Self {
+        match eye {
+            None => Self {
+                index: 0,
+                side: 0.0,
+            },
+            Some(StereoEye::Left) => Self {
+                index: 0,
+                side: -1.0,
+            },
+            Some(StereoEye::Right) => Self {
+                index: 1,
+                side: 1.0,
+            },
+        }
+    }
+}
+
+/// The buffers containing the [`VfxEyeUniform`] of cameras without an eye, the left eye
+/// and the right eye.
+#[derive(Resource, Default)]
+pub(crate) struct VfxEyeBuffers {
+    buffers: [UniformBuffer<VfxEyeUniform>; 3],
+}
+
+impl VfxEyeBuffers {
+    fn slot(eye: Option<StereoEye>) -> usize {
+        match eye {
+            None => 0,
+            Some(StereoEye::Left) => 1,
+            Some(StereoEye::Right) => 2,
+        }
+    }
+
+    /// The binding of the buffer for views rendering the given eye.
+    pub(crate) fn binding(&self, eye: Option<StereoEye>) -> BindingResource<'_> {
+        self.buffers[Self::slot(eye)]
+            .binding()
+            .expect("Eye buffers should be available")
+    }
+}
+
+fn prepare_vfx_eye_buffers(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut eye_buffers: ResMut<VfxEyeBuffers>,
+) {
+    // The contents never change, so the buffers are only written once.
+    if eye_buffers.buffers[0].buffer().is_some() {
+        return;
+    }
+
+    for eye in [None, Some(StereoEye::Left), Some(StereoEye::Right)] {
+        let buffer = &mut eye_buffers.buffers[VfxEyeBuffers::slot(eye)];
+        buffer.set(eye.into());
+        buffer.write_buffer(&render_device, &render_queue);
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StereoEye>();
+
+        app.add_plugins(ExtractComponentPlugin::<StereoEye>::default());
+
+        super::render_app(app)
+            .init_resource::<VfxEyeBuffers>()
+            .add_systems(Render, prepare_vfx_eye_buffers.in_set(RenderSet::Prepare));
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    blur::Blur,
+    line_jitter::LineJitter,
+    masks::Mask,
+    pixelate::Pixelate,
+    process_image::{output_image, ProcessImage},
+};
+
+/// Applies the effect `C` to the image of a UI node, e.g. to pixelate a portrait or glitch a panel.
+///
+/// UI nodes can't have materials of their own in this version of Bevy, so the effect runs on a
+/// [`ProcessImage`] of the [`UiImage`] of the node, and the node shows its output instead:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::post_processing::ui::UiGlitch;
+/// # let mut commands: Commands = panic!();
+/// # let panel: Handle<Image> = panic!();
+/// commands.spawn((
+///     ImageBundle {
+///         image: UiImage::new(panel),
+///         ..default()
+///     },
+///     UiGlitch::default(),
+/// ));
+/// ```
+///
+/// The node needs a [`UiImage`]. It is read once the effect is added, and given back to the node
+/// when the effect is removed.
+/// Changes to the effect are applied to the image as they happen.
+///
+/// The effect only sees the image of the node, not what is drawn behind it, so a blurred panel
+/// blurs its own image rather than the scene behind it.
+///
+/// [`UiBlur`], [`UiGlitch`], [`UiPixelate`] and [`UiVignette`] work out of the box.
+/// Other effects work the same once [`update_ui_effects`] is added for them.
+#[derive(Debug, Default, Clone, Component)]
+pub struct UiEffect<C: Component + Clone> {
+    /// The settings of the effect.
+    pub effect: C,
+}
+
+impl<C: Component + Clone> UiEffect<C> {
+    /// Apply the effect with the given settings.
+    pub fn new(effect: C) -> Self {
+        Self { effect }
+    }
+}
+
+/// Blurs the image of a UI node.
+pub type UiBlur = UiEffect<Blur>;
+
+/// Tears the lines of the image of a UI node, like a glitching display.
+pub type UiGlitch = UiEffect<LineJitter>;
+
+/// Pixelates the image of a UI node.
+pub type UiPixelate = UiEffect<Pixelate>;
+
+/// Darkens the edges of the image of a UI node.
+pub type UiVignette = UiEffect<Mask>;
+
+/// Applies the [`UiEffect<C>`]s on UI nodes.
+///
+/// The effects of this crate which are common on UI have this added already.
+/// Add it to `PostUpdate` for other effects.
+pub fn update_ui_effects<C: Component + Clone>(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    nodes: Query<(Entity, Ref<UiEffect<C>>), With<UiImage>>,
+    mut ui_images: Query<&mut UiImage>,
+    mut removed: RemovedComponents<UiEffect<C>>,
+    // The entity with the `ProcessImage` of each node, and the image the node showed before.
+    mut processors: Local<HashMap<Entity, (Entity, Handle<Image>)>>,
+) {
+    for entity in removed.iter() {
+        let Some((processor, input)) = processors.remove(&entity) else {
+            continue;
+        };
+
+        commands.entity(processor).despawn();
+        if let Ok(mut ui_image) = ui_images.get_mut(entity) {
+            ui_image.texture = input;
+        }
+    }
+
+    for (entity, effect) in &nodes {
+        if let Some((processor, _)) = processors.get(&entity) {
+            if effect.is_changed() {
+                commands.entity(*processor).insert(effect.effect.clone());
+            }
+            continue;
+        }
+
+        let Ok(mut ui_image) = ui_images.get_mut(entity) else {
+            continue;
+        };
+
+        // The output is as big as the input, so wait until the input is loaded.
+        let input = ui_image.texture.clone();
+        let Some(size) = images.get(&input).map(|image| image.size().as_uvec2()) else {
+            continue;
+        };
+
+        let output = images.add(output_image(size));
+        let processor = commands
+            .spawn((
+                Name::new("UI effect"),
+                ProcessImage::every_frame(input.clone(), output.clone()),
+                effect.effect.clone(),
+            ))
+            .id();
+
+        ui_image.texture = output;
+        processors.insert(entity, (processor, input));
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                update_ui_effects::<Blur>,
+                update_ui_effects::<LineJitter>,
+                update_ui_effects::<Pixelate>,
+                update_ui_effects::<Mask>,
+            ),
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend,
+    prepass::{EffectPrepasses, Prepasses},
+    resolution::Resolution,
+    sampler::SourceSampler,
+    Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const UNDERWATER_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2329870280052418455);
+
+#[derive(Resource)]
+pub(crate) struct UnderwaterData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for UnderwaterData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Underwater",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(UnderwaterUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            UNDERWATER_SHADER_HANDLE.typed(),
+        );
+
+        UnderwaterData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            UNDERWATER_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "underwater.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Underwater>()
+            .register_type::<UnderwaterDistortion>()
+            .register_type::<UnderwaterTint>()
+            .register_type::<UnderwaterMurk>()
+            .register_type::<UnderwaterCaustics>();
+        super::register_effect_types::<Underwater>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<Underwater>::default(),
+            UniformComponentPlugin::<UnderwaterUniform>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            super::prepass::configure_prepasses::<Underwater>,
+        );
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<Underwater>,
+            )
+            .init_resource::<UnderwaterData>()
+            .init_resource::<UniformBindGroup<UnderwaterUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<UnderwaterUniform>>();
+    }
+}
+
+impl EffectPrepasses for Underwater {
+    const WITHOUT: &'static str = "the murk will not look right";
+
+    fn prepasses(&self) -> Prepasses {
+        if self.murk.density > 0.0 {
+            Prepasses::DEPTH
+        } else {
+            Prepasses::NONE
+        }
+    }
+}
+
+fn prepare(
+    data: Res<UnderwaterData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<Underwater>,
+        &Blend<Underwater>,
+        &Resolution<Underwater>,
+        &SourceSampler<Underwater>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::underwater::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<UnderwaterUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<UnderwaterData>,
+    mut bind_group: ResMut<UniformBindGroup<UnderwaterUniform>>,
+    uniforms: Res<ComponentUniforms<UnderwaterUniform>>,
+    views: Query<Entity, With<UnderwaterUniform>>,
+) {
+    let _span = info_span!("vfx::underwater::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Underwater Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// The wavy distortion of an [`Underwater`] effect.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnderwaterDistortion {
+    /// How far the image is displaced, in UV space. `0.0` disables the distortion.
+    pub amplitude: f32,
+
+    /// How many waves fit across the view.
+    pub frequency: f32,
+
+    /// How fast the waves move.
+    pub speed: f32,
+}
+
+/// The color cast of an [`Underwater`] effect.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnderwaterTint {
+    /// The color the image is multiplied by.
+    /// The alpha channel is how strongly, where `0.0` disables the tint.
+    pub color: Color,
+}
+
+/// The depth-based murk of an [`Underwater`] effect.
+///
+/// This reads the depth prepass, so the camera needs a
+/// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass) component, which is added
+/// while the murk is dense at all. MSAA must be off (`Msaa::Off`).
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnderwaterMurk {
+    /// The color of the water far away.
+    /// The alpha channel controls how opaque the murk is at its thickest.
+    pub color: Color,
+
+    /// How quickly the murk thickens with distance. `0.0` disables the murk.
+    pub density: f32,
+}
+
+/// The flickering caustics of an [`Underwater`] effect, like light refracted by waves above.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnderwaterCaustics {
+    /// How much the caustics brighten the image. `0.0` disables the caustics.
+    pub intensity: f32,
+
+    /// How many caustic cells fit across the height of the view.
+    pub scale: f32,
+
+    /// How fast the caustics move.
+    pub speed: f32,
+}
+
+/// Makes the scene look like it is under water.
+///
+/// Combines wavy distortion, a color cast, depth-based murk, and caustics in a single pass,
+/// which is cheaper than composing separate effects.
+/// Each part has its own parameters, and can be disabled by setting its strength to zero.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct Underwater {
+    /// See [`UnderwaterDistortion`].
+    pub distortion: UnderwaterDistortion,
+
+    /// See [`UnderwaterTint`].
+    pub tint: UnderwaterTint,
+
+    /// See [`UnderwaterMurk`].
+    pub murk: UnderwaterMurk,
+
+    /// See [`UnderwaterCaustics`].
+    pub caustics: UnderwaterCaustics,
+}
+
+impl Default for Underwater {
+    fn default() -> Self {
+        Self {
+            distortion: UnderwaterDistortion {
+                amplitude: 0.004,
+                frequency: 8.0,
+                speed: 0.6,
+            },
+            tint: UnderwaterTint {
+                color: Color::rgba(0.3, 0.8, 0.9, 0.7),
+            },
+            murk: UnderwaterMurk {
+                color: Color::rgba(0.02, 0.2, 0.3, 1.0),
+                density: 0.06,
+            },
+            caustics: UnderwaterCaustics {
+                intensity: 0.25,
+                scale: 6.0,
+                speed: 0.8,
+            },
+        }
+    }
+}
+
+impl Display for Underwater {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Underwater distortion: {:.4}, tint: {:.2}, murk: {:.3}, caustics: {:.2}",
+            self.distortion.amplitude,
+            self.tint.color.a(),
+            self.murk.density,
+            self.caustics.intensity
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`Underwater`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct UnderwaterUniform {
+    pub(crate) tint: Vec4,
+    pub(crate) murk_color: Vec4,
+    pub(crate) distortion_amplitude: f32,
+    pub(crate) distortion_frequency: f32,
+    pub(crate) distortion_speed: f32,
+    pub(crate) murk_density: f32,
+    pub(crate) caustics_intensity: f32,
+    pub(crate) caustics_scale: f32,
+    pub(crate) caustics_speed: f32,
+
+    /// Used to turn depth values into view space distances.
+    pub(crate) inverse_projection: Mat4,
+}
+
+impl ExtractComponent for Underwater {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = UnderwaterUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::underwater::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(UnderwaterUniform {
+            tint: Vec4::from(settings.tint.color.as_linear_rgba_f32()),
+            murk_color: Vec4::from(settings.murk.color.as_linear_rgba_f32()),
+            distortion_amplitude: settings.distortion.amplitude,
+            distortion_frequency: settings.distortion.frequency,
+            distortion_speed: settings.distortion.speed,
+            murk_density: settings.murk.density,
+            caustics_intensity: settings.caustics.intensity,
+            caustics_scale: settings.caustics.scale,
+            caustics_speed: settings.caustics.speed,
+            inverse_projection: camera.projection_matrix().inverse(),
+        })
+    }
+}
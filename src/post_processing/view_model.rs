@@ -0,0 +1,147 @@
+use bevy::{
+    core_pipeline::{clear_color::ClearColorConfig, core_3d::Camera3dDepthLoadOp},
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    render::camera::{CameraOutputMode, CameraUpdateSystem},
+    window::PrimaryWindow,
+};
+
+/// Makes the camera draw a first person view model, e.g. the arms and weapon of the player,
+/// over the image of the camera drawing the world.
+///
+/// The view model has its own camera so it never clips into walls, and can have its own
+/// field of view. Put the view model on its own [`bevy::render::view::RenderLayers`],
+/// with this camera only seeing that layer and the world camera not seeing it,
+/// and make this camera a child of the world camera so they move together.
+///
+/// The world camera draws first, then this camera draws the view model over it,
+/// in front of the whole world. This camera is an overlay (see
+/// [`super::overlay::ApplyAfterCompositing`]), so which effects apply to what depends on
+/// the camera they are on:
+///
+/// - Effects on the world camera only apply to the world, e.g. [`super::depth_of_field::DepthOfField`]
+///   and [`super::motion_blur::MotionBlur`], which would smear the view model otherwise.
+/// - Effects on this camera only apply to the view model.
+/// - Effects on this camera with an [`super::overlay::ApplyAfterCompositing`] apply to the whole
+///   image, e.g. [`super::color_adjust::ColorAdjust`] and [`super::lut::Lut`] for grading.
+///
+/// Both cameras need the same render target, viewport and [`Camera::hdr`] to share the image,
+/// so these are kept in sync with the world camera, as is the order, so this camera draws last.
+/// Each camera tonemaps what it draws, before the view model is composited over the world.
+///
+/// For more cameras than these two, or other ways of combining them, see
+/// [`super::compositor::Compositor`].
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct ViewModel {
+    /// The camera drawing the world.
+    pub world_camera: Entity,
+}
+
+impl ViewModel {
+    /// Draws the view model over the image of the given camera.
+    pub fn new(world_camera: Entity) -> Self {
+        Self { world_camera }
+    }
+}
+
+impl FromWorld for ViewModel {
+    fn from_world(_world: &mut World) -> Self {
+        // Scenes set the actual entity when they are spawned.
+        Self::new(Entity::PLACEHOLDER)
+    }
+}
+
+impl MapEntities for ViewModel {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.world_camera = entity_mapper.get_or_reserve(self.world_camera);
+    }
+}
+
+type CameraItem = (&'static mut Camera, Option<&'static mut Camera3d>);
+
+/// Keeps the view model cameras drawing into the image of their world cameras.
+///
+/// Only changes what differs, so the cameras aren't marked as changed each frame.
+fn sync_view_models(
+    view_models: Query<(Entity, &ViewModel)>,
+    mut cameras: Query<CameraItem>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut warned: Local<bevy::utils::HashSet<Entity>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    for (entity, view_model) in &view_models {
+        let Ok([view_model_camera, world_camera]) =
+            cameras.get_many_mut([entity, view_model.world_camera])
+        else {
+            if warned.insert(entity) {
+                warn!(
+                    "The view model camera {entity:?} needs its world camera {:?} to be a different camera",
+                    view_model.world_camera
+                );
+            }
+            continue;
+        };
+
+        let (mut camera, camera_3d) = view_model_camera;
+        let (mut world, _) = world_camera;
+
+        if camera.target.normalize(primary_window) != world.target.normalize(primary_window) {
+            camera.target = world.target.clone();
+        }
+        if camera.hdr != world.hdr {
+            camera.hdr = world.hdr;
+        }
+        if camera.order <= world.order {
+            camera.order = world.order + 1;
+        }
+        if viewport_differs(&camera, &world) {
+            camera.viewport = world.viewport.clone();
+        }
+
+        // Draws the view model over the world, with its own depth so it is in front of everything.
+        if let Some(mut camera_3d) = camera_3d {
+            if !matches!(camera_3d.clear_color, ClearColorConfig::None) {
+                camera_3d.clear_color = ClearColorConfig::None;
+            }
+            if !matches!(camera_3d.depth_load_op, Camera3dDepthLoadOp::Clear(_)) {
+                camera_3d.depth_load_op = Camera3dDepthLoadOp::Clear(0.0);
+            }
+        }
+
+        // The view model camera writes the composited image, so the world camera doesn't have to.
+        let world_output = if camera.is_active {
+            CameraOutputMode::Skip
+        } else {
+            CameraOutputMode::default()
+        };
+        if std::mem::discriminant(&world.output_mode) != std::mem::discriminant(&world_output) {
+            world.output_mode = world_output;
+        }
+    }
+}
+
+fn viewport_differs(camera: &Camera, world: &Camera) -> bool {
+    match (&camera.viewport, &world.viewport) {
+        (None, None) => false,
+        (Some(a), Some(b)) => {
+            a.physical_position != b.physical_position
+                || a.physical_size != b.physical_size
+                || a.depth != b.depth
+        }
+        _ => true,
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ViewModel>()
+            .add_systems(PostUpdate, sync_view_models.before(CameraUpdateSystem));
+    }
+}
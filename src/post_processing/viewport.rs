@@ -0,0 +1,148 @@
+use bevy::{
+    core_pipeline::blit::{BlitPipeline, BlitPipelineKey},
+    prelude::*,
+    render::{
+        camera::ExtractedCamera,
+        render_phase::{RenderPhase, TrackedRenderPass},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindingResource, CachedRenderPipelineId, LoadOp,
+            Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            SpecializedRenderPipelines,
+        },
+        renderer::RenderContext,
+        view::ViewTarget,
+        Render, RenderSet,
+    },
+};
+
+use super::PostProcessingPhaseItem;
+
+/// Keeps what is outside the viewport of a camera intact while its effects draw, in the render world.
+///
+/// Cameras drawing into the same target share its main textures, e.g. the cameras of
+/// split-screen players. Effects are drawn over the whole texture, so they read the image,
+/// depth and history where the camera drew them, and are [`clip`]ped to the viewport.
+/// What is outside the viewport is copied into the other main texture once before the effects,
+/// so it stays the same however many times the effects swap the textures.
+#[derive(Component)]
+pub(crate) struct ViewportCopy {
+    pipeline_id: CachedRenderPipelineId,
+}
+
+impl ViewportCopy {
+    /// Copies the main texture into the other one.
+    pub(crate) fn copy(
+        &self,
+        render_context: &mut RenderContext,
+        pipeline_cache: &PipelineCache,
+        blit: &BlitPipeline,
+        view_target: &ViewTarget,
+    ) {
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(self.pipeline_id) else {
+            return;
+        };
+
+        let bind_group = render_context
+            .render_device()
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("Viewport copy bind group"),
+                layout: &blit.texture_bind_group,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(view_target.main_texture_view()),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&blit.sampler),
+                    },
+                ],
+            });
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("Viewport copy"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: view_target.main_texture_other_view(),
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// What a pass of an effect does with what is already in the texture it draws into.
+///
+/// Passes clipped to a viewport must keep what is outside of it, so they load instead of clearing.
+pub(crate) fn operations<V: Default>(camera: &ExtractedCamera) -> Operations<V> {
+    if camera.viewport.is_some() {
+        Operations {
+            load: LoadOp::Load,
+            store: true,
+        }
+    } else {
+        Operations::default()
+    }
+}
+
+/// Clips a pass of an effect to the viewport of the camera, if it has one.
+///
+/// The pass draws into a texture the size of the camera's target divided by `divisor`,
+/// which is more than one for effects drawn at a lower resolution.
+pub(crate) fn clip(render_pass: &mut TrackedRenderPass, camera: &ExtractedCamera, divisor: u32) {
+    let (Some(viewport), Some(size)) = (&camera.viewport, camera.physical_target_size) else {
+        return;
+    };
+
+    let size = UVec2::new(size.x.div_ceil(divisor), size.y.div_ceil(divisor));
+
+    // The viewport may still be the one of a larger window for a frame after a resize.
+    let start = (viewport.physical_position / divisor).min(size);
+    let end = ((viewport.physical_position + viewport.physical_size) / divisor).min(size);
+    let extent = (end - start).max(UVec2::ONE);
+
+    render_pass.set_scissor_rect(start.x, start.y, extent.x, extent.y);
+}
+
+fn queue(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    blit: Res<BlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    views: Query<
+        (Entity, &ExtractedCamera, &ViewTarget),
+        With<RenderPhase<PostProcessingPhaseItem>>,
+    >,
+) {
+    let _span = info_span!("vfx::viewport::queue").entered();
+
+    for (entity, camera, view_target) in &views {
+        if camera.viewport.is_none() {
+            continue;
+        }
+
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &blit,
+            BlitPipelineKey {
+                texture_format: view_target.main_texture_format(),
+                blend_state: None,
+                samples: 1,
+            },
+        );
+
+        commands.entity(entity).insert(ViewportCopy { pipeline_id });
+    }
+}
+
+/// Keeps the effects of cameras with a viewport inside of it.
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        super::render_app(app).add_systems(Render, queue.in_set(RenderSet::Queue));
+    }
+}
@@ -0,0 +1,305 @@
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+use std::fmt::Display;
+
+use crate::post_processing::DrawPostProcessingEffect;
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+    UniformBindGroup,
+};
+pub(crate) const VISION_MODE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9614708157002055465);
+
+#[derive(Resource)]
+pub(crate) struct VisionModeData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+}
+
+impl FromWorld for VisionModeData {
+    fn from_world(world: &mut World) -> Self {
+        let uniform_layout = super::create_layout(
+            world,
+            "Vision Mode",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(VisionModeUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+        VisionModeData {
+            uniform_layout,
+            shared_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            VISION_MODE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "vision_mode.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<VisionMode>();
+        app.register_type::<VisionModeVariant>();
+        super::register_effect_types::<VisionMode>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<VisionMode>::default(),
+            UniformComponentPlugin::<VisionModeUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<VisionMode>,
+            )
+            .init_resource::<VisionModeData>()
+            .init_resource::<UniformBindGroup<VisionModeUniform>>()
+            .init_resource::<SpecializedRenderPipelines<VisionModeData>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<VisionModeUniform>>();
+    }
+}
+
+impl SpecializedRenderPipeline for VisionModeData {
+    type Key = VisionModeVariant;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Vision Mode",
+            &self.shared_layout,
+            &self.uniform_layout,
+            VISION_MODE_SHADER_HANDLE.typed(),
+            vec![key.into()],
+        )
+    }
+}
+
+fn prepare(
+    data: Res<VisionModeData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<VisionModeData>>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<VisionMode>,
+        &Blend<VisionMode>,
+        &Resolution<VisionMode>,
+        &SourceSampler<VisionMode>,
+        &VisionModeVariant,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::vision_mode::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<VisionModeUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, *key);
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<VisionModeData>,
+    mut bind_group: ResMut<UniformBindGroup<VisionModeUniform>>,
+    uniforms: Res<ComponentUniforms<VisionModeUniform>>,
+    views: Query<Entity, With<VisionModeUniform>>,
+) {
+    let _span = info_span!("vfx::vision_mode::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Vision Mode Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Which kind of vision a [`VisionMode`] simulates.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum VisionModeVariant {
+    /// An image intensifier: amplified, tinted luminance with grain and a dark vignette.
+    #[default]
+    NightVision,
+
+    /// A thermal camera: luminance mapped to a false-color gradient,
+    /// from black through blue, purple, red, and yellow to white.
+    ///
+    /// Since there is no real temperature in the scene, bright things are shown as hot.
+    Thermal,
+}
+
+impl From<VisionModeVariant> for ShaderDefVal {
+    fn from(variant: VisionModeVariant) -> Self {
+        match variant {
+            VisionModeVariant::NightVision => "NIGHT_VISION",
+            VisionModeVariant::Thermal => "THERMAL",
+        }
+        .into()
+    }
+}
+
+/// Night vision or thermal vision, in a single effect.
+///
+/// This does the job of a color grade, grain, and a vignette at once.
+/// Start from [`VisionMode::night_vision`] or [`VisionMode::thermal`] and adjust from there.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct VisionMode {
+    /// How much the luminance of the scene is amplified.
+    /// Dark scenes need more.
+    pub gain: f32,
+
+    /// How much animated grain is added, where `0.0` is none.
+    pub noise: f32,
+
+    /// How dark the edges of the view are, where `0.0` is no vignette.
+    pub vignette: f32,
+
+    /// The color of the amplified image.
+    /// Only used by [`VisionModeVariant::NightVision`].
+    pub tint: Color,
+
+    /// Which [`VisionModeVariant`] to simulate.
+    pub variant: VisionModeVariant,
+}
+
+impl VisionMode {
+    /// Green phosphor night vision goggles.
+    pub fn night_vision() -> Self {
+        Self {
+            gain: 4.0,
+            noise: 0.15,
+            vignette: 1.2,
+            tint: Color::rgb(0.1, 1.0, 0.2),
+            variant: VisionModeVariant::NightVision,
+        }
+    }
+
+    /// A thermal camera.
+    pub fn thermal() -> Self {
+        Self {
+            gain: 1.5,
+            noise: 0.03,
+            vignette: 0.3,
+            tint: Color::WHITE,
+            variant: VisionModeVariant::Thermal,
+        }
+    }
+}
+
+impl Default for VisionMode {
+    fn default() -> Self {
+        Self::night_vision()
+    }
+}
+
+impl Display for VisionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Vision Mode {:?}, gain: {:.2}, noise: {:.2}, vignette: {:.2}, tint: {:?}",
+            self.variant, self.gain, self.noise, self.vignette, self.tint
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`VisionMode`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct VisionModeUniform {
+    pub(crate) tint: Vec4,
+    pub(crate) gain: f32,
+    pub(crate) noise: f32,
+    pub(crate) vignette: f32,
+}
+
+impl From<VisionMode> for VisionModeUniform {
+    fn from(vision_mode: VisionMode) -> Self {
+        Self {
+            tint: Vec4::from(vision_mode.tint.as_linear_rgba_f32()),
+            gain: vision_mode.gain,
+            noise: vision_mode.noise,
+            vignette: vision_mode.vignette,
+        }
+    }
+}
+
+impl ExtractComponent for VisionMode {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (VisionModeUniform, VisionModeVariant);
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::vision_mode::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some(((*settings).into(), settings.variant))
+    }
+}
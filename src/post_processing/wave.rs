@@ -11,7 +11,8 @@ pub(crate) use bevy::{
         render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
         render_resource::{
             BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
-            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
         },
         renderer::RenderDevice,
     },
@@ -19,9 +20,12 @@ pub(crate) use bevy::{
 
 use crate::post_processing::UniformBindGroup;
 
-use super::{DrawPostProcessingEffect, Order, PostProcessingPhaseItem};
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem,
+};
 
-const WAVE_SHADER_HANDLE: HandleUntyped =
+pub(crate) const WAVE_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1792660281364049744);
 
 /// Wave parameters.
@@ -31,7 +35,12 @@ const WAVE_SHADER_HANDLE: HandleUntyped =
 /// For example, if we have 1 wave in the X axis,
 /// we will have one part of the screen stretched towards the right
 /// horizontally, and one part stretched towards the left.
-#[derive(Default, Debug, Copy, Clone, Component, ShaderType)]
+///
+/// The shape of the waves is chosen via [`Wave::profile`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
 pub struct Wave {
     /// How many waves in the x axis.
     pub waves_x: f32,
@@ -50,17 +59,74 @@ pub struct Wave {
 
     /// How much displacement the y axis waves cause.
     pub amplitude_y: f32,
+
+    /// Which [`WaveProfile`] the waves have.
+    pub profile: WaveProfile,
+}
+
+/// The shape of the waves of a [`Wave`].
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum WaveProfile {
+    /// Smooth, rolling waves, like water.
+    #[default]
+    Sine,
+
+    /// Straight ramps up and down, with sharp turns. Feels mechanical.
+    Triangle,
+
+    /// Ramps up, then snaps back at once, like a broken signal.
+    Sawtooth,
+
+    /// Sine waves whose amplitude swells and fades irregularly, like an unsteady signal.
+    Noise,
+}
+
+impl WaveProfile {
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            WaveProfile::Sine => vec!["SINE".into()],
+            WaveProfile::Triangle => vec!["TRIANGLE".into()],
+            WaveProfile::Sawtooth => vec!["SAWTOOTH".into()],
+            WaveProfile::Noise => vec!["NOISE".into()],
+        }
+    }
+}
+
+#[doc(hidden)]
+/// [`Wave`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct WaveUniform {
+    pub(crate) waves_x: f32,
+    pub(crate) waves_y: f32,
+    pub(crate) speed_x: f32,
+    pub(crate) speed_y: f32,
+    pub(crate) amplitude_x: f32,
+    pub(crate) amplitude_y: f32,
+}
+
+impl From<Wave> for WaveUniform {
+    fn from(wave: Wave) -> Self {
+        Self {
+            waves_x: wave.waves_x,
+            waves_y: wave.waves_y,
+            speed_x: wave.speed_x,
+            speed_y: wave.speed_y,
+            amplitude_x: wave.amplitude_x,
+            amplitude_y: wave.amplitude_y,
+        }
+    }
 }
 
 #[derive(Resource)]
 pub(crate) struct WaveData {
-    pub pipeline_id: CachedRenderPipelineId,
     pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
 }
 
 impl FromWorld for WaveData {
     fn from_world(world: &mut World) -> Self {
-        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+        let uniform_layout = super::create_layout(
             world,
             "Wave",
             &[BindGroupLayoutEntry {
@@ -68,17 +134,20 @@ impl FromWorld for WaveData {
                 ty: BindingType::Buffer {
                     ty: BufferBindingType::Uniform,
                     has_dynamic_offset: true,
-                    min_binding_size: Some(Wave::min_size()),
+                    min_binding_size: Some(WaveUniform::min_size()),
                 },
                 visibility: ShaderStages::FRAGMENT,
                 count: None,
             }],
-            WAVE_SHADER_HANDLE.typed(),
         );
 
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
         WaveData {
-            pipeline_id,
             uniform_layout,
+            shared_layout,
         }
     }
 }
@@ -93,10 +162,14 @@ impl bevy::prelude::Plugin for Plugin {
             Shader::from_wgsl
         );
 
+        app.register_type::<Wave>();
+        app.register_type::<WaveProfile>();
+        super::register_effect_types::<Wave>(app);
+
         // This puts the uniform into the render world.
         app.add_plugins((
             ExtractComponentPlugin::<Wave>::default(),
-            UniformComponentPlugin::<Wave>::default(),
+            UniformComponentPlugin::<WaveUniform>::default(),
         ));
 
         super::render_app(app)
@@ -105,30 +178,60 @@ impl bevy::prelude::Plugin for Plugin {
                 super::extract_post_processing_camera_phases::<Wave>,
             )
             .init_resource::<WaveData>()
-            .init_resource::<UniformBindGroup<Wave>>()
+            .init_resource::<UniformBindGroup<WaveUniform>>()
+            .init_resource::<SpecializedRenderPipelines<WaveData>>()
             .add_systems(Render, prepare.in_set(RenderSet::Prepare))
             .add_systems(Render, queue.in_set(RenderSet::Queue))
-            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<Wave>>();
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<WaveUniform>>();
+    }
+}
+
+impl SpecializedRenderPipeline for WaveData {
+    type Key = WaveProfile;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Wave",
+            &self.shared_layout,
+            &self.uniform_layout,
+            WAVE_SHADER_HANDLE.typed(),
+            key.shader_defs(),
+        )
     }
 }
 
 fn prepare(
     data: Res<WaveData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<WaveData>>,
     mut views: Query<(
         Entity,
         &mut RenderPhase<PostProcessingPhaseItem>,
         &Order<Wave>,
+        &Blend<Wave>,
+        &Resolution<Wave>,
+        &SourceSampler<Wave>,
+        &WaveProfile,
     )>,
     draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
 ) {
-    for (entity, mut phase, order) in views.iter_mut() {
-        let draw_function = draw_functions.read().id::<DrawPostProcessingEffect<Wave>>();
+    let _span = info_span!("vfx::wave::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<WaveUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, *key);
 
         phase.add(PostProcessingPhaseItem {
             entity,
             sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
             draw_function,
-            pipeline_id: data.pipeline_id,
+            pipeline_id,
         });
     }
 }
@@ -136,10 +239,12 @@ fn prepare(
 fn queue(
     render_device: Res<RenderDevice>,
     data: Res<WaveData>,
-    mut bind_group: ResMut<UniformBindGroup<Wave>>,
-    uniforms: Res<ComponentUniforms<Wave>>,
-    views: Query<Entity, With<Wave>>,
+    mut bind_group: ResMut<UniformBindGroup<WaveUniform>>,
+    uniforms: Res<ComponentUniforms<WaveUniform>>,
+    views: Query<Entity, With<WaveUniform>>,
 ) {
+    let _span = info_span!("vfx::wave::queue").entered();
+
     bind_group.inner = None;
 
     if let Some(uniforms) = uniforms.binding() {
@@ -159,13 +264,40 @@ fn queue(
 impl ExtractComponent for Wave {
     type Query = (&'static Self, &'static Camera);
     type Filter = ();
-    type Out = Self;
+    type Out = (WaveUniform, WaveProfile);
 
     fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::wave::extract").entered();
+
         if !camera.is_active {
             return None;
         }
 
-        Some(*settings)
+        Some(((*settings).into(), settings.profile))
+    }
+}
+
+#[cfg(feature = "material2d")]
+impl super::material::MaterialEffect for Wave {
+    type Uniform = WaveUniform;
+    type Key = WaveProfile;
+
+    const MATERIAL_UUID: bevy::utils::Uuid =
+        bevy::utils::Uuid::from_u128(0xc63d7ba94cfa43fdb19e23085d820e32);
+
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        bevy::render::render_resource::ShaderRef::Handle(WAVE_SHADER_HANDLE.typed())
+    }
+
+    fn uniform(&self) -> Self::Uniform {
+        (*self).into()
+    }
+
+    fn key(&self) -> Self::Key {
+        self.profile
+    }
+
+    fn shader_defs(key: &Self::Key) -> Vec<ShaderDefVal> {
+        key.shader_defs()
     }
 }
@@ -0,0 +1,306 @@
+use std::fmt::Display;
+
+use bevy::render::{Render, RenderSet};
+pub(crate) use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, PipelineCache, RenderPipelineDescriptor, ShaderDefVal,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        },
+        renderer::RenderDevice,
+    },
+};
+
+use crate::post_processing::{DrawPostProcessingEffect, UniformBindGroup};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, Order, PostProcessingPhaseItem,
+};
+
+pub(crate) const WEATHER_OVERLAY_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 15764304620137394618);
+
+#[derive(Resource)]
+pub(crate) struct WeatherOverlayData {
+    pub uniform_layout: BindGroupLayout,
+    pub shared_layout: BindGroupLayout,
+}
+
+impl FromWorld for WeatherOverlayData {
+    fn from_world(world: &mut World) -> Self {
+        let uniform_layout = super::create_layout(
+            world,
+            "Weather Overlay",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(WeatherOverlayUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+        );
+
+        let shared_layout = world
+            .resource::<super::PostProcessingSharedLayout>()
+            .shared_layout
+            .clone();
+        WeatherOverlayData {
+            uniform_layout,
+            shared_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            WEATHER_OVERLAY_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "weather_overlay.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<WeatherOverlay>();
+        app.register_type::<WeatherVariant>();
+        super::register_effect_types::<WeatherOverlay>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<WeatherOverlay>::default(),
+            UniformComponentPlugin::<WeatherOverlayUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<WeatherOverlay>,
+            )
+            .init_resource::<WeatherOverlayData>()
+            .init_resource::<UniformBindGroup<WeatherOverlayUniform>>()
+            .init_resource::<SpecializedRenderPipelines<WeatherOverlayData>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<WeatherOverlayUniform>>();
+    }
+}
+
+impl SpecializedRenderPipeline for WeatherOverlayData {
+    type Key = WeatherVariant;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        super::render_pipeline_descriptor(
+            "Weather Overlay",
+            &self.shared_layout,
+            &self.uniform_layout,
+            WEATHER_OVERLAY_SHADER_HANDLE.typed(),
+            key.shader_defs(),
+        )
+    }
+}
+
+fn prepare(
+    data: Res<WeatherOverlayData>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<WeatherOverlayData>>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<WeatherOverlay>,
+        &Blend<WeatherOverlay>,
+        &Resolution<WeatherOverlay>,
+        &SourceSampler<WeatherOverlay>,
+        &WeatherVariant,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::weather_overlay::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler, key) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<WeatherOverlayUniform>>();
+
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &data, *key);
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<WeatherOverlayData>,
+    mut bind_group: ResMut<UniformBindGroup<WeatherOverlayUniform>>,
+    uniforms: Res<ComponentUniforms<WeatherOverlayUniform>>,
+    views: Query<Entity, With<WeatherOverlayUniform>>,
+) {
+    let _span = info_span!("vfx::weather_overlay::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Weather Overlay Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// What falls in a [`WeatherOverlay`].
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum WeatherVariant {
+    /// Streaks of rain.
+    #[default]
+    Rain,
+
+    /// Snowflakes, drifting from side to side as they fall.
+    Snow,
+}
+
+impl WeatherVariant {
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        match self {
+            WeatherVariant::Rain => vec!["RAIN".into()],
+            WeatherVariant::Snow => vec!["SNOW".into()],
+        }
+    }
+}
+
+/// Rain or snow falling in front of the camera, drawn in screen space.
+///
+/// Far cheaper than particles in the world, so it suits weather in the background,
+/// or weather on low end hardware. The weather does not react to the scene,
+/// e.g. it also falls under a roof.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct WeatherOverlay {
+    /// Whether it rains or snows.
+    pub variant: WeatherVariant,
+
+    /// How much falls, between `0.0` (nothing) and `1.0` (a downpour).
+    pub density: f32,
+
+    /// How fast it falls. `1.0` is a natural speed for the [`WeatherVariant`].
+    pub speed: f32,
+
+    /// How far it falls from straight down, in degrees, as if blown by the wind.
+    /// Positive values blow it to the right.
+    pub angle: f32,
+
+    /// How visible the drops or flakes are, between `0.0` and `1.0`.
+    pub opacity: f32,
+
+    /// The color of the drops or flakes.
+    pub color: Color,
+}
+
+impl WeatherOverlay {
+    /// Rain, falling slightly at an angle.
+    pub fn rain() -> Self {
+        Self {
+            variant: WeatherVariant::Rain,
+            density: 0.5,
+            speed: 1.0,
+            angle: 10.0,
+            opacity: 0.4,
+            color: Color::rgb(0.8, 0.85, 0.9),
+        }
+    }
+
+    /// Snow, falling gently.
+    pub fn snow() -> Self {
+        Self {
+            variant: WeatherVariant::Snow,
+            density: 0.4,
+            speed: 1.0,
+            angle: 0.0,
+            opacity: 0.8,
+            color: Color::WHITE,
+        }
+    }
+}
+
+impl Default for WeatherOverlay {
+    fn default() -> Self {
+        Self::rain()
+    }
+}
+
+impl Display for WeatherOverlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Weather Overlay {:?}, density: {:.2}, speed: {:.2}, angle: {:.0}°, opacity: {:.2}, color: {:?}",
+            self.variant, self.density, self.speed, self.angle, self.opacity, self.color
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`WeatherOverlay`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct WeatherOverlayUniform {
+    pub(crate) color: Vec4,
+    pub(crate) density: f32,
+    pub(crate) speed: f32,
+    pub(crate) angle: f32,
+    pub(crate) opacity: f32,
+}
+
+impl ExtractComponent for WeatherOverlay {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = (WeatherOverlayUniform, WeatherVariant);
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::weather_overlay::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((
+            WeatherOverlayUniform {
+                color: Vec4::from(settings.color.as_linear_rgba_f32()),
+                density: settings.density,
+                speed: settings.speed,
+                angle: settings.angle.to_radians(),
+                opacity: settings.opacity,
+            },
+            settings.variant,
+        ))
+    }
+}
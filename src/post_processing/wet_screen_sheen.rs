@@ -0,0 +1,255 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const WET_SCREEN_SHEEN_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10545200048217160881);
+
+#[derive(Resource)]
+pub(crate) struct WetScreenSheenData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for WetScreenSheenData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "Wet Screen Sheen",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(WetScreenSheenUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            WET_SCREEN_SHEEN_SHADER_HANDLE.typed(),
+        );
+
+        WetScreenSheenData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            WET_SCREEN_SHEEN_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "wet_screen_sheen.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<WetScreenSheen>();
+        super::register_effect_types::<WetScreenSheen>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<WetScreenSheen>::default(),
+            UniformComponentPlugin::<WetScreenSheenUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<WetScreenSheen>,
+            )
+            .init_resource::<WetScreenSheenData>()
+            .init_resource::<UniformBindGroup<WetScreenSheenUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<WetScreenSheenUniform>>(
+            );
+    }
+}
+
+fn prepare(
+    data: Res<WetScreenSheenData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<WetScreenSheen>,
+        &Blend<WetScreenSheen>,
+        &Resolution<WetScreenSheen>,
+        &SourceSampler<WetScreenSheen>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::wet_screen_sheen::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<WetScreenSheenUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<WetScreenSheenData>,
+    mut bind_group: ResMut<UniformBindGroup<WetScreenSheenUniform>>,
+    uniforms: Res<ComponentUniforms<WetScreenSheenUniform>>,
+    views: Query<Entity, With<WetScreenSheenUniform>>,
+) {
+    let _span = info_span!("vfx::wet_screen_sheen::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("Wet Screen Sheen Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Makes the scene look wet, as if it just rained.
+///
+/// Brightens highlights so surfaces look glossy, and smears faint vertical reflections of
+/// the highlights downwards in the lower part of the image, like lights reflected in puddles
+/// and wet streets. It works in screen space, so it is cheap but does not know where the ground is.
+///
+/// Drive [`WetScreenSheen::wetness`] from a weather system to make the scene dry off,
+/// e.g. from [`super::raindrops::Raindrops::wetness`].
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct WetScreenSheen {
+    /// How wet the scene is, from `0.0` (dry, the image is unchanged) to `1.0` (soaked).
+    /// Scales the highlights and the reflections.
+    pub wetness: f32,
+
+    /// How bright parts of the image must be to count as highlights, between `0.0` and `1.0`.
+    pub threshold: f32,
+
+    /// How much the highlights are brightened when soaked.
+    pub highlight_boost: f32,
+
+    /// How strong the reflections are when soaked.
+    pub reflection_strength: f32,
+
+    /// How far the reflections smear downwards, as a fraction of the height of the image.
+    pub reflection_length: f32,
+
+    /// Where the reflections start, as a fraction of the height of the image from the top.
+    /// `0.5` keeps them to the lower half.
+    pub horizon: f32,
+}
+
+impl Default for WetScreenSheen {
+    fn default() -> Self {
+        Self {
+            wetness: 1.0,
+            threshold: 0.6,
+            highlight_boost: 0.4,
+            reflection_strength: 0.35,
+            reflection_length: 0.15,
+            horizon: 0.5,
+        }
+    }
+}
+
+impl Display for WetScreenSheen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Wet Screen Sheen wetness: {:.2}, threshold: {:.2}, highlight boost: {:.2}, reflection strength: {:.2}, reflection length: {:.2}, horizon: {:.2}",
+            self.wetness,
+            self.threshold,
+            self.highlight_boost,
+            self.reflection_strength,
+            self.reflection_length,
+            self.horizon
+        )
+    }
+}
+
+#[doc(hidden)]
+/// [`WetScreenSheen`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct WetScreenSheenUniform {
+    pub(crate) wetness: f32,
+    pub(crate) threshold: f32,
+    pub(crate) highlight_boost: f32,
+    pub(crate) reflection_strength: f32,
+    pub(crate) reflection_length: f32,
+    pub(crate) horizon: f32,
+}
+
+impl From<WetScreenSheen> for WetScreenSheenUniform {
+    fn from(sheen: WetScreenSheen) -> Self {
+        Self {
+            wetness: sheen.wetness.clamp(0.0, 1.0),
+            threshold: sheen.threshold,
+            highlight_boost: sheen.highlight_boost,
+            reflection_strength: sheen.reflection_strength,
+            reflection_length: sheen.reflection_length,
+            horizon: sheen.horizon,
+        }
+    }
+}
+
+impl ExtractComponent for WetScreenSheen {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = WetScreenSheenUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::wet_screen_sheen::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_component::{
+            ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
+        },
+        render_phase::{AddRenderCommand, DrawFunctions, RenderPhase},
+        render_resource::{
+            BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+            BindingType, BufferBindingType, CachedRenderPipelineId, ShaderStages, ShaderType,
+        },
+        renderer::RenderDevice,
+        Render, RenderSet,
+    },
+};
+
+use super::{
+    blend::Blend, resolution::Resolution, sampler::SourceSampler, DrawPostProcessingEffect, Order,
+    PostProcessingPhaseItem, UniformBindGroup,
+};
+
+pub(crate) const WHITE_BALANCE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9386627370156404031);
+
+#[derive(Resource)]
+pub(crate) struct WhiteBalanceData {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for WhiteBalanceData {
+    fn from_world(world: &mut World) -> Self {
+        let (uniform_layout, pipeline_id) = super::create_layout_and_pipeline(
+            world,
+            "White Balance",
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(WhiteBalanceUniform::min_size()),
+                },
+                visibility: ShaderStages::FRAGMENT,
+                count: None,
+            }],
+            WHITE_BALANCE_SHADER_HANDLE.typed(),
+        );
+
+        WhiteBalanceData {
+            pipeline_id,
+            uniform_layout,
+        }
+    }
+}
+
+pub(crate) struct Plugin;
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            WHITE_BALANCE_SHADER_HANDLE,
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/assets/shaders/",
+                "white_balance.wgsl"
+            ),
+            Shader::from_wgsl
+        );
+
+        app.register_type::<WhiteBalance>();
+        super::register_effect_types::<WhiteBalance>(app);
+
+        // This puts the uniform into the render world.
+        app.add_plugins((
+            ExtractComponentPlugin::<WhiteBalance>::default(),
+            UniformComponentPlugin::<WhiteBalanceUniform>::default(),
+        ));
+
+        super::render_app(app)
+            .add_systems(
+                ExtractSchedule,
+                super::extract_post_processing_camera_phases::<WhiteBalance>,
+            )
+            .init_resource::<WhiteBalanceData>()
+            .init_resource::<UniformBindGroup<WhiteBalanceUniform>>()
+            .add_systems(Render, prepare.in_set(RenderSet::Prepare))
+            .add_systems(Render, queue.in_set(RenderSet::Queue))
+            .add_render_command::<PostProcessingPhaseItem, DrawPostProcessingEffect<WhiteBalanceUniform>>();
+    }
+}
+
+fn prepare(
+    data: Res<WhiteBalanceData>,
+    mut views: Query<(
+        Entity,
+        &mut RenderPhase<PostProcessingPhaseItem>,
+        &Order<WhiteBalance>,
+        &Blend<WhiteBalance>,
+        &Resolution<WhiteBalance>,
+        &SourceSampler<WhiteBalance>,
+    )>,
+    draw_functions: Res<DrawFunctions<PostProcessingPhaseItem>>,
+) {
+    let _span = info_span!("vfx::white_balance::prepare").entered();
+
+    for (entity, mut phase, order, blend, resolution, sampler) in views.iter_mut() {
+        let draw_function = draw_functions
+            .read()
+            .id::<DrawPostProcessingEffect<WhiteBalanceUniform>>();
+
+        phase.add(PostProcessingPhaseItem {
+            entity,
+            sort_key: (*order).into(),
+            blend: (*blend).into(),
+            resolution: (*resolution).into(),
+            sampler: (*sampler).into(),
+            draw_function,
+            pipeline_id: data.pipeline_id,
+        });
+    }
+}
+
+fn queue(
+    render_device: Res<RenderDevice>,
+    data: Res<WhiteBalanceData>,
+    mut bind_group: ResMut<UniformBindGroup<WhiteBalanceUniform>>,
+    uniforms: Res<ComponentUniforms<WhiteBalanceUniform>>,
+    views: Query<Entity, With<WhiteBalanceUniform>>,
+) {
+    let _span = info_span!("vfx::white_balance::queue").entered();
+
+    bind_group.inner = None;
+
+    if let Some(uniforms) = uniforms.binding() {
+        if !views.is_empty() {
+            bind_group.inner = Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("White Balance Uniform Bind Group"),
+                layout: &data.uniform_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.clone(),
+                }],
+            }));
+        }
+    }
+}
+
+/// Warms or cools the image, like the white balance of a camera.
+///
+/// The balance is applied in LMS, the space of the eye's cone responses,
+/// so colors shift the way they do when the eye adapts to the color of the light.
+/// The defaults leave the image unchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[reflect(Component, Default)]
+pub struct WhiteBalance {
+    /// The color temperature of the light the image is balanced for, in Kelvin.
+    ///
+    /// `6500.0` leaves the image unchanged. As with a camera, higher values warm the image
+    /// (e.g. `9000.0` for a sunset mood) and lower values cool it (e.g. `4000.0` for moonlight).
+    /// Clamped to between `1667.0` and `25000.0`.
+    pub temperature: f32,
+
+    /// Shifts the image between green and magenta. `0.0` leaves the image unchanged,
+    /// positive values are more magenta and negative values more green.
+    ///
+    /// Values between `-1.0` and `1.0` are sensible.
+    pub tint: f32,
+}
+
+impl Default for WhiteBalance {
+    fn default() -> Self {
+        Self {
+            temperature: NEUTRAL_TEMPERATURE,
+            tint: 0.0,
+        }
+    }
+}
+
+impl Display for WhiteBalance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "White Balance temperature: {:.0}K, tint: {:.2}",
+            self.temperature, self.tint
+        )
+    }
+}
+
+/// The temperature which leaves the image unchanged, close to the white point of sRGB.
+const NEUTRAL_TEMPERATURE: f32 = 6500.0;
+
+/// Converts CIE XYZ to LMS cone responses (CAT02).
+const XYZ_TO_LMS: Mat3 = Mat3::from_cols_array(&[
+    0.7328, -0.7036, 0.0030, //
+    0.4296, 1.6975, 0.0136, //
+    -0.1624, 0.0061, 0.9834,
+]);
+
+/// The chromaticity of a black body at the given temperature, shifted by the tint.
+///
+/// Uses the approximation of the Planckian locus by Kim et al.
+fn chromaticity(temperature: f32, tint: f32) -> Vec2 {
+    let t = temperature.clamp(1667.0, 25000.0);
+    let (t2, t3) = (t * t, t * t * t);
+
+    let x = if t <= 4000.0 {
+        -0.266_123_9e9 / t3 - 0.234_358_9e6 / t2 + 0.877_695_6e3 / t + 0.179_910
+    } else {
+        -3.025_846_9e9 / t3 + 2.107_038e6 / t2 + 0.222_634_7e3 / t + 0.240_390
+    };
+
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.106_381_4 * x3 - 1.348_110_2 * x2 + 2.185_558_3 * x - 0.202_196_83
+    } else if t <= 4000.0 {
+        -0.954_947_6 * x3 - 1.374_185_9 * x2 + 2.091_37 * x - 0.167_488_67
+    } else {
+        3.081_758 * x3 - 5.873_387 * x2 + 3.751_13 * x - 0.370_014_83
+    };
+
+    // Green is up the y axis. The image is balanced for a greener light by making it more magenta.
+    Vec2::new(x, y + tint * 0.05)
+}
+
+/// The cone responses to white at the given chromaticity.
+fn white_lms(xy: Vec2) -> Vec3 {
+    let xyz = Vec3::new(xy.x / xy.y, 1.0, (1.0 - xy.x - xy.y) / xy.y);
+    XYZ_TO_LMS * xyz
+}
+
+#[doc(hidden)]
+/// [`WhiteBalance`] as a uniform.
+#[derive(Debug, ShaderType, Clone, Component, Copy)]
+pub struct WhiteBalanceUniform {
+    /// What the cone responses are multiplied with.
+    pub(crate) balance: Vec3,
+}
+
+impl From<WhiteBalance> for WhiteBalanceUniform {
+    fn from(white_balance: WhiteBalance) -> Self {
+        // White under the given light is balanced to look like white under the neutral light.
+        let neutral = white_lms(chromaticity(NEUTRAL_TEMPERATURE, 0.0));
+        let light = white_lms(chromaticity(white_balance.temperature, white_balance.tint));
+
+        Self {
+            balance: neutral / light,
+        }
+    }
+}
+
+impl ExtractComponent for WhiteBalance {
+    type Query = (&'static Self, &'static Camera);
+    type Filter = ();
+    type Out = WhiteBalanceUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let _span = info_span!("vfx::white_balance::extract").entered();
+
+        if !camera.is_active {
+            return None;
+        }
+
+        Some((*settings).into())
+    }
+}
@@ -0,0 +1,273 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    ecs::{system::Command, world::EntityMut},
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    utils::{BoxedFuture, HashSet},
+};
+
+use crate::{
+    post_processing::{
+        dead_pixels::DeadPixels,
+        external::{ExternalEffectEntry, ExternalEffects},
+        fog_2d::Fog2d,
+        god_rays_2d::GodRays2d,
+        heat_haze::HeatHaze,
+        palette_swap::{BuiltInPalette, PaletteSwap},
+        stable_regions::StableRegions,
+    },
+    state::{EffectEntry, EffectState},
+};
+
+/// A stack of effects with their parameters and orders, authored in a `.vfx.ron` file.
+///
+/// Load it via the asset server and put the handle on a camera to apply it:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::preset::EffectPreset;
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         Camera3dBundle::default(),
+///         asset_server.load::<EffectPreset, _>("presets/underwater.vfx.ron"),
+///     ));
+/// }
+/// ```
+///
+/// The effects on the camera are made to match the preset, so effects which aren't
+/// in the preset are removed. When the file changes and the asset server watches for changes,
+/// the preset is applied again, so editing the file live-updates the camera.
+///
+/// A preset file lists the effects along with optional orders. Parameters which are left out
+/// keep their defaults:
+///
+/// ```ron
+/// (
+///     effects: [
+///         (effect: Underwater(())),
+///         (effect: Wave((waves_x: 2.0, speed_x: 1.0, amplitude_x: 0.01)), order: Some(1.0)),
+///         (effect: Mask((strength: 1.2, variant: Vignette))),
+///     ],
+///     stable_regions: Some((rects: [(min: (0.0, 0.9), max: (1.0, 1.0))])),
+/// )
+/// ```
+///
+/// Effects of other crates (see [`crate::post_processing::external::EffectPlugin`]) are listed
+/// by name, along with their numeric parameters:
+///
+/// ```ron
+/// (
+///     external: [
+///         (name: "Sepia", parameters: {"strength": 0.8}, order: Some(2.0)),
+///     ],
+/// )
+/// ```
+///
+/// Effects which refer to assets, such as [`crate::post_processing::lut::Lut`], can't be part
+/// of a preset, the mask of a [`HeatHaze`] is left out, and the occluders of a [`GodRays2d`]
+/// keep their default. A [`PaletteSwap`] uses one of the built-in palettes, see
+/// [`PaletteSwapPreset`].
+#[derive(
+    Debug, Clone, Default, PartialEq, TypeUuid, TypePath, serde::Serialize, serde::Deserialize,
+)]
+#[uuid = "b4b0f9a6-5c1e-4a36-9d1e-2f7c3b8e6a41"]
+#[serde(default)]
+pub struct EffectPreset {
+    /// The effects, with their parameters and orders.
+    pub effects: Vec<EffectEntry>,
+
+    /// The [`StableRegions`], if any.
+    pub stable_regions: Option<StableRegions>,
+
+    /// The [`DeadPixels`], if any.
+    pub dead_pixels: Option<DeadPixels>,
+
+    /// The [`Fog2d`], if any.
+    pub fog_2d: Option<Fog2d>,
+
+    /// The [`GodRays2d`], if any.
+    pub god_rays_2d: Option<GodRays2d>,
+
+    /// The [`HeatHaze`], if any.
+    pub heat_haze: Option<HeatHaze>,
+
+    /// The [`PaletteSwap`], if any.
+    pub palette_swap: Option<PaletteSwapPreset>,
+
+    /// The effects of other crates, see [`ExternalEffects`].
+    pub external: Vec<ExternalEffectEntry>,
+}
+
+impl EffectPreset {
+    /// Parse a preset from the contents of a `.vfx.ron` file.
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+
+    /// The effects of the preset which can be part of an [`EffectState`].
+    pub fn state(&self) -> EffectState {
+        self.effects
+            .iter()
+            .fold(EffectState::default(), |state, entry| {
+                state.with(entry.effect, entry.order)
+            })
+    }
+
+    /// Make the effects on the given entity match this preset.
+    pub fn apply(&self, entity: &mut EntityMut) {
+        self.state().apply(entity);
+
+        set_or_remove(entity, self.stable_regions.clone());
+        set_or_remove(entity, self.dead_pixels.clone());
+        set_or_remove(entity, self.fog_2d.clone());
+        set_or_remove(entity, self.god_rays_2d);
+        set_or_remove(entity, self.heat_haze.clone());
+        set_or_remove(entity, self.palette_swap.map(PaletteSwap::from));
+
+        // The registry is cloned since the entity borrows the world.
+        let external = entity.world().get_resource::<ExternalEffects>().cloned();
+        if let Some(external) = external {
+            external.apply(entity, &self.external);
+        }
+    }
+}
+
+/// A [`PaletteSwap`] in an [`EffectPreset`], with one of the built-in palettes:
+///
+/// ```ron
+/// (
+///     palette_swap: Some((palette: Nes, dither: 0.5)),
+/// )
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PaletteSwapPreset {
+    /// The palette to limit the image to.
+    pub palette: BuiltInPalette,
+
+    /// See [`PaletteSwap::dither`].
+    pub dither: f32,
+
+    /// See [`PaletteSwap::dither_scale`].
+    pub dither_scale: f32,
+}
+
+impl Default for PaletteSwapPreset {
+    fn default() -> Self {
+        let PaletteSwap {
+            dither,
+            dither_scale,
+            ..
+        } = PaletteSwap::default();
+
+        Self {
+            palette: BuiltInPalette::default(),
+            dither,
+            dither_scale,
+        }
+    }
+}
+
+impl From<PaletteSwapPreset> for PaletteSwap {
+    fn from(preset: PaletteSwapPreset) -> Self {
+        Self {
+            dither: preset.dither,
+            dither_scale: preset.dither_scale,
+            ..Self::built_in(preset.palette)
+        }
+    }
+}
+
+fn set_or_remove<C: Component>(entity: &mut EntityMut, effect: Option<C>) {
+    match effect {
+        Some(effect) => {
+            entity.insert(effect);
+        }
+        None => {
+            entity.remove::<C>();
+        }
+    }
+}
+
+/// A command which applies an [`EffectPreset`] to an entity.
+///
+/// Use via `commands.add(ApplyEffectPreset { entity, preset })`.
+#[derive(Debug, Clone)]
+pub struct ApplyEffectPreset {
+    /// The entity to apply the preset to, typically a camera.
+    pub entity: Entity,
+
+    /// The preset to apply.
+    pub preset: EffectPreset,
+}
+
+impl Command for ApplyEffectPreset {
+    fn apply(self, world: &mut World) {
+        if let Some(mut entity) = world.get_entity_mut(self.entity) {
+            self.preset.apply(&mut entity);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EffectPresetLoader;
+
+impl AssetLoader for EffectPresetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let preset: EffectPreset = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(preset));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["vfx.ron"]
+    }
+}
+
+/// Applies presets to cameras when their handle is added or changed, and when they are
+/// (re)loaded.
+fn apply_presets(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<EffectPreset>>,
+    presets: Res<Assets<EffectPreset>>,
+    cameras: Query<(Entity, Ref<Handle<EffectPreset>>)>,
+) {
+    let loaded: HashSet<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle.id()),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+
+    for (entity, handle) in &cameras {
+        if !handle.is_changed() && !loaded.contains(&handle.id()) {
+            continue;
+        }
+
+        if let Some(preset) = presets.get(&handle) {
+            commands.add(ApplyEffectPreset {
+                entity,
+                preset: preset.clone(),
+            });
+        }
+    }
+}
+
+/// Loads and applies [`EffectPreset`]s.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<EffectPreset>()
+            .init_asset_loader::<EffectPresetLoader>()
+            .add_systems(PostUpdate, apply_presets);
+    }
+}
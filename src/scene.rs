@@ -0,0 +1,171 @@
+use bevy::{
+    asset::LoadState,
+    ecs::{
+        entity::EntityMap,
+        reflect::ReflectMapEntities,
+        system::{Command, SystemParam},
+    },
+    prelude::*,
+    scene::DynamicScene,
+};
+
+/// Spawns the effects of a scene onto existing cameras, e.g. an effect stack saved as a
+/// `.scn.ron` file by an editor.
+///
+/// Bevy's own scene spawner spawns new entities. This puts the components of the scene onto
+/// the given camera instead, once the scene is loaded:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::scene::PostProcessingSceneSpawner;
+/// fn setup(mut commands: Commands, mut spawner: PostProcessingSceneSpawner) {
+///     let camera = commands.spawn(Camera3dBundle::default()).id();
+///     spawner.load("scenes/wavy.scn.ron", camera);
+/// }
+/// ```
+///
+/// The scene lists the effects as components of an entity, along with the components which
+/// go with them, such as [`crate::post_processing::Order`]:
+///
+/// ```ron
+/// (
+///     resources: {},
+///     entities: {
+///         0: (
+///             components: {
+///                 "bevy_vfx_bag::post_processing::wave::Wave": (
+///                     waves_x: 2.0,
+///                     waves_y: 0.0,
+///                     speed_x: 1.0,
+///                     speed_y: 0.0,
+///                     amplitude_x: 0.01,
+///                     amplitude_y: 0.0,
+///                     profile: Sine,
+///                 ),
+///                 "bevy_vfx_bag::post_processing::Order<bevy_vfx_bag::post_processing::wave::Wave>": (
+///                     order: 1.0,
+///                     stage: AfterTonemapping,
+///                 ),
+///             },
+///         ),
+///     },
+/// )
+/// ```
+///
+/// Effects already on the camera are changed to match the scene, and other effects are kept.
+/// The components of all entities of the scene go onto the camera, and components referring to
+/// entities of the scene refer to the camera. Resources of the scene are ignored.
+/// Effects which refer to assets, such as [`crate::post_processing::lut::Lut`], don't load them.
+#[derive(SystemParam)]
+pub struct PostProcessingSceneSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl<'w, 's> PostProcessingSceneSpawner<'w, 's> {
+    /// Spawn the effects of the scene onto the camera, once the scene is loaded.
+    pub fn spawn(&mut self, scene: Handle<DynamicScene>, camera: Entity) {
+        self.commands.entity(camera).insert(EffectScene(scene));
+    }
+
+    /// Load the scene at the given asset path, and spawn its effects onto the camera.
+    pub fn load(&mut self, path: &str, camera: Entity) -> Handle<DynamicScene> {
+        let scene = self.asset_server.load(path);
+        self.spawn(scene.clone(), camera);
+        scene
+    }
+}
+
+/// A scene which is yet to be spawned onto this camera, see [`PostProcessingSceneSpawner`].
+#[derive(Debug, Clone, Component)]
+struct EffectScene(Handle<DynamicScene>);
+
+/// A command which puts the components of a loaded [`DynamicScene`] onto an entity.
+///
+/// [`PostProcessingSceneSpawner`] waits for the scene to load and uses this.
+#[derive(Debug, Clone)]
+pub struct ApplyEffectScene {
+    /// The entity to put the components onto, typically a camera.
+    pub entity: Entity,
+
+    /// The scene, which must be loaded.
+    pub scene: Handle<DynamicScene>,
+}
+
+impl Command for ApplyEffectScene {
+    fn apply(self, world: &mut World) {
+        world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            let Some(scene) = scenes.get(&self.scene) else {
+                warn!("The effect scene for {:?} is not loaded", self.entity);
+                return;
+            };
+            if world.get_entity(self.entity).is_none() {
+                return;
+            }
+
+            let registry = world.resource::<AppTypeRegistry>().clone();
+            let registry = registry.read();
+
+            let mut entity_map = EntityMap::default();
+            for scene_entity in &scene.entities {
+                entity_map.insert(scene_entity.entity, self.entity);
+            }
+
+            for component in scene.entities.iter().flat_map(|entity| &entity.components) {
+                let Some(registration) = registry.get_with_name(component.type_name()) else {
+                    warn!(
+                        "Skipping {} of the effect scene, it is not registered",
+                        component.type_name()
+                    );
+                    continue;
+                };
+                let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                    warn!(
+                        "Skipping {} of the effect scene, it is not a reflected component",
+                        component.type_name()
+                    );
+                    continue;
+                };
+
+                reflect_component.apply_or_insert(&mut world.entity_mut(self.entity), &**component);
+
+                if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+                    map_entities.map_entities(world, &mut entity_map, &[self.entity]);
+                }
+            }
+        });
+    }
+}
+
+/// Applies the scenes of [`PostProcessingSceneSpawner`] once they are loaded.
+fn spawn_effect_scenes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    scenes: Res<Assets<DynamicScene>>,
+    cameras: Query<(Entity, &EffectScene)>,
+) {
+    for (entity, EffectScene(scene)) in &cameras {
+        if scenes.contains(scene) {
+            commands.add(ApplyEffectScene {
+                entity,
+                scene: scene.clone(),
+            });
+        } else if asset_server.get_load_state(scene) == LoadState::Failed {
+            error!("Could not load the effect scene for {entity:?}");
+        } else {
+            continue;
+        }
+
+        commands.entity(entity).remove::<EffectScene>();
+    }
+}
+
+/// Spawns the effects of scenes onto cameras.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, spawn_effect_scenes);
+    }
+}
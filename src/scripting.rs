@@ -0,0 +1,266 @@
+use std::fmt::Display;
+
+use bevy::{
+    ecs::system::Command,
+    prelude::*,
+    reflect::{GetPath, TypeInfo},
+};
+
+use crate::{
+    one_shot::OneShot,
+    state::{EffectKind, EffectState},
+    util,
+};
+
+/// Why a [`ScriptCommand`] or query failed.
+///
+/// Meant to be turned into an error on the script side.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    /// There is no effect with this name, see [`effect_names`].
+    UnknownEffect(String),
+
+    /// The entity does not exist.
+    NoSuchEntity(Entity),
+
+    /// The effect is not on the entity, so its parameters can't be accessed.
+    EffectNotActive {
+        /// The entity.
+        entity: Entity,
+
+        /// The effect.
+        effect: EffectKind,
+    },
+
+    /// The parameter does not exist, or is not a number.
+    BadParameter {
+        /// The effect.
+        effect: EffectKind,
+
+        /// The path of the parameter.
+        field: String,
+
+        /// What went wrong.
+        reason: String,
+    },
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownEffect(name) => write!(f, "unknown effect `{name}`"),
+            Self::NoSuchEntity(entity) => write!(f, "entity {entity:?} does not exist"),
+            Self::EffectNotActive { entity, effect } => {
+                write!(f, "entity {entity:?} does not have `{}`", effect.name())
+            }
+            Self::BadParameter {
+                effect,
+                field,
+                reason,
+            } => write!(f, "can't access `{field}` of `{}`: {reason}", effect.name()),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+fn lookup(effect: &str) -> Result<EffectKind, ScriptError> {
+    EffectKind::from_name(effect).ok_or_else(|| ScriptError::UnknownEffect(effect.to_string()))
+}
+
+/// The names of all effects scripts can use.
+pub fn effect_names() -> impl Iterator<Item = &'static str> {
+    EffectKind::ALL.iter().map(|kind| kind.name())
+}
+
+/// The names of the parameters of an effect.
+///
+/// Effects which are enums (such as [`crate::post_processing::flip::Flip`]) have none.
+pub fn effect_parameters(effect: &str) -> Result<Vec<&'static str>, ScriptError> {
+    let effect = lookup(effect)?.default_effect();
+
+    match effect.as_reflect().get_represented_type_info() {
+        Some(TypeInfo::Struct(info)) => Ok(info.field_names().to_vec()),
+        _ => Ok(vec![]),
+    }
+}
+
+/// The effects currently on an entity.
+pub fn active_effects(world: &World, entity: Entity) -> Result<EffectState, ScriptError> {
+    let entity = world
+        .get_entity(entity)
+        .ok_or(ScriptError::NoSuchEntity(entity))?;
+
+    Ok(EffectState::capture(&entity))
+}
+
+/// Read a numeric parameter of an effect on an entity.
+///
+/// `field` is a reflection path, e.g. `"amount"` or `"color.a"`.
+pub fn get_parameter(
+    world: &World,
+    entity: Entity,
+    effect: &str,
+    field: &str,
+) -> Result<f32, ScriptError> {
+    let kind = lookup(effect)?;
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or(ScriptError::NoSuchEntity(entity))?;
+
+    let component = kind
+        .reflect(&entity_ref)
+        .ok_or(ScriptError::EffectNotActive {
+            entity,
+            effect: kind,
+        })?;
+
+    let bad_parameter = |reason: String| ScriptError::BadParameter {
+        effect: kind,
+        field: field.to_string(),
+        reason,
+    };
+
+    let value = component
+        .reflect_path(field)
+        .map_err(|e| bad_parameter(e.to_string()))?;
+
+    util::get_number(value).ok_or_else(|| bad_parameter("not a number".to_string()))
+}
+
+/// Something a script wants to do with the effects.
+///
+/// Script runtimes bind these to script functions.
+/// Either queue them via `commands.add(command)` (which logs failures),
+/// or run them directly on the world with [`ScriptCommand::execute`] to pass errors back to the script.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScriptCommand {
+    /// Add an effect with default parameters to an entity, typically a camera.
+    ///
+    /// If the effect is already there its parameters are reset.
+    Add {
+        /// The entity to add the effect to.
+        entity: Entity,
+
+        /// The name of the effect, see [`effect_names`].
+        effect: String,
+
+        /// The order of the effect, see [`crate::post_processing::Order`].
+        order: Option<f32>,
+    },
+
+    /// Remove an effect from an entity.
+    Remove {
+        /// The entity to remove the effect from.
+        entity: Entity,
+
+        /// The name of the effect, see [`effect_names`].
+        effect: String,
+    },
+
+    /// Set a numeric parameter of an effect on an entity.
+    Set {
+        /// The entity with the effect.
+        entity: Entity,
+
+        /// The name of the effect, see [`effect_names`].
+        effect: String,
+
+        /// The path of the parameter, e.g. `"amount"` or `"color.a"`.
+        field: String,
+
+        /// The new value. Integer parameters get this rounded.
+        value: f32,
+    },
+
+    /// Add an effect with default parameters, and remove it again after some time.
+    ///
+    /// See [`OneShot`].
+    OneShot {
+        /// The entity to add the effect to.
+        entity: Entity,
+
+        /// The name of the effect, see [`effect_names`].
+        effect: String,
+
+        /// How many seconds the effect stays.
+        duration: f32,
+    },
+}
+
+impl ScriptCommand {
+    /// Run the command.
+    pub fn execute(self, world: &mut World) -> Result<(), ScriptError> {
+        match self {
+            ScriptCommand::Add {
+                entity,
+                effect,
+                order,
+            } => {
+                let kind = lookup(&effect)?;
+                let mut entity = world
+                    .get_entity_mut(entity)
+                    .ok_or(ScriptError::NoSuchEntity(entity))?;
+
+                kind.default_effect().insert(&mut entity, order);
+            }
+            ScriptCommand::Remove { entity, effect } => {
+                let kind = lookup(&effect)?;
+                let mut entity = world
+                    .get_entity_mut(entity)
+                    .ok_or(ScriptError::NoSuchEntity(entity))?;
+
+                kind.remove(&mut entity);
+            }
+            ScriptCommand::Set {
+                entity,
+                effect,
+                field,
+                value,
+            } => {
+                let kind = lookup(&effect)?;
+                let mut entity_mut = world
+                    .get_entity_mut(entity)
+                    .ok_or(ScriptError::NoSuchEntity(entity))?;
+
+                let mut component =
+                    kind.reflect_mut(&mut entity_mut)
+                        .ok_or(ScriptError::EffectNotActive {
+                            entity,
+                            effect: kind,
+                        })?;
+
+                util::set_field_number(&mut *component, &field, value).map_err(|reason| {
+                    ScriptError::BadParameter {
+                        effect: kind,
+                        field: field.clone(),
+                        reason,
+                    }
+                })?;
+            }
+            ScriptCommand::OneShot {
+                entity,
+                effect,
+                duration,
+            } => {
+                let kind = lookup(&effect)?;
+                if world.get_entity(entity).is_none() {
+                    return Err(ScriptError::NoSuchEntity(entity));
+                }
+
+                OneShot::new(entity, kind.default_effect(), duration).apply(world);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Command for ScriptCommand {
+    fn apply(self, world: &mut World) {
+        if let Err(e) = self.execute(world) {
+            warn!("Script command failed: {e}");
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use bevy::{
+    ecs::{system::Command, world::EntityMut},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::{
+    metadata::{ParameterInfo, ParameterRange},
+    post_processing::{for_each_effect, intensity::Intensity, Order},
+    time::VfxTime,
+};
+
+/// Declares the effects which may be part of an [`EffectState`], those with `state: true`
+/// in `for_each_effect!`.
+///
+/// Effects which refer to assets (such as [`crate::post_processing::lut::Lut`])
+/// can't be serialized meaningfully, so they are left out.
+macro_rules! effects {
+    // Keeps the effects with `state: true`, one at a time.
+    (@filter [$($kept:tt)*]) => {
+        effects!(@declare $($kept)*);
+    };
+    (
+        @filter [$($kept:tt)*]
+        $name:ident($effect:path) { state: true $(, $($meta:tt)*)? }
+        $(, $($rest:tt)*)?
+    ) => {
+        effects!(@filter [$($kept)* $name($effect),] $($($rest)*)?);
+    };
+    (@filter [$($kept:tt)*] $name:ident($effect:path) $meta:tt $(, $($rest:tt)*)?) => {
+        effects!(@filter [$($kept)*] $($($rest)*)?);
+    };
+    (@declare $($name:ident($effect:path)),* $(,)?) => {
+        /// An effect component, as stored in an [`EffectState`].
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        pub enum Effect {
+            $(
+                #[allow(missing_docs)]
+                $name($effect),
+            )*
+        }
+
+        /// Which kind of effect an [`Effect`] is.
+        ///
+        /// Used to refer to effects without their parameters, e.g. when removing them.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+        pub enum EffectKind {
+            $(
+                #[allow(missing_docs)]
+                $name,
+            )*
+        }
+
+        impl Effect {
+            /// Which kind of effect this is.
+            pub fn kind(&self) -> EffectKind {
+                match self {
+                    $(Self::$name(_) => EffectKind::$name,)*
+                }
+            }
+
+            /// The effect component, for inspecting its parameters.
+            pub fn as_reflect(&self) -> &dyn Reflect {
+                match self {
+                    $(Self::$name(effect) => effect,)*
+                }
+            }
+
+            /// The numeric parameters of the effect, see [`ParameterRange`].
+            pub fn parameters(&self) -> Vec<ParameterInfo> {
+                match self {
+                    $(Self::$name(effect) => effect.parameters(),)*
+                }
+            }
+
+            /// The effect component, for changing its parameters.
+            pub(crate) fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                match self {
+                    $(Self::$name(effect) => effect,)*
+                }
+            }
+
+            pub(crate) fn insert(self, entity: &mut EntityMut, order: Option<f32>) {
+                match self {
+                    $(
+                        Self::$name(effect) => {
+                            entity.insert(effect);
+                            match order {
+                                Some(order) => {
+                                    // Snapshots don't have the stage, so the one on the camera is kept.
+                                    let stage = entity
+                                        .get::<Order<$effect>>()
+                                        .map(|order| order.stage)
+                                        .unwrap_or_default();
+                                    entity.insert(Order::<$effect>::new(order).with_stage(stage));
+                                }
+                                None => {
+                                    entity.remove::<Order<$effect>>();
+                                }
+                            }
+                        }
+                    )*
+                }
+            }
+
+            fn capture_all(entity: &EntityRef) -> Vec<EffectEntry> {
+                let mut effects = vec![];
+                $(
+                    if let Some(effect) = entity.get::<$effect>() {
+                        effects.push(EffectEntry {
+                            effect: Self::$name(*effect),
+                            order: entity.get::<Order<$effect>>().map(|order| order.order),
+                        });
+                    }
+                )*
+                effects
+            }
+        }
+
+        $(
+            impl From<$effect> for Effect {
+                fn from(effect: $effect) -> Self {
+                    Self::$name(effect)
+                }
+            }
+        )*
+
+        impl EffectKind {
+            /// All kinds of effects.
+            pub const ALL: &'static [EffectKind] = &[$(Self::$name,)*];
+
+            /// The name of the effect, which is also the name of its component.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => stringify!($name),)*
+                }
+            }
+
+            /// Look up a kind of effect by its [`EffectKind::name`].
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $(stringify!($name) => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+
+            /// This kind of effect with default parameters.
+            pub fn default_effect(self) -> Effect {
+                match self {
+                    $(Self::$name => Effect::$name(<$effect>::default()),)*
+                }
+            }
+
+            /// The intensity of this kind of effect on the entity, see [`Intensity`].
+            ///
+            /// Returns `None` if the entity has no [`Intensity`] for it.
+            pub(crate) fn intensity(self, entity: &EntityMut) -> Option<f32> {
+                match self {
+                    $(Self::$name => entity.get::<Intensity<$effect>>().map(|i| i.intensity),)*
+                }
+            }
+
+            /// Set the intensity of this kind of effect on the entity, see [`Intensity`].
+            ///
+            /// `None` removes the [`Intensity`], which leaves the effect at full intensity.
+            pub(crate) fn set_intensity(self, entity: &mut EntityMut, intensity: Option<f32>) {
+                match self {
+                    $(
+                        Self::$name => match intensity {
+                            Some(intensity) => {
+                                entity.insert(Intensity::<$effect>::new(intensity));
+                            }
+                            None => {
+                                entity.remove::<Intensity<$effect>>();
+                            }
+                        },
+                    )*
+                }
+            }
+
+            /// The effect component on the entity, if it is there.
+            #[cfg(any(feature = "scripting", feature = "egui"))]
+            pub(crate) fn reflect<'a>(self, entity: &'a EntityRef) -> Option<&'a dyn Reflect> {
+                match self {
+                    $(Self::$name => entity.get::<$effect>().map(|effect| effect as &dyn Reflect),)*
+                }
+            }
+
+            /// The effect component on the entity, if it is there.
+            #[cfg(any(feature = "scripting", feature = "egui"))]
+            pub(crate) fn reflect_mut<'a>(
+                self,
+                entity: &'a mut EntityMut,
+            ) -> Option<Mut<'a, dyn Reflect>> {
+                match self {
+                    $(
+                        Self::$name => entity
+                            .get_mut::<$effect>()
+                            .map(|effect| effect.map_unchanged(|effect| effect as &mut dyn Reflect)),
+                    )*
+                }
+            }
+
+            pub(crate) fn remove(self, entity: &mut EntityMut) {
+                match self {
+                    $(
+                        Self::$name => {
+                            entity.remove::<($effect, Order<$effect>)>();
+                        }
+                    )*
+                }
+            }
+        }
+    };
+    ($($effects:tt)*) => {
+        effects!(@filter [] $($effects)*);
+    };
+}
+
+for_each_effect!(effects);
+
+/// An effect along with its [`Order`], if any.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectEntry {
+    /// The effect and its parameters.
+    pub effect: Effect,
+
+    /// The order of the effect, see [`Order`].
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub order: Option<f32>,
+}
+
+/// A snapshot of which effects are active on a camera, and their parameters.
+///
+/// Meant for replicating visual states between game instances.
+/// Instead of sending full snapshots each frame, send an [`EffectStateDiff`]
+/// made via [`EffectState::diff`], which is empty if nothing changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectState {
+    effects: Vec<EffectEntry>,
+}
+
+impl EffectState {
+    /// A snapshot of the given effects, without orders.
+    ///
+    /// Useful for describing a set of effects to switch to, e.g. via an [`EffectState::apply`]
+    /// or a [`crate::transition::Transition`].
+    pub fn new(effects: impl IntoIterator<Item = Effect>) -> Self {
+        Self::from_entries(
+            effects
+                .into_iter()
+                .map(|effect| EffectEntry {
+                    effect,
+                    order: None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Adds an effect with the given order, replacing any effect of the same kind.
+    pub fn with(mut self, effect: impl Into<Effect>, order: Option<f32>) -> Self {
+        let effect = effect.into();
+        self.effects
+            .retain(|entry| entry.effect.kind() != effect.kind());
+        self.effects.push(EffectEntry { effect, order });
+        Self::from_entries(self.effects)
+    }
+
+    /// Capture the effects currently on the given entity.
+    pub fn capture(entity: &EntityRef) -> Self {
+        Self::from_entries(Effect::capture_all(entity))
+    }
+
+    fn from_entries(mut effects: Vec<EffectEntry>) -> Self {
+        effects.sort_by_key(|entry| entry.effect.kind());
+        Self { effects }
+    }
+
+    /// The effects in this snapshot.
+    pub fn effects(&self) -> &[EffectEntry] {
+        &self.effects
+    }
+
+    /// Get the entry for the given kind of effect, if it is active.
+    pub fn get(&self, kind: EffectKind) -> Option<&EffectEntry> {
+        self.effects
+            .iter()
+            .find(|entry| entry.effect.kind() == kind)
+    }
+
+    /// Creates the diff which turns `self` into `newer` when applied.
+    pub fn diff(&self, newer: &EffectState) -> EffectStateDiff {
+        let set = newer
+            .effects
+            .iter()
+            .filter(|entry| self.get(entry.effect.kind()) != Some(entry))
+            .copied()
+            .collect();
+
+        let removed = self
+            .effects
+            .iter()
+            .map(|entry| entry.effect.kind())
+            .filter(|kind| newer.get(*kind).is_none())
+            .collect();
+
+        EffectStateDiff { set, removed }
+    }
+
+    /// Apply a diff to this snapshot.
+    ///
+    /// Useful for keeping track of the last known state on the receiving end.
+    pub fn apply_diff(&mut self, diff: &EffectStateDiff) {
+        let mut effects: HashMap<EffectKind, EffectEntry> = self
+            .effects
+            .drain(..)
+            .map(|entry| (entry.effect.kind(), entry))
+            .collect();
+
+        for kind in &diff.removed {
+            effects.remove(kind);
+        }
+        for entry in &diff.set {
+            effects.insert(entry.effect.kind(), *entry);
+        }
+
+        *self = Self::from_entries(effects.into_values().collect());
+    }
+
+    /// Make the effects on the given entity match this snapshot.
+    ///
+    /// Effects not in this snapshot are removed from the entity.
+    pub fn apply(&self, entity: &mut EntityMut) {
+        let current = Self::capture(&entity.world().entity(entity.id()));
+        current.diff(self).apply(entity);
+    }
+}
+
+/// The difference between two [`EffectState`]s.
+///
+/// Only contains the effects which were added or changed, and the kinds of effects removed.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct EffectStateDiff {
+    /// Effects which were added or had their parameters changed.
+    pub set: Vec<EffectEntry>,
+
+    /// Effects which were removed.
+    pub removed: Vec<EffectKind>,
+}
+
+impl EffectStateDiff {
+    /// True if the diff would not change anything.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.removed.is_empty()
+    }
+
+    /// Apply this diff to the effects on the given entity.
+    pub fn apply(&self, entity: &mut EntityMut) {
+        for kind in &self.removed {
+            kind.remove(entity);
+        }
+        for entry in &self.set {
+            entry.effect.insert(entity, entry.order);
+        }
+    }
+}
+
+/// A command which applies an [`EffectStateDiff`] to an entity.
+///
+/// Use via `commands.add(ApplyEffectStateDiff { entity, diff })`.
+#[derive(Debug, Clone)]
+pub struct ApplyEffectStateDiff {
+    /// The entity to apply the diff to, typically a camera.
+    pub entity: Entity,
+
+    /// The diff to apply.
+    pub diff: EffectStateDiff,
+}
+
+impl Command for ApplyEffectStateDiff {
+    fn apply(self, world: &mut World) {
+        if let Some(mut entity) = world.get_entity_mut(self.entity) {
+            self.diff.apply(&mut entity);
+        }
+    }
+}
+
+/// A snapshot of the effect state: the effects on each camera which can be part of an
+/// [`EffectState`] (see [`EffectKind`]), and the [`VfxTime`] clock animating them.
+///
+/// Capture one of these when recording a replay or before a kill-cam,
+/// and restore it to reproduce the same post processing.
+/// Other effects, e.g. [`Lut`](crate::post_processing::lut::Lut) and
+/// [`DeadPixels`](crate::post_processing::dead_pixels::DeadPixels), are not captured,
+/// and are left as they are when the snapshot is restored.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct VfxSnapshot {
+    /// The clock driving animated effects.
+    pub time: VfxTime,
+
+    /// The effects on each camera, see [`EffectState`].
+    pub cameras: Vec<(Entity, EffectState)>,
+}
+
+impl VfxSnapshot {
+    /// Capture the effects on all cameras, as well as the [`VfxTime`] clock.
+    pub fn capture(world: &mut World) -> Self {
+        let time = *world.resource::<VfxTime>();
+
+        let cameras = world
+            .query_filtered::<Entity, With<Camera>>()
+            .iter(world)
+            .map(|entity| (entity, EffectState::capture(&world.entity(entity))))
+            .collect();
+
+        Self { time, cameras }
+    }
+
+    /// Restore the effects on the cameras in this snapshot, as well as the [`VfxTime`] clock.
+    ///
+    /// Cameras which no longer exist are skipped.
+    pub fn restore(&self, world: &mut World) {
+        *world.resource_mut::<VfxTime>() = self.time;
+
+        for (entity, state) in &self.cameras {
+            if let Some(mut entity) = world.get_entity_mut(*entity) {
+                state.apply(&mut entity);
+            }
+        }
+    }
+}
+
+/// A command which restores a [`VfxSnapshot`].
+///
+/// Use via `commands.add(RestoreVfxSnapshot(snapshot))`.
+#[derive(Debug, Clone)]
+pub struct RestoreVfxSnapshot(pub VfxSnapshot);
+
+impl Command for RestoreVfxSnapshot {
+    fn apply(self, world: &mut World) {
+        self.0.restore(world);
+    }
+}
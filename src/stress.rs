@@ -0,0 +1,333 @@
+use bevy::{
+    app::AppExit,
+    diagnostic::{
+        Diagnostic, DiagnosticId, Diagnostics, FrameTimeDiagnosticsPlugin, RegisterDiagnostic,
+    },
+    prelude::*,
+    render::camera::Viewport,
+    window::PrimaryWindow,
+};
+
+use crate::state::EffectKind;
+
+/// How the stress test is built and measured.
+///
+/// Insert this as a resource before adding the [`StressPlugin`] to change it.
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct StressConfig {
+    /// How many cameras to spawn.
+    /// They are laid out in a grid covering the primary window.
+    pub cameras: usize,
+
+    /// How many effects each camera has.
+    ///
+    /// A camera can only have one of each kind of effect,
+    /// so this is limited to the number of [`StressConfig::kinds`].
+    pub effects_per_camera: usize,
+
+    /// The kinds of effects to pick from, with default parameters.
+    ///
+    /// Cameras take their effects from this list in turn,
+    /// so each kind is used about as often as the others.
+    pub kinds: Vec<EffectKind>,
+
+    /// Seconds to wait before measuring, so shader compilation and pipeline creation don't count.
+    pub warmup: f32,
+
+    /// Seconds to measure for.
+    pub duration: f32,
+
+    /// The frame budget to compare against in the report, in milliseconds.
+    pub budget_ms: Option<f32>,
+
+    /// If true, the app exits once the report is done.
+    pub exit_when_done: bool,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self {
+            cameras: 1,
+            effects_per_camera: 4,
+            kinds: EffectKind::ALL.to_vec(),
+            warmup: 2.0,
+            duration: 10.0,
+            budget_ms: None,
+            exit_when_done: false,
+        }
+    }
+}
+
+impl StressConfig {
+    /// A stress test of `effects_per_camera` effects on each of `cameras` cameras.
+    pub fn new(cameras: usize, effects_per_camera: usize) -> Self {
+        Self {
+            cameras,
+            effects_per_camera,
+            ..default()
+        }
+    }
+
+    /// Only pick effects of the given kinds.
+    pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = EffectKind>) -> Self {
+        self.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Compare the frame times against a budget, e.g. `16.6` for 60 FPS.
+    pub fn with_budget_ms(mut self, budget_ms: f32) -> Self {
+        self.budget_ms = Some(budget_ms);
+        self
+    }
+
+    /// Exit the app once the report is done, e.g. for running stress tests from scripts.
+    pub fn exit_when_done(mut self) -> Self {
+        self.exit_when_done = true;
+        self
+    }
+}
+
+/// The frame timings measured by the stress test.
+///
+/// Inserted as a resource (and logged) once the measurement is done.
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct StressReport {
+    /// How many cameras were measured.
+    pub cameras: usize,
+
+    /// How many effects were measured, in total across all cameras.
+    pub effects: usize,
+
+    /// How many frames were measured.
+    pub frames: usize,
+
+    /// The mean frame time, in milliseconds.
+    pub mean_ms: f32,
+
+    /// The frame time 99% of frames were faster than, in milliseconds.
+    pub p99_ms: f32,
+
+    /// The slowest frame time, in milliseconds.
+    pub max_ms: f32,
+
+    /// The frame budget from the [`StressConfig`], if any.
+    pub budget_ms: Option<f32>,
+}
+
+impl StressReport {
+    /// True if the 99th percentile frame time fits the budget.
+    ///
+    /// Without a budget, this is always true.
+    pub fn fits_budget(&self) -> bool {
+        match self.budget_ms {
+            Some(budget) => self.p99_ms <= budget,
+            None => true,
+        }
+    }
+}
+
+impl std::fmt::Display for StressReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} effects across {} cameras, {} frames: mean {:.2} ms, p99 {:.2} ms, max {:.2} ms",
+            self.effects, self.cameras, self.frames, self.mean_ms, self.p99_ms, self.max_ms
+        )?;
+
+        if let Some(budget) = self.budget_ms {
+            let verdict = if self.fits_budget() {
+                "fits"
+            } else {
+                "exceeds"
+            };
+            write!(f, " ({verdict} the budget of {budget:.2} ms)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Marks the cameras spawned by the stress test.
+#[derive(Debug, Component)]
+pub struct StressCamera {
+    index: usize,
+}
+
+/// Frame times collected so far.
+#[derive(Debug, Default, Resource)]
+struct StressMeasurement {
+    elapsed: f32,
+    frame_times_ms: Vec<f32>,
+    done: bool,
+}
+
+fn spawn_stress_test(world: &mut World) {
+    let config = world.resource::<StressConfig>().clone();
+
+    let effects_per_camera = config.effects_per_camera.min(config.kinds.len());
+    if effects_per_camera < config.effects_per_camera {
+        warn!(
+            "A camera can only have one of each kind of effect, so the stress test uses {effects_per_camera} effects per camera instead of {}",
+            config.effects_per_camera
+        );
+    }
+
+    let transform = Transform::from_xyz(-5.0, 12., 10.0).looking_at(Vec3::ZERO, Vec3::Y);
+
+    for index in 0..config.cameras {
+        let mut camera = world.spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    order: index as isize,
+                    ..default()
+                },
+                transform,
+                ..default()
+            },
+            StressCamera { index },
+        ));
+
+        for slot in 0..effects_per_camera {
+            let kind = config.kinds[(index + slot) % config.kinds.len()];
+            kind.default_effect().insert(&mut camera, Some(slot as f32));
+        }
+    }
+}
+
+/// Lays out the stress test cameras in a grid covering the primary window.
+fn layout_viewports(
+    windows: Query<Ref<Window>, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Camera, Ref<StressCamera>)>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    if !window.is_changed() && !cameras.iter().any(|(_, marker)| marker.is_added()) {
+        return;
+    }
+
+    let count = cameras.iter().count();
+    if count == 0 {
+        return;
+    }
+
+    let columns = (count as f32).sqrt().ceil() as u32;
+    let rows = (count as u32).div_ceil(columns);
+
+    let size = UVec2::new(
+        window.physical_width() / columns,
+        window.physical_height() / rows,
+    )
+    .max(UVec2::ONE);
+
+    for (mut camera, marker) in &mut cameras {
+        let index = marker.index as u32;
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(index % columns, index / columns) * size,
+            physical_size: size,
+            ..default()
+        });
+    }
+}
+
+fn measure(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<StressConfig>,
+    mut measurement: ResMut<StressMeasurement>,
+    cameras: Query<Entity, With<StressCamera>>,
+    mut diagnostics: Diagnostics,
+    mut exit: EventWriter<AppExit>,
+) {
+    let camera_count = cameras.iter().count();
+    let effect_count = camera_count * config.effects_per_camera.min(config.kinds.len());
+
+    diagnostics.add_measurement(StressPlugin::CAMERAS, || camera_count as f64);
+    diagnostics.add_measurement(StressPlugin::EFFECTS, || effect_count as f64);
+
+    if measurement.done {
+        return;
+    }
+
+    measurement.elapsed += time.delta_seconds();
+    if measurement.elapsed < config.warmup {
+        return;
+    }
+
+    let frame_time_ms = time.delta_seconds() * 1000.0;
+    measurement.frame_times_ms.push(frame_time_ms);
+
+    if measurement.elapsed < config.warmup + config.duration {
+        return;
+    }
+
+    measurement.done = true;
+
+    let mut frame_times = std::mem::take(&mut measurement.frame_times_ms);
+    frame_times.sort_by(f32::total_cmp);
+
+    let frames = frame_times.len();
+    let p99_index = ((frames as f32 * 0.99).ceil() as usize).clamp(1, frames) - 1;
+
+    let report = StressReport {
+        cameras: camera_count,
+        effects: effect_count,
+        frames,
+        mean_ms: frame_times.iter().sum::<f32>() / frames as f32,
+        p99_ms: frame_times[p99_index],
+        max_ms: frame_times[frames - 1],
+        budget_ms: config.budget_ms,
+    };
+
+    if report.fits_budget() {
+        info!("Stress test: {report}");
+    } else {
+        warn!("Stress test: {report}");
+    }
+
+    commands.insert_resource(report);
+
+    if config.exit_when_done {
+        exit.send(AppExit);
+    }
+}
+
+/// Builds a configurable stack of effects across several cameras, and measures the frame times.
+///
+/// Useful for checking whether a given stack fits the frame budget on target hardware.
+/// Configure it via the [`StressConfig`] resource.
+///
+/// The number of cameras and effects are reported as diagnostics along with the frame time,
+/// so they show up in e.g. [`bevy::diagnostic::LogDiagnosticsPlugin`].
+/// Once the measurement is done, a [`StressReport`] is logged and inserted as a resource.
+///
+/// This is meant to be used on its own, along with [`crate::BevyVfxBagPlugin`],
+/// as the cameras cover the whole primary window.
+#[derive(Debug, Default)]
+pub struct StressPlugin;
+
+impl StressPlugin {
+    /// The number of stress test cameras.
+    pub const CAMERAS: DiagnosticId =
+        DiagnosticId::from_u128(33044462700450385717683361723378646314);
+
+    /// The number of effects, in total across all stress test cameras.
+    pub const EFFECTS: DiagnosticId =
+        DiagnosticId::from_u128(94521855151087604264550393793474064920);
+}
+
+impl Plugin for StressPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+
+        app.init_resource::<StressConfig>()
+            .init_resource::<StressMeasurement>()
+            .register_diagnostic(Diagnostic::new(Self::CAMERAS, "stress_cameras", 1))
+            .register_diagnostic(Diagnostic::new(Self::EFFECTS, "stress_effects", 1))
+            .add_systems(Startup, spawn_stress_test)
+            .add_systems(Update, (layout_viewports, measure));
+    }
+}
@@ -0,0 +1,364 @@
+use bevy::{
+    core_pipeline::tonemapping::Tonemapping,
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+
+use crate::time::VfxTime;
+
+/// The sRGB colors of the 24 patches of the classic color checker chart,
+/// row by row from dark skin to black.
+pub const COLOR_CHECKER: [[u8; 3]; 24] = [
+    // Natural colors.
+    [115, 82, 68],
+    [194, 150, 130],
+    [98, 122, 157],
+    [87, 108, 67],
+    [133, 128, 177],
+    [103, 189, 170],
+    // Miscellaneous colors.
+    [214, 126, 44],
+    [80, 91, 166],
+    [193, 90, 99],
+    [94, 60, 108],
+    [157, 188, 64],
+    [224, 163, 46],
+    // Primary and secondary colors.
+    [56, 61, 150],
+    [70, 148, 73],
+    [175, 54, 60],
+    [231, 199, 31],
+    [187, 86, 149],
+    [8, 133, 161],
+    // Grayscale.
+    [243, 243, 242],
+    [200, 200, 200],
+    [160, 160, 160],
+    [122, 122, 121],
+    [85, 85, 85],
+    [52, 52, 52],
+];
+
+/// Which parts of the reference scene to spawn.
+///
+/// Insert this as a resource before adding the [`ReferenceScenePlugin`] to change it.
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct ReferenceScene {
+    /// A grid of spheres, whose hue changes along the columns and roughness along the rows.
+    pub sphere_grid: bool,
+
+    /// An unlit chart of the [`COLOR_CHECKER`] patches, for checking color grading.
+    pub color_checker: bool,
+
+    /// A checkered [`MotionTarget`] moving back and forth, for checking temporal effects.
+    pub motion_target: bool,
+
+    /// A [`ReferenceSceneCamera`] looking at the scene.
+    /// Without it, bring a camera of your own.
+    pub camera: bool,
+}
+
+impl Default for ReferenceScene {
+    fn default() -> Self {
+        Self {
+            sphere_grid: true,
+            color_checker: true,
+            motion_target: true,
+            camera: true,
+        }
+    }
+}
+
+/// Marks all entities spawned as part of the reference scene, e.g. for despawning them.
+#[derive(Debug, Component)]
+pub struct ReferenceSceneEntity;
+
+/// Marks the camera of the reference scene.
+///
+/// It has no tonemapping, so the unlit [`COLOR_CHECKER`] patches come out as their exact colors
+/// before any effects are applied.
+#[derive(Debug, Component)]
+pub struct ReferenceSceneCamera;
+
+/// Moves an entity back and forth along the X axis at a constant speed.
+///
+/// The position follows the elapsed time of the [`VfxTime`] clock, so setting the clock
+/// (see [`VfxTime::set_elapsed_seconds`]) puts the target at the same place on every run.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct MotionTarget {
+    /// How far the target moves per second.
+    pub speed: f32,
+
+    /// How far from its starting point the target moves before turning around.
+    pub extent: f32,
+
+    /// Where the target starts, and turns around between.
+    pub origin: Vec3,
+}
+
+impl MotionTarget {
+    /// Where the target is after the given seconds.
+    pub fn position(&self, elapsed: f32) -> Vec3 {
+        if self.extent <= 0.0 {
+            return self.origin;
+        }
+
+        // A triangle wave, so the speed is the same all the way except when turning around.
+        let period = 4.0 * self.extent;
+        let phase = (elapsed * self.speed).rem_euclid(period);
+        let offset = if phase < 2.0 * self.extent {
+            phase - self.extent
+        } else {
+            3.0 * self.extent - phase
+        };
+
+        self.origin + Vec3::X * offset
+    }
+}
+
+/// Spawns the parts of the reference scene given by the [`ReferenceScene`] resource,
+/// or all of them if there is none.
+///
+/// The [`ReferenceScenePlugin`] does this on startup, but it can be called directly,
+/// e.g. by a harness rendering the scene with each effect in turn.
+pub fn spawn_reference_scene(world: &mut World) {
+    let scene = world
+        .get_resource::<ReferenceScene>()
+        .copied()
+        .unwrap_or_default();
+
+    world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+        world.resource_scope(|world, mut materials: Mut<Assets<StandardMaterial>>| {
+            // A neutral backdrop, so depth based effects have something behind the scene.
+            world.spawn((
+                PbrBundle {
+                    mesh: meshes.add(shape::Quad::new(Vec2::new(40.0, 24.0)).into()),
+                    material: materials.add(StandardMaterial {
+                        base_color: Color::rgb(0.18, 0.18, 0.18),
+                        perceptual_roughness: 1.0,
+                        ..default()
+                    }),
+                    transform: Transform::from_xyz(0.0, 0.0, -3.0),
+                    ..default()
+                },
+                ReferenceSceneEntity,
+            ));
+
+            world.spawn((
+                DirectionalLightBundle {
+                    directional_light: DirectionalLight {
+                        illuminance: 20_000.0,
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(-4.0, 6.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+                    ..default()
+                },
+                ReferenceSceneEntity,
+            ));
+
+            if scene.sphere_grid {
+                spawn_sphere_grid(world, &mut meshes, &mut materials);
+            }
+
+            if scene.color_checker {
+                spawn_color_checker(world, &mut meshes, &mut materials);
+            }
+
+            if scene.motion_target {
+                let image = world.resource_mut::<Assets<Image>>().add(checker_texture());
+                let target = MotionTarget {
+                    speed: 4.0,
+                    extent: 7.0,
+                    origin: Vec3::new(0.0, -3.5, 0.0),
+                };
+
+                world.spawn((
+                    PbrBundle {
+                        mesh: meshes.add(shape::Cube::new(1.5).into()),
+                        material: materials.add(StandardMaterial {
+                            base_color_texture: Some(image),
+                            unlit: true,
+                            ..default()
+                        }),
+                        transform: Transform::from_translation(target.position(0.0)),
+                        ..default()
+                    },
+                    target,
+                    ReferenceSceneEntity,
+                ));
+            }
+        });
+    });
+
+    if scene.camera {
+        world.spawn((
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 14.0).looking_at(Vec3::ZERO, Vec3::Y),
+                tonemapping: Tonemapping::None,
+                ..default()
+            },
+            ReferenceSceneCamera,
+            ReferenceSceneEntity,
+        ));
+    }
+}
+
+fn spawn_sphere_grid(
+    world: &mut World,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    const COLUMNS: usize = 6;
+    const ROWS: usize = 4;
+    const SPACING: f32 = 1.2;
+    const CENTER: Vec2 = Vec2::new(-4.5, 1.5);
+
+    let mesh = meshes.add(
+        Mesh::try_from(shape::Icosphere {
+            radius: 0.5,
+            subdivisions: 5,
+        })
+        .expect("The sphere has few enough vertices"),
+    );
+
+    for row in 0..ROWS {
+        for column in 0..COLUMNS {
+            let hue = 300.0 * column as f32 / (COLUMNS - 1) as f32;
+            let roughness = 0.1 + 0.8 * row as f32 / (ROWS - 1) as f32;
+
+            let offset = Vec2::new(
+                column as f32 - (COLUMNS - 1) as f32 / 2.0,
+                (ROWS - 1) as f32 / 2.0 - row as f32,
+            ) * SPACING;
+
+            world.spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: materials.add(StandardMaterial {
+                        base_color: Color::hsl(hue, 0.8, 0.5),
+                        perceptual_roughness: roughness,
+                        ..default()
+                    }),
+                    transform: Transform::from_translation((CENTER + offset).extend(0.0)),
+                    ..default()
+                },
+                ReferenceSceneEntity,
+            ));
+        }
+    }
+}
+
+fn spawn_color_checker(
+    world: &mut World,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    const COLUMNS: usize = 6;
+    const PATCH: f32 = 0.9;
+    const SPACING: f32 = 1.0;
+    const CENTER: Vec2 = Vec2::new(4.5, 1.5);
+
+    let rows = COLOR_CHECKER.len() / COLUMNS;
+    let mesh = meshes.add(shape::Quad::new(Vec2::splat(PATCH)).into());
+
+    // The chart is framed in black, like the real one.
+    world.spawn((
+        PbrBundle {
+            mesh: meshes.add(
+                shape::Quad::new(Vec2::new(
+                    COLUMNS as f32 * SPACING + 0.3,
+                    rows as f32 * SPACING + 0.3,
+                ))
+                .into(),
+            ),
+            material: materials.add(StandardMaterial {
+                base_color: Color::BLACK,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_translation(CENTER.extend(-0.01)),
+            ..default()
+        },
+        ReferenceSceneEntity,
+    ));
+
+    for (index, [r, g, b]) in COLOR_CHECKER.into_iter().enumerate() {
+        let (row, column) = (index / COLUMNS, index % COLUMNS);
+
+        let offset = Vec2::new(
+            column as f32 - (COLUMNS - 1) as f32 / 2.0,
+            (rows - 1) as f32 / 2.0 - row as f32,
+        ) * SPACING;
+
+        world.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgb_u8(r, g, b),
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_translation((CENTER + offset).extend(0.0)),
+                ..default()
+            },
+            ReferenceSceneEntity,
+        ));
+    }
+}
+
+/// A black and white checkerboard, sampled without filtering so its edges stay sharp.
+fn checker_texture() -> Image {
+    const TEXTURE_SIZE: usize = 8;
+
+    let mut texture_data = [0; TEXTURE_SIZE * TEXTURE_SIZE * 4];
+    for (index, pixel) in texture_data.chunks_exact_mut(4).enumerate() {
+        let (x, y) = (index % TEXTURE_SIZE, index / TEXTURE_SIZE);
+        let value = if (x + y) % 2 == 0 { 255 } else { 0 };
+        pixel.copy_from_slice(&[value, value, value, 255]);
+    }
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: TEXTURE_SIZE as u32,
+            height: TEXTURE_SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &texture_data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::nearest();
+
+    image
+}
+
+fn move_motion_targets(time: Res<VfxTime>, mut targets: Query<(&MotionTarget, &mut Transform)>) {
+    for (target, mut transform) in &mut targets {
+        transform.translation = target.position(time.elapsed_seconds());
+    }
+}
+
+/// Spawns a reference scene for evaluating effects in the same way across versions and hardware.
+///
+/// The scene has a grid of spheres with gradients of hue and roughness, a color checker chart,
+/// and a [`MotionTarget`] moving at a constant speed. Configure it via the [`ReferenceScene`]
+/// resource.
+///
+/// The scene does not depend on the window size or frame rate: pausing the [`VfxTime`] clock and
+/// setting its elapsed time gives the same image every time, which is what golden image tests need.
+///
+/// This is meant to be used on its own, along with [`crate::BevyVfxBagPlugin`],
+/// as the scene is built around the origin and lit by its own light.
+#[derive(Debug, Default)]
+pub struct ReferenceScenePlugin;
+
+impl Plugin for ReferenceScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReferenceScene>()
+            .add_systems(Startup, spawn_reference_scene)
+            .add_systems(Update, move_motion_targets);
+    }
+}
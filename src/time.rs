@@ -0,0 +1,142 @@
+use bevy::{
+    prelude::*,
+    render::{
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_resource::{ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+/// The elapsed time wraps to zero after this many seconds, same as Bevy's globals.
+/// This keeps precision reasonable in shaders.
+const WRAP_PERIOD: f32 = 3600.0;
+
+/// The clock driving animated effects.
+///
+/// Effects read this clock instead of Bevy's [`Time`], which means the animation of all effects can be
+/// paused, slowed down, or set to an exact point in time.
+/// This is what makes replays and kill-cams reproduce the same post processing:
+/// capture this resource along with the effects (see [`crate::state::VfxSnapshot`]) and restore it later.
+/// That covers the effects a snapshot captures; others have to be restored by the app.
+///
+/// Effects don't use any other source of randomness than this clock and their parameters,
+/// such as [`DeadPixels::seed`](crate::post_processing::dead_pixels::DeadPixels::seed),
+/// so noise in shaders is also reproduced by restoring this clock.
+#[derive(Resource, ExtractResource, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct VfxTime {
+    elapsed: f32,
+    delta: f32,
+    frame_count: u32,
+
+    /// If true, the clock does not advance on its own.
+    /// It can still be changed via [`VfxTime::advance`] and [`VfxTime::set_elapsed_seconds`].
+    pub paused: bool,
+
+    /// How fast the clock advances relative to Bevy's [`Time`].
+    pub relative_speed: f32,
+}
+
+impl Default for VfxTime {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.0,
+            delta: 0.0,
+            frame_count: 0,
+            paused: false,
+            relative_speed: 1.0,
+        }
+    }
+}
+
+impl VfxTime {
+    /// Seconds elapsed, wrapped to zero every hour.
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// How many seconds the clock advanced last time it was advanced.
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta
+    }
+
+    /// How many times the clock has advanced.
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Set the elapsed time directly, e.g. to jump to a point in a replay.
+    pub fn set_elapsed_seconds(&mut self, elapsed: f32) {
+        self.elapsed = elapsed.rem_euclid(WRAP_PERIOD);
+    }
+
+    /// Advance the clock by the given number of seconds, counting it as a frame.
+    ///
+    /// Useful for driving the clock manually (while [`VfxTime::paused`]) with a fixed timestep.
+    pub fn advance(&mut self, seconds: f32) {
+        self.delta = seconds;
+        self.elapsed = (self.elapsed + seconds).rem_euclid(WRAP_PERIOD);
+        self.frame_count = self.frame_count.wrapping_add(1);
+    }
+}
+
+fn update_vfx_time(time: Res<Time>, mut vfx_time: ResMut<VfxTime>) {
+    if vfx_time.paused {
+        vfx_time.delta = 0.0;
+        return;
+    }
+
+    let delta = time.delta_seconds() * vfx_time.relative_speed;
+    vfx_time.advance(delta);
+}
+
+/// The uniform the shaders see as `globals`.
+/// Laid out like Bevy's globals uniform, so effect shaders can keep importing that type.
+#[derive(Debug, Default, Clone, ShaderType)]
+pub(crate) struct VfxGlobalsUniform {
+    time: f32,
+    delta_time: f32,
+    frame_count: u32,
+}
+
+/// The buffer containing the [`VfxGlobalsUniform`].
+#[derive(Resource, Default)]
+pub(crate) struct VfxGlobalsBuffer {
+    pub buffer: UniformBuffer<VfxGlobalsUniform>,
+}
+
+fn prepare_vfx_globals_buffer(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut globals_buffer: ResMut<VfxGlobalsBuffer>,
+    vfx_time: Res<VfxTime>,
+) {
+    let buffer = globals_buffer.buffer.get_mut();
+    buffer.time = vfx_time.elapsed;
+    buffer.delta_time = vfx_time.delta;
+    buffer.frame_count = vfx_time.frame_count;
+
+    globals_buffer
+        .buffer
+        .write_buffer(&render_device, &render_queue);
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VfxTime>()
+            .add_plugins(ExtractResourcePlugin::<VfxTime>::default())
+            .add_systems(First, update_vfx_time.after(bevy::time::TimeSystem));
+
+        app.get_sub_app_mut(RenderApp)
+            .expect("Need a render app for post processing")
+            .init_resource::<VfxGlobalsBuffer>()
+            .add_systems(
+                Render,
+                prepare_vfx_globals_buffer.in_set(RenderSet::Prepare),
+            );
+    }
+}
@@ -0,0 +1,250 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{time::VfxTime, util};
+
+/// A point in a [`ParamTrack`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe {
+    /// When the parameter reaches this value, in seconds since the timeline started.
+    pub time: f32,
+
+    /// The value of the parameter.
+    pub value: f32,
+}
+
+impl Keyframe {
+    /// Creates a keyframe.
+    pub fn new(time: f32, value: f32) -> Self {
+        Self { time, value }
+    }
+}
+
+/// How a [`ParamTrack`] goes from one keyframe to the next.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Jump to the value of the next keyframe once it is reached.
+    Step,
+
+    /// Move at a constant rate towards the next keyframe.
+    #[default]
+    Linear,
+
+    /// Ease in and out of each keyframe (smoothstep).
+    Smooth,
+}
+
+/// The animation of a single numeric effect parameter.
+#[derive(Debug, Default, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamTrack {
+    /// The name of the effect component, e.g. `"Mask"`.
+    /// The full type path also works.
+    pub component: String,
+
+    /// The path of the parameter within the component, e.g. `"strength"` or `"color.a"`.
+    pub field: String,
+
+    /// How to go from one keyframe to the next.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub interpolation: Interpolation,
+
+    /// The keyframes, sorted by time.
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl ParamTrack {
+    /// The value of the parameter at the given time.
+    ///
+    /// Before the first and after the last keyframe, the value of that keyframe is held.
+    /// Returns `None` if there are no keyframes.
+    pub fn sample(&self, time: f32) -> Option<f32> {
+        let first = self.keyframes.first()?;
+
+        // The index of the first keyframe after `time`.
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        if next == 0 {
+            return Some(first.value);
+        }
+
+        let from = self.keyframes[next - 1];
+        let Some(to) = self.keyframes.get(next) else {
+            return Some(from.value);
+        };
+
+        let t = (time - from.time) / (to.time - from.time);
+        let t = match self.interpolation {
+            Interpolation::Step => 0.0,
+            Interpolation::Linear => t,
+            Interpolation::Smooth => t * t * (3.0 - 2.0 * t),
+        };
+
+        Some(from.value + (to.value - from.value) * t)
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |keyframe| keyframe.time)
+    }
+}
+
+/// Keyframed animation of effect parameters on the same entity.
+///
+/// The timeline is advanced by the [`VfxTime`] clock, so it is paused, slowed down,
+/// and restored along with the rest of the effects.
+///
+/// With the `json` feature, timelines can be imported via [`EffectTimeline::from_json`],
+/// which is handy for parameter animation authored in external tools.
+#[derive(Debug, Default, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct EffectTimeline {
+    /// The animated parameters.
+    pub tracks: Vec<ParamTrack>,
+
+    /// If true, the timeline starts over after the last keyframe of the longest track.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub looping: bool,
+
+    /// Seconds since the timeline started.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub elapsed: f32,
+}
+
+impl EffectTimeline {
+    /// The time of the last keyframe of the longest track.
+    pub fn duration(&self) -> f32 {
+        self.tracks
+            .iter()
+            .map(ParamTrack::duration)
+            .fold(0.0, f32::max)
+    }
+
+    /// Import a timeline from JSON.
+    ///
+    /// The format is a list of named parameter tracks:
+    ///
+    /// ```json
+    /// {
+    ///     "looping": true,
+    ///     "frame_rate": 24,
+    ///     "tracks": [
+    ///         {
+    ///             "component": "Mask",
+    ///             "field": "strength",
+    ///             "interpolation": "Smooth",
+    ///             "keyframes": [
+    ///                 { "time": 0, "value": 0.0 },
+    ///                 { "time": 24, "value": 1.0 }
+    ///             ]
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// Animation tools (e.g. Blender actions, After Effects keyframes) usually export keyframes
+    /// on frame numbers, so if `frame_rate` is given the keyframe times are read as frames
+    /// and converted to seconds. Otherwise they are seconds.
+    ///
+    /// The keyframes of each track are sorted by time, so they may be given in any order.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        #[derive(serde::Deserialize)]
+        struct Import {
+            #[serde(default)]
+            looping: bool,
+            frame_rate: Option<f32>,
+            tracks: Vec<ParamTrack>,
+        }
+
+        let import: Import = serde_json::from_str(json)?;
+
+        let mut tracks = import.tracks;
+        for track in &mut tracks {
+            if let Some(frame_rate) = import.frame_rate {
+                for keyframe in &mut track.keyframes {
+                    keyframe.time /= frame_rate;
+                }
+            }
+
+            track.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        }
+
+        Ok(Self {
+            tracks,
+            looping: import.looping,
+            elapsed: 0.0,
+        })
+    }
+}
+
+fn play_timelines(world: &mut World, mut warned: Local<HashSet<(String, String)>>) {
+    let delta = world.resource::<VfxTime>().delta_seconds();
+
+    let mut timelines = world.query::<(Entity, &mut EffectTimeline)>();
+    let timelines: Vec<(Entity, EffectTimeline)> = timelines
+        .iter_mut(world)
+        .map(|(entity, mut timeline)| {
+            timeline.elapsed += delta;
+
+            let duration = timeline.duration();
+            if timeline.looping && duration > 0.0 {
+                timeline.elapsed = timeline.elapsed.rem_euclid(duration);
+            }
+
+            (entity, timeline.clone())
+        })
+        .collect();
+
+    if timelines.is_empty() {
+        return;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    for (entity, timeline) in timelines {
+        let mut entity = world.entity_mut(entity);
+
+        for track in &timeline.tracks {
+            let Some(value) = track.sample(timeline.elapsed) else {
+                continue;
+            };
+
+            if let Err(e) = util::set_component_number(
+                &type_registry,
+                &mut entity,
+                &track.component,
+                &track.field,
+                value,
+            ) {
+                // Only warn once per parameter, instead of every frame.
+                if warned.insert((track.component.clone(), track.field.clone())) {
+                    warn!(
+                        "Can't animate `{}` of `{}`: {e}",
+                        track.field, track.component
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Plays [`EffectTimeline`]s.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Keyframe>()
+            .register_type::<Vec<Keyframe>>()
+            .register_type::<Interpolation>()
+            .register_type::<ParamTrack>()
+            .register_type::<Vec<ParamTrack>>()
+            .register_type::<EffectTimeline>()
+            .add_systems(PostUpdate, play_timelines);
+    }
+}
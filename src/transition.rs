@@ -0,0 +1,229 @@
+use bevy::{ecs::world::EntityMut, prelude::*};
+
+use crate::{
+    animation::{self, Easing},
+    state::{EffectEntry, EffectKind, EffectState},
+    time::VfxTime,
+};
+
+/// Cross-fades the effects on a camera to another set of effects, e.g. to an underwater look
+/// when the player dives.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_vfx_bag::{
+/// #     post_processing::{underwater::Underwater, wave::Wave},
+/// #     state::EffectState,
+/// #     transition::Transition,
+/// # };
+/// fn dive(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     let underwater = EffectState::new([Underwater::default().into(), Wave::default().into()]);
+///     commands.entity(camera.single()).insert(Transition::new(underwater, 1.0));
+/// }
+/// ```
+///
+/// Effects which are only in the new set are added, and fade in via their
+/// [`crate::post_processing::intensity::Intensity`].
+/// Effects which are not in the new set fade out, and are removed at the end.
+/// Effects in both sets stay, and their parameters are interpolated to the new ones.
+///
+/// The transition is advanced by the [`VfxTime`] clock, and removes itself when done,
+/// sending a [`TransitionFinished`]. Inserting another transition while one is running
+/// starts from wherever the running one got to.
+#[derive(Debug, Clone, Component)]
+pub struct Transition {
+    /// The effects to transition to.
+    pub to: EffectState,
+
+    /// How many seconds the transition takes.
+    pub duration: f32,
+
+    /// How to move from the old effects to the new ones.
+    pub easing: Easing,
+
+    /// Seconds since the transition started.
+    pub elapsed: f32,
+
+    /// How each kind of effect fades, once the transition started.
+    fades: Option<Vec<Fade>>,
+}
+
+impl Transition {
+    /// Transition to the given effects over the given seconds, easing in and out.
+    pub fn new(to: EffectState, duration: f32) -> Self {
+        Self {
+            to,
+            duration,
+            easing: Easing::default(),
+            elapsed: 0.0,
+            fades: None,
+        }
+    }
+
+    /// Transition to no effects at all over the given seconds.
+    pub fn to_none(duration: f32) -> Self {
+        Self::new(EffectState::default(), duration)
+    }
+
+    /// Use the given easing.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// The eased progress from the old effects (`0.0`) to the new ones (`1.0`).
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        self.easing.ease(self.elapsed / self.duration)
+    }
+}
+
+/// How a kind of effect fades during a [`Transition`].
+#[derive(Debug, Clone, Copy)]
+struct Fade {
+    kind: EffectKind,
+
+    /// The effect when the transition started, if it was there.
+    from: Option<EffectEntry>,
+
+    /// The effect when the transition ends, if it stays.
+    to: Option<EffectEntry>,
+
+    from_intensity: f32,
+    to_intensity: f32,
+}
+
+/// Sent when a [`Transition`] is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct TransitionFinished {
+    /// The camera which transitioned.
+    pub camera: Entity,
+}
+
+fn start_fades(entity: &mut EntityMut, to: &EffectState) -> Vec<Fade> {
+    let current = EffectState::capture(&entity.world().entity(entity.id()));
+
+    EffectKind::ALL
+        .iter()
+        .copied()
+        .filter_map(|kind| {
+            let from = current.get(kind).copied();
+            let to = to.get(kind).copied();
+
+            if from.is_none() && to.is_none() {
+                return None;
+            }
+
+            // Effects which are fading out from an earlier transition carry on from where they are.
+            let from_intensity = match from {
+                Some(_) => kind.intensity(entity).unwrap_or(1.0),
+                None => 0.0,
+            };
+
+            if let (None, Some(to)) = (from, to) {
+                to.effect.insert(entity, to.order);
+                kind.set_intensity(entity, Some(0.0));
+            }
+
+            Some(Fade {
+                kind,
+                from,
+                to,
+                from_intensity,
+                to_intensity: if to.is_some() { 1.0 } else { 0.0 },
+            })
+        })
+        .collect()
+}
+
+fn update_fade(fade: &Fade, entity: &mut EntityMut, t: f32) {
+    let intensity = fade.from_intensity + (fade.to_intensity - fade.from_intensity) * t;
+    fade.kind.set_intensity(entity, Some(intensity));
+
+    if let (Some(from), Some(to)) = (fade.from, fade.to) {
+        if from != to {
+            // Values which can't be interpolated switch halfway through.
+            let mut effect = if t < 0.5 { from.effect } else { to.effect };
+            animation::interpolate(
+                effect.as_reflect_mut(),
+                from.effect.as_reflect(),
+                to.effect.as_reflect(),
+                t,
+            );
+            effect.insert(entity, to.order);
+        }
+    }
+}
+
+fn finish_fade(fade: &Fade, entity: &mut EntityMut) {
+    match fade.to {
+        Some(to) => to.effect.insert(entity, to.order),
+        None => fade.kind.remove(entity),
+    }
+
+    fade.kind.set_intensity(entity, None);
+}
+
+fn run_transitions(world: &mut World) {
+    let delta = world.resource::<VfxTime>().delta_seconds();
+
+    let cameras: Vec<Entity> = world
+        .query_filtered::<Entity, With<Transition>>()
+        .iter(world)
+        .collect();
+
+    for camera in cameras {
+        let mut entity = world.entity_mut(camera);
+        let transition = entity
+            .get::<Transition>()
+            .expect("Transition should be there");
+
+        let fades = match &transition.fades {
+            Some(fades) => fades.clone(),
+            None => {
+                let to = transition.to.clone();
+                let fades = start_fades(&mut entity, &to);
+                entity
+                    .get_mut::<Transition>()
+                    .expect("Transition should be there")
+                    .fades = Some(fades.clone());
+                fades
+            }
+        };
+
+        let mut transition = entity
+            .get_mut::<Transition>()
+            .expect("Transition should be there");
+        transition.elapsed += delta;
+        let t = transition.progress();
+        let finished = transition.elapsed >= transition.duration;
+
+        if finished {
+            for fade in &fades {
+                finish_fade(fade, &mut entity);
+            }
+
+            entity.remove::<Transition>();
+            world.send_event(TransitionFinished { camera });
+            continue;
+        }
+
+        for fade in &fades {
+            update_fade(fade, &mut entity, t);
+        }
+    }
+}
+
+/// Runs [`Transition`]s.
+#[derive(Debug, Default)]
+pub(crate) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TransitionFinished>()
+            .add_systems(PostUpdate, run_transitions);
+    }
+}
@@ -1,42 +1,77 @@
 use bevy::{
-    core_pipeline::{core_2d, core_3d},
+    ecs::world::EntityMut,
     prelude::*,
-    render::render_graph::{self, RenderGraph},
+    reflect::{GetPath, TypeRegistryInternal},
 };
 
-pub fn add_nodes<T: FromWorld + render_graph::Node>(
-    render_app: &mut App,
-    name_2d: &str,
-    name_3d: &str,
-) {
-    {
-        let node = <T as FromWorld>::from_world(&mut render_app.world);
-        let mut binding = render_app.world.resource_mut::<RenderGraph>();
-        let graph = binding
-            .get_sub_graph_mut(core_3d::graph::NAME)
-            .expect("Graph should be available");
-
-        graph.add_node(name_3d.to_owned(), node);
+/// Writes a value into a reflected numeric field.
+/// Returns false if the field is not a number.
+pub fn set_number(field: &mut dyn Reflect, value: f32) -> bool {
+    if let Some(field) = field.downcast_mut::<f32>() {
+        *field = value;
+    } else if let Some(field) = field.downcast_mut::<f64>() {
+        *field = value as f64;
+    } else if let Some(field) = field.downcast_mut::<u32>() {
+        *field = value.round().max(0.0) as u32;
+    } else if let Some(field) = field.downcast_mut::<i32>() {
+        *field = value.round() as i32;
+    } else {
+        return false;
+    }
 
-        graph.add_node_edge(core_3d::graph::node::MAIN_OPAQUE_PASS, name_3d.to_owned());
+    true
+}
 
-        graph.add_node_edge(
-            name_3d.to_owned(),
-            core_3d::graph::node::END_MAIN_PASS_POST_PROCESSING,
-        );
+/// Writes a value into the numeric field at the reflect path (e.g. `"strength"` or `"color.r"`).
+///
+/// This is what all parameter drivers go through, so they agree on which fields are numbers.
+pub fn set_field_number(value: &mut dyn Reflect, path: &str, number: f32) -> Result<(), String> {
+    match value.reflect_path_mut(path) {
+        Ok(field) => {
+            if set_number(field, number) {
+                Ok(())
+            } else {
+                Err("the field is not a number".to_string())
+            }
+        }
+        Err(e) => Err(e.to_string()),
     }
-    {
-        let node = <T as FromWorld>::from_world(&mut render_app.world);
-        let mut binding = render_app.world.resource_mut::<RenderGraph>();
-        let graph = binding
-            .get_sub_graph_mut(core_2d::graph::NAME)
-            .expect("Graph should be available");
+}
 
-        graph.add_node(name_2d.to_owned(), node);
+/// Sets a numeric field of a reflected component on the entity.
+///
+/// The component is looked up in the type registry by its short name (e.g. `"Blur"`) or full type path.
+/// It not being on the entity is not an error, since effects may be added and removed at any time.
+pub fn set_component_number(
+    type_registry: &TypeRegistryInternal,
+    entity: &mut EntityMut,
+    component: &str,
+    field: &str,
+    value: f32,
+) -> Result<(), String> {
+    let reflect_component = type_registry
+        .get_with_short_name(component)
+        .or_else(|| type_registry.get_with_name(component))
+        .and_then(|registration| registration.data::<ReflectComponent>())
+        .ok_or_else(|| "no such reflected component".to_string())?;
+
+    let Some(mut component) = reflect_component.reflect_mut(entity) else {
+        return Ok(());
+    };
+
+    set_field_number(&mut *component, field, value)
+}
 
-        graph.add_node_edge(
-            name_2d.to_owned(),
-            core_2d::graph::node::END_MAIN_PASS_POST_PROCESSING,
-        );
+/// Reads a reflected numeric field.
+/// Returns `None` if the field is not a number.
+pub fn get_number(field: &dyn Reflect) -> Option<f32> {
+    if let Some(field) = field.downcast_ref::<f32>() {
+        Some(*field)
+    } else if let Some(field) = field.downcast_ref::<f64>() {
+        Some(*field as f32)
+    } else if let Some(field) = field.downcast_ref::<u32>() {
+        Some(*field as f32)
+    } else {
+        field.downcast_ref::<i32>().map(|field| *field as f32)
     }
 }